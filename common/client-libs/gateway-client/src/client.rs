@@ -468,6 +468,17 @@ impl<C, St> GatewayClient<C, St> {
             ServerResponse::Error { message } => {
                 return Err(GatewayClientError::GatewayError(message))
             }
+            ServerResponse::UpgradeRequired {
+                client_protocol_version,
+                minimum_protocol_version,
+                message,
+            } => {
+                return Err(GatewayClientError::UpgradeRequired {
+                    minimum: minimum_protocol_version,
+                    current: client_protocol_version,
+                    message,
+                })
+            }
             _ => return Err(GatewayClientError::UnexpectedResponse),
         };
 
@@ -534,6 +545,15 @@ impl<C, St> GatewayClient<C, St> {
                 Ok(())
             }
             ServerResponse::Error { message } => Err(GatewayClientError::GatewayError(message)),
+            ServerResponse::UpgradeRequired {
+                client_protocol_version,
+                minimum_protocol_version,
+                message,
+            } => Err(GatewayClientError::UpgradeRequired {
+                minimum: minimum_protocol_version,
+                current: client_protocol_version,
+                message,
+            }),
             _ => Err(GatewayClientError::UnexpectedResponse),
         }
     }
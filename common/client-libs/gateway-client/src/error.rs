@@ -83,6 +83,13 @@ pub enum GatewayClientError {
     #[error("Attempted to negotiate connection with gateway using incompatible protocol version. Ours is {current} and the gateway reports {gateway:?}")]
     IncompatibleProtocol { gateway: Option<u8>, current: u8 },
 
+    #[error("The gateway requires clients to use at least protocol version {minimum}, but we're using {current:?}. Please upgrade your client: {message}")]
+    UpgradeRequired {
+        minimum: u8,
+        current: Option<u8>,
+        message: String,
+    },
+
     #[error(
         "The packet router hasn't been set - are you sure you started up the client correctly?"
     )]
@@ -14,21 +14,30 @@ use nym_mixnet_contract_common::{
     families::{Family, FamilyHead},
     mixnode::{
         MixnodeRewardingDetailsResponse, PagedMixnodesDetailsResponse,
-        PagedUnbondedMixnodesResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
+        PagedMixnodesDetailsWithStatusResponse, PagedSkimmedMixnodesResponse,
+        PagedUnbondedMixnodesResponse, SkimmedMixNode, StakeSaturationResponse,
+        UnbondedMixnodeResponse,
     },
     reward_params::{Performance, RewardingParams},
-    rewarding::{EstimatedCurrentEpochRewardResponse, PendingRewardResponse},
-    ContractBuildInformation, ContractState, ContractStateParams, CurrentIntervalResponse,
-    Delegation, EpochEventId, EpochStatus, FamilyByHeadResponse, FamilyByLabelResponse,
-    FamilyMembersByHeadResponse, FamilyMembersByLabelResponse, GatewayBond, GatewayBondResponse,
-    GatewayOwnershipResponse, IdentityKey, IdentityKeyRef, IntervalEventId, LayerDistribution,
-    MixId, MixNodeBond, MixNodeDetails, MixOwnershipResponse, MixnodeDetailsByIdentityResponse,
-    MixnodeDetailsResponse, NumberOfPendingEventsResponse, PagedAllDelegationsResponse,
-    PagedDelegatorDelegationsResponse, PagedFamiliesResponse, PagedGatewayResponse,
-    PagedMembersResponse, PagedMixNodeDelegationsResponse, PagedMixnodeBondsResponse,
-    PagedRewardedSetResponse, PendingEpochEvent, PendingEpochEventResponse,
+    rewarding::{
+        EpochRewardingSimulationParams, EstimatedCurrentEpochRewardResponse, PendingRewardResponse,
+        SimulatedEpochRewardingResponse, StakeSummaryResponse,
+    },
+    ActiveSetMembershipResponse, ContractBuildInformation, ContractState, ContractStateParams,
+    CurrentIntervalResponse, Delegation, EpochActiveSetResponse, EpochEventId, EpochId,
+    EpochStatus, FamilyByHeadResponse, FamilyByLabelResponse, FamilyMembersByHeadResponse,
+    FamilyMembersByLabelResponse, GatewayBond, GatewayBondResponse, GatewayOwnershipResponse,
+    IdentityKey, IdentityKeyRef, IntervalEventId, LayerDistribution, MinSupportedVersionResponse,
+    MixId, MixNodeBond, MixNodeDetails, MixOwnershipResponse, MixnodeDescriptionResponse,
+    MixnodeDetailsByIdentityResponse, MixnodeDetailsResponse,
+    MixnodeDetailsWithPerformanceResponse, MixnodePledgeBreakdownResponse,
+    NumberOfPendingEventsResponse, PagedAllDelegationsResponse, PagedDelegatorDelegationsResponse,
+    PagedFamiliesResponse, PagedGatewayResponse, PagedMembersResponse,
+    PagedMixNodeDelegationsResponse, PagedMixnodeBondsResponse, PagedRewardedSetResponse,
+    PendingDelegationEvent, PendingEpochEvent, PendingEpochEventResponse,
     PendingEpochEventsResponse, PendingIntervalEvent, PendingIntervalEventResponse,
-    PendingIntervalEventsResponse, QueryMsg as MixnetQueryMsg, RewardedSetNodeStatus,
+    PendingIntervalEventsResponse, PendingMixNodeDelegationEventsResponse,
+    PendingOwnershipTransferResponse, QueryMsg as MixnetQueryMsg, RewardedSetNodeStatus,
     UnbondedMixnode,
 };
 use serde::Deserialize;
@@ -67,6 +76,13 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_min_supported_mixnode_version(
+        &self,
+    ) -> Result<MinSupportedVersionResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetMinSupportedMixnodeVersion {})
+            .await
+    }
+
     async fn get_mixnet_contract_state(&self) -> Result<ContractState, NyxdError> {
         self.query_mixnet_contract(MixnetQueryMsg::GetState {})
             .await
@@ -77,6 +93,11 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_stake_summary(&self) -> Result<StakeSummaryResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetStakeSummary {})
+            .await
+    }
+
     async fn get_current_epoch_status(&self) -> Result<EpochStatus, NyxdError> {
         self.query_mixnet_contract(MixnetQueryMsg::GetEpochStatus {})
             .await
@@ -96,6 +117,23 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_epoch_active_set(
+        &self,
+        epoch_id: EpochId,
+    ) -> Result<EpochActiveSetResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetEpochActiveSet { epoch_id })
+            .await
+    }
+
+    async fn get_active_set_membership(
+        &self,
+        mix_id: MixId,
+        epoch_id: EpochId,
+    ) -> Result<ActiveSetMembershipResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetActiveSetMembership { mix_id, epoch_id })
+            .await
+    }
+
     async fn get_all_node_families_paged(
         &self,
         start_after: Option<String>,
@@ -143,6 +181,15 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_skimmed_mixnodes_paged(
+        &self,
+        start_after: Option<MixId>,
+        limit: Option<u32>,
+    ) -> Result<PagedSkimmedMixnodesResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetSkimmedMixNodes { limit, start_after })
+            .await
+    }
+
     async fn get_mixnodes_detailed_paged(
         &self,
         start_after: Option<MixId>,
@@ -152,6 +199,18 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_mixnodes_detailed_with_status_paged(
+        &self,
+        start_after: Option<MixId>,
+        limit: Option<u32>,
+    ) -> Result<PagedMixnodesDetailsWithStatusResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetMixNodesDetailedWithStatus {
+            limit,
+            start_after,
+        })
+        .await
+    }
+
     async fn get_unbonded_paged(
         &self,
         start_after: Option<MixId>,
@@ -207,6 +266,30 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_mixnode_details_with_performance(
+        &self,
+        mix_id: MixId,
+    ) -> Result<MixnodeDetailsWithPerformanceResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetMixnodeDetailsWithPerformance { mix_id })
+            .await
+    }
+
+    async fn get_mixnode_description(
+        &self,
+        mix_id: MixId,
+    ) -> Result<MixnodeDescriptionResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetMixnodeDescription { mix_id })
+            .await
+    }
+
+    async fn get_pending_ownership_transfer(
+        &self,
+        mix_id: MixId,
+    ) -> Result<PendingOwnershipTransferResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetPendingOwnershipTransfer { mix_id })
+            .await
+    }
+
     async fn get_mixnode_details_by_identity(
         &self,
         mix_identity: IdentityKey,
@@ -233,6 +316,14 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_mixnode_pledge_breakdown(
+        &self,
+        mix_id: MixId,
+    ) -> Result<MixnodePledgeBreakdownResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetMixnodePledgeBreakdown { mix_id })
+            .await
+    }
+
     async fn get_unbonded_mixnode_information(
         &self,
         mix_id: MixId,
@@ -397,6 +488,20 @@ pub trait MixnetQueryClient {
         .await
     }
 
+    async fn simulate_epoch_rewarding_paged(
+        &self,
+        params: EpochRewardingSimulationParams,
+        start_after: Option<MixId>,
+        limit: Option<u32>,
+    ) -> Result<SimulatedEpochRewardingResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::SimulateEpochRewarding {
+            params,
+            limit,
+            start_after,
+        })
+        .await
+    }
+
     // interval-related
 
     async fn get_pending_epoch_events_paged(
@@ -408,6 +513,20 @@ pub trait MixnetQueryClient {
             .await
     }
 
+    async fn get_pending_mixnode_delegation_events_paged(
+        &self,
+        mix_id: MixId,
+        start_after: Option<EpochEventId>,
+        limit: Option<u32>,
+    ) -> Result<PendingMixNodeDelegationEventsResponse, NyxdError> {
+        self.query_mixnet_contract(MixnetQueryMsg::GetPendingMixNodeDelegationEvents {
+            mix_id,
+            limit,
+            start_after,
+        })
+        .await
+    }
+
     async fn get_pending_interval_events_paged(
         &self,
         start_after: Option<IntervalEventId>,
@@ -487,6 +606,10 @@ pub trait PagedMixnetQueryClient: MixnetQueryClient {
         collect_paged!(self, get_mixnode_bonds_paged, nodes)
     }
 
+    async fn get_all_skimmed_mixnodes(&self) -> Result<Vec<SkimmedMixNode>, NyxdError> {
+        collect_paged!(self, get_skimmed_mixnodes_paged, nodes)
+    }
+
     async fn get_all_mixnodes_detailed(&self) -> Result<Vec<MixNodeDetails>, NyxdError> {
         collect_paged!(self, get_mixnodes_detailed_paged, nodes)
     }
@@ -540,6 +663,18 @@ pub trait PagedMixnetQueryClient: MixnetQueryClient {
         collect_paged!(self, get_pending_epoch_events_paged, events)
     }
 
+    async fn get_all_pending_mixnode_delegation_events(
+        &self,
+        mix_id: MixId,
+    ) -> Result<Vec<PendingDelegationEvent>, NyxdError> {
+        collect_paged!(
+            self,
+            get_pending_mixnode_delegation_events_paged,
+            events,
+            mix_id
+        )
+    }
+
     async fn get_all_pending_interval_events(
         &self,
     ) -> Result<Vec<PendingIntervalEvent>, NyxdError> {
@@ -607,7 +742,11 @@ mod tests {
             }
             MixnetQueryMsg::GetStateParams {} => client.get_mixnet_contract_state_params().ignore(),
             MixnetQueryMsg::GetState {} => client.get_mixnet_contract_state().ignore(),
+            MixnetQueryMsg::GetMinSupportedMixnodeVersion {} => {
+                client.get_min_supported_mixnode_version().ignore()
+            }
             MixnetQueryMsg::GetRewardingParams {} => client.get_rewarding_parameters().ignore(),
+            MixnetQueryMsg::GetStakeSummary {} => client.get_stake_summary().ignore(),
             MixnetQueryMsg::GetEpochStatus {} => client.get_current_epoch_status().ignore(),
             MixnetQueryMsg::GetCurrentIntervalDetails {} => {
                 client.get_current_interval_details().ignore()
@@ -615,12 +754,24 @@ mod tests {
             MixnetQueryMsg::GetRewardedSet { limit, start_after } => {
                 client.get_rewarded_set_paged(start_after, limit).ignore()
             }
+            MixnetQueryMsg::GetEpochActiveSet { epoch_id } => {
+                client.get_epoch_active_set(epoch_id).ignore()
+            }
+            MixnetQueryMsg::GetActiveSetMembership { mix_id, epoch_id } => {
+                client.get_active_set_membership(mix_id, epoch_id).ignore()
+            }
             MixnetQueryMsg::GetMixNodeBonds { limit, start_after } => {
                 client.get_mixnode_bonds_paged(start_after, limit).ignore()
             }
+            MixnetQueryMsg::GetSkimmedMixNodes { limit, start_after } => client
+                .get_skimmed_mixnodes_paged(start_after, limit)
+                .ignore(),
             MixnetQueryMsg::GetMixNodesDetailed { limit, start_after } => client
                 .get_mixnodes_detailed_paged(start_after, limit)
                 .ignore(),
+            MixnetQueryMsg::GetMixNodesDetailedWithStatus { limit, start_after } => client
+                .get_mixnodes_detailed_with_status_paged(start_after, limit)
+                .ignore(),
             MixnetQueryMsg::GetUnbondedMixNodes { limit, start_after } => {
                 client.get_unbonded_paged(start_after, limit).ignore()
             }
@@ -644,12 +795,24 @@ mod tests {
             MixnetQueryMsg::GetMixnodeDetails { mix_id } => {
                 client.get_mixnode_details(mix_id).ignore()
             }
+            MixnetQueryMsg::GetMixnodeDetailsWithPerformance { mix_id } => {
+                client.get_mixnode_details_with_performance(mix_id).ignore()
+            }
+            MixnetQueryMsg::GetMixnodeDescription { mix_id } => {
+                client.get_mixnode_description(mix_id).ignore()
+            }
+            MixnetQueryMsg::GetPendingOwnershipTransfer { mix_id } => {
+                client.get_pending_ownership_transfer(mix_id).ignore()
+            }
             MixnetQueryMsg::GetMixnodeRewardingDetails { mix_id } => {
                 client.get_mixnode_rewarding_details(mix_id).ignore()
             }
             MixnetQueryMsg::GetStakeSaturation { mix_id } => {
                 client.get_mixnode_stake_saturation(mix_id).ignore()
             }
+            MixnetQueryMsg::GetMixnodePledgeBreakdown { mix_id } => {
+                client.get_mixnode_pledge_breakdown(mix_id).ignore()
+            }
             MixnetQueryMsg::GetUnbondedMixNodeInformation { mix_id } => {
                 client.get_unbonded_mixnode_information(mix_id).ignore()
             }
@@ -722,9 +885,23 @@ mod tests {
                     estimated_performance,
                 )
                 .ignore(),
+            MixnetQueryMsg::SimulateEpochRewarding {
+                params,
+                limit,
+                start_after,
+            } => client
+                .simulate_epoch_rewarding_paged(params, start_after, limit)
+                .ignore(),
             MixnetQueryMsg::GetPendingEpochEvents { limit, start_after } => client
                 .get_pending_epoch_events_paged(start_after, limit)
                 .ignore(),
+            MixnetQueryMsg::GetPendingMixNodeDelegationEvents {
+                mix_id,
+                limit,
+                start_after,
+            } => client
+                .get_pending_mixnode_delegation_events_paged(mix_id, start_after, limit)
+                .ignore(),
             MixnetQueryMsg::GetPendingIntervalEvents { limit, start_after } => client
                 .get_pending_interval_events_paged(start_after, limit)
                 .ignore(),
@@ -12,11 +12,13 @@ use cosmrs::AccountId;
 use nym_contracts_common::signing::MessageSignature;
 use nym_mixnet_contract_common::families::FamilyHead;
 use nym_mixnet_contract_common::gateway::GatewayConfigUpdate;
-use nym_mixnet_contract_common::mixnode::{MixNodeConfigUpdate, MixNodeCostParams};
+use nym_mixnet_contract_common::mixnode::{
+    MixNodeConfigUpdate, MixNodeCostParams, MixNodeDescription,
+};
 use nym_mixnet_contract_common::reward_params::{IntervalRewardingParamsUpdate, Performance};
 use nym_mixnet_contract_common::{
-    ContractStateParams, ExecuteMsg as MixnetExecuteMsg, Gateway, Layer, LayerAssignment, MixId,
-    MixNode,
+    ActiveSetProvenance, ContractStateParams, EpochId, ExecuteMsg as MixnetExecuteMsg, Gateway,
+    Layer, LayerAssignment, MixId, MixNode, SphinxKey,
 };
 
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
@@ -122,12 +124,29 @@ pub trait MixnetSigningClient {
         new_rewarded_set: Vec<LayerAssignment>,
         expected_active_set_size: u32,
         fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.advance_current_epoch_with_provenance(
+            new_rewarded_set,
+            expected_active_set_size,
+            None,
+            fee,
+        )
+        .await
+    }
+
+    async fn advance_current_epoch_with_provenance(
+        &self,
+        new_rewarded_set: Vec<LayerAssignment>,
+        expected_active_set_size: u32,
+        selection_provenance: Option<ActiveSetProvenance>,
+        fee: Option<Fee>,
     ) -> Result<ExecuteResult, NyxdError> {
         self.execute_mixnet_contract(
             fee,
             MixnetExecuteMsg::AdvanceCurrentEpoch {
                 new_rewarded_set,
                 expected_active_set_size,
+                selection_provenance,
             },
             vec![],
         )
@@ -460,6 +479,76 @@ pub trait MixnetSigningClient {
         .await
     }
 
+    async fn update_mixnode_description(
+        &self,
+        description: MixNodeDescription,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::UpdateMixnodeDescription { description },
+            vec![],
+        )
+        .await
+    }
+
+    async fn transfer_mixnode_ownership(
+        &self,
+        new_owner: AccountId,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::TransferMixNodeOwnership {
+                new_owner: new_owner.to_string(),
+            },
+            vec![],
+        )
+        .await
+    }
+
+    async fn cancel_mixnode_ownership_transfer(
+        &self,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::CancelMixNodeOwnershipTransfer {},
+            vec![],
+        )
+        .await
+    }
+
+    async fn accept_mixnode_ownership(
+        &self,
+        mix_id: MixId,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::AcceptMixNodeOwnership { mix_id },
+            vec![],
+        )
+        .await
+    }
+
+    async fn rotate_mixnode_sphinx_key(
+        &self,
+        next_sphinx_key: SphinxKey,
+        activation_epoch: EpochId,
+        fee: Option<Fee>,
+    ) -> Result<ExecuteResult, NyxdError> {
+        self.execute_mixnet_contract(
+            fee,
+            MixnetExecuteMsg::RotateMixnodeSphinxKey {
+                next_sphinx_key,
+                activation_epoch,
+            },
+            vec![],
+        )
+        .await
+    }
+
     // gateway-related:
 
     async fn bond_gateway(
@@ -817,8 +906,14 @@ mod tests {
             MixnetExecuteMsg::AdvanceCurrentEpoch {
                 new_rewarded_set,
                 expected_active_set_size,
+                selection_provenance,
             } => client
-                .advance_current_epoch(new_rewarded_set, expected_active_set_size, None)
+                .advance_current_epoch_with_provenance(
+                    new_rewarded_set,
+                    expected_active_set_size,
+                    selection_provenance,
+                    None,
+                )
                 .ignore(),
             MixnetExecuteMsg::ReconcileEpochEvents { limit } => {
                 client.reconcile_epoch_events(limit, None).ignore()
@@ -871,6 +966,24 @@ mod tests {
             MixnetExecuteMsg::UpdateMixnodeConfigOnBehalf { new_config, owner } => client
                 .update_mixnode_config_on_behalf(owner.parse().unwrap(), new_config, None)
                 .ignore(),
+            MixnetExecuteMsg::UpdateMixnodeDescription { description } => client
+                .update_mixnode_description(description, None)
+                .ignore(),
+            MixnetExecuteMsg::TransferMixNodeOwnership { new_owner } => client
+                .transfer_mixnode_ownership(new_owner.parse().unwrap(), None)
+                .ignore(),
+            MixnetExecuteMsg::CancelMixNodeOwnershipTransfer {} => {
+                client.cancel_mixnode_ownership_transfer(None).ignore()
+            }
+            MixnetExecuteMsg::AcceptMixNodeOwnership { mix_id } => {
+                client.accept_mixnode_ownership(mix_id, None).ignore()
+            }
+            MixnetExecuteMsg::RotateMixnodeSphinxKey {
+                next_sphinx_key,
+                activation_epoch,
+            } => client
+                .rotate_mixnode_sphinx_key(next_sphinx_key, activation_epoch, None)
+                .ignore(),
             MixnetExecuteMsg::BondGateway {
                 gateway,
                 owner_signature,
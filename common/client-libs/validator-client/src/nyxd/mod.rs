@@ -25,7 +25,7 @@ use std::fmt::Debug;
 use std::time::SystemTime;
 use tendermint_rpc::endpoint::block::Response as BlockResponse;
 use tendermint_rpc::endpoint::*;
-use tendermint_rpc::{Error as TendermintRpcError, Order};
+use tendermint_rpc::Error as TendermintRpcError;
 use url::Url;
 
 pub use crate::nyxd::{
@@ -61,7 +61,7 @@ pub use fee::{gas_price::GasPrice, GasAdjustable, GasAdjustment};
 pub use tendermint_rpc::{
     endpoint::{tx::Response as TxResponse, validators::Response as ValidatorResponse},
     query::Query,
-    Paging, Request, Response, SimpleRequest,
+    Order, Paging, Request, Response, SimpleRequest,
 };
 
 #[cfg(feature = "http-client")]
@@ -81,6 +81,7 @@ pub async fn bond_mixnode(args: Args, client: SigningClient) {
         sphinx_key: args.sphinx_key,
         identity_key: args.identity_key,
         version: args.version,
+        next_sphinx_key: None,
     };
 
     let coin = Coin::new(args.amount, denom);
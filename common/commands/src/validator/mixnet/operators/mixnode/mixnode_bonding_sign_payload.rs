@@ -73,6 +73,7 @@ pub async fn create_payload(args: Args, client: SigningClient) {
         sphinx_key: args.sphinx_key,
         identity_key: args.identity_key,
         version: args.version,
+        next_sphinx_key: None,
     };
 
     let coin = Coin::new(args.amount, denom);
@@ -80,6 +80,7 @@ pub async fn vesting_bond_mixnode(client: SigningClient, args: Args, denom: &str
         sphinx_key: args.sphinx_key,
         identity_key: args.identity_key,
         version: args.version,
+        next_sphinx_key: None,
     };
 
     let coin = Coin::new(args.amount, denom);
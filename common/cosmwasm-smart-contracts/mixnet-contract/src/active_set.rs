@@ -0,0 +1,95 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::{EpochId, MixId};
+use cosmwasm_schema::cw_serde;
+
+/// Source of the randomness used by the rewarding validator when selecting the active set,
+/// so that anyone could independently verify the selection has been performed deterministically.
+#[cw_serde]
+pub struct ActiveSetProvenance {
+    /// Height of the block whose hash has been used for seeding the selection.
+    pub block_height: u64,
+
+    /// Hex-encoded hash of the block at `block_height`.
+    pub block_hash: String,
+
+    /// Hex-encoded VRF output used as the selection seed, if the selection has been performed using a VRF.
+    #[serde(default)]
+    pub vrf_seed: Option<String>,
+}
+
+/// Active set selected for the particular epoch.
+#[cw_serde]
+pub struct EpochActiveSet {
+    /// The absolute id of the epoch the set has been selected for.
+    pub epoch_id: EpochId,
+
+    /// Ids of all the nodes selected into the active set, sorted in ascending order.
+    pub active: Vec<MixId>,
+
+    /// Provenance of the selection, if it has been provided by the rewarding validator.
+    pub provenance: Option<ActiveSetProvenance>,
+}
+
+impl EpochActiveSet {
+    pub fn new(
+        epoch_id: EpochId,
+        mut active: Vec<MixId>,
+        provenance: Option<ActiveSetProvenance>,
+    ) -> Self {
+        active.sort_unstable();
+        active.dedup();
+
+        EpochActiveSet {
+            epoch_id,
+            active,
+            provenance,
+        }
+    }
+
+    /// Checks whether the provided node has been part of this active set.
+    pub fn contains(&self, mix_id: MixId) -> bool {
+        self.active.binary_search(&mix_id).is_ok()
+    }
+}
+
+/// Response containing the active set selected for the particular epoch.
+#[cw_serde]
+pub struct EpochActiveSetResponse {
+    /// The absolute id of the epoch.
+    pub epoch_id: EpochId,
+
+    /// The active set of the epoch, if it's still retained by the contract.
+    pub active_set: Option<EpochActiveSet>,
+}
+
+/// Response indicating whether the particular node has been part of the active set in the given epoch.
+#[cw_serde]
+pub struct ActiveSetMembershipResponse {
+    /// Id of the node in question.
+    pub mix_id: MixId,
+
+    /// The absolute id of the epoch.
+    pub epoch_id: EpochId,
+
+    /// Whether the node has been in the active set of the epoch.
+    /// `None` if the contract does not (or no longer) hold the information about that epoch.
+    pub in_active_set: Option<bool>,
+
+    /// Provenance of the active set selection, if known.
+    pub provenance: Option<ActiveSetProvenance>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn active_set_is_normalised() {
+        let set = EpochActiveSet::new(42, vec![5, 1, 3, 5, 2], None);
+        assert_eq!(set.active, vec![1, 2, 3, 5]);
+        assert!(set.contains(3));
+        assert!(!set.contains(4));
+    }
+}
@@ -1,7 +1,7 @@
 // Copyright 2022-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use crate::{EpochEventId, EpochState, IdentityKey, MixId};
+use crate::{EpochEventId, EpochId, EpochState, IdentityKey, MixId, SphinxKey};
 use contracts_common::signing::verifier::ApiVerifierError;
 use cosmwasm_std::{Addr, Coin, Decimal, Uint128};
 use thiserror::Error;
@@ -174,6 +174,12 @@ pub enum MixnetContractError {
     #[error("Invalid layer expected 1, 2 or 3, got {0}")]
     InvalidLayer(u8),
 
+    #[error("mix layer {layer} is outside of the {layers}-layer topology")]
+    InvalidMixLayer { layer: u8, layers: u8 },
+
+    #[error("could not parse '{event_type}' event: {reason}")]
+    MalformedEvent { event_type: String, reason: String },
+
     #[error("Head already has a family")]
     FamilyCanHaveOnlyOne,
 
@@ -240,6 +246,57 @@ pub enum MixnetContractError {
         error_message: String,
     },
 
+    #[error("the provided mixnode description field '{field}' is too long. it has {length} bytes while the maximum is {max_length}")]
+    DescriptionFieldTooLong {
+        field: String,
+        length: usize,
+        max_length: usize,
+    },
+
+    #[error("mixnode {mix_id} has been bonded with vesting tokens and its ownership can't be transferred")]
+    VestingMixnodeOwnershipTransfer { mix_id: MixId },
+
+    #[error("there is no pending ownership transfer of mixnode {mix_id}")]
+    NoPendingOwnershipTransfer { mix_id: MixId },
+
+    #[error(
+        "the ownership of mixnode {mix_id} has been proposed to {proposed_owner} and not {sender}"
+    )]
+    InvalidOwnershipTransferRecipient {
+        mix_id: MixId,
+        proposed_owner: Addr,
+        sender: Addr,
+    },
+
+    #[error("the mixnode ownership can't be transferred to its current owner")]
+    OwnershipTransferToSelf,
+
+    #[error("the next sphinx key can't be announced during mixnode bonding")]
+    UnexpectedNextSphinxKey,
+
+    #[error("the sphinx key rotation has to be scheduled for a future epoch. the current epoch is {current_epoch} and the requested activation epoch is {activation_epoch}")]
+    SphinxKeyRotationNotInFuture {
+        current_epoch: EpochId,
+        activation_epoch: EpochId,
+    },
+
+    #[error("the sphinx key '{sphinx_key}' is already in use")]
+    SphinxKeyAlreadyInUse { sphinx_key: SphinxKey },
+
+    #[error("mixnode version {version} is no longer supported. the minimum supported version is {min_supported_version}")]
+    UnsupportedMixnodeVersion {
+        version: String,
+        min_supported_version: String,
+    },
+
+    #[error("delegating {attempted} to mixnode {mix_id} would exceed its delegation cap of {cap}. it has already received {current_delegation}")]
+    DelegationCapExceeded {
+        mix_id: MixId,
+        cap: Decimal,
+        current_delegation: Decimal,
+        attempted: Uint128,
+    },
+
     #[error("failed to verify message signature: {source}")]
     SignatureVerificationFailure {
         #[from]
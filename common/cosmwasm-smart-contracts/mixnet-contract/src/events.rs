@@ -1,14 +1,18 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::MixnetContractError;
 use crate::gateway::GatewayConfigUpdate;
-use crate::mixnode::{MixNodeConfigUpdate, MixNodeCostParams};
+use crate::mixnode::{MixNodeConfigUpdate, MixNodeCostParams, MixNodeDescription, NextSphinxKey};
 use crate::reward_params::{IntervalRewardParams, IntervalRewardingParamsUpdate};
 use crate::rewarding::RewardDistribution;
-use crate::{BlockHeight, ContractStateParams, IdentityKeyRef, Interval, Layer, MixId};
+use crate::{
+    BlockHeight, ContractStateParams, EpochId, IdentityKey, IdentityKeyRef, Interval, Layer, MixId,
+};
 pub use contracts_common::events::*;
-use cosmwasm_std::{Addr, Coin, Decimal, Event};
+use cosmwasm_std::{Addr, Coin, Decimal, Event, Uint128};
 use std::fmt::Display;
+use std::str::FromStr;
 
 pub const EVENT_VERSION_PREFIX: &str = "v2_";
 
@@ -23,6 +27,11 @@ pub enum MixnetEventType {
     PendingMixnodeUnbonding,
     MixnodeUnbonding,
     MixnodeConfigUpdate,
+    MixnodeDescriptionUpdate,
+    MixnodeOwnershipTransferProposal,
+    MixnodeOwnershipTransferCancellation,
+    MixnodeOwnershipTransfer,
+    MixnodeSphinxKeyRotationAnnouncement,
     PendingMixnodeCostParamsUpdate,
     MixnodeCostParamsUpdate,
     MixnodeRewarding,
@@ -36,6 +45,7 @@ pub enum MixnetEventType {
     PendingUndelegation,
     Delegation,
     DelegationOnUnbonding,
+    DelegationCapExceeded,
     Undelegation,
     ContractSettingsUpdate,
     RewardingValidatorUpdate,
@@ -67,6 +77,17 @@ impl Display for MixnetEventType {
             MixnetEventType::GatewayUnbonding => "gateway_unbonding",
             MixnetEventType::PendingMixnodeUnbonding => "pending_mixnode_unbonding",
             MixnetEventType::MixnodeConfigUpdate => "mixnode_config_update",
+            MixnetEventType::MixnodeDescriptionUpdate => "mixnode_description_update",
+            MixnetEventType::MixnodeOwnershipTransferProposal => {
+                "mixnode_ownership_transfer_proposal"
+            }
+            MixnetEventType::MixnodeOwnershipTransferCancellation => {
+                "mixnode_ownership_transfer_cancellation"
+            }
+            MixnetEventType::MixnodeOwnershipTransfer => "mixnode_ownership_transfer",
+            MixnetEventType::MixnodeSphinxKeyRotationAnnouncement => {
+                "mixnode_sphinx_key_rotation_announcement"
+            }
             MixnetEventType::MixnodeUnbonding => "mixnode_unbonding",
             MixnetEventType::PendingMixnodeCostParamsUpdate => "pending_mixnode_cost_params_update",
             MixnetEventType::MixnodeCostParamsUpdate => "mixnode_cost_params_update",
@@ -93,6 +114,7 @@ impl Display for MixnetEventType {
             MixnetEventType::PendingIntervalConfigUpdate => "pending_interval_config_update",
             MixnetEventType::IntervalConfigUpdate => "interval_config_update",
             MixnetEventType::DelegationOnUnbonding => "delegation_on_unbonding_node",
+            MixnetEventType::DelegationCapExceeded => "delegation_cap_exceeded",
             MixnetEventType::GatewayConfigUpdate => "gateway_config_update",
         };
 
@@ -126,10 +148,23 @@ pub const NEW_MINIMUM_MIXNODE_PLEDGE_KEY: &str = "new_minimum_mixnode_pledge";
 pub const NEW_MINIMUM_GATEWAY_PLEDGE_KEY: &str = "new_minimum_gateway_pledge";
 pub const NEW_MINIMUM_DELEGATION_KEY: &str = "new_minimum_delegation";
 
+pub const OLD_MAX_DELEGATION_TO_PLEDGE_RATIO_KEY: &str = "old_max_delegation_to_pledge_ratio";
+pub const NEW_MAX_DELEGATION_TO_PLEDGE_RATIO_KEY: &str = "new_max_delegation_to_pledge_ratio";
+
+pub const OLD_MIN_SUPPORTED_VERSION_KEY: &str = "old_min_supported_version";
+pub const NEW_MIN_SUPPORTED_VERSION_KEY: &str = "new_min_supported_version";
+
 pub const OLD_REWARDING_VALIDATOR_ADDRESS_KEY: &str = "old_rewarding_validator_address";
 pub const NEW_REWARDING_VALIDATOR_ADDRESS_KEY: &str = "new_rewarding_validator_address";
 
 pub const UPDATED_MIXNODE_CONFIG_KEY: &str = "updated_mixnode_config";
+pub const UPDATED_MIXNODE_DESCRIPTION_KEY: &str = "updated_mixnode_description";
+
+// ownership transfer
+pub const PREVIOUS_OWNER_KEY: &str = "previous_owner";
+pub const NEW_OWNER_KEY: &str = "new_owner";
+pub const NEXT_SPHINX_KEY_KEY: &str = "next_sphinx_key";
+pub const ACTIVATION_EPOCH_KEY: &str = "activation_epoch";
 pub const UPDATED_GATEWAY_CONFIG_KEY: &str = "updated_gateway_config";
 pub const UPDATED_MIXNODE_COST_PARAMS_KEY: &str = "updated_mixnode_cost_params";
 
@@ -160,6 +195,195 @@ pub const REWARDED_SET_NODES_KEY: &str = "rewarded_set_nodes";
 pub const NEW_EPOCHS_DURATION_SECS_KEY: &str = "new_epoch_durations_secs";
 pub const NEW_EPOCHS_IN_INTERVAL: &str = "new_epochs_in_interval";
 
+/// Typed representation of the events emitted throughout the bonding lifecycle.
+/// Both the emitted attribute keys and their formatting are stable, so that indexers could
+/// reconstruct the events with [`MixnetEvent::try_from_event`] rather than parsing the attributes themselves.
+#[derive(Debug, Clone, PartialEq)]
+pub enum MixnetEvent {
+    /// A new mixnode has been bonded.
+    Bonded {
+        mix_id: MixId,
+        identity: IdentityKey,
+        owner: Addr,
+        proxy: Option<Addr>,
+        amount: Coin,
+        assigned_layer: Layer,
+    },
+
+    /// A mixnode has been unbonded at the end of an epoch.
+    Unbonded {
+        created_at: BlockHeight,
+        mix_id: MixId,
+    },
+
+    /// A pending delegation has been applied at the end of an epoch.
+    DelegationAdded {
+        created_at: BlockHeight,
+        delegator: Addr,
+        proxy: Option<Addr>,
+        amount: Coin,
+        mix_id: MixId,
+        unit_reward: Decimal,
+    },
+
+    /// A mixnode has been rewarded for its work in the specified epoch.
+    RewardDistributed {
+        epoch: EpochId,
+        mix_id: MixId,
+        prior_delegates: Decimal,
+        prior_unit_reward: Decimal,
+        distribution: RewardDistribution,
+    },
+}
+
+impl MixnetEvent {
+    pub fn event_type(&self) -> MixnetEventType {
+        match self {
+            MixnetEvent::Bonded { .. } => MixnetEventType::MixnodeBonding,
+            MixnetEvent::Unbonded { .. } => MixnetEventType::MixnodeUnbonding,
+            MixnetEvent::DelegationAdded { .. } => MixnetEventType::Delegation,
+            MixnetEvent::RewardDistributed { .. } => MixnetEventType::MixnodeRewarding,
+        }
+    }
+
+    /// Attempts to recover the typed event out of the emitted cosmos event.
+    pub fn try_from_event(event: &Event) -> Result<Self, MixnetContractError> {
+        let parser = EventParser { event };
+
+        if event.ty == MixnetEventType::MixnodeBonding.to_string() {
+            Ok(MixnetEvent::Bonded {
+                mix_id: parser.parse(MIX_ID_KEY)?,
+                identity: parser.attribute(NODE_IDENTITY_KEY)?,
+                owner: Addr::unchecked(parser.attribute(OWNER_KEY)?),
+                proxy: may_find_attribute(event, PROXY_KEY).map(Addr::unchecked),
+                amount: parser.coin(AMOUNT_KEY)?,
+                assigned_layer: parser.layer(ASSIGNED_LAYER_KEY)?,
+            })
+        } else if event.ty == MixnetEventType::MixnodeUnbonding.to_string() {
+            Ok(MixnetEvent::Unbonded {
+                created_at: parser.parse(EVENT_CREATION_HEIGHT_KEY)?,
+                mix_id: parser.parse(MIX_ID_KEY)?,
+            })
+        } else if event.ty == MixnetEventType::Delegation.to_string() {
+            Ok(MixnetEvent::DelegationAdded {
+                created_at: parser.parse(EVENT_CREATION_HEIGHT_KEY)?,
+                delegator: Addr::unchecked(parser.attribute(DELEGATOR_KEY)?),
+                proxy: may_find_attribute(event, PROXY_KEY).map(Addr::unchecked),
+                amount: parser.coin(AMOUNT_KEY)?,
+                mix_id: parser.parse(DELEGATION_TARGET_KEY)?,
+                unit_reward: parser.parse(UNIT_REWARD_KEY)?,
+            })
+        } else if event.ty == MixnetEventType::MixnodeRewarding.to_string() {
+            Ok(MixnetEvent::RewardDistributed {
+                epoch: parser.parse(INTERVAL_KEY)?,
+                mix_id: parser.parse(MIX_ID_KEY)?,
+                prior_delegates: parser.parse(PRIOR_DELEGATES_KEY)?,
+                prior_unit_reward: parser.parse(PRIOR_UNIT_REWARD_KEY)?,
+                distribution: RewardDistribution {
+                    operator: parser.parse(OPERATOR_REWARD_KEY)?,
+                    delegates: parser.parse(DELEGATES_REWARD_KEY)?,
+                },
+            })
+        } else {
+            Err(parser.malformed("it is not a typed mixnet event"))
+        }
+    }
+}
+
+impl From<MixnetEvent> for Event {
+    fn from(mixnet_event: MixnetEvent) -> Self {
+        let event = Event::new(mixnet_event.event_type());
+        match mixnet_event {
+            // coin implements Display trait and we use that implementation here
+            MixnetEvent::Bonded {
+                mix_id,
+                identity,
+                owner,
+                proxy,
+                amount,
+                assigned_layer,
+            } => event
+                .add_attribute(MIX_ID_KEY, mix_id.to_string())
+                .add_attribute(NODE_IDENTITY_KEY, identity)
+                .add_attribute(OWNER_KEY, owner)
+                .add_optional_attribute(PROXY_KEY, proxy)
+                .add_attribute(ASSIGNED_LAYER_KEY, assigned_layer)
+                .add_attribute(AMOUNT_KEY, amount.to_string()),
+            MixnetEvent::Unbonded { created_at, mix_id } => event
+                .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+                .add_attribute(MIX_ID_KEY, mix_id.to_string()),
+            MixnetEvent::DelegationAdded {
+                created_at,
+                delegator,
+                proxy,
+                amount,
+                mix_id,
+                unit_reward,
+            } => event
+                .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
+                .add_attribute(DELEGATOR_KEY, delegator)
+                .add_optional_attribute(PROXY_KEY, proxy)
+                .add_attribute(AMOUNT_KEY, amount.to_string())
+                .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
+                .add_attribute(UNIT_REWARD_KEY, unit_reward.to_string()),
+            MixnetEvent::RewardDistributed {
+                epoch,
+                mix_id,
+                prior_delegates,
+                prior_unit_reward,
+                distribution,
+            } => event
+                .add_attribute(INTERVAL_KEY, epoch.to_string())
+                .add_attribute(PRIOR_DELEGATES_KEY, prior_delegates.to_string())
+                .add_attribute(PRIOR_UNIT_REWARD_KEY, prior_unit_reward.to_string())
+                .add_attribute(MIX_ID_KEY, mix_id.to_string())
+                .add_attribute(OPERATOR_REWARD_KEY, distribution.operator.to_string())
+                .add_attribute(DELEGATES_REWARD_KEY, distribution.delegates.to_string()),
+        }
+    }
+}
+
+struct EventParser<'a> {
+    event: &'a Event,
+}
+
+impl EventParser<'_> {
+    fn malformed(&self, reason: impl Into<String>) -> MixnetContractError {
+        MixnetContractError::MalformedEvent {
+            event_type: self.event.ty.clone(),
+            reason: reason.into(),
+        }
+    }
+
+    fn attribute(&self, key: &str) -> Result<String, MixnetContractError> {
+        may_find_attribute(self.event, key)
+            .ok_or_else(|| self.malformed(format!("the '{key}' attribute is missing")))
+    }
+
+    fn parse<T: FromStr>(&self, key: &str) -> Result<T, MixnetContractError> {
+        self.attribute(key)?
+            .parse()
+            .map_err(|_| self.malformed(format!("the '{key}' attribute has an invalid value")))
+    }
+
+    fn layer(&self, key: &str) -> Result<Layer, MixnetContractError> {
+        Layer::try_from(self.parse::<u8>(key)?)
+    }
+
+    // parse the `Display` representation of a coin, i.e. `<amount><denom>`
+    fn coin(&self, key: &str) -> Result<Coin, MixnetContractError> {
+        let raw = self.attribute(key)?;
+        let denom_start = raw
+            .find(|c: char| !c.is_ascii_digit())
+            .ok_or_else(|| self.malformed(format!("the '{key}' attribute is missing the denom")))?;
+        let (amount, denom) = raw.split_at(denom_start);
+        let amount = Uint128::from_str(amount)
+            .map_err(|_| self.malformed(format!("the '{key}' attribute has an invalid amount")))?;
+
+        Ok(Coin::new(amount.u128(), denom))
+    }
+}
+
 pub fn new_delegation_event(
     created_at: BlockHeight,
     delegator: &Addr,
@@ -167,24 +391,39 @@ pub fn new_delegation_event(
     amount: &Coin,
     mix_id: MixId,
     unit_reward: Decimal,
+) -> Event {
+    MixnetEvent::DelegationAdded {
+        created_at,
+        delegator: delegator.clone(),
+        proxy: proxy.clone(),
+        amount: amount.clone(),
+        mix_id,
+        unit_reward,
+    }
+    .into()
+}
+
+pub fn new_delegation_on_unbonded_node_event(
+    delegator: &Addr,
+    proxy: &Option<Addr>,
+    mix_id: MixId,
 ) -> Event {
     Event::new(MixnetEventType::Delegation)
-        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
         .add_attribute(DELEGATOR_KEY, delegator)
         .add_optional_attribute(PROXY_KEY, proxy.as_ref())
-        .add_attribute(AMOUNT_KEY, amount.to_string())
         .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
-        .add_attribute(UNIT_REWARD_KEY, unit_reward.to_string())
 }
 
-pub fn new_delegation_on_unbonded_node_event(
+pub fn new_delegation_cap_exceeded_event(
     delegator: &Addr,
     proxy: &Option<Addr>,
+    amount: &Coin,
     mix_id: MixId,
 ) -> Event {
-    Event::new(MixnetEventType::Delegation)
+    Event::new(MixnetEventType::DelegationCapExceeded)
         .add_attribute(DELEGATOR_KEY, delegator)
         .add_optional_attribute(PROXY_KEY, proxy.as_ref())
+        .add_attribute(AMOUNT_KEY, amount.to_string())
         .add_attribute(DELEGATION_TARGET_KEY, mix_id.to_string())
 }
 
@@ -336,14 +575,15 @@ pub fn new_mixnode_bonding_event(
     mix_id: MixId,
     assigned_layer: Layer,
 ) -> Event {
-    // coin implements Display trait and we use that implementation here
-    Event::new(MixnetEventType::MixnodeBonding)
-        .add_attribute(MIX_ID_KEY, mix_id.to_string())
-        .add_attribute(NODE_IDENTITY_KEY, identity)
-        .add_attribute(OWNER_KEY, owner)
-        .add_optional_attribute(PROXY_KEY, proxy.as_ref())
-        .add_attribute(ASSIGNED_LAYER_KEY, assigned_layer)
-        .add_attribute(AMOUNT_KEY, amount.to_string())
+    MixnetEvent::Bonded {
+        mix_id,
+        identity: identity.to_string(),
+        owner: owner.clone(),
+        proxy: proxy.clone(),
+        amount: amount.clone(),
+        assigned_layer,
+    }
+    .into()
 }
 
 pub fn new_pending_pledge_increase_event(mix_id: MixId, amount: &Coin) -> Event {
@@ -373,9 +613,7 @@ pub fn new_pledge_decrease_event(created_at: BlockHeight, mix_id: MixId, amount:
 }
 
 pub fn new_mixnode_unbonding_event(created_at: BlockHeight, mix_id: MixId) -> Event {
-    Event::new(MixnetEventType::MixnodeUnbonding)
-        .add_attribute(EVENT_CREATION_HEIGHT_KEY, created_at.to_string())
-        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+    MixnetEvent::Unbonded { created_at, mix_id }.into()
 }
 
 pub fn new_pending_mixnode_unbonding_event(
@@ -404,6 +642,63 @@ pub fn new_mixnode_config_update_event(
         .add_attribute(UPDATED_MIXNODE_CONFIG_KEY, update.to_inline_json())
 }
 
+pub fn new_mixnode_description_update_event(
+    mix_id: MixId,
+    owner: &Addr,
+    description: &MixNodeDescription,
+) -> Event {
+    Event::new(MixnetEventType::MixnodeDescriptionUpdate)
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(
+            UPDATED_MIXNODE_DESCRIPTION_KEY,
+            description.to_inline_json(),
+        )
+}
+
+pub fn new_mixnode_ownership_transfer_proposal_event(
+    mix_id: MixId,
+    owner: &Addr,
+    new_owner: &Addr,
+) -> Event {
+    Event::new(MixnetEventType::MixnodeOwnershipTransferProposal)
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(NEW_OWNER_KEY, new_owner)
+}
+
+pub fn new_mixnode_ownership_transfer_cancellation_event(mix_id: MixId, owner: &Addr) -> Event {
+    Event::new(MixnetEventType::MixnodeOwnershipTransferCancellation)
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(OWNER_KEY, owner)
+}
+
+pub fn new_mixnode_ownership_transfer_event(
+    mix_id: MixId,
+    previous_owner: &Addr,
+    new_owner: &Addr,
+) -> Event {
+    Event::new(MixnetEventType::MixnodeOwnershipTransfer)
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(PREVIOUS_OWNER_KEY, previous_owner)
+        .add_attribute(NEW_OWNER_KEY, new_owner)
+}
+
+pub fn new_mixnode_sphinx_key_rotation_announcement_event(
+    mix_id: MixId,
+    owner: &Addr,
+    next_sphinx_key: &NextSphinxKey,
+) -> Event {
+    Event::new(MixnetEventType::MixnodeSphinxKeyRotationAnnouncement)
+        .add_attribute(MIX_ID_KEY, mix_id.to_string())
+        .add_attribute(OWNER_KEY, owner)
+        .add_attribute(NEXT_SPHINX_KEY_KEY, &next_sphinx_key.sphinx_key)
+        .add_attribute(
+            ACTIVATION_EPOCH_KEY,
+            next_sphinx_key.activation_epoch.to_string(),
+        )
+}
+
 pub fn new_gateway_config_update_event(
     owner: &Addr,
     proxy: &Option<Addr>,
@@ -488,6 +783,34 @@ pub fn new_settings_update_event(
         }
     }
 
+    if old_params.max_delegation_to_pledge_ratio != new_params.max_delegation_to_pledge_ratio {
+        let old = old_params
+            .max_delegation_to_pledge_ratio
+            .map(|ratio| ratio.to_string())
+            .unwrap_or_else(|| "None".to_string());
+        let new = new_params
+            .max_delegation_to_pledge_ratio
+            .map(|ratio| ratio.to_string())
+            .unwrap_or_else(|| "None".to_string());
+        event = event
+            .add_attribute(OLD_MAX_DELEGATION_TO_PLEDGE_RATIO_KEY, old)
+            .add_attribute(NEW_MAX_DELEGATION_TO_PLEDGE_RATIO_KEY, new)
+    }
+
+    if old_params.min_supported_version != new_params.min_supported_version {
+        let old = old_params
+            .min_supported_version
+            .clone()
+            .unwrap_or_else(|| "None".to_string());
+        let new = new_params
+            .min_supported_version
+            .clone()
+            .unwrap_or_else(|| "None".to_string());
+        event = event
+            .add_attribute(OLD_MIN_SUPPORTED_VERSION_KEY, old)
+            .add_attribute(NEW_MIN_SUPPORTED_VERSION_KEY, new)
+    }
+
     event
 }
 
@@ -518,22 +841,14 @@ pub fn new_mix_rewarding_event(
     prior_delegates: Decimal,
     prior_unit_reward: Decimal,
 ) -> Event {
-    Event::new(MixnetEventType::MixnodeRewarding)
-        .add_attribute(
-            INTERVAL_KEY,
-            interval.current_epoch_absolute_id().to_string(),
-        )
-        .add_attribute(PRIOR_DELEGATES_KEY, prior_delegates.to_string())
-        .add_attribute(PRIOR_UNIT_REWARD_KEY, prior_unit_reward.to_string())
-        .add_attribute(MIX_ID_KEY, mix_id.to_string())
-        .add_attribute(
-            OPERATOR_REWARD_KEY,
-            reward_distribution.operator.to_string(),
-        )
-        .add_attribute(
-            DELEGATES_REWARD_KEY,
-            reward_distribution.delegates.to_string(),
-        )
+    MixnetEvent::RewardDistributed {
+        epoch: interval.current_epoch_absolute_id(),
+        mix_id,
+        prior_delegates,
+        prior_unit_reward,
+        distribution: reward_distribution,
+    }
+    .into()
 }
 
 pub fn new_epoch_transition_start_event(current_interval: Interval) -> Event {
@@ -601,3 +916,65 @@ pub fn new_pending_interval_config_update_event(
             approximate_time_remaining_secs.to_string(),
         )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_events_roundtrip() {
+        let events = vec![
+            MixnetEvent::Bonded {
+                mix_id: 42,
+                identity: "identity".to_string(),
+                owner: Addr::unchecked("owner"),
+                proxy: None,
+                amount: Coin::new(100_000_000, "unym"),
+                assigned_layer: Layer::Two,
+            },
+            MixnetEvent::Unbonded {
+                created_at: 1234,
+                mix_id: 42,
+            },
+            MixnetEvent::DelegationAdded {
+                created_at: 1234,
+                delegator: Addr::unchecked("delegator"),
+                proxy: Some(Addr::unchecked("proxy")),
+                amount: Coin::new(5_000, "unym"),
+                mix_id: 42,
+                unit_reward: Decimal::percent(150),
+            },
+            MixnetEvent::RewardDistributed {
+                epoch: 100,
+                mix_id: 42,
+                prior_delegates: Decimal::percent(1000),
+                prior_unit_reward: Decimal::percent(3),
+                distribution: RewardDistribution {
+                    operator: Decimal::percent(250),
+                    delegates: Decimal::percent(125),
+                },
+            },
+        ];
+
+        for typed in events {
+            let event: Event = typed.clone().into();
+            assert_eq!(event.ty, typed.event_type().to_string());
+            assert_eq!(MixnetEvent::try_from_event(&event).unwrap(), typed);
+        }
+    }
+
+    #[test]
+    fn legacy_builders_emit_typed_events() {
+        let event = new_mixnode_unbonding_event(1234, 42);
+        assert_eq!(
+            MixnetEvent::try_from_event(&event).unwrap(),
+            MixnetEvent::Unbonded {
+                created_at: 1234,
+                mix_id: 42
+            }
+        );
+
+        let not_typed = new_active_set_update_event(1234, 240);
+        assert!(MixnetEvent::try_from_event(&not_typed).is_err());
+    }
+}
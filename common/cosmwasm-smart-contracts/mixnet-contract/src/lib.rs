@@ -4,6 +4,7 @@
 #![warn(clippy::expect_used)]
 #![warn(clippy::unwrap_used)]
 
+pub mod active_set;
 mod constants;
 pub mod delegation;
 pub mod error;
@@ -20,6 +21,9 @@ pub mod rewarding;
 pub mod signing_types;
 pub mod types;
 
+pub use active_set::{
+    ActiveSetMembershipResponse, ActiveSetProvenance, EpochActiveSet, EpochActiveSetResponse,
+};
 pub use contracts_common::types::*;
 pub use cosmwasm_std::{Addr, Coin, Decimal, Fraction};
 pub use delegation::{
@@ -38,20 +42,27 @@ pub use interval::{
     CurrentIntervalResponse, EpochId, EpochState, EpochStatus, Interval, IntervalId,
 };
 pub use mixnode::{
-    Layer, MixNode, MixNodeBond, MixNodeConfigUpdate, MixNodeCostParams, MixNodeDetails,
-    MixNodeRewarding, MixOwnershipResponse, MixnodeDetailsByIdentityResponse,
-    MixnodeDetailsResponse, PagedMixnodeBondsResponse, RewardedSetNodeStatus, UnbondedMixnode,
+    Layer, MixLayer, MixNode, MixNodeBond, MixNodeConfigUpdate, MixNodeCostParams,
+    MixNodeDescription, MixNodeDetails, MixNodeDetailsWithStatus, MixNodeRewarding, MixNodeStatus,
+    MixOwnershipResponse, MixnodeDescriptionResponse, MixnodeDetailsByIdentityResponse,
+    MixnodeDetailsResponse, MixnodeDetailsWithPerformanceResponse, MixnodePledgeBreakdownResponse,
+    NextSphinxKey, PagedMixnodeBondsResponse, PagedSkimmedMixnodesResponse,
+    PendingOwnershipTransfer, PendingOwnershipTransferResponse, PledgeBreakdown,
+    RewardedSetNodeStatus, SkimmedMixNode, UnbondedMixnode,
 };
 pub use msg::*;
 pub use pending_events::{
-    EpochEventId, IntervalEventId, NumberOfPendingEventsResponse, PendingEpochEvent,
-    PendingEpochEventData, PendingEpochEventKind, PendingEpochEventResponse,
-    PendingEpochEventsResponse, PendingIntervalEvent, PendingIntervalEventData,
-    PendingIntervalEventKind, PendingIntervalEventResponse, PendingIntervalEventsResponse,
+    EpochEventId, IntervalEventId, NumberOfPendingEventsResponse, PendingDelegationEvent,
+    PendingDelegationEventKind, PendingEpochEvent, PendingEpochEventData, PendingEpochEventKind,
+    PendingEpochEventResponse, PendingEpochEventsResponse, PendingIntervalEvent,
+    PendingIntervalEventData, PendingIntervalEventKind, PendingIntervalEventResponse,
+    PendingIntervalEventsResponse, PendingMixNodeDelegationEventsResponse,
 };
 pub use reward_params::{IntervalRewardParams, IntervalRewardingParamsUpdate, RewardingParams};
 pub use rewarding::{
-    EstimatedCurrentEpochRewardResponse, PagedRewardedSetResponse, PendingRewardResponse,
+    EpochRewardingSimulationParams, EstimatedCurrentEpochRewardResponse, PagedRewardedSetResponse,
+    PendingRewardResponse, SimulatedEpochRewardingResponse, SimulatedNodeReward,
+    StakeSummaryResponse,
 };
 pub use signing_types::*;
 pub use types::*;
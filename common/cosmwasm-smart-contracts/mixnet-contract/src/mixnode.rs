@@ -7,14 +7,18 @@
 use crate::constants::{TOKEN_SUPPLY, UNIT_DELEGATION_BASE};
 use crate::error::MixnetContractError;
 use crate::helpers::IntoBaseDecimal;
-use crate::reward_params::{NodeRewardParams, RewardingParams};
+use crate::reward_params::{NodeRewardParams, Performance, RewardingParams};
 use crate::rewarding::helpers::truncate_reward;
 use crate::rewarding::RewardDistribution;
 use crate::{Delegation, EpochEventId, EpochId, IdentityKey, MixId, Percent, SphinxKey};
 use cosmwasm_schema::cw_serde;
-use cosmwasm_std::{Addr, Coin, Decimal, StdResult, Uint128};
+use cosmwasm_std::{Addr, Coin, Decimal, StdError, StdResult, Uint128};
 use schemars::JsonSchema;
+use serde::de::{self, Visitor};
+use serde::{Deserialize, Deserializer, Serialize};
 use serde_repr::{Deserialize_repr, Serialize_repr};
+use std::fmt::Display;
+use std::str::FromStr;
 
 /// Current state of given node in the rewarded set.
 #[cfg_attr(feature = "generate-ts", derive(ts_rs::TS))]
@@ -40,6 +44,65 @@ impl RewardedSetNodeStatus {
     }
 }
 
+/// Overall status of a bonded mixnode, combining its position in the rewarded set with its bonding state.
+#[cfg_attr(feature = "generate-ts", derive(ts_rs::TS))]
+#[cfg_attr(
+    feature = "generate-ts",
+    ts(
+        rename = "MixNodeContractStatus",
+        export_to = "ts-packages/types/src/types/rust/MixNodeContractStatus.ts"
+    )
+)]
+#[cw_serde]
+#[derive(Copy)]
+pub enum MixNodeStatus {
+    /// Node that is currently active, i.e. is expected to be used by clients for mixing packets.
+    Active,
+
+    /// Node that is currently in standby, i.e. it's present in the rewarded set but is not active.
+    Standby,
+
+    /// Node that is bonded, but is not present in the rewarded set.
+    Inactive,
+
+    /// Node that is in the process of unbonding that will conclude upon the epoch finishing.
+    Unbonding,
+
+    /// Node that has been blacklisted by the network monitor.
+    /// Note: this status is never assigned by the contract itself as it has no knowledge of node performance,
+    /// it's only set by other services, such as the nym-api, that extend the contract data.
+    Blacklisted,
+}
+
+impl MixNodeStatus {
+    pub fn new(is_unbonding: bool, rewarded_set_status: Option<RewardedSetNodeStatus>) -> Self {
+        if is_unbonding {
+            return MixNodeStatus::Unbonding;
+        }
+        match rewarded_set_status {
+            Some(status) => status.into(),
+            None => MixNodeStatus::Inactive,
+        }
+    }
+
+    pub fn is_active(&self) -> bool {
+        matches!(self, MixNodeStatus::Active)
+    }
+
+    pub fn is_in_rewarded_set(&self) -> bool {
+        matches!(self, MixNodeStatus::Active | MixNodeStatus::Standby)
+    }
+}
+
+impl From<RewardedSetNodeStatus> for MixNodeStatus {
+    fn from(status: RewardedSetNodeStatus) -> Self {
+        match status {
+            RewardedSetNodeStatus::Active => MixNodeStatus::Active,
+            RewardedSetNodeStatus::Standby => MixNodeStatus::Standby,
+        }
+    }
+}
+
 /// Full details associated with given mixnode.
 #[cw_serde]
 pub struct MixNodeDetails {
@@ -101,6 +164,15 @@ impl MixNodeDetails {
     pub fn pending_pledge_change(&self) -> Option<EpochEventId> {
         self.pending_changes.pledge_change
     }
+
+    pub fn pledge_breakdown(&self) -> PledgeBreakdown {
+        self.bond_information
+            .pledge_breakdown(&self.rewarding_details)
+    }
+
+    pub fn cost_params(&self) -> &MixNodeCostParams {
+        &self.rewarding_details.cost_params
+    }
 }
 
 #[cw_serde]
@@ -131,6 +203,37 @@ pub struct MixNodeRewarding {
 }
 
 impl MixNodeRewarding {
+    /// Maximum total delegation this node can hold given the ratio to its current pledge.
+    pub fn delegation_cap(&self, max_delegation_to_pledge_ratio: Decimal) -> Decimal {
+        self.operator * max_delegation_to_pledge_ratio
+    }
+
+    /// Ensure the additional delegation wouldn't push the total delegation of this node above its cap, if any.
+    /// `pending_delegation` is the amount delegated towards this node that has not been applied yet.
+    pub fn ensure_within_delegation_cap(
+        &self,
+        mix_id: MixId,
+        max_delegation_to_pledge_ratio: Option<Decimal>,
+        pending_delegation: Uint128,
+        delegation: &Coin,
+    ) -> Result<(), MixnetContractError> {
+        let Some(ratio) = max_delegation_to_pledge_ratio else {
+            return Ok(());
+        };
+
+        let cap = self.delegation_cap(ratio);
+        let current_delegation = self.delegates + pending_delegation.into_base_decimal()?;
+        if current_delegation + delegation.amount.into_base_decimal()? > cap {
+            return Err(MixnetContractError::DelegationCapExceeded {
+                mix_id,
+                cap,
+                current_delegation,
+                attempted: delegation.amount,
+            });
+        }
+        Ok(())
+    }
+
     pub fn initialise_new(
         cost_params: MixNodeCostParams,
         initial_pledge: &Coin,
@@ -548,6 +651,76 @@ impl MixNodeBond {
     pub fn mix_node(&self) -> &MixNode {
         &self.mix_node
     }
+
+    /// Checks whether this mixnode has been bonded using tokens coming from a vesting account.
+    pub fn is_vesting_bond(&self) -> bool {
+        self.proxy.is_some()
+    }
+
+    /// Returns the total amount of tokens pledged by the operator, including any subsequent top-ups,
+    /// but without any compounded rewards.
+    pub fn total_pledge(&self) -> Coin {
+        self.original_pledge.clone()
+    }
+
+    /// Splits the operator pledge based on the origin of the tokens, i.e. whether they came from
+    /// a vesting account or liquid balance, alongside any compounded rewards.
+    pub fn pledge_breakdown(&self, rewarding_details: &MixNodeRewarding) -> PledgeBreakdown {
+        let denom = &self.original_pledge.denom;
+        let zero = Coin::new(0, denom);
+
+        // the contract does not allow mixing the sources of the pledge, i.e. if the node has been
+        // bonded with vesting tokens, any top-ups must have also come from the vesting account
+        let (vesting, liquid) = if self.is_vesting_bond() {
+            (self.total_pledge(), zero)
+        } else {
+            (zero, self.total_pledge())
+        };
+
+        PledgeBreakdown {
+            vesting,
+            liquid,
+            compounded_rewards: rewarding_details.pending_operator_reward(&self.original_pledge),
+        }
+    }
+}
+
+impl Display for MixNodeBond {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mix_id: {}, amount: {} {}, owner: {}, identity: {}, layer: {}",
+            self.mix_id,
+            self.original_pledge.amount,
+            self.original_pledge.denom,
+            self.owner,
+            self.mix_node.identity_key,
+            self.layer as u8
+        )
+    }
+}
+
+/// Breakdown of the tokens pledged by the mixnode operator based on their origin.
+#[cw_serde]
+pub struct PledgeBreakdown {
+    /// Part of the pledge that came from a vesting account and thus might still be locked.
+    pub vesting: Coin,
+
+    /// Part of the pledge that came directly from the liquid balance of the operator.
+    pub liquid: Coin,
+
+    /// Operator rewards that have been compounded on top of the pledge and are yet to be withdrawn.
+    pub compounded_rewards: Coin,
+}
+
+impl PledgeBreakdown {
+    /// Total amount of tokens currently attributed to the operator, i.e. the pledge with compounded rewards.
+    pub fn total(&self) -> Coin {
+        Coin::new(
+            (self.vesting.amount + self.liquid.amount + self.compounded_rewards.amount).u128(),
+            &self.liquid.denom,
+        )
+    }
 }
 
 /// Information provided by the node operator during bonding that are used to allow other entities to use the services of this node.
@@ -578,6 +751,43 @@ pub struct MixNode {
 
     /// The self-reported semver version of this mixnode.
     pub version: String,
+
+    /// The sphinx key announced by the operator that is going to replace the current one
+    /// once the specified epoch begins.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "generate-ts", ts(optional))]
+    pub next_sphinx_key: Option<NextSphinxKey>,
+}
+
+impl MixNode {
+    /// Returns the sphinx key that should be used for constructing packets during the specified epoch.
+    pub fn sphinx_key_for_epoch(&self, epoch_id: EpochId) -> &SphinxKey {
+        match &self.next_sphinx_key {
+            Some(next) if next.is_active(epoch_id) => &next.sphinx_key,
+            _ => &self.sphinx_key,
+        }
+    }
+}
+
+/// Sphinx key announced in advance of its activation so that clients would never construct packets using a stale key.
+#[cw_serde]
+#[cfg_attr(feature = "generate-ts", derive(ts_rs::TS))]
+#[cfg_attr(
+    feature = "generate-ts",
+    ts(export_to = "ts-packages/types/src/types/rust/NextSphinxKey.ts")
+)]
+pub struct NextSphinxKey {
+    /// Base58-encoded x25519 public key used for sphinx key derivation.
+    pub sphinx_key: SphinxKey,
+
+    /// Absolute id of the epoch from which this key is going to be used.
+    pub activation_epoch: EpochId,
+}
+
+impl NextSphinxKey {
+    pub fn is_active(&self, epoch_id: EpochId) -> bool {
+        epoch_id >= self.activation_epoch
+    }
 }
 
 /// The cost parameters, or the cost function, defined for the particular mixnode that influences
@@ -597,6 +807,18 @@ impl MixNodeCostParams {
     }
 }
 
+impl Display for MixNodeCostParams {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "profit margin: {}, interval operating cost: {} {}",
+            self.profit_margin_percent,
+            self.interval_operating_cost.amount,
+            self.interval_operating_cost.denom
+        )
+    }
+}
+
 impl MixNodeCostParams {
     pub fn epoch_operating_cost(&self, epochs_in_interval: u32) -> Decimal {
         Decimal::from_ratio(self.interval_operating_cost.amount, epochs_in_interval)
@@ -652,6 +874,139 @@ impl From<Layer> for u8 {
     }
 }
 
+/// Number of mix layers in the current network topology.
+pub const DEFAULT_MIX_LAYERS: u8 = 3;
+
+/// Numeric layer of a mixnode that, unlike [`Layer`], is not tied to the three-layer topology.
+/// It's (de)serialised the same way as [`Layer`], i.e. as a plain number, but for compatibility
+/// it also accepts the variant names of the enum.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct MixLayer(u8);
+
+impl MixLayer {
+    /// Creates a new layer, making sure it exists in a topology with the provided number of layers.
+    pub fn new(layer: u8, layers: u8) -> Result<Self, MixnetContractError> {
+        let mix_layer = MixLayer(layer);
+        mix_layer.validate(layers)?;
+        Ok(mix_layer)
+    }
+
+    pub fn value(&self) -> u8 {
+        self.0
+    }
+
+    /// Checks whether this layer exists in a topology with the provided number of layers.
+    pub fn validate(&self, layers: u8) -> Result<(), MixnetContractError> {
+        if self.0 == 0 || self.0 > layers {
+            return Err(MixnetContractError::InvalidMixLayer {
+                layer: self.0,
+                layers,
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns all the layers of a topology with the provided number of layers.
+    pub fn all(layers: u8) -> impl Iterator<Item = MixLayer> {
+        (1..=layers).map(MixLayer)
+    }
+}
+
+impl Display for MixLayer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl FromStr for MixLayer {
+    type Err = MixnetContractError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let layer = match s {
+            "One" => 1,
+            "Two" => 2,
+            "Three" => 3,
+            numeric => numeric.parse().map_err(|_| {
+                StdError::parse_err("MixLayer", format!("'{s}' is not a valid layer"))
+            })?,
+        };
+        if layer == 0 {
+            return Err(MixnetContractError::InvalidMixLayer {
+                layer,
+                layers: DEFAULT_MIX_LAYERS,
+            });
+        }
+        Ok(MixLayer(layer))
+    }
+}
+
+impl<'de> Deserialize<'de> for MixLayer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MixLayerVisitor;
+
+        impl<'de> Visitor<'de> for MixLayerVisitor {
+            type Value = MixLayer;
+
+            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+                formatter.write_str("a positive layer number or a layer variant name")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Self::Value, E> {
+                match u8::try_from(v) {
+                    Ok(layer) if layer > 0 => Ok(MixLayer(layer)),
+                    _ => Err(E::invalid_value(de::Unexpected::Unsigned(v), &self)),
+                }
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Self::Value, E> {
+                let unsigned = u64::try_from(v)
+                    .map_err(|_| E::invalid_value(de::Unexpected::Signed(v), &self))?;
+                self.visit_u64(unsigned)
+            }
+
+            fn visit_f64<E: de::Error>(self, v: f64) -> Result<Self::Value, E> {
+                if v.fract() != 0.0 || v < 0.0 {
+                    return Err(E::invalid_value(de::Unexpected::Float(v), &self));
+                }
+                self.visit_u64(v as u64)
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                v.parse().map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(MixLayerVisitor)
+    }
+}
+
+impl From<Layer> for MixLayer {
+    fn from(layer: Layer) -> Self {
+        MixLayer(layer.into())
+    }
+}
+
+impl TryFrom<MixLayer> for Layer {
+    type Error = MixnetContractError;
+
+    fn try_from(layer: MixLayer) -> Result<Self, Self::Error> {
+        Layer::try_from(layer.0)
+    }
+}
+
+impl From<MixLayer> for u8 {
+    fn from(layer: MixLayer) -> u8 {
+        layer.0
+    }
+}
+
+impl From<MixLayer> for String {
+    fn from(layer: MixLayer) -> Self {
+        layer.0.to_string()
+    }
+}
+
 #[cfg_attr(feature = "generate-ts", derive(ts_rs::TS))]
 #[cfg_attr(
     feature = "generate-ts",
@@ -717,6 +1072,80 @@ impl MixNodeConfigUpdate {
     }
 }
 
+/// Ownership transfer of a mixnode proposed by its current owner that is waiting to be accepted by the new owner.
+#[cw_serde]
+pub struct PendingOwnershipTransfer {
+    /// Id of the mixnode being transferred.
+    pub mix_id: MixId,
+
+    /// Address of the owner that has proposed the transfer.
+    pub current_owner: Addr,
+
+    /// Address of the owner that has to accept the transfer for it to complete.
+    pub proposed_owner: Addr,
+
+    /// Block height at which the transfer has been proposed.
+    pub proposed_at_height: u64,
+}
+
+// the limits are the same as the ones used for the validator descriptions in the cosmos staking module
+pub const MAX_MONIKER_LENGTH: usize = 70;
+pub const MAX_WEBSITE_LENGTH: usize = 140;
+pub const MAX_SECURITY_CONTACT_LENGTH: usize = 140;
+pub const MAX_DETAILS_LENGTH: usize = 280;
+
+/// Operator-provided metadata of a mixnode, so that it wouldn't have to be retrieved from the node itself.
+#[cfg_attr(feature = "generate-ts", derive(ts_rs::TS))]
+#[cfg_attr(
+    feature = "generate-ts",
+    ts(export_to = "ts-packages/types/src/types/rust/MixNodeDescription.ts")
+)]
+#[cw_serde]
+#[derive(Default)]
+pub struct MixNodeDescription {
+    /// Human-readable name of the node.
+    pub moniker: String,
+
+    /// Website associated with the node or its operator.
+    pub website: String,
+
+    /// Contact that should be used for reporting any security issues with the node.
+    pub security_contact: String,
+
+    /// Any additional details the operator wishes to share.
+    pub details: String,
+}
+
+impl MixNodeDescription {
+    pub fn validate(&self) -> Result<(), MixnetContractError> {
+        let fields = [
+            ("moniker", &self.moniker, MAX_MONIKER_LENGTH),
+            ("website", &self.website, MAX_WEBSITE_LENGTH),
+            (
+                "security_contact",
+                &self.security_contact,
+                MAX_SECURITY_CONTACT_LENGTH,
+            ),
+            ("details", &self.details, MAX_DETAILS_LENGTH),
+        ];
+
+        for (field, value, max_length) in fields {
+            if value.len() > max_length {
+                return Err(MixnetContractError::DescriptionFieldTooLong {
+                    field: field.to_string(),
+                    length: value.len(),
+                    max_length,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    pub fn to_inline_json(&self) -> String {
+        serde_json_wasm::to_string(self).unwrap_or_else(|_| "serialisation failure".into())
+    }
+}
+
 /// Response containing paged list of all mixnode bonds in the contract.
 #[cw_serde]
 pub struct PagedMixnodeBondsResponse {
@@ -741,6 +1170,76 @@ impl PagedMixnodeBondsResponse {
     }
 }
 
+/// Minimal subset of the mixnode bond information required for constructing the network topology.
+#[cw_serde]
+pub struct SkimmedMixNode {
+    /// Unique id assigned to the bonded mixnode.
+    pub mix_id: MixId,
+
+    /// Base58-encoded ed25519 EdDSA public key.
+    pub identity_key: IdentityKey,
+
+    /// Base58-encoded x25519 public key used for sphinx key derivation.
+    pub sphinx_key: SphinxKey,
+
+    /// Network address of this mixnode, for example 1.1.1.1 or foo.mixnode.com
+    pub host: String,
+
+    /// Port used by this mixnode for listening for mix packets.
+    pub mix_port: u16,
+
+    /// Port used by this mixnode for listening for verloc requests.
+    pub verloc_port: u16,
+
+    /// Port used by this mixnode for its http(s) API
+    pub http_api_port: u16,
+
+    /// Layer assigned to this mixnode.
+    pub layer: Layer,
+}
+
+impl From<MixNodeBond> for SkimmedMixNode {
+    fn from(bond: MixNodeBond) -> Self {
+        SkimmedMixNode {
+            mix_id: bond.mix_id,
+            identity_key: bond.mix_node.identity_key,
+            sphinx_key: bond.mix_node.sphinx_key,
+            host: bond.mix_node.host,
+            mix_port: bond.mix_node.mix_port,
+            verloc_port: bond.mix_node.verloc_port,
+            http_api_port: bond.mix_node.http_api_port,
+            layer: bond.layer,
+        }
+    }
+}
+
+/// Response containing paged list of skimmed mixnode information, i.e. only the data required for constructing the network topology.
+#[cw_serde]
+pub struct PagedSkimmedMixnodesResponse {
+    /// The skimmed mixnode information present in the contract.
+    pub nodes: Vec<SkimmedMixNode>,
+
+    /// Maximum number of entries that could be included in a response. `per_page <= nodes.len()`
+    pub per_page: usize,
+
+    /// Field indicating paging information for the following queries if the caller wishes to get further entries.
+    pub start_next_after: Option<MixId>,
+}
+
+impl PagedSkimmedMixnodesResponse {
+    pub fn new(
+        nodes: Vec<SkimmedMixNode>,
+        per_page: usize,
+        start_next_after: Option<MixId>,
+    ) -> Self {
+        PagedSkimmedMixnodesResponse {
+            nodes,
+            per_page,
+            start_next_after,
+        }
+    }
+}
+
 /// Response containing paged list of all mixnode details in the contract.
 #[cw_serde]
 pub struct PagedMixnodesDetailsResponse {
@@ -771,6 +1270,54 @@ impl PagedMixnodesDetailsResponse {
     }
 }
 
+/// Full details associated with given mixnode alongside its current status.
+#[cw_serde]
+pub struct MixNodeDetailsWithStatus {
+    /// Full details of the mixnode, such as its bond information, rewarding details and any pending changes.
+    pub details: MixNodeDetails,
+
+    /// The current status of this mixnode.
+    pub status: MixNodeStatus,
+}
+
+impl MixNodeDetailsWithStatus {
+    pub fn new(details: MixNodeDetails, status: MixNodeStatus) -> Self {
+        MixNodeDetailsWithStatus { details, status }
+    }
+
+    pub fn mix_id(&self) -> MixId {
+        self.details.mix_id()
+    }
+}
+
+/// Response containing paged list of all mixnode details in the contract alongside their current status.
+#[cw_serde]
+pub struct PagedMixnodesDetailsWithStatusResponse {
+    /// All mixnode details stored in the contract alongside their status.
+    pub nodes: Vec<MixNodeDetailsWithStatus>,
+
+    /// Maximum number of entries that could be included in a response. `per_page <= nodes.len()`
+    // this field is rather redundant and should be deprecated.
+    pub per_page: usize,
+
+    /// Field indicating paging information for the following queries if the caller wishes to get further entries.
+    pub start_next_after: Option<MixId>,
+}
+
+impl PagedMixnodesDetailsWithStatusResponse {
+    pub fn new(
+        nodes: Vec<MixNodeDetailsWithStatus>,
+        per_page: usize,
+        start_next_after: Option<MixId>,
+    ) -> Self {
+        PagedMixnodesDetailsWithStatusResponse {
+            nodes,
+            per_page,
+            start_next_after,
+        }
+    }
+}
+
 /// Response containing paged list of all mixnodes that have ever unbonded.
 #[cw_serde]
 pub struct PagedUnbondedMixnodesResponse {
@@ -819,6 +1366,42 @@ pub struct MixnodeDetailsResponse {
     pub mixnode_details: Option<MixNodeDetails>,
 }
 
+/// Response containing details of a mixnode with the provided id alongside the performance
+/// it has most recently been rewarded with.
+#[cw_serde]
+pub struct MixnodeDetailsWithPerformanceResponse {
+    /// Id of the requested mixnode.
+    pub mix_id: MixId,
+
+    /// If there exists a mixnode with the provided id, this field contains its detailed information.
+    pub mixnode_details: Option<MixNodeDetails>,
+
+    /// The performance of the mixnode in the last epoch it has been rewarded for, as reported
+    /// by the rewarding validator. Note that it's a single epoch value rather than a rolling average.
+    /// It's not set for nodes that have not yet been rewarded.
+    pub last_rewarded_performance: Option<Performance>,
+}
+
+/// Response containing the operator-provided description of a mixnode with the provided id.
+#[cw_serde]
+pub struct MixnodeDescriptionResponse {
+    /// Id of the requested mixnode.
+    pub mix_id: MixId,
+
+    /// If the operator of the mixnode has announced its description, this field contains it.
+    pub description: Option<MixNodeDescription>,
+}
+
+/// Response containing the pending ownership transfer of a mixnode with the provided id.
+#[cw_serde]
+pub struct PendingOwnershipTransferResponse {
+    /// Id of the requested mixnode.
+    pub mix_id: MixId,
+
+    /// If the owner of the mixnode has proposed an ownership transfer that hasn't been accepted yet, this field contains it.
+    pub transfer: Option<PendingOwnershipTransfer>,
+}
+
 /// Response containing details of a bonded mixnode with the provided identity key.
 #[cw_serde]
 pub struct MixnodeDetailsByIdentityResponse {
@@ -864,3 +1447,43 @@ pub struct StakeSaturationResponse {
     /// However, anything beyond that value has no effect on the total node reward.
     pub uncapped_saturation: Option<Decimal>,
 }
+
+/// Response containing the breakdown of the pledge of a mixnode with the provided id.
+#[cw_serde]
+pub struct MixnodePledgeBreakdownResponse {
+    /// Id of the requested mixnode.
+    pub mix_id: MixId,
+
+    /// If there exists a mixnode with the provided id, this field contains the breakdown of its pledge.
+    pub pledge_breakdown: Option<PledgeBreakdown>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mix_layer_is_compatible_with_layer_serialization() {
+        for layer in [Layer::One, Layer::Two, Layer::Three] {
+            let serialized = serde_json_wasm::to_string(&layer).unwrap();
+            let mix_layer: MixLayer = serde_json_wasm::from_str(&serialized).unwrap();
+            assert_eq!(mix_layer, MixLayer::from(layer));
+            assert_eq!(serde_json_wasm::to_string(&mix_layer).unwrap(), serialized);
+            assert_eq!(Layer::try_from(mix_layer).unwrap(), layer);
+        }
+
+        let named: MixLayer = serde_json_wasm::from_str("\"Two\"").unwrap();
+        assert_eq!(named, MixLayer::from(Layer::Two));
+        assert!(serde_json_wasm::from_str::<MixLayer>("0").is_err());
+    }
+
+    #[test]
+    fn mix_layer_is_validated_against_the_topology() {
+        assert!(MixLayer::new(4, DEFAULT_MIX_LAYERS).is_err());
+        assert!(MixLayer::new(0, DEFAULT_MIX_LAYERS).is_err());
+
+        let layer = MixLayer::new(4, 5).unwrap();
+        assert!(Layer::try_from(layer).is_err());
+        assert_eq!(MixLayer::all(5).count(), 5);
+    }
+}
@@ -1,17 +1,20 @@
 // Copyright 2021-2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::active_set::ActiveSetProvenance;
 use crate::delegation::{self, OwnerProxySubKey};
 use crate::error::MixnetContractError;
 use crate::families::FamilyHead;
 use crate::gateway::{Gateway, GatewayConfigUpdate};
 use crate::helpers::IntoBaseDecimal;
-use crate::mixnode::{Layer, MixNode, MixNodeConfigUpdate, MixNodeCostParams};
+use crate::interval::EpochId;
+use crate::mixnode::{Layer, MixNode, MixNodeConfigUpdate, MixNodeCostParams, MixNodeDescription};
 use crate::pending_events::{EpochEventId, IntervalEventId};
 use crate::reward_params::{
     IntervalRewardParams, IntervalRewardingParamsUpdate, Performance, RewardingParams,
 };
-use crate::types::{ContractStateParams, LayerAssignment, MixId};
+use crate::rewarding::EpochRewardingSimulationParams;
+use crate::types::{ContractStateParams, LayerAssignment, MixId, SphinxKey};
 use contracts_common::{signing::MessageSignature, IdentityKey, Percent};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, Decimal};
@@ -19,6 +22,7 @@ use std::time::Duration;
 
 #[cfg(feature = "schema")]
 use crate::{
+    active_set::{ActiveSetMembershipResponse, EpochActiveSetResponse},
     delegation::{
         MixNodeDelegationResponse, PagedAllDelegationsResponse, PagedDelegatorDelegationsResponse,
         PagedMixNodeDelegationsResponse,
@@ -30,18 +34,23 @@ use crate::{
     gateway::{GatewayBondResponse, GatewayOwnershipResponse, PagedGatewayResponse},
     interval::{CurrentIntervalResponse, EpochStatus},
     mixnode::{
-        MixOwnershipResponse, MixnodeDetailsByIdentityResponse, MixnodeDetailsResponse,
-        MixnodeRewardingDetailsResponse, PagedMixnodeBondsResponse, PagedMixnodesDetailsResponse,
-        PagedUnbondedMixnodesResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
+        MixOwnershipResponse, MixnodeDescriptionResponse, MixnodeDetailsByIdentityResponse,
+        MixnodeDetailsResponse, MixnodeDetailsWithPerformanceResponse,
+        MixnodePledgeBreakdownResponse, MixnodeRewardingDetailsResponse, PagedMixnodeBondsResponse,
+        PagedMixnodesDetailsResponse, PagedMixnodesDetailsWithStatusResponse,
+        PagedSkimmedMixnodesResponse, PagedUnbondedMixnodesResponse,
+        PendingOwnershipTransferResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
     },
     pending_events::{
         NumberOfPendingEventsResponse, PendingEpochEventResponse, PendingEpochEventsResponse,
         PendingIntervalEventResponse, PendingIntervalEventsResponse,
+        PendingMixNodeDelegationEventsResponse,
     },
     rewarding::{
         EstimatedCurrentEpochRewardResponse, PagedRewardedSetResponse, PendingRewardResponse,
+        SimulatedEpochRewardingResponse, StakeSummaryResponse,
     },
-    types::{ContractState, LayerDistribution},
+    types::{ContractState, LayerDistribution, MinSupportedVersionResponse},
 };
 #[cfg(feature = "schema")]
 use contracts_common::{signing::Nonce, ContractBuildInformation};
@@ -167,6 +176,9 @@ pub enum ExecuteMsg {
         new_rewarded_set: Vec<LayerAssignment>,
         // families_in_layer: HashMap<String, Layer>,
         expected_active_set_size: u32,
+        /// Source of the randomness used for selecting the new active set, if applicable.
+        #[serde(default)]
+        selection_provenance: Option<ActiveSetProvenance>,
     },
     ReconcileEpochEvents {
         limit: Option<u32>,
@@ -213,6 +225,26 @@ pub enum ExecuteMsg {
         new_config: MixNodeConfigUpdate,
         owner: String,
     },
+    UpdateMixnodeDescription {
+        description: MixNodeDescription,
+    },
+    /// Proposes transferring the ownership of the sender's mixnode to the specified address.
+    /// The transfer only happens once the new owner accepts it.
+    TransferMixNodeOwnership {
+        new_owner: String,
+    },
+    /// Cancels the ownership transfer of the sender's mixnode that hasn't yet been accepted.
+    CancelMixNodeOwnershipTransfer {},
+    /// Accepts the ownership transfer of the specified mixnode proposed to the sender.
+    AcceptMixNodeOwnership {
+        mix_id: MixId,
+    },
+    /// Announces the sphinx key that is going to replace the current key of the sender's mixnode
+    /// once the specified epoch begins.
+    RotateMixnodeSphinxKey {
+        next_sphinx_key: SphinxKey,
+        activation_epoch: EpochId,
+    },
 
     // gateway-related:
     BondGateway {
@@ -257,10 +289,16 @@ pub enum ExecuteMsg {
         mix_id: MixId,
         performance: Performance,
     },
+    /// Withdraws the operator reward accumulated since the node has been bonded
+    /// (or since the previous withdrawal) without unbonding the node.
+    #[serde(alias = "claim_operator_reward")]
     WithdrawOperatorReward {},
     WithdrawOperatorRewardOnBehalf {
         owner: String,
     },
+    /// Withdraws the reward accumulated by the delegation towards the specified node
+    /// without undelegating.
+    #[serde(alias = "claim_delegator_reward")]
     WithdrawDelegatorReward {
         mix_id: MixId,
     },
@@ -345,6 +383,19 @@ impl ExecuteMsg {
             ExecuteMsg::UpdateMixnodeConfigOnBehalf { .. } => {
                 "updating mixnode configuration on behalf".into()
             }
+            ExecuteMsg::UpdateMixnodeDescription { .. } => "updating mixnode description".into(),
+            ExecuteMsg::TransferMixNodeOwnership { new_owner } => {
+                format!("proposing mixnode ownership transfer to {new_owner}")
+            }
+            ExecuteMsg::CancelMixNodeOwnershipTransfer { .. } => {
+                "cancelling mixnode ownership transfer".into()
+            }
+            ExecuteMsg::AcceptMixNodeOwnership { mix_id } => {
+                format!("accepting ownership of mixnode {mix_id}")
+            }
+            ExecuteMsg::RotateMixnodeSphinxKey {
+                activation_epoch, ..
+            } => format!("announcing mixnode sphinx key rotation at epoch {activation_epoch}"),
             ExecuteMsg::BondGateway { gateway, .. } => {
                 format!("bonding gateway {}", gateway.identity_key)
             }
@@ -451,10 +502,19 @@ pub enum QueryMsg {
     #[cfg_attr(feature = "schema", returns(ContractState))]
     GetState {},
 
+    /// Gets the minimum version a mixnode must be running in order to get bonded.
+    #[cfg_attr(feature = "schema", returns(MinSupportedVersionResponse))]
+    GetMinSupportedMixnodeVersion {},
+
     /// Gets the current parameters used for reward calculation.
     #[cfg_attr(feature = "schema", returns(RewardingParams))]
     GetRewardingParams {},
 
+    /// Gets the aggregated stake of the whole network, i.e. the total bonded and delegated tokens,
+    /// the stake of the active set and the size of the reward pool.
+    #[cfg_attr(feature = "schema", returns(StakeSummaryResponse))]
+    GetStakeSummary {},
+
     /// Gets the status of the current rewarding epoch.
     #[cfg_attr(feature = "schema", returns(EpochStatus))]
     GetEpochStatus {},
@@ -473,6 +533,24 @@ pub enum QueryMsg {
         start_after: Option<MixId>,
     },
 
+    /// Gets the active set selected for the provided epoch alongside the provenance of the selection.
+    /// Only a bounded number of the most recent epochs is retained.
+    #[cfg_attr(feature = "schema", returns(EpochActiveSetResponse))]
+    GetEpochActiveSet {
+        /// The absolute id of the epoch to query.
+        epoch_id: EpochId,
+    },
+
+    /// Checks whether the provided node has been part of the active set in the given epoch.
+    #[cfg_attr(feature = "schema", returns(ActiveSetMembershipResponse))]
+    GetActiveSetMembership {
+        /// Id of the node to query.
+        mix_id: MixId,
+
+        /// The absolute id of the epoch to query.
+        epoch_id: EpochId,
+    },
+
     // mixnode-related:
     /// Gets the basic list of all currently bonded mixnodes.
     #[cfg_attr(feature = "schema", returns(PagedMixnodeBondsResponse))]
@@ -484,6 +562,16 @@ pub enum QueryMsg {
         start_after: Option<MixId>,
     },
 
+    /// Gets the skimmed list of all currently bonded mixnodes, i.e. only the information required for constructing the network topology.
+    #[cfg_attr(feature = "schema", returns(PagedSkimmedMixnodesResponse))]
+    GetSkimmedMixNodes {
+        /// Controls the maximum number of entries returned by the query. Note that too large values will be overwritten by a saner default.
+        limit: Option<u32>,
+
+        /// Pagination control for the values returned by the query. Note that the provided value itself will **not** be used for the response.
+        start_after: Option<MixId>,
+    },
+
     /// Gets the detailed list of all currently bonded mixnodes.
     #[cfg_attr(feature = "schema", returns(PagedMixnodesDetailsResponse))]
     GetMixNodesDetailed {
@@ -494,6 +582,16 @@ pub enum QueryMsg {
         start_after: Option<MixId>,
     },
 
+    /// Gets the detailed list of all currently bonded mixnodes alongside their current status.
+    #[cfg_attr(feature = "schema", returns(PagedMixnodesDetailsWithStatusResponse))]
+    GetMixNodesDetailedWithStatus {
+        /// Controls the maximum number of entries returned by the query. Note that too large values will be overwritten by a saner default.
+        limit: Option<u32>,
+
+        /// Pagination control for the values returned by the query. Note that the provided value itself will **not** be used for the response.
+        start_after: Option<MixId>,
+    },
+
     /// Gets the basic list of all unbonded mixnodes.
     #[cfg_attr(feature = "schema", returns(PagedUnbondedMixnodesResponse))]
     GetUnbondedMixNodes {
@@ -544,6 +642,28 @@ pub enum QueryMsg {
         mix_id: MixId,
     },
 
+    /// Gets the detailed mixnode information of a node with the provided id alongside
+    /// the performance reported by the rewarding validator for the last epoch the node has been rewarded for.
+    #[cfg_attr(feature = "schema", returns(MixnodeDetailsWithPerformanceResponse))]
+    GetMixnodeDetailsWithPerformance {
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
+    /// Gets the operator-provided description of a mixnode with the provided id.
+    #[cfg_attr(feature = "schema", returns(MixnodeDescriptionResponse))]
+    GetMixnodeDescription {
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
+    /// Gets the ownership transfer of a mixnode with the provided id that's waiting to be accepted.
+    #[cfg_attr(feature = "schema", returns(PendingOwnershipTransferResponse))]
+    GetPendingOwnershipTransfer {
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
     /// Gets the rewarding information of a mixnode with the provided id.
     #[cfg_attr(feature = "schema", returns(MixnodeRewardingDetailsResponse))]
     GetMixnodeRewardingDetails {
@@ -558,6 +678,14 @@ pub enum QueryMsg {
         mix_id: MixId,
     },
 
+    /// Gets the breakdown of the pledge of a mixnode with the provided id based on the origin of the tokens,
+    /// i.e. whether they came from a vesting account or liquid balance.
+    #[cfg_attr(feature = "schema", returns(MixnodePledgeBreakdownResponse))]
+    GetMixnodePledgeBreakdown {
+        /// Id of the node to query.
+        mix_id: MixId,
+    },
+
     /// Gets the basic information of an unbonded mixnode with the provided id.
     #[cfg_attr(feature = "schema", returns(UnbondedMixnodeResponse))]
     GetUnbondedMixNodeInformation {
@@ -710,6 +838,21 @@ pub enum QueryMsg {
         estimated_performance: Performance,
     },
 
+    /// Simulates the rewarding of the nodes in the current rewarded set for a single epoch
+    /// given hypothetical changes to the rewarding parameters, such as different active set size
+    /// or reward pool emission.
+    #[cfg_attr(feature = "schema", returns(SimulatedEpochRewardingResponse))]
+    SimulateEpochRewarding {
+        /// Hypothetical changes to the current rewarding parameters.
+        params: EpochRewardingSimulationParams,
+
+        /// Controls the maximum number of entries returned by the query. Note that too large values will be overwritten by a saner default.
+        limit: Option<u32>,
+
+        /// Pagination control for the values returned by the query. Note that the provided value itself will **not** be used for the response.
+        start_after: Option<MixId>,
+    },
+
     // interval-related
     /// Gets the list of all currently pending epoch events that will be resolved once the current epoch finishes.
     #[cfg_attr(feature = "schema", returns(PendingEpochEventsResponse))]
@@ -721,6 +864,20 @@ pub enum QueryMsg {
         start_after: Option<u32>,
     },
 
+    /// Gets the list of all currently pending delegation and undelegation requests towards particular mixnode
+    /// that will be resolved once the current epoch finishes.
+    #[cfg_attr(feature = "schema", returns(PendingMixNodeDelegationEventsResponse))]
+    GetPendingMixNodeDelegationEvents {
+        /// Id of the node to query.
+        mix_id: MixId,
+
+        /// Controls the maximum number of entries returned by the query. Note that too large values will be overwritten by a saner default.
+        limit: Option<u32>,
+
+        /// Pagination control for the values returned by the query. Note that the provided value itself will **not** be used for the response.
+        start_after: Option<EpochEventId>,
+    },
+
     /// Gets the list of all currently pending interval events that will be resolved once the current interval finishes.
     #[cfg_attr(feature = "schema", returns(PendingIntervalEventsResponse))]
     GetPendingIntervalEvents {
@@ -762,3 +919,25 @@ pub enum QueryMsg {
 pub struct MigrateMsg {
     pub vesting_contract_address: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reward_claims_are_withdrawals() {
+        let claim: ExecuteMsg =
+            serde_json_wasm::from_str(r#"{"claim_operator_reward":{}}"#).unwrap();
+        assert_eq!(claim, ExecuteMsg::WithdrawOperatorReward {});
+
+        let claim: ExecuteMsg =
+            serde_json_wasm::from_str(r#"{"claim_delegator_reward":{"mix_id":42}}"#).unwrap();
+        assert_eq!(claim, ExecuteMsg::WithdrawDelegatorReward { mix_id: 42 });
+
+        // the canonical names are still used for serialization
+        assert_eq!(
+            serde_json_wasm::to_string(&ExecuteMsg::WithdrawOperatorReward {}).unwrap(),
+            r#"{"withdraw_operator_reward":{}}"#
+        );
+    }
+}
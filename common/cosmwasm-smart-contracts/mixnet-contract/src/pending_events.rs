@@ -120,6 +120,74 @@ impl From<(EpochEventId, PendingEpochEventData)> for PendingEpochEvent {
     }
 }
 
+impl PendingEpochEvent {
+    /// If this event is a delegation or an undelegation request towards the provided mixnode,
+    /// returns its details.
+    pub fn mixnode_delegation_event(&self, target: MixId) -> Option<PendingDelegationEvent> {
+        let (owner, proxy, kind) = match &self.event.kind {
+            PendingEpochEventKind::Delegate {
+                owner,
+                mix_id,
+                amount,
+                proxy,
+            } if *mix_id == target => (
+                owner,
+                proxy,
+                PendingDelegationEventKind::Delegate {
+                    amount: amount.clone(),
+                },
+            ),
+            PendingEpochEventKind::Undelegate {
+                owner,
+                mix_id,
+                proxy,
+            } if *mix_id == target => (owner, proxy, PendingDelegationEventKind::Undelegate),
+            _ => return None,
+        };
+
+        Some(PendingDelegationEvent {
+            event_id: self.id,
+            created_at: self.event.created_at,
+            owner: owner.clone(),
+            proxy: proxy.clone(),
+            kind,
+        })
+    }
+}
+
+/// A delegation or an undelegation request made at some point in the current epoch that's going to get resolved once the epoch rolls over.
+#[cw_serde]
+pub struct PendingDelegationEvent {
+    /// The unique id associated with the underlying epoch event.
+    pub event_id: EpochEventId,
+
+    /// The block height at which the request has been made.
+    pub created_at: BlockHeight,
+
+    /// The address of the owner of the delegation.
+    pub owner: Addr,
+
+    /// Entity who made the delegation on behalf of the owner.
+    /// If present, it's most likely the address of the vesting contract.
+    pub proxy: Option<Addr>,
+
+    /// The type of the pending change.
+    pub kind: PendingDelegationEventKind,
+}
+
+/// Enum encompassing all possible pending changes to a delegation.
+#[cw_serde]
+pub enum PendingDelegationEventKind {
+    /// Request to create a delegation (or to increase an existing one) with the provided amount of tokens.
+    Delegate {
+        /// The amount of tokens to use for the delegation.
+        amount: Coin,
+    },
+
+    /// Request to remove the delegation.
+    Undelegate,
+}
+
 /// A request made at some point in the current interval that's going to get resolved once the interval rolls over.
 #[cw_serde]
 pub struct PendingIntervalEvent {
@@ -269,6 +337,24 @@ impl PendingIntervalEventResponse {
     }
 }
 
+/// Response containing all currently pending delegation and undelegation requests towards particular mixnode
+/// that will be resolved once the current epoch finishes.
+#[cw_serde]
+pub struct PendingMixNodeDelegationEventsResponse {
+    /// Id of the mixnode the delegation requests are targeting.
+    pub mix_id: MixId,
+
+    /// Amount of seconds until the events would be eligible to be resolved.
+    /// It's equivalent to the time until the current epoch finishes.
+    pub seconds_until_executable: i64,
+
+    /// The currently pending delegation events.
+    pub events: Vec<PendingDelegationEvent>,
+
+    /// Field indicating paging information for the following queries if the caller wishes to get further entries.
+    pub start_next_after: Option<EpochEventId>,
+}
+
 /// Response containing number of currently pending epoch and interval events.
 #[cw_serde]
 pub struct NumberOfPendingEventsResponse {
@@ -1,6 +1,7 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::reward_params::{IntervalRewardingParamsUpdate, Performance, RewardingParams};
 use crate::{MixId, RewardedSetNodeStatus};
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Coin, Decimal};
@@ -47,6 +48,23 @@ pub struct RewardDistribution {
     pub delegates: Decimal,
 }
 
+/// Response containing the aggregated stake of the whole network.
+#[cw_serde]
+pub struct StakeSummaryResponse {
+    /// The total amount of tokens pledged by the operators of all mixnodes (including their unclaimed rewards)
+    /// and gateways.
+    pub total_bonded: Coin,
+
+    /// The total amount of tokens delegated towards all mixnodes (including their unclaimed rewards).
+    pub total_delegated: Coin,
+
+    /// The total stake, i.e. pledges and delegations, of all the mixnodes in the active set.
+    pub active_set_stake: Coin,
+
+    /// The amount of tokens still remaining in the reward pool.
+    pub reward_pool: Coin,
+}
+
 /// Response containing information about accrued rewards.
 #[cw_serde]
 #[derive(Default)]
@@ -109,3 +127,43 @@ pub struct PagedRewardedSetResponse {
     /// Field indicating paging information for the following queries if the caller wishes to get further entries.
     pub start_next_after: Option<MixId>,
 }
+
+/// Hypothetical changes to the rewarding parameters used for simulating the epoch rewarding.
+#[cw_serde]
+#[derive(Copy, Default)]
+pub struct EpochRewardingSimulationParams {
+    /// Changes to the interval rewarding parameters, such as the reward pool or its emission rate.
+    pub interval_updates: Option<IntervalRewardingParamsUpdate>,
+
+    /// Defines the hypothetical size of the active set.
+    pub active_set_size: Option<u32>,
+
+    /// The performance assumed for every node in the rewarded set. If not provided, 100% is used.
+    pub performance: Option<Performance>,
+}
+
+/// Simulated reward of a single node from the rewarded set.
+#[cw_serde]
+pub struct SimulatedNodeReward {
+    /// Id of the node.
+    pub mix_id: MixId,
+
+    /// The current status of the node within the rewarded set.
+    pub status: RewardedSetNodeStatus,
+
+    /// The rewards the node would have received for the epoch.
+    pub reward: RewardEstimate,
+}
+
+/// Response containing paged list of simulated rewards of the nodes in the current rewarded set.
+#[cw_serde]
+pub struct SimulatedEpochRewardingResponse {
+    /// The rewarding parameters, after applying all hypothetical changes, that were used for the simulation.
+    pub rewarding_params: RewardingParams,
+
+    /// The simulated rewards of the nodes.
+    pub nodes: Vec<SimulatedNodeReward>,
+
+    /// Field indicating paging information for the following queries if the caller wishes to get further entries.
+    pub start_next_after: Option<MixId>,
+}
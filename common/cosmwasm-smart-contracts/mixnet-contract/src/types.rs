@@ -6,6 +6,7 @@ use crate::Layer;
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::Addr;
 use cosmwasm_std::Coin;
+use cosmwasm_std::Decimal;
 use std::ops::Index;
 
 // type aliases for better reasoning about available data
@@ -154,4 +155,21 @@ pub struct ContractStateParams {
 
     /// Minimum amount a gateway must pledge to get into the system.
     pub minimum_gateway_pledge: Coin,
+
+    /// Maximum total delegation a mixnode can receive, expressed as a multiple of its pledge.
+    /// If not set, the delegations are not capped.
+    #[serde(default)]
+    pub max_delegation_to_pledge_ratio: Option<Decimal>,
+
+    /// Minimum semver version a mixnode must be running in order to get bonded.
+    /// If not set, nodes of any version are accepted.
+    #[serde(default)]
+    pub min_supported_version: Option<String>,
+}
+
+/// Response containing the minimum version a mixnode must be running in order to get bonded.
+#[cw_serde]
+pub struct MinSupportedVersionResponse {
+    /// The minimum semver version of the mixnodes, if any is enforced.
+    pub min_supported_version: Option<String>,
 }
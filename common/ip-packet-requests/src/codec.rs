@@ -53,6 +53,19 @@ impl MultiIpPacketCodec {
         }
     }
 
+    // The number of packets currently waiting in the buffer.
+    pub fn buffered_packets(&self) -> usize {
+        let mut count = 0;
+        let mut offset = 0;
+        while offset + LENGTH_PREFIX_SIZE <= self.buffer.len() {
+            let packet_size =
+                u16::from_be_bytes([self.buffer[offset], self.buffer[offset + 1]]) as usize;
+            offset += LENGTH_PREFIX_SIZE + packet_size;
+            count += 1;
+        }
+        count
+    }
+
     // Flush the current buffer and return it.
     pub fn flush_current_buffer(&mut self) -> Bytes {
         let mut output_buffer = BytesMut::new();
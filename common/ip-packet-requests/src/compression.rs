@@ -0,0 +1,120 @@
+use bytes::Bytes;
+use serde::{Deserialize, Serialize};
+
+// Payloads smaller than this are sent as they are, since the compression is unlikely to save
+// enough to reduce the number of sphinx packets.
+pub const COMPRESSION_THRESHOLD: usize = 256;
+
+// Upper bound on the size of decompressed payloads, so that a malicious peer couldn't make us
+// allocate an arbitrary amount of memory.
+pub const MAX_DECOMPRESSED_SIZE: usize = 1024 * 1024;
+
+// lz4 payloads are prefixed by the little-endian u32 size of the decompressed data
+const LZ4_SIZE_PREFIX_LEN: usize = 4;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CompressionError {
+    #[error("compressed payload is too short")]
+    TruncatedPayload,
+
+    #[error("decompressed payload would be {size} bytes long, which is more than the maximum of {MAX_DECOMPRESSED_SIZE}")]
+    PayloadTooLarge { size: usize },
+
+    #[error("failed to decompress lz4 payload: {0}")]
+    Lz4(#[from] lz4_flex::block::DecompressError),
+}
+
+// Compression algorithm applied to the ip packets carried by data requests and responses.
+// It's negotiated during the connect handshake: the client specifies the one it would like to
+// use and the router confirms it in the connect response if it supports it.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    Lz4,
+}
+
+impl Compression {
+    // Compress the payload if it's above the threshold and if doing so actually makes it smaller.
+    // Returns the compression that has been applied, if any.
+    pub fn compress_if_beneficial(
+        compression: Option<Self>,
+        payload: Bytes,
+    ) -> (Bytes, Option<Self>) {
+        let Some(compression) = compression else {
+            return (payload, None);
+        };
+        if payload.len() < COMPRESSION_THRESHOLD {
+            return (payload, None);
+        }
+
+        let compressed = match compression {
+            Compression::Lz4 => lz4_flex::block::compress_prepend_size(&payload),
+        };
+        if compressed.len() < payload.len() {
+            (compressed.into(), Some(compression))
+        } else {
+            (payload, None)
+        }
+    }
+
+    // Restore the original payload based on the compression that has been applied to it.
+    pub fn decompress(
+        compression: Option<Self>,
+        payload: Bytes,
+    ) -> Result<Bytes, CompressionError> {
+        match compression {
+            None => Ok(payload),
+            Some(Compression::Lz4) => {
+                let size_prefix = payload
+                    .get(..LZ4_SIZE_PREFIX_LEN)
+                    .ok_or(CompressionError::TruncatedPayload)?;
+                let mut size_bytes = [0u8; LZ4_SIZE_PREFIX_LEN];
+                size_bytes.copy_from_slice(size_prefix);
+                let size = u32::from_le_bytes(size_bytes) as usize;
+                if size > MAX_DECOMPRESSED_SIZE {
+                    return Err(CompressionError::PayloadTooLarge { size });
+                }
+                Ok(lz4_flex::block::decompress_size_prepended(&payload)?.into())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn small_payloads_are_not_compressed() {
+        let payload = Bytes::from(vec![0u8; COMPRESSION_THRESHOLD - 1]);
+        let (compressed, applied) =
+            Compression::compress_if_beneficial(Some(Compression::Lz4), payload.clone());
+        assert_eq!(applied, None);
+        assert_eq!(compressed, payload);
+    }
+
+    #[test]
+    fn compressed_payloads_roundtrip() {
+        let payload = Bytes::from(vec![42u8; 1500]);
+        let (compressed, applied) =
+            Compression::compress_if_beneficial(Some(Compression::Lz4), payload.clone());
+        assert_eq!(applied, Some(Compression::Lz4));
+        assert!(compressed.len() < payload.len());
+
+        let decompressed = Compression::decompress(applied, compressed).unwrap();
+        assert_eq!(decompressed, payload);
+    }
+
+    #[test]
+    fn oversized_payloads_are_rejected() {
+        let mut payload = ((MAX_DECOMPRESSED_SIZE + 1) as u32).to_le_bytes().to_vec();
+        payload.extend_from_slice(&[0u8; 16]);
+        assert!(matches!(
+            Compression::decompress(Some(Compression::Lz4), payload.into()),
+            Err(CompressionError::PayloadTooLarge { .. })
+        ));
+        assert!(matches!(
+            Compression::decompress(Some(Compression::Lz4), Bytes::from_static(&[1, 2])),
+            Err(CompressionError::TruncatedPayload)
+        ));
+    }
+}
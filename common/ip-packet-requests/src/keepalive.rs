@@ -0,0 +1,13 @@
+// Keepalive parameters of the v7 protocol. The heartbeat requests and the negotiated interval are
+// only defined on the wire for now: the ip packet router still speaks v6, so it neither answers
+// the heartbeats nor expires the silent sessions.
+
+use std::time::Duration;
+
+// The keepalive interval used when the client doesn't request a specific one.
+pub const DEFAULT_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(30);
+
+// Bounds on the keepalive interval that can be agreed to. Too frequent heartbeats waste
+// bandwidth, while too infrequent ones defeat the purpose of detecting dead sessions.
+pub const MIN_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(5);
+pub const MAX_KEEPALIVE_INTERVAL: Duration = Duration::from_secs(120);
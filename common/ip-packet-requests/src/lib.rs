@@ -11,6 +11,16 @@ pub use v6::request;
 pub use v6::response;
 
 pub mod codec;
+pub mod compression;
+pub mod keepalive;
+pub mod mix_params;
+pub mod nat64;
+pub mod pending;
+pub mod priority;
+pub mod replay;
+pub mod session;
+pub mod surbs;
+pub mod trace;
 pub mod v6;
 pub mod v7;
 
@@ -18,7 +28,7 @@ pub mod v7;
 // version 4: IPv6 support
 // version 5: Add severity level to info response
 // version 6: Increase the available IPs
-// version 7: Add signature support (for the future)
+// version 7: Add signature support (for the future) and optional compression of the data
 pub const CURRENT_VERSION: u8 = 6;
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -0,0 +1,113 @@
+// The maximum number of additional mix node hops the replies can be requested to take. The
+// mixnet has three layers, so anything above that wouldn't add any anonymity.
+pub const MAX_REPLY_TO_HOPS: u8 = 3;
+
+// The maximum average delay, in milliseconds, that can be requested at each mix node.
+pub const MAX_AVG_MIX_DELAY_MS: f64 = 1000.0;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum MixParamsError {
+    #[error("requested {requested} reply hops, but at most {max} are supported", max = MAX_REPLY_TO_HOPS)]
+    TooManyReplyHops { requested: u8 },
+
+    #[error("average mix delay must be a non-negative number of milliseconds, got {requested}")]
+    InvalidAvgMixDelay { requested: f64 },
+
+    #[error(
+        "requested an average mix delay of {requested}ms, but at most {max}ms is supported",
+        max = MAX_AVG_MIX_DELAY_MS
+    )]
+    AvgMixDelayTooLarge { requested: f64 },
+}
+
+// The mixnet parameters of the replies, as sent by the client in the connect request via the
+// `reply_to_hops` and `reply_to_avg_mix_delays` fields.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct MixParams {
+    pub reply_to_hops: Option<u8>,
+    pub reply_to_avg_mix_delays: Option<f64>,
+}
+
+impl MixParams {
+    // Replies only go client -> entry -> exit -> client, trading anonymity for latency
+    pub fn fast() -> Self {
+        MixParams {
+            reply_to_hops: Some(0),
+            reply_to_avg_mix_delays: None,
+        }
+    }
+
+    // Replies go through all the layers of the mixnet
+    pub fn anonymous() -> Self {
+        MixParams {
+            reply_to_hops: Some(MAX_REPLY_TO_HOPS),
+            reply_to_avg_mix_delays: None,
+        }
+    }
+
+    // Make sure the values are within the protocol limits before they're put in a connect request
+    pub fn validate(&self) -> Result<(), MixParamsError> {
+        if let Some(hops) = self.reply_to_hops {
+            if hops > MAX_REPLY_TO_HOPS {
+                return Err(MixParamsError::TooManyReplyHops { requested: hops });
+            }
+        }
+
+        if let Some(delay) = self.reply_to_avg_mix_delays {
+            if !delay.is_finite() || delay < 0.0 {
+                return Err(MixParamsError::InvalidAvgMixDelay { requested: delay });
+            }
+            if delay > MAX_AVG_MIX_DELAY_MS {
+                return Err(MixParamsError::AvgMixDelayTooLarge { requested: delay });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn presets_are_valid() {
+        assert!(MixParams::default().validate().is_ok());
+        assert!(MixParams::fast().validate().is_ok());
+        assert!(MixParams::anonymous().validate().is_ok());
+    }
+
+    #[test]
+    fn out_of_range_values_are_rejected() {
+        let too_many_hops = MixParams {
+            reply_to_hops: Some(MAX_REPLY_TO_HOPS + 1),
+            ..Default::default()
+        };
+        assert_eq!(
+            too_many_hops.validate(),
+            Err(MixParamsError::TooManyReplyHops {
+                requested: MAX_REPLY_TO_HOPS + 1
+            })
+        );
+
+        for delay in [-1.0, f64::NAN, f64::INFINITY] {
+            let params = MixParams {
+                reply_to_avg_mix_delays: Some(delay),
+                ..Default::default()
+            };
+            assert!(matches!(
+                params.validate(),
+                Err(MixParamsError::InvalidAvgMixDelay { .. })
+            ));
+        }
+
+        let too_slow = MixParams {
+            reply_to_avg_mix_delays: Some(MAX_AVG_MIX_DELAY_MS + 1.0),
+            ..Default::default()
+        };
+        assert!(matches!(
+            too_slow.validate(),
+            Err(MixParamsError::AvgMixDelayTooLarge { .. })
+        ));
+    }
+}
@@ -0,0 +1,76 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+// The well-known prefix (RFC 6052) for representing IPv4 addresses within the IPv6 address space
+pub const WELL_KNOWN_NAT64_PREFIX: Ipv6Addr = Ipv6Addr::new(0x64, 0xff9b, 0, 0, 0, 0, 0, 0);
+
+// The length of the prefix. The IPv4 address is embedded in the last 32 bits of the IPv6 address.
+pub const NAT64_PREFIX_LEN: u8 = 96;
+
+// The /96 prefix used by the router for translating between IPv6 clients and IPv4 destinations.
+// IPv6-only clients reach an IPv4 destination `a.b.c.d` by sending their packets to
+// `<prefix>:a.b.c.d`, either by synthesizing the address themselves or via a DNS64 resolver.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Nat64Prefix(Ipv6Addr);
+
+impl Nat64Prefix {
+    // Create the prefix out of the provided address, ignoring its last 32 bits
+    pub fn new(prefix: Ipv6Addr) -> Self {
+        Nat64Prefix(Ipv6Addr::from(u128::from(prefix) & !u128::from(u32::MAX)))
+    }
+
+    pub fn address(&self) -> Ipv6Addr {
+        self.0
+    }
+
+    pub fn contains(&self, address: &Ipv6Addr) -> bool {
+        Self::new(*address) == *self
+    }
+
+    // Represent the IPv4 address within the prefix
+    pub fn embed(&self, address: Ipv4Addr) -> Ipv6Addr {
+        Ipv6Addr::from(u128::from(self.0) | u128::from(u32::from(address)))
+    }
+
+    // Recover the IPv4 address embedded in the IPv6 address, if it belongs to the prefix
+    pub fn extract(&self, address: &Ipv6Addr) -> Option<Ipv4Addr> {
+        self.contains(address)
+            .then(|| Ipv4Addr::from(u128::from(*address) as u32))
+    }
+}
+
+impl Default for Nat64Prefix {
+    fn default() -> Self {
+        Nat64Prefix(WELL_KNOWN_NAT64_PREFIX)
+    }
+}
+
+impl Display for Nat64Prefix {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{NAT64_PREFIX_LEN}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn embeds_and_extracts_ipv4_addresses() {
+        let prefix = Nat64Prefix::default();
+        let ipv4 = Ipv4Addr::new(192, 0, 2, 33);
+        let embedded = prefix.embed(ipv4);
+
+        assert_eq!(embedded, "64:ff9b::192.0.2.33".parse::<Ipv6Addr>().unwrap());
+        assert_eq!(prefix.extract(&embedded), Some(ipv4));
+        assert_eq!(prefix.extract(&"2001:db8::1".parse().unwrap()), None);
+    }
+
+    #[test]
+    fn prefix_ignores_embedded_bits() {
+        let prefix = Nat64Prefix::new("64:ff9b::1.2.3.4".parse().unwrap());
+        assert_eq!(prefix, Nat64Prefix::default());
+        assert_eq!(prefix.to_string(), "64:ff9b::/96");
+    }
+}
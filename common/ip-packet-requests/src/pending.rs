@@ -0,0 +1,335 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use tokio::sync::oneshot;
+
+// For how long to wait for the response to a control request before retransmitting it.
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+// The upper bound on the timeout, however many times the request has been retransmitted.
+pub const MAX_REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+// The number of times a request is retransmitted before giving up on it.
+pub const DEFAULT_MAX_RETRANSMISSIONS: u32 = 2;
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum PendingRequestError {
+    #[error("request {request_id} timed out after {attempts} attempts")]
+    TimedOut { request_id: u64, attempts: u32 },
+
+    #[error("request {request_id} has been cancelled")]
+    Cancelled { request_id: u64 },
+
+    #[error("request {request_id} is already pending")]
+    DuplicateRequestId { request_id: u64 },
+}
+
+pub type PendingResponse<T> = oneshot::Receiver<Result<T, PendingRequestError>>;
+
+// How the requests that haven't been responded to in time are retransmitted. The timeout doubles
+// with every retransmission, since the lack of response is most likely due to congestion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetransmissionPolicy {
+    pub timeout: Duration,
+    pub max_timeout: Duration,
+    pub max_retransmissions: u32,
+}
+
+impl Default for RetransmissionPolicy {
+    fn default() -> Self {
+        RetransmissionPolicy {
+            timeout: DEFAULT_REQUEST_TIMEOUT,
+            max_timeout: MAX_REQUEST_TIMEOUT,
+            max_retransmissions: DEFAULT_MAX_RETRANSMISSIONS,
+        }
+    }
+}
+
+impl RetransmissionPolicy {
+    // Never retransmit, just fail the request once the timeout elapses
+    pub fn no_retransmissions(timeout: Duration) -> Self {
+        RetransmissionPolicy {
+            timeout,
+            max_timeout: timeout,
+            max_retransmissions: 0,
+        }
+    }
+
+    // The timeout of the given attempt, starting from 1 for the original transmission
+    pub fn timeout_for_attempt(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(31);
+        self.timeout
+            .saturating_mul(1 << exponent)
+            .min(self.max_timeout.max(self.timeout))
+    }
+}
+
+// A request that timed out and should be sent again by the caller
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Retransmission<R> {
+    pub request_id: u64,
+    pub request: R,
+    pub attempt: u32,
+}
+
+struct PendingRequest<R, T> {
+    request: R,
+    attempt: u32,
+    deadline: Instant,
+    policy: RetransmissionPolicy,
+    response_tx: oneshot::Sender<Result<T, PendingRequestError>>,
+}
+
+// Correlates the responses received from the ip packet router with the requests they answer,
+// based on their request ids, and keeps track of the requests that need to be retransmitted.
+// The owner is expected to periodically call `handle_expired`, e.g. at `next_deadline`.
+pub struct PendingRequests<R, T> {
+    default_policy: RetransmissionPolicy,
+    pending: Mutex<HashMap<u64, PendingRequest<R, T>>>,
+}
+
+impl<R, T> Default for PendingRequests<R, T> {
+    fn default() -> Self {
+        PendingRequests::new(RetransmissionPolicy::default())
+    }
+}
+
+impl<R, T> PendingRequests<R, T> {
+    pub fn new(default_policy: RetransmissionPolicy) -> Self {
+        PendingRequests {
+            default_policy,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<u64, PendingRequest<R, T>>> {
+        self.pending.lock().unwrap()
+    }
+
+    pub fn len(&self) -> usize {
+        self.pending().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending().is_empty()
+    }
+
+    pub fn is_pending(&self, request_id: u64) -> bool {
+        self.pending().contains_key(&request_id)
+    }
+
+    // Start tracking a request that has just been sent, using the default retransmission policy
+    pub fn register(
+        &self,
+        request_id: u64,
+        request: R,
+        now: Instant,
+    ) -> Result<PendingResponse<T>, PendingRequestError> {
+        self.register_with_policy(request_id, request, self.default_policy, now)
+    }
+
+    pub fn register_with_policy(
+        &self,
+        request_id: u64,
+        request: R,
+        policy: RetransmissionPolicy,
+        now: Instant,
+    ) -> Result<PendingResponse<T>, PendingRequestError> {
+        let mut pending = self.pending();
+        if pending.contains_key(&request_id) {
+            return Err(PendingRequestError::DuplicateRequestId { request_id });
+        }
+
+        let (response_tx, response_rx) = oneshot::channel();
+        pending.insert(
+            request_id,
+            PendingRequest {
+                request,
+                attempt: 1,
+                deadline: now + policy.timeout_for_attempt(1),
+                policy,
+                response_tx,
+            },
+        );
+        Ok(response_rx)
+    }
+
+    // Deliver the response to whoever is waiting for it. Returns `false` if the request wasn't
+    // pending, e.g. because it's a duplicate response to a retransmitted request.
+    pub fn complete(&self, request_id: u64, response: T) -> bool {
+        let Some(pending) = self.pending().remove(&request_id) else {
+            return false;
+        };
+        pending.response_tx.send(Ok(response)).is_ok()
+    }
+
+    pub fn cancel(&self, request_id: u64) -> bool {
+        let Some(pending) = self.pending().remove(&request_id) else {
+            return false;
+        };
+        let _ = pending
+            .response_tx
+            .send(Err(PendingRequestError::Cancelled { request_id }));
+        true
+    }
+
+    // Fail all the pending requests, e.g. when the connection to the router has been lost
+    pub fn cancel_all(&self) {
+        for (request_id, pending) in self.pending().drain() {
+            let _ = pending
+                .response_tx
+                .send(Err(PendingRequestError::Cancelled { request_id }));
+        }
+    }
+
+    // The earliest time at which any of the pending requests is going to expire
+    pub fn next_deadline(&self) -> Option<Instant> {
+        self.pending()
+            .values()
+            .map(|pending| pending.deadline)
+            .min()
+    }
+
+    // Fail the expired requests that have exhausted their retransmissions and return the
+    // remaining ones, which the caller should send again.
+    pub fn handle_expired(&self, now: Instant) -> Vec<Retransmission<R>>
+    where
+        R: Clone,
+    {
+        let mut pending = self.pending();
+
+        // abandon the requests nobody is waiting for anymore
+        pending.retain(|_, request| !request.response_tx.is_closed());
+
+        let expired = pending
+            .iter()
+            .filter(|(_, request)| request.deadline <= now)
+            .map(|(request_id, _)| *request_id)
+            .collect::<Vec<_>>();
+
+        let mut retransmissions = Vec::new();
+        for request_id in expired {
+            let Some(request) = pending.get_mut(&request_id) else {
+                continue;
+            };
+            if request.attempt > request.policy.max_retransmissions {
+                let attempts = request.attempt;
+                if let Some(request) = pending.remove(&request_id) {
+                    let _ = request.response_tx.send(Err(PendingRequestError::TimedOut {
+                        request_id,
+                        attempts,
+                    }));
+                }
+                continue;
+            }
+
+            request.attempt += 1;
+            request.deadline = now + request.policy.timeout_for_attempt(request.attempt);
+            retransmissions.push(Retransmission {
+                request_id,
+                request: request.request.clone(),
+                attempt: request.attempt,
+            });
+        }
+        retransmissions
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RetransmissionPolicy {
+        RetransmissionPolicy {
+            timeout: Duration::from_secs(1),
+            max_timeout: Duration::from_secs(3),
+            max_retransmissions: 2,
+        }
+    }
+
+    #[test]
+    fn timeout_backs_off_up_to_the_limit() {
+        let policy = policy();
+        assert_eq!(policy.timeout_for_attempt(1), Duration::from_secs(1));
+        assert_eq!(policy.timeout_for_attempt(2), Duration::from_secs(2));
+        assert_eq!(policy.timeout_for_attempt(3), Duration::from_secs(3));
+        assert_eq!(policy.timeout_for_attempt(100), Duration::from_secs(3));
+    }
+
+    #[test]
+    fn responses_are_delivered_to_the_matching_request() {
+        let now = Instant::now();
+        let pending = PendingRequests::<&str, u32>::new(policy());
+        let mut first = pending.register(1, "first", now).unwrap();
+        let mut second = pending.register(2, "second", now).unwrap();
+        assert_eq!(
+            pending.register(1, "again", now).unwrap_err(),
+            PendingRequestError::DuplicateRequestId { request_id: 1 }
+        );
+
+        assert!(pending.complete(2, 42));
+        assert!(!pending.complete(2, 43));
+        assert!(!pending.complete(3, 44));
+        assert_eq!(second.try_recv().unwrap(), Ok(42));
+        assert!(first.try_recv().is_err());
+        assert_eq!(pending.len(), 1);
+
+        assert!(pending.cancel(1));
+        assert_eq!(
+            first.try_recv().unwrap(),
+            Err(PendingRequestError::Cancelled { request_id: 1 })
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn expired_requests_are_retransmitted_then_failed() {
+        let start = Instant::now();
+        let pending = PendingRequests::<&str, u32>::new(policy());
+        let mut response = pending.register(1, "request", start).unwrap();
+        assert_eq!(
+            pending.next_deadline(),
+            Some(start + Duration::from_secs(1))
+        );
+        assert!(pending.handle_expired(start).is_empty());
+
+        let now = start + Duration::from_secs(1);
+        assert_eq!(
+            pending.handle_expired(now),
+            vec![Retransmission {
+                request_id: 1,
+                request: "request",
+                attempt: 2,
+            }]
+        );
+        assert_eq!(pending.next_deadline(), Some(now + Duration::from_secs(2)));
+
+        let now = now + Duration::from_secs(2);
+        assert_eq!(pending.handle_expired(now).len(), 1);
+
+        let now = now + Duration::from_secs(3);
+        assert!(pending.handle_expired(now).is_empty());
+        assert_eq!(
+            response.try_recv().unwrap(),
+            Err(PendingRequestError::TimedOut {
+                request_id: 1,
+                attempts: 3
+            })
+        );
+        assert!(pending.is_empty());
+    }
+
+    #[test]
+    fn abandoned_requests_are_not_retransmitted() {
+        let start = Instant::now();
+        let pending = PendingRequests::<&str, u32>::new(policy());
+        drop(pending.register(1, "request", start).unwrap());
+
+        assert!(pending
+            .handle_expired(start + Duration::from_secs(1))
+            .is_empty());
+        assert!(pending.is_empty());
+    }
+}
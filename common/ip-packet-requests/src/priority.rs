@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+// The average delay, in milliseconds, at each mix node for the high priority messages and their
+// responses. It's lower than the default, so that setting up and tearing down sessions doesn't
+// get stuck behind the bulk data under load.
+pub const HIGH_PRIORITY_AVG_MIX_DELAY_MS: f64 = 10.0;
+
+// Advisory hint about how urgently a control message, such as a connect or disconnect request,
+// should be delivered. It's part of the v7 protocol only: neither the clients nor the ip packet
+// router schedule anything based on it yet, so it doesn't affect how the messages are sent.
+// It's never meant for the data messages: had they been sent with distinct delays, the timing of
+// the tunnelled traffic would stand out in the mixnet.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Priority {
+    #[default]
+    Normal,
+    High,
+}
+
+impl Priority {
+    // The average delay, in milliseconds, at each mix node the message should be sent with, or
+    // `None` if it should use the default of the client
+    pub fn avg_mix_delay_ms(&self) -> Option<f64> {
+        match self {
+            Priority::Normal => None,
+            Priority::High => Some(HIGH_PRIORITY_AVG_MIX_DELAY_MS),
+        }
+    }
+}
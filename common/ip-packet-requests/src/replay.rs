@@ -0,0 +1,154 @@
+// Replay protection of the data requests. The mixnet can deliver the same sphinx message more
+// than once, and injecting the same ip packets twice confuses the TCP stacks on either side and
+// inflates the bandwidth accounting. The clients number their data requests within the session,
+// and the receiving side remembers which of the recent sequence numbers it has already seen.
+//
+// Only the building blocks of the v7 protocol live here: the ip packet router still speaks v6,
+// which has no sequence numbers, so it doesn't apply a replay window to the traffic yet.
+
+// The number of sequence numbers behind the highest one seen that are still tracked. The mixnet
+// reorders the messages, so the window has to be large enough to accommodate the delays.
+pub const REPLAY_WINDOW_SIZE: u64 = 1024;
+
+const WORD_BITS: u64 = u64::BITS as u64;
+const WINDOW_WORDS: usize = (REPLAY_WINDOW_SIZE / WORD_BITS) as usize;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayCheck {
+    // The sequence number hasn't been seen before, the packets should be injected
+    Accepted,
+
+    // The sequence number has already been seen, so this is a duplicated delivery
+    Duplicate,
+
+    // The sequence number is too far behind the highest one seen to tell whether it's a
+    // duplicate, so it's dropped to be on the safe side
+    TooOld,
+}
+
+impl ReplayCheck {
+    pub fn is_accepted(&self) -> bool {
+        matches!(self, ReplayCheck::Accepted)
+    }
+}
+
+// Sliding window over the sequence numbers of a single session, in the spirit of the one
+// described in RFC 6479. Requests without a sequence number bypass it altogether.
+#[derive(Debug, Clone)]
+pub struct ReplayWindow {
+    // The highest sequence number seen so far, if any
+    highest: Option<u64>,
+
+    // Bitmap of the seen sequence numbers, indexed by the sequence number modulo the window size
+    bitmap: [u64; WINDOW_WORDS],
+}
+
+impl Default for ReplayWindow {
+    fn default() -> Self {
+        ReplayWindow::new()
+    }
+}
+
+impl ReplayWindow {
+    pub fn new() -> Self {
+        ReplayWindow {
+            highest: None,
+            bitmap: [0; WINDOW_WORDS],
+        }
+    }
+
+    fn bit(sequence: u64) -> (usize, u64) {
+        let index = sequence % REPLAY_WINDOW_SIZE;
+        ((index / WORD_BITS) as usize, 1 << (index % WORD_BITS))
+    }
+
+    fn clear(&mut self, sequence: u64) {
+        let (word, mask) = Self::bit(sequence);
+        self.bitmap[word] &= !mask;
+    }
+
+    // Check whether the request with the given sequence number should be accepted and, if so,
+    // mark it as seen
+    pub fn check_and_update(&mut self, sequence: u64) -> ReplayCheck {
+        match self.highest {
+            Some(highest) if sequence > highest => {
+                // slide the window forward, forgetting the sequence numbers that fell out of it
+                if sequence - highest >= REPLAY_WINDOW_SIZE {
+                    self.bitmap = [0; WINDOW_WORDS];
+                } else {
+                    for skipped in highest + 1..=sequence {
+                        self.clear(skipped)
+                    }
+                }
+                self.highest = Some(sequence);
+            }
+            Some(highest) if highest - sequence >= REPLAY_WINDOW_SIZE => {
+                return ReplayCheck::TooOld;
+            }
+            Some(_) => {}
+            None => self.highest = Some(sequence),
+        }
+
+        let (word, mask) = Self::bit(sequence);
+        if self.bitmap[word] & mask != 0 {
+            return ReplayCheck::Duplicate;
+        }
+        self.bitmap[word] |= mask;
+        ReplayCheck::Accepted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicates_are_rejected() {
+        let mut window = ReplayWindow::new();
+        assert_eq!(window.check_and_update(5), ReplayCheck::Accepted);
+        assert_eq!(window.check_and_update(5), ReplayCheck::Duplicate);
+        assert_eq!(window.check_and_update(6), ReplayCheck::Accepted);
+        assert_eq!(window.check_and_update(5), ReplayCheck::Duplicate);
+    }
+
+    #[test]
+    fn reordered_requests_within_the_window_are_accepted() {
+        let mut window = ReplayWindow::new();
+        assert!(window.check_and_update(100).is_accepted());
+        assert!(window.check_and_update(98).is_accepted());
+        assert!(window.check_and_update(99).is_accepted());
+        assert!(window.check_and_update(0).is_accepted());
+        assert_eq!(window.check_and_update(98), ReplayCheck::Duplicate);
+    }
+
+    #[test]
+    fn requests_behind_the_window_are_rejected() {
+        let mut window = ReplayWindow::new();
+        assert!(window.check_and_update(10).is_accepted());
+        assert!(window
+            .check_and_update(10 + REPLAY_WINDOW_SIZE)
+            .is_accepted());
+        assert_eq!(window.check_and_update(10), ReplayCheck::TooOld);
+        assert!(window.check_and_update(11).is_accepted());
+    }
+
+    #[test]
+    fn sliding_the_window_forgets_old_sequence_numbers() {
+        let mut window = ReplayWindow::new();
+        assert!(window.check_and_update(1).is_accepted());
+        // lands on the same bit as the first one
+        assert!(window
+            .check_and_update(1 + REPLAY_WINDOW_SIZE)
+            .is_accepted());
+        assert!(window
+            .check_and_update(3 + 3 * REPLAY_WINDOW_SIZE)
+            .is_accepted());
+        assert!(window
+            .check_and_update(2 + 3 * REPLAY_WINDOW_SIZE)
+            .is_accepted());
+        assert_eq!(
+            window.check_and_update(3 + 3 * REPLAY_WINDOW_SIZE),
+            ReplayCheck::Duplicate
+        );
+    }
+}
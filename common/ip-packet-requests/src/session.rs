@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Debug, Formatter};
+
+pub const SESSION_TOKEN_LEN: usize = 32;
+
+// Secret issued to the client in the v7 connect response. Since the reply address of the client
+// is not bound to the session in any other way, presenting the token is what allows the client to
+// take over its existing session from a different nym-address, for example after switching its
+// entry gateway. This is a wire type only so far: the ip packet router still speaks v6, so it
+// doesn't issue the tokens nor handle the session moves, and roaming clients have to reconnect.
+#[derive(Copy, Clone, Eq, Serialize, Deserialize)]
+pub struct SessionToken(pub [u8; SESSION_TOKEN_LEN]);
+
+impl SessionToken {
+    pub fn generate() -> Self {
+        use rand::RngCore;
+        let mut rng = rand::rngs::OsRng;
+        let mut token = [0u8; SESSION_TOKEN_LEN];
+        rng.fill_bytes(&mut token);
+        SessionToken(token)
+    }
+}
+
+// Compare the tokens in constant time, so that the router doesn't leak how much of the presented
+// token matched the one it has issued
+impl PartialEq for SessionToken {
+    fn eq(&self, other: &Self) -> bool {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(0, |acc, (a, b)| acc | (a ^ b))
+            == 0
+    }
+}
+
+// Don't leak the token into the logs
+impl Debug for SessionToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SessionToken(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_tokens_are_distinct() {
+        let token = SessionToken::generate();
+        let copy = SessionToken(token.0);
+        assert_eq!(token, copy);
+        assert_ne!(token, SessionToken::generate());
+    }
+
+    #[test]
+    fn tokens_are_not_logged() {
+        let token = SessionToken([0xab; SESSION_TOKEN_LEN]);
+        assert_eq!(format!("{token:?}"), "SessionToken(<redacted>)");
+    }
+}
@@ -0,0 +1,154 @@
+use bytes::Bytes;
+use nym_sphinx::anonymous_replies::{ReplySurb, ReplySurbError};
+
+// The number of reply SURBs the router keeps for a client below which it warns the client that
+// it's running low and asks for more.
+pub const DEFAULT_LOW_SURB_THRESHOLD: u32 = 50;
+
+// The number of reply SURBs the router asks the client to top it up to.
+pub const DEFAULT_TARGET_SURBS: u32 = 200;
+
+// The maximum number of reply SURBs that can be supplied in a single request, so that it still
+// fits in a reasonable number of mix packets.
+pub const MAX_SURBS_PER_REQUEST: usize = 100;
+
+#[derive(thiserror::Error, Debug)]
+pub enum ReplySurbsError {
+    #[error("reply surb {index} is truncated: got {len} bytes")]
+    TruncatedSurb { index: usize, len: usize },
+
+    #[error("reply surb {index} is malformed: {source}")]
+    MalformedSurb {
+        index: usize,
+        #[source]
+        source: ReplySurbError,
+    },
+
+    #[error("too many reply surbs supplied at once: {count}, the maximum is {max}", max = MAX_SURBS_PER_REQUEST)]
+    TooManySurbs { count: usize },
+}
+
+pub fn encode_reply_surbs(reply_surbs: &[ReplySurb]) -> Vec<Bytes> {
+    reply_surbs
+        .iter()
+        .map(|surb| Bytes::from(surb.to_bytes()))
+        .collect()
+}
+
+pub fn decode_reply_surbs(encoded: &[Bytes]) -> Result<Vec<ReplySurb>, ReplySurbsError> {
+    if encoded.len() > MAX_SURBS_PER_REQUEST {
+        return Err(ReplySurbsError::TooManySurbs {
+            count: encoded.len(),
+        });
+    }
+
+    // even a surb with no mix hops can't be shorter than that
+    let min_len = ReplySurb::serialized_len(0);
+    encoded
+        .iter()
+        .enumerate()
+        .map(|(index, bytes)| {
+            if bytes.len() < min_len {
+                return Err(ReplySurbsError::TruncatedSurb {
+                    index,
+                    len: bytes.len(),
+                });
+            }
+            ReplySurb::from_bytes(bytes)
+                .map_err(|source| ReplySurbsError::MalformedSurb { index, source })
+        })
+        .collect()
+}
+
+// Keeps track of the reply SURBs the router holds for a single client, so that it could warn the
+// client before it runs out of them, rather than silently stalling the session.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplySurbStock {
+    available: u32,
+    low_threshold: u32,
+    target: u32,
+
+    // whether the client has already been warned since it last supplied any surbs
+    warned: bool,
+}
+
+impl Default for ReplySurbStock {
+    fn default() -> Self {
+        ReplySurbStock::new(DEFAULT_LOW_SURB_THRESHOLD, DEFAULT_TARGET_SURBS)
+    }
+}
+
+impl ReplySurbStock {
+    pub fn new(low_threshold: u32, target: u32) -> Self {
+        ReplySurbStock {
+            available: 0,
+            low_threshold,
+            target: target.max(low_threshold),
+            warned: false,
+        }
+    }
+
+    pub fn available(&self) -> u32 {
+        self.available
+    }
+
+    pub fn supplied(&mut self, count: u32) {
+        self.available = self.available.saturating_add(count);
+        if self.available >= self.low_threshold {
+            self.warned = false;
+        }
+    }
+
+    // Record a surb being used up. Returns the number of surbs the client should be asked to
+    // supply, if it's the first time the stock dropped below the threshold since the last top up.
+    pub fn used(&mut self) -> Option<u32> {
+        self.available = self.available.saturating_sub(1);
+        if self.available < self.low_threshold && !self.warned {
+            self.warned = true;
+            Some(self.target - self.available)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn warns_once_when_running_low() {
+        let mut stock = ReplySurbStock::new(2, 5);
+        stock.supplied(3);
+
+        assert_eq!(stock.used(), None);
+        assert_eq!(stock.used(), Some(4));
+        assert_eq!(stock.used(), None);
+        assert_eq!(stock.available(), 0);
+        assert_eq!(stock.used(), None);
+
+        // a partial top up that doesn't get it above the threshold doesn't reset the warning
+        stock.supplied(1);
+        assert_eq!(stock.used(), None);
+
+        stock.supplied(4);
+        assert_eq!(stock.used(), None);
+        assert_eq!(stock.used(), None);
+        assert_eq!(stock.used(), Some(4));
+    }
+
+    #[test]
+    fn rejects_malformed_surbs() {
+        assert!(matches!(
+            decode_reply_surbs(&[Bytes::from_static(&[1, 2, 3])]),
+            Err(ReplySurbsError::TruncatedSurb { index: 0, len: 3 })
+        ));
+
+        let too_many = vec![Bytes::new(); MAX_SURBS_PER_REQUEST + 1];
+        assert!(matches!(
+            decode_reply_surbs(&too_many),
+            Err(ReplySurbsError::TooManySurbs { .. })
+        ));
+        assert!(decode_reply_surbs(&[]).unwrap().is_empty());
+    }
+}
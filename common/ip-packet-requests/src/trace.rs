@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+
+// Identifier attached by the client to its requests and echoed back by the router in the
+// corresponding responses, so that the lifecycle of the packets can be followed across the logs
+// of both sides.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TraceId(pub u64);
+
+impl TraceId {
+    pub fn generate() -> Self {
+        use rand::RngCore;
+        let mut rng = rand::rngs::OsRng;
+        TraceId(rng.next_u64())
+    }
+}
+
+impl Display for TraceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+// Helper for including optional trace ids in log messages, i.e.
+// `log::debug!("[{}] received data request", DisplayTraceId(request.trace_id))`
+pub struct DisplayTraceId(pub Option<TraceId>);
+
+impl Display for DisplayTraceId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            Some(trace_id) => write!(f, "trace:{trace_id}"),
+            None => write!(f, "trace:-"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trace_ids_are_displayed_as_fixed_width_hex() {
+        assert_eq!(TraceId(0xabc).to_string(), "0000000000000abc");
+        assert_eq!(
+            DisplayTraceId(Some(TraceId(u64::MAX))).to_string(),
+            "trace:ffffffffffffffff"
+        );
+        assert_eq!(DisplayTraceId(None).to_string(), "trace:-");
+    }
+}
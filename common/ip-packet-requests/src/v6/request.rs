@@ -62,8 +62,11 @@ impl IpPacketRequest {
         )
     }
 
-    pub fn new_disconnect_request(reply_to: Recipient) -> (Self, u64) {
-        let request_id = generate_random();
+    // The disconnect has to carry the id of the connect request that established the session, as
+    // that's what tells the ip packet router it's sent by the connected client rather than by
+    // anyone else claiming its nym address.
+    pub fn new_disconnect_request(connect_request_id: u64, reply_to: Recipient) -> (Self, u64) {
+        let request_id = connect_request_id;
         (
             Self {
                 version: CURRENT_VERSION,
@@ -207,6 +210,7 @@ pub struct DynamicConnectRequest {
 // up the allocated IP address.
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DisconnectRequest {
+    // The id of the connect request that established the session being closed
     pub request_id: u64,
     // The nym-address the response should be sent back to
     pub reply_to: Recipient,
@@ -0,0 +1,337 @@
+// Manual framing of the data requests and responses, which make up the vast majority of the
+// traffic exchanged with the router. Unlike bincode, it doesn't copy the ip packets when decoding:
+// they're sliced out of the received buffer. All other messages are still encoded with bincode.
+//
+// Layout of a data frame:
+// version (1 byte) | DATA_FRAME_TAG (1 byte) | flags (1 byte) | optional fields | ip packets
+//
+// The optional fields are present in the order of the flags below, with the integers encoded as
+// big-endian u64 and the compression as a single byte. The ip packets take the rest of the frame.
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::request::{DataRequest, IpPacketRequest, IpPacketRequestData};
+use super::response::{DataResponse, IpPacketResponse, IpPacketResponseData};
+use crate::compression::Compression;
+use crate::make_bincode_serializer;
+use crate::trace::TraceId;
+
+// bincode encodes the enum discriminants as varints, which never start with this byte, so a data
+// frame can't be mistaken for a bincode encoded message
+pub const DATA_FRAME_TAG: u8 = 0xff;
+
+const HEADER_LEN: usize = 3;
+
+const FLAG_CONNECTION_ID: u8 = 0b0001;
+const FLAG_TRACE_ID: u8 = 0b0010;
+const FLAG_COMPRESSION: u8 = 0b0100;
+const FLAG_SEQUENCE: u8 = 0b1000;
+const KNOWN_FLAGS: u8 = FLAG_CONNECTION_ID | FLAG_TRACE_ID | FLAG_COMPRESSION | FLAG_SEQUENCE;
+
+const COMPRESSION_LZ4: u8 = 0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum FramingError {
+    #[error("the data frame is truncated")]
+    TruncatedFrame,
+
+    #[error("the data frame has unknown flags set: {flags:#06b}")]
+    UnknownFlags { flags: u8 },
+
+    #[error("the data frame uses unknown compression: {compression}")]
+    UnknownCompression { compression: u8 },
+
+    #[error("the data frame fields are not valid for this kind of message")]
+    UnexpectedField,
+
+    #[error("failed to deserialize the message: {0}")]
+    Bincode(#[from] bincode::Error),
+}
+
+#[derive(Default)]
+struct DataFrameFields {
+    connection_id: Option<u64>,
+    trace_id: Option<TraceId>,
+    compression: Option<Compression>,
+    sequence: Option<u64>,
+}
+
+impl DataFrameFields {
+    fn encode(&self, version: u8, payload: &Bytes) -> Bytes {
+        let mut flags = 0;
+        let mut optional = BytesMut::new();
+        if let Some(connection_id) = self.connection_id {
+            flags |= FLAG_CONNECTION_ID;
+            optional.put_u64(connection_id);
+        }
+        if let Some(trace_id) = self.trace_id {
+            flags |= FLAG_TRACE_ID;
+            optional.put_u64(trace_id.0);
+        }
+        if let Some(compression) = self.compression {
+            flags |= FLAG_COMPRESSION;
+            optional.put_u8(match compression {
+                Compression::Lz4 => COMPRESSION_LZ4,
+            });
+        }
+        if let Some(sequence) = self.sequence {
+            flags |= FLAG_SEQUENCE;
+            optional.put_u64(sequence);
+        }
+
+        let mut frame = BytesMut::with_capacity(HEADER_LEN + optional.len() + payload.len());
+        frame.put_u8(version);
+        frame.put_u8(DATA_FRAME_TAG);
+        frame.put_u8(flags);
+        frame.put_slice(&optional);
+        frame.put_slice(payload);
+        frame.freeze()
+    }
+
+    // Parse the fields following the header, leaving only the payload in the frame
+    fn decode(flags: u8, frame: &mut Bytes) -> Result<Self, FramingError> {
+        if flags & !KNOWN_FLAGS != 0 {
+            return Err(FramingError::UnknownFlags { flags });
+        }
+
+        let read_u64 = |flag: u8, frame: &mut Bytes| -> Result<Option<u64>, FramingError> {
+            if flags & flag == 0 {
+                return Ok(None);
+            }
+            if frame.remaining() < 8 {
+                return Err(FramingError::TruncatedFrame);
+            }
+            Ok(Some(frame.get_u64()))
+        };
+        let connection_id = read_u64(FLAG_CONNECTION_ID, frame)?;
+        let trace_id = read_u64(FLAG_TRACE_ID, frame)?.map(TraceId);
+
+        let compression = if flags & FLAG_COMPRESSION != 0 {
+            if !frame.has_remaining() {
+                return Err(FramingError::TruncatedFrame);
+            }
+            match frame.get_u8() {
+                COMPRESSION_LZ4 => Some(Compression::Lz4),
+                compression => return Err(FramingError::UnknownCompression { compression }),
+            }
+        } else {
+            None
+        };
+        let sequence = read_u64(FLAG_SEQUENCE, frame)?;
+
+        Ok(DataFrameFields {
+            connection_id,
+            trace_id,
+            compression,
+            sequence,
+        })
+    }
+}
+
+// Split the header off the frame, returning the version and the flags of the data frame,
+// or `None` if it's a bincode encoded message instead
+fn split_data_frame_header(frame: &mut Bytes) -> Result<Option<(u8, u8)>, FramingError> {
+    if frame.len() < 2 || frame[1] != DATA_FRAME_TAG {
+        return Ok(None);
+    }
+    if frame.len() < HEADER_LEN {
+        return Err(FramingError::TruncatedFrame);
+    }
+    let version = frame.get_u8();
+    frame.advance(1);
+    let flags = frame.get_u8();
+    Ok(Some((version, flags)))
+}
+
+impl IpPacketRequest {
+    // Encode the request, using the manual framing for data requests and bincode otherwise
+    pub fn to_frame(&self) -> Result<Bytes, bincode::Error> {
+        match &self.data {
+            IpPacketRequestData::Data(request) => {
+                let fields = DataFrameFields {
+                    connection_id: request.connection_id,
+                    trace_id: request.trace_id,
+                    compression: request.compression,
+                    sequence: request.sequence,
+                };
+                Ok(fields.encode(self.version, &request.ip_packets))
+            }
+            _ => self.to_bytes().map(Into::into),
+        }
+    }
+
+    // Decode the request produced by `to_frame`. The ip packets of data requests are sliced out
+    // of the frame without being copied.
+    pub fn from_frame(mut frame: Bytes) -> Result<Self, FramingError> {
+        use bincode::Options;
+
+        let Some((version, flags)) = split_data_frame_header(&mut frame)? else {
+            return Ok(make_bincode_serializer().deserialize(&frame)?);
+        };
+
+        let fields = DataFrameFields::decode(flags, &mut frame)?;
+        Ok(IpPacketRequest {
+            version,
+            data: IpPacketRequestData::Data(DataRequest {
+                ip_packets: frame,
+                connection_id: fields.connection_id,
+                compression: fields.compression,
+                trace_id: fields.trace_id,
+                sequence: fields.sequence,
+            }),
+            priority: None,
+        })
+    }
+}
+
+impl IpPacketResponse {
+    // Encode the response, using the manual framing for data responses and bincode otherwise
+    pub fn to_frame(&self) -> Result<Bytes, bincode::Error> {
+        match &self.data {
+            IpPacketResponseData::Data(response) => {
+                let fields = DataFrameFields {
+                    connection_id: response.connection_id,
+                    trace_id: response.trace_id,
+                    compression: response.compression,
+                    sequence: None,
+                };
+                Ok(fields.encode(self.version, &response.ip_packet))
+            }
+            _ => self.to_bytes().map(Into::into),
+        }
+    }
+
+    // Decode the response produced by `to_frame`. The ip packets of data responses are sliced out
+    // of the frame without being copied.
+    pub fn from_frame(mut frame: Bytes) -> Result<Self, FramingError> {
+        use bincode::Options;
+
+        let Some((version, flags)) = split_data_frame_header(&mut frame)? else {
+            return Ok(make_bincode_serializer().deserialize(&frame)?);
+        };
+
+        let fields = DataFrameFields::decode(flags, &mut frame)?;
+        if fields.sequence.is_some() {
+            return Err(FramingError::UnexpectedField);
+        }
+        Ok(IpPacketResponse {
+            version,
+            data: IpPacketResponseData::Data(DataResponse {
+                ip_packet: frame,
+                connection_id: fields.connection_id,
+                compression: fields.compression,
+                trace_id: fields.trace_id,
+            }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::v7::request::tests::recipient;
+
+    #[test]
+    fn data_request_frame_roundtrip() {
+        let packets = Bytes::from(vec![42u8; 100]);
+        let request = IpPacketRequest::new_data_request_on_connection(packets.clone(), 1234)
+            .with_trace_id(TraceId(5678));
+
+        let frame = request.to_frame().unwrap();
+        assert_eq!(frame.len(), HEADER_LEN + 16 + packets.len());
+
+        let decoded = IpPacketRequest::from_frame(frame.clone()).unwrap();
+        assert_eq!(decoded.version, request.version);
+        assert_eq!(decoded.data, request.data);
+
+        // the packets point into the original frame rather than being copied
+        let IpPacketRequestData::Data(data) = decoded.data else {
+            panic!("expected data request");
+        };
+        assert_eq!(
+            data.ip_packets.as_ptr(),
+            frame[frame.len() - packets.len()..].as_ptr()
+        );
+    }
+
+    #[test]
+    fn data_request_sequence_frame_roundtrip() {
+        let request = IpPacketRequest::new_compressed_data_request(
+            Bytes::from(vec![7u8; 1000]),
+            Some(Compression::Lz4),
+        )
+        .with_sequence(42);
+
+        let decoded = IpPacketRequest::from_frame(request.to_frame().unwrap()).unwrap();
+        assert_eq!(decoded.data, request.data);
+        let IpPacketRequestData::Data(data) = decoded.data else {
+            panic!("expected data request");
+        };
+        assert_eq!(data.sequence, Some(42));
+        assert_eq!(data.compression, Some(Compression::Lz4));
+    }
+
+    #[test]
+    fn compressed_data_response_frame_roundtrip() {
+        let response = IpPacketResponse::new_compressed_ip_packet(
+            Bytes::from(vec![7u8; 1000]),
+            Some(Compression::Lz4),
+        );
+        let decoded = IpPacketResponse::from_frame(response.to_frame().unwrap()).unwrap();
+
+        let (IpPacketResponseData::Data(original), IpPacketResponseData::Data(decoded)) =
+            (response.data, decoded.data)
+        else {
+            panic!("expected data responses");
+        };
+        assert_eq!(decoded.compression, Some(Compression::Lz4));
+        assert_eq!(decoded.ip_packet, original.ip_packet);
+        assert_eq!(
+            decoded.decompressed_packets().unwrap(),
+            Bytes::from(vec![7u8; 1000])
+        );
+    }
+
+    #[test]
+    fn control_messages_are_still_bincode_encoded() {
+        let (request, _) = IpPacketRequest::new_ping(recipient());
+
+        let frame = request.to_frame().unwrap();
+        assert_eq!(frame.as_ref(), request.to_bytes().unwrap().as_slice());
+        assert_eq!(
+            IpPacketRequest::from_frame(frame).unwrap().data,
+            request.data
+        );
+    }
+
+    #[test]
+    fn malformed_data_frames_are_rejected() {
+        let version = crate::CURRENT_VERSION;
+        assert!(matches!(
+            IpPacketRequest::from_frame(Bytes::from(vec![version, DATA_FRAME_TAG])),
+            Err(FramingError::TruncatedFrame)
+        ));
+        assert!(matches!(
+            IpPacketRequest::from_frame(Bytes::from(vec![
+                version,
+                DATA_FRAME_TAG,
+                FLAG_CONNECTION_ID,
+                1
+            ])),
+            Err(FramingError::TruncatedFrame)
+        ));
+        assert!(matches!(
+            IpPacketRequest::from_frame(Bytes::from(vec![version, DATA_FRAME_TAG, 0x80])),
+            Err(FramingError::UnknownFlags { .. })
+        ));
+        assert!(matches!(
+            IpPacketRequest::from_frame(Bytes::from(vec![
+                version,
+                DATA_FRAME_TAG,
+                FLAG_COMPRESSION,
+                42
+            ])),
+            Err(FramingError::UnknownCompression { compression: 42 })
+        ));
+    }
+}
@@ -1,2 +1,3 @@
+pub mod framing;
 pub mod request;
 pub mod response;
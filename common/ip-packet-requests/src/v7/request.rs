@@ -1,7 +1,14 @@
 use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::anonymous_replies::ReplySurb;
 use serde::{Deserialize, Serialize};
+use std::net::SocketAddr;
 use time::OffsetDateTime;
 
+use crate::compression::{Compression, CompressionError};
+use crate::priority::Priority;
+use crate::session::SessionToken;
+use crate::surbs::{decode_reply_surbs, encode_reply_surbs, ReplySurbsError};
+use crate::trace::TraceId;
 use crate::{make_bincode_serializer, IpPair, CURRENT_VERSION};
 
 fn generate_random() -> u64 {
@@ -14,6 +21,11 @@ fn generate_random() -> u64 {
 pub struct IpPacketRequest {
     pub version: u8,
     pub data: IpPacketRequestData,
+
+    // Optional, advisory hint about how urgently the request should be delivered. It's only
+    // meaningful for the control messages, see `IpPacketRequest::priority`. The router doesn't
+    // read it yet.
+    pub priority: Option<Priority>,
 }
 
 impl IpPacketRequest {
@@ -23,6 +35,8 @@ impl IpPacketRequest {
         reply_to_hops: Option<u8>,
         reply_to_avg_mix_delays: Option<f64>,
         buffer_timeout: Option<u64>,
+        compression: Option<Compression>,
+        keepalive_interval: Option<u64>,
     ) -> (Self, u64) {
         let request_id = generate_random();
         (
@@ -36,10 +50,14 @@ impl IpPacketRequest {
                         reply_to_hops,
                         reply_to_avg_mix_delays,
                         buffer_timeout,
+                        compression,
+                        keepalive_interval,
+                        trace_id: None,
                         timestamp: OffsetDateTime::now_utc(),
                     },
                     signature: None,
                 }),
+                priority: None,
             },
             request_id,
         )
@@ -50,6 +68,8 @@ impl IpPacketRequest {
         reply_to_hops: Option<u8>,
         reply_to_avg_mix_delays: Option<f64>,
         buffer_timeout: Option<u64>,
+        compression: Option<Compression>,
+        keepalive_interval: Option<u64>,
     ) -> (Self, u64) {
         let request_id = generate_random();
         (
@@ -62,10 +82,14 @@ impl IpPacketRequest {
                         reply_to_hops,
                         reply_to_avg_mix_delays,
                         buffer_timeout,
+                        compression,
+                        keepalive_interval,
+                        trace_id: None,
                         timestamp: OffsetDateTime::now_utc(),
                     },
                     signature: None,
                 }),
+                priority: None,
             },
             request_id,
         )
@@ -84,6 +108,7 @@ impl IpPacketRequest {
                     },
                     signature: None,
                 }),
+                priority: None,
             },
             request_id,
         )
@@ -92,7 +117,49 @@ impl IpPacketRequest {
     pub fn new_data_request(ip_packets: bytes::Bytes) -> Self {
         Self {
             version: CURRENT_VERSION,
-            data: IpPacketRequestData::Data(DataRequest { ip_packets }),
+            data: IpPacketRequestData::Data(DataRequest {
+                ip_packets,
+                connection_id: None,
+                compression: None,
+                trace_id: None,
+                sequence: None,
+            }),
+            priority: None,
+        }
+    }
+
+    pub fn new_data_request_on_connection(ip_packets: bytes::Bytes, connection_id: u64) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::Data(DataRequest {
+                ip_packets,
+                connection_id: Some(connection_id),
+                compression: None,
+                trace_id: None,
+                sequence: None,
+            }),
+            priority: None,
+        }
+    }
+
+    // Create a data request with the packets compressed using the negotiated compression,
+    // if that's worthwhile for their size.
+    pub fn new_compressed_data_request(
+        ip_packets: bytes::Bytes,
+        compression: Option<Compression>,
+    ) -> Self {
+        let (ip_packets, compression) =
+            Compression::compress_if_beneficial(compression, ip_packets);
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::Data(DataRequest {
+                ip_packets,
+                connection_id: None,
+                compression,
+                trace_id: None,
+                sequence: None,
+            }),
+            priority: None,
         }
     }
 
@@ -106,6 +173,7 @@ impl IpPacketRequest {
                     reply_to,
                     timestamp: OffsetDateTime::now_utc(),
                 }),
+                priority: None,
             },
             request_id,
         )
@@ -121,11 +189,237 @@ impl IpPacketRequest {
                     reply_to,
                     timestamp: OffsetDateTime::now_utc(),
                 }),
+                priority: None,
             },
             request_id,
         )
     }
 
+    pub fn new_info_request(reply_to: Recipient) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::Info(InfoRequest {
+                    request_id,
+                    reply_to,
+                    timestamp: OffsetDateTime::now_utc(),
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    pub fn new_stats_request(reply_to: Recipient) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::Stats(StatsRequest {
+                    request_id,
+                    reply_to,
+                    timestamp: OffsetDateTime::now_utc(),
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    pub fn new_heartbeat(reply_to: Recipient) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::Heartbeat(HeartbeatRequest {
+                    request_id,
+                    reply_to,
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    pub fn new_open_stream_request(
+        stream_id: u64,
+        destination: SocketAddr,
+        reply_to: Recipient,
+    ) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::OpenStream(OpenStreamRequest {
+                    request_id,
+                    stream_id,
+                    destination,
+                    reply_to,
+                    timestamp: OffsetDateTime::now_utc(),
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    pub fn new_stream_data_request(stream_id: u64, sequence: u64, data: bytes::Bytes) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::StreamData(StreamDataRequest {
+                stream_id,
+                sequence,
+                data,
+            }),
+            priority: None,
+        }
+    }
+
+    pub fn new_close_stream_request(stream_id: u64, sequence: u64) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::CloseStream(CloseStreamRequest {
+                stream_id,
+                sequence,
+            }),
+            priority: None,
+        }
+    }
+
+    pub fn new_supply_reply_surbs_request(reply_surbs: &[ReplySurb]) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::SupplyReplySurbs(SupplyReplySurbsRequest {
+                reply_surbs: encode_reply_surbs(reply_surbs),
+            }),
+            priority: None,
+        }
+    }
+
+    // Create a request for switching the reply address of the already established session to
+    // `reply_to`, for example after switching to a different entry gateway. The allocated ips and
+    // the session state are kept.
+    pub fn new_update_reply_address_request(
+        session_token: SessionToken,
+        reply_to: Recipient,
+    ) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::UpdateReplyAddress(UpdateReplyAddressRequest {
+                    request_id,
+                    session_token,
+                    reply_to,
+                    timestamp: OffsetDateTime::now_utc(),
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    // Create a request for extending the bandwidth allowance of the session with the provided
+    // serialized bandwidth credential
+    pub fn new_top_up_bandwidth_request(credential: Vec<u8>, reply_to: Recipient) -> (Self, u64) {
+        let request_id = generate_random();
+        (
+            Self {
+                version: CURRENT_VERSION,
+                data: IpPacketRequestData::TopUpBandwidth(TopUpBandwidthRequest {
+                    request_id,
+                    credential,
+                    reply_to,
+                    timestamp: OffsetDateTime::now_utc(),
+                }),
+                priority: None,
+            },
+            request_id,
+        )
+    }
+
+    // Mark the request with the priority it should be delivered with. It has no effect on the
+    // requests carrying the tunnelled traffic. It's only a hint carried with the request: it's up
+    // to the sender to use `avg_mix_delay_ms` when sending it, and the router ignores it for now.
+    pub fn with_priority(mut self, priority: Priority) -> Self {
+        if !self.data.is_traffic() {
+            self.priority = Some(priority)
+        }
+        self
+    }
+
+    // The priority the request should be delivered with. The requests carrying the tunnelled
+    // traffic always have the normal priority, whatever they claim, so that they can't be told
+    // apart by their timing.
+    pub fn priority(&self) -> Priority {
+        if self.data.is_traffic() {
+            return Priority::Normal;
+        }
+        self.priority.unwrap_or_default()
+    }
+
+    // The average delay, in milliseconds, at each mix node the request and the response to it
+    // should be sent with, or `None` if the default of the client should be used
+    pub fn avg_mix_delay_ms(&self) -> Option<f64> {
+        self.priority().avg_mix_delay_ms()
+    }
+
+    // Attach the session sequence number to the data request, so that duplicated deliveries can
+    // be told apart with a `ReplayWindow`. It has no effect on the other kinds of requests.
+    pub fn with_sequence(mut self, sequence: u64) -> Self {
+        if let IpPacketRequestData::Data(request) = &mut self.data {
+            request.sequence = Some(sequence)
+        }
+        self
+    }
+
+    // Attach the trace id to the request, if it's of a kind that carries one
+    pub fn with_trace_id(mut self, trace_id: TraceId) -> Self {
+        match &mut self.data {
+            IpPacketRequestData::StaticConnect(request) => {
+                request.request.trace_id = Some(trace_id)
+            }
+            IpPacketRequestData::DynamicConnect(request) => {
+                request.request.trace_id = Some(trace_id)
+            }
+            IpPacketRequestData::Data(request) => request.trace_id = Some(trace_id),
+            IpPacketRequestData::Disconnect(_)
+            | IpPacketRequestData::Ping(_)
+            | IpPacketRequestData::Health(_)
+            | IpPacketRequestData::Info(_)
+            | IpPacketRequestData::Heartbeat(_)
+            | IpPacketRequestData::OpenStream(_)
+            | IpPacketRequestData::StreamData(_)
+            | IpPacketRequestData::CloseStream(_)
+            | IpPacketRequestData::SupplyReplySurbs(_)
+            | IpPacketRequestData::Stats(_)
+            | IpPacketRequestData::UpdateReplyAddress(_)
+            | IpPacketRequestData::TopUpBandwidth(_) => {}
+        }
+        self
+    }
+
+    pub fn trace_id(&self) -> Option<TraceId> {
+        match &self.data {
+            IpPacketRequestData::StaticConnect(request) => request.request.trace_id,
+            IpPacketRequestData::DynamicConnect(request) => request.request.trace_id,
+            IpPacketRequestData::Data(request) => request.trace_id,
+            IpPacketRequestData::Disconnect(_)
+            | IpPacketRequestData::Ping(_)
+            | IpPacketRequestData::Health(_)
+            | IpPacketRequestData::Info(_)
+            | IpPacketRequestData::Heartbeat(_)
+            | IpPacketRequestData::OpenStream(_)
+            | IpPacketRequestData::StreamData(_)
+            | IpPacketRequestData::CloseStream(_)
+            | IpPacketRequestData::SupplyReplySurbs(_)
+            | IpPacketRequestData::Stats(_)
+            | IpPacketRequestData::UpdateReplyAddress(_)
+            | IpPacketRequestData::TopUpBandwidth(_) => None,
+        }
+    }
+
     pub fn id(&self) -> Option<u64> {
         match &self.data {
             IpPacketRequestData::StaticConnect(request) => Some(request.request.request_id),
@@ -134,6 +428,15 @@ impl IpPacketRequest {
             IpPacketRequestData::Data(_) => None,
             IpPacketRequestData::Ping(request) => Some(request.request_id),
             IpPacketRequestData::Health(request) => Some(request.request_id),
+            IpPacketRequestData::Info(request) => Some(request.request_id),
+            IpPacketRequestData::Heartbeat(request) => Some(request.request_id),
+            IpPacketRequestData::OpenStream(request) => Some(request.request_id),
+            IpPacketRequestData::StreamData(_) => None,
+            IpPacketRequestData::CloseStream(_) => None,
+            IpPacketRequestData::SupplyReplySurbs(_) => None,
+            IpPacketRequestData::Stats(request) => Some(request.request_id),
+            IpPacketRequestData::UpdateReplyAddress(request) => Some(request.request_id),
+            IpPacketRequestData::TopUpBandwidth(request) => Some(request.request_id),
         }
     }
 
@@ -145,6 +448,15 @@ impl IpPacketRequest {
             IpPacketRequestData::Data(_) => None,
             IpPacketRequestData::Ping(request) => Some(&request.reply_to),
             IpPacketRequestData::Health(request) => Some(&request.reply_to),
+            IpPacketRequestData::Info(request) => Some(&request.reply_to),
+            IpPacketRequestData::Heartbeat(request) => Some(&request.reply_to),
+            IpPacketRequestData::OpenStream(request) => Some(&request.reply_to),
+            IpPacketRequestData::StreamData(_) => None,
+            IpPacketRequestData::CloseStream(_) => None,
+            IpPacketRequestData::SupplyReplySurbs(_) => None,
+            IpPacketRequestData::Stats(request) => Some(&request.reply_to),
+            IpPacketRequestData::UpdateReplyAddress(request) => Some(&request.reply_to),
+            IpPacketRequestData::TopUpBandwidth(request) => Some(&request.reply_to),
         }
     }
 
@@ -170,9 +482,39 @@ pub enum IpPacketRequestData {
     Data(DataRequest),
     Ping(PingRequest),
     Health(HealthRequest),
+    Info(InfoRequest),
+    Heartbeat(HeartbeatRequest),
+    OpenStream(OpenStreamRequest),
+    StreamData(StreamDataRequest),
+    CloseStream(CloseStreamRequest),
+    SupplyReplySurbs(SupplyReplySurbsRequest),
+    Stats(StatsRequest),
+    UpdateReplyAddress(UpdateReplyAddressRequest),
+    TopUpBandwidth(TopUpBandwidthRequest),
 }
 
 impl IpPacketRequestData {
+    // Whether the request carries the tunnelled traffic, rather than controlling the session
+    pub fn is_traffic(&self) -> bool {
+        match self {
+            IpPacketRequestData::Data(_)
+            | IpPacketRequestData::StreamData(_)
+            | IpPacketRequestData::CloseStream(_)
+            | IpPacketRequestData::SupplyReplySurbs(_) => true,
+            IpPacketRequestData::StaticConnect(_)
+            | IpPacketRequestData::DynamicConnect(_)
+            | IpPacketRequestData::Disconnect(_)
+            | IpPacketRequestData::Ping(_)
+            | IpPacketRequestData::Health(_)
+            | IpPacketRequestData::Info(_)
+            | IpPacketRequestData::Heartbeat(_)
+            | IpPacketRequestData::OpenStream(_)
+            | IpPacketRequestData::Stats(_)
+            | IpPacketRequestData::UpdateReplyAddress(_)
+            | IpPacketRequestData::TopUpBandwidth(_) => false,
+        }
+    }
+
     pub fn add_signature(&mut self, signature: Vec<u8>) -> Option<Vec<u8>> {
         match self {
             IpPacketRequestData::StaticConnect(request) => {
@@ -189,7 +531,16 @@ impl IpPacketRequestData {
             }
             IpPacketRequestData::Data(_)
             | IpPacketRequestData::Ping(_)
-            | IpPacketRequestData::Health(_) => None,
+            | IpPacketRequestData::Health(_)
+            | IpPacketRequestData::Info(_)
+            | IpPacketRequestData::Heartbeat(_)
+            | IpPacketRequestData::OpenStream(_)
+            | IpPacketRequestData::StreamData(_)
+            | IpPacketRequestData::CloseStream(_)
+            | IpPacketRequestData::SupplyReplySurbs(_)
+            | IpPacketRequestData::Stats(_)
+            | IpPacketRequestData::UpdateReplyAddress(_)
+            | IpPacketRequestData::TopUpBandwidth(_) => None,
         }
     }
 }
@@ -217,6 +568,18 @@ pub struct StaticConnectRequest {
     // with ip packets.
     pub buffer_timeout: Option<u64>,
 
+    // The compression the client would like to apply to the ip packets exchanged in data
+    // requests and responses. The router confirms it in the connect response if it supports it.
+    pub compression: Option<Compression>,
+
+    // The interval in seconds at which the client would like to exchange heartbeats with the
+    // router. The router confirms the interval it agreed to in the connect response.
+    pub keepalive_interval: Option<u64>,
+
+    // Optional identifier used for following the request across the logs of the client and the
+    // router. It's echoed back in the connect response.
+    pub trace_id: Option<TraceId>,
+
     // Timestamp of when the request was sent by the client.
     pub timestamp: OffsetDateTime,
 }
@@ -255,6 +618,18 @@ pub struct DynamicConnectRequest {
     // with ip packets.
     pub buffer_timeout: Option<u64>,
 
+    // The compression the client would like to apply to the ip packets exchanged in data
+    // requests and responses. The router confirms it in the connect response if it supports it.
+    pub compression: Option<Compression>,
+
+    // The interval in seconds at which the client would like to exchange heartbeats with the
+    // router. The router confirms the interval it agreed to in the connect response.
+    pub keepalive_interval: Option<u64>,
+
+    // Optional identifier used for following the request across the logs of the client and the
+    // router. It's echoed back in the connect response.
+    pub trace_id: Option<TraceId>,
+
     // Timestamp of when the request was sent by the client.
     pub timestamp: OffsetDateTime,
 }
@@ -302,6 +677,29 @@ pub struct SignedDisconnectRequest {
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct DataRequest {
     pub ip_packets: bytes::Bytes,
+
+    // Optional identifier of the logical tunnel the packets belong to. It allows multiplexing
+    // several tunnels over a single registered client address without performing additional
+    // connect handshakes. The router echoes it back in the corresponding data responses.
+    pub connection_id: Option<u64>,
+
+    // The compression that has been applied to the ip packets, if any
+    pub compression: Option<Compression>,
+
+    // Optional identifier used for following the packets across the logs of the client and the
+    // router. It's echoed back in the data responses carrying the replies, when known.
+    pub trace_id: Option<TraceId>,
+
+    // Optional sequence number of the request within the session, since the mixnet might deliver
+    // the same message more than once. It's meant to be checked against a `ReplayWindow`, but the
+    // ip packet router doesn't handle v7 requests yet, so nothing drops the duplicates for now.
+    pub sequence: Option<u64>,
+}
+
+impl DataRequest {
+    pub fn decompressed_packets(self) -> Result<bytes::Bytes, CompressionError> {
+        Compression::decompress(self.compression, self.ip_packets)
+    }
 }
 
 // A ping request is when the client wants to check if the ip packet router is still alive.
@@ -327,32 +725,180 @@ pub struct HealthRequest {
     pub timestamp: OffsetDateTime,
 }
 
+// An info request is when the client wants to learn about the capabilities of the ip packet
+// router, such as the supported protocol versions and the available capacity, before connecting.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct InfoRequest {
+    pub request_id: u64,
+
+    // The nym-address the response should be sent back to
+    pub reply_to: Recipient,
+
+    // Timestamp of when the request was sent by the client.
+    pub timestamp: OffsetDateTime,
+}
+
+// A stats request is when a connected client wants to learn the counters of its session on the
+// router, for example to tell apart the router dropping its packets from the destination being down.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StatsRequest {
+    pub request_id: u64,
+
+    // The nym-address the response should be sent back to
+    pub reply_to: Recipient,
+
+    // Timestamp of when the request was sent by the client.
+    pub timestamp: OffsetDateTime,
+}
+
+// Sent by a connected client roaming to a different nym-address, for example after switching its
+// entry gateway, to keep its allocated ips and session instead of reconnecting. Since the request
+// arrives from an address the router doesn't know yet, it's authenticated with the session token
+// issued in the connect response. Note that the router doesn't handle it yet.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct UpdateReplyAddressRequest {
+    pub request_id: u64,
+
+    // The token issued to the client when it connected
+    pub session_token: SessionToken,
+
+    // The new nym-address the responses and the data of the session should be sent to
+    pub reply_to: Recipient,
+
+    // Timestamp of when the request was sent by the client.
+    pub timestamp: OffsetDateTime,
+}
+
+// Sent by a connected client to extend the bandwidth allowance of its session, either ahead of time
+// or in response to a data limit warning. The router verifies and spends the credential itself, so
+// the allowance is enforced in-band rather than only by the gateway.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct TopUpBandwidthRequest {
+    pub request_id: u64,
+
+    // The serialized bandwidth credential
+    pub credential: Vec<u8>,
+
+    // The nym-address the response should be sent back to
+    pub reply_to: Recipient,
+
+    // Timestamp of when the request was sent by the client.
+    pub timestamp: OffsetDateTime,
+}
+
+// A heartbeat is periodically sent by connected clients, at the interval negotiated during the
+// connect handshake, so that both sides can promptly detect a dead session. It's kept as small as
+// possible as it's sent even when there's no other traffic.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct HeartbeatRequest {
+    pub request_id: u64,
+
+    // The nym-address the response should be sent back to
+    pub reply_to: Recipient,
+}
+
+// In stream mode, instead of sending raw ip packets, the client asks the router to open a TCP
+// connection to the destination on its behalf and only exchanges the stream payloads with it.
+// Since the TCP retransmissions then happen between the router and the destination, the high
+// latency of the mixnet no longer amplifies them.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct OpenStreamRequest {
+    pub request_id: u64,
+
+    // Identifier chosen by the client for all the messages exchanged over this stream
+    pub stream_id: u64,
+
+    // The destination the router should connect to
+    pub destination: SocketAddr,
+
+    // The nym-address the response should be sent back to
+    pub reply_to: Recipient,
+
+    // Timestamp of when the request was sent by the client.
+    pub timestamp: OffsetDateTime,
+}
+
+// Payload to be written to the stream. As the mix packets might get reordered, the router writes
+// them to the connection in the order of their sequence numbers.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct StreamDataRequest {
+    pub stream_id: u64,
+
+    // Sequence number of the payload within the stream, starting at zero
+    pub sequence: u64,
+
+    pub data: bytes::Bytes,
+}
+
+// The client has finished writing to the stream. The router shuts down the write half of the
+// connection once all the data up to the sequence number has been written.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct CloseStreamRequest {
+    pub stream_id: u64,
+
+    // The sequence number following the last payload sent over the stream
+    pub sequence: u64,
+}
+
+// The client proactively supplies the router with fresh single-use reply blocks, so that
+// long-running sessions don't stall once the router runs out of ways to send data back. The router
+// associates them with the sender tag of the message they arrived in.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct SupplyReplySurbsRequest {
+    // The serialized reply SURBs
+    pub reply_surbs: Vec<bytes::Bytes>,
+}
+
+impl SupplyReplySurbsRequest {
+    pub fn reply_surbs(&self) -> Result<Vec<ReplySurb>, ReplySurbsError> {
+        decode_reply_surbs(&self.reply_surbs)
+    }
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
     use std::net::{Ipv4Addr, Ipv6Addr};
     use std::str::FromStr;
 
+    pub(crate) fn recipient() -> Recipient {
+        Recipient::try_from_base58_string("D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN").unwrap()
+    }
+
+    fn roundtrip(request: &IpPacketRequest) -> IpPacketRequest {
+        let serialized = request.to_bytes().unwrap();
+        IpPacketRequest::from_reconstructed_message(&nym_sphinx::receiver::ReconstructedMessage {
+            message: serialized,
+            sender_tag: None,
+        })
+        .unwrap()
+    }
+
     #[test]
     fn check_size_of_request() {
         let connect = IpPacketRequest {
             version: 4,
-            data: IpPacketRequestData::StaticConnect(
-                SignedStaticConnectRequest {
-                    request: StaticConnectRequest {
-                        request_id: 123,
-                        ips: IpPair::new(Ipv4Addr::from_str("10.0.0.1").unwrap(), Ipv6Addr::from_str("2001:db8:a160::1").unwrap()),
-                        reply_to: Recipient::try_from_base58_string("D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN").unwrap(),
-                        reply_to_hops: None,
-                        reply_to_avg_mix_delays: None,
-                        buffer_timeout: None,
-                        timestamp: OffsetDateTime::now_utc(),
-                    },
-                    signature: None,
-                }
-            ),
+            data: IpPacketRequestData::StaticConnect(SignedStaticConnectRequest {
+                request: StaticConnectRequest {
+                    request_id: 123,
+                    ips: IpPair::new(
+                        Ipv4Addr::from_str("10.0.0.1").unwrap(),
+                        Ipv6Addr::from_str("2001:db8:a160::1").unwrap(),
+                    ),
+                    reply_to: recipient(),
+                    reply_to_hops: None,
+                    reply_to_avg_mix_delays: None,
+                    buffer_timeout: None,
+                    compression: None,
+                    keepalive_interval: None,
+                    trace_id: None,
+                    timestamp: OffsetDateTime::now_utc(),
+                },
+                signature: None,
+            }),
+            priority: None,
         };
-        assert_eq!(connect.to_bytes().unwrap().len(), 139);
+        assert_eq!(connect.to_bytes().unwrap().len(), 143);
     }
 
     #[test]
@@ -361,35 +907,148 @@ mod tests {
             version: 4,
             data: IpPacketRequestData::Data(DataRequest {
                 ip_packets: bytes::Bytes::from(vec![1u8; 32]),
+                connection_id: None,
+                compression: None,
+                trace_id: None,
+                sequence: None,
             }),
+            priority: None,
         };
-        assert_eq!(data.to_bytes().unwrap().len(), 35);
+        assert_eq!(data.to_bytes().unwrap().len(), 40);
     }
 
     #[test]
-    fn serialize_and_deserialize_data_request() {
-        let data = IpPacketRequest {
-            version: 4,
-            data: IpPacketRequestData::Data(DataRequest {
-                ip_packets: bytes::Bytes::from(vec![1, 2, 4, 2, 5]),
+    fn serialize_and_deserialize_requests() {
+        let packets = bytes::Bytes::from(vec![1, 2, 4, 2, 5]);
+        let data = |connection_id| {
+            IpPacketRequestData::Data(DataRequest {
+                ip_packets: packets.clone(),
+                connection_id,
+                compression: None,
+                trace_id: None,
+                sequence: None,
+            })
+        };
+
+        // requests that don't expect any response
+        let unsolicited = [
+            (
+                IpPacketRequest::new_data_request(packets.clone()),
+                data(None),
+            ),
+            (
+                IpPacketRequest::new_data_request_on_connection(packets.clone(), 1234),
+                data(Some(1234)),
+            ),
+        ];
+        for (request, expected) in unsolicited {
+            let deserialized = roundtrip(&request);
+            assert_eq!(deserialized.version, CURRENT_VERSION);
+            assert_eq!(deserialized.data, expected);
+            assert_eq!(deserialized.id(), None);
+        }
+
+        // requests the router replies to
+        let reply_to = recipient();
+        let solicited = [
+            IpPacketRequest::new_info_request(reply_to),
+            IpPacketRequest::new_update_reply_address_request(SessionToken::generate(), reply_to),
+            IpPacketRequest::new_top_up_bandwidth_request(vec![1, 2, 3, 4], reply_to),
+            IpPacketRequest::new_open_stream_request(7, "1.1.1.1:443".parse().unwrap(), reply_to),
+            IpPacketRequest::new_stats_request(reply_to),
+        ];
+        for (request, request_id) in solicited {
+            let deserialized = roundtrip(&request);
+            assert_eq!(deserialized.data, request.data);
+            assert_eq!(deserialized.id(), Some(request_id));
+            assert_eq!(deserialized.recipient(), Some(&reply_to));
+        }
+    }
+
+    #[test]
+    fn compressed_data_request_roundtrip() {
+        let packets = bytes::Bytes::from(vec![7u8; 1000]);
+        let data =
+            IpPacketRequest::new_compressed_data_request(packets.clone(), Some(Compression::Lz4));
+
+        assert!(data.to_bytes().unwrap().len() < packets.len());
+        let IpPacketRequestData::Data(data_request) = roundtrip(&data).data else {
+            panic!("expected data request");
+        };
+        assert_eq!(data_request.compression, Some(Compression::Lz4));
+        assert_eq!(data_request.decompressed_packets().unwrap(), packets);
+    }
+
+    #[test]
+    fn trace_id_is_preserved() {
+        let trace_id = TraceId::generate();
+        let data = IpPacketRequest::new_data_request(bytes::Bytes::from(vec![1, 2, 3]))
+            .with_trace_id(trace_id);
+
+        assert_eq!(roundtrip(&data).trace_id(), Some(trace_id));
+    }
+
+    #[test]
+    fn session_token_is_preserved() {
+        let session_token = SessionToken::generate();
+        let (request, _) =
+            IpPacketRequest::new_update_reply_address_request(session_token, recipient());
+
+        let IpPacketRequestData::UpdateReplyAddress(update) = roundtrip(&request).data else {
+            panic!("expected update reply address request");
+        };
+        assert_eq!(update.session_token, session_token);
+    }
+
+    #[test]
+    fn priority_is_only_honoured_for_control_messages() {
+        let (disconnect, _) = IpPacketRequest::new_disconnect_request(recipient());
+        let disconnect = disconnect.with_priority(Priority::High);
+        assert_eq!(disconnect.priority(), Priority::High);
+        assert!(disconnect.avg_mix_delay_ms().is_some());
+
+        let mut data = IpPacketRequest::new_data_request(bytes::Bytes::from(vec![1, 2, 3]))
+            .with_priority(Priority::High);
+        assert_eq!(data.priority, None);
+
+        // even if the sender has set it regardless
+        data.priority = Some(Priority::High);
+        assert_eq!(data.priority(), Priority::Normal);
+        assert_eq!(data.avg_mix_delay_ms(), None);
+    }
+
+    #[test]
+    fn serialize_and_deserialize_stream_requests() {
+        let data =
+            IpPacketRequest::new_stream_data_request(7, 0, bytes::Bytes::from(vec![1, 2, 3]));
+        let close = IpPacketRequest::new_close_stream_request(7, 1);
+
+        for request in [data, close] {
+            assert_eq!(roundtrip(&request).data, request.data);
+        }
+    }
+
+    #[test]
+    fn serialize_and_deserialize_supply_reply_surbs_request() {
+        let request = IpPacketRequest {
+            version: CURRENT_VERSION,
+            data: IpPacketRequestData::SupplyReplySurbs(SupplyReplySurbsRequest {
+                reply_surbs: vec![bytes::Bytes::from(vec![1u8; 16]); 3],
             }),
+            priority: None,
         };
+        assert_eq!(request.id(), None);
+        assert_eq!(request.recipient(), None);
 
-        let serialized = data.to_bytes().unwrap();
-        let deserialized = IpPacketRequest::from_reconstructed_message(
-            &nym_sphinx::receiver::ReconstructedMessage {
-                message: serialized,
-                sender_tag: None,
-            },
-        )
-        .unwrap();
+        let deserialized = roundtrip(&request);
+        assert_eq!(deserialized.data, request.data);
 
-        assert_eq!(deserialized.version, 4);
-        assert_eq!(
-            deserialized.data,
-            IpPacketRequestData::Data(DataRequest {
-                ip_packets: bytes::Bytes::from(vec![1, 2, 4, 2, 5]),
-            })
-        );
+        let IpPacketRequestData::SupplyReplySurbs(supply) = deserialized.data else {
+            panic!("expected supply reply surbs request");
+        };
+        assert!(matches!(
+            supply.reply_surbs(),
+            Err(ReplySurbsError::TruncatedSurb { index: 0, len: 16 })
+        ));
     }
 }
@@ -1,6 +1,10 @@
 use nym_sphinx::addressing::clients::Recipient;
 use serde::{Deserialize, Serialize};
 
+use crate::compression::{Compression, CompressionError};
+use crate::nat64::Nat64Prefix;
+use crate::session::SessionToken;
+use crate::trace::TraceId;
 use crate::{make_bincode_serializer, IpPair, CURRENT_VERSION};
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -10,13 +14,28 @@ pub struct IpPacketResponse {
 }
 
 impl IpPacketResponse {
-    pub fn new_static_connect_success(request_id: u64, reply_to: Recipient) -> Self {
+    pub fn new_static_connect_success(
+        request_id: u64,
+        reply_to: Recipient,
+        compression: Option<Compression>,
+        keepalive_interval: Option<u64>,
+        nat64_prefix: Option<Nat64Prefix>,
+        session_token: SessionToken,
+        remaining_bandwidth: Option<u64>,
+    ) -> Self {
         Self {
             version: CURRENT_VERSION,
             data: IpPacketResponseData::StaticConnect(StaticConnectResponse {
                 request_id,
                 reply_to,
-                reply: StaticConnectResponseReply::Success,
+                trace_id: None,
+                reply: StaticConnectResponseReply::Success(StaticConnectSuccess {
+                    compression,
+                    keepalive_interval,
+                    nat64_prefix,
+                    session_token,
+                    remaining_bandwidth,
+                }),
             }),
         }
     }
@@ -31,18 +50,36 @@ impl IpPacketResponse {
             data: IpPacketResponseData::StaticConnect(StaticConnectResponse {
                 request_id,
                 reply_to,
+                trace_id: None,
                 reply: StaticConnectResponseReply::Failure(reason),
             }),
         }
     }
 
-    pub fn new_dynamic_connect_success(request_id: u64, reply_to: Recipient, ips: IpPair) -> Self {
+    pub fn new_dynamic_connect_success(
+        request_id: u64,
+        reply_to: Recipient,
+        ips: IpPair,
+        compression: Option<Compression>,
+        keepalive_interval: Option<u64>,
+        nat64_prefix: Option<Nat64Prefix>,
+        session_token: SessionToken,
+        remaining_bandwidth: Option<u64>,
+    ) -> Self {
         Self {
             version: CURRENT_VERSION,
             data: IpPacketResponseData::DynamicConnect(DynamicConnectResponse {
                 request_id,
                 reply_to,
-                reply: DynamicConnectResponseReply::Success(DynamicConnectSuccess { ips }),
+                trace_id: None,
+                reply: DynamicConnectResponseReply::Success(DynamicConnectSuccess {
+                    ips,
+                    compression,
+                    keepalive_interval,
+                    nat64_prefix,
+                    session_token,
+                    remaining_bandwidth,
+                }),
             }),
         }
     }
@@ -57,18 +94,23 @@ impl IpPacketResponse {
             data: IpPacketResponseData::DynamicConnect(DynamicConnectResponse {
                 request_id,
                 reply_to,
+                trace_id: None,
                 reply: DynamicConnectResponseReply::Failure(reason),
             }),
         }
     }
 
-    pub fn new_disconnect_success(request_id: u64, reply_to: Recipient) -> Self {
+    pub fn new_disconnect_success(
+        request_id: u64,
+        reply_to: Recipient,
+        flushed_packets: u32,
+    ) -> Self {
         Self {
             version: CURRENT_VERSION,
             data: IpPacketResponseData::Disconnect(DisconnectResponse {
                 request_id,
                 reply_to,
-                reply: DisconnectResponseReply::Success,
+                reply: DisconnectResponseReply::Success(DisconnectSuccess { flushed_packets }),
             }),
         }
     }
@@ -104,7 +146,42 @@ impl IpPacketResponse {
     pub fn new_ip_packet(ip_packet: bytes::Bytes) -> Self {
         Self {
             version: CURRENT_VERSION,
-            data: IpPacketResponseData::Data(DataResponse { ip_packet }),
+            data: IpPacketResponseData::Data(DataResponse {
+                ip_packet,
+                connection_id: None,
+                compression: None,
+                trace_id: None,
+            }),
+        }
+    }
+
+    // Create a data response with the packets compressed using the negotiated compression,
+    // if that's worthwhile for their size.
+    pub fn new_compressed_ip_packet(
+        ip_packet: bytes::Bytes,
+        compression: Option<Compression>,
+    ) -> Self {
+        let (ip_packet, compression) = Compression::compress_if_beneficial(compression, ip_packet);
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::Data(DataResponse {
+                ip_packet,
+                connection_id: None,
+                compression,
+                trace_id: None,
+            }),
+        }
+    }
+
+    pub fn new_ip_packet_on_connection(ip_packet: bytes::Bytes, connection_id: u64) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::Data(DataResponse {
+                ip_packet,
+                connection_id: Some(connection_id),
+                compression: None,
+                trace_id: None,
+            }),
         }
     }
 
@@ -173,6 +250,243 @@ impl IpPacketResponse {
         }
     }
 
+    pub fn new_router_info_response(
+        request_id: u64,
+        reply_to: Recipient,
+        info: RouterInfo,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::RouterInfo(RouterInfoResponse {
+                request_id,
+                reply_to,
+                reply: info,
+            }),
+        }
+    }
+
+    pub fn new_stats_response(request_id: u64, reply_to: Recipient, stats: SessionStats) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::Stats(StatsResponse {
+                request_id,
+                reply_to,
+                reply: StatsResponseReply::Success(stats),
+            }),
+        }
+    }
+
+    pub fn new_stats_failure(
+        request_id: u64,
+        reply_to: Recipient,
+        reason: StatsFailureReason,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::Stats(StatsResponse {
+                request_id,
+                reply_to,
+                reply: StatsResponseReply::Failure(reason),
+            }),
+        }
+    }
+
+    // Sent to the new reply address once the session has been moved over to it
+    pub fn new_update_reply_address_success(request_id: u64, reply_to: Recipient) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::UpdateReplyAddress(UpdateReplyAddressResponse {
+                request_id,
+                reply_to,
+                reply: UpdateReplyAddressResponseReply::Success,
+            }),
+        }
+    }
+
+    pub fn new_update_reply_address_failure(
+        request_id: u64,
+        reply_to: Recipient,
+        reason: UpdateReplyAddressFailureReason,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::UpdateReplyAddress(UpdateReplyAddressResponse {
+                request_id,
+                reply_to,
+                reply: UpdateReplyAddressResponseReply::Failure(reason),
+            }),
+        }
+    }
+
+    pub fn new_top_up_bandwidth_success(
+        request_id: u64,
+        reply_to: Recipient,
+        remaining_bandwidth: u64,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::TopUpBandwidth(TopUpBandwidthResponse {
+                request_id,
+                reply_to,
+                reply: TopUpBandwidthResponseReply::Success {
+                    remaining_bandwidth,
+                },
+            }),
+        }
+    }
+
+    pub fn new_top_up_bandwidth_failure(
+        request_id: u64,
+        reply_to: Recipient,
+        reason: TopUpBandwidthFailureReason,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::TopUpBandwidth(TopUpBandwidthResponse {
+                request_id,
+                reply_to,
+                reply: TopUpBandwidthResponseReply::Failure(reason),
+            }),
+        }
+    }
+
+    pub fn new_data_limit_warning(remaining_bandwidth: u64) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::DataLimitWarning(DataLimitWarningResponse {
+                remaining_bandwidth,
+            }),
+        }
+    }
+
+    pub fn new_heartbeat_response(request_id: u64, reply_to: Recipient) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::Heartbeat(HeartbeatResponse {
+                request_id,
+                reply_to,
+            }),
+        }
+    }
+
+    pub fn new_open_stream_success(request_id: u64, reply_to: Recipient, stream_id: u64) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::StreamOpened(OpenStreamResponse {
+                request_id,
+                reply_to,
+                stream_id,
+                reply: OpenStreamResponseReply::Success,
+            }),
+        }
+    }
+
+    pub fn new_open_stream_failure(
+        request_id: u64,
+        reply_to: Recipient,
+        stream_id: u64,
+        reason: OpenStreamFailureReason,
+    ) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::StreamOpened(OpenStreamResponse {
+                request_id,
+                reply_to,
+                stream_id,
+                reply: OpenStreamResponseReply::Failure(reason),
+            }),
+        }
+    }
+
+    pub fn new_stream_data(stream_id: u64, sequence: u64, data: bytes::Bytes) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::StreamData(StreamDataResponse {
+                stream_id,
+                sequence,
+                data,
+            }),
+        }
+    }
+
+    pub fn new_stream_closed(stream_id: u64, sequence: u64, reason: StreamCloseReason) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::StreamClosed(StreamClosedResponse {
+                stream_id,
+                sequence,
+                reason,
+            }),
+        }
+    }
+
+    pub fn new_low_surb_warning(remaining_surbs: u32, requested_surbs: u32) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::LowSurbWarning(LowSurbWarningResponse {
+                remaining_surbs,
+                requested_surbs,
+            }),
+        }
+    }
+
+    pub fn new_router_status(status: RouterStatus) -> Self {
+        Self {
+            version: CURRENT_VERSION,
+            data: IpPacketResponseData::RouterStatus(RouterStatusNotification { status }),
+        }
+    }
+
+    // Echo back the trace id of the request, if the response is of a kind that carries one
+    pub fn with_trace_id(mut self, trace_id: Option<TraceId>) -> Self {
+        match &mut self.data {
+            IpPacketResponseData::StaticConnect(response) => response.trace_id = trace_id,
+            IpPacketResponseData::DynamicConnect(response) => response.trace_id = trace_id,
+            IpPacketResponseData::Data(response) => response.trace_id = trace_id,
+            IpPacketResponseData::Disconnect(_)
+            | IpPacketResponseData::UnrequestedDisconnect(_)
+            | IpPacketResponseData::Pong(_)
+            | IpPacketResponseData::Health(_)
+            | IpPacketResponseData::Info(_)
+            | IpPacketResponseData::RouterInfo(_)
+            | IpPacketResponseData::Heartbeat(_)
+            | IpPacketResponseData::StreamOpened(_)
+            | IpPacketResponseData::StreamData(_)
+            | IpPacketResponseData::StreamClosed(_)
+            | IpPacketResponseData::LowSurbWarning(_)
+            | IpPacketResponseData::RouterStatus(_)
+            | IpPacketResponseData::Stats(_)
+            | IpPacketResponseData::UpdateReplyAddress(_)
+            | IpPacketResponseData::TopUpBandwidth(_)
+            | IpPacketResponseData::DataLimitWarning(_) => {}
+        }
+        self
+    }
+
+    pub fn trace_id(&self) -> Option<TraceId> {
+        match &self.data {
+            IpPacketResponseData::StaticConnect(response) => response.trace_id,
+            IpPacketResponseData::DynamicConnect(response) => response.trace_id,
+            IpPacketResponseData::Data(response) => response.trace_id,
+            IpPacketResponseData::Disconnect(_)
+            | IpPacketResponseData::UnrequestedDisconnect(_)
+            | IpPacketResponseData::Pong(_)
+            | IpPacketResponseData::Health(_)
+            | IpPacketResponseData::Info(_)
+            | IpPacketResponseData::RouterInfo(_)
+            | IpPacketResponseData::Heartbeat(_)
+            | IpPacketResponseData::StreamOpened(_)
+            | IpPacketResponseData::StreamData(_)
+            | IpPacketResponseData::StreamClosed(_)
+            | IpPacketResponseData::LowSurbWarning(_)
+            | IpPacketResponseData::RouterStatus(_)
+            | IpPacketResponseData::Stats(_)
+            | IpPacketResponseData::UpdateReplyAddress(_)
+            | IpPacketResponseData::TopUpBandwidth(_)
+            | IpPacketResponseData::DataLimitWarning(_) => None,
+        }
+    }
+
     pub fn id(&self) -> Option<u64> {
         match &self.data {
             IpPacketResponseData::StaticConnect(response) => Some(response.request_id),
@@ -183,6 +497,17 @@ impl IpPacketResponse {
             IpPacketResponseData::Pong(response) => Some(response.request_id),
             IpPacketResponseData::Health(response) => Some(response.request_id),
             IpPacketResponseData::Info(response) => Some(response.request_id),
+            IpPacketResponseData::RouterInfo(response) => Some(response.request_id),
+            IpPacketResponseData::Heartbeat(response) => Some(response.request_id),
+            IpPacketResponseData::StreamOpened(response) => Some(response.request_id),
+            IpPacketResponseData::StreamData(_) => None,
+            IpPacketResponseData::StreamClosed(_) => None,
+            IpPacketResponseData::LowSurbWarning(_) => None,
+            IpPacketResponseData::RouterStatus(_) => None,
+            IpPacketResponseData::Stats(response) => Some(response.request_id),
+            IpPacketResponseData::UpdateReplyAddress(response) => Some(response.request_id),
+            IpPacketResponseData::TopUpBandwidth(response) => Some(response.request_id),
+            IpPacketResponseData::DataLimitWarning(_) => None,
         }
     }
 
@@ -196,6 +521,17 @@ impl IpPacketResponse {
             IpPacketResponseData::Pong(response) => Some(&response.reply_to),
             IpPacketResponseData::Health(response) => Some(&response.reply_to),
             IpPacketResponseData::Info(response) => Some(&response.reply_to),
+            IpPacketResponseData::RouterInfo(response) => Some(&response.reply_to),
+            IpPacketResponseData::Heartbeat(response) => Some(&response.reply_to),
+            IpPacketResponseData::StreamOpened(response) => Some(&response.reply_to),
+            IpPacketResponseData::StreamData(_) => None,
+            IpPacketResponseData::StreamClosed(_) => None,
+            IpPacketResponseData::LowSurbWarning(_) => None,
+            IpPacketResponseData::RouterStatus(_) => None,
+            IpPacketResponseData::Stats(response) => Some(&response.reply_to),
+            IpPacketResponseData::UpdateReplyAddress(response) => Some(&response.reply_to),
+            IpPacketResponseData::TopUpBandwidth(response) => Some(&response.reply_to),
+            IpPacketResponseData::DataLimitWarning(_) => None,
         }
     }
 
@@ -238,6 +574,39 @@ pub enum IpPacketResponseData {
 
     // Info response. This can be anything from informative messages to errors
     Info(InfoResponse),
+
+    // Response to an info request, advertising the capabilities of the router
+    RouterInfo(RouterInfoResponse),
+
+    // Response to a heartbeat request
+    Heartbeat(HeartbeatResponse),
+
+    // Response to an open stream request
+    StreamOpened(OpenStreamResponse),
+
+    // Payload read from the connection of an open stream
+    StreamData(StreamDataResponse),
+
+    // The connection of the stream has been closed, either by the remote or by the router
+    StreamClosed(StreamClosedResponse),
+
+    // The router is running low on the reply SURBs supplied by the client
+    LowSurbWarning(LowSurbWarningResponse),
+
+    // Response to a stats request
+    Stats(StatsResponse),
+
+    // Unsolicited notification about the state of the router, sent to all connected clients
+    RouterStatus(RouterStatusNotification),
+
+    // Response to a request for updating the reply address of the session
+    UpdateReplyAddress(UpdateReplyAddressResponse),
+
+    // Response to a bandwidth top up request
+    TopUpBandwidth(TopUpBandwidthResponse),
+
+    // The client is running out of its bandwidth allowance
+    DataLimitWarning(DataLimitWarningResponse),
 }
 
 impl IpPacketResponseData {
@@ -251,30 +620,53 @@ impl IpPacketResponseData {
 pub struct StaticConnectResponse {
     pub request_id: u64,
     pub reply_to: Recipient,
+    pub trace_id: Option<TraceId>,
     pub reply: StaticConnectResponseReply,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum StaticConnectResponseReply {
-    Success,
+    Success(StaticConnectSuccess),
     Failure(StaticConnectFailureReason),
 }
 
 impl StaticConnectResponseReply {
     pub fn is_success(&self) -> bool {
         match self {
-            StaticConnectResponseReply::Success => true,
+            StaticConnectResponseReply::Success(_) => true,
             StaticConnectResponseReply::Failure(_) => false,
         }
     }
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StaticConnectSuccess {
+    // The compression the router agreed to use for the data exchanged with the client
+    pub compression: Option<Compression>,
+
+    // The interval in seconds at which the router expects heartbeats from the client. The session
+    // is expired once several of them are missed in a row.
+    pub keepalive_interval: Option<u64>,
+
+    // The prefix the router translates to IPv4 destinations, if it has NAT64 enabled. IPv6-only
+    // clients can reach IPv4 destinations by embedding their addresses within it.
+    pub nat64_prefix: Option<Nat64Prefix>,
+
+    // The token the client has to present when updating the reply address of the session
+    pub session_token: SessionToken,
+
+    // The bandwidth, in bytes, the client is still allowed to use, if the router enforces it
+    pub remaining_bandwidth: Option<u64>,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
 pub enum StaticConnectFailureReason {
     #[error("requested ip address is already in use")]
     RequestedIpAlreadyInUse,
     #[error("requested nym-address is already in use")]
     RequestedNymAddressAlreadyInUse,
+    #[error("requested ip address is outside of the ip pool of the router")]
+    RequestedIpOutsidePool,
     #[error("{0}")]
     Other(String),
 }
@@ -283,6 +675,7 @@ pub enum StaticConnectFailureReason {
 pub struct DynamicConnectResponse {
     pub request_id: u64,
     pub reply_to: Recipient,
+    pub trace_id: Option<TraceId>,
     pub reply: DynamicConnectResponseReply,
 }
 
@@ -304,6 +697,23 @@ impl DynamicConnectResponseReply {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DynamicConnectSuccess {
     pub ips: IpPair,
+
+    // The compression the router agreed to use for the data exchanged with the client
+    pub compression: Option<Compression>,
+
+    // The interval in seconds at which the router expects heartbeats from the client. The session
+    // is expired once several of them are missed in a row.
+    pub keepalive_interval: Option<u64>,
+
+    // The prefix the router translates to IPv4 destinations, if it has NAT64 enabled. IPv6-only
+    // clients can reach IPv4 destinations by embedding their addresses within it.
+    pub nat64_prefix: Option<Nat64Prefix>,
+
+    // The token the client has to present when updating the reply address of the session
+    pub session_token: SessionToken,
+
+    // The bandwidth, in bytes, the client is still allowed to use, if the router enforces it
+    pub remaining_bandwidth: Option<u64>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
@@ -312,6 +722,8 @@ pub enum DynamicConnectFailureReason {
     RequestedNymAddressAlreadyInUse,
     #[error("no available ip address")]
     NoAvailableIp,
+    #[error("all {capacity} addresses in the ip pool of the router are in use")]
+    IpPoolExhausted { capacity: u32 },
     #[error("{0}")]
     Other(String),
 }
@@ -325,14 +737,35 @@ pub struct DisconnectResponse {
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum DisconnectResponseReply {
-    Success,
+    Success(DisconnectSuccess),
     Failure(DisconnectFailureReason),
 }
 
+impl DisconnectResponseReply {
+    pub fn is_success(&self) -> bool {
+        match self {
+            DisconnectResponseReply::Success(_) => true,
+            DisconnectResponseReply::Failure(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DisconnectSuccess {
+    // The number of packets that were still queued for the client when it disconnected and that
+    // were flushed to it before the response was sent. Once the client receives this response,
+    // no more packets are going to arrive from the router.
+    pub flushed_packets: u32,
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
 pub enum DisconnectFailureReason {
     #[error("requested nym-address is not currently connected")]
     RequestedNymAddressNotConnected,
+    #[error(
+        "failed to flush the {queued_packets} packets queued for the client before disconnecting"
+    )]
+    FailedToFlushQueuedPackets { queued_packets: u32 },
     #[error("{0}")]
     Other(String),
 }
@@ -356,6 +789,21 @@ pub enum UnrequestedDisconnectReason {
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DataResponse {
     pub ip_packet: bytes::Bytes,
+
+    // Identifier of the logical tunnel the packets belong to, as specified in the data request
+    pub connection_id: Option<u64>,
+
+    // The compression that has been applied to the ip packet, if any
+    pub compression: Option<Compression>,
+
+    // The trace id of the data request these packets are replies to, if it's known
+    pub trace_id: Option<TraceId>,
+}
+
+impl DataResponse {
+    pub fn decompressed_packets(self) -> Result<bytes::Bytes, CompressionError> {
+        Compression::decompress(self.compression, self.ip_packet)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -402,6 +850,342 @@ pub enum InfoResponseReply {
     ExitPolicyFilterCheckFailed { dst: String },
 }
 
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct HeartbeatResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct RouterInfoResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    pub reply: RouterInfo,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterInfo {
+    // The protocol versions the router is able to handle
+    pub supported_versions: Vec<u8>,
+
+    // The maximum number of clients the router is willing to have connected at the same time
+    pub max_clients: u32,
+
+    // The number of addresses in the ip pool of the router that are not leased to any client
+    pub available_ips: u32,
+
+    pub features: RouterFeatures,
+}
+
+impl RouterInfo {
+    pub fn supports_version(&self, version: u8) -> bool {
+        self.supported_versions.contains(&version)
+    }
+
+    // Whether a new client could currently connect to the router
+    pub fn has_capacity(&self) -> bool {
+        self.available_ips > 0
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterFeatures {
+    // The router is able to route ipv6 traffic
+    pub ipv6: bool,
+
+    // The router bundles multiple ip packets into a single mix packet
+    pub batching: bool,
+
+    // The compression algorithms the router is able to negotiate
+    pub compression: Vec<Compression>,
+
+    // The prefix the router translates to IPv4 destinations, if it has NAT64 enabled
+    pub nat64_prefix: Option<Nat64Prefix>,
+
+    // The router is able to terminate TCP connections on behalf of the client (stream mode)
+    pub stream_mode: bool,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OpenStreamResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    pub stream_id: u64,
+    pub reply: OpenStreamResponseReply,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum OpenStreamResponseReply {
+    Success,
+    Failure(OpenStreamFailureReason),
+}
+
+impl OpenStreamResponseReply {
+    pub fn is_success(&self) -> bool {
+        match self {
+            OpenStreamResponseReply::Success => true,
+            OpenStreamResponseReply::Failure(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum OpenStreamFailureReason {
+    #[error("the router does not support stream mode")]
+    StreamModeNotSupported,
+    #[error("client is not connected to the router")]
+    ClientNotConnected,
+    #[error("stream {stream_id} is already open")]
+    StreamAlreadyOpen { stream_id: u64 },
+    #[error("the client has reached the maximum number of open streams")]
+    TooManyStreams,
+    #[error("destination failed exit policy filter check: {dst}")]
+    ExitPolicyFilterCheckFailed { dst: String },
+    #[error("the destination refused the connection")]
+    ConnectionRefused,
+    #[error("timed out while connecting to the destination")]
+    ConnectionTimedOut,
+    #[error("{0}")]
+    Other(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StreamDataResponse {
+    pub stream_id: u64,
+
+    // Sequence number of the payload within the stream, starting at zero. The client is expected
+    // to reorder the payloads before handing them over to the application.
+    pub sequence: u64,
+
+    pub data: bytes::Bytes,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StreamClosedResponse {
+    pub stream_id: u64,
+
+    // The sequence number following the last payload sent over the stream
+    pub sequence: u64,
+
+    pub reason: StreamCloseReason,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum StreamCloseReason {
+    #[error("the stream was closed by the client")]
+    ClosedByClient,
+    #[error("the stream was closed by the destination")]
+    ClosedByRemote,
+    #[error("the connection was reset")]
+    ConnectionReset,
+    #[error("the stream has been idle for too long")]
+    IdleTimeout,
+    #[error("{0}")]
+    Other(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StatsResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    pub reply: StatsResponseReply,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum StatsResponseReply {
+    Success(SessionStats),
+    Failure(StatsFailureReason),
+}
+
+impl StatsResponseReply {
+    pub fn is_success(&self) -> bool {
+        match self {
+            StatsResponseReply::Success(_) => true,
+            StatsResponseReply::Failure(_) => false,
+        }
+    }
+}
+
+// Counters of a single client session on the router, accumulated since it connected.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SessionStats {
+    // The number of ip packets received from the client and forwarded to their destinations
+    pub packets_in: u64,
+
+    // The number of ip packets received from the destinations and sent back to the client
+    pub packets_out: u64,
+
+    // The number of ip packets received from the client that the router dropped instead of
+    // forwarding, for example because of the exit policy or for being malformed
+    pub dropped_packets: u64,
+
+    // The number of ip packets destined to the client that the router failed to send back to it
+    pub dropped_replies: u64,
+
+    // The number of NAT translation entries currently held for the client
+    pub nat_entries: u32,
+
+    // For how long the session has been established, in seconds
+    pub session_duration_secs: u64,
+}
+
+impl SessionStats {
+    // The proportion of the packets sent by the client the router has dropped
+    pub fn drop_rate(&self) -> f64 {
+        let total = self.packets_in + self.dropped_packets;
+        if total == 0 {
+            return 0.;
+        }
+        self.dropped_packets as f64 / total as f64
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum StatsFailureReason {
+    #[error("client is not connected to the router")]
+    ClientNotConnected,
+    #[error("{0}")]
+    Other(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct UpdateReplyAddressResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    pub reply: UpdateReplyAddressResponseReply,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum UpdateReplyAddressResponseReply {
+    Success,
+    Failure(UpdateReplyAddressFailureReason),
+}
+
+impl UpdateReplyAddressResponseReply {
+    pub fn is_success(&self) -> bool {
+        match self {
+            UpdateReplyAddressResponseReply::Success => true,
+            UpdateReplyAddressResponseReply::Failure(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum UpdateReplyAddressFailureReason {
+    #[error("no session has been issued the presented token")]
+    InvalidSessionToken,
+    #[error("requested nym-address is already in use")]
+    RequestedNymAddressAlreadyInUse,
+    #[error("{0}")]
+    Other(String),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TopUpBandwidthResponse {
+    pub request_id: u64,
+    pub reply_to: Recipient,
+    pub reply: TopUpBandwidthResponseReply,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum TopUpBandwidthResponseReply {
+    // The credential has been accepted. Contains the bandwidth, in bytes, the client is now
+    // allowed to use.
+    Success { remaining_bandwidth: u64 },
+    Failure(TopUpBandwidthFailureReason),
+}
+
+impl TopUpBandwidthResponseReply {
+    pub fn is_success(&self) -> bool {
+        match self {
+            TopUpBandwidthResponseReply::Success { .. } => true,
+            TopUpBandwidthResponseReply::Failure(_) => false,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, thiserror::Error)]
+pub enum TopUpBandwidthFailureReason {
+    #[error("client is not connected to the router")]
+    ClientNotConnected,
+    #[error("the credential could not be deserialized")]
+    MalformedCredential,
+    #[error("the credential is not valid: {0}")]
+    InvalidCredential(String),
+    #[error("the credential has already been spent")]
+    CredentialAlreadySpent,
+    #[error("the router does not accept bandwidth credentials")]
+    NotSupported,
+    #[error("{0}")]
+    Other(String),
+}
+
+// Sent by the router once the bandwidth allowance of the client drops below its threshold. The
+// client is expected to top it up before it runs out, at which point the router stops forwarding
+// its data.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DataLimitWarningResponse {
+    // The bandwidth, in bytes, the client is still allowed to use
+    pub remaining_bandwidth: u64,
+}
+
+// Sent by the router, over one of the remaining reply SURBs, once the number of SURBs it holds for
+// the client drops below its threshold. The client is expected to respond with a supply request.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LowSurbWarningResponse {
+    // The number of reply SURBs the router still holds for the client
+    pub remaining_surbs: u32,
+
+    // The number of reply SURBs the client is asked to supply
+    pub requested_surbs: u32,
+}
+
+// Pushed by the router to every connected client, without any prior request, whenever its state
+// changes in a way that might soon affect the clients. This allows them to migrate to a different
+// router before they experience hard failures.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RouterStatusNotification {
+    pub status: RouterStatus,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, thiserror::Error)]
+pub enum RouterStatus {
+    // The ip pool of the router is close to being exhausted, so reconnecting clients might not
+    // get an address
+    #[error("only {available_ips} out of {capacity} addresses in the ip pool of the router are available")]
+    PoolNearlyFull { available_ips: u32, capacity: u32 },
+
+    // The router is going to shut down, disconnecting all of its clients
+    #[error("the router is going to shut down in {shutdown_in_secs} seconds")]
+    PlannedShutdown { shutdown_in_secs: u64 },
+
+    // The protocol version used by the client is going to stop being supported
+    #[error(
+        "protocol v{deprecated_version} is deprecated, please upgrade to v{recommended_version}"
+    )]
+    VersionDeprecated {
+        deprecated_version: u8,
+        recommended_version: u8,
+
+        // In how many seconds the router is going to stop accepting the deprecated version, if known
+        removal_in_secs: Option<u64>,
+    },
+}
+
+impl RouterStatus {
+    // Whether the client should start migrating to a different router straight away, as opposed
+    // to only avoiding this one for any new connections
+    pub fn requires_migration(&self) -> bool {
+        match self {
+            RouterStatus::PoolNearlyFull { .. } => false,
+            RouterStatus::PlannedShutdown { .. } => true,
+            RouterStatus::VersionDeprecated {
+                removal_in_secs, ..
+            } => removal_in_secs.is_some(),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum InfoLevel {
     Info,
@@ -0,0 +1,17 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+-- block hashes reported by the rpc endpoints that didn't match the block being processed
+CREATE TABLE rpc_endpoint_disagreement
+(
+    id            INTEGER                     PRIMARY KEY AUTOINCREMENT,
+    height        BIGINT                      NOT NULL,
+    endpoint      TEXT                        NOT NULL,
+    expected_hash TEXT                        NOT NULL,
+    received_hash TEXT                        NOT NULL,
+    observed_at   TIMESTAMP WITHOUT TIME ZONE NOT NULL
+);
+CREATE INDEX rpc_endpoint_disagreement_height_index ON rpc_endpoint_disagreement (height);
+CREATE INDEX rpc_endpoint_disagreement_endpoint_index ON rpc_endpoint_disagreement (endpoint);
@@ -10,16 +10,18 @@ use crate::rpc_client::RpcClient;
 use crate::storage::{persist_block, ScraperStorage};
 use crate::PruningOptions;
 use futures::StreamExt;
-use std::collections::{BTreeMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::ops::{Add, Range};
 use std::sync::Arc;
 use std::time::Duration;
+use tendermint::Hash;
 use tokio::sync::mpsc::{Sender, UnboundedReceiver};
 use tokio::sync::Notify;
 use tokio::time::{interval_at, Instant};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, instrument, trace, warn};
+use url::Url;
 
 mod helpers;
 pub(crate) mod pruning;
@@ -43,6 +45,7 @@ impl PendingSync {
 
 pub struct BlockProcessor {
     pruning_options: PruningOptions,
+    cross_check_rpc: bool,
     cancel: CancellationToken,
     synced: Arc<Notify>,
     last_processed_height: u32,
@@ -65,8 +68,10 @@ pub struct BlockProcessor {
 }
 
 impl BlockProcessor {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         pruning_options: PruningOptions,
+        cross_check_rpc: bool,
         cancel: CancellationToken,
         synced: Arc<Notify>,
         incoming: UnboundedReceiver<BlockToProcess>,
@@ -82,6 +87,7 @@ impl BlockProcessor {
 
         Ok(BlockProcessor {
             pruning_options,
+            cross_check_rpc,
             cancel,
             synced,
             last_processed_height,
@@ -99,9 +105,66 @@ impl BlockProcessor {
         })
     }
 
+    /// Compare the hash of the block against the one reported by every available rpc endpoint.
+    /// Each disagreement is persisted and if the majority of responding endpoints agree on a different hash,
+    /// the block is rejected and one of those endpoints is going to be used for re-requesting it.
+    async fn cross_check_block(&self, block: &BlockToProcess) -> Result<(), ScraperError> {
+        let height = block.height;
+        let expected = block.block.header.hash();
+
+        let mut reported: HashMap<Hash, Vec<Url>> = HashMap::new();
+        for (endpoint, res) in self.rpc_client.get_block_hashes(height).await {
+            match res {
+                Ok(hash) => reported.entry(hash).or_default().push(endpoint),
+                Err(err) => warn!("could not cross-check block {height} against {endpoint}: {err}"),
+            }
+        }
+
+        for (hash, endpoints) in &reported {
+            if hash == &expected {
+                continue;
+            }
+            for endpoint in endpoints {
+                warn!("rpc endpoint {endpoint} reported hash {hash} for block {height} while we expected {expected}");
+                self.storage
+                    .record_rpc_endpoint_disagreement(
+                        height.into(),
+                        endpoint.to_string(),
+                        expected.to_string(),
+                        hash.to_string(),
+                    )
+                    .await?;
+            }
+        }
+
+        let agreeing = reported.get(&expected).map(Vec::len).unwrap_or_default();
+        let Some((_, majority)) = reported
+            .iter()
+            .filter(|(hash, _)| *hash != &expected)
+            .max_by_key(|(_, endpoints)| endpoints.len())
+        else {
+            return Ok(());
+        };
+
+        if majority.len() > agreeing {
+            self.rpc_client.prefer_endpoint(&majority[0]);
+            return Err(ScraperError::RpcEndpointsDisagreement {
+                height,
+                agreeing,
+                disagreeing: majority.len(),
+            });
+        }
+
+        Ok(())
+    }
+
     async fn process_block(&mut self, block: BlockToProcess) -> Result<(), ScraperError> {
         info!("processing block at height {}", block.height);
 
+        if self.cross_check_rpc && self.rpc_client.has_fallbacks() {
+            self.cross_check_block(&block).await?;
+        }
+
         let full_info = self.rpc_client.try_get_full_details(block).await?;
 
         debug!(
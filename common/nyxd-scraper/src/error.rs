@@ -67,6 +67,13 @@ pub enum ScraperError {
         source: tendermint_rpc::Error,
     },
 
+    #[error("the majority of the rpc endpoints ({disagreeing} against {agreeing}) disagreed with the data of the block at height {height}")]
+    RpcEndpointsDisagreement {
+        height: u32,
+        agreeing: usize,
+        disagreeing: usize,
+    },
+
     #[error("could not obtain current abci info: {source}")]
     AbciInfoQueryFailure {
         #[source]
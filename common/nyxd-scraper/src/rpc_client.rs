@@ -6,38 +6,133 @@ use crate::block_processor::types::{
 };
 use crate::error::ScraperError;
 use crate::helpers::tx_hash;
-use futures::future::join3;
+use futures::future::{join3, join_all};
 use futures::StreamExt;
 use std::collections::BTreeMap;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use tendermint::Hash;
 use tendermint_rpc::endpoint::{block, block_results, tx, validators};
 use tendermint_rpc::{Client, HttpClient, Paging};
 use tokio::sync::Mutex;
-use tracing::{debug, instrument};
+use tracing::{debug, instrument, warn};
 use url::Url;
 
-#[derive(Clone)]
-pub struct RpcClient {
+struct RpcEndpoint {
+    url: Url,
+
     // right now I don't care about anything nym specific, so a simple http client is sufficient,
     // once this is inadequate, we can switch to a NyxdClient
-    inner: Arc<HttpClient>,
+    client: HttpClient,
 }
 
-impl RpcClient {
-    pub fn new(url: &Url) -> Result<Self, ScraperError> {
-        let http_client = HttpClient::new(url.as_str()).map_err(|source| {
+impl RpcEndpoint {
+    fn new(url: &Url) -> Result<Self, ScraperError> {
+        let client = HttpClient::new(url.as_str()).map_err(|source| {
             ScraperError::HttpConnectionFailure {
                 url: url.to_string(),
                 source,
             }
         })?;
 
+        Ok(RpcEndpoint {
+            url: url.clone(),
+            client,
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct RpcClient {
+    // the first endpoint is the primary one, the rest are used as fallbacks
+    endpoints: Arc<Vec<RpcEndpoint>>,
+
+    // index of the endpoint that has most recently responded to a query
+    preferred: Arc<AtomicUsize>,
+}
+
+impl RpcClient {
+    pub fn new(url: &Url) -> Result<Self, ScraperError> {
+        Self::new_with_fallbacks(url, &[])
+    }
+
+    pub fn new_with_fallbacks(primary: &Url, fallbacks: &[Url]) -> Result<Self, ScraperError> {
+        let endpoints = std::iter::once(primary)
+            .chain(fallbacks)
+            .map(RpcEndpoint::new)
+            .collect::<Result<Vec<_>, _>>()?;
+
         Ok(RpcClient {
-            inner: Arc::new(http_client),
+            endpoints: Arc::new(endpoints),
+            preferred: Arc::new(AtomicUsize::new(0)),
         })
     }
 
+    pub(crate) fn has_fallbacks(&self) -> bool {
+        self.endpoints.len() > 1
+    }
+
+    /// Make the provided endpoint the first one to be queried in subsequent requests.
+    pub(crate) fn prefer_endpoint(&self, url: &Url) {
+        if let Some(index) = self.endpoints.iter().position(|e| &e.url == url) {
+            self.preferred.store(index, Ordering::Relaxed)
+        }
+    }
+
+    /// Attempt to execute the query against the preferred endpoint, and if that fails,
+    /// against all the remaining ones, in order, until one of them succeeds.
+    async fn with_failover<F, Fut, T>(&self, query: F) -> Result<T, tendermint_rpc::Error>
+    where
+        F: Fn(HttpClient) -> Fut,
+        Fut: Future<Output = Result<T, tendermint_rpc::Error>>,
+    {
+        let preferred = self.preferred.load(Ordering::Relaxed);
+        let mut last_err = None;
+
+        for offset in 0..self.endpoints.len() {
+            let index = (preferred + offset) % self.endpoints.len();
+            let endpoint = &self.endpoints[index];
+
+            match query(endpoint.client.clone()).await {
+                Ok(res) => {
+                    if index != preferred {
+                        warn!("failing over to rpc endpoint {}", endpoint.url);
+                        self.preferred.store(index, Ordering::Relaxed);
+                    }
+                    return Ok(res);
+                }
+                Err(err) => {
+                    if self.has_fallbacks() {
+                        warn!("query to rpc endpoint {} has failed: {err}", endpoint.url);
+                    }
+                    last_err = Some(err)
+                }
+            }
+        }
+
+        // safety: there's always at least a single endpoint, so we must have either returned or set the error
+        #[allow(clippy::unwrap_used)]
+        Err(last_err.unwrap())
+    }
+
+    /// Query every available endpoint for the hash of the block at the provided height.
+    pub(crate) async fn get_block_hashes(
+        &self,
+        height: u32,
+    ) -> Vec<(Url, Result<Hash, ScraperError>)> {
+        join_all(self.endpoints.iter().map(|endpoint| async move {
+            let res = endpoint
+                .client
+                .block(height)
+                .await
+                .map(|res| res.block_id.hash)
+                .map_err(|source| ScraperError::BlockQueryFailure { height, source });
+            (endpoint.url.clone(), res)
+        }))
+        .await
+    }
+
     #[instrument(skip(self, block), fields(height = block.height))]
     pub async fn try_get_full_details(
         &self,
@@ -87,8 +182,7 @@ impl RpcClient {
     ) -> Result<block::Response, ScraperError> {
         debug!("getting basic block details");
 
-        self.inner
-            .block(height)
+        self.with_failover(|client| async move { client.block(height).await })
             .await
             .map_err(|source| ScraperError::BlockQueryFailure { height, source })
     }
@@ -100,8 +194,7 @@ impl RpcClient {
     ) -> Result<block_results::Response, ScraperError> {
         debug!("getting block results");
 
-        self.inner
-            .block_results(height)
+        self.with_failover(|client| async move { client.block_results(height).await })
             .await
             .map_err(|source| ScraperError::BlockResultsQueryFailure { height, source })
     }
@@ -110,8 +203,7 @@ impl RpcClient {
         debug!("getting current block height");
 
         let info = self
-            .inner
-            .abci_info()
+            .with_failover(|client| async move { client.abci_info().await })
             .await
             .map_err(|source| ScraperError::AbciInfoQueryFailure { source })?;
         Ok(info.last_block_height.value())
@@ -151,8 +243,7 @@ impl RpcClient {
     async fn get_transaction_result(&self, tx_hash: Hash) -> Result<tx::Response, ScraperError> {
         debug!("getting tx results");
 
-        self.inner
-            .tx(tx_hash, false)
+        self.with_failover(|client| async move { client.tx(tx_hash, false).await })
             .await
             .map_err(|source| ScraperError::TxResultsQueryFailure {
                 hash: tx_hash,
@@ -167,8 +258,7 @@ impl RpcClient {
     ) -> Result<validators::Response, ScraperError> {
         debug!("getting validators set");
 
-        self.inner
-            .validators(height, Paging::All)
+        self.with_failover(|client| async move { client.validators(height, Paging::All).await })
             .await
             .map_err(|source| ScraperError::ValidatorsQueryFailure { height, source })
     }
@@ -27,6 +27,13 @@ pub struct Config {
     /// Url to the rpc endpoint of a validator, for example `https://rpc.nymtech.net/`
     pub rpc_url: Url,
 
+    /// Additional rpc endpoints used whenever the primary one fails to respond.
+    pub fallback_rpc_urls: Vec<Url>,
+
+    /// Specifies whether every processed block should be cross-checked against all the available rpc endpoints.
+    /// Any disagreements are recorded in the storage and a block rejected by the majority of endpoints is not processed.
+    pub cross_check_rpc: bool,
+
     pub database_path: PathBuf,
 
     pub pruning_options: PruningOptions,
@@ -47,7 +54,10 @@ impl NyxdScraperBuilder {
         let (processing_tx, processing_rx) = unbounded_channel();
         let (req_tx, req_rx) = channel(5);
 
-        let rpc_client = RpcClient::new(&scraper.config.rpc_url)?;
+        let rpc_client = RpcClient::new_with_fallbacks(
+            &scraper.config.rpc_url,
+            &scraper.config.fallback_rpc_urls,
+        )?;
 
         // create the tasks
         let block_requester = BlockRequester::new(
@@ -58,6 +68,7 @@ impl NyxdScraperBuilder {
         );
         let mut block_processor = BlockProcessor::new(
             scraper.config.pruning_options,
+            scraper.config.cross_check_rpc,
             scraper.cancel_token.clone(),
             scraper.startup_sync.clone(),
             processing_rx,
@@ -155,7 +166,8 @@ impl NyxdScraper {
         let (processing_tx, processing_rx) = unbounded_channel();
         let (req_tx, req_rx) = channel(5);
 
-        let rpc_client = RpcClient::new(&self.config.rpc_url)?;
+        let rpc_client =
+            RpcClient::new_with_fallbacks(&self.config.rpc_url, &self.config.fallback_rpc_urls)?;
 
         // create the tasks
         let block_requester = BlockRequester::new(
@@ -166,6 +178,7 @@ impl NyxdScraper {
         );
         let block_processor = BlockProcessor::new(
             self.config.pruning_options,
+            self.config.cross_check_rpc,
             self.cancel_token.clone(),
             self.startup_sync.clone(),
             processing_rx,
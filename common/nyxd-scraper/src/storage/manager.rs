@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::storage::log_db_operation_time;
-use crate::storage::models::{CommitSignature, Validator};
+use crate::storage::models::{CommitSignature, RpcEndpointDisagreement, Validator};
 use sqlx::types::time::OffsetDateTime;
 use sqlx::{Executor, Sqlite};
 use tokio::time::Instant;
@@ -217,6 +217,31 @@ impl StorageManager {
         }
     }
 
+    pub(crate) async fn get_rpc_endpoint_disagreements(
+        &self,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<Vec<RpcEndpointDisagreement>, sqlx::Error> {
+        trace!("get_rpc_endpoint_disagreements");
+        let start = Instant::now();
+
+        let disagreements = sqlx::query_as(
+            r#"
+                SELECT height, endpoint, expected_hash, received_hash, observed_at
+                FROM rpc_endpoint_disagreement
+                WHERE height >= ? AND height <= ?
+                ORDER BY height
+            "#,
+        )
+        .bind(start_height)
+        .bind(end_height)
+        .fetch_all(&self.connection_pool)
+        .await?;
+        log_db_operation_time("get_rpc_endpoint_disagreements", start);
+
+        Ok(disagreements)
+    }
+
     pub(crate) async fn get_pruned_height(&self) -> Result<i64, sqlx::Error> {
         trace!("get_pruned_height");
         let start = Instant::now();
@@ -424,6 +449,39 @@ where
     Ok(())
 }
 
+#[instrument(skip(executor))]
+pub(crate) async fn insert_rpc_endpoint_disagreement<'a, E>(
+    height: i64,
+    endpoint: String,
+    expected_hash: String,
+    received_hash: String,
+    executor: E,
+) -> Result<(), sqlx::Error>
+where
+    E: Executor<'a, Database = Sqlite>,
+{
+    trace!("insert_rpc_endpoint_disagreement");
+    let start = Instant::now();
+
+    let now = OffsetDateTime::now_utc();
+    sqlx::query!(
+        r#"
+            INSERT INTO rpc_endpoint_disagreement (height, endpoint, expected_hash, received_hash, observed_at)
+            VALUES (?, ?, ?, ?, ?)
+        "#,
+        height,
+        endpoint,
+        expected_hash,
+        received_hash,
+        now
+    )
+    .execute(executor)
+    .await?;
+    log_db_operation_time("insert_rpc_endpoint_disagreement", start);
+
+    Ok(())
+}
+
 #[instrument(skip(executor))]
 pub(crate) async fn update_last_processed<'a, E>(
     height: i64,
@@ -4,11 +4,11 @@
 use crate::block_processor::types::{FullBlockInformation, ParsedTransactionResponse};
 use crate::error::ScraperError;
 use crate::storage::manager::{
-    insert_block, insert_message, insert_precommit, insert_transaction, insert_validator,
-    prune_blocks, prune_messages, prune_pre_commits, prune_transactions, update_last_processed,
-    update_last_pruned, StorageManager,
+    insert_block, insert_message, insert_precommit, insert_rpc_endpoint_disagreement,
+    insert_transaction, insert_validator, prune_blocks, prune_messages, prune_pre_commits,
+    prune_transactions, update_last_processed, update_last_pruned, StorageManager,
 };
-use crate::storage::models::{CommitSignature, Validator};
+use crate::storage::models::{CommitSignature, RpcEndpointDisagreement, Validator};
 use sqlx::types::time::OffsetDateTime;
 use sqlx::{ConnectOptions, Sqlite, Transaction};
 use std::fmt::Debug;
@@ -204,6 +204,35 @@ impl ScraperStorage {
     pub async fn get_pruned_height(&self) -> Result<i64, ScraperError> {
         Ok(self.manager.get_pruned_height().await?)
     }
+
+    pub async fn record_rpc_endpoint_disagreement(
+        &self,
+        height: i64,
+        endpoint: String,
+        expected_hash: String,
+        received_hash: String,
+    ) -> Result<(), ScraperError> {
+        Ok(insert_rpc_endpoint_disagreement(
+            height,
+            endpoint,
+            expected_hash,
+            received_hash,
+            &self.manager.connection_pool,
+        )
+        .await?)
+    }
+
+    /// Get all the recorded disagreements between the rpc endpoints for blocks within the specified (inclusive) range.
+    pub async fn get_rpc_endpoint_disagreements(
+        &self,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<Vec<RpcEndpointDisagreement>, ScraperError> {
+        Ok(self
+            .manager
+            .get_rpc_endpoint_disagreements(start_height, end_height)
+            .await?)
+    }
 }
 
 pub async fn persist_block(
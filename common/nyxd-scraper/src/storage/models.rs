@@ -28,3 +28,12 @@ pub struct CommitSignature {
     pub proposer_priority: i64,
     pub timestamp: OffsetDateTime,
 }
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RpcEndpointDisagreement {
+    pub height: i64,
+    pub endpoint: String,
+    pub expected_hash: String,
+    pub received_hash: String,
+    pub observed_at: OffsetDateTime,
+}
@@ -118,6 +118,11 @@ pub struct Socks5 {
     /// The mix address of the provider to which all requests are going to be sent.
     pub provider_mix_address: String,
 
+    /// Optional mix address of the ip packet router through which datagrams of `UDP ASSOCIATE`
+    /// requests are going to be tunnelled. If not specified, UDP is not supported.
+    #[serde(default)]
+    pub ip_packet_router_address: Option<String>,
+
     /// The version of the 'service provider' this client is going to use in its communication with the
     /// specified socks5 provider.
     // if in doubt, use the legacy version as initially nobody will be using the updated binaries
@@ -147,6 +152,7 @@ impl Socks5 {
                 DEFAULT_SOCKS5_LISTENING_PORT,
             ),
             provider_mix_address: provider_mix_address.into(),
+            ip_packet_router_address: None,
             provider_interface_version: ProviderInterfaceVersion::Legacy,
             socks5_protocol_version: Socks5ProtocolVersion::Legacy,
             send_anonymously: false,
@@ -158,6 +164,12 @@ impl Socks5 {
         Recipient::try_from_base58_string(&self.provider_mix_address)
             .expect("malformed provider address")
     }
+
+    pub fn get_ip_packet_router_address(&self) -> Option<Recipient> {
+        self.ip_packet_router_address.as_ref().map(|address| {
+            Recipient::try_from_base58_string(address).expect("malformed ip packet router address")
+        })
+    }
 }
 
 #[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq, Serialize)]
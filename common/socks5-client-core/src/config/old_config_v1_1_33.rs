@@ -70,6 +70,7 @@ impl From<Socks5V1_1_33> for Socks5 {
         Socks5 {
             bind_address: value.bind_address,
             provider_mix_address: value.provider_mix_address,
+            ip_packet_router_address: None,
             provider_interface_version: value.provider_interface_version,
             socks5_protocol_version: value.socks5_protocol_version,
             send_anonymously: value.send_anonymously,
@@ -127,6 +127,7 @@ where
             socks5_config.bind_address,
             authenticator,
             socks5_config.get_provider_mix_address(),
+            socks5_config.get_ip_packet_router_address(),
             self_address,
             shared_lane_queue_lengths,
             socks::client::Config::new(
@@ -3,10 +3,14 @@
 use super::authentication::{AuthenticationMethods, Authenticator, User};
 use super::request::{SocksCommand, SocksRequest};
 use super::types::{ResponseCodeV4, ResponseCodeV5, SocksProxyError};
+use super::udp::{self, UdpDatagram};
+use super::udp_relay::{UdpRelayCommand, UdpRelaySender, UdpSessionReceiver};
 use super::{SocksVersion, RESERVED, SOCKS4_VERSION, SOCKS5_VERSION};
 use crate::config;
-use futures::channel::mpsc;
+use bytes::Bytes;
+use futures::channel::{mpsc, oneshot};
 use futures::task::{Context, Poll};
+use futures::StreamExt;
 use log::*;
 use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
 use nym_service_providers_common::interface::{ProviderInterfaceVersion, RequestVersion};
@@ -25,10 +29,12 @@ use nym_task::TaskClient;
 use pin_project::pin_project;
 use rand::RngCore;
 use std::io;
-use std::net::SocketAddr;
+use std::net::{IpAddr, SocketAddr};
 use std::pin::Pin;
 use tokio::io::{AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, ReadBuf};
-use tokio::net::TcpStream;
+use tokio::net::{TcpStream, UdpSocket};
+
+const MAX_UDP_DATAGRAM_SIZE: usize = 65535;
 
 #[pin_project(project = StateProject)]
 enum StreamState {
@@ -69,6 +75,17 @@ impl StreamState {
         }
     }
 
+    /// Returns the local address that this stream is bound to.
+    fn local_addr(&self) -> io::Result<SocketAddr> {
+        match self {
+            StreamState::RunningProxy => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "stream is being used to run the proxy",
+            )),
+            StreamState::Available(ref stream) => stream.local_addr(),
+        }
+    }
+
     async fn shutdown(&mut self) -> io::Result<()> {
         // shutdown should only be called if proxy is not being run. If it is, there's some bug
         // somewhere
@@ -175,6 +192,7 @@ impl Config {
 pub(crate) struct SocksClient {
     config: Config,
     controller_sender: ControllerSender,
+    udp_relay: Option<UdpRelaySender>,
     stream: StreamState,
     auth_nmethods: u8,
     authenticator: Authenticator,
@@ -212,6 +230,7 @@ impl SocksClient {
         input_sender: InputMessageSender,
         service_provider: &Recipient,
         controller_sender: ControllerSender,
+        udp_relay: Option<UdpRelaySender>,
         self_address: &Recipient,
         lane_queue_lengths: LaneQueueLengths,
         mut shutdown_listener: TaskClient,
@@ -225,6 +244,7 @@ impl SocksClient {
         SocksClient {
             config,
             controller_sender,
+            udp_relay,
             connection_id,
             stream: StreamState::Available(stream),
             auth_nmethods: 0,
@@ -508,12 +528,147 @@ impl SocksClient {
             }
 
             SocksCommand::Bind => return Err(SocksProxyError::BindNotSupported), // not handled
-            SocksCommand::UdpAssociate => return Err(SocksProxyError::UdpNotSupported),
+            SocksCommand::UdpAssociate => {
+                let Some(udp_relay) = self.udp_relay.clone() else {
+                    return Err(SocksProxyError::UdpNotSupported);
+                };
+                self.run_udp_association(udp_relay).await?;
+            }
         };
 
         Ok(())
     }
 
+    /// Handles the `UDP ASSOCIATE` command. Datagrams received from the client are wrapped in
+    /// UDP/IP packets and tunnelled through the ip packet router by the UDP relay.
+    /// As per RFC1928, the association lasts for as long as the TCP connection stays open.
+    async fn run_udp_association(
+        &mut self,
+        udp_relay: UdpRelaySender,
+    ) -> Result<(), SocksProxyError> {
+        let client_ip = self
+            .stream
+            .peer_addr()
+            .map_err(|source| SocksProxyError::PeerAddrExtractionFailure { source })?
+            .ip();
+        let local_addr = self
+            .stream
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpAssociationFailure { source })?;
+
+        let socket = UdpSocket::bind(SocketAddr::new(local_addr.ip(), 0))
+            .await
+            .map_err(|source| SocksProxyError::UdpAssociationFailure { source })?;
+        let bound_addr = socket
+            .local_addr()
+            .map_err(|source| SocksProxyError::UdpAssociationFailure { source })?;
+
+        let (datagram_sender, datagram_receiver) = mpsc::unbounded();
+        let (response_sender, response_receiver) = oneshot::channel();
+        udp_relay
+            .unbounded_send(UdpRelayCommand::OpenSession {
+                datagram_sender,
+                response: response_sender,
+            })
+            .map_err(|_| SocksProxyError::UdpRelayUnavailable)?;
+        let port = response_receiver
+            .await
+            .ok()
+            .flatten()
+            .ok_or(SocksProxyError::UdpRelayUnavailable)?;
+
+        self.acknowledge_socks5_with_address(bound_addr).await?;
+
+        info!("Starting UDP association on {bound_addr} (port: {port})");
+        let res = self
+            .relay_datagrams(&socket, client_ip, port, &udp_relay, datagram_receiver)
+            .await;
+        info!("UDP association on {bound_addr} is finished (port: {port})");
+
+        // the relay might have already stopped if we're shutting down
+        let _ = udp_relay.unbounded_send(UdpRelayCommand::CloseSession { port });
+        res
+    }
+
+    async fn relay_datagrams(
+        &mut self,
+        socket: &UdpSocket,
+        client_ip: IpAddr,
+        port: u16,
+        udp_relay: &UdpRelaySender,
+        mut datagram_receiver: UdpSessionReceiver,
+    ) -> Result<(), SocksProxyError> {
+        // the client only tells us its UDP address once it sends the first datagram
+        let mut client_addr: Option<SocketAddr> = None;
+        let mut buf = vec![0u8; MAX_UDP_DATAGRAM_SIZE];
+        let mut tcp_buf = [0u8; 64];
+
+        loop {
+            tokio::select! {
+                received = socket.recv_from(&mut buf) => {
+                    let (len, sender) = received
+                        .map_err(|source| SocksProxyError::SocketReadError { source })?;
+                    if sender.ip() != client_ip {
+                        debug!("dropping datagram from unexpected sender {sender}");
+                        continue;
+                    }
+                    client_addr = Some(sender);
+
+                    match UdpDatagram::parse(&buf[..len]) {
+                        Ok(datagram) => {
+                            let command = UdpRelayCommand::SendDatagram {
+                                port,
+                                destination: datagram.destination,
+                                payload: Bytes::copy_from_slice(datagram.payload),
+                            };
+                            udp_relay
+                                .unbounded_send(command)
+                                .map_err(|_| SocksProxyError::UdpRelayUnavailable)?;
+                        }
+                        Err(err) => debug!("dropping datagram from {sender}: {err}"),
+                    }
+                }
+                datagram = datagram_receiver.next() => {
+                    let Some((source, payload)) = datagram else {
+                        return Err(SocksProxyError::UdpRelayUnavailable);
+                    };
+                    let Some(client_addr) = client_addr else {
+                        continue;
+                    };
+                    socket
+                        .send_to(&udp::encode_datagram(source, &payload), client_addr)
+                        .await
+                        .map_err(|source| SocksProxyError::SocketWriteError { source })?;
+                }
+                read = self.stream.read(&mut tcp_buf) => {
+                    match read {
+                        // the client has closed the control connection
+                        Ok(0) => return Ok(()),
+                        Ok(_) => trace!("ignoring data received on the UDP control connection"),
+                        Err(source) => return Err(SocksProxyError::SocketReadError { source }),
+                    }
+                }
+                _ = self.shutdown_listener.recv() => {
+                    log::trace!("SocksClient: Received shutdown");
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    /// Writes a Socks5 reply containing the specified bound address back to the client.
+    async fn acknowledge_socks5_with_address(
+        &mut self,
+        bound_addr: SocketAddr,
+    ) -> Result<(), SocksProxyError> {
+        let mut response = vec![SOCKS5_VERSION, ResponseCodeV5::Success as u8, RESERVED];
+        udp::encode_address(bound_addr, &mut response);
+        self.stream
+            .write_all(&response)
+            .await
+            .map_err(|source| SocksProxyError::SocketWriteError { source })
+    }
+
     /// Writes a Socks5 header back to the requesting client's TCP stream,
     /// basically saying "I acknowledge your request and am dealing with it".
     async fn acknowledge_socks5(&mut self) {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Socks5ClientCoreError;
+use crate::socks::udp_relay::{UdpRelayCommand, UdpRelaySender};
 use futures::channel::mpsc;
 use futures::StreamExt;
 use log::*;
@@ -9,6 +10,7 @@ use nym_client_core::client::received_buffer::ReconstructedMessagesReceiver;
 use nym_client_core::client::received_buffer::{
     ReceivedBufferMessage, ReceivedBufferRequestSender,
 };
+use nym_ip_packet_requests::response::IpPacketResponse;
 use nym_service_providers_common::interface::{ControlResponse, ResponseContent};
 use nym_socks5_proxy_helpers::connection_controller::{ControllerCommand, ControllerSender};
 use nym_socks5_requests::{Socks5ProviderResponse, Socks5Response, Socks5ResponseContent};
@@ -19,6 +21,7 @@ pub(crate) struct MixnetResponseListener {
     buffer_requester: ReceivedBufferRequestSender,
     mix_response_receiver: ReconstructedMessagesReceiver,
     controller_sender: ControllerSender,
+    udp_relay: Option<UdpRelaySender>,
     shutdown: TaskClient,
 }
 
@@ -41,6 +44,7 @@ impl MixnetResponseListener {
     pub(crate) fn new(
         buffer_requester: ReceivedBufferRequestSender,
         controller_sender: ControllerSender,
+        udp_relay: Option<UdpRelaySender>,
         shutdown: TaskClient,
     ) -> Self {
         let (mix_response_sender, mix_response_receiver) = mpsc::unbounded();
@@ -52,6 +56,7 @@ impl MixnetResponseListener {
             buffer_requester,
             mix_response_receiver,
             controller_sender,
+            udp_relay,
             shutdown,
         }
     }
@@ -97,10 +102,44 @@ impl MixnetResponseListener {
         }
     }
 
+    // Attempt to interpret the message as a response from the ip packet router. If it was one,
+    // it's forwarded to the UDP relay and `None` is returned.
+    fn try_forward_ip_packet_router_response(
+        &self,
+        reconstructed_message: ReconstructedMessage,
+    ) -> Option<ReconstructedMessage> {
+        let Some(udp_relay) = &self.udp_relay else {
+            return Some(reconstructed_message);
+        };
+        if reconstructed_message.message.first() != Some(&nym_ip_packet_requests::CURRENT_VERSION) {
+            return Some(reconstructed_message);
+        }
+
+        // the version byte could theoretically collide with a socks5 response,
+        // so if it fails to deserialize, treat it as such instead
+        let Ok(response) = IpPacketResponse::from_reconstructed_message(&reconstructed_message)
+        else {
+            return Some(reconstructed_message);
+        };
+        if udp_relay
+            .unbounded_send(UdpRelayCommand::RouterResponse(response))
+            .is_err()
+        {
+            warn!("failed to forward ip packet router response: the UDP relay has stopped");
+        }
+        None
+    }
+
     fn on_message(
         &self,
         reconstructed_message: ReconstructedMessage,
     ) -> Result<(), Socks5ClientCoreError> {
+        let Some(reconstructed_message) =
+            self.try_forward_ip_packet_router_response(reconstructed_message)
+        else {
+            return Ok(());
+        };
+
         let raw_message = reconstructed_message.message;
         if reconstructed_message.sender_tag.is_some() {
             warn!("this message was sent anonymously - it couldn't have come from the service provider");
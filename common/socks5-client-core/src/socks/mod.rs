@@ -8,6 +8,8 @@ pub(crate) mod mixnet_responses;
 mod request;
 pub mod server;
 pub mod types;
+mod udp;
+pub(crate) mod udp_relay;
 pub mod utils;
 
 /// Version of socks
@@ -2,6 +2,7 @@ use crate::error::Socks5ClientCoreError;
 
 use super::{
     authentication::Authenticator, client::SocksClient, mixnet_responses::MixnetResponseListener,
+    udp_relay::UdpRelay,
 };
 use crate::socks::client;
 use log::*;
@@ -22,6 +23,7 @@ pub struct NymSocksServer {
     authenticator: Authenticator,
     listening_address: SocketAddr,
     service_provider: Recipient,
+    ip_packet_router: Option<Recipient>,
     self_address: Recipient,
     client_config: client::Config,
     lane_queue_lengths: LaneQueueLengths,
@@ -36,6 +38,7 @@ impl NymSocksServer {
         bind_address: SocketAddr,
         authenticator: Authenticator,
         service_provider: Recipient,
+        ip_packet_router: Option<Recipient>,
         self_address: Recipient,
         lane_queue_lengths: LaneQueueLengths,
         client_config: client::Config,
@@ -47,6 +50,7 @@ impl NymSocksServer {
             authenticator,
             listening_address: bind_address,
             service_provider,
+            ip_packet_router,
             self_address,
             client_config,
             lane_queue_lengths,
@@ -78,10 +82,26 @@ impl NymSocksServer {
             active_streams_controller.run().await;
         });
 
+        // relay for datagrams of UDP associations, if we know where to send them
+        let udp_relay = self.ip_packet_router.map(|ip_packet_router| {
+            let (mut udp_relay, udp_relay_sender) = UdpRelay::new(
+                ip_packet_router,
+                self.self_address,
+                input_sender.clone(),
+                Some(self.packet_type),
+                self.shutdown.clone(),
+            );
+            tokio::spawn(async move {
+                udp_relay.run().await;
+            });
+            udp_relay_sender
+        });
+
         // listener for mix messages
         let mut mixnet_response_listener = MixnetResponseListener::new(
             buffer_requester,
             controller_sender.clone(),
+            udp_relay.clone(),
             self.shutdown.clone(),
         );
         tokio::spawn(async move {
@@ -103,6 +123,7 @@ impl NymSocksServer {
                         input_sender.clone(),
                         &self.service_provider,
                         controller_sender.clone(),
+                        udp_relay.clone(),
                         &self.self_address,
                         self.lane_queue_lengths.clone(),
                         self.shutdown.clone(),
@@ -84,9 +84,27 @@ pub enum SocksProxyError {
         source: Socks5RequestError,
     },
 
-    #[error("SOCKS5 UDP not (yet) supported")]
+    #[error("SOCKS5 UDP is not supported without a configured ip packet router")]
     UdpNotSupported,
 
+    #[error("failed to set up the UDP association: {source}")]
+    UdpAssociationFailure {
+        #[source]
+        source: std::io::Error,
+    },
+
+    #[error("the UDP relay is not available")]
+    UdpRelayUnavailable,
+
+    #[error("received malformed UDP datagram")]
+    MalformedUdpDatagram,
+
+    #[error("fragmented UDP datagrams are not supported")]
+    UdpFragmentationNotSupported,
+
+    #[error("UDP datagrams addressed to domain names are not supported")]
+    UdpDomainNotSupported,
+
     #[error("SOCKS5 BIND not (yet) supported")]
     BindNotSupported,
 }
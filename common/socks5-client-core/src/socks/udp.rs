@@ -0,0 +1,159 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+//! Encapsulation of the datagrams exchanged with clients that have issued `UDP ASSOCIATE`.
+//! Each of them is preceded by the following header, as described in RFC1928, section 7:
+//!
+//! +----+------+------+----------+----------+----------+
+//! |RSV | FRAG | ATYP | DST.ADDR | DST.PORT |   DATA   |
+//! +----+------+------+----------+----------+----------+
+//! | 2  |  1   |  1   | Variable |    2     | Variable |
+//! +----+------+------+----------+----------+----------+
+
+use super::types::{AddrType, SocksProxyError};
+use super::RESERVED;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+// RSV + FRAG + ATYP
+const HEADER_PREFIX_LEN: usize = 4;
+
+/// Datagram received from the client that is meant to be forwarded to the specified address.
+#[derive(Debug, PartialEq, Eq)]
+pub(crate) struct UdpDatagram<'a> {
+    pub(crate) destination: SocketAddr,
+    pub(crate) payload: &'a [u8],
+}
+
+impl<'a> UdpDatagram<'a> {
+    pub(crate) fn parse(datagram: &'a [u8]) -> Result<Self, SocksProxyError> {
+        if datagram.len() < HEADER_PREFIX_LEN {
+            return Err(SocksProxyError::MalformedUdpDatagram);
+        }
+
+        // we don't do any reassembly, so only standalone datagrams are accepted
+        if datagram[2] != 0 {
+            return Err(SocksProxyError::UdpFragmentationNotSupported);
+        }
+
+        let remaining = &datagram[HEADER_PREFIX_LEN..];
+        let (ip, remaining) = match AddrType::from(datagram[3] as usize) {
+            Some(AddrType::V4) => {
+                let (octets, remaining) = split_array::<4>(remaining)?;
+                (IpAddr::V4(Ipv4Addr::from(octets)), remaining)
+            }
+            Some(AddrType::V6) => {
+                let (octets, remaining) = split_array::<16>(remaining)?;
+                (IpAddr::V6(Ipv6Addr::from(octets)), remaining)
+            }
+            // the datagrams are sent as raw ip packets, so there's nobody who could resolve it
+            Some(AddrType::Domain) => return Err(SocksProxyError::UdpDomainNotSupported),
+            None => return Err(SocksProxyError::MalformedUdpDatagram),
+        };
+        let (port, payload) = split_array::<2>(remaining)?;
+
+        Ok(UdpDatagram {
+            destination: SocketAddr::new(ip, u16::from_be_bytes(port)),
+            payload,
+        })
+    }
+}
+
+/// Prepends the header to a datagram received from `source` so that it could be sent back to the client.
+pub(crate) fn encode_datagram(source: SocketAddr, payload: &[u8]) -> Vec<u8> {
+    let mut datagram = Vec::with_capacity(HEADER_PREFIX_LEN + 18 + payload.len());
+    datagram.extend_from_slice(&[RESERVED, RESERVED, 0]);
+    encode_address(source, &mut datagram);
+    datagram.extend_from_slice(payload);
+    datagram
+}
+
+/// Writes the ATYP, ADDR and PORT fields for the provided address.
+pub(crate) fn encode_address(address: SocketAddr, out: &mut Vec<u8>) {
+    match address.ip() {
+        IpAddr::V4(ip) => {
+            out.push(AddrType::V4 as u8);
+            out.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            out.push(AddrType::V6 as u8);
+            out.extend_from_slice(&ip.octets());
+        }
+    }
+    out.extend_from_slice(&address.port().to_be_bytes());
+}
+
+fn split_array<const N: usize>(data: &[u8]) -> Result<([u8; N], &[u8]), SocksProxyError> {
+    if data.len() < N {
+        return Err(SocksProxyError::MalformedUdpDatagram);
+    }
+    let (head, tail) = data.split_at(N);
+    // the length has been checked above
+    let mut array = [0u8; N];
+    array.copy_from_slice(head);
+    Ok((array, tail))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parsing_ipv4_datagram() {
+        let raw = [0, 0, 0, 1, 10, 0, 0, 1, 0x01, 0xbb, 42, 43];
+        let datagram = UdpDatagram::parse(&raw).unwrap();
+        assert_eq!(datagram.destination, "10.0.0.1:443".parse().unwrap());
+        assert_eq!(datagram.payload, &[42, 43]);
+    }
+
+    #[test]
+    fn parsing_ipv6_datagram() {
+        let mut raw = vec![0, 0, 0, 4];
+        raw.extend_from_slice(&"2001:db8::1".parse::<Ipv6Addr>().unwrap().octets());
+        raw.extend_from_slice(&[0, 53]);
+        let datagram = UdpDatagram::parse(&raw).unwrap();
+        assert_eq!(datagram.destination, "[2001:db8::1]:53".parse().unwrap());
+        assert!(datagram.payload.is_empty());
+    }
+
+    #[test]
+    fn parsing_rejects_invalid_datagrams() {
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 0]),
+            Err(SocksProxyError::MalformedUdpDatagram)
+        ));
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 0, 1, 10, 0, 0]),
+            Err(SocksProxyError::MalformedUdpDatagram)
+        ));
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 0, 1, 10, 0, 0, 1, 0]),
+            Err(SocksProxyError::MalformedUdpDatagram)
+        ));
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 0, 2, 10, 0, 0, 1, 0, 53]),
+            Err(SocksProxyError::MalformedUdpDatagram)
+        ));
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 1, 1, 10, 0, 0, 1, 0, 53]),
+            Err(SocksProxyError::UdpFragmentationNotSupported)
+        ));
+        assert!(matches!(
+            UdpDatagram::parse(&[0, 0, 0, 3, 3, b'f', b'o', b'o', 0, 53]),
+            Err(SocksProxyError::UdpDomainNotSupported)
+        ));
+    }
+
+    #[test]
+    fn encoded_datagrams_can_be_parsed() {
+        let sources: [SocketAddr; 2] = [
+            "1.2.3.4:5678".parse().unwrap(),
+            "[2001:db8::42]:443".parse().unwrap(),
+        ];
+        for source in sources {
+            let encoded = encode_datagram(source, b"hello");
+            let parsed = UdpDatagram::parse(&encoded).unwrap();
+            assert_eq!(parsed.destination, source);
+            assert_eq!(parsed.payload, b"hello");
+        }
+    }
+}
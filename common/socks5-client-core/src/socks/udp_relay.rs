@@ -0,0 +1,517 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use bytes::{Bytes, BytesMut};
+use etherparse::{InternetSlice, PacketBuilder, SlicedPacket, TransportSlice};
+use futures::channel::{mpsc, oneshot};
+use futures::StreamExt;
+use log::*;
+use nym_client_core::client::inbound_messages::{InputMessage, InputMessageSender};
+use nym_ip_packet_requests::codec::MultiIpPacketCodec;
+use nym_ip_packet_requests::request::IpPacketRequest;
+use nym_ip_packet_requests::response::{
+    DataResponse, DynamicConnectResponse, DynamicConnectResponseReply, IpPacketResponse,
+    IpPacketResponseData,
+};
+use nym_ip_packet_requests::IpPair;
+use nym_sphinx::addressing::clients::Recipient;
+use nym_sphinx::params::PacketType;
+use nym_task::connections::TransmissionLane;
+use nym_task::TaskClient;
+use std::collections::{HashMap, VecDeque};
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+use tokio::time::Instant;
+use tokio_util::codec::Decoder;
+
+// Source ports assigned to the associations are taken from the dynamic range (RFC6335)
+const EPHEMERAL_PORT_START: u16 = 49152;
+
+// Maximum number of datagrams held while waiting for the ip packet router to assign us addresses
+const MAX_QUEUED_DATAGRAMS: usize = 64;
+
+const DEFAULT_TTL: u8 = 64;
+
+// How long we wait for the ip packet router to respond to the connect request
+// before failing the associations that are waiting for it
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(crate) type UdpRelaySender = mpsc::UnboundedSender<UdpRelayCommand>;
+type UdpRelayReceiver = mpsc::UnboundedReceiver<UdpRelayCommand>;
+
+/// Channel used for delivering datagrams, alongside their source addresses, back to the association.
+pub(crate) type UdpSessionSender = mpsc::UnboundedSender<(SocketAddr, Bytes)>;
+pub(crate) type UdpSessionReceiver = mpsc::UnboundedReceiver<(SocketAddr, Bytes)>;
+
+pub(crate) enum UdpRelayCommand {
+    /// Register new association. The response contains the source port assigned to it,
+    /// or `None` if all ports are already in use.
+    OpenSession {
+        datagram_sender: UdpSessionSender,
+        response: oneshot::Sender<Option<u16>>,
+    },
+
+    /// Remove the association using the specified source port.
+    CloseSession { port: u16 },
+
+    /// Forward the payload to the destination on behalf of the association using the specified port.
+    SendDatagram {
+        port: u16,
+        destination: SocketAddr,
+        payload: Bytes,
+    },
+
+    /// Response received from the ip packet router.
+    RouterResponse(IpPacketResponse),
+}
+
+// Maps the source ports of the packets we send to the associations that sent them,
+// so that the responses could be routed back to the correct clients.
+// The table lives on the client side as all associations share the single address pair
+// the ip packet router has assigned to us, and the router only ever sees the translated packets.
+struct NatTable {
+    sessions: HashMap<u16, UdpSessionSender>,
+    next_port: u16,
+}
+
+impl Default for NatTable {
+    fn default() -> Self {
+        NatTable {
+            sessions: HashMap::new(),
+            next_port: EPHEMERAL_PORT_START,
+        }
+    }
+}
+
+impl NatTable {
+    fn insert(&mut self, sender: UdpSessionSender) -> Option<u16> {
+        let available_ports = (u16::MAX - EPHEMERAL_PORT_START) as usize + 1;
+        if self.sessions.len() >= available_ports {
+            return None;
+        }
+
+        loop {
+            let port = self.next_port;
+            self.next_port = if port == u16::MAX {
+                EPHEMERAL_PORT_START
+            } else {
+                port + 1
+            };
+
+            if !self.sessions.contains_key(&port) {
+                self.sessions.insert(port, sender);
+                return Some(port);
+            }
+        }
+    }
+
+    fn remove(&mut self, port: u16) {
+        self.sessions.remove(&port);
+    }
+
+    fn get(&self, port: u16) -> Option<&UdpSessionSender> {
+        self.sessions.get(&port)
+    }
+}
+
+struct QueuedDatagram {
+    port: u16,
+    destination: SocketAddr,
+    payload: Bytes,
+}
+
+enum RouterConnection {
+    Disconnected,
+    Connecting {
+        request_id: u64,
+        deadline: Instant,
+        queued: VecDeque<QueuedDatagram>,
+    },
+    Connected {
+        ips: IpPair,
+    },
+}
+
+/// Relays the datagrams of all UDP associations through the ip packet router.
+/// The relay connects to the router upon receiving the first datagram and then wraps all of them
+/// in UDP/IP packets using the addresses it got assigned.
+pub(crate) struct UdpRelay {
+    ip_packet_router: Recipient,
+    self_address: Recipient,
+    input_sender: InputMessageSender,
+    packet_type: Option<PacketType>,
+    nat_table: NatTable,
+    connection: RouterConnection,
+    command_receiver: UdpRelayReceiver,
+    shutdown: TaskClient,
+}
+
+impl UdpRelay {
+    pub(crate) fn new(
+        ip_packet_router: Recipient,
+        self_address: Recipient,
+        input_sender: InputMessageSender,
+        packet_type: Option<PacketType>,
+        shutdown: TaskClient,
+    ) -> (Self, UdpRelaySender) {
+        let (command_sender, command_receiver) = mpsc::unbounded();
+        (
+            UdpRelay {
+                ip_packet_router,
+                self_address,
+                input_sender,
+                packet_type,
+                nat_table: NatTable::default(),
+                connection: RouterConnection::Disconnected,
+                command_receiver,
+                shutdown,
+            },
+            command_sender,
+        )
+    }
+
+    async fn send_to_router(&self, request: IpPacketRequest) {
+        let message = match request.to_bytes() {
+            Ok(message) => message,
+            Err(err) => {
+                error!("failed to serialize ip packet router request: {err}");
+                return;
+            }
+        };
+
+        let input_message = InputMessage::new_regular(
+            self.ip_packet_router,
+            message,
+            TransmissionLane::General,
+            self.packet_type,
+        );
+        if self.input_sender.send(input_message).await.is_err() {
+            error!("InputMessageReceiver has stopped receiving!");
+        }
+    }
+
+    async fn send_datagram(&mut self, datagram: QueuedDatagram) {
+        match &mut self.connection {
+            RouterConnection::Connected { ips } => {
+                let ips = *ips;
+                let Some(packet) =
+                    build_udp_packet(&ips, datagram.port, datagram.destination, &datagram.payload)
+                else {
+                    return;
+                };
+                let request = IpPacketRequest::new_data_request(
+                    MultiIpPacketCodec::bundle_one_packet(packet),
+                );
+                self.send_to_router(request).await;
+            }
+            RouterConnection::Connecting { queued, .. } => {
+                if queued.len() < MAX_QUEUED_DATAGRAMS {
+                    queued.push_back(datagram);
+                } else {
+                    debug!(
+                        "dropping datagram to {} as the queue is full",
+                        datagram.destination
+                    );
+                }
+            }
+            RouterConnection::Disconnected => {
+                debug!("connecting to the ip packet router");
+                let (request, request_id) = IpPacketRequest::new_dynamic_connect_request(
+                    self.self_address,
+                    None,
+                    None,
+                    None,
+                );
+                self.connection = RouterConnection::Connecting {
+                    request_id,
+                    deadline: Instant::now() + CONNECT_TIMEOUT,
+                    queued: VecDeque::from([datagram]),
+                };
+                self.send_to_router(request).await;
+            }
+        }
+    }
+
+    async fn on_connect_response(&mut self, response: DynamicConnectResponse) {
+        match &self.connection {
+            RouterConnection::Connecting { request_id, .. }
+                if *request_id == response.request_id => {}
+            _ => {
+                debug!("received unexpected connect response from the ip packet router");
+                return;
+            }
+        }
+
+        let ips = match response.reply {
+            DynamicConnectResponseReply::Success(success) => success.ips,
+            DynamicConnectResponseReply::Failure(reason) => {
+                warn!("failed to connect to the ip packet router: {reason}");
+                self.abandon_connection();
+                return;
+            }
+        };
+
+        info!("connected to the ip packet router. {ips}");
+        let previous = std::mem::replace(&mut self.connection, RouterConnection::Connected { ips });
+        if let RouterConnection::Connecting { queued, .. } = previous {
+            for datagram in queued {
+                self.send_datagram(datagram).await;
+            }
+        }
+    }
+
+    fn on_connect_timeout(&mut self) {
+        warn!("timed out while waiting for the ip packet router to accept our connection");
+        self.abandon_connection();
+    }
+
+    // Closes the associations whose datagrams were waiting for the connection,
+    // so that their clients would get notified instead of waiting forever.
+    fn abandon_connection(&mut self) {
+        let previous = std::mem::replace(&mut self.connection, RouterConnection::Disconnected);
+        if let RouterConnection::Connecting { queued, .. } = previous {
+            for datagram in queued {
+                self.nat_table.remove(datagram.port);
+            }
+        }
+    }
+
+    fn on_data_response(&mut self, response: DataResponse) {
+        let mut decoder = MultiIpPacketCodec::new(nym_ip_packet_requests::codec::BUFFER_TIMEOUT);
+        let mut bytes = BytesMut::from(response.ip_packet.as_ref());
+        while let Ok(Some(packet)) = decoder.decode(&mut bytes) {
+            let Some((source, port, payload)) = parse_udp_packet(&packet) else {
+                trace!("received packet that is not a valid UDP packet");
+                continue;
+            };
+            let Some(session) = self.nat_table.get(port) else {
+                trace!("received datagram for port {port} without an association");
+                continue;
+            };
+            if session
+                .unbounded_send((source, Bytes::copy_from_slice(payload)))
+                .is_err()
+            {
+                // the association has already finished
+                self.nat_table.remove(port);
+            }
+        }
+    }
+
+    async fn on_router_response(&mut self, response: IpPacketResponse) {
+        match response.data {
+            IpPacketResponseData::DynamicConnect(response) => {
+                self.on_connect_response(response).await
+            }
+            IpPacketResponseData::Data(response) => self.on_data_response(response),
+            IpPacketResponseData::UnrequestedDisconnect(disconnect) => {
+                warn!(
+                    "the ip packet router has disconnected us: {}",
+                    disconnect.reason
+                );
+                self.connection = RouterConnection::Disconnected;
+            }
+            _ => trace!("ignoring ip packet router response"),
+        }
+    }
+
+    async fn on_command(&mut self, command: UdpRelayCommand) {
+        match command {
+            UdpRelayCommand::OpenSession {
+                datagram_sender,
+                response,
+            } => {
+                let port = self.nat_table.insert(datagram_sender);
+                if response.send(port).is_err() {
+                    // the association got dropped before it even started
+                    if let Some(port) = port {
+                        self.nat_table.remove(port)
+                    }
+                }
+            }
+            UdpRelayCommand::CloseSession { port } => self.nat_table.remove(port),
+            UdpRelayCommand::SendDatagram {
+                port,
+                destination,
+                payload,
+            } => {
+                self.send_datagram(QueuedDatagram {
+                    port,
+                    destination,
+                    payload,
+                })
+                .await
+            }
+            UdpRelayCommand::RouterResponse(response) => self.on_router_response(response).await,
+        }
+    }
+
+    pub(crate) async fn run(&mut self) {
+        while !self.shutdown.is_shutdown() {
+            tokio::select! {
+                command = self.command_receiver.next() => {
+                    if let Some(command) = command {
+                        self.on_command(command).await
+                    } else {
+                        log::trace!("UdpRelay: Stopping since channel closed");
+                        break;
+                    }
+                },
+                _ = connect_timeout(&self.connection) => self.on_connect_timeout(),
+                _ = self.shutdown.recv() => {
+                    log::trace!("UdpRelay: Received shutdown");
+                }
+            }
+        }
+        self.shutdown.recv_timeout().await;
+        log::debug!("UdpRelay: Exiting");
+    }
+}
+
+async fn connect_timeout(connection: &RouterConnection) {
+    match connection {
+        RouterConnection::Connecting { deadline, .. } => tokio::time::sleep_until(*deadline).await,
+        _ => futures::future::pending().await,
+    }
+}
+
+fn build_udp_packet(
+    ips: &IpPair,
+    source_port: u16,
+    destination: SocketAddr,
+    payload: &[u8],
+) -> Option<Bytes> {
+    let builder = match destination.ip() {
+        IpAddr::V4(ip) => PacketBuilder::ipv4(ips.ipv4.octets(), ip.octets(), DEFAULT_TTL),
+        IpAddr::V6(ip) => PacketBuilder::ipv6(ips.ipv6.octets(), ip.octets(), DEFAULT_TTL),
+    }
+    .udp(source_port, destination.port());
+
+    let mut packet = Vec::with_capacity(builder.size(payload.len()));
+    if let Err(err) = builder.write(&mut packet, payload) {
+        warn!("failed to construct UDP packet for {destination}: {err}");
+        return None;
+    }
+    Some(packet.into())
+}
+
+// Returns the source of the datagram alongside the destination port and the payload
+fn parse_udp_packet(packet: &[u8]) -> Option<(SocketAddr, u16, &[u8])> {
+    let headers = SlicedPacket::from_ip(packet).ok()?;
+    let Some(TransportSlice::Udp(udp_header)) = headers.transport else {
+        return None;
+    };
+    let source_ip: IpAddr = match headers.ip? {
+        InternetSlice::Ipv4(ipv4_header, _) => ipv4_header.source_addr().into(),
+        InternetSlice::Ipv6(ipv6_header, _) => ipv6_header.source_addr().into(),
+    };
+
+    Some((
+        SocketAddr::new(source_ip, udp_header.source_port()),
+        udp_header.destination_port(),
+        headers.payload,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::executor::block_on;
+
+    fn recipient() -> Recipient {
+        Recipient::try_from_base58_string("D1rrpsysCGCYXy9saP8y3kmNpGtJZUXN9SvFoUcqAsM9.9Ssso1ea5NfkbMASdiseDSjTN1fSWda5SgEVjdSN4CvV@GJqd3ZxpXWSNxTfx7B1pPtswpetH4LnJdFeLeuY5KUuN").unwrap()
+    }
+
+    #[test]
+    fn nat_table_assigns_unique_ephemeral_ports() {
+        let (sender, _receiver) = mpsc::unbounded();
+        let mut table = NatTable::default();
+
+        let first = table.insert(sender.clone()).unwrap();
+        let second = table.insert(sender.clone()).unwrap();
+        assert_eq!(first, EPHEMERAL_PORT_START);
+        assert_ne!(first, second);
+
+        // ports are not immediately reused after being released
+        table.remove(first);
+        let third = table.insert(sender).unwrap();
+        assert_ne!(third, first);
+        assert!(table.get(first).is_none());
+        assert!(table.get(third).is_some());
+    }
+
+    #[test]
+    fn nat_table_gets_exhausted() {
+        let (sender, _receiver) = mpsc::unbounded();
+        let mut table = NatTable::default();
+
+        let available = (u16::MAX - EPHEMERAL_PORT_START) as usize + 1;
+        for _ in 0..available {
+            assert!(table.insert(sender.clone()).is_some());
+        }
+        assert!(table.insert(sender.clone()).is_none());
+
+        // once any port is released, it can be assigned again
+        table.remove(50000);
+        assert_eq!(table.insert(sender), Some(50000));
+    }
+
+    #[test]
+    fn waiting_associations_fail_when_connecting_times_out() {
+        let (input_sender, _input_receiver) = tokio::sync::mpsc::channel(8);
+        let (mut relay, _) = UdpRelay::new(
+            recipient(),
+            recipient(),
+            input_sender,
+            None,
+            TaskClient::dummy(),
+        );
+
+        let (waiting_sender, mut waiting_receiver) = mpsc::unbounded();
+        let (idle_sender, mut idle_receiver) = mpsc::unbounded();
+        let waiting = relay.nat_table.insert(waiting_sender).unwrap();
+        let idle = relay.nat_table.insert(idle_sender).unwrap();
+
+        block_on(relay.send_datagram(QueuedDatagram {
+            port: waiting,
+            destination: "1.1.1.1:53".parse().unwrap(),
+            payload: Bytes::from_static(b"query"),
+        }));
+        assert!(matches!(
+            relay.connection,
+            RouterConnection::Connecting { .. }
+        ));
+
+        relay.on_connect_timeout();
+        assert!(matches!(relay.connection, RouterConnection::Disconnected));
+
+        // the association that was waiting for the connection gets closed
+        assert!(relay.nat_table.get(waiting).is_none());
+        assert_eq!(block_on(waiting_receiver.next()), None);
+
+        // while the one that hasn't sent anything yet is unaffected
+        assert!(relay.nat_table.get(idle).is_some());
+        assert!(idle_receiver.try_next().is_err());
+    }
+
+    #[test]
+    fn udp_packets_roundtrip() {
+        let ips = IpPair::new("10.0.0.5".parse().unwrap(), "fd00::5".parse().unwrap());
+        let destinations: [SocketAddr; 2] = [
+            "1.1.1.1:53".parse().unwrap(),
+            "[2606:4700::1111]:443".parse().unwrap(),
+        ];
+
+        for destination in destinations {
+            let packet = build_udp_packet(&ips, 50123, destination, b"payload").unwrap();
+            let (source, port, payload) = parse_udp_packet(&packet).unwrap();
+            match destination {
+                SocketAddr::V4(_) => assert_eq!(source.ip(), IpAddr::V4(ips.ipv4)),
+                SocketAddr::V6(_) => assert_eq!(source.ip(), IpAddr::V6(ips.ipv6)),
+            }
+            assert_eq!(source.port(), 50123);
+            assert_eq!(port, destination.port());
+            assert_eq!(payload, b"payload");
+        }
+    }
+}
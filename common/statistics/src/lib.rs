@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use error::StatsError;
 
@@ -36,6 +37,14 @@ pub enum StatsData {
 pub struct StatsGatewayData {
     pub gateway_id: String,
     pub inbox_count: u32,
+
+    /// Number of accepted client connections keyed by the reported protocol version.
+    #[serde(default)]
+    pub client_protocol_versions: HashMap<String, u64>,
+
+    /// Number of client connections rejected by the gateway keyed by the reported protocol version.
+    #[serde(default)]
+    pub rejected_client_protocol_versions: HashMap<String, u64>,
 }
 
 impl StatsGatewayData {
@@ -43,8 +52,21 @@ impl StatsGatewayData {
         StatsGatewayData {
             gateway_id,
             inbox_count,
+            client_protocol_versions: HashMap::new(),
+            rejected_client_protocol_versions: HashMap::new(),
         }
     }
+
+    #[must_use]
+    pub fn with_client_protocol_versions(
+        mut self,
+        accepted: HashMap<String, u64>,
+        rejected: HashMap<String, u64>,
+    ) -> Self {
+        self.client_protocol_versions = accepted;
+        self.rejected_client_protocol_versions = rejected;
+        self
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
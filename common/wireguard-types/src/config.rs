@@ -1,7 +1,17 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use crate::error::Error;
+use crate::MacAlgorithm;
 use std::net::{IpAddr, SocketAddr};
+use std::time::{Duration, SystemTime};
+use tokio::sync::watch;
+
+/// Time clients are asked to wait before attempting to register with a gateway that has reached its capacity.
+pub const REGISTRY_FULL_RETRY_AFTER: Duration = Duration::from_secs(5 * 60);
+
+pub type ConfigSender = watch::Sender<Config>;
+pub type ConfigReceiver = watch::Receiver<Config>;
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Debug)]
 pub struct Config {
@@ -20,4 +30,113 @@ pub struct Config {
     /// The prefix denoting the maximum number of the clients that can be connected via Wireguard.
     /// The maximum value for IPv4 is 32 and for IPv6 is 128
     pub private_network_prefix: u8,
+
+    /// Mac algorithm preferred for authenticating client registrations,
+    /// if supported by the registering client.
+    pub registration_mac: MacAlgorithm,
+
+    /// Maximum number of peers that can be registered with the gateway at the same time.
+    /// If not set, it's only limited by the size of the private network.
+    pub max_registered_peers: Option<usize>,
+
+    /// Number of registration requests per minute beyond which clients have to solve
+    /// a proof of work challenge, with its difficulty growing alongside the request rate.
+    /// If not set, the challenges are never issued.
+    pub pow_rate_threshold: Option<u32>,
+
+    /// Duration for which the registrations remain valid, after which the peers are removed,
+    /// unless they renew them in the meantime.
+    /// If not set, the registrations never expire.
+    pub registration_ttl: Option<Duration>,
+
+    /// Specifies whether clients have to present a valid bandwidth credential
+    /// when registering, renewing their registrations or rotating their keys.
+    pub require_bandwidth_credential: bool,
+}
+
+impl Config {
+    /// Make sure the updated config only changes values that can be applied at runtime.
+    /// The bind address and the private network are fixed for the lifetime of the interface,
+    /// as changing them would invalidate the addresses of the already registered peers.
+    pub fn ensure_reloadable(&self, updated: &Config) -> Result<(), Error> {
+        let field = if self.bind_address != updated.bind_address {
+            "bind_address"
+        } else if self.private_ip != updated.private_ip {
+            "private_ip"
+        } else if self.private_network_prefix != updated.private_network_prefix {
+            "private_network_prefix"
+        } else {
+            return Ok(());
+        };
+
+        Err(Error::NonReloadableConfigChange { field })
+    }
+
+    /// Make sure another peer could be registered given the number of the currently registered ones.
+    pub fn ensure_registry_capacity(&self, registered_peers: usize) -> Result<(), Error> {
+        match self.max_registered_peers {
+            Some(max) if registered_peers >= max => Err(Error::RegistryFull {
+                retry_after: REGISTRY_FULL_RETRY_AFTER,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Time at which the registration made, or renewed, at the provided time is going to expire,
+    /// if the registrations are time-bound.
+    pub fn registration_expiry(&self, now: SystemTime) -> Option<SystemTime> {
+        self.registration_ttl.map(|ttl| now + ttl)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        Config {
+            bind_address: "0.0.0.0:51822".parse().unwrap(),
+            private_ip: "10.1.0.1".parse().unwrap(),
+            announced_port: 51822,
+            private_network_prefix: 16,
+            registration_mac: MacAlgorithm::default(),
+            max_registered_peers: None,
+            pow_rate_threshold: None,
+            registration_ttl: None,
+            require_bandwidth_credential: false,
+        }
+    }
+
+    #[test]
+    fn only_runtime_values_can_be_reloaded() {
+        let current = config();
+
+        let mut updated = config();
+        updated.announced_port = 51823;
+        updated.registration_mac = MacAlgorithm::Blake3Keyed;
+        updated.max_registered_peers = Some(100);
+        updated.pow_rate_threshold = Some(60);
+        updated.registration_ttl = Some(Duration::from_secs(24 * 60 * 60));
+        assert!(current.ensure_reloadable(&updated).is_ok());
+
+        updated.private_network_prefix = 24;
+        assert!(matches!(
+            current.ensure_reloadable(&updated),
+            Err(Error::NonReloadableConfigChange {
+                field: "private_network_prefix"
+            })
+        ));
+    }
+    #[test]
+    fn registry_capacity() {
+        let mut config = config();
+        assert!(config.ensure_registry_capacity(usize::MAX - 1).is_ok());
+
+        config.max_registered_peers = Some(2);
+        assert!(config.ensure_registry_capacity(1).is_ok());
+        assert!(matches!(
+            config.ensure_registry_capacity(2),
+            Err(Error::RegistryFull { retry_after }) if retry_after == REGISTRY_FULL_RETRY_AFTER
+        ));
+    }
 }
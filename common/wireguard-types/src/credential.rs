@@ -0,0 +1,245 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::PeerPublicKey;
+use async_trait::async_trait;
+use base64::{engine::general_purpose, Engine};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::ops::Deref;
+use std::str::FromStr;
+use std::sync::{Arc, OnceLock};
+
+/// Serialised presentation of a bandwidth (ecash) credential attached to the registration request.
+/// Its content is opaque to the registration flow itself and is only interpreted by the [CredentialVerifier]
+/// configured by the gateway, so that the same credentials could be used for both mixnet and wireguard modes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BandwidthCredential(Vec<u8>);
+
+impl BandwidthCredential {
+    pub fn new(serialised_presentation: Vec<u8>) -> Self {
+        BandwidthCredential(serialised_presentation)
+    }
+}
+
+impl fmt::Display for BandwidthCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", general_purpose::STANDARD.encode(&self.0))
+    }
+}
+
+impl Deref for BandwidthCredential {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl FromStr for BandwidthCredential {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let credential_bytes = general_purpose::STANDARD
+            .decode(s)
+            .map_err(|source| Error::MalformedBandwidthCredential { source })?;
+
+        Ok(BandwidthCredential(credential_bytes))
+    }
+}
+
+impl Serialize for BandwidthCredential {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let encoded = general_purpose::STANDARD.encode(&self.0);
+        serializer.serialize_str(&encoded)
+    }
+}
+
+impl<'de> Deserialize<'de> for BandwidthCredential {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let encoded = String::deserialize(deserializer)?;
+        BandwidthCredential::from_str(&encoded).map_err(serde::de::Error::custom)
+    }
+}
+
+/// Hook invoked by the gateway right before it completes registering a new wireguard peer.
+/// If the gateway has a verifier configured, a peer can't be registered without presenting
+/// a credential it accepts.
+#[async_trait]
+pub trait CredentialVerifier: Send + Sync {
+    /// Verify (and, if applicable, spend) the credential presented by the registering client.
+    async fn verify_credential(
+        &self,
+        client: PeerPublicKey,
+        credential: &BandwidthCredential,
+    ) -> Result<(), Error>;
+}
+
+/// Verifier shared by all the components handling the registrations.
+/// The gateway can only construct it once it has connected to the chain, which might happen after
+/// the registration endpoints have already been exposed, hence it's installed at runtime.
+#[derive(Clone, Default)]
+pub struct SharedCredentialVerifier(Arc<OnceLock<Arc<dyn CredentialVerifier>>>);
+
+impl SharedCredentialVerifier {
+    pub fn new(verifier: Arc<dyn CredentialVerifier>) -> Self {
+        let shared = SharedCredentialVerifier::default();
+        shared.install(verifier);
+        shared
+    }
+
+    /// Install the verifier. Returns `false` if one has already been installed, in which case it's retained.
+    pub fn install(&self, verifier: Arc<dyn CredentialVerifier>) -> bool {
+        self.0.set(verifier).is_ok()
+    }
+
+    pub fn get(&self) -> Option<&dyn CredentialVerifier> {
+        self.0.get().map(|verifier| verifier.as_ref())
+    }
+}
+
+impl fmt::Debug for SharedCredentialVerifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedCredentialVerifier")
+            .field("installed", &self.0.get().is_some())
+            .finish()
+    }
+}
+
+/// Make sure the client has presented a credential, if the gateway requires one, without verifying it.
+/// It's meant for rejecting the requests early, as the verification itself spends the credential.
+/// If the credentials are required, but the verifier hasn't been installed yet, all the requests are rejected.
+pub fn ensure_registration_credential(
+    verifier: Option<&dyn CredentialVerifier>,
+    required: bool,
+    client: PeerPublicKey,
+    credential: Option<&BandwidthCredential>,
+) -> Result<(), Error> {
+    if verifier.is_none() {
+        if required {
+            return Err(Error::CredentialVerifierUnavailable);
+        }
+        return Ok(());
+    }
+
+    if credential.is_none() {
+        return Err(Error::MissingBandwidthCredential {
+            client: client.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Make sure the client has presented a valid credential, if the gateway requires one.
+/// If the credentials are required, but the verifier hasn't been installed yet, all the requests are rejected.
+pub async fn verify_registration_credential(
+    verifier: Option<&dyn CredentialVerifier>,
+    required: bool,
+    client: PeerPublicKey,
+    credential: Option<&BandwidthCredential>,
+) -> Result<(), Error> {
+    ensure_registration_credential(verifier, required, client, credential)?;
+    match (verifier, credential) {
+        (Some(verifier), Some(credential)) => verifier.verify_credential(client, credential).await,
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct AcceptNonEmpty;
+
+    #[async_trait]
+    impl CredentialVerifier for AcceptNonEmpty {
+        async fn verify_credential(
+            &self,
+            client: PeerPublicKey,
+            credential: &BandwidthCredential,
+        ) -> Result<(), Error> {
+            if credential.is_empty() {
+                return Err(Error::InvalidBandwidthCredential {
+                    client: client.to_string(),
+                    reason: "empty credential".to_string(),
+                });
+            }
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn credential_is_required_only_with_configured_verifier() {
+        let client = PeerPublicKey::new(x25519_dalek::PublicKey::from([1; 32]));
+        let valid = BandwidthCredential::new(vec![1, 2, 3]);
+        let invalid = BandwidthCredential::new(Vec::new());
+
+        assert!(verify_registration_credential(None, false, client, None)
+            .await
+            .is_ok());
+        assert!(matches!(
+            verify_registration_credential(Some(&AcceptNonEmpty), true, client, None).await,
+            Err(Error::MissingBandwidthCredential { .. })
+        ));
+        assert!(matches!(
+            verify_registration_credential(Some(&AcceptNonEmpty), true, client, Some(&invalid))
+                .await,
+            Err(Error::InvalidBandwidthCredential { .. })
+        ));
+        assert!(
+            verify_registration_credential(Some(&AcceptNonEmpty), true, client, Some(&valid))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn ensuring_credential_presence_does_not_verify_it() {
+        let client = PeerPublicKey::new(x25519_dalek::PublicKey::from([1; 32]));
+        let invalid = BandwidthCredential::new(Vec::new());
+
+        assert!(ensure_registration_credential(None, false, client, None).is_ok());
+        assert!(matches!(
+            ensure_registration_credential(None, true, client, Some(&invalid)),
+            Err(Error::CredentialVerifierUnavailable)
+        ));
+        assert!(matches!(
+            ensure_registration_credential(Some(&AcceptNonEmpty), true, client, None),
+            Err(Error::MissingBandwidthCredential { .. })
+        ));
+        assert!(ensure_registration_credential(
+            Some(&AcceptNonEmpty),
+            true,
+            client,
+            Some(&invalid)
+        )
+        .is_ok());
+    }
+
+    #[tokio::test]
+    async fn required_credentials_are_rejected_until_the_verifier_is_installed() {
+        let client = PeerPublicKey::new(x25519_dalek::PublicKey::from([1; 32]));
+        let valid = BandwidthCredential::new(vec![1, 2, 3]);
+
+        let shared = SharedCredentialVerifier::default();
+        assert!(matches!(
+            verify_registration_credential(shared.get(), true, client, Some(&valid)).await,
+            Err(Error::CredentialVerifierUnavailable)
+        ));
+
+        assert!(shared.install(Arc::new(AcceptNonEmpty)));
+        assert!(!shared.clone().install(Arc::new(AcceptNonEmpty)));
+        assert!(
+            verify_registration_credential(shared.get(), true, client, Some(&valid))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn malformed_credentials_are_not_echoed_back() {
+        let err = "not-base64!".parse::<BandwidthCredential>().unwrap_err();
+        assert!(!err.to_string().contains("not-base64!"));
+    }
+}
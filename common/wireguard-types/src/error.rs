@@ -1,6 +1,9 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -25,11 +28,188 @@ pub enum Error {
         decoded_length: usize,
     },
 
-    #[cfg(feature = "verify")]
-    #[error("failed to verify mac provided by '{client}': {source}")]
-    FailedClientMacVerification {
+    #[error("peer '{client}' is not registered with the gateway")]
+    PeerNotRegistered { client: String },
+
+    #[error("peer '{client}' has been suspended by the gateway operator")]
+    PeerSuspended { client: String },
+
+    #[error("the requested private ip {ip} is outside the gateway's private network")]
+    RequestedIpOutsideNetwork { ip: IpAddr },
+
+    #[error("the requested private ip {ip} is already taken by another client")]
+    RequestedIpUnavailable { ip: IpAddr },
+
+    #[error("the wireguard '{field}' can't be changed without restarting the node")]
+    NonReloadableConfigChange { field: &'static str },
+
+    #[error("the gateway can't accept any more peers. try again in {} seconds", retry_after.as_secs())]
+    RegistryFull { retry_after: Duration },
+
+    #[error(
+        "failed to exchange registration messages over any of the available transports: {failures}"
+    )]
+    RegistrationTransportsExhausted { failures: String },
+
+    #[error("the provided base64-encoded bandwidth credential was malformed: {source}")]
+    MalformedBandwidthCredential {
+        #[source]
+        source: base64::DecodeError,
+    },
+
+    #[error(
+        "client '{client}' has not presented a bandwidth credential required for the registration"
+    )]
+    MissingBandwidthCredential { client: String },
+
+    #[error("the bandwidth credential presented by '{client}' is invalid: {reason}")]
+    InvalidBandwidthCredential { client: String, reason: String },
+
+    #[error("the gateway is not able to verify bandwidth credentials at the moment")]
+    CredentialVerifierUnavailable,
+
+    #[error("failed to parse the wireguard peer dump line '{line}': {reason}")]
+    MalformedPeerDump { line: String, reason: String },
+
+    #[error("client '{client}' has not provided a solution to the proof of work challenge")]
+    MissingProofOfWork { client: String },
+
+    #[error("client '{client}' has provided an invalid solution to the proof of work challenge of difficulty {difficulty}")]
+    InvalidProofOfWork { client: String, difficulty: u8 },
+
+    #[error("the registration message was malformed: {reason}")]
+    MalformedRegistrationMessage { reason: String },
+
+    #[error("the mac provided by '{client}' does not match the registration data. expected a {expected_len} bytes long mac computed with the most recent nonce")]
+    MacMismatch {
         client: String,
+        expected_len: usize,
         #[source]
-        source: hmac::digest::MacError,
+        source: Box<dyn std::error::Error + Send + Sync>,
     },
+
+    #[error("there is no registration in progress for '{client}'. its nonce has either already been used or got superseded by a newer registration attempt")]
+    StaleNonce { client: String },
+
+    #[error("the key rotation request of '{client}' is stale. it has to be sent within {} seconds of its timestamp", max_age.as_secs())]
+    StaleKeyRotation { client: String, max_age: Duration },
+
+    #[error("the key rotation request of '{client}' does not prove the possession of the new key")]
+    MissingNewKeyPossessionProof { client: String },
+
+    #[error("the nonce of the key rotation request of '{client}' has already been used")]
+    ReplayedKeyRotation { client: String },
+
+    #[error("the registration renewal request of '{client}' is stale. it has to be sent within {} seconds of its timestamp", max_age.as_secs())]
+    StaleRenewal { client: String, max_age: Duration },
+
+    #[error("registration protocol version {version} is no longer supported. the minimum supported version is {min_supported}")]
+    UnsupportedClientVersion { version: u8, min_supported: u8 },
+
+    #[error("the client provisioning payload is malformed: {reason}")]
+    MalformedProvisioningPayload { reason: String },
+
+    #[error("the wg-quick configuration is malformed at line {line}: {reason}")]
+    MalformedWgQuickConfig { line: usize, reason: String },
+}
+
+impl Error {
+    /// Machine-readable kind of the registration failure that could be attached to the response,
+    /// so that the clients could act on it.
+    pub fn registration_error_kind(&self) -> Option<RegistrationErrorKind> {
+        match self {
+            Error::MalformedPeerPublicKeyEncoding { .. }
+            | Error::InvalidPeerPublicKeyLength { .. } => {
+                Some(RegistrationErrorKind::MalformedPublicKey)
+            }
+            Error::MalformedRegistrationMessage { .. } => {
+                Some(RegistrationErrorKind::MalformedMessage)
+            }
+            Error::MacMismatch { expected_len, .. } => Some(RegistrationErrorKind::MacMismatch {
+                expected_len: *expected_len,
+            }),
+            Error::StaleNonce { .. } => Some(RegistrationErrorKind::StaleNonce),
+            Error::UnsupportedClientVersion {
+                version,
+                min_supported,
+            } => Some(RegistrationErrorKind::UnsupportedClientVersion {
+                version: *version,
+                min_supported: *min_supported,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// Machine-readable kind of the registration failure returned alongside the error message.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum RegistrationErrorKind {
+    /// The public key included in the message is not a valid base64-encoded x25519 key.
+    /// It's most likely a bug in the client.
+    MalformedPublicKey,
+
+    /// The message could not be deserialized.
+    MalformedMessage,
+
+    /// The mac did not verify. The client should restart the registration to obtain a fresh nonce.
+    MacMismatch { expected_len: usize },
+
+    /// The nonce is no longer valid. The client should restart the registration.
+    StaleNonce,
+
+    /// The client has to be updated in order to register with this gateway.
+    UnsupportedClientVersion { version: u8, min_supported: u8 },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::PeerPublicKey;
+    use std::str::FromStr;
+
+    #[test]
+    fn public_key_parsing_failures_are_recognised() {
+        let err = PeerPublicKey::from_str("Zm9vbXA=").unwrap_err();
+        assert!(matches!(err, Error::InvalidPeerPublicKeyLength { .. }));
+        assert_eq!(
+            err.registration_error_kind(),
+            Some(RegistrationErrorKind::MalformedPublicKey)
+        );
+
+        let err = PeerPublicKey::from_str("not base64!").unwrap_err();
+        assert_eq!(
+            err.registration_error_kind(),
+            Some(RegistrationErrorKind::MalformedPublicKey)
+        );
+
+        let err = Error::MalformedRegistrationMessage {
+            reason: "missing field `pub_key`".to_string(),
+        };
+        assert_eq!(
+            err.registration_error_kind(),
+            Some(RegistrationErrorKind::MalformedMessage)
+        );
+    }
+
+    #[test]
+    fn registration_error_kind_serialization() {
+        let kind = Error::MacMismatch {
+            client: "foo".to_string(),
+            expected_len: 32,
+            source: "mac mismatch".into(),
+        }
+        .registration_error_kind()
+        .unwrap();
+        assert_eq!(
+            serde_json::to_string(&kind).unwrap(),
+            r#"{"kind":"mac_mismatch","expected_len":32}"#
+        );
+        assert!(Error::RegistryFull {
+            retry_after: Duration::from_secs(1)
+        }
+        .registration_error_kind()
+        .is_none());
+    }
 }
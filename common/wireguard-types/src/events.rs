@@ -0,0 +1,60 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::PeerPublicKey;
+use std::net::IpAddr;
+use tokio::sync::broadcast;
+
+// we don't want to keep a lot of events around if nobody is keeping up with them
+pub(crate) const PEER_EVENTS_CHANNEL_CAPACITY: usize = 256;
+
+pub type PeerEventSender = broadcast::Sender<PeerEvent>;
+pub type PeerEventReceiver = broadcast::Receiver<PeerEvent>;
+
+/// Changes to the set of wireguard peers registered with the gateway.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PeerEvent {
+    /// New peer has successfully completed its registration.
+    PeerRegistered {
+        pub_key: PeerPublicKey,
+        private_ip: IpAddr,
+    },
+
+    /// Peer has been removed from the registry.
+    PeerRemoved { pub_key: PeerPublicKey },
+
+    /// Already registered peer has replaced its public key. It retains its private IP.
+    PeerKeyRotated {
+        old_pub_key: PeerPublicKey,
+        new_pub_key: PeerPublicKey,
+        private_ip: IpAddr,
+    },
+
+    /// Peer has been suspended and should no longer be allowed to send any traffic.
+    PeerSuspended { pub_key: PeerPublicKey },
+
+    /// Previously suspended peer has been allowed to send traffic again.
+    PeerResumed {
+        pub_key: PeerPublicKey,
+        private_ip: IpAddr,
+    },
+}
+
+impl PeerEvent {
+    /// Public key of the peer the event refers to. In the case of key rotation, it's the new key.
+    pub fn pub_key(&self) -> PeerPublicKey {
+        match self {
+            PeerEvent::PeerRegistered { pub_key, .. } => *pub_key,
+            PeerEvent::PeerRemoved { pub_key } => *pub_key,
+            PeerEvent::PeerKeyRotated { new_pub_key, .. } => *new_pub_key,
+            PeerEvent::PeerSuspended { pub_key } => *pub_key,
+            PeerEvent::PeerResumed { pub_key, .. } => *pub_key,
+        }
+    }
+}
+
+/// Notify all the subscribers about the change to the registered peers.
+pub fn emit_peer_event(sender: &PeerEventSender, event: PeerEvent) {
+    // an error only means there are currently no subscribers, which is fine
+    let _ = sender.send(event);
+}
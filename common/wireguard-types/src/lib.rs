@@ -1,44 +1,113 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use dashmap::DashMap;
+use dashmap::{DashMap, DashSet};
+use ipnetwork::IpNetwork;
 use nym_crypto::asymmetric::encryption::KeyPair;
 use std::sync::Arc;
+use tokio::sync::{broadcast, watch};
 
 pub mod config;
+pub mod credential;
 pub mod error;
+pub mod events;
+pub mod mac;
+pub mod metadata;
+pub mod pow;
+pub mod provisioning;
 pub mod public_key;
 pub mod registration;
+pub mod registry;
+pub mod stats;
+pub mod transport;
+pub mod wg_quick;
 
-pub use config::Config;
-pub use error::Error;
+pub use config::{Config, ConfigReceiver, ConfigSender};
+pub use credential::{BandwidthCredential, CredentialVerifier, SharedCredentialVerifier};
+pub use error::{Error, RegistrationErrorKind};
+pub use events::{PeerEvent, PeerEventReceiver, PeerEventSender};
+pub use mac::MacAlgorithm;
+pub use metadata::PeerMetadata;
+pub use pow::{PowChallenge, PowSolution, RegistrationDifficulty};
+pub use provisioning::ProvisioningPayload;
 pub use public_key::PeerPublicKey;
 pub use registration::{
-    ClientMac, ClientMessage, ClientRegistrationResponse, GatewayClient, GatewayClientRegistry,
-    InitMessage, Nonce,
+    ClientMac, ClientMessage, ClientRegistrationResponse, FinalMessage, GatewayClient,
+    GatewayClientRegistry, InitMessage, IpReservations, KeyRotationMessage, Nonce,
+    PendingRegistration, PrivateIPs, RenewRegistrationMessage, SeenRotationNonces, SuspendedPeers,
 };
+pub use stats::{AllowedIp, PeerStats};
+pub use transport::{FallbackTransport, RegistrationTransport, TransportError};
+pub use wg_quick::{WgQuickConfig, WgQuickInterface, WgQuickPeer};
 
+#[cfg(feature = "verify")]
+pub use mac::{Blake3KeyedMac, HmacSha512, RegistrationMac};
 #[cfg(feature = "verify")]
 pub use registration::HmacSha256;
 
 #[derive(Clone)]
 pub struct WireguardGatewayData {
-    config: Config,
+    config: Arc<ConfigSender>,
     keypair: Arc<KeyPair>,
     client_registry: Arc<GatewayClientRegistry>,
+    free_private_ips: Arc<PrivateIPs>,
+    ip_reservations: Arc<IpReservations>,
+    suspended_peers: Arc<SuspendedPeers>,
+    peer_events: PeerEventSender,
+    credential_verifier: SharedCredentialVerifier,
 }
 
 impl WireguardGatewayData {
+    /// Note that the private network of the config is expected to have been validated beforehand,
+    /// as otherwise there are no private IPs that could be assigned to the peers.
     pub fn new(config: Config, keypair: Arc<KeyPair>) -> Self {
+        let (peer_events, _) = broadcast::channel(events::PEER_EVENTS_CHANNEL_CAPACITY);
+        let free_private_ips = IpNetwork::new(config.private_ip, config.private_network_prefix)
+            .map(|network| network.iter().map(|ip| (ip, true)).collect())
+            .unwrap_or_default();
         WireguardGatewayData {
-            config,
+            config: Arc::new(watch::channel(config).0),
             keypair,
-            client_registry: Arc::new(DashMap::default()),
+            client_registry: Arc::new(GatewayClientRegistry::default()),
+            free_private_ips: Arc::new(free_private_ips),
+            ip_reservations: Arc::new(DashMap::default()),
+            suspended_peers: Arc::new(DashSet::default()),
+            peer_events,
+            credential_verifier: SharedCredentialVerifier::default(),
         }
     }
 
+    /// Use the provided verifier for checking the bandwidth credentials presented by the clients.
+    #[must_use]
+    pub fn with_credential_verifier(self, verifier: Arc<dyn CredentialVerifier>) -> Self {
+        self.set_credential_verifier(verifier);
+        self
+    }
+
+    /// Install the verifier for checking the bandwidth credentials presented by the clients
+    /// with all the components sharing this data.
+    /// Returns `false` if a verifier has already been installed.
+    pub fn set_credential_verifier(&self, verifier: Arc<dyn CredentialVerifier>) -> bool {
+        self.credential_verifier.install(verifier)
+    }
+
+    /// Current snapshot of the config. Note that it might change at runtime,
+    /// so long-lived tasks should rather use [Self::subscribe_config].
     pub fn config(&self) -> Config {
-        self.config
+        *self.config.borrow()
+    }
+
+    /// Get a handle to the config that always observes its most recent version.
+    pub fn subscribe_config(&self) -> ConfigReceiver {
+        self.config.subscribe()
+    }
+
+    /// Apply the updated config without affecting any of the registered peers.
+    /// It fails if the update attempts to change values that can't be reloaded at runtime.
+    pub fn update_config(&self, updated: Config) -> Result<(), Error> {
+        self.config().ensure_reloadable(&updated)?;
+        self.config.send_replace(updated);
+        Ok(())
     }
 
     pub fn keypair(&self) -> &Arc<KeyPair> {
@@ -48,4 +117,239 @@ impl WireguardGatewayData {
     pub fn client_registry(&self) -> &Arc<GatewayClientRegistry> {
         &self.client_registry
     }
+
+    /// Private IPs of the wireguard network alongside the information whether they can still be assigned.
+    pub fn free_private_ips(&self) -> &Arc<PrivateIPs> {
+        &self.free_private_ips
+    }
+
+    pub fn ip_reservations(&self) -> &Arc<IpReservations> {
+        &self.ip_reservations
+    }
+
+    pub fn suspended_peers(&self) -> &Arc<SuspendedPeers> {
+        &self.suspended_peers
+    }
+
+    pub fn is_peer_suspended(&self, pub_key: &PeerPublicKey) -> bool {
+        self.suspended_peers.contains(pub_key)
+    }
+
+    /// Temporarily disable the registered peer without removing it from the registry,
+    /// so that it wouldn't have to register again once resumed.
+    /// Returns `false` if the peer has already been suspended.
+    pub fn suspend_peer(&self, pub_key: PeerPublicKey) -> Result<bool, Error> {
+        if !self.client_registry.contains_key(&pub_key) {
+            return Err(Error::PeerNotRegistered {
+                client: pub_key.to_string(),
+            });
+        }
+
+        if !self.suspended_peers.insert(pub_key) {
+            return Ok(false);
+        }
+        self.emit_peer_event(PeerEvent::PeerSuspended { pub_key });
+        Ok(true)
+    }
+
+    /// Allow the previously suspended peer to send traffic again.
+    /// Returns `false` if the peer hasn't been suspended.
+    pub fn resume_peer(&self, pub_key: PeerPublicKey) -> Result<bool, Error> {
+        let Some(private_ip) = self
+            .client_registry
+            .get(&pub_key)
+            .map(|client| client.private_ip)
+        else {
+            return Err(Error::PeerNotRegistered {
+                client: pub_key.to_string(),
+            });
+        };
+
+        if self.suspended_peers.remove(&pub_key).is_none() {
+            return Ok(false);
+        }
+        self.emit_peer_event(PeerEvent::PeerResumed {
+            pub_key,
+            private_ip,
+        });
+        Ok(true)
+    }
+
+    /// Remove the peers whose registrations have expired by the provided time and let the subscribers know about it.
+    /// Their private IPs, alongside any reservations they held, are released, so that they could be assigned again.
+    /// Returns the keys of the removed peers.
+    pub fn remove_expired_peers(&self, now: std::time::SystemTime) -> Vec<PeerPublicKey> {
+        let expired = self.client_registry.remove_expired(now);
+        for client in &expired {
+            let pub_key = client.pub_key;
+            self.suspended_peers.remove(&pub_key);
+            self.ip_reservations.retain(|_, holder| *holder != pub_key);
+            if let Some(mut free) = self.free_private_ips.get_mut(&client.private_ip) {
+                *free = true;
+            }
+            self.emit_peer_event(PeerEvent::PeerRemoved { pub_key });
+        }
+        expired.into_iter().map(|client| client.pub_key).collect()
+    }
+
+    pub fn credential_verifier(&self) -> &SharedCredentialVerifier {
+        &self.credential_verifier
+    }
+
+    pub fn peer_event_sender(&self) -> &PeerEventSender {
+        &self.peer_events
+    }
+
+    /// Subscribe to the stream of changes to the registered peers.
+    /// Note that the receiver is only going to get events emitted after it was created.
+    pub fn subscribe_peer_events(&self) -> PeerEventReceiver {
+        self.peer_events.subscribe()
+    }
+
+    pub fn emit_peer_event(&self, event: PeerEvent) {
+        events::emit_peer_event(&self.peer_events, event)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gateway_data() -> WireguardGatewayData {
+        let mut rng = rand::thread_rng();
+        let config = Config {
+            bind_address: "0.0.0.0:51822".parse().unwrap(),
+            private_ip: "10.1.0.1".parse().unwrap(),
+            announced_port: 51822,
+            private_network_prefix: 16,
+            registration_mac: MacAlgorithm::default(),
+            max_registered_peers: None,
+            pow_rate_threshold: None,
+            registration_ttl: None,
+            require_bandwidth_credential: false,
+        };
+        WireguardGatewayData::new(config, Arc::new(KeyPair::new(&mut rng)))
+    }
+
+    fn register(data: &WireguardGatewayData, seed: u8) -> PeerPublicKey {
+        let pub_key = PeerPublicKey::new(x25519_dalek::PublicKey::from([seed; 32]));
+        data.client_registry().insert(
+            pub_key,
+            GatewayClient {
+                pub_key,
+                private_ip: [10, 1, 0, seed].into(),
+                mac: ClientMac::new(vec![]),
+                mac_algorithm: MacAlgorithm::default(),
+            },
+        );
+        pub_key
+    }
+
+    #[test]
+    fn suspending_and_resuming_peers() {
+        let data = gateway_data();
+        let mut events = data.subscribe_peer_events();
+        let peer = register(&data, 1);
+        let unknown = PeerPublicKey::new(x25519_dalek::PublicKey::from([2; 32]));
+
+        assert!(matches!(
+            data.suspend_peer(unknown),
+            Err(Error::PeerNotRegistered { .. })
+        ));
+
+        assert!(data.suspend_peer(peer).unwrap());
+        assert!(!data.suspend_peer(peer).unwrap());
+        assert!(data.is_peer_suspended(&peer));
+        assert!(data.client_registry().contains_key(&peer));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            PeerEvent::PeerSuspended { pub_key: peer }
+        );
+        assert!(events.try_recv().is_err());
+
+        assert!(data.resume_peer(peer).unwrap());
+        assert!(!data.resume_peer(peer).unwrap());
+        assert!(!data.is_peer_suspended(&peer));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            PeerEvent::PeerResumed {
+                pub_key: peer,
+                private_ip: [10, 1, 0, 1].into(),
+            }
+        );
+    }
+
+    #[test]
+    fn removing_expired_peers() {
+        let data = gateway_data();
+        let peer = register(&data, 1);
+        let other = register(&data, 2);
+        let now = std::time::SystemTime::now();
+        data.client_registry().set_expiry(peer, Some(now)).unwrap();
+
+        let mut events = data.subscribe_peer_events();
+        assert_eq!(data.remove_expired_peers(now), vec![peer]);
+        assert!(!data.client_registry().contains_key(&peer));
+        assert!(data.client_registry().contains_key(&other));
+        assert_eq!(
+            events.try_recv().unwrap(),
+            PeerEvent::PeerRemoved { pub_key: peer }
+        );
+        assert!(events.try_recv().is_err());
+    }
+
+    #[test]
+    fn expired_peers_release_their_private_ips() {
+        let data = gateway_data();
+        let expired = PeerPublicKey::new(x25519_dalek::PublicKey::from([1; 32]));
+        let newcomer = PeerPublicKey::new(x25519_dalek::PublicKey::from([2; 32]));
+        let private_ip: std::net::IpAddr = [10, 1, 0, 42].into();
+
+        registration::reserve_requested_ip(
+            data.free_private_ips(),
+            data.ip_reservations(),
+            expired,
+            private_ip,
+        )
+        .unwrap();
+        data.client_registry().insert(
+            expired,
+            GatewayClient {
+                pub_key: expired,
+                private_ip,
+                mac: ClientMac::new(vec![]),
+                mac_algorithm: MacAlgorithm::default(),
+            },
+        );
+        data.suspend_peer(expired).unwrap();
+
+        // while the peer is registered, nobody else can take its ip
+        assert!(matches!(
+            registration::reserve_requested_ip(
+                data.free_private_ips(),
+                data.ip_reservations(),
+                newcomer,
+                private_ip,
+            ),
+            Err(Error::RequestedIpUnavailable { .. })
+        ));
+
+        let now = std::time::SystemTime::now();
+        data.client_registry()
+            .set_expiry(expired, Some(now))
+            .unwrap();
+        assert_eq!(data.remove_expired_peers(now), vec![expired]);
+        assert!(!data.is_peer_suspended(&expired));
+        assert!(data.ip_reservations().is_empty());
+        assert!(*data.free_private_ips().get(&private_ip).unwrap());
+
+        registration::reserve_requested_ip(
+            data.free_private_ips(),
+            data.ip_reservations(),
+            newcomer,
+            private_ip,
+        )
+        .unwrap();
+        assert_eq!(*data.ip_reservations().get(&private_ip).unwrap(), newcomer);
+    }
 }
@@ -0,0 +1,227 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+#[cfg(feature = "verify")]
+use crate::registration::ClientMac;
+#[cfg(feature = "verify")]
+use hmac::digest::MacError;
+#[cfg(feature = "verify")]
+use hmac::{Hmac, Mac};
+#[cfg(feature = "verify")]
+use sha2::{Sha256, Sha512};
+
+#[cfg(feature = "verify")]
+pub type HmacSha256 = Hmac<Sha256>;
+#[cfg(feature = "verify")]
+pub type HmacSha512 = Hmac<Sha512>;
+
+/// Identifier of the algorithm used for computing the macs exchanged during the registration.
+#[derive(
+    Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash,
+)]
+#[serde(rename_all = "snake_case")]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub enum MacAlgorithm {
+    // this is the only algorithm understood by the legacy clients
+    #[default]
+    HmacSha256,
+    HmacSha512,
+    Blake3Keyed,
+}
+
+impl fmt::Display for MacAlgorithm {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MacAlgorithm::HmacSha256 => write!(f, "hmac_sha256"),
+            MacAlgorithm::HmacSha512 => write!(f, "hmac_sha512"),
+            MacAlgorithm::Blake3Keyed => write!(f, "blake3_keyed"),
+        }
+    }
+}
+
+impl MacAlgorithm {
+    pub const ALL: [MacAlgorithm; 3] = [
+        MacAlgorithm::HmacSha256,
+        MacAlgorithm::HmacSha512,
+        MacAlgorithm::Blake3Keyed,
+    ];
+
+    /// Length of the macs produced by the algorithm.
+    pub fn output_len(&self) -> usize {
+        match self {
+            MacAlgorithm::HmacSha256 => 32,
+            MacAlgorithm::HmacSha512 => 64,
+            MacAlgorithm::Blake3Keyed => 32,
+        }
+    }
+
+    /// Chooses the algorithm for the registration based on the one preferred by the gateway
+    /// and the ones announced by the client. Clients that haven't announced anything
+    /// are assumed to only understand the default algorithm.
+    pub fn negotiate(preferred: MacAlgorithm, client_supported: &[MacAlgorithm]) -> MacAlgorithm {
+        if client_supported.contains(&preferred) {
+            preferred
+        } else {
+            MacAlgorithm::default()
+        }
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn compute(&self, shared_secret: &[u8; 32], data: &[&[u8]]) -> ClientMac {
+        let mac = match self {
+            MacAlgorithm::HmacSha256 => {
+                <HmacSha256 as RegistrationMac>::compute(shared_secret, data)
+            }
+            MacAlgorithm::HmacSha512 => {
+                <HmacSha512 as RegistrationMac>::compute(shared_secret, data)
+            }
+            MacAlgorithm::Blake3Keyed => {
+                <Blake3KeyedMac as RegistrationMac>::compute(shared_secret, data)
+            }
+        };
+        ClientMac::new(mac)
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn verify(
+        &self,
+        shared_secret: &[u8; 32],
+        data: &[&[u8]],
+        mac: &[u8],
+    ) -> Result<(), MacError> {
+        match self {
+            MacAlgorithm::HmacSha256 => {
+                <HmacSha256 as RegistrationMac>::verify(shared_secret, data, mac)
+            }
+            MacAlgorithm::HmacSha512 => {
+                <HmacSha512 as RegistrationMac>::verify(shared_secret, data, mac)
+            }
+            MacAlgorithm::Blake3Keyed => {
+                <Blake3KeyedMac as RegistrationMac>::verify(shared_secret, data, mac)
+            }
+        }
+    }
+}
+
+/// Mac algorithm that can be used for authenticating registration messages
+/// using the x25519 shared secret derived between the client and the gateway.
+#[cfg(feature = "verify")]
+pub trait RegistrationMac {
+    const ALGORITHM: MacAlgorithm;
+
+    fn compute(shared_secret: &[u8; 32], data: &[&[u8]]) -> Vec<u8>;
+
+    fn verify(shared_secret: &[u8; 32], data: &[&[u8]], mac: &[u8]) -> Result<(), MacError>;
+}
+
+#[cfg(feature = "verify")]
+macro_rules! impl_hmac_registration_mac {
+    ( $hmac: ty, $algorithm: expr ) => {
+        impl RegistrationMac for $hmac {
+            const ALGORITHM: MacAlgorithm = $algorithm;
+
+            fn compute(shared_secret: &[u8; 32], data: &[&[u8]]) -> Vec<u8> {
+                // safety: HMAC can take a key of any size
+                #[allow(clippy::expect_used)]
+                let mut mac = <$hmac>::new_from_slice(shared_secret)
+                    .expect("HMAC can take a key of any size");
+                for chunk in data {
+                    mac.update(chunk);
+                }
+                mac.finalize().into_bytes().to_vec()
+            }
+
+            fn verify(
+                shared_secret: &[u8; 32],
+                data: &[&[u8]],
+                mac: &[u8],
+            ) -> Result<(), MacError> {
+                // safety: HMAC can take a key of any size
+                #[allow(clippy::expect_used)]
+                let mut expected = <$hmac>::new_from_slice(shared_secret)
+                    .expect("HMAC can take a key of any size");
+                for chunk in data {
+                    expected.update(chunk);
+                }
+                expected.verify_slice(mac)
+            }
+        }
+    };
+}
+
+#[cfg(feature = "verify")]
+impl_hmac_registration_mac!(HmacSha256, MacAlgorithm::HmacSha256);
+#[cfg(feature = "verify")]
+impl_hmac_registration_mac!(HmacSha512, MacAlgorithm::HmacSha512);
+
+/// Keyed blake3 hash used as a mac.
+#[cfg(feature = "verify")]
+pub struct Blake3KeyedMac;
+
+#[cfg(feature = "verify")]
+impl RegistrationMac for Blake3KeyedMac {
+    const ALGORITHM: MacAlgorithm = MacAlgorithm::Blake3Keyed;
+
+    fn compute(shared_secret: &[u8; 32], data: &[&[u8]]) -> Vec<u8> {
+        let mut hasher = blake3::Hasher::new_keyed(shared_secret);
+        for chunk in data {
+            hasher.update(chunk);
+        }
+        hasher.finalize().as_bytes().to_vec()
+    }
+
+    fn verify(shared_secret: &[u8; 32], data: &[&[u8]], mac: &[u8]) -> Result<(), MacError> {
+        let Ok(mac) = <[u8; blake3::OUT_LEN]>::try_from(mac) else {
+            return Err(MacError);
+        };
+
+        let mut hasher = blake3::Hasher::new_keyed(shared_secret);
+        for chunk in data {
+            hasher.update(chunk);
+        }
+
+        // `blake3::Hash` equality is constant time
+        if hasher.finalize() == blake3::Hash::from(mac) {
+            Ok(())
+        } else {
+            Err(MacError)
+        }
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "verify")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn macs_roundtrip() {
+        let key = [42u8; 32];
+        let data: &[&[u8]] = &[b"foo", b"bar"];
+
+        for algorithm in MacAlgorithm::ALL {
+            let mac = algorithm.compute(&key, data);
+            assert!(algorithm.verify(&key, data, &mac).is_ok());
+            assert!(algorithm.verify(&[1u8; 32], data, &mac).is_err());
+            assert!(algorithm.verify(&key, &[b"foo"], &mac).is_err());
+        }
+    }
+
+    #[test]
+    fn negotiation_falls_back_to_default() {
+        assert_eq!(
+            MacAlgorithm::negotiate(MacAlgorithm::Blake3Keyed, &[]),
+            MacAlgorithm::HmacSha256
+        );
+        assert_eq!(
+            MacAlgorithm::negotiate(
+                MacAlgorithm::Blake3Keyed,
+                &[MacAlgorithm::HmacSha512, MacAlgorithm::Blake3Keyed]
+            ),
+            MacAlgorithm::Blake3Keyed
+        );
+    }
+}
@@ -0,0 +1,93 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Label denoting the platform of the client, e.g. `android` or `linux`.
+pub const PLATFORM_LABEL: &str = "platform";
+
+/// Label denoting how the peer got registered, e.g. through the http api or by the operator.
+pub const REGISTRATION_SOURCE_LABEL: &str = "registration_source";
+
+/// Label denoting the tier of the bandwidth credential presented by the client.
+pub const CREDENTIAL_TIER_LABEL: &str = "credential_tier";
+
+/// Classification labels attached by the gateway to a registered peer, kept alongside its registry entry.
+/// They're never provided by the clients themselves, so they can be relied upon when applying policies,
+/// such as tier-based bandwidth limits. Apart from the well-known labels, any others can be attached.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+#[serde(transparent)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PeerMetadata {
+    labels: BTreeMap<String, String>,
+}
+
+impl PeerMetadata {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.set_label(key, value);
+        self
+    }
+
+    #[must_use]
+    pub fn with_platform(self, platform: impl Into<String>) -> Self {
+        self.with_label(PLATFORM_LABEL, platform)
+    }
+
+    #[must_use]
+    pub fn with_registration_source(self, source: impl Into<String>) -> Self {
+        self.with_label(REGISTRATION_SOURCE_LABEL, source)
+    }
+
+    #[must_use]
+    pub fn with_credential_tier(self, tier: impl Into<String>) -> Self {
+        self.with_label(CREDENTIAL_TIER_LABEL, tier)
+    }
+
+    /// Set the label, returning its previous value, if any.
+    pub fn set_label(
+        &mut self,
+        key: impl Into<String>,
+        value: impl Into<String>,
+    ) -> Option<String> {
+        self.labels.insert(key.into(), value.into())
+    }
+
+    pub fn remove_label(&mut self, key: &str) -> Option<String> {
+        self.labels.remove(key)
+    }
+
+    pub fn label(&self, key: &str) -> Option<&str> {
+        self.labels.get(key).map(String::as_str)
+    }
+
+    /// Checks whether the peer has the label set to the provided value.
+    pub fn has_label(&self, key: &str, value: &str) -> bool {
+        self.label(key) == Some(value)
+    }
+
+    pub fn platform(&self) -> Option<&str> {
+        self.label(PLATFORM_LABEL)
+    }
+
+    pub fn registration_source(&self) -> Option<&str> {
+        self.label(REGISTRATION_SOURCE_LABEL)
+    }
+
+    pub fn credential_tier(&self) -> Option<&str> {
+        self.label(CREDENTIAL_TIER_LABEL)
+    }
+
+    pub fn labels(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.labels.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
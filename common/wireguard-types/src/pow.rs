@@ -0,0 +1,248 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "verify")]
+use crate::error::Error;
+#[cfg(feature = "verify")]
+use crate::PeerPublicKey;
+
+/// Period over which the rate of the registration requests is measured.
+pub const REGISTRATION_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+/// Difficulty of the challenges issued as soon as the registration rate exceeds the configured threshold.
+pub const MIN_POW_DIFFICULTY: u8 = 8;
+
+/// Upper bound on the difficulty of the issued challenges so that legitimate clients,
+/// including the ones running on mobile devices, could still solve them in reasonable time.
+pub const MAX_POW_DIFFICULTY: u8 = 24;
+
+/// Additional difficulty demanded every time the registration rate doubles beyond the threshold.
+const DIFFICULTY_STEP: u8 = 2;
+
+#[cfg(feature = "verify")]
+const POW_DOMAIN_SEPARATOR: &[u8] = b"nym-wireguard-registration-pow";
+
+/// Proof of work solution: a counter such that the hash of the challenge alongside it
+/// has at least the required number of leading zero bits.
+pub type PowSolution = u64;
+
+/// Proof of work challenge issued by a gateway under load that the client has to solve
+/// before its registration is going to be finalised.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PowChallenge {
+    /// Required number of leading zero bits of the solution hash
+    pub difficulty: u8,
+
+    /// Random salt binding the solution to this particular challenge
+    pub salt: u64,
+}
+
+impl PowChallenge {
+    pub fn new(difficulty: u8, salt: u64) -> Self {
+        PowChallenge { difficulty, salt }
+    }
+
+    #[cfg(feature = "verify")]
+    fn digest(&self, pub_key: &PeerPublicKey, solution: PowSolution) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(POW_DOMAIN_SEPARATOR);
+        hasher.update(&self.salt.to_le_bytes());
+        hasher.update(pub_key.as_bytes());
+        hasher.update(&solution.to_le_bytes());
+        hasher.finalize()
+    }
+
+    #[cfg(feature = "verify")]
+    fn is_solution(&self, pub_key: &PeerPublicKey, solution: PowSolution) -> bool {
+        leading_zero_bits(self.digest(pub_key, solution).as_bytes()) >= self.difficulty as u32
+    }
+
+    /// Find a solution to the challenge for the registration of the provided key.
+    /// On average it requires `2^difficulty` hash computations.
+    #[cfg(feature = "verify")]
+    pub fn solve(&self, pub_key: &PeerPublicKey) -> PowSolution {
+        (0..=PowSolution::MAX)
+            .find(|solution| self.is_solution(pub_key, *solution))
+            .unwrap_or_default()
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn verify(
+        &self,
+        pub_key: &PeerPublicKey,
+        solution: Option<PowSolution>,
+    ) -> Result<(), Error> {
+        let Some(solution) = solution else {
+            return Err(Error::MissingProofOfWork {
+                client: pub_key.to_string(),
+            });
+        };
+
+        if self.is_solution(pub_key, solution) {
+            Ok(())
+        } else {
+            Err(Error::InvalidProofOfWork {
+                client: pub_key.to_string(),
+                difficulty: self.difficulty,
+            })
+        }
+    }
+}
+
+#[cfg(feature = "verify")]
+fn leading_zero_bits(bytes: &[u8]) -> u32 {
+    let mut zeros = 0;
+    for byte in bytes {
+        zeros += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    zeros
+}
+
+#[derive(Debug)]
+struct RateWindow {
+    started: Instant,
+    current: u32,
+    previous: u32,
+}
+
+/// Tracks the rate of the incoming registration requests in order to determine
+/// the difficulty of the proof of work challenges, if any, the clients have to solve.
+#[derive(Debug)]
+pub struct RegistrationDifficulty {
+    window: Mutex<RateWindow>,
+}
+
+impl Default for RegistrationDifficulty {
+    fn default() -> Self {
+        RegistrationDifficulty::new(Instant::now())
+    }
+}
+
+impl RegistrationDifficulty {
+    pub fn new(now: Instant) -> Self {
+        RegistrationDifficulty {
+            window: Mutex::new(RateWindow {
+                started: now,
+                current: 0,
+                previous: 0,
+            }),
+        }
+    }
+
+    /// Record a new registration request and return the difficulty of the challenge
+    /// it should be issued given the provided threshold of requests per minute.
+    /// Zero difficulty means no challenge is necessary.
+    pub fn record_request(&self, now: Instant, threshold: Option<u32>) -> u8 {
+        #[allow(clippy::unwrap_used)]
+        let mut window = self.window.lock().unwrap();
+
+        let elapsed = now.saturating_duration_since(window.started);
+        if elapsed >= REGISTRATION_RATE_WINDOW * 2 {
+            window.started = now;
+            window.previous = 0;
+            window.current = 0;
+        } else if elapsed >= REGISTRATION_RATE_WINDOW {
+            window.started += REGISTRATION_RATE_WINDOW;
+            window.previous = window.current;
+            window.current = 0;
+        }
+        window.current = window.current.saturating_add(1);
+
+        let Some(threshold) = threshold else {
+            return 0;
+        };
+
+        // approximate the sliding window by weighting the previous period
+        // by the portion of it still overlapping with the window
+        let into_window = now.saturating_duration_since(window.started).as_secs_f64()
+            / REGISTRATION_RATE_WINDOW.as_secs_f64();
+        let rate = window.previous as f64 * (1. - into_window).max(0.) + window.current as f64;
+
+        difficulty_for_rate(rate, threshold)
+    }
+}
+
+fn difficulty_for_rate(rate: f64, threshold: u32) -> u8 {
+    let threshold = threshold.max(1) as f64;
+    if rate <= threshold {
+        return 0;
+    }
+
+    let doublings = (rate / threshold).log2().floor() as u32;
+    let difficulty = MIN_POW_DIFFICULTY as u32 + doublings * DIFFICULTY_STEP as u32;
+    difficulty.min(MAX_POW_DIFFICULTY as u32) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn difficulty_grows_with_the_request_rate() {
+        assert_eq!(difficulty_for_rate(10., 10), 0);
+        assert_eq!(difficulty_for_rate(11., 10), MIN_POW_DIFFICULTY);
+        assert_eq!(
+            difficulty_for_rate(20., 10),
+            MIN_POW_DIFFICULTY + DIFFICULTY_STEP
+        );
+        assert_eq!(
+            difficulty_for_rate(45., 10),
+            MIN_POW_DIFFICULTY + 2 * DIFFICULTY_STEP
+        );
+        assert_eq!(difficulty_for_rate(1e12, 10), MAX_POW_DIFFICULTY);
+    }
+
+    #[test]
+    fn registration_rate_tracking() {
+        let start = Instant::now();
+        let tracker = RegistrationDifficulty::new(start);
+
+        for _ in 0..5 {
+            assert_eq!(tracker.record_request(start, Some(5)), 0);
+        }
+        assert_eq!(tracker.record_request(start, Some(5)), MIN_POW_DIFFICULTY);
+        assert_eq!(tracker.record_request(start, None), 0);
+
+        // half-way through the next window only half of the previous requests count
+        let later = start + REGISTRATION_RATE_WINDOW + REGISTRATION_RATE_WINDOW / 2;
+        assert_eq!(tracker.record_request(later, Some(5)), 0);
+
+        // and after a quiet period, the history is forgotten
+        let much_later = later + REGISTRATION_RATE_WINDOW * 3;
+        assert_eq!(tracker.record_request(much_later, Some(1)), 0);
+    }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn challenge_roundtrip() {
+        let pub_key = PeerPublicKey::new(x25519_dalek::PublicKey::from([42u8; 32]));
+        let challenge = PowChallenge::new(10, 1234567890);
+
+        let solution = challenge.solve(&pub_key);
+        assert!(challenge.verify(&pub_key, Some(solution)).is_ok());
+        assert!(matches!(
+            challenge.verify(&pub_key, None),
+            Err(Error::MissingProofOfWork { .. })
+        ));
+
+        // every counter preceding the found solution must have been rejected
+        assert!((0..solution).all(|attempt| challenge.verify(&pub_key, Some(attempt)).is_err()));
+    }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn counting_leading_zeros() {
+        assert_eq!(leading_zero_bits(&[0xff]), 0);
+        assert_eq!(leading_zero_bits(&[0x00, 0x01]), 15);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00]), 16);
+        assert_eq!(leading_zero_bits(&[0x00, 0x20, 0x00]), 10);
+    }
+}
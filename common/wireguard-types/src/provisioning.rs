@@ -0,0 +1,296 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::registration::GatewayClient;
+use crate::PeerPublicKey;
+use base64::engine::general_purpose;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use x25519_dalek::PublicKey;
+
+/// Version of the compact encoding of the provisioning payload.
+pub const PROVISIONING_PAYLOAD_VERSION: u8 = 1;
+
+/// Prefix of the textual form of the payload, as embedded in the QR codes.
+pub const PROVISIONING_URI_PREFIX: &str = "nymwg:";
+
+/// Upper bound on the number of DNS servers so that the payload still fits in a small QR code.
+pub const MAX_PROVISIONING_DNS_SERVERS: usize = 4;
+
+const FLAG_PRESHARED_KEY: u8 = 0b0000_0001;
+
+const IPV4_TAG: u8 = 4;
+const IPV6_TAG: u8 = 6;
+
+/// Everything a client needs to bring up a tunnel with a gateway it has been registered with
+/// by another device, e.g. a mobile client provisioned from a registration made on a desktop.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProvisioningPayload {
+    /// Address and port the gateway is listening for the wireguard traffic on
+    pub gateway_endpoint: SocketAddr,
+
+    /// Base64 encoded x25519 public key of the gateway
+    pub gateway_public_key: PeerPublicKey,
+
+    /// Private IP assigned to the client by the gateway
+    pub private_ip: IpAddr,
+
+    /// DNS servers the client should use while the tunnel is up
+    #[serde(default)]
+    pub dns: Vec<IpAddr>,
+
+    /// Optional wireguard preshared key, for an additional layer of symmetric encryption
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub preshared_key: Option<[u8; 32]>,
+}
+
+impl ProvisioningPayload {
+    pub fn new(
+        gateway_endpoint: SocketAddr,
+        gateway_public_key: PeerPublicKey,
+        private_ip: IpAddr,
+    ) -> Self {
+        ProvisioningPayload {
+            gateway_endpoint,
+            gateway_public_key,
+            private_ip,
+            dns: Vec::new(),
+            preshared_key: None,
+        }
+    }
+
+    /// Build the payload out of the data returned by the gateway during the registration.
+    pub fn from_registration(
+        gateway_ip: IpAddr,
+        wg_port: u16,
+        gateway_data: &GatewayClient,
+    ) -> Self {
+        ProvisioningPayload::new(
+            SocketAddr::new(gateway_ip, wg_port),
+            gateway_data.pub_key,
+            gateway_data.private_ip,
+        )
+    }
+
+    #[must_use]
+    pub fn with_dns(mut self, dns: Vec<IpAddr>) -> Self {
+        self.dns = dns;
+        self
+    }
+
+    #[must_use]
+    pub fn with_preshared_key(mut self, preshared_key: [u8; 32]) -> Self {
+        self.preshared_key = Some(preshared_key);
+        self
+    }
+
+    /// Compact binary encoding of the payload:
+    /// version | flags | endpoint ip | endpoint port | gateway key | private ip | dns count | dns ips | [psk]
+    /// where every ip is prefixed with a byte indicating its family.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, Error> {
+        if self.dns.len() > MAX_PROVISIONING_DNS_SERVERS {
+            return Err(malformed(format!(
+                "too many dns servers: {}, the maximum is {MAX_PROVISIONING_DNS_SERVERS}",
+                self.dns.len()
+            )));
+        }
+
+        let mut flags = 0;
+        if self.preshared_key.is_some() {
+            flags |= FLAG_PRESHARED_KEY;
+        }
+
+        let mut bytes = vec![PROVISIONING_PAYLOAD_VERSION, flags];
+        put_ip(&mut bytes, self.gateway_endpoint.ip());
+        bytes.extend_from_slice(&self.gateway_endpoint.port().to_be_bytes());
+        bytes.extend_from_slice(self.gateway_public_key.as_bytes());
+        put_ip(&mut bytes, self.private_ip);
+        bytes.push(self.dns.len() as u8);
+        for dns in &self.dns {
+            put_ip(&mut bytes, *dns);
+        }
+        if let Some(preshared_key) = &self.preshared_key {
+            bytes.extend_from_slice(preshared_key);
+        }
+        Ok(bytes)
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(bytes);
+
+        let version = reader.u8()?;
+        if version != PROVISIONING_PAYLOAD_VERSION {
+            return Err(malformed(format!("unsupported payload version {version}")));
+        }
+        let flags = reader.u8()?;
+        if flags & !FLAG_PRESHARED_KEY != 0 {
+            return Err(malformed(format!("unknown flags set: {flags:#010b}")));
+        }
+
+        let endpoint_ip = reader.ip()?;
+        let endpoint_port = u16::from_be_bytes(reader.array()?);
+        let gateway_public_key = PeerPublicKey::new(PublicKey::from(reader.array::<32>()?));
+        let private_ip = reader.ip()?;
+
+        let dns_count = reader.u8()? as usize;
+        if dns_count > MAX_PROVISIONING_DNS_SERVERS {
+            return Err(malformed(format!("too many dns servers: {dns_count}")));
+        }
+        let dns = (0..dns_count)
+            .map(|_| reader.ip())
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let preshared_key = if flags & FLAG_PRESHARED_KEY != 0 {
+            Some(reader.array()?)
+        } else {
+            None
+        };
+
+        if !reader.0.is_empty() {
+            return Err(malformed(format!("{} trailing bytes", reader.0.len())));
+        }
+
+        Ok(ProvisioningPayload {
+            gateway_endpoint: SocketAddr::new(endpoint_ip, endpoint_port),
+            gateway_public_key,
+            private_ip,
+            dns,
+            preshared_key,
+        })
+    }
+}
+
+/// The textual form of the payload, suitable for rendering as a QR code.
+impl fmt::Display for ProvisioningPayload {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes = self.to_bytes().map_err(|_| fmt::Error)?;
+        write!(
+            f,
+            "{PROVISIONING_URI_PREFIX}{}",
+            general_purpose::URL_SAFE_NO_PAD.encode(bytes)
+        )
+    }
+}
+
+impl FromStr for ProvisioningPayload {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some(encoded) = s.trim().strip_prefix(PROVISIONING_URI_PREFIX) else {
+            return Err(malformed(format!(
+                "missing the '{PROVISIONING_URI_PREFIX}' prefix"
+            )));
+        };
+        let bytes = general_purpose::URL_SAFE_NO_PAD
+            .decode(encoded)
+            .map_err(|err| malformed(err.to_string()))?;
+        ProvisioningPayload::from_bytes(&bytes)
+    }
+}
+
+fn malformed(reason: String) -> Error {
+    Error::MalformedProvisioningPayload { reason }
+}
+
+fn put_ip(bytes: &mut Vec<u8>, ip: IpAddr) {
+    match ip {
+        IpAddr::V4(ip) => {
+            bytes.push(IPV4_TAG);
+            bytes.extend_from_slice(&ip.octets());
+        }
+        IpAddr::V6(ip) => {
+            bytes.push(IPV6_TAG);
+            bytes.extend_from_slice(&ip.octets());
+        }
+    }
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], Error> {
+        if self.0.len() < N {
+            return Err(malformed("the payload is truncated".to_string()));
+        }
+        let (head, tail) = self.0.split_at(N);
+        self.0 = tail;
+        // the length has been checked above
+        Ok(head.try_into().unwrap_or([0; N]))
+    }
+
+    fn u8(&mut self) -> Result<u8, Error> {
+        Ok(self.array::<1>()?[0])
+    }
+
+    fn ip(&mut self) -> Result<IpAddr, Error> {
+        match self.u8()? {
+            IPV4_TAG => Ok(Ipv4Addr::from(self.array::<4>()?).into()),
+            IPV6_TAG => Ok(Ipv6Addr::from(self.array::<16>()?).into()),
+            tag => Err(malformed(format!("unknown ip address family {tag}"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn payload() -> ProvisioningPayload {
+        ProvisioningPayload::new(
+            "1.2.3.4:51822".parse().unwrap(),
+            PeerPublicKey::new(PublicKey::from([42u8; 32])),
+            "10.1.0.2".parse().unwrap(),
+        )
+    }
+
+    #[test]
+    fn compact_encoding_roundtrip() {
+        let minimal = payload();
+        let bytes = minimal.to_bytes().unwrap();
+        // version, flags, endpoint, port, key, private ip and the dns count
+        assert_eq!(bytes.len(), 1 + 1 + 5 + 2 + 32 + 5 + 1);
+        assert_eq!(ProvisioningPayload::from_bytes(&bytes).unwrap(), minimal);
+
+        let full = payload()
+            .with_dns(vec![
+                "1.1.1.1".parse().unwrap(),
+                "2606:4700:4700::1111".parse().unwrap(),
+            ])
+            .with_preshared_key([7u8; 32]);
+        let encoded = full.to_string();
+        assert!(encoded.starts_with(PROVISIONING_URI_PREFIX));
+        assert_eq!(encoded.parse::<ProvisioningPayload>().unwrap(), full);
+    }
+
+    #[test]
+    fn malformed_payloads_are_rejected() {
+        let bytes = payload().with_preshared_key([7u8; 32]).to_bytes().unwrap();
+        for truncated in [0, 1, 10, bytes.len() - 1] {
+            assert!(matches!(
+                ProvisioningPayload::from_bytes(&bytes[..truncated]),
+                Err(Error::MalformedProvisioningPayload { .. })
+            ));
+        }
+
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(ProvisioningPayload::from_bytes(&trailing).is_err());
+
+        let mut wrong_version = bytes;
+        wrong_version[0] = PROVISIONING_PAYLOAD_VERSION + 1;
+        assert!(ProvisioningPayload::from_bytes(&wrong_version).is_err());
+
+        assert!("not-a-payload".parse::<ProvisioningPayload>().is_err());
+        assert!(payload()
+            .with_dns(vec![
+                "1.1.1.1".parse().unwrap();
+                MAX_PROVISIONING_DNS_SERVERS + 1
+            ])
+            .to_bytes()
+            .is_err());
+    }
+}
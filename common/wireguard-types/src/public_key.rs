@@ -12,6 +12,8 @@ use std::str::FromStr;
 
 use x25519_dalek::PublicKey;
 
+const PUBLIC_KEY_EXPECTATION: &str = "base64-encoded 32 bytes long x25519 public key";
+
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct PeerPublicKey(PublicKey);
 
@@ -83,6 +85,11 @@ impl Serialize for PeerPublicKey {
 impl<'de> serde::Deserialize<'de> for PeerPublicKey {
     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
         let encoded_key = String::deserialize(deserializer)?;
-        Ok(PeerPublicKey::from_str(&encoded_key).map_err(serde::de::Error::custom))?
+        PeerPublicKey::from_str(&encoded_key).map_err(|_| {
+            serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&encoded_key),
+                &PUBLIC_KEY_EXPECTATION,
+            )
+        })
     }
 }
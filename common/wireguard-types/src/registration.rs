@@ -2,44 +2,158 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::error::Error;
-use crate::PeerPublicKey;
+use crate::{BandwidthCredential, MacAlgorithm, PeerPublicKey, PowChallenge, PowSolution};
 use base64::{engine::general_purpose, Engine};
-use dashmap::DashMap;
+use dashmap::mapref::entry::Entry;
+use dashmap::{DashMap, DashSet};
 use serde::{Deserialize, Serialize};
 use std::net::IpAddr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use std::{fmt, ops::Deref, str::FromStr};
 
-#[cfg(feature = "verify")]
-use hmac::{Hmac, Mac};
 #[cfg(feature = "verify")]
 use nym_crypto::asymmetric::encryption::PrivateKey;
+
 #[cfg(feature = "verify")]
-use sha2::Sha256;
+pub use crate::mac::HmacSha256;
 
-pub type GatewayClientRegistry = DashMap<PeerPublicKey, GatewayClient>;
-pub type PendingRegistrations = DashMap<PeerPublicKey, Nonce>;
+pub use crate::registry::GatewayClientRegistry;
+pub type PendingRegistrations = DashMap<PeerPublicKey, PendingRegistration>;
 pub type PrivateIPs = DashMap<IpAddr, Free>;
 
-#[cfg(feature = "verify")]
-pub type HmacSha256 = Hmac<Sha256>;
+/// Registered peers that have been temporarily disabled by the operator.
+/// They retain their registry entries, but they're not allowed to send any traffic until resumed.
+pub type SuspendedPeers = DashSet<PeerPublicKey>;
+
+/// Private IPs explicitly requested by clients, alongside the keys of the clients holding them,
+/// so that they would get the same tunnel IP whenever they reconnect.
+pub type IpReservations = DashMap<IpAddr, PeerPublicKey>;
 
 pub type Nonce = u64;
 pub type Free = bool;
 
+/// Version of the registration protocol spoken by this implementation.
+pub const REGISTRATION_PROTOCOL_VERSION: u8 = 2;
+
+/// Oldest version of the registration protocol still accepted by the gateways.
+/// Clients predating the versioning are assumed to speak version 1, so as long as they're supported,
+/// this only rejects the clients explicitly announcing version 0, which has never existed.
+/// It's going to be raised to 2 once the legacy clients are retired.
+pub const MIN_SUPPORTED_REGISTRATION_VERSION: u8 = 1;
+
+/// Maximum difference between the timestamp of the renewal request and the time it's received by the gateway,
+/// in either direction, so that the captured requests couldn't be replayed to keep the registration alive indefinitely.
+pub const MAX_RENEWAL_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Maximum difference between the timestamp of the key rotation request and the time it's received by the gateway.
+/// The nonces of the accepted rotations are remembered for that long, so that they couldn't be replayed.
+pub const MAX_KEY_ROTATION_AGE: Duration = Duration::from_secs(5 * 60);
+
+/// Nonces of the recently accepted key rotations alongside the time at which they got accepted.
+pub type SeenRotationNonces = DashMap<Nonce, SystemTime>;
+
+// clients predating the versioning are not announcing it
+fn legacy_registration_version() -> u8 {
+    1
+}
+
+/// State of a registration awaiting the final message from the client.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingRegistration {
+    pub nonce: Nonce,
+
+    /// Private IP assigned, or reserved, for the client. The final message has to claim the same one.
+    pub private_ip: IpAddr,
+
+    /// Proof of work challenge the client has been issued, if the gateway was under load at the time.
+    pub pow_challenge: Option<PowChallenge>,
+
+    /// Bandwidth credential presented with the initial message. It only gets spent once the final
+    /// message has been verified, so that a failed registration doesn't cost the client its credential.
+    pub credential: Option<BandwidthCredential>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub enum ClientMessage {
     Initial(InitMessage),
-    Final(GatewayClient),
+    Final(FinalMessage),
+    RotateKey(KeyRotationMessage),
+    RenewRegistration(RenewRegistrationMessage),
+}
+
+/// Message finalising the registration, containing the client's data authenticated with the received nonce.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct FinalMessage {
+    #[serde(flatten)]
+    pub gateway_client: GatewayClient,
+
+    /// Solution to the proof of work challenge, if one has been issued with the nonce.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pow_solution: Option<PowSolution>,
+}
+
+impl FinalMessage {
+    pub fn new(gateway_client: GatewayClient) -> Self {
+        FinalMessage {
+            gateway_client,
+            pow_solution: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_pow_solution(mut self, pow_solution: PowSolution) -> Self {
+        self.pow_solution = Some(pow_solution);
+        self
+    }
+
+    /// Solve the issued challenge, if any, for the registration of this client.
+    #[cfg(feature = "verify")]
+    #[must_use]
+    pub fn solving(self, pow_challenge: Option<&PowChallenge>) -> Self {
+        match pow_challenge {
+            Some(challenge) => {
+                let solution = challenge.solve(&self.gateway_client.pub_key);
+                self.with_pow_solution(solution)
+            }
+            None => self,
+        }
+    }
+}
+
+impl From<GatewayClient> for FinalMessage {
+    fn from(gateway_client: GatewayClient) -> Self {
+        FinalMessage::new(gateway_client)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct InitMessage {
+    /// Version of the registration protocol used by the client.
+    #[serde(default = "legacy_registration_version")]
+    pub version: u8,
+
     /// Base64 encoded x25519 public key
     #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
     pub pub_key: PeerPublicKey,
+
+    /// Mac algorithms supported by the client. If empty, only the default `hmac_sha256` is assumed.
+    #[serde(default)]
+    pub supported_macs: Vec<MacAlgorithm>,
+
+    /// Private IP the client wishes to be assigned. Once granted, it stays reserved for this client
+    /// so that it could be reclaimed on subsequent registrations.
+    #[serde(default)]
+    pub requested_ip: Option<IpAddr>,
+
+    /// Base64 encoded presentation of the bandwidth credential authorising the registration.
+    /// It's required by gateways that have credential verification enabled.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, format = Byte))]
+    pub credential: Option<BandwidthCredential>,
 }
 
 impl InitMessage {
@@ -48,10 +162,409 @@ impl InitMessage {
     }
 
     pub fn new(pub_key: PeerPublicKey) -> Self {
-        InitMessage { pub_key }
+        InitMessage {
+            version: REGISTRATION_PROTOCOL_VERSION,
+            pub_key,
+            supported_macs: MacAlgorithm::ALL.to_vec(),
+            requested_ip: None,
+            credential: None,
+        }
+    }
+
+    /// Make sure the gateway still understands the protocol version used by the client.
+    pub fn ensure_supported_version(&self) -> Result<(), Error> {
+        if self.version < MIN_SUPPORTED_REGISTRATION_VERSION {
+            return Err(Error::UnsupportedClientVersion {
+                version: self.version,
+                min_supported: MIN_SUPPORTED_REGISTRATION_VERSION,
+            });
+        }
+        Ok(())
+    }
+
+    #[must_use]
+    pub fn with_requested_ip(mut self, requested_ip: IpAddr) -> Self {
+        self.requested_ip = Some(requested_ip);
+        self
+    }
+
+    #[must_use]
+    pub fn with_credential(mut self, credential: BandwidthCredential) -> Self {
+        self.credential = Some(credential);
+        self
     }
 }
 
+/// Attempt to assign the requested private IP to the client and reserve it for any future registrations.
+///
+/// The request is granted if the IP is either already reserved by the same client
+/// or if it is currently free and not reserved by anybody else.
+/// A client can only hold a single reservation, so any previous one is released.
+pub fn reserve_requested_ip(
+    free_private_ips: &PrivateIPs,
+    reservations: &IpReservations,
+    client: PeerPublicKey,
+    requested_ip: IpAddr,
+) -> Result<(), Error> {
+    let Some(mut free) = free_private_ips.get_mut(&requested_ip) else {
+        return Err(Error::RequestedIpOutsideNetwork { ip: requested_ip });
+    };
+
+    match reservations.entry(requested_ip) {
+        Entry::Occupied(reservation) => {
+            // it's fine to reclaim your own reservation, for example when reconnecting
+            if *reservation.get() != client {
+                return Err(Error::RequestedIpUnavailable { ip: requested_ip });
+            }
+        }
+        Entry::Vacant(reservation) => {
+            // the address is in use by a client that didn't explicitly request it
+            if !*free {
+                return Err(Error::RequestedIpUnavailable { ip: requested_ip });
+            }
+            reservation.insert(client);
+        }
+    }
+    *free = false;
+    drop(free);
+
+    reservations.retain(|ip, holder| *ip == requested_ip || *holder != client);
+    Ok(())
+}
+
+/// Hand over the reservation held by the client under its old key, if any, to its new key,
+/// so that the rotation wouldn't cost it the reserved IP.
+pub fn transfer_reservation(
+    reservations: &IpReservations,
+    old_pub_key: PeerPublicKey,
+    new_pub_key: PeerPublicKey,
+) {
+    for mut holder in reservations.iter_mut() {
+        if *holder == old_pub_key {
+            *holder = new_pub_key;
+        }
+    }
+}
+
+/// Request sent by an already registered client that wishes to replace its public key with a new one.
+/// The request is authenticated with a mac derived from the DH shared secret of the *old* key,
+/// so only the owner of the currently registered key can perform the rotation.
+/// It also carries a second mac derived from the DH shared secret of the *new* key, proving that the client
+/// is in possession of it, and a fresh nonce, so that the request couldn't be replayed.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct KeyRotationMessage {
+    /// Base64 encoded x25519 public key the client is currently registered with
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub old_pub_key: PeerPublicKey,
+
+    /// Base64 encoded x25519 public key that is going to replace the old key
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub new_pub_key: PeerPublicKey,
+
+    /// Private IP currently assigned to the client
+    pub private_ip: IpAddr,
+
+    /// Random nonce chosen by the client. The gateway rejects the nonces it has already seen.
+    pub nonce: Nonce,
+
+    /// Unix timestamp, in seconds, at which the request has been created
+    pub timestamp: u64,
+
+    /// Mac on both keys, the assigned private IP, the nonce and the timestamp,
+    /// keyed with the shared secret of the old key
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub mac: ClientMac,
+
+    /// Mac on both keys, the nonce and the timestamp, keyed with the shared secret of the new key
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub new_key_mac: ClientMac,
+
+    /// Algorithm used for computing the mac
+    #[serde(default)]
+    pub mac_algorithm: MacAlgorithm,
+
+    /// Base64 encoded presentation of the bandwidth credential authorising the rotation.
+    /// It's required by gateways that have credential verification enabled.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, format = Byte))]
+    pub credential: Option<BandwidthCredential>,
+}
+
+impl KeyRotationMessage {
+    /// Create the rotation request. The nonce has to be chosen at random, as the gateway is going to reject
+    /// any nonce it has already seen.
+    #[cfg(feature = "verify")]
+    pub fn new(
+        old_secret: &PrivateKey,
+        new_secret: &PrivateKey,
+        gateway_public: x25519_dalek::PublicKey,
+        private_ip: IpAddr,
+        nonce: Nonce,
+        mac_algorithm: MacAlgorithm,
+    ) -> Self {
+        // convert from 1.0 x25519-dalek private keys into 2.0 x25519-dalek
+        let old_static_secret = x25519_dalek::StaticSecret::from(old_secret.to_bytes());
+        let new_static_secret = x25519_dalek::StaticSecret::from(new_secret.to_bytes());
+        let old_pub_key = PeerPublicKey::new((&old_static_secret).into());
+        let new_pub_key = PeerPublicKey::new((&new_static_secret).into());
+        let timestamp = unix_timestamp(SystemTime::now());
+
+        let old_dh = old_static_secret.diffie_hellman(&gateway_public);
+        let mac = mac_algorithm.compute(
+            old_dh.as_bytes(),
+            &[
+                old_pub_key.as_bytes(),
+                new_pub_key.as_bytes(),
+                private_ip.to_string().as_bytes(),
+                &nonce.to_be_bytes(),
+                &timestamp.to_be_bytes(),
+            ],
+        );
+
+        let new_dh = new_static_secret.diffie_hellman(&gateway_public);
+        let new_key_mac = mac_algorithm.compute(
+            new_dh.as_bytes(),
+            &[
+                old_pub_key.as_bytes(),
+                new_pub_key.as_bytes(),
+                &nonce.to_be_bytes(),
+                &timestamp.to_be_bytes(),
+            ],
+        );
+
+        KeyRotationMessage {
+            old_pub_key,
+            new_pub_key,
+            private_ip,
+            nonce,
+            timestamp,
+            mac,
+            new_key_mac,
+            mac_algorithm,
+            credential: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_credential(mut self, credential: BandwidthCredential) -> Self {
+        self.credential = Some(credential);
+        self
+    }
+
+    /// Verify the request has been created within `MAX_KEY_ROTATION_AGE` of `now`, that it has been authorised
+    /// by the old key and that the client is in possession of the new key.
+    /// `gateway_key` is the wireguard private key of the gateway.
+    #[cfg(feature = "verify")]
+    pub fn verify(&self, gateway_key: &PrivateKey, now: SystemTime) -> Result<(), Error> {
+        // the timestamp is chosen by the client, so it might not even be representable
+        let age = timestamp_age(self.timestamp, now);
+        if !age.is_some_and(|age| age <= MAX_KEY_ROTATION_AGE) {
+            return Err(Error::StaleKeyRotation {
+                client: self.old_pub_key.to_string(),
+                max_age: MAX_KEY_ROTATION_AGE,
+            });
+        }
+
+        // convert from 1.0 x25519-dalek private key into 2.0 x25519-dalek
+        let static_secret = x25519_dalek::StaticSecret::from(gateway_key.to_bytes());
+
+        let old_dh = static_secret.diffie_hellman(&self.old_pub_key);
+        self.mac_algorithm
+            .verify(
+                old_dh.as_bytes(),
+                &[
+                    self.old_pub_key.as_bytes(),
+                    self.new_pub_key.as_bytes(),
+                    self.private_ip.to_string().as_bytes(),
+                    &self.nonce.to_be_bytes(),
+                    &self.timestamp.to_be_bytes(),
+                ],
+                &self.mac,
+            )
+            .map_err(|source| Error::MacMismatch {
+                client: self.old_pub_key.to_string(),
+                expected_len: self.mac_algorithm.output_len(),
+                source: source.into(),
+            })?;
+
+        let new_dh = static_secret.diffie_hellman(&self.new_pub_key);
+        self.mac_algorithm
+            .verify(
+                new_dh.as_bytes(),
+                &[
+                    self.old_pub_key.as_bytes(),
+                    self.new_pub_key.as_bytes(),
+                    &self.nonce.to_be_bytes(),
+                    &self.timestamp.to_be_bytes(),
+                ],
+                &self.new_key_mac,
+            )
+            .map_err(|_| Error::MissingNewKeyPossessionProof {
+                client: self.old_pub_key.to_string(),
+            })
+    }
+
+    /// Remember the nonce of the verified request, failing if it has already been used within `MAX_KEY_ROTATION_AGE`.
+    /// The nonces older than that are forgotten, as their requests would be rejected as stale anyway.
+    pub fn consume_nonce(&self, seen: &SeenRotationNonces, now: SystemTime) -> Result<(), Error> {
+        seen.retain(|_, accepted_at| {
+            now.duration_since(*accepted_at)
+                .map(|age| age <= MAX_KEY_ROTATION_AGE)
+                .unwrap_or(true)
+        });
+
+        match seen.entry(self.nonce) {
+            Entry::Occupied(_) => Err(Error::ReplayedKeyRotation {
+                client: self.old_pub_key.to_string(),
+            }),
+            Entry::Vacant(entry) => {
+                entry.insert(now);
+                Ok(())
+            }
+        }
+    }
+
+    /// Produces registry entry for the client under its new key, retaining its previously assigned private IP.
+    pub fn rotated_client(&self) -> GatewayClient {
+        GatewayClient {
+            pub_key: self.new_pub_key,
+            private_ip: self.private_ip,
+            mac: self.mac.clone(),
+            mac_algorithm: self.mac_algorithm,
+        }
+    }
+}
+
+/// Request sent by a registered client wishing to extend its registration before it expires.
+/// Like the key rotation, it's authenticated with a mac derived from the DH shared secret of the registered key.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct RenewRegistrationMessage {
+    /// Base64 encoded x25519 public key the client is registered with
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub pub_key: PeerPublicKey,
+
+    /// Private IP assigned to the client
+    pub private_ip: IpAddr,
+
+    /// Unix timestamp, in seconds, at which the request has been created
+    pub timestamp: u64,
+
+    /// Mac on the key, the assigned private IP and the timestamp, keyed with the shared secret of the key
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub mac: ClientMac,
+
+    /// Algorithm used for computing the mac
+    #[serde(default)]
+    pub mac_algorithm: MacAlgorithm,
+
+    /// Base64 encoded presentation of the bandwidth credential paying for the extended registration.
+    /// It's required by gateways that have credential verification enabled.
+    #[serde(default)]
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>, format = Byte))]
+    pub credential: Option<BandwidthCredential>,
+}
+
+impl RenewRegistrationMessage {
+    #[cfg(feature = "verify")]
+    pub fn new(
+        secret: &PrivateKey,
+        gateway_public: x25519_dalek::PublicKey,
+        private_ip: IpAddr,
+        mac_algorithm: MacAlgorithm,
+    ) -> Self {
+        // convert from 1.0 x25519-dalek private key into 2.0 x25519-dalek
+        let static_secret = x25519_dalek::StaticSecret::from(secret.to_bytes());
+        let public: x25519_dalek::PublicKey = (&static_secret).into();
+        let pub_key = PeerPublicKey::new(public);
+        let timestamp = unix_timestamp(SystemTime::now());
+
+        let dh = static_secret.diffie_hellman(&gateway_public);
+        let mac = mac_algorithm.compute(
+            dh.as_bytes(),
+            &[
+                pub_key.as_bytes(),
+                private_ip.to_string().as_bytes(),
+                &timestamp.to_be_bytes(),
+            ],
+        );
+
+        RenewRegistrationMessage {
+            pub_key,
+            private_ip,
+            timestamp,
+            mac,
+            mac_algorithm,
+            credential: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_credential(mut self, credential: BandwidthCredential) -> Self {
+        self.credential = Some(credential);
+        self
+    }
+
+    /// Verify the mac of the request and make sure it has been created within `MAX_RENEWAL_AGE` of `now`.
+    #[cfg(feature = "verify")]
+    pub fn verify(&self, gateway_key: &PrivateKey, now: SystemTime) -> Result<(), Error> {
+        // the timestamp is chosen by the client, so it might not even be representable
+        let age = timestamp_age(self.timestamp, now);
+        if !age.is_some_and(|age| age <= MAX_RENEWAL_AGE) {
+            return Err(Error::StaleRenewal {
+                client: self.pub_key.to_string(),
+                max_age: MAX_RENEWAL_AGE,
+            });
+        }
+
+        // convert from 1.0 x25519-dalek private key into 2.0 x25519-dalek
+        let static_secret = x25519_dalek::StaticSecret::from(gateway_key.to_bytes());
+
+        let dh = static_secret.diffie_hellman(&self.pub_key);
+
+        self.mac_algorithm
+            .verify(
+                dh.as_bytes(),
+                &[
+                    self.pub_key.as_bytes(),
+                    self.private_ip.to_string().as_bytes(),
+                    &self.timestamp.to_be_bytes(),
+                ],
+                &self.mac,
+            )
+            .map_err(|source| Error::MacMismatch {
+                client: self.pub_key.to_string(),
+                expected_len: self.mac_algorithm.output_len(),
+                source: source.into(),
+            })
+    }
+}
+
+/// Seconds elapsed since the unix epoch, as used by the timestamps of the registration messages.
+pub fn unix_timestamp(time: SystemTime) -> u64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default()
+}
+
+// Converts the unix timestamp, in seconds, back into the time it represents,
+// or `None` if it's too far in the future to be represented on this platform.
+fn from_unix_timestamp(timestamp: u64) -> Option<SystemTime> {
+    UNIX_EPOCH.checked_add(Duration::from_secs(timestamp))
+}
+
+// Absolute difference between `now` and the time of the timestamp, in either direction,
+// or `None` if the timestamp can't be represented.
+#[cfg(feature = "verify")]
+fn timestamp_age(timestamp: u64, now: SystemTime) -> Option<Duration> {
+    let created_at = from_unix_timestamp(timestamp)?;
+    Some(
+        now.duration_since(created_at)
+            .unwrap_or_else(|err| err.duration()),
+    )
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type", rename_all = "camelCase")]
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
@@ -60,10 +573,48 @@ pub enum ClientRegistrationResponse {
         nonce: u64,
         gateway_data: GatewayClient,
         wg_port: u16,
+
+        /// Proof of work challenge the client has to solve before finalising the registration.
+        /// It's only issued by gateways under load.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        pow_challenge: Option<PowChallenge>,
     },
     Registered {
         success: bool,
+
+        /// Unix timestamp, in seconds, after which the registration expires, unless it gets renewed.
+        /// It's not set if the gateway doesn't put a time bound on the registrations.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
     },
+    KeyRotated {
+        success: bool,
+
+        /// Unix timestamp, in seconds, after which the registration expires, unless it gets renewed.
+        /// The rotation retains the expiry of the old key.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+    },
+    Renewed {
+        success: bool,
+
+        /// Unix timestamp, in seconds, after which the renewed registration expires.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        expires_at: Option<u64>,
+    },
+}
+
+impl ClientRegistrationResponse {
+    /// Time at which the registration the response refers to is going to expire, if it's time-bound.
+    pub fn expires_at(&self) -> Option<SystemTime> {
+        let expires_at = match self {
+            ClientRegistrationResponse::PendingRegistration { .. } => None,
+            ClientRegistrationResponse::Registered { expires_at, .. }
+            | ClientRegistrationResponse::KeyRotated { expires_at, .. }
+            | ClientRegistrationResponse::Renewed { expires_at, .. } => *expires_at,
+        };
+        expires_at.and_then(from_unix_timestamp)
+    }
 }
 
 /// Client that wants to register sends its PublicKey bytes mac digest encrypted with a DH shared secret.
@@ -78,9 +629,13 @@ pub struct GatewayClient {
     /// Assigned private IP
     pub private_ip: IpAddr,
 
-    /// Sha256 hmac on the data (alongside the prior nonce)
+    /// Mac on the data (alongside the prior nonce)
     #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
     pub mac: ClientMac,
+
+    /// Algorithm used for computing the mac
+    #[serde(default)]
+    pub mac_algorithm: MacAlgorithm,
 }
 
 impl GatewayClient {
@@ -90,6 +645,23 @@ impl GatewayClient {
         remote_public: x25519_dalek::PublicKey,
         private_ip: IpAddr,
         nonce: u64,
+    ) -> Self {
+        Self::new_with_mac_algorithm(
+            local_secret,
+            remote_public,
+            private_ip,
+            nonce,
+            MacAlgorithm::default(),
+        )
+    }
+
+    #[cfg(feature = "verify")]
+    pub fn new_with_mac_algorithm(
+        local_secret: &PrivateKey,
+        remote_public: x25519_dalek::PublicKey,
+        private_ip: IpAddr,
+        nonce: u64,
+        mac_algorithm: MacAlgorithm,
     ) -> Self {
         // convert from 1.0 x25519-dalek private key into 2.0 x25519-dalek
         #[allow(clippy::expect_used)]
@@ -98,19 +670,20 @@ impl GatewayClient {
 
         let dh = static_secret.diffie_hellman(&remote_public);
 
-        // TODO: change that to use our nym_crypto::hmac module instead
-        #[allow(clippy::expect_used)]
-        let mut mac = HmacSha256::new_from_slice(dh.as_bytes())
-            .expect("x25519 shared secret is always 32 bytes long");
-
-        mac.update(local_public.as_bytes());
-        mac.update(private_ip.to_string().as_bytes());
-        mac.update(&nonce.to_le_bytes());
+        let mac = mac_algorithm.compute(
+            dh.as_bytes(),
+            &[
+                local_public.as_bytes(),
+                private_ip.to_string().as_bytes(),
+                &nonce.to_le_bytes(),
+            ],
+        );
 
         GatewayClient {
             pub_key: PeerPublicKey::new(local_public),
             private_ip,
-            mac: ClientMac(mac.finalize().into_bytes().to_vec()),
+            mac,
+            mac_algorithm,
         }
     }
 
@@ -124,19 +697,20 @@ impl GatewayClient {
 
         let dh = static_secret.diffie_hellman(&self.pub_key);
 
-        // TODO: change that to use our nym_crypto::hmac module instead
-        #[allow(clippy::expect_used)]
-        let mut mac = HmacSha256::new_from_slice(dh.as_bytes())
-            .expect("x25519 shared secret is always 32 bytes long");
-
-        mac.update(self.pub_key.as_bytes());
-        mac.update(self.private_ip.to_string().as_bytes());
-        mac.update(&nonce.to_le_bytes());
-
-        mac.verify_slice(&self.mac)
-            .map_err(|source| Error::FailedClientMacVerification {
+        self.mac_algorithm
+            .verify(
+                dh.as_bytes(),
+                &[
+                    self.pub_key.as_bytes(),
+                    self.private_ip.to_string().as_bytes(),
+                    &nonce.to_le_bytes(),
+                ],
+                &self.mac,
+            )
+            .map_err(|source| Error::MacMismatch {
                 client: self.pub_key.to_string(),
-                source,
+                expected_len: self.mac_algorithm.output_len(),
+                source: source.into(),
             })
     }
 
@@ -206,6 +780,54 @@ mod tests {
     use super::*;
     use nym_crypto::asymmetric::encryption;
 
+    fn peer(seed: u8) -> PeerPublicKey {
+        PeerPublicKey::new(x25519_dalek::PublicKey::from([seed; 32]))
+    }
+
+    #[test]
+    fn requested_ip_reservations() {
+        let free_ips: PrivateIPs = (1..=4)
+            .map(|i| (IpAddr::from([10, 1, 0, i]), true))
+            .collect();
+        let reservations = IpReservations::new();
+        let ip = |i: u8| IpAddr::from([10, 1, 0, i]);
+
+        reserve_requested_ip(&free_ips, &reservations, peer(1), ip(2)).unwrap();
+        assert!(!*free_ips.get(&ip(2)).unwrap());
+
+        // the same client can reclaim it, but nobody else can take it
+        reserve_requested_ip(&free_ips, &reservations, peer(1), ip(2)).unwrap();
+        assert!(matches!(
+            reserve_requested_ip(&free_ips, &reservations, peer(2), ip(2)),
+            Err(Error::RequestedIpUnavailable { .. })
+        ));
+
+        // addresses assigned without a reservation are not up for grabs either
+        *free_ips.get_mut(&ip(3)).unwrap() = false;
+        assert!(matches!(
+            reserve_requested_ip(&free_ips, &reservations, peer(2), ip(3)),
+            Err(Error::RequestedIpUnavailable { .. })
+        ));
+        assert!(matches!(
+            reserve_requested_ip(&free_ips, &reservations, peer(2), ip(42)),
+            Err(Error::RequestedIpOutsideNetwork { .. })
+        ));
+
+        // switching to a different address releases the previous reservation
+        reserve_requested_ip(&free_ips, &reservations, peer(1), ip(4)).unwrap();
+        assert_eq!(reservations.len(), 1);
+        assert_eq!(*reservations.get(&ip(4)).unwrap(), peer(1));
+
+        // the reservation follows the client when it rotates its key
+        transfer_reservation(&reservations, peer(1), peer(3));
+        assert_eq!(*reservations.get(&ip(4)).unwrap(), peer(3));
+        reserve_requested_ip(&free_ips, &reservations, peer(3), ip(4)).unwrap();
+        assert!(matches!(
+            reserve_requested_ip(&free_ips, &reservations, peer(1), ip(4)),
+            Err(Error::RequestedIpUnavailable { .. })
+        ));
+    }
+
     #[test]
     #[cfg(feature = "verify")]
     fn client_request_roundtrip() {
@@ -224,4 +846,249 @@ mod tests {
         );
         assert!(client.verify(gateway_key_pair.private_key(), nonce).is_ok())
     }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn client_request_with_negotiated_mac_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        let gateway_key_pair = encryption::KeyPair::new(&mut rng);
+        let client_key_pair = encryption::KeyPair::new(&mut rng);
+
+        let nonce = 1234567890;
+
+        let mut client = GatewayClient::new_with_mac_algorithm(
+            client_key_pair.private_key(),
+            x25519_dalek::PublicKey::from(gateway_key_pair.public_key().to_bytes()),
+            "10.0.0.42".parse().unwrap(),
+            nonce,
+            MacAlgorithm::HmacSha512,
+        );
+        assert!(client.verify(gateway_key_pair.private_key(), nonce).is_ok());
+
+        // the mac must be verified with the algorithm it was created with
+        client.mac_algorithm = MacAlgorithm::HmacSha256;
+        assert!(matches!(
+            client.verify(gateway_key_pair.private_key(), nonce),
+            Err(Error::MacMismatch {
+                expected_len: 32,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn legacy_init_messages_are_supported() {
+        let legacy = format!(r#"{{"pub_key":"{}"}}"#, peer(1));
+        let init: InitMessage = serde_json::from_str(&legacy).unwrap();
+        assert_eq!(init.version, 1);
+        assert!(init.ensure_supported_version().is_ok());
+
+        let mut outdated = InitMessage::new(peer(1));
+        outdated.version = 0;
+        assert!(matches!(
+            outdated.ensure_supported_version(),
+            Err(Error::UnsupportedClientVersion {
+                version: 0,
+                min_supported: MIN_SUPPORTED_REGISTRATION_VERSION
+            })
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn key_rotation_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        let gateway_key_pair = encryption::KeyPair::new(&mut rng);
+        let old_client_key_pair = encryption::KeyPair::new(&mut rng);
+        let new_client_key_pair = encryption::KeyPair::new(&mut rng);
+        let new_pub_key = PeerPublicKey::new(x25519_dalek::PublicKey::from(
+            new_client_key_pair.public_key().to_bytes(),
+        ));
+
+        let rotation = KeyRotationMessage::new(
+            old_client_key_pair.private_key(),
+            new_client_key_pair.private_key(),
+            x25519_dalek::PublicKey::from(gateway_key_pair.public_key().to_bytes()),
+            "10.0.0.42".parse().unwrap(),
+            42,
+            MacAlgorithm::Blake3Keyed,
+        );
+        let now = SystemTime::now();
+        assert_eq!(rotation.new_pub_key, new_pub_key);
+        assert!(rotation.verify(gateway_key_pair.private_key(), now).is_ok());
+
+        // the rotation must have been authorised by the old key
+        let mut forged = rotation.clone();
+        forged.old_pub_key = new_pub_key;
+        assert!(forged.verify(gateway_key_pair.private_key(), now).is_err());
+
+        // and the client must prove it holds the new key, so that it couldn't claim somebody else's
+        let unrelated_pub_key = PeerPublicKey::new(x25519_dalek::PublicKey::from(
+            encryption::KeyPair::new(&mut rng).public_key().to_bytes(),
+        ));
+        let mut forged = rotation.clone();
+        forged.new_pub_key = unrelated_pub_key;
+        forged.mac = MacAlgorithm::Blake3Keyed.compute(
+            x25519_dalek::StaticSecret::from(old_client_key_pair.private_key().to_bytes())
+                .diffie_hellman(&x25519_dalek::PublicKey::from(
+                    gateway_key_pair.public_key().to_bytes(),
+                ))
+                .as_bytes(),
+            &[
+                forged.old_pub_key.as_bytes(),
+                forged.new_pub_key.as_bytes(),
+                forged.private_ip.to_string().as_bytes(),
+                &forged.nonce.to_be_bytes(),
+                &forged.timestamp.to_be_bytes(),
+            ],
+        );
+        assert!(matches!(
+            forged.verify(gateway_key_pair.private_key(), now),
+            Err(Error::MissingNewKeyPossessionProof { .. })
+        ));
+
+        assert!(matches!(
+            rotation.verify(
+                gateway_key_pair.private_key(),
+                now + 2 * MAX_KEY_ROTATION_AGE
+            ),
+            Err(Error::StaleKeyRotation { .. })
+        ));
+
+        // timestamps that can't even be represented are rejected rather than overflowing
+        let mut forged = rotation.clone();
+        forged.timestamp = u64::MAX;
+        assert!(matches!(
+            forged.verify(gateway_key_pair.private_key(), now),
+            Err(Error::StaleKeyRotation { .. })
+        ));
+
+        let rotated = rotation.rotated_client();
+        assert_eq!(rotated.pub_key(), new_pub_key);
+        assert_eq!(rotated.private_ip, rotation.private_ip);
+    }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn rotation_nonces_cannot_be_replayed() {
+        let mut rng = rand::thread_rng();
+
+        let gateway_key_pair = encryption::KeyPair::new(&mut rng);
+        let rotation = KeyRotationMessage::new(
+            encryption::KeyPair::new(&mut rng).private_key(),
+            encryption::KeyPair::new(&mut rng).private_key(),
+            x25519_dalek::PublicKey::from(gateway_key_pair.public_key().to_bytes()),
+            "10.0.0.42".parse().unwrap(),
+            42,
+            MacAlgorithm::default(),
+        );
+
+        let seen = SeenRotationNonces::new();
+        let now = SystemTime::now();
+        assert!(rotation.consume_nonce(&seen, now).is_ok());
+        assert!(matches!(
+            rotation.consume_nonce(&seen, now + Duration::from_secs(1)),
+            Err(Error::ReplayedKeyRotation { .. })
+        ));
+
+        // the nonce is forgotten once the request would be considered stale anyway
+        assert!(rotation
+            .consume_nonce(&seen, now + 2 * MAX_KEY_ROTATION_AGE)
+            .is_ok());
+        assert_eq!(seen.len(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "verify")]
+    fn renewal_roundtrip() {
+        let mut rng = rand::thread_rng();
+
+        let gateway_key_pair = encryption::KeyPair::new(&mut rng);
+        let client_key_pair = encryption::KeyPair::new(&mut rng);
+
+        let renewal = RenewRegistrationMessage::new(
+            client_key_pair.private_key(),
+            x25519_dalek::PublicKey::from(gateway_key_pair.public_key().to_bytes()),
+            "10.0.0.42".parse().unwrap(),
+            MacAlgorithm::HmacSha256,
+        );
+        let now = SystemTime::now();
+        assert!(renewal.verify(gateway_key_pair.private_key(), now).is_ok());
+
+        // the timestamp is covered by the mac
+        let mut forged = renewal.clone();
+        forged.timestamp += 1;
+        assert!(matches!(
+            forged.verify(gateway_key_pair.private_key(), now),
+            Err(Error::MacMismatch { .. })
+        ));
+
+        // and captured requests can't be replayed later on
+        assert!(matches!(
+            renewal.verify(
+                gateway_key_pair.private_key(),
+                now + MAX_RENEWAL_AGE + Duration::from_secs(60)
+            ),
+            Err(Error::StaleRenewal { .. })
+        ));
+
+        // timestamps that can't even be represented are rejected rather than overflowing
+        let mut forged = renewal.clone();
+        forged.timestamp = u64::MAX;
+        assert!(matches!(
+            forged.verify(gateway_key_pair.private_key(), now),
+            Err(Error::StaleRenewal { .. })
+        ));
+    }
+
+    #[test]
+    fn registration_expiry_is_optional() {
+        let legacy: ClientRegistrationResponse =
+            serde_json::from_str(r#"{"type":"registered","success":true}"#).unwrap();
+        assert!(legacy.expires_at().is_none());
+
+        let response = ClientRegistrationResponse::Renewed {
+            success: true,
+            expires_at: Some(1_700_000_000),
+        };
+        let encoded = serde_json::to_string(&response).unwrap();
+        let decoded: ClientRegistrationResponse = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(
+            decoded.expires_at(),
+            Some(UNIX_EPOCH + Duration::from_secs(1_700_000_000))
+        );
+
+        let unrepresentable = ClientRegistrationResponse::Renewed {
+            success: true,
+            expires_at: Some(u64::MAX),
+        };
+        assert!(unrepresentable.expires_at().is_none());
+    }
+
+    #[test]
+    fn final_message_is_backwards_compatible() {
+        let client = GatewayClient {
+            pub_key: peer(1),
+            private_ip: "10.0.0.42".parse().unwrap(),
+            mac: ClientMac::new(vec![1, 2, 3]),
+            mac_algorithm: MacAlgorithm::HmacSha256,
+        };
+
+        // messages without the solution look exactly like they used to
+        let legacy = serde_json::to_value(&client).unwrap();
+        let message = ClientMessage::Final(FinalMessage::new(client.clone()));
+        let mut encoded = serde_json::to_value(&message).unwrap();
+        encoded.as_object_mut().unwrap().remove("type");
+        assert_eq!(encoded, legacy);
+
+        let message = ClientMessage::Final(FinalMessage::new(client).with_pow_solution(42));
+        let encoded = serde_json::to_string(&message).unwrap();
+        let ClientMessage::Final(decoded) = serde_json::from_str(&encoded).unwrap() else {
+            panic!("unexpected message type")
+        };
+        assert_eq!(decoded.gateway_client.pub_key, peer(1));
+        assert_eq!(decoded.pow_solution, Some(42));
+    }
 }
@@ -0,0 +1,347 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::metadata::PeerMetadata;
+use crate::registration::GatewayClient;
+use crate::PeerPublicKey;
+use dashmap::mapref::entry::Entry;
+use dashmap::mapref::multiple::RefMulti;
+use dashmap::mapref::one::{Ref, RefMut};
+use dashmap::DashMap;
+use std::collections::BTreeMap;
+use std::time::SystemTime;
+
+/// Registry of the clients registered with the gateway.
+///
+/// It exposes the same api as the `DashMap` it wraps. On top of that, it holds the classification
+/// labels and the expiry times of the registered peers, which are dropped alongside their entries.
+#[derive(Debug, Default)]
+pub struct GatewayClientRegistry {
+    clients: DashMap<PeerPublicKey, GatewayClient>,
+    metadata: DashMap<PeerPublicKey, PeerMetadata>,
+    expirations: DashMap<PeerPublicKey, SystemTime>,
+}
+
+impl GatewayClientRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&self, key: PeerPublicKey, client: GatewayClient) -> Option<GatewayClient> {
+        self.clients.insert(key, client)
+    }
+
+    pub fn get(&self, key: &PeerPublicKey) -> Option<Ref<'_, PeerPublicKey, GatewayClient>> {
+        self.clients.get(key)
+    }
+
+    pub fn get_mut(&self, key: &PeerPublicKey) -> Option<RefMut<'_, PeerPublicKey, GatewayClient>> {
+        self.clients.get_mut(key)
+    }
+
+    pub fn remove(&self, key: &PeerPublicKey) -> Option<(PeerPublicKey, GatewayClient)> {
+        let removed = self.clients.remove(key);
+        self.metadata.remove(key);
+        self.expirations.remove(key);
+        removed
+    }
+
+    pub fn contains_key(&self, key: &PeerPublicKey) -> bool {
+        self.clients.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.clients.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.clients.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = RefMulti<'_, PeerPublicKey, GatewayClient>> {
+        self.clients.iter()
+    }
+
+    pub fn retain(&self, f: impl FnMut(&PeerPublicKey, &mut GatewayClient) -> bool) {
+        self.clients.retain(f);
+        self.metadata.retain(|key, _| self.contains_key(key));
+        self.expirations.retain(|key, _| self.contains_key(key));
+    }
+
+    pub fn clear(&self) {
+        self.clients.clear();
+        self.metadata.clear();
+        self.expirations.clear();
+    }
+
+    // The registration is checked whilst holding the entry of the auxiliary map, so that the peer
+    // couldn't be concurrently removed in between, leaving its data behind.
+    // Note that `remove` never holds both locks at the same time.
+    fn registered_entry<'a, V>(
+        &self,
+        map: &'a DashMap<PeerPublicKey, V>,
+        key: PeerPublicKey,
+    ) -> Result<Entry<'a, PeerPublicKey, V>, Error> {
+        let entry = map.entry(key);
+        if !self.contains_key(&key) {
+            return Err(Error::PeerNotRegistered {
+                client: key.to_string(),
+            });
+        }
+        Ok(entry)
+    }
+
+    /// Replace the classification labels of the registered peer, returning the previous ones.
+    pub fn set_metadata(
+        &self,
+        key: PeerPublicKey,
+        metadata: PeerMetadata,
+    ) -> Result<Option<PeerMetadata>, Error> {
+        Ok(match self.registered_entry(&self.metadata, key)? {
+            Entry::Occupied(mut entry) => Some(entry.insert(metadata)),
+            Entry::Vacant(entry) => {
+                entry.insert(metadata);
+                None
+            }
+        })
+    }
+
+    /// Modify the classification labels of the registered peer in place.
+    pub fn update_metadata(
+        &self,
+        key: PeerPublicKey,
+        f: impl FnOnce(&mut PeerMetadata),
+    ) -> Result<(), Error> {
+        f(&mut self.registered_entry(&self.metadata, key)?.or_default());
+        Ok(())
+    }
+
+    /// Classification labels of the peer. Registered peers without any labels have empty metadata.
+    pub fn metadata(&self, key: &PeerPublicKey) -> Option<PeerMetadata> {
+        if let Some(metadata) = self.metadata.get(key) {
+            return Some(metadata.clone());
+        }
+        self.contains_key(key).then(PeerMetadata::default)
+    }
+
+    /// Keys of all the registered peers with the label set to the provided value.
+    pub fn peers_with_label(&self, label: &str, value: &str) -> Vec<PeerPublicKey> {
+        self.metadata
+            .iter()
+            .filter(|entry| entry.value().has_label(label, value))
+            .map(|entry| *entry.key())
+            .filter(|key| self.contains_key(key))
+            .collect()
+    }
+
+    /// Number of the registered peers per each value of the provided label.
+    /// Peers without the label are not counted.
+    pub fn label_counts(&self, label: &str) -> BTreeMap<String, usize> {
+        let mut counts = BTreeMap::new();
+        for entry in self.metadata.iter() {
+            if let Some(value) = entry.value().label(label) {
+                if self.contains_key(entry.key()) {
+                    *counts.entry(value.to_string()).or_default() += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    /// Set the time at which the registration of the peer expires, returning the previous one.
+    /// Setting it to `None` makes the registration last until the peer is explicitly removed.
+    pub fn set_expiry(
+        &self,
+        key: PeerPublicKey,
+        expires_at: Option<SystemTime>,
+    ) -> Result<Option<SystemTime>, Error> {
+        Ok(
+            match (self.registered_entry(&self.expirations, key)?, expires_at) {
+                (Entry::Occupied(mut entry), Some(expires_at)) => Some(entry.insert(expires_at)),
+                (Entry::Occupied(entry), None) => Some(entry.remove()),
+                (Entry::Vacant(entry), Some(expires_at)) => {
+                    entry.insert(expires_at);
+                    None
+                }
+                (Entry::Vacant(_), None) => None,
+            },
+        )
+    }
+
+    /// Time at which the registration of the peer expires, if it's time-bound.
+    pub fn expires_at(&self, key: &PeerPublicKey) -> Option<SystemTime> {
+        self.expirations.get(key).map(|expires_at| *expires_at)
+    }
+
+    /// Checks whether the registration of the peer has expired by the provided time,
+    /// even if the peer hasn't been removed yet.
+    pub fn is_expired(&self, key: &PeerPublicKey, now: SystemTime) -> bool {
+        self.expires_at(key)
+            .is_some_and(|expires_at| expires_at <= now)
+    }
+
+    /// Remove all the peers whose registrations have expired by the provided time, returning their entries.
+    /// Registrations renewed concurrently with the removal are retained.
+    pub fn remove_expired(&self, now: SystemTime) -> Vec<GatewayClient> {
+        let expired = self
+            .expirations
+            .iter()
+            .filter(|entry| *entry.value() <= now)
+            .map(|entry| *entry.key())
+            .collect::<Vec<_>>();
+
+        expired
+            .into_iter()
+            .filter(|key| {
+                self.expirations
+                    .remove_if(key, |_, expires_at| *expires_at <= now)
+                    .is_some()
+            })
+            .filter_map(|key| self.remove(&key).map(|(_, client)| client))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientMac, MacAlgorithm};
+
+    fn client(seed: u8) -> GatewayClient {
+        GatewayClient {
+            pub_key: PeerPublicKey::new(x25519_dalek::PublicKey::from([seed; 32])),
+            private_ip: [10, 1, 0, seed].into(),
+            mac: ClientMac::new(vec![]),
+            mac_algorithm: MacAlgorithm::default(),
+        }
+    }
+
+    #[test]
+    fn behaves_like_a_single_map() {
+        let registry = GatewayClientRegistry::new();
+        assert!(registry.is_empty());
+
+        for seed in 0..10 {
+            assert!(registry
+                .insert(client(seed).pub_key, client(seed))
+                .is_none());
+        }
+        assert_eq!(registry.len(), 10);
+        assert!(registry.contains_key(&client(3).pub_key));
+        assert_eq!(
+            registry.get(&client(7).pub_key).unwrap().private_ip,
+            client(7).private_ip
+        );
+
+        assert_eq!(registry.iter().count(), 10);
+
+        assert!(registry.remove(&client(3).pub_key).is_some());
+        assert!(registry.remove(&client(3).pub_key).is_none());
+
+        registry.retain(|_, c| c.private_ip != client(4).private_ip);
+        assert_eq!(registry.len(), 8);
+
+        registry.clear();
+        assert!(registry.is_empty());
+    }
+
+    #[test]
+    fn peer_metadata_is_kept_alongside_the_entries() {
+        let registry = GatewayClientRegistry::new();
+        for seed in 0..4 {
+            registry.insert(client(seed).pub_key, client(seed));
+        }
+        let key = |seed: u8| client(seed).pub_key;
+
+        let tier = |tier: &str| {
+            PeerMetadata::new()
+                .with_platform("android")
+                .with_credential_tier(tier)
+        };
+        registry.set_metadata(key(0), tier("free")).unwrap();
+        registry.set_metadata(key(1), tier("premium")).unwrap();
+        registry.set_metadata(key(2), tier("premium")).unwrap();
+        registry
+            .update_metadata(key(2), |metadata| {
+                metadata.set_label(crate::metadata::PLATFORM_LABEL, "linux");
+            })
+            .unwrap();
+        assert!(matches!(
+            registry.set_metadata(key(42), tier("free")),
+            Err(Error::PeerNotRegistered { .. })
+        ));
+
+        assert_eq!(registry.metadata(&key(3)), Some(PeerMetadata::default()));
+        assert_eq!(registry.metadata(&key(42)), None);
+        assert_eq!(
+            registry.metadata(&key(2)).unwrap().platform(),
+            Some("linux")
+        );
+
+        let mut premium =
+            registry.peers_with_label(crate::metadata::CREDENTIAL_TIER_LABEL, "premium");
+        premium.sort_by_key(|key| key.as_bytes().to_vec());
+        assert_eq!(premium, vec![key(1), key(2)]);
+        assert_eq!(
+            registry.label_counts(crate::metadata::PLATFORM_LABEL),
+            [("android".to_string(), 2), ("linux".to_string(), 1)].into()
+        );
+
+        // the labels are gone together with the peer
+        registry.remove(&key(1));
+        registry.retain(|key, _| *key != client(2).pub_key);
+        assert!(registry
+            .peers_with_label(crate::metadata::CREDENTIAL_TIER_LABEL, "premium")
+            .is_empty());
+        registry.insert(key(1), client(1));
+        assert_eq!(registry.metadata(&key(1)), Some(PeerMetadata::default()));
+    }
+
+    #[test]
+    fn expired_registrations_are_removed() {
+        let registry = GatewayClientRegistry::new();
+        for seed in 0..4 {
+            registry.insert(client(seed).pub_key, client(seed));
+        }
+        let key = |seed: u8| client(seed).pub_key;
+        let now = SystemTime::now();
+        let minute = std::time::Duration::from_secs(60);
+
+        registry.set_expiry(key(0), Some(now - minute)).unwrap();
+        registry.set_expiry(key(1), Some(now + minute)).unwrap();
+        registry.set_expiry(key(2), Some(now - minute)).unwrap();
+        assert!(matches!(
+            registry.set_expiry(key(42), Some(now)),
+            Err(Error::PeerNotRegistered { .. })
+        ));
+
+        // renewing the registration pushes back its expiry
+        assert_eq!(
+            registry.set_expiry(key(2), Some(now + minute)).unwrap(),
+            Some(now - minute)
+        );
+        assert!(registry.is_expired(&key(0), now));
+        assert!(!registry.is_expired(&key(2), now));
+        assert!(!registry.is_expired(&key(3), now));
+
+        let expired = registry.remove_expired(now);
+        assert_eq!(
+            expired.iter().map(|c| c.pub_key).collect::<Vec<_>>(),
+            vec![key(0)]
+        );
+        assert!(!registry.contains_key(&key(0)));
+        assert_eq!(registry.expires_at(&key(0)), None);
+        assert_eq!(registry.len(), 3);
+
+        // peers without an expiry are never removed
+        let mut expired = registry
+            .remove_expired(now + 2 * minute)
+            .into_iter()
+            .map(|c| c.pub_key)
+            .collect::<Vec<_>>();
+        expired.sort_by_key(|key| key.as_bytes().to_vec());
+        assert_eq!(expired, vec![key(1), key(2)]);
+        assert!(registry.contains_key(&key(3)));
+    }
+}
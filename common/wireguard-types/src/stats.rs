@@ -0,0 +1,220 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::PeerPublicKey;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::net::{IpAddr, SocketAddr};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+// value used by the dump for any optional field that is not set
+const DUMP_NONE: &str = "(none)";
+
+// number of tab-separated fields in a peer line of the dump
+const PEER_DUMP_FIELDS: usize = 8;
+
+/// Network allowed to be routed through a particular peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct AllowedIp {
+    #[cfg_attr(feature = "openapi", schema(value_type = String))]
+    pub address: IpAddr,
+    pub prefix: u8,
+}
+
+impl fmt::Display for AllowedIp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.address, self.prefix)
+    }
+}
+
+impl FromStr for AllowedIp {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let malformed = |reason: &str| Error::MalformedPeerDump {
+            line: s.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let (address, prefix) = s
+            .split_once('/')
+            .ok_or_else(|| malformed("allowed ip is missing its prefix"))?;
+        let address: IpAddr = address
+            .parse()
+            .map_err(|_| malformed("invalid allowed ip address"))?;
+        let prefix: u8 = prefix
+            .parse()
+            .map_err(|_| malformed("invalid allowed ip prefix"))?;
+
+        let max_prefix = if address.is_ipv4() { 32 } else { 128 };
+        if prefix > max_prefix {
+            return Err(malformed("allowed ip prefix is out of range"));
+        }
+
+        Ok(AllowedIp { address, prefix })
+    }
+}
+
+/// Typed snapshot of the state of a single wireguard peer, as reported by the interface.
+/// It's shared between the http api and the metrics exporters, so they'd always agree on the values.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
+pub struct PeerStats {
+    /// Base64 encoded x25519 public key of the peer
+    #[cfg_attr(feature = "openapi", schema(value_type = String, format = Byte))]
+    pub pub_key: PeerPublicKey,
+
+    /// Unix timestamp (in seconds) of the most recent handshake with the peer, if any.
+    pub last_handshake: Option<u64>,
+
+    /// Number of bytes received from the peer.
+    pub rx_bytes: u64,
+
+    /// Number of bytes sent to the peer.
+    pub tx_bytes: u64,
+
+    /// The most recent address the peer has been seen at.
+    #[cfg_attr(feature = "openapi", schema(value_type = Option<String>))]
+    pub endpoint: Option<SocketAddr>,
+
+    /// Networks routed through this peer.
+    pub allowed_ips: Vec<AllowedIp>,
+}
+
+impl PeerStats {
+    /// Parse a single peer line of the `wg show <interface> dump` output, i.e.
+    /// `public-key preshared-key endpoint allowed-ips latest-handshake transfer-rx transfer-tx persistent-keepalive`.
+    pub fn from_dump_line(line: &str) -> Result<Self, Error> {
+        let malformed = |reason: &str| Error::MalformedPeerDump {
+            line: line.to_string(),
+            reason: reason.to_string(),
+        };
+
+        let fields: Vec<_> = line.trim_end().split('\t').collect();
+        if fields.len() != PEER_DUMP_FIELDS {
+            return Err(malformed(&format!(
+                "expected {PEER_DUMP_FIELDS} fields, got {}",
+                fields.len()
+            )));
+        }
+
+        let pub_key = fields[0].parse()?;
+
+        let endpoint = match fields[2] {
+            DUMP_NONE => None,
+            endpoint => Some(
+                endpoint
+                    .parse()
+                    .map_err(|_| malformed("invalid peer endpoint"))?,
+            ),
+        };
+
+        let allowed_ips = match fields[3] {
+            DUMP_NONE => Vec::new(),
+            allowed_ips => allowed_ips
+                .split(',')
+                .map(str::parse)
+                .collect::<Result<_, _>>()?,
+        };
+
+        let last_handshake = match fields[4]
+            .parse::<u64>()
+            .map_err(|_| malformed("invalid latest handshake timestamp"))?
+        {
+            0 => None,
+            timestamp => Some(timestamp),
+        };
+
+        let rx_bytes = fields[5]
+            .parse()
+            .map_err(|_| malformed("invalid number of received bytes"))?;
+        let tx_bytes = fields[6]
+            .parse()
+            .map_err(|_| malformed("invalid number of sent bytes"))?;
+
+        Ok(PeerStats {
+            pub_key,
+            last_handshake,
+            rx_bytes,
+            tx_bytes,
+            endpoint,
+            allowed_ips,
+        })
+    }
+
+    /// Parse the full `wg show <interface> dump` output. The first line describes the interface itself
+    /// and is skipped, while each of the remaining ones corresponds to a single peer.
+    pub fn parse_dump(dump: &str) -> Result<Vec<Self>, Error> {
+        dump.lines()
+            .skip(1)
+            .filter(|line| !line.trim().is_empty())
+            .map(PeerStats::from_dump_line)
+            .collect()
+    }
+
+    /// Time of the most recent handshake with the peer, if any.
+    pub fn last_handshake_time(&self) -> Option<SystemTime> {
+        self.last_handshake
+            .map(|timestamp| UNIX_EPOCH + Duration::from_secs(timestamp))
+    }
+
+    /// Time elapsed since the most recent handshake with the peer, if any.
+    pub fn since_last_handshake(&self, now: SystemTime) -> Option<Duration> {
+        self.last_handshake_time()
+            .map(|handshake| now.duration_since(handshake).unwrap_or_default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PEER_KEY: &str = "mYTmNs1Dz+7yWdNo6C1EUHw2lKt5Sgp2EVjQpCLr6mI=";
+    const DUMP: &str = "\
+oPP0dO2pwSA/1U0k8MJqFj5YdC5k6oWmK3m3ma1iy1k=\tmYTmNs1Dz+7yWdNo6C1EUHw2lKt5Sgp2EVjQpCLr6mI=\t51822\toff
+mYTmNs1Dz+7yWdNo6C1EUHw2lKt5Sgp2EVjQpCLr6mI=\t(none)\t1.2.3.4:51820\t10.1.0.2/32,fc01::2/128\t1700000000\t1024\t2048\toff
+mYTmNs1Dz+7yWdNo6C1EUHw2lKt5Sgp2EVjQpCLr6mI=\t(none)\t(none)\t(none)\t0\t0\t0\t25
+";
+
+    #[test]
+    fn parsing_peer_dump() {
+        let stats = PeerStats::parse_dump(DUMP).unwrap();
+        assert_eq!(stats.len(), 2);
+
+        let active = &stats[0];
+        assert_eq!(active.pub_key, PEER_KEY.parse().unwrap());
+        assert_eq!(active.last_handshake, Some(1700000000));
+        assert_eq!(active.rx_bytes, 1024);
+        assert_eq!(active.tx_bytes, 2048);
+        assert_eq!(active.endpoint, Some("1.2.3.4:51820".parse().unwrap()));
+        assert_eq!(
+            active
+                .allowed_ips
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>(),
+            vec!["10.1.0.2/32", "fc01::2/128"]
+        );
+
+        let idle = &stats[1];
+        assert!(idle.last_handshake.is_none());
+        assert!(idle.endpoint.is_none());
+        assert!(idle.allowed_ips.is_empty());
+    }
+
+    #[test]
+    fn malformed_peer_lines_are_rejected() {
+        assert!(PeerStats::from_dump_line(&format!("{PEER_KEY}\t(none)\t(none)")).is_err());
+        assert!(PeerStats::from_dump_line(&format!(
+            "{PEER_KEY}\t(none)\t(none)\t10.1.0.2/33\t0\t0\t0\toff"
+        ))
+        .is_err());
+        assert!(PeerStats::from_dump_line(&format!(
+            "{PEER_KEY}\t(none)\t(none)\t(none)\tnever\t0\t0\toff"
+        ))
+        .is_err());
+    }
+}
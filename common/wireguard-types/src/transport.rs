@@ -0,0 +1,158 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::registration::{ClientMessage, ClientRegistrationResponse};
+use async_trait::async_trait;
+use log::{debug, warn};
+
+pub type TransportError = Box<dyn std::error::Error + Send + Sync>;
+
+/// Channel over which the registration messages are exchanged with the gateway.
+/// Regardless of the underlying transport, the same `ClientMessage` / `ClientRegistrationResponse`
+/// exchange takes place, so the gateway handles all of them identically.
+#[async_trait]
+pub trait RegistrationTransport: Send + Sync {
+    /// Name of the transport used for logging and error reporting.
+    fn name(&self) -> &'static str;
+
+    /// Send the registration message to the gateway and wait for its response.
+    async fn exchange(
+        &self,
+        message: &ClientMessage,
+    ) -> Result<ClientRegistrationResponse, TransportError>;
+}
+
+/// Transport that attempts the exchange over each of the underlying transports, in order,
+/// until one of them succeeds. This allows clients behind networks blocking the primary channel
+/// to still complete their registration, e.g. over the gateway's HTTPS endpoint.
+pub struct FallbackTransport {
+    transports: Vec<Box<dyn RegistrationTransport>>,
+}
+
+impl FallbackTransport {
+    pub fn new(primary: Box<dyn RegistrationTransport>) -> Self {
+        FallbackTransport {
+            transports: vec![primary],
+        }
+    }
+
+    #[must_use]
+    pub fn with_fallback(mut self, fallback: Box<dyn RegistrationTransport>) -> Self {
+        self.transports.push(fallback);
+        self
+    }
+
+    /// Send the registration message using the first transport that manages to deliver it.
+    /// Note that every step of the registration goes through the same procedure, so it's
+    /// possible for the initial message and the final one to be sent over different transports.
+    pub async fn exchange(
+        &self,
+        message: &ClientMessage,
+    ) -> Result<ClientRegistrationResponse, Error> {
+        let mut failures = Vec::with_capacity(self.transports.len());
+        for transport in &self.transports {
+            match transport.exchange(message).await {
+                Ok(response) => {
+                    debug!("completed registration exchange over {}", transport.name());
+                    return Ok(response);
+                }
+                Err(err) => {
+                    warn!(
+                        "registration exchange over {} has failed: {err}",
+                        transport.name()
+                    );
+                    failures.push(format!("{}: {err}", transport.name()));
+                }
+            }
+        }
+
+        Err(Error::RegistrationTransportsExhausted {
+            failures: failures.join("; "),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::registration::InitMessage;
+    use crate::PeerPublicKey;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    struct MockTransport {
+        name: &'static str,
+        succeeds: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl RegistrationTransport for MockTransport {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+
+        async fn exchange(
+            &self,
+            _message: &ClientMessage,
+        ) -> Result<ClientRegistrationResponse, TransportError> {
+            self.calls.fetch_add(1, Ordering::Relaxed);
+            if self.succeeds {
+                Ok(ClientRegistrationResponse::Registered {
+                    success: true,
+                    expires_at: None,
+                })
+            } else {
+                Err("blocked".into())
+            }
+        }
+    }
+
+    fn mock(name: &'static str, succeeds: bool) -> (Box<MockTransport>, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let transport = Box::new(MockTransport {
+            name,
+            succeeds,
+            calls: Arc::clone(&calls),
+        });
+        (transport, calls)
+    }
+
+    fn message() -> ClientMessage {
+        ClientMessage::Initial(InitMessage::new(PeerPublicKey::new([1u8; 32].into())))
+    }
+
+    #[tokio::test]
+    async fn fallback_is_only_used_when_primary_fails() {
+        let (primary, primary_calls) = mock("primary", true);
+        let (https, https_calls) = mock("https", true);
+        let transport = FallbackTransport::new(primary).with_fallback(https);
+
+        transport.exchange(&message()).await.unwrap();
+        assert_eq!(primary_calls.load(Ordering::Relaxed), 1);
+        assert_eq!(https_calls.load(Ordering::Relaxed), 0);
+
+        let (primary, _) = mock("primary", false);
+        let (https, https_calls) = mock("https", true);
+        let transport = FallbackTransport::new(primary).with_fallback(https);
+
+        transport.exchange(&message()).await.unwrap();
+        assert_eq!(https_calls.load(Ordering::Relaxed), 1);
+    }
+
+    #[tokio::test]
+    async fn all_failures_are_reported() {
+        let (primary, _) = mock("primary", false);
+        let (https, _) = mock("https", false);
+        let transport = FallbackTransport::new(primary).with_fallback(https);
+
+        let Err(Error::RegistrationTransportsExhausted { failures }) =
+            transport.exchange(&message()).await
+        else {
+            panic!("expected the exchange to fail")
+        };
+        assert!(failures.contains("primary"));
+        assert!(failures.contains("https"));
+    }
+}
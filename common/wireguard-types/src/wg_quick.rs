@@ -0,0 +1,448 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::error::Error;
+use crate::registration::GatewayClient;
+use crate::{AllowedIp, PeerPublicKey, ProvisioningPayload};
+use base64::engine::general_purpose;
+use base64::Engine;
+use std::fmt;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::str::FromStr;
+use x25519_dalek::PublicKey;
+
+/// Keepalive interval, in seconds, set on the exported gateway peers, so that the tunnel survives
+/// NATs dropping idle mappings.
+pub const DEFAULT_PERSISTENT_KEEPALIVE: u16 = 25;
+
+/// `[Interface]` section of a `wg-quick` configuration file.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WgQuickInterface {
+    /// The x25519 private key of the local end of the tunnel
+    pub private_key: Option<[u8; 32]>,
+
+    /// Addresses assigned to the local end of the tunnel
+    pub addresses: Vec<AllowedIp>,
+
+    pub listen_port: Option<u16>,
+
+    /// DNS servers to use while the tunnel is up
+    pub dns: Vec<IpAddr>,
+
+    pub mtu: Option<u16>,
+
+    /// Any other `wg-quick` specific settings, such as `PostUp` scripts, preserved verbatim.
+    pub other: Vec<(String, String)>,
+}
+
+/// `[Peer]` section of a `wg-quick` configuration file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WgQuickPeer {
+    pub public_key: PeerPublicKey,
+
+    pub preshared_key: Option<[u8; 32]>,
+
+    /// Networks routed through the peer
+    pub allowed_ips: Vec<AllowedIp>,
+
+    pub endpoint: Option<SocketAddr>,
+
+    /// Keepalive interval in seconds, if any
+    pub persistent_keepalive: Option<u16>,
+}
+
+impl WgQuickPeer {
+    pub fn new(public_key: PeerPublicKey) -> Self {
+        WgQuickPeer {
+            public_key,
+            preshared_key: None,
+            allowed_ips: Vec::new(),
+            endpoint: None,
+            persistent_keepalive: None,
+        }
+    }
+}
+
+/// Entry of the client registered with the gateway, as seen from the gateway's interface.
+impl From<&GatewayClient> for WgQuickPeer {
+    fn from(client: &GatewayClient) -> Self {
+        WgQuickPeer {
+            allowed_ips: vec![host_network(client.private_ip)],
+            ..WgQuickPeer::new(client.pub_key)
+        }
+    }
+}
+
+/// Configuration file understood by `wg-quick` and the WireGuard apps, allowing to bring up
+/// a registered nym wireguard session outside of the nym clients.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct WgQuickConfig {
+    pub interface: WgQuickInterface,
+    pub peers: Vec<WgQuickPeer>,
+}
+
+impl WgQuickConfig {
+    /// Build the client configuration out of the provisioning payload of the registration,
+    /// routing all the traffic through the gateway.
+    pub fn from_provisioning(private_key: [u8; 32], payload: &ProvisioningPayload) -> Self {
+        let gateway = WgQuickPeer {
+            preshared_key: payload.preshared_key,
+            allowed_ips: vec![
+                AllowedIp {
+                    address: Ipv4Addr::UNSPECIFIED.into(),
+                    prefix: 0,
+                },
+                AllowedIp {
+                    address: Ipv6Addr::UNSPECIFIED.into(),
+                    prefix: 0,
+                },
+            ],
+            endpoint: Some(payload.gateway_endpoint),
+            persistent_keepalive: Some(DEFAULT_PERSISTENT_KEEPALIVE),
+            ..WgQuickPeer::new(payload.gateway_public_key)
+        };
+
+        WgQuickConfig {
+            interface: WgQuickInterface {
+                private_key: Some(private_key),
+                addresses: vec![host_network(payload.private_ip)],
+                dns: payload.dns.clone(),
+                ..Default::default()
+            },
+            peers: vec![gateway],
+        }
+    }
+
+    /// Build the client configuration out of the data returned by the gateway during the registration.
+    pub fn from_registration(
+        private_key: [u8; 32],
+        gateway_ip: IpAddr,
+        wg_port: u16,
+        gateway_data: &GatewayClient,
+    ) -> Self {
+        let payload = ProvisioningPayload::from_registration(gateway_ip, wg_port, gateway_data);
+        WgQuickConfig::from_provisioning(private_key, &payload)
+    }
+}
+
+fn host_network(address: IpAddr) -> AllowedIp {
+    let prefix = if address.is_ipv4() { 32 } else { 128 };
+    AllowedIp { address, prefix }
+}
+
+fn encode_key(key: &[u8]) -> String {
+    general_purpose::STANDARD.encode(key)
+}
+
+fn join<T: fmt::Display>(values: &[T]) -> String {
+    values
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl fmt::Display for WgQuickConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let interface = &self.interface;
+        writeln!(f, "[Interface]")?;
+        if let Some(private_key) = &interface.private_key {
+            writeln!(f, "PrivateKey = {}", encode_key(private_key))?;
+        }
+        if !interface.addresses.is_empty() {
+            writeln!(f, "Address = {}", join(&interface.addresses))?;
+        }
+        if let Some(listen_port) = interface.listen_port {
+            writeln!(f, "ListenPort = {listen_port}")?;
+        }
+        if !interface.dns.is_empty() {
+            writeln!(f, "DNS = {}", join(&interface.dns))?;
+        }
+        if let Some(mtu) = interface.mtu {
+            writeln!(f, "MTU = {mtu}")?;
+        }
+        for (key, value) in &interface.other {
+            writeln!(f, "{key} = {value}")?;
+        }
+
+        for peer in &self.peers {
+            writeln!(f)?;
+            writeln!(f, "[Peer]")?;
+            writeln!(f, "PublicKey = {}", peer.public_key)?;
+            if let Some(preshared_key) = &peer.preshared_key {
+                writeln!(f, "PresharedKey = {}", encode_key(preshared_key))?;
+            }
+            if !peer.allowed_ips.is_empty() {
+                writeln!(f, "AllowedIPs = {}", join(&peer.allowed_ips))?;
+            }
+            if let Some(endpoint) = peer.endpoint {
+                writeln!(f, "Endpoint = {endpoint}")?;
+            }
+            if let Some(keepalive) = peer.persistent_keepalive {
+                writeln!(f, "PersistentKeepalive = {keepalive}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+enum Section {
+    None,
+    Interface,
+    Peer(PartialPeer),
+}
+
+#[derive(Default)]
+struct PartialPeer {
+    public_key: Option<PeerPublicKey>,
+    preshared_key: Option<[u8; 32]>,
+    allowed_ips: Vec<AllowedIp>,
+    endpoint: Option<SocketAddr>,
+    persistent_keepalive: Option<u16>,
+}
+
+impl FromStr for WgQuickConfig {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut interface = None;
+        let mut peers = Vec::new();
+        let mut section = Section::None;
+
+        // finish the section once the next one starts or the file ends
+        let mut finish = |section: Section, line: usize| -> Result<(), Error> {
+            if let Section::Peer(peer) = section {
+                let Some(public_key) = peer.public_key else {
+                    return Err(malformed(line, "the peer is missing its public key"));
+                };
+                peers.push(WgQuickPeer {
+                    public_key,
+                    preshared_key: peer.preshared_key,
+                    allowed_ips: peer.allowed_ips,
+                    endpoint: peer.endpoint,
+                    persistent_keepalive: peer.persistent_keepalive,
+                })
+            }
+            Ok(())
+        };
+
+        for (i, raw) in s.lines().enumerate() {
+            let line_number = i + 1;
+            let malformed = |reason: &str| malformed(line_number, reason);
+
+            // both '#' and ';' start comments, which also might follow the values
+            let line = raw.split(['#', ';']).next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(name) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+                let next = match name.trim().to_ascii_lowercase().as_str() {
+                    "interface" if interface.is_some() => {
+                        return Err(malformed("duplicate [Interface] section"))
+                    }
+                    "interface" => {
+                        interface = Some(WgQuickInterface::default());
+                        Section::Interface
+                    }
+                    "peer" => Section::Peer(PartialPeer::default()),
+                    _ => return Err(malformed("unknown section")),
+                };
+                finish(std::mem::replace(&mut section, next), line_number)?;
+                continue;
+            }
+
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(malformed("expected a 'key = value' pair"));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let list = || value.split(',').map(str::trim).filter(|v| !v.is_empty());
+
+            match &mut section {
+                Section::None => return Err(malformed("setting outside of any section")),
+                Section::Interface => {
+                    let Some(interface) = interface.as_mut() else {
+                        return Err(malformed("setting outside of any section"));
+                    };
+                    match key.to_ascii_lowercase().as_str() {
+                        "privatekey" => interface.private_key = Some(decode_key(value, malformed)?),
+                        "address" => {
+                            for address in list() {
+                                interface.addresses.push(parse_network(address, malformed)?)
+                            }
+                        }
+                        "listenport" => {
+                            interface.listen_port = Some(parse(value, "listen port", malformed)?)
+                        }
+                        "dns" => {
+                            for dns in list() {
+                                interface.dns.push(parse(dns, "dns server", malformed)?)
+                            }
+                        }
+                        "mtu" => interface.mtu = Some(parse(value, "mtu", malformed)?),
+                        _ => interface.other.push((key.to_string(), value.to_string())),
+                    }
+                }
+                Section::Peer(peer) => match key.to_ascii_lowercase().as_str() {
+                    "publickey" => {
+                        peer.public_key = Some(PeerPublicKey::new(PublicKey::from(decode_key(
+                            value, malformed,
+                        )?)))
+                    }
+                    "presharedkey" => peer.preshared_key = Some(decode_key(value, malformed)?),
+                    "allowedips" => {
+                        for allowed_ip in list() {
+                            peer.allowed_ips.push(parse_network(allowed_ip, malformed)?)
+                        }
+                    }
+                    "endpoint" => peer.endpoint = Some(parse(value, "endpoint", malformed)?),
+                    "persistentkeepalive" if value.eq_ignore_ascii_case("off") => {
+                        peer.persistent_keepalive = None
+                    }
+                    "persistentkeepalive" => {
+                        peer.persistent_keepalive =
+                            Some(parse(value, "persistent keepalive", malformed)?)
+                    }
+                    _ => return Err(malformed("unknown peer setting")),
+                },
+            }
+        }
+        finish(section, s.lines().count())?;
+
+        let Some(interface) = interface else {
+            return Err(malformed(0, "missing the [Interface] section"));
+        };
+        Ok(WgQuickConfig { interface, peers })
+    }
+}
+
+fn malformed(line: usize, reason: &str) -> Error {
+    Error::MalformedWgQuickConfig {
+        line,
+        reason: reason.to_string(),
+    }
+}
+
+fn parse<T: FromStr>(
+    value: &str,
+    what: &str,
+    malformed: impl Fn(&str) -> Error,
+) -> Result<T, Error> {
+    value
+        .parse()
+        .map_err(|_| malformed(&format!("invalid {what} '{value}'")))
+}
+
+// the addresses might be specified without the prefix, in which case they denote a single host
+fn parse_network(value: &str, malformed: impl Fn(&str) -> Error) -> Result<AllowedIp, Error> {
+    if value.contains('/') {
+        return value
+            .parse()
+            .map_err(|_| malformed(&format!("invalid network '{value}'")));
+    }
+    Ok(host_network(parse(value, "address", malformed)?))
+}
+
+fn decode_key(value: &str, malformed: impl Fn(&str) -> Error) -> Result<[u8; 32], Error> {
+    general_purpose::STANDARD
+        .decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(|| malformed("expected a base64-encoded 32 bytes long key"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ClientMac, MacAlgorithm};
+
+    fn gateway_data() -> GatewayClient {
+        GatewayClient {
+            pub_key: PeerPublicKey::new(PublicKey::from([42u8; 32])),
+            private_ip: "10.1.0.2".parse().unwrap(),
+            mac: ClientMac::new(vec![]),
+            mac_algorithm: MacAlgorithm::default(),
+        }
+    }
+
+    #[test]
+    fn exported_registration_roundtrip() {
+        let config = WgQuickConfig::from_registration(
+            [7u8; 32],
+            "1.2.3.4".parse().unwrap(),
+            51822,
+            &gateway_data(),
+        );
+
+        let exported = config.to_string();
+        assert!(exported.contains("Address = 10.1.0.2/32\n"));
+        assert!(exported.contains("AllowedIPs = 0.0.0.0/0, ::/0\n"));
+        assert!(exported.contains("Endpoint = 1.2.3.4:51822\n"));
+        assert_eq!(exported.parse::<WgQuickConfig>().unwrap(), config);
+    }
+
+    #[test]
+    fn parsing_wg_quick_config() {
+        let config: WgQuickConfig = r#"
+            # exported from another app
+            [Interface]
+            PrivateKey = BwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwcHBwc=
+            Address = 10.1.0.2, fd00::2/128
+            DNS = 1.1.1.1
+            PostUp = iptables -A FORWARD -i %i -j ACCEPT
+
+            [peer]
+            publickey = KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio= ; the gateway
+            AllowedIPs = 0.0.0.0/0
+            Endpoint = [2001:db8::1]:51822
+            PersistentKeepalive = off
+        "#
+        .parse()
+        .unwrap();
+
+        assert_eq!(config.interface.private_key, Some([7u8; 32]));
+        assert_eq!(
+            config.interface.addresses,
+            vec![
+                "10.1.0.2/32".parse().unwrap(),
+                "fd00::2/128".parse().unwrap()
+            ]
+        );
+        assert_eq!(config.interface.other.len(), 1);
+        assert_eq!(config.peers.len(), 1);
+        assert_eq!(config.peers[0].public_key, gateway_data().pub_key);
+        assert_eq!(
+            config.peers[0].endpoint,
+            Some("[2001:db8::1]:51822".parse().unwrap())
+        );
+        assert_eq!(config.peers[0].persistent_keepalive, None);
+    }
+
+    #[test]
+    fn registered_clients_map_to_gateway_peers() {
+        let peer = WgQuickPeer::from(&gateway_data());
+        assert_eq!(peer.allowed_ips, vec!["10.1.0.2/32".parse().unwrap()]);
+        assert_eq!(peer.endpoint, None);
+    }
+
+    #[test]
+    fn malformed_configs_are_rejected() {
+        for config in [
+            "",
+            "PrivateKey = foo",
+            "[Interface]\n[Interface]",
+            "[Interface]\n[Peer]\nAllowedIPs = 0.0.0.0/0",
+            "[Interface]\nPrivateKey = Zm9v",
+            "[Interface]\n[Peer]\nPublicKey = KioqKioqKioqKioqKioqKioqKioqKioqKioqKioqKio=\nFoo = bar",
+            "[Wat]",
+        ] {
+            assert!(
+                matches!(
+                    config.parse::<WgQuickConfig>(),
+                    Err(Error::MalformedWgQuickConfig { .. })
+                ),
+                "{config}"
+            );
+        }
+    }
+}
@@ -4,28 +4,57 @@
 // #![warn(clippy::unwrap_used)]
 
 use defguard_wireguard_rs::WGApi;
+use std::sync::Arc;
+
+pub mod peer_controller;
 
 const WG_TUN_NAME: &str = "nymwg";
 
+// how often the peers with expired registrations are removed
+const EXPIRED_PEERS_SWEEP_INTERVAL: std::time::Duration = std::time::Duration::from_secs(60);
+
 pub struct WgApiWrapper {
-    wg_api: WGApi,
+    wg_api: Arc<WGApi>,
 }
 
 impl WgApiWrapper {
-    pub fn new(wg_api: WGApi) -> Self {
+    pub fn new(wg_api: Arc<WGApi>) -> Self {
         WgApiWrapper { wg_api }
     }
 }
 
 impl Drop for WgApiWrapper {
     fn drop(&mut self) {
-        if let Err(e) = defguard_wireguard_rs::WireguardInterfaceApi::remove_interface(&self.wg_api)
+        if let Err(e) =
+            defguard_wireguard_rs::WireguardInterfaceApi::remove_interface(self.wg_api.as_ref())
         {
             log::error!("Could not remove the wireguard interface: {:?}", e);
         }
     }
 }
 
+/// Interface peer allowed to only use its own private IP.
+#[cfg(target_os = "linux")]
+fn interface_peer(
+    pub_key: nym_wireguard_types::PeerPublicKey,
+    private_ip: std::net::IpAddr,
+) -> defguard_wireguard_rs::host::Peer {
+    use defguard_wireguard_rs::{host::Peer, key::Key, net::IpAddrMask};
+
+    let host_prefix = if private_ip.is_ipv4() { 32 } else { 128 };
+    let mut peer = Peer::new(Key::new(pub_key.to_bytes()));
+    peer.set_allowed_ips(vec![IpAddrMask::new(private_ip, host_prefix)]);
+    peer
+}
+
+#[cfg(target_os = "linux")]
+fn apply_peer_event(interface: &WGApi, event: nym_wireguard_types::PeerEvent) {
+    let pub_key = event.pub_key();
+    if let Err(err) = peer_controller::handle_peer_event(interface, event) {
+        log::error!("failed to update the wireguard interface for peer {pub_key}: {err}");
+    }
+}
+
 /// Start wireguard device
 #[cfg(target_os = "linux")]
 pub async fn start_wireguard(
@@ -33,16 +62,19 @@ pub async fn start_wireguard(
     wireguard_data: std::sync::Arc<nym_wireguard_types::WireguardGatewayData>,
 ) -> Result<WgApiWrapper, Box<dyn std::error::Error + Send + Sync + 'static>> {
     use base64::{prelude::BASE64_STANDARD, Engine};
-    use defguard_wireguard_rs::{
-        host::Peer, key::Key, net::IpAddrMask, InterfaceConfiguration, WGApi, WireguardInterfaceApi,
-    };
+    use defguard_wireguard_rs::{InterfaceConfiguration, WGApi, WireguardInterfaceApi};
+    use tokio::sync::broadcast::error::RecvError;
+
+    // subscribe before reading the registry so that no changes could be missed in between
+    let mut peer_events = wireguard_data.subscribe_peer_events();
 
     let mut peers = vec![];
     for peer_client in wireguard_data.client_registry().iter() {
-        let mut peer = Peer::new(Key::new(peer_client.pub_key.to_bytes()));
-        let peer_ip_mask = IpAddrMask::new(peer_client.private_ip, 32);
-        peer.set_allowed_ips(vec![peer_ip_mask]);
-        peers.push(peer);
+        // suspended peers retain their registrations, but they're not allowed to send any traffic
+        if wireguard_data.is_peer_suspended(&peer_client.pub_key) {
+            continue;
+        }
+        peers.push(interface_peer(peer_client.pub_key, peer_client.private_ip));
     }
 
     let ifname = String::from(WG_TUN_NAME);
@@ -58,7 +90,31 @@ pub async fn start_wireguard(
     wgapi.configure_interface(&interface_config)?;
     // wgapi.configure_peer_routing(&peers)?;
 
-    tokio::spawn(async move { task_client.recv().await });
+    let wgapi = Arc::new(wgapi);
+    let interface = Arc::clone(&wgapi);
+    tokio::spawn(async move {
+        let mut sweep_interval = tokio::time::interval(EXPIRED_PEERS_SWEEP_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = task_client.recv() => break,
+                event = peer_events.recv() => match event {
+                    Ok(event) => apply_peer_event(interface.as_ref(), event),
+                    Err(RecvError::Lagged(missed)) => log::error!(
+                        "the wireguard interface has missed {missed} peer updates \
+                        and might be out of sync with the registry"
+                    ),
+                    Err(RecvError::Closed) => break,
+                },
+                _ = sweep_interval.tick() => {
+                    // the peers are taken off the interface once the emitted removal events are handled
+                    let expired = wireguard_data.remove_expired_peers(std::time::SystemTime::now());
+                    if !expired.is_empty() {
+                        log::info!("{} peer registrations have expired, removing them from the interface", expired.len());
+                    }
+                }
+            }
+        }
+    });
 
     Ok(WgApiWrapper::new(wgapi))
 }
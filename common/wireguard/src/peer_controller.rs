@@ -0,0 +1,225 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: Apache-2.0
+
+use nym_wireguard_types::{PeerEvent, PeerPublicKey};
+use std::fmt::Display;
+use std::net::IpAddr;
+
+/// Operations on the wireguard interface needed for keeping it in sync with the registered peers.
+pub trait PeerControl {
+    type Error: Display;
+
+    /// Add the peer to the interface, or update its allowed IPs if it's already there.
+    fn configure_peer(&self, pub_key: PeerPublicKey, private_ip: IpAddr)
+        -> Result<(), Self::Error>;
+
+    /// Remove the peer from the interface, so that it can no longer send any traffic.
+    fn remove_peer(&self, pub_key: PeerPublicKey) -> Result<(), Self::Error>;
+}
+
+#[cfg(target_os = "linux")]
+impl PeerControl for defguard_wireguard_rs::WGApi {
+    type Error = defguard_wireguard_rs::error::WireguardInterfaceError;
+
+    fn configure_peer(
+        &self,
+        pub_key: PeerPublicKey,
+        private_ip: IpAddr,
+    ) -> Result<(), Self::Error> {
+        defguard_wireguard_rs::WireguardInterfaceApi::configure_peer(
+            self,
+            &crate::interface_peer(pub_key, private_ip),
+        )
+    }
+
+    fn remove_peer(&self, pub_key: PeerPublicKey) -> Result<(), Self::Error> {
+        let key = defguard_wireguard_rs::key::Key::new(pub_key.to_bytes());
+        defguard_wireguard_rs::WireguardInterfaceApi::remove_peer(self, &key)
+    }
+}
+
+/// Apply the change to the registered peers to the wireguard interface.
+pub fn handle_peer_event<C: PeerControl>(control: &C, event: PeerEvent) -> Result<(), C::Error> {
+    match event {
+        PeerEvent::PeerRegistered {
+            pub_key,
+            private_ip,
+        } => control.configure_peer(pub_key, private_ip),
+        PeerEvent::PeerRemoved { pub_key } => control.remove_peer(pub_key),
+        PeerEvent::PeerKeyRotated {
+            old_pub_key,
+            new_pub_key,
+            private_ip,
+        } => {
+            control.remove_peer(old_pub_key)?;
+            control.configure_peer(new_pub_key, private_ip)
+        }
+        // suspended peers are kept in the registry, but they're taken off the interface
+        // so that they couldn't send any traffic until resumed
+        PeerEvent::PeerSuspended { pub_key } => control.remove_peer(pub_key),
+        PeerEvent::PeerResumed {
+            pub_key,
+            private_ip,
+        } => control.configure_peer(pub_key, private_ip),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::encryption::KeyPair;
+    use nym_wireguard_types::{
+        ClientMac, Config, GatewayClient, MacAlgorithm, PeerEventReceiver, WireguardGatewayData,
+    };
+    use std::cell::RefCell;
+    use std::collections::HashMap;
+    use std::convert::Infallible;
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+
+    /// Interface that only keeps track of the configured peers.
+    #[derive(Default)]
+    struct MockInterface {
+        peers: RefCell<HashMap<PeerPublicKey, IpAddr>>,
+    }
+
+    impl PeerControl for MockInterface {
+        type Error = Infallible;
+
+        fn configure_peer(
+            &self,
+            pub_key: PeerPublicKey,
+            private_ip: IpAddr,
+        ) -> Result<(), Self::Error> {
+            self.peers.borrow_mut().insert(pub_key, private_ip);
+            Ok(())
+        }
+
+        fn remove_peer(&self, pub_key: PeerPublicKey) -> Result<(), Self::Error> {
+            self.peers.borrow_mut().remove(&pub_key);
+            Ok(())
+        }
+    }
+
+    fn peer(seed: u8) -> PeerPublicKey {
+        PeerPublicKey::new(x25519_dalek::PublicKey::from([seed; 32]))
+    }
+
+    fn gateway_data() -> WireguardGatewayData {
+        let mut rng = rand::thread_rng();
+        let config = Config {
+            bind_address: "0.0.0.0:51822".parse().unwrap(),
+            private_ip: "10.1.0.1".parse().unwrap(),
+            announced_port: 51822,
+            private_network_prefix: 16,
+            registration_mac: MacAlgorithm::default(),
+            max_registered_peers: None,
+            pow_rate_threshold: None,
+            registration_ttl: None,
+            require_bandwidth_credential: false,
+        };
+        WireguardGatewayData::new(config, Arc::new(KeyPair::new(&mut rng)))
+    }
+
+    fn register(data: &WireguardGatewayData, interface: &MockInterface, seed: u8) -> IpAddr {
+        let pub_key = peer(seed);
+        let private_ip = [10, 1, 0, seed].into();
+        data.client_registry().insert(
+            pub_key,
+            GatewayClient {
+                pub_key,
+                private_ip,
+                mac: ClientMac::new(vec![]),
+                mac_algorithm: MacAlgorithm::default(),
+            },
+        );
+        handle_peer_event(
+            interface,
+            PeerEvent::PeerRegistered {
+                pub_key,
+                private_ip,
+            },
+        )
+        .unwrap();
+        private_ip
+    }
+
+    fn apply_emitted_events(interface: &MockInterface, events: &mut PeerEventReceiver) {
+        while let Ok(event) = events.try_recv() {
+            handle_peer_event(interface, event).unwrap();
+        }
+    }
+
+    #[test]
+    fn suspended_peers_are_taken_off_the_interface_until_resumed() {
+        let data = gateway_data();
+        let interface = MockInterface::default();
+        let mut events = data.subscribe_peer_events();
+
+        let suspended_ip = register(&data, &interface, 1);
+        let active_ip = register(&data, &interface, 2);
+
+        assert!(data.suspend_peer(peer(1)).unwrap());
+        apply_emitted_events(&interface, &mut events);
+        assert!(!interface.peers.borrow().contains_key(&peer(1)));
+        assert_eq!(interface.peers.borrow().get(&peer(2)), Some(&active_ip));
+
+        assert!(data.resume_peer(peer(1)).unwrap());
+        apply_emitted_events(&interface, &mut events);
+        assert_eq!(interface.peers.borrow().get(&peer(1)), Some(&suspended_ip));
+        assert_eq!(interface.peers.borrow().get(&peer(2)), Some(&active_ip));
+    }
+
+    #[test]
+    fn expired_peers_are_removed_from_the_interface() {
+        let data = gateway_data();
+        let interface = MockInterface::default();
+        let mut events = data.subscribe_peer_events();
+
+        let now = SystemTime::now();
+        register(&data, &interface, 1);
+        let active_ip = register(&data, &interface, 2);
+        data.client_registry()
+            .set_expiry(peer(1), Some(now - Duration::from_secs(1)))
+            .unwrap();
+        data.client_registry()
+            .set_expiry(peer(2), Some(now + Duration::from_secs(60)))
+            .unwrap();
+
+        assert_eq!(data.remove_expired_peers(now), vec![peer(1)]);
+        apply_emitted_events(&interface, &mut events);
+        assert!(!interface.peers.borrow().contains_key(&peer(1)));
+        assert_eq!(interface.peers.borrow().get(&peer(2)), Some(&active_ip));
+    }
+
+    #[test]
+    fn registrations_and_removals_are_applied_to_the_interface() {
+        let interface = MockInterface::default();
+        let private_ip: IpAddr = "10.1.0.42".parse().unwrap();
+
+        handle_peer_event(
+            &interface,
+            PeerEvent::PeerRegistered {
+                pub_key: peer(1),
+                private_ip,
+            },
+        )
+        .unwrap();
+        assert_eq!(interface.peers.borrow().get(&peer(1)), Some(&private_ip));
+
+        handle_peer_event(
+            &interface,
+            PeerEvent::PeerKeyRotated {
+                old_pub_key: peer(1),
+                new_pub_key: peer(2),
+                private_ip,
+            },
+        )
+        .unwrap();
+        assert!(!interface.peers.borrow().contains_key(&peer(1)));
+        assert_eq!(interface.peers.borrow().get(&peer(2)), Some(&private_ip));
+
+        handle_peer_event(&interface, PeerEvent::PeerRemoved { pub_key: peer(2) }).unwrap();
+        assert!(interface.peers.borrow().is_empty());
+    }
+}
@@ -195,6 +195,7 @@ impl TestSetup {
                 &MixnetExecuteMsg::AdvanceCurrentEpoch {
                     new_rewarded_set,
                     expected_active_set_size: current_params.active_set_size,
+                    selection_provenance: None,
                 },
                 &[],
             )
@@ -236,6 +237,7 @@ impl TestSetup {
             verloc_port: 1790,
             http_api_port: 8000,
             version: "1.1.14".to_string(),
+            next_sphinx_key: None,
         };
 
         let payload = MixnodeBondingPayload::new(mixnode.clone(), cost_params);
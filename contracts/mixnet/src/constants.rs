@@ -17,6 +17,9 @@ pub const GATEWAY_BOND_MAX_RETRIEVAL_LIMIT: u32 = 150;
 pub const MIXNODE_BOND_DEFAULT_RETRIEVAL_LIMIT: u32 = 100;
 pub const MIXNODE_BOND_MAX_RETRIEVAL_LIMIT: u32 = 150;
 
+pub const SKIMMED_MIXNODES_DEFAULT_RETRIEVAL_LIMIT: u32 = 250;
+pub const SKIMMED_MIXNODES_MAX_RETRIEVAL_LIMIT: u32 = 400;
+
 pub const MIXNODE_DETAILS_DEFAULT_RETRIEVAL_LIMIT: u32 = 75;
 pub const MIXNODE_DETAILS_MAX_RETRIEVAL_LIMIT: u32 = 100;
 
@@ -35,6 +38,12 @@ pub const INTERVAL_EVENTS_MAX_RETRIEVAL_LIMIT: u32 = 250;
 pub const REWARDED_SET_DEFAULT_RETRIEVAL_LIMIT: u32 = 500;
 pub const REWARDED_SET_MAX_RETRIEVAL_LIMIT: u32 = 1000;
 
+// with hourly epochs, that's 30 days worth of active sets
+pub const ACTIVE_SET_HISTORY_RETENTION: u32 = 720;
+
+pub const REWARDING_SIMULATION_DEFAULT_RETRIEVAL_LIMIT: u32 = 75;
+pub const REWARDING_SIMULATION_MAX_RETRIEVAL_LIMIT: u32 = 100;
+
 pub const FAMILIES_DEFAULT_RETRIEVAL_LIMIT: u32 = 10;
 pub const FAMILIES_MAX_RETRIEVAL_LIMIT: u32 = 20;
 
@@ -42,11 +51,13 @@ pub const FAMILIES_MAX_RETRIEVAL_LIMIT: u32 = 20;
 pub const DELEGATION_PK_NAMESPACE: &str = "dl";
 pub const DELEGATION_OWNER_IDX_NAMESPACE: &str = "dlo";
 pub const DELEGATION_MIXNODE_IDX_NAMESPACE: &str = "dlm";
+pub const PENDING_DELEGATIONS_NAMESPACE: &str = "pdl";
 
 pub const GATEWAYS_PK_NAMESPACE: &str = "gt";
 pub const GATEWAYS_OWNER_IDX_NAMESPACE: &str = "gto";
 
 pub const REWARDED_SET_KEY: &str = "rs";
+pub const ACTIVE_SET_HISTORY_NAMESPACE: &str = "ash";
 pub const CURRENT_EPOCH_STATUS_KEY: &str = "ces";
 pub const CURRENT_INTERVAL_KEY: &str = "ci";
 pub const EPOCH_EVENT_ID_COUNTER_KEY: &str = "eic";
@@ -66,6 +77,9 @@ pub const MIXNODES_PK_NAMESPACE: &str = "mnn";
 pub const MIXNODES_OWNER_IDX_NAMESPACE: &str = "mno";
 pub const MIXNODES_IDENTITY_IDX_NAMESPACE: &str = "mni";
 pub const MIXNODES_SPHINX_IDX_NAMESPACE: &str = "mns";
+pub const MIXNODE_DESCRIPTIONS_NAMESPACE: &str = "mnd";
+pub const PENDING_OWNERSHIP_TRANSFERS_NAMESPACE: &str = "pot";
+pub const PENDING_SPHINX_KEY_ROTATIONS_NAMESPACE: &str = "psk";
 
 pub const UNBONDED_MIXNODES_PK_NAMESPACE: &str = "ubm";
 pub const UNBONDED_MIXNODES_OWNER_IDX_NAMESPACE: &str = "umo";
@@ -74,6 +88,7 @@ pub const UNBONDED_MIXNODES_IDENTITY_IDX_NAMESPACE: &str = "umi";
 pub const REWARDING_PARAMS_KEY: &str = "rparams";
 pub const PENDING_REWARD_POOL_KEY: &str = "prp";
 pub const MIXNODES_REWARDING_PK_NAMESPACE: &str = "mnr";
+pub const MIXNODES_PERFORMANCE_NAMESPACE: &str = "mnp";
 
 pub const FAMILIES_INDEX_NAMESPACE: &str = "faml2";
 pub const FAMILIES_MAP_NAMESPACE: &str = "fam2";
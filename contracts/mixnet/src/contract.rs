@@ -40,6 +40,8 @@ fn default_initial_state(
                 denom: rewarding_denom,
                 amount: INITIAL_GATEWAY_PLEDGE_AMOUNT,
             },
+            max_delegation_to_pledge_ratio: None,
+            min_supported_version: None,
         },
     }
 }
@@ -207,12 +209,14 @@ pub fn execute(
             new_rewarded_set,
             // families_in_layer,
             expected_active_set_size,
+            selection_provenance,
         } => crate::interval::transactions::try_advance_epoch(
             deps,
             env,
             info,
             new_rewarded_set,
             expected_active_set_size,
+            selection_provenance,
         ),
         ExecuteMsg::ReconcileEpochEvents { limit } => {
             crate::interval::transactions::try_reconcile_epoch_events(deps, env, info, limit)
@@ -287,6 +291,29 @@ pub fn execute(
                 deps, info, new_config, owner,
             )
         }
+        ExecuteMsg::UpdateMixnodeDescription { description } => {
+            crate::mixnodes::transactions::try_update_mixnode_description(deps, info, description)
+        }
+        ExecuteMsg::TransferMixNodeOwnership { new_owner } => {
+            crate::mixnodes::transactions::try_propose_mixnode_ownership_transfer(
+                deps, env, info, new_owner,
+            )
+        }
+        ExecuteMsg::CancelMixNodeOwnershipTransfer {} => {
+            crate::mixnodes::transactions::try_cancel_mixnode_ownership_transfer(deps, info)
+        }
+        ExecuteMsg::AcceptMixNodeOwnership { mix_id } => {
+            crate::mixnodes::transactions::try_accept_mixnode_ownership(deps, info, mix_id)
+        }
+        ExecuteMsg::RotateMixnodeSphinxKey {
+            next_sphinx_key,
+            activation_epoch,
+        } => crate::mixnodes::transactions::try_rotate_mixnode_sphinx_key(
+            deps,
+            info,
+            next_sphinx_key,
+            activation_epoch,
+        ),
 
         // gateway-related:
         ExecuteMsg::BondGateway {
@@ -410,12 +437,18 @@ pub fn query(
         QueryMsg::GetStateParams {} => to_binary(
             &crate::mixnet_contract_settings::queries::query_contract_settings_params(deps)?,
         ),
+        QueryMsg::GetMinSupportedMixnodeVersion {} => to_binary(
+            &crate::mixnet_contract_settings::queries::query_min_supported_mixnode_version(deps)?,
+        ),
         QueryMsg::GetState {} => {
             to_binary(&crate::mixnet_contract_settings::queries::query_contract_state(deps)?)
         }
         QueryMsg::GetRewardingParams {} => {
             to_binary(&crate::rewards::queries::query_rewarding_params(deps)?)
         }
+        QueryMsg::GetStakeSummary {} => {
+            to_binary(&crate::rewards::queries::query_stake_summary(deps)?)
+        }
         QueryMsg::GetEpochStatus {} => {
             to_binary(&crate::interval::queries::query_epoch_status(deps)?)
         }
@@ -425,14 +458,30 @@ pub fn query(
         QueryMsg::GetRewardedSet { limit, start_after } => to_binary(
             &crate::interval::queries::query_rewarded_set_paged(deps, start_after, limit)?,
         ),
+        QueryMsg::GetEpochActiveSet { epoch_id } => to_binary(
+            &crate::interval::queries::query_epoch_active_set(deps, epoch_id)?,
+        ),
+        QueryMsg::GetActiveSetMembership { mix_id, epoch_id } => to_binary(
+            &crate::interval::queries::query_active_set_membership(deps, mix_id, epoch_id)?,
+        ),
 
         // mixnode-related:
         QueryMsg::GetMixNodeBonds { start_after, limit } => to_binary(
             &crate::mixnodes::queries::query_mixnode_bonds_paged(deps, start_after, limit)?,
         ),
+        QueryMsg::GetSkimmedMixNodes { start_after, limit } => to_binary(
+            &crate::mixnodes::queries::query_skimmed_mixnodes_paged(deps, start_after, limit)?,
+        ),
         QueryMsg::GetMixNodesDetailed { start_after, limit } => to_binary(
             &crate::mixnodes::queries::query_mixnodes_details_paged(deps, start_after, limit)?,
         ),
+        QueryMsg::GetMixNodesDetailedWithStatus { start_after, limit } => to_binary(
+            &crate::mixnodes::queries::query_mixnodes_details_with_status_paged(
+                deps,
+                start_after,
+                limit,
+            )?,
+        ),
         QueryMsg::GetUnbondedMixNodes { limit, start_after } => to_binary(
             &crate::mixnodes::queries::query_unbonded_mixnodes_paged(deps, start_after, limit)?,
         ),
@@ -466,12 +515,24 @@ pub fn query(
         QueryMsg::GetMixnodeDetails { mix_id } => to_binary(
             &crate::mixnodes::queries::query_mixnode_details(deps, mix_id)?,
         ),
+        QueryMsg::GetMixnodeDetailsWithPerformance { mix_id } => to_binary(
+            &crate::mixnodes::queries::query_mixnode_details_with_performance(deps, mix_id)?,
+        ),
+        QueryMsg::GetMixnodeDescription { mix_id } => to_binary(
+            &crate::mixnodes::queries::query_mixnode_description(deps, mix_id)?,
+        ),
+        QueryMsg::GetPendingOwnershipTransfer { mix_id } => to_binary(
+            &crate::mixnodes::queries::query_pending_ownership_transfer(deps, mix_id)?,
+        ),
         QueryMsg::GetMixnodeRewardingDetails { mix_id } => to_binary(
             &crate::mixnodes::queries::query_mixnode_rewarding_details(deps, mix_id)?,
         ),
         QueryMsg::GetStakeSaturation { mix_id } => to_binary(
             &crate::mixnodes::queries::query_stake_saturation(deps, mix_id)?,
         ),
+        QueryMsg::GetMixnodePledgeBreakdown { mix_id } => to_binary(
+            &crate::mixnodes::queries::query_mixnode_pledge_breakdown(deps, mix_id)?,
+        ),
         QueryMsg::GetUnbondedMixNodeInformation { mix_id } => to_binary(
             &crate::mixnodes::queries::query_unbonded_mixnode(deps, mix_id)?,
         ),
@@ -567,6 +628,16 @@ pub fn query(
                 estimated_performance,
             )?,
         ),
+        QueryMsg::SimulateEpochRewarding {
+            params,
+            limit,
+            start_after,
+        } => to_binary(&crate::rewards::queries::query_simulated_epoch_rewarding(
+            deps,
+            params,
+            start_after,
+            limit,
+        )?),
 
         // interval-related
         QueryMsg::GetPendingEpochEvents { limit, start_after } => {
@@ -577,6 +648,19 @@ pub fn query(
                 limit,
             )?)
         }
+        QueryMsg::GetPendingMixNodeDelegationEvents {
+            mix_id,
+            limit,
+            start_after,
+        } => to_binary(
+            &crate::interval::queries::query_pending_mixnode_delegation_events_paged(
+                deps,
+                env,
+                mix_id,
+                start_after,
+                limit,
+            )?,
+        ),
         QueryMsg::GetPendingIntervalEvents { limit, start_after } => to_binary(
             &crate::interval::queries::query_pending_interval_events_paged(
                 deps,
@@ -698,6 +782,8 @@ mod tests {
                     denom: "uatom".into(),
                     amount: INITIAL_GATEWAY_PLEDGE_AMOUNT,
                 },
+                max_delegation_to_pledge_ratio: None,
+                min_supported_version: None,
             },
         };
 
@@ -3,14 +3,51 @@
 
 use crate::constants::{
     DELEGATION_MIXNODE_IDX_NAMESPACE, DELEGATION_OWNER_IDX_NAMESPACE, DELEGATION_PK_NAMESPACE,
+    PENDING_DELEGATIONS_NAMESPACE,
 };
-use cw_storage_plus::{Index, IndexList, IndexedMap, MultiIndex};
+use cosmwasm_std::{StdResult, Storage, Uint128};
+use cw_storage_plus::{Index, IndexList, IndexedMap, Map, MultiIndex};
 use mixnet_contract_common::delegation::OwnerProxySubKey;
 use mixnet_contract_common::{Addr, Delegation, MixId};
 
 // It's a composite key on node's id and delegator address
 type PrimaryKey = (MixId, OwnerProxySubKey);
 
+// Total amount of delegations towards given mixnode that are still waiting in the pending epoch events queue.
+// It's used for enforcing the delegation cap, as otherwise multiple delegations sent within the same epoch
+// would have all been checked against the same, already applied, amount.
+pub(crate) const PENDING_DELEGATIONS: Map<MixId, Uint128> = Map::new(PENDING_DELEGATIONS_NAMESPACE);
+
+pub(crate) fn pending_delegation(storage: &dyn Storage, mix_id: MixId) -> StdResult<Uint128> {
+    Ok(PENDING_DELEGATIONS
+        .may_load(storage, mix_id)?
+        .unwrap_or_default())
+}
+
+pub(crate) fn increase_pending_delegation(
+    storage: &mut dyn Storage,
+    mix_id: MixId,
+    amount: Uint128,
+) -> StdResult<()> {
+    let pending = pending_delegation(storage, mix_id)?;
+    PENDING_DELEGATIONS.save(storage, mix_id, &(pending + amount))
+}
+
+pub(crate) fn decrease_pending_delegation(
+    storage: &mut dyn Storage,
+    mix_id: MixId,
+    amount: Uint128,
+) -> StdResult<()> {
+    // events pushed before the pending delegations were tracked are not accounted for, hence the saturation
+    let remaining = pending_delegation(storage, mix_id)?.saturating_sub(amount);
+    if remaining.is_zero() {
+        PENDING_DELEGATIONS.remove(storage, mix_id);
+        Ok(())
+    } else {
+        PENDING_DELEGATIONS.save(storage, mix_id, &remaining)
+    }
+}
+
 pub(crate) struct DelegationIndex<'a> {
     pub(crate) owner: MultiIndex<'a, Addr, Delegation, PrimaryKey>,
 
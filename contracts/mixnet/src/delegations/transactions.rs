@@ -5,6 +5,7 @@ use super::storage;
 use crate::interval::storage as interval_storage;
 use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes::storage as mixnodes_storage;
+use crate::rewards::storage as rewards_storage;
 use crate::support::helpers::{
     ensure_epoch_in_progress_state, ensure_sent_by_vesting_contract, validate_delegation_stake,
 };
@@ -56,6 +57,7 @@ pub(crate) fn _try_delegate_to_mixnode(
         contract_state.params.minimum_mixnode_delegation,
         contract_state.rewarding_denom,
     )?;
+    let max_delegation_to_pledge_ratio = contract_state.params.max_delegation_to_pledge_ratio;
 
     // check if the target node actually exists and is still bonded
     match mixnodes_storage::mixnode_bonds().may_load(deps.storage, mix_id)? {
@@ -66,6 +68,17 @@ pub(crate) fn _try_delegate_to_mixnode(
         _ => (),
     }
 
+    // delegations still waiting in the queue count towards the cap as well
+    let mix_rewarding = rewards_storage::MIXNODE_REWARDING.load(deps.storage, mix_id)?;
+    let pending_delegation = storage::pending_delegation(deps.storage, mix_id)?;
+    mix_rewarding.ensure_within_delegation_cap(
+        mix_id,
+        max_delegation_to_pledge_ratio,
+        pending_delegation,
+        &delegation,
+    )?;
+    storage::increase_pending_delegation(deps.storage, mix_id, delegation.amount)?;
+
     // push the event onto the queue and wait for it to be picked up at the end of the epoch
     let cosmos_event = new_pending_delegation_event(&delegate, &proxy, &delegation, mix_id);
 
@@ -151,7 +164,7 @@ mod tests {
         use crate::support::tests::fixtures::TEST_COIN_DENOM;
         use crate::support::tests::test_helpers::TestSetup;
         use cosmwasm_std::testing::mock_info;
-        use cosmwasm_std::{coin, Decimal};
+        use cosmwasm_std::{coin, Decimal, Uint128};
         use mixnet_contract_common::{EpochState, EpochStatus};
 
         #[test]
@@ -258,6 +271,92 @@ mod tests {
             assert!(res.is_ok())
         }
 
+        #[test]
+        fn if_applicable_must_not_exceed_the_delegation_cap() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+
+            let owner = "delegator";
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000)));
+
+            let mut contract_state = mixnet_params_storage::CONTRACT_STATE
+                .load(test.deps().storage)
+                .unwrap();
+            contract_state.params.max_delegation_to_pledge_ratio = Some(Decimal::percent(150));
+            mixnet_params_storage::CONTRACT_STATE
+                .save(test.deps_mut().storage, &contract_state)
+                .unwrap();
+
+            let sender1 = mock_info(owner, &[coin(150_000_001, TEST_COIN_DENOM)]);
+            let sender2 = mock_info(owner, &[coin(150_000_000, TEST_COIN_DENOM)]);
+
+            let res = try_delegate_to_mixnode(test.deps_mut(), env.clone(), sender1, mix_id);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::DelegationCapExceeded {
+                    mix_id,
+                    cap: Decimal::from_atomics(150_000_000u128, 0).unwrap(),
+                    current_delegation: Decimal::zero(),
+                    attempted: Uint128::new(150_000_001),
+                })
+            );
+
+            let res = try_delegate_to_mixnode(test.deps_mut(), env, sender2, mix_id);
+            assert!(res.is_ok())
+        }
+
+        #[test]
+        fn delegation_cap_accounts_for_delegations_pending_in_the_same_epoch() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000)));
+
+            let mut contract_state = mixnet_params_storage::CONTRACT_STATE
+                .load(test.deps().storage)
+                .unwrap();
+            contract_state.params.max_delegation_to_pledge_ratio = Some(Decimal::percent(150));
+            mixnet_params_storage::CONTRACT_STATE
+                .save(test.deps_mut().storage, &contract_state)
+                .unwrap();
+
+            let sender1 = mock_info("delegator1", &[coin(100_000_000, TEST_COIN_DENOM)]);
+            let sender2 = mock_info("delegator2", &[coin(50_000_000, TEST_COIN_DENOM)]);
+            let sender3 = mock_info("delegator1", &[coin(1_000_000, TEST_COIN_DENOM)]);
+
+            try_delegate_to_mixnode(test.deps_mut(), env.clone(), sender1, mix_id).unwrap();
+            try_delegate_to_mixnode(test.deps_mut(), env.clone(), sender2, mix_id).unwrap();
+            assert_eq!(
+                storage::pending_delegation(test.deps().storage, mix_id).unwrap(),
+                Uint128::new(150_000_000)
+            );
+
+            // none of the delegations have been applied yet, but the cap has already been reached
+            let res = try_delegate_to_mixnode(test.deps_mut(), env, sender3, mix_id);
+            assert_eq!(
+                res,
+                Err(MixnetContractError::DelegationCapExceeded {
+                    mix_id,
+                    cap: Decimal::from_atomics(150_000_000u128, 0).unwrap(),
+                    current_delegation: Decimal::from_atomics(150_000_000u128, 0).unwrap(),
+                    attempted: Uint128::new(1_000_000),
+                })
+            );
+
+            // once they're applied, they're no longer counted as pending
+            test.execute_all_pending_events();
+            assert!(storage::pending_delegation(test.deps().storage, mix_id)
+                .unwrap()
+                .is_zero());
+            let mix_rewarding = rewards_storage::MIXNODE_REWARDING
+                .load(test.deps().storage, mix_id)
+                .unwrap();
+            assert_eq!(
+                mix_rewarding.delegates,
+                Decimal::from_atomics(150_000_000u128, 0).unwrap()
+            );
+        }
+
         #[test]
         fn can_only_be_done_towards_fully_bonded_mixnode() {
             let mut test = TestSetup::new();
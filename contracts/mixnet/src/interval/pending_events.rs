@@ -1,13 +1,14 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
-use cosmwasm_std::{Addr, Coin, DepsMut, Env, Response};
+use cosmwasm_std::{Addr, Coin, DepsMut, Env, Response, Uint128};
 
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
-    new_active_set_update_event, new_delegation_event, new_delegation_on_unbonded_node_event,
-    new_mixnode_cost_params_update_event, new_mixnode_unbonding_event, new_pledge_decrease_event,
-    new_pledge_increase_event, new_rewarding_params_update_event, new_undelegation_event,
+    new_active_set_update_event, new_delegation_cap_exceeded_event, new_delegation_event,
+    new_delegation_on_unbonded_node_event, new_mixnode_cost_params_update_event,
+    new_mixnode_unbonding_event, new_pledge_decrease_event, new_pledge_increase_event,
+    new_rewarding_params_update_event, new_undelegation_event,
 };
 use mixnet_contract_common::mixnode::MixNodeCostParams;
 use mixnet_contract_common::pending_events::{
@@ -21,6 +22,7 @@ use crate::delegations;
 use crate::delegations::storage as delegations_storage;
 use crate::interval::helpers::change_interval_config;
 use crate::interval::storage;
+use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes::helpers::{cleanup_post_unbond_mixnode_storage, get_mixnode_details_by_id};
 use crate::mixnodes::storage as mixnodes_storage;
 use crate::rewards::storage as rewards_storage;
@@ -42,6 +44,9 @@ pub(crate) fn delegate(
     amount: Coin,
     proxy: Option<Addr>,
 ) -> Result<Response, MixnetContractError> {
+    // regardless of the outcome, the delegation is no longer pending
+    delegations_storage::decrease_pending_delegation(deps.storage, mix_id, amount.amount)?;
+
     // check if the target node still exists (it might have unbonded between this event getting created
     // and being executed). Do note that it's absolutely possible for a mixnode to get immediately
     // unbonded at this very block (if the event was pending), but that's tough luck, then it's up
@@ -78,6 +83,38 @@ pub(crate) fn delegate(
     let new_delegation_amount = amount.clone();
     let mut mix_rewarding = mixnode_details.rewarding_details;
 
+    // the cap might have been lowered, or the pledge decreased, since the delegation got queued,
+    // in which case the tokens are returned to the delegator
+    let max_delegation_to_pledge_ratio = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .params
+        .max_delegation_to_pledge_ratio;
+    if mix_rewarding
+        .ensure_within_delegation_cap(
+            mix_id,
+            max_delegation_to_pledge_ratio,
+            Uint128::zero(),
+            &amount,
+        )
+        .is_err()
+    {
+        let return_tokens = send_to_proxy_or_owner(&proxy, &owner, vec![amount.clone()]);
+        let response = Response::new()
+            .add_message(return_tokens)
+            .add_event(new_delegation_cap_exceeded_event(
+                &owner, &proxy, &amount, mix_id,
+            ))
+            .maybe_add_track_vesting_undelegation_message(
+                deps.storage,
+                proxy,
+                owner.to_string(),
+                mix_id,
+                amount,
+            )?;
+
+        return Ok(response);
+    }
+
     // the delegation_amount might get increased if there's already a pre-existing delegation on this mixnode
     // (in that case we just create a fresh delegation with the sum of both)
     let mut stored_delegation_amount = amount;
@@ -565,6 +602,66 @@ mod tests {
             assert_eq!(sent_amount[0], delegation_coin);
         }
 
+        #[test]
+        fn returns_the_tokens_if_delegation_cap_got_exceeded() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(Uint128::new(100_000_000)));
+            test.add_immediate_delegation("delegator1", 100_000_000u128, mix_id);
+
+            // the cap got introduced whilst the delegation was pending
+            let mut contract_state = mixnet_params_storage::CONTRACT_STATE
+                .load(test.deps().storage)
+                .unwrap();
+            contract_state.params.max_delegation_to_pledge_ratio = Some(Decimal::percent(150));
+            mixnet_params_storage::CONTRACT_STATE
+                .save(test.deps_mut().storage, &contract_state)
+                .unwrap();
+
+            let env = test.env();
+            let over_cap = coin(60_000_000, TEST_COIN_DENOM);
+            let res = delegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked("delegator2"),
+                mix_id,
+                over_cap.clone(),
+                None,
+            )
+            .unwrap();
+
+            let storage_key =
+                Delegation::generate_storage_key(mix_id, &Addr::unchecked("delegator2"), None);
+            assert!(delegations_storage::delegations()
+                .may_load(test.deps().storage, storage_key.clone())
+                .unwrap()
+                .is_none());
+            let (receiver, sent_amount) = get_bank_send_msg(&res).unwrap();
+            assert_eq!(receiver, "delegator2");
+            assert_eq!(sent_amount[0], over_cap);
+
+            // but delegations within the cap are still applied
+            let within_cap = coin(50_000_000, TEST_COIN_DENOM);
+            let res = delegate(
+                test.deps_mut(),
+                &env,
+                123,
+                Addr::unchecked("delegator2"),
+                mix_id,
+                within_cap.clone(),
+                None,
+            )
+            .unwrap();
+            assert!(get_bank_send_msg(&res).is_none());
+            assert_eq!(
+                delegations_storage::delegations()
+                    .load(test.deps().storage, storage_key)
+                    .unwrap()
+                    .amount,
+                within_cap
+            );
+        }
+
         #[test]
         fn returns_the_tokens_is_mixnode_is_unbonding() {
             let mut test = TestSetup::new();
@@ -12,9 +12,11 @@ use cw_storage_plus::Bound;
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::pending_events::{PendingEpochEvent, PendingIntervalEvent};
 use mixnet_contract_common::{
-    CurrentIntervalResponse, EpochEventId, EpochStatus, IntervalEventId, MixId,
-    NumberOfPendingEventsResponse, PagedRewardedSetResponse, PendingEpochEventResponse,
-    PendingEpochEventsResponse, PendingIntervalEventResponse, PendingIntervalEventsResponse,
+    ActiveSetMembershipResponse, CurrentIntervalResponse, EpochActiveSetResponse, EpochEventId,
+    EpochId, EpochStatus, IntervalEventId, MixId, NumberOfPendingEventsResponse,
+    PagedRewardedSetResponse, PendingEpochEventResponse, PendingEpochEventsResponse,
+    PendingIntervalEventResponse, PendingIntervalEventsResponse,
+    PendingMixNodeDelegationEventsResponse,
 };
 
 pub fn query_epoch_status(deps: Deps<'_>) -> StdResult<EpochStatus> {
@@ -54,6 +56,33 @@ pub fn query_rewarded_set_paged(
     })
 }
 
+pub fn query_epoch_active_set(
+    deps: Deps<'_>,
+    epoch_id: EpochId,
+) -> StdResult<EpochActiveSetResponse> {
+    let active_set = storage::ACTIVE_SET_HISTORY.may_load(deps.storage, epoch_id)?;
+
+    Ok(EpochActiveSetResponse {
+        epoch_id,
+        active_set,
+    })
+}
+
+pub fn query_active_set_membership(
+    deps: Deps<'_>,
+    mix_id: MixId,
+    epoch_id: EpochId,
+) -> StdResult<ActiveSetMembershipResponse> {
+    let active_set = storage::ACTIVE_SET_HISTORY.may_load(deps.storage, epoch_id)?;
+
+    Ok(ActiveSetMembershipResponse {
+        mix_id,
+        epoch_id,
+        in_active_set: active_set.as_ref().map(|set| set.contains(mix_id)),
+        provenance: active_set.and_then(|set| set.provenance),
+    })
+}
+
 pub fn query_pending_epoch_events_paged(
     deps: Deps<'_>,
     env: Env,
@@ -83,6 +112,40 @@ pub fn query_pending_epoch_events_paged(
     })
 }
 
+pub fn query_pending_mixnode_delegation_events_paged(
+    deps: Deps<'_>,
+    env: Env,
+    mix_id: MixId,
+    start_after: Option<EpochEventId>,
+    limit: Option<u32>,
+) -> StdResult<PendingMixNodeDelegationEventsResponse> {
+    let interval = storage::current_interval(deps.storage)?;
+
+    let limit = limit
+        .unwrap_or(EPOCH_EVENTS_DEFAULT_RETRIEVAL_LIMIT)
+        .min(EPOCH_EVENTS_MAX_RETRIEVAL_LIMIT) as usize;
+
+    let start = start_after.map(Bound::exclusive);
+
+    let events = storage::PENDING_EPOCH_EVENTS
+        .range(deps.storage, start, None, Order::Ascending)
+        .filter_map(|res| {
+            res.map(|row| PendingEpochEvent::from(row).mixnode_delegation_event(mix_id))
+                .transpose()
+        })
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let start_next_after = events.last().map(|event| event.event_id);
+
+    Ok(PendingMixNodeDelegationEventsResponse {
+        mix_id,
+        seconds_until_executable: interval.secs_until_current_epoch_end(&env),
+        events,
+        start_next_after,
+    })
+}
+
 pub fn query_pending_interval_events_paged(
     deps: Deps<'_>,
     env: Env,
@@ -306,6 +369,44 @@ mod tests {
         }
     }
 
+    mod active_set_history {
+        use super::*;
+        use crate::constants::ACTIVE_SET_HISTORY_RETENTION;
+        use mixnet_contract_common::{ActiveSetProvenance, EpochActiveSet};
+
+        #[test]
+        fn membership_is_unknown_outside_the_retained_history() {
+            let mut test = TestSetup::new();
+            let provenance = ActiveSetProvenance {
+                block_height: 42,
+                block_hash: "deadbeef".to_string(),
+                vrf_seed: Some("cafebabe".to_string()),
+            };
+            let active_set = EpochActiveSet::new(1, vec![5, 3], Some(provenance.clone()));
+            storage::save_epoch_active_set(test.deps_mut().storage, &active_set).unwrap();
+
+            let res = query_epoch_active_set(test.deps(), 1).unwrap();
+            assert_eq!(res.active_set, Some(active_set));
+
+            let member = query_active_set_membership(test.deps(), 3, 1).unwrap();
+            assert_eq!(member.in_active_set, Some(true));
+            assert_eq!(member.provenance, Some(provenance));
+
+            let non_member = query_active_set_membership(test.deps(), 4, 1).unwrap();
+            assert_eq!(non_member.in_active_set, Some(false));
+
+            let unknown = query_active_set_membership(test.deps(), 3, 2).unwrap();
+            assert_eq!(unknown.in_active_set, None);
+            assert_eq!(unknown.provenance, None);
+
+            // once the retention window moves past the epoch, it's no longer known
+            let newer = EpochActiveSet::new(1 + ACTIVE_SET_HISTORY_RETENTION, vec![3], None);
+            storage::save_epoch_active_set(test.deps_mut().storage, &newer).unwrap();
+            let pruned = query_active_set_membership(test.deps(), 3, 1).unwrap();
+            assert_eq!(pruned.in_active_set, None);
+        }
+    }
+
     #[cfg(test)]
     mod pending_epoch_events {
         use super::*;
@@ -692,4 +793,116 @@ mod tests {
             query_number_of_pending_events(test.deps())
         );
     }
+
+    #[cfg(test)]
+    mod pending_mixnode_delegation_events {
+        use super::*;
+        use cosmwasm_std::coin;
+        use mixnet_contract_common::{PendingDelegationEvent, PendingDelegationEventKind};
+
+        fn push_delegation_action(test: &mut TestSetup, owner: &str, mix_id: MixId) {
+            let action = PendingEpochEventKind::Delegate {
+                owner: Addr::unchecked(owner),
+                mix_id,
+                amount: coin(1000, fixtures::TEST_COIN_DENOM),
+                proxy: None,
+            };
+            let env = test.env();
+            storage::push_new_epoch_event(test.deps_mut().storage, &env, action).unwrap();
+        }
+
+        fn push_undelegation_action(test: &mut TestSetup, owner: &str, mix_id: MixId) {
+            let action = PendingEpochEventKind::Undelegate {
+                owner: Addr::unchecked(owner),
+                mix_id,
+                proxy: None,
+            };
+            let env = test.env();
+            storage::push_new_epoch_event(test.deps_mut().storage, &env, action).unwrap();
+        }
+
+        #[test]
+        fn only_returns_delegation_events_of_the_node() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+
+            push_delegation_action(&mut test, "alice", 1);
+            push_delegation_action(&mut test, "bob", 2);
+            push_n_dummy_epoch_actions(&mut test, 5);
+            push_undelegation_action(&mut test, "carol", 1);
+
+            let res = query_pending_mixnode_delegation_events_paged(
+                test.deps(),
+                env.clone(),
+                1,
+                None,
+                None,
+            )
+            .unwrap();
+            assert_eq!(res.mix_id, 1);
+            assert_eq!(
+                res.events,
+                vec![
+                    PendingDelegationEvent {
+                        event_id: 1,
+                        created_at: env.block.height,
+                        owner: Addr::unchecked("alice"),
+                        proxy: None,
+                        kind: PendingDelegationEventKind::Delegate {
+                            amount: coin(1000, fixtures::TEST_COIN_DENOM)
+                        },
+                    },
+                    PendingDelegationEvent {
+                        event_id: 8,
+                        created_at: env.block.height,
+                        owner: Addr::unchecked("carol"),
+                        proxy: None,
+                        kind: PendingDelegationEventKind::Undelegate,
+                    }
+                ]
+            );
+            assert_eq!(res.start_next_after, Some(8));
+
+            // and they're gone once the epoch rolls over
+            test.execute_all_pending_events();
+            let res =
+                query_pending_mixnode_delegation_events_paged(test.deps(), env, 1, None, None)
+                    .unwrap();
+            assert!(res.events.is_empty());
+            assert!(res.start_next_after.is_none());
+        }
+
+        #[test]
+        fn pagination_works() {
+            let mut test = TestSetup::new();
+            let env = test.env();
+
+            for i in 0..10 {
+                push_delegation_action(&mut test, &format!("owner{i}"), 42);
+                push_dummy_epoch_action(&mut test);
+            }
+
+            let page1 = query_pending_mixnode_delegation_events_paged(
+                test.deps(),
+                env.clone(),
+                42,
+                None,
+                Some(4),
+            )
+            .unwrap();
+            assert_eq!(page1.events.len(), 4);
+            assert_eq!(page1.start_next_after, Some(7));
+
+            let page2 = query_pending_mixnode_delegation_events_paged(
+                test.deps(),
+                env,
+                42,
+                page1.start_next_after,
+                None,
+            )
+            .unwrap();
+            assert_eq!(page2.events.len(), 6);
+            assert_eq!(page2.events[0].event_id, 9);
+        }
+    }
 }
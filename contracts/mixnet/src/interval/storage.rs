@@ -2,9 +2,10 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::constants::{
-    CURRENT_EPOCH_STATUS_KEY, CURRENT_INTERVAL_KEY, EPOCH_EVENT_ID_COUNTER_KEY,
-    INTERVAL_EVENT_ID_COUNTER_KEY, LAST_EPOCH_EVENT_ID_KEY, LAST_INTERVAL_EVENT_ID_KEY,
-    PENDING_EPOCH_EVENTS_NAMESPACE, PENDING_INTERVAL_EVENTS_NAMESPACE, REWARDED_SET_KEY,
+    ACTIVE_SET_HISTORY_NAMESPACE, ACTIVE_SET_HISTORY_RETENTION, CURRENT_EPOCH_STATUS_KEY,
+    CURRENT_INTERVAL_KEY, EPOCH_EVENT_ID_COUNTER_KEY, INTERVAL_EVENT_ID_COUNTER_KEY,
+    LAST_EPOCH_EVENT_ID_KEY, LAST_INTERVAL_EVENT_ID_KEY, PENDING_EPOCH_EVENTS_NAMESPACE,
+    PENDING_INTERVAL_EVENTS_NAMESPACE, REWARDED_SET_KEY,
 };
 use cosmwasm_std::{Addr, Env, Order, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
@@ -12,8 +13,8 @@ use mixnet_contract_common::pending_events::{
     PendingEpochEventData, PendingEpochEventKind, PendingIntervalEventData,
 };
 use mixnet_contract_common::{
-    EpochEventId, EpochStatus, Interval, IntervalEventId, MixId, PendingIntervalEventKind,
-    RewardedSetNodeStatus,
+    EpochActiveSet, EpochEventId, EpochId, EpochStatus, Interval, IntervalEventId, MixId,
+    PendingIntervalEventKind, RewardedSetNodeStatus,
 };
 use std::collections::HashMap;
 
@@ -21,6 +22,10 @@ pub(crate) const CURRENT_EPOCH_STATUS: Item<'_, EpochStatus> = Item::new(CURRENT
 pub(crate) const CURRENT_INTERVAL: Item<'_, Interval> = Item::new(CURRENT_INTERVAL_KEY);
 pub(crate) const REWARDED_SET: Map<MixId, RewardedSetNodeStatus> = Map::new(REWARDED_SET_KEY);
 
+/// Contains the active sets selected for the most recent `ACTIVE_SET_HISTORY_RETENTION` epochs.
+pub(crate) const ACTIVE_SET_HISTORY: Map<EpochId, EpochActiveSet> =
+    Map::new(ACTIVE_SET_HISTORY_NAMESPACE);
+
 pub(crate) const EPOCH_EVENT_ID_COUNTER: Item<EpochEventId> = Item::new(EPOCH_EVENT_ID_COUNTER_KEY);
 pub(crate) const INTERVAL_EVENT_ID_COUNTER: Item<IntervalEventId> =
     Item::new(INTERVAL_EVENT_ID_COUNTER_KEY);
@@ -154,6 +159,23 @@ pub(crate) fn update_rewarded_set(
     Ok(())
 }
 
+pub(crate) fn save_epoch_active_set(
+    storage: &mut dyn Storage,
+    active_set: &EpochActiveSet,
+) -> StdResult<()> {
+    ACTIVE_SET_HISTORY.save(storage, active_set.epoch_id, active_set)?;
+
+    // epochs are advanced one at a time, so it's enough to drop the single entry
+    // that has just fallen out of the retention window
+    if let Some(stale) = active_set
+        .epoch_id
+        .checked_sub(ACTIVE_SET_HISTORY_RETENTION)
+    {
+        ACTIVE_SET_HISTORY.remove(storage, stale)
+    }
+    Ok(())
+}
+
 pub(crate) fn initialise_storage(
     storage: &mut dyn Storage,
     starting_interval: Interval,
@@ -5,6 +5,7 @@ use super::storage;
 use crate::interval::helpers::change_interval_config;
 use crate::interval::pending_events::ContractExecutableEvent;
 use crate::interval::storage::push_new_interval_event;
+use crate::mixnodes::helpers::apply_due_sphinx_key_rotations;
 use crate::mixnodes::transactions::update_mixnode_layer;
 use crate::rewards;
 use crate::rewards::storage as rewards_storage;
@@ -19,7 +20,9 @@ use mixnet_contract_common::events::{
     new_pending_interval_events_execution_event, new_reconcile_pending_events,
 };
 use mixnet_contract_common::pending_events::PendingIntervalEventKind;
-use mixnet_contract_common::{EpochState, EpochStatus, LayerAssignment, MixId};
+use mixnet_contract_common::{
+    ActiveSetProvenance, EpochActiveSet, EpochState, EpochStatus, LayerAssignment, MixId,
+};
 use std::collections::BTreeSet;
 
 // those two should be called in separate tx (from advancing epoch),
@@ -276,6 +279,7 @@ pub fn try_advance_epoch(
     info: MessageInfo,
     layer_assignments: Vec<LayerAssignment>,
     expected_active_set_size: u32,
+    selection_provenance: Option<ActiveSetProvenance>,
 ) -> Result<Response, MixnetContractError> {
     // Only rewarding validator can attempt to advance epoch
     let mut current_epoch_status = ensure_can_advance_epoch(&info.sender, deps.storage)?;
@@ -303,14 +307,29 @@ pub fn try_advance_epoch(
 
     let new_rewarded_set = layer_assignments.iter().map(|l| l.mix_id()).collect();
 
+    // first k nodes are active
+    let active_set = EpochActiveSet::new(
+        updated_interval.current_epoch_absolute_id(),
+        layer_assignments
+            .iter()
+            .take(expected_active_set_size as usize)
+            .map(|l| l.mix_id())
+            .collect(),
+        selection_provenance,
+    );
+
     // finally save updated interval and the rewarded set
     storage::save_interval(deps.storage, &updated_interval)?;
     update_rewarded_set(deps.storage, new_rewarded_set, expected_active_set_size)?;
+    storage::save_epoch_active_set(deps.storage, &active_set)?;
 
     for a in layer_assignments {
         update_mixnode_layer(a.mix_id(), a.layer(), deps.storage)?;
     }
 
+    // switch over to any sphinx keys that were announced for the new epoch
+    apply_due_sphinx_key_rotations(deps.storage, updated_interval.current_epoch_absolute_id())?;
+
     current_epoch_status.state = EpochState::InProgress;
     storage::save_current_epoch_status(deps.storage, &current_epoch_status)?;
 
@@ -1515,6 +1534,7 @@ mod tests {
                     sender,
                     layer_assignments,
                     current_active_set,
+                    None,
                 );
                 assert_eq!(
                     res,
@@ -1550,6 +1570,7 @@ mod tests {
                 sender,
                 layer_assignments,
                 current_active_set,
+                None,
             )
             .unwrap();
 
@@ -1563,6 +1584,48 @@ mod tests {
             )
         }
 
+        #[test]
+        fn records_the_selected_active_set() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnode("1", Some(Uint128::new(100000000)));
+            test.add_dummy_mixnode("2", Some(Uint128::new(100000000)));
+            test.add_dummy_mixnode("3", Some(Uint128::new(100000000)));
+            let current_active_set = test.rewarding_params().active_set_size;
+
+            test.skip_to_current_epoch_end();
+            test.set_epoch_advancement_state();
+
+            let layer_assignments = vec![
+                LayerAssignment::new(3, Layer::One),
+                LayerAssignment::new(1, Layer::Two),
+                LayerAssignment::new(2, Layer::Three),
+            ];
+            let provenance = ActiveSetProvenance {
+                block_height: 123,
+                block_hash: "deadbeef".to_string(),
+                vrf_seed: None,
+            };
+
+            let env = test.env();
+            let sender = test.rewarding_validator();
+            try_advance_epoch(
+                test.deps_mut(),
+                env,
+                sender,
+                layer_assignments,
+                current_active_set,
+                Some(provenance.clone()),
+            )
+            .unwrap();
+
+            let epoch_id = test.current_interval().current_epoch_absolute_id();
+            let recorded = storage::ACTIVE_SET_HISTORY
+                .load(test.deps().storage, epoch_id)
+                .unwrap();
+            assert_eq!(recorded.active, vec![1, 2, 3]);
+            assert_eq!(recorded.provenance, Some(provenance));
+        }
+
         #[test]
         fn can_only_be_performed_by_specified_rewarding_validator() {
             let mut test = TestSetup::new();
@@ -1588,6 +1651,7 @@ mod tests {
                 some_sender,
                 layer_assignments.clone(),
                 current_active_set,
+                None,
             );
             assert_eq!(res, Err(MixnetContractError::Unauthorized));
 
@@ -1600,6 +1664,7 @@ mod tests {
                 sender,
                 layer_assignments,
                 current_active_set,
+                None,
             );
             assert!(res.is_ok())
         }
@@ -1629,6 +1694,7 @@ mod tests {
                 sender.clone(),
                 layer_assignments.clone(),
                 current_active_set,
+                None,
             );
             assert!(matches!(
                 res,
@@ -1663,6 +1729,7 @@ mod tests {
                 sender,
                 layer_assignments,
                 current_active_set,
+                None,
             );
             assert!(res.is_ok())
         }
@@ -1709,6 +1776,7 @@ mod tests {
                 sender,
                 layer_assignments.clone(),
                 current_active_set,
+                None,
             )
             .unwrap();
 
@@ -1730,6 +1798,7 @@ mod tests {
                 sender,
                 layer_assignments,
                 current_active_set,
+                None,
             )
             .unwrap();
 
@@ -1782,6 +1851,7 @@ mod tests {
                 sender,
                 layer_assignments,
                 current_active_set,
+                None,
             )
             .unwrap();
 
@@ -3,7 +3,9 @@
 
 use super::storage;
 use cosmwasm_std::{Deps, StdResult};
-use mixnet_contract_common::{ContractBuildInformation, ContractState, ContractStateParams};
+use mixnet_contract_common::{
+    ContractBuildInformation, ContractState, ContractStateParams, MinSupportedVersionResponse,
+};
 
 pub(crate) fn query_contract_state(deps: Deps<'_>) -> StdResult<ContractState> {
     storage::CONTRACT_STATE.load(deps.storage)
@@ -15,6 +17,16 @@ pub(crate) fn query_contract_settings_params(deps: Deps<'_>) -> StdResult<Contra
         .map(|settings| settings.params)
 }
 
+pub(crate) fn query_min_supported_mixnode_version(
+    deps: Deps<'_>,
+) -> StdResult<MinSupportedVersionResponse> {
+    storage::CONTRACT_STATE
+        .load(deps.storage)
+        .map(|settings| MinSupportedVersionResponse {
+            min_supported_version: settings.params.min_supported_version,
+        })
+}
+
 pub(crate) fn query_rewarding_validator_address(deps: Deps<'_>) -> StdResult<String> {
     storage::CONTRACT_STATE
         .load(deps.storage)
@@ -58,6 +70,8 @@ pub(crate) mod tests {
                 minimum_mixnode_delegation: None,
                 minimum_mixnode_pledge: coin(123u128, "unym"),
                 minimum_gateway_pledge: coin(456u128, "unym"),
+                max_delegation_to_pledge_ratio: None,
+                min_supported_version: None,
             },
         };
 
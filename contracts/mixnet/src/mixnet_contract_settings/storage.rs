@@ -28,6 +28,14 @@ pub(crate) fn minimum_gateway_pledge(storage: &dyn Storage) -> Result<Coin, Mixn
         .map(|state| state.params.minimum_gateway_pledge)?)
 }
 
+pub(crate) fn min_supported_mixnode_version(
+    storage: &dyn Storage,
+) -> Result<Option<String>, MixnetContractError> {
+    Ok(CONTRACT_STATE
+        .load(storage)
+        .map(|state| state.params.min_supported_version)?)
+}
+
 #[allow(unused)]
 pub(crate) fn minimum_delegation_stake(
     storage: &dyn Storage,
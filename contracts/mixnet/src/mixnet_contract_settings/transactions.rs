@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage;
+use crate::support::helpers::parse_semver;
 use cosmwasm_std::DepsMut;
 use cosmwasm_std::MessageInfo;
 use cosmwasm_std::Response;
@@ -48,6 +49,10 @@ pub(crate) fn try_update_contract_settings(
         return Err(MixnetContractError::Unauthorized);
     }
 
+    if let Some(min_supported_version) = &params.min_supported_version {
+        parse_semver(min_supported_version)?;
+    }
+
     let response = Response::new().add_event(new_settings_update_event(&state.params, &params));
 
     state.params = params;
@@ -121,6 +126,8 @@ pub mod tests {
                 denom,
                 amount: INITIAL_GATEWAY_PLEDGE_AMOUNT + Uint128::new(1234),
             },
+            max_delegation_to_pledge_ratio: None,
+            min_supported_version: None,
         };
 
         let initial_params = storage::CONTRACT_STATE
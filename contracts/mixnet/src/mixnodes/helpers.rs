@@ -5,12 +5,13 @@ use super::storage;
 use crate::interval::storage as interval_storage;
 use crate::mixnodes::storage::{assign_layer, next_mixnode_id_counter};
 use crate::rewards::storage as rewards_storage;
-use cosmwasm_std::{Addr, Coin, Decimal, Env, StdResult, Storage};
+use cosmwasm_std::{Addr, Coin, Decimal, Env, Order, StdResult, Storage};
+use cw_storage_plus::Bound;
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::mixnode::{
-    MixNodeCostParams, MixNodeDetails, MixNodeRewarding, UnbondedMixnode,
+    MixNodeCostParams, MixNodeDetails, MixNodeRewarding, NextSphinxKey, UnbondedMixnode,
 };
-use mixnet_contract_common::{IdentityKey, Layer, MixId, MixNode, MixNodeBond};
+use mixnet_contract_common::{EpochId, IdentityKey, Layer, MixId, MixNode, MixNodeBond};
 
 pub(crate) fn must_get_mixnode_bond_by_owner(
     store: &dyn Storage,
@@ -125,6 +126,53 @@ pub(crate) fn save_new_mixnode(
     Ok((mix_id, layer))
 }
 
+// replaces the sphinx keys of all mixnodes whose announced rotations are due by the provided epoch.
+// announcements that can no longer be applied, for example because the node has unbonded in the meantime
+// or because somebody else has bonded with the same key, are discarded
+pub(crate) fn apply_due_sphinx_key_rotations(
+    storage: &mut dyn Storage,
+    epoch_id: EpochId,
+) -> Result<(), MixnetContractError> {
+    let due = storage::PENDING_SPHINX_KEY_ROTATIONS
+        .range(
+            storage,
+            None,
+            Some(Bound::inclusive((epoch_id, MixId::MAX))),
+            Order::Ascending,
+        )
+        .collect::<StdResult<Vec<_>>>()?;
+
+    for ((activation_epoch, mix_id), sphinx_key) in due {
+        storage::PENDING_SPHINX_KEY_ROTATIONS.remove(storage, (activation_epoch, mix_id));
+
+        let Some(bond) = storage::mixnode_bonds().may_load(storage, mix_id)? else {
+            continue;
+        };
+        let announced = NextSphinxKey {
+            sphinx_key,
+            activation_epoch,
+        };
+        if bond.mix_node.next_sphinx_key.as_ref() != Some(&announced) {
+            continue;
+        }
+
+        let key_taken = storage::mixnode_bonds()
+            .idx
+            .sphinx_key
+            .item(storage, announced.sphinx_key.clone())?
+            .is_some();
+
+        let mut updated_bond = bond.clone();
+        updated_bond.mix_node.next_sphinx_key = None;
+        if !key_taken {
+            updated_bond.mix_node.sphinx_key = announced.sphinx_key;
+        }
+        storage::mixnode_bonds().replace(storage, mix_id, Some(&updated_bond), Some(&bond))?;
+    }
+
+    Ok(())
+}
+
 pub(crate) fn cleanup_post_unbond_mixnode_storage(
     storage: &mut dyn Storage,
     env: &Env,
@@ -140,6 +188,9 @@ pub(crate) fn cleanup_post_unbond_mixnode_storage(
         None,
         Some(&current_details.bond_information),
     )?;
+    storage::MIXNODE_DESCRIPTIONS.remove(storage, mix_id);
+    storage::PENDING_OWNERSHIP_TRANSFERS.remove(storage, mix_id);
+    rewards_storage::MIXNODE_PERFORMANCE.remove(storage, mix_id);
 
     // if there are no pending delegations to return, we can also
     // purge all information regarding rewarding parameters
@@ -178,7 +229,7 @@ pub(crate) mod tests {
     use crate::support::tests::fixtures::{
         mix_node_cost_params_fixture, mix_node_fixture, TEST_COIN_DENOM,
     };
-    use crate::support::tests::test_helpers::TestSetup;
+    use crate::support::tests::test_helpers::{self, TestSetup};
     use cosmwasm_std::{coin, Uint128};
 
     pub(crate) struct DummyMixnode {
@@ -499,6 +550,14 @@ pub(crate) mod tests {
             .load(test.deps().storage, mix_id_leftover)
             .unwrap();
 
+        rewards_storage::MIXNODE_PERFORMANCE
+            .save(
+                test.deps_mut().storage,
+                mix_id,
+                &test_helpers::performance(95.0),
+            )
+            .unwrap();
+
         let env = test.env();
         let details1 = get_mixnode_details_by_id(test.deps().storage, mix_id)
             .unwrap()
@@ -517,6 +576,12 @@ pub(crate) mod tests {
             .unwrap();
         assert!(mix_rewarding.is_none());
 
+        // last reported performance is gone
+        let performance = rewards_storage::MIXNODE_PERFORMANCE
+            .may_load(test.deps().storage, mix_id)
+            .unwrap();
+        assert!(performance.is_none());
+
         // unbonded details are inserted
         let unbonded_details = storage::unbonded_mixnodes()
             .load(test.deps().storage, mix_id)
@@ -5,8 +5,10 @@ use super::storage;
 use crate::constants::{
     MIXNODE_BOND_DEFAULT_RETRIEVAL_LIMIT, MIXNODE_BOND_MAX_RETRIEVAL_LIMIT,
     MIXNODE_DETAILS_DEFAULT_RETRIEVAL_LIMIT, MIXNODE_DETAILS_MAX_RETRIEVAL_LIMIT,
+    SKIMMED_MIXNODES_DEFAULT_RETRIEVAL_LIMIT, SKIMMED_MIXNODES_MAX_RETRIEVAL_LIMIT,
     UNBONDED_MIXNODES_DEFAULT_RETRIEVAL_LIMIT, UNBONDED_MIXNODES_MAX_RETRIEVAL_LIMIT,
 };
+use crate::interval::storage as interval_storage;
 use crate::mixnodes::helpers::{
     attach_mix_details, get_mixnode_details_by_id, get_mixnode_details_by_identity,
     get_mixnode_details_by_owner,
@@ -15,12 +17,16 @@ use crate::rewards::storage as rewards_storage;
 use cosmwasm_std::{Deps, Order, StdResult, Storage};
 use cw_storage_plus::Bound;
 use mixnet_contract_common::mixnode::{
-    MixNodeBond, MixNodeDetails, MixnodeRewardingDetailsResponse, PagedMixnodesDetailsResponse,
-    PagedUnbondedMixnodesResponse, StakeSaturationResponse, UnbondedMixnodeResponse,
+    MixNodeBond, MixNodeDetails, MixNodeDetailsWithStatus, MixNodeStatus,
+    MixnodeDescriptionResponse, MixnodeRewardingDetailsResponse, PagedMixnodesDetailsResponse,
+    PagedMixnodesDetailsWithStatusResponse, PagedSkimmedMixnodesResponse,
+    PagedUnbondedMixnodesResponse, PendingOwnershipTransferResponse, SkimmedMixNode,
+    StakeSaturationResponse, UnbondedMixnodeResponse,
 };
 use mixnet_contract_common::{
     IdentityKey, LayerDistribution, MixId, MixOwnershipResponse, MixnodeDetailsByIdentityResponse,
-    MixnodeDetailsResponse, PagedMixnodeBondsResponse,
+    MixnodeDetailsResponse, MixnodeDetailsWithPerformanceResponse, MixnodePledgeBreakdownResponse,
+    PagedMixnodeBondsResponse,
 };
 
 pub fn query_mixnode_bonds_paged(
@@ -49,6 +55,32 @@ pub fn query_mixnode_bonds_paged(
     ))
 }
 
+pub fn query_skimmed_mixnodes_paged(
+    deps: Deps<'_>,
+    start_after: Option<MixId>,
+    limit: Option<u32>,
+) -> StdResult<PagedSkimmedMixnodesResponse> {
+    let limit = limit
+        .unwrap_or(SKIMMED_MIXNODES_DEFAULT_RETRIEVAL_LIMIT)
+        .min(SKIMMED_MIXNODES_MAX_RETRIEVAL_LIMIT) as usize;
+
+    let start = start_after.map(Bound::exclusive);
+
+    let nodes = storage::mixnode_bonds()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| res.map(|item| SkimmedMixNode::from(item.1)))
+        .collect::<StdResult<Vec<SkimmedMixNode>>>()?;
+
+    let start_next_after = nodes.last().map(|node| node.mix_id);
+
+    Ok(PagedSkimmedMixnodesResponse::new(
+        nodes,
+        limit,
+        start_next_after,
+    ))
+}
+
 fn attach_node_details(
     storage: &dyn Storage,
     read_bond: StdResult<(MixId, MixNodeBond)>,
@@ -85,6 +117,44 @@ pub fn query_mixnodes_details_paged(
     ))
 }
 
+fn attach_node_status(
+    storage: &dyn Storage,
+    details: MixNodeDetails,
+) -> StdResult<MixNodeDetailsWithStatus> {
+    let rewarded_set_status = interval_storage::REWARDED_SET.may_load(storage, details.mix_id())?;
+    let status = MixNodeStatus::new(details.is_unbonding(), rewarded_set_status);
+    Ok(MixNodeDetailsWithStatus::new(details, status))
+}
+
+pub fn query_mixnodes_details_with_status_paged(
+    deps: Deps<'_>,
+    start_after: Option<MixId>,
+    limit: Option<u32>,
+) -> StdResult<PagedMixnodesDetailsWithStatusResponse> {
+    let limit = limit
+        .unwrap_or(MIXNODE_DETAILS_DEFAULT_RETRIEVAL_LIMIT)
+        .min(MIXNODE_DETAILS_MAX_RETRIEVAL_LIMIT) as usize;
+
+    let start = start_after.map(Bound::exclusive);
+
+    let nodes = storage::mixnode_bonds()
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .map(|res| {
+            attach_node_details(deps.storage, res)
+                .and_then(|details| attach_node_status(deps.storage, details))
+        })
+        .collect::<StdResult<Vec<MixNodeDetailsWithStatus>>>()?;
+
+    let start_next_after = nodes.last().map(|node| node.mix_id());
+
+    Ok(PagedMixnodesDetailsWithStatusResponse::new(
+        nodes,
+        limit,
+        start_next_after,
+    ))
+}
+
 pub fn query_unbonded_mixnodes_paged(
     deps: Deps<'_>,
     start_after: Option<MixId>,
@@ -189,6 +259,46 @@ pub fn query_mixnode_details(deps: Deps<'_>, mix_id: MixId) -> StdResult<Mixnode
     })
 }
 
+pub fn query_mixnode_details_with_performance(
+    deps: Deps<'_>,
+    mix_id: MixId,
+) -> StdResult<MixnodeDetailsWithPerformanceResponse> {
+    let mixnode_details = get_mixnode_details_by_id(deps.storage, mix_id)?;
+
+    // don't return the performance of nodes that have since unbonded
+    let last_rewarded_performance = match mixnode_details {
+        Some(_) => rewards_storage::MIXNODE_PERFORMANCE.may_load(deps.storage, mix_id)?,
+        None => None,
+    };
+
+    Ok(MixnodeDetailsWithPerformanceResponse {
+        mix_id,
+        mixnode_details,
+        last_rewarded_performance,
+    })
+}
+
+pub fn query_mixnode_description(
+    deps: Deps<'_>,
+    mix_id: MixId,
+) -> StdResult<MixnodeDescriptionResponse> {
+    let description = storage::MIXNODE_DESCRIPTIONS.may_load(deps.storage, mix_id)?;
+
+    Ok(MixnodeDescriptionResponse {
+        mix_id,
+        description,
+    })
+}
+
+pub fn query_pending_ownership_transfer(
+    deps: Deps<'_>,
+    mix_id: MixId,
+) -> StdResult<PendingOwnershipTransferResponse> {
+    let transfer = storage::PENDING_OWNERSHIP_TRANSFERS.may_load(deps.storage, mix_id)?;
+
+    Ok(PendingOwnershipTransferResponse { mix_id, transfer })
+}
+
 pub fn query_mixnode_details_by_identity(
     deps: Deps<'_>,
     identity_key: IdentityKey,
@@ -243,6 +353,19 @@ pub fn query_stake_saturation(deps: Deps<'_>, mix_id: MixId) -> StdResult<StakeS
     })
 }
 
+pub fn query_mixnode_pledge_breakdown(
+    deps: Deps<'_>,
+    mix_id: MixId,
+) -> StdResult<MixnodePledgeBreakdownResponse> {
+    let pledge_breakdown =
+        get_mixnode_details_by_id(deps.storage, mix_id)?.map(|details| details.pledge_breakdown());
+
+    Ok(MixnodePledgeBreakdownResponse {
+        mix_id,
+        pledge_breakdown,
+    })
+}
+
 pub(crate) fn query_layer_distribution(deps: Deps<'_>) -> StdResult<LayerDistribution> {
     storage::LAYERS.load(deps.storage)
 }
@@ -344,6 +467,68 @@ pub(crate) mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod skimmed_mixnodes {
+        use super::*;
+
+        #[test]
+        fn obeys_limits() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(1000);
+            let limit = 2;
+
+            let page1 = query_skimmed_mixnodes_paged(test.deps(), None, Some(limit)).unwrap();
+            assert_eq!(limit, page1.nodes.len() as u32);
+        }
+
+        #[test]
+        fn has_default_limit() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(1000);
+
+            // query without explicitly setting a limit
+            let page1 = query_skimmed_mixnodes_paged(test.deps(), None, None).unwrap();
+
+            assert_eq!(
+                SKIMMED_MIXNODES_DEFAULT_RETRIEVAL_LIMIT,
+                page1.nodes.len() as u32
+            );
+        }
+
+        #[test]
+        fn has_max_limit() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(1000);
+
+            // query with a crazily high limit in an attempt to use too many resources
+            let crazy_limit = 1000;
+            let page1 = query_skimmed_mixnodes_paged(test.deps(), None, Some(crazy_limit)).unwrap();
+
+            // we default to a decent sized upper bound instead
+            assert_eq!(
+                SKIMMED_MIXNODES_MAX_RETRIEVAL_LIMIT,
+                page1.nodes.len() as u32
+            );
+        }
+
+        #[test]
+        fn matches_full_bond_information() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(10);
+
+            let bonds = query_mixnode_bonds_paged(test.deps(), None, None).unwrap();
+            let skimmed = query_skimmed_mixnodes_paged(test.deps(), None, None).unwrap();
+
+            assert_eq!(bonds.start_next_after, skimmed.start_next_after);
+            let expected = bonds
+                .nodes
+                .into_iter()
+                .map(SkimmedMixNode::from)
+                .collect::<Vec<_>>();
+            assert_eq!(expected, skimmed.nodes);
+        }
+    }
+
     #[cfg(test)]
     mod mixnode_details {
         use super::*;
@@ -436,6 +621,89 @@ pub(crate) mod tests {
         }
     }
 
+    #[cfg(test)]
+    mod mixnode_details_with_status {
+        use super::*;
+        use mixnet_contract_common::RewardedSetNodeStatus;
+
+        #[test]
+        fn obeys_limits() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(1000);
+            let limit = 2;
+
+            let page1 =
+                query_mixnodes_details_with_status_paged(test.deps(), None, Some(limit)).unwrap();
+            assert_eq!(limit, page1.nodes.len() as u32);
+        }
+
+        #[test]
+        fn has_max_limit() {
+            let mut test = TestSetup::new();
+            test.add_dummy_mixnodes(1000);
+
+            let crazy_limit = 1000;
+            let page1 =
+                query_mixnodes_details_with_status_paged(test.deps(), None, Some(crazy_limit))
+                    .unwrap();
+
+            assert_eq!(
+                MIXNODE_DETAILS_MAX_RETRIEVAL_LIMIT,
+                page1.nodes.len() as u32
+            );
+        }
+
+        #[test]
+        fn includes_current_node_status() {
+            let mut test = TestSetup::new();
+
+            let active = test.add_dummy_mixnode("addr1", None);
+            let standby = test.add_dummy_mixnode("addr2", None);
+            let inactive = test.add_dummy_mixnode("addr3", None);
+            let unbonding = test.add_dummy_mixnode("addr4", None);
+
+            interval_storage::REWARDED_SET
+                .save(
+                    test.deps_mut().storage,
+                    active,
+                    &RewardedSetNodeStatus::Active,
+                )
+                .unwrap();
+            interval_storage::REWARDED_SET
+                .save(
+                    test.deps_mut().storage,
+                    standby,
+                    &RewardedSetNodeStatus::Standby,
+                )
+                .unwrap();
+            interval_storage::REWARDED_SET
+                .save(
+                    test.deps_mut().storage,
+                    unbonding,
+                    &RewardedSetNodeStatus::Active,
+                )
+                .unwrap();
+            test.start_unbonding_mixnode(unbonding);
+
+            let res = query_mixnodes_details_with_status_paged(test.deps(), None, None).unwrap();
+            let statuses = res
+                .nodes
+                .iter()
+                .map(|node| (node.mix_id(), node.status))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                statuses,
+                vec![
+                    (active, MixNodeStatus::Active),
+                    (standby, MixNodeStatus::Standby),
+                    (inactive, MixNodeStatus::Inactive),
+                    (unbonding, MixNodeStatus::Unbonding),
+                ]
+            );
+        }
+    }
+
     #[cfg(test)]
     mod unbonded_mixnodes {
         use super::*;
@@ -1109,6 +1377,41 @@ pub(crate) mod tests {
         assert_eq!(mix_id, res.mix_id);
     }
 
+    #[test]
+    fn query_for_mixnode_details_with_performance() {
+        let mut test = TestSetup::new();
+
+        // no node under this id
+        let res = query_mixnode_details_with_performance(test.deps(), 42).unwrap();
+        assert!(res.mixnode_details.is_none());
+        assert!(res.last_rewarded_performance.is_none());
+
+        // it exists, but hasn't been rewarded yet
+        let mix_id = test.add_dummy_mixnode("foomp", None);
+        let res = query_mixnode_details_with_performance(test.deps(), mix_id).unwrap();
+        assert!(res.mixnode_details.is_some());
+        assert!(res.last_rewarded_performance.is_none());
+
+        // the performance reported during the most recent rewarding is returned
+        test.skip_to_next_epoch_end();
+        test.force_change_rewarded_set(vec![mix_id]);
+        test.reward_with_distribution_with_state_bypass(mix_id, test_helpers::performance(95.0));
+        test.skip_to_next_epoch_end();
+        test.reward_with_distribution_with_state_bypass(mix_id, test_helpers::performance(80.0));
+
+        let res = query_mixnode_details_with_performance(test.deps(), mix_id).unwrap();
+        assert_eq!(
+            res.mixnode_details,
+            query_mixnode_details(test.deps(), mix_id)
+                .unwrap()
+                .mixnode_details
+        );
+        assert_eq!(
+            res.last_rewarded_performance,
+            Some(test_helpers::performance(80.0))
+        );
+    }
+
     #[test]
     fn query_for_mixnode_details_by_identity() {
         let mut test = TestSetup::new();
@@ -1227,4 +1530,47 @@ pub(crate) mod tests {
         assert_eq!(Decimal::percent(250), res.uncapped_saturation.unwrap());
         assert_eq!(mix_id, res.mix_id);
     }
+
+    #[test]
+    fn query_for_pledge_breakdown() {
+        let mut test = TestSetup::new();
+
+        // no node under this id
+        let res = query_mixnode_pledge_breakdown(test.deps(), 42).unwrap();
+        assert!(res.pledge_breakdown.is_none());
+        assert_eq!(42, res.mix_id);
+
+        let pledge = good_mixnode_pledge()[0].clone();
+
+        // liquid pledge
+        let mix_id = test.add_dummy_mixnode("liquid-owner", None);
+        let res = query_mixnode_pledge_breakdown(test.deps(), mix_id).unwrap();
+        let breakdown = res.pledge_breakdown.unwrap();
+        assert_eq!(pledge, breakdown.liquid);
+        assert!(breakdown.vesting.amount.is_zero());
+        assert!(breakdown.compounded_rewards.amount.is_zero());
+        assert_eq!(pledge, breakdown.total());
+
+        // vesting pledge
+        let mix_id = test.add_dummy_mixnode_with_legal_proxy("vesting-owner", None);
+        let res = query_mixnode_pledge_breakdown(test.deps(), mix_id).unwrap();
+        let breakdown = res.pledge_breakdown.unwrap();
+        assert_eq!(pledge, breakdown.vesting);
+        assert!(breakdown.liquid.amount.is_zero());
+
+        // with some compounded rewards
+        let mut mix_rewarding = rewards_storage::MIXNODE_REWARDING
+            .load(test.deps().storage, mix_id)
+            .unwrap();
+        mix_rewarding.operator += Decimal::from_atomics(1000u32, 0).unwrap();
+        rewards_storage::MIXNODE_REWARDING
+            .save(test.deps_mut().storage, mix_id, &mix_rewarding)
+            .unwrap();
+
+        let res = query_mixnode_pledge_breakdown(test.deps(), mix_id).unwrap();
+        let breakdown = res.pledge_breakdown.unwrap();
+        assert_eq!(pledge, breakdown.vesting);
+        assert_eq!(1000, breakdown.compounded_rewards.amount.u128());
+        assert_eq!(pledge.amount.u128() + 1000, breakdown.total().amount.u128());
+    }
 }
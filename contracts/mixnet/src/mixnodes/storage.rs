@@ -3,22 +3,39 @@
 
 use crate::constants::{
     LAYER_DISTRIBUTION_KEY, MIXNODES_IDENTITY_IDX_NAMESPACE, MIXNODES_OWNER_IDX_NAMESPACE,
-    MIXNODES_PK_NAMESPACE, MIXNODES_SPHINX_IDX_NAMESPACE, NODE_ID_COUNTER_KEY,
-    PENDING_MIXNODE_CHANGES_NAMESPACE, UNBONDED_MIXNODES_IDENTITY_IDX_NAMESPACE,
+    MIXNODES_PK_NAMESPACE, MIXNODES_SPHINX_IDX_NAMESPACE, MIXNODE_DESCRIPTIONS_NAMESPACE,
+    NODE_ID_COUNTER_KEY, PENDING_MIXNODE_CHANGES_NAMESPACE, PENDING_OWNERSHIP_TRANSFERS_NAMESPACE,
+    PENDING_SPHINX_KEY_ROTATIONS_NAMESPACE, UNBONDED_MIXNODES_IDENTITY_IDX_NAMESPACE,
     UNBONDED_MIXNODES_OWNER_IDX_NAMESPACE, UNBONDED_MIXNODES_PK_NAMESPACE,
 };
 use cosmwasm_std::{StdResult, Storage};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex, UniqueIndex};
 use mixnet_contract_common::error::MixnetContractError;
-use mixnet_contract_common::mixnode::{PendingMixNodeChanges, UnbondedMixnode};
+use mixnet_contract_common::mixnode::{
+    MixNodeDescription, PendingMixNodeChanges, PendingOwnershipTransfer, UnbondedMixnode,
+};
 use mixnet_contract_common::SphinxKey;
-use mixnet_contract_common::{Addr, IdentityKey, Layer, LayerDistribution, MixId, MixNodeBond};
+use mixnet_contract_common::{
+    Addr, EpochId, IdentityKey, Layer, LayerDistribution, MixId, MixNodeBond,
+};
 
 pub const LAYERS: Item<'_, LayerDistribution> = Item::new(LAYER_DISTRIBUTION_KEY);
 pub const MIXNODE_ID_COUNTER: Item<MixId> = Item::new(NODE_ID_COUNTER_KEY);
 pub const PENDING_MIXNODE_CHANGES: Map<MixId, PendingMixNodeChanges> =
     Map::new(PENDING_MIXNODE_CHANGES_NAMESPACE);
 
+// operator-provided metadata of bonded mixnodes. it's purged once the node unbonds
+pub const MIXNODE_DESCRIPTIONS: Map<MixId, MixNodeDescription> =
+    Map::new(MIXNODE_DESCRIPTIONS_NAMESPACE);
+
+// ownership transfers proposed by the current owners that haven't been accepted yet
+pub const PENDING_OWNERSHIP_TRANSFERS: Map<MixId, PendingOwnershipTransfer> =
+    Map::new(PENDING_OWNERSHIP_TRANSFERS_NAMESPACE);
+
+// sphinx keys announced by the operators, keyed by the epoch they're meant to become active at
+pub const PENDING_SPHINX_KEY_ROTATIONS: Map<(EpochId, MixId), SphinxKey> =
+    Map::new(PENDING_SPHINX_KEY_ROTATIONS_NAMESPACE);
+
 // keeps track of `node_id -> IdentityKey, Owner, unbonding_height` so we'd known a bit more about past mixnodes
 // if we ever decide it's too bloaty, we can deprecate it and start removing all data in
 // subsequent migrations
@@ -6,12 +6,18 @@ use cosmwasm_std::{coin, Addr, Coin, DepsMut, Env, MessageInfo, Response, Storag
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::events::{
     new_mixnode_bonding_event, new_mixnode_config_update_event,
-    new_mixnode_pending_cost_params_update_event, new_pending_mixnode_unbonding_event,
+    new_mixnode_description_update_event, new_mixnode_ownership_transfer_cancellation_event,
+    new_mixnode_ownership_transfer_event, new_mixnode_ownership_transfer_proposal_event,
+    new_mixnode_pending_cost_params_update_event,
+    new_mixnode_sphinx_key_rotation_announcement_event, new_pending_mixnode_unbonding_event,
     new_pending_pledge_decrease_event, new_pending_pledge_increase_event,
 };
-use mixnet_contract_common::mixnode::{MixNodeConfigUpdate, MixNodeCostParams};
+use mixnet_contract_common::mixnode::{
+    MixNodeConfigUpdate, MixNodeCostParams, MixNodeDescription, NextSphinxKey,
+    PendingOwnershipTransfer,
+};
 use mixnet_contract_common::pending_events::{PendingEpochEventKind, PendingIntervalEventKind};
-use mixnet_contract_common::{Layer, MixId, MixNode};
+use mixnet_contract_common::{EpochId, Layer, MixId, MixNode, MixNodeBond, SphinxKey};
 use nym_contracts_common::signing::MessageSignature;
 
 use crate::interval::storage as interval_storage;
@@ -26,7 +32,7 @@ use crate::signing::storage as signing_storage;
 use crate::support::helpers::{
     ensure_bonded, ensure_epoch_in_progress_state, ensure_is_authorized, ensure_no_existing_bond,
     ensure_no_pending_pledge_changes, ensure_proxy_match, ensure_sent_by_vesting_contract,
-    validate_pledge,
+    ensure_supported_mixnode_version, validate_pledge,
 };
 
 use super::storage;
@@ -125,11 +131,19 @@ fn _try_add_mixnode(
     let minimum_pledge = mixnet_params_storage::minimum_mixnode_pledge(deps.storage)?;
     let pledge = validate_pledge(pledge, minimum_pledge)?;
 
+    // don't let obsolete nodes into the network
+    ensure_supported_mixnode_version(deps.storage, &mixnode.version)?;
+
     // if the client has an active bonded mixnode or gateway, don't allow bonding
     // note that this has to be done explicitly as `UniqueIndex` constraint would not protect us
     // against attempting to use different node types (i.e. gateways and mixnodes)
     ensure_no_existing_bond(&owner, deps.storage)?;
 
+    // sphinx key rotations have to go through the dedicated message
+    if mixnode.next_sphinx_key.is_some() {
+        return Err(MixnetContractError::UnexpectedNextSphinxKey);
+    }
+
     // there's no need to explicitly check whether there already exists mixnode with the same
     // identity or sphinx keys as this is going to be done implicitly when attempting to save
     // the bond information due to `UniqueIndex` constraint defined on those fields.
@@ -414,6 +428,7 @@ pub(crate) fn _try_update_mixnode_config(
 
     ensure_bonded(&existing_bond)?;
     ensure_proxy_match(&proxy, &existing_bond.proxy)?;
+    ensure_supported_mixnode_version(deps.storage, &new_config.version)?;
 
     let cfg_update_event =
         new_mixnode_config_update_event(existing_bond.mix_id, &owner, &proxy, &new_config);
@@ -437,6 +452,213 @@ pub(crate) fn _try_update_mixnode_config(
     Ok(Response::new().add_event(cfg_update_event))
 }
 
+pub(crate) fn try_update_mixnode_description(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    description: MixNodeDescription,
+) -> Result<Response, MixnetContractError> {
+    // note: we don't care whether the node has been bonded with a vesting account,
+    // the description doesn't involve any tokens so the owner can always update it directly
+    let owner = info.sender;
+    let existing_bond = must_get_mixnode_bond_by_owner(deps.storage, &owner)?;
+
+    ensure_bonded(&existing_bond)?;
+    description.validate()?;
+
+    let description_update_event =
+        new_mixnode_description_update_event(existing_bond.mix_id, &owner, &description);
+    storage::MIXNODE_DESCRIPTIONS.save(deps.storage, existing_bond.mix_id, &description)?;
+
+    Ok(Response::new().add_event(description_update_event))
+}
+
+pub(crate) fn try_propose_mixnode_ownership_transfer(
+    deps: DepsMut<'_>,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+) -> Result<Response, MixnetContractError> {
+    let owner = info.sender;
+    let new_owner = deps.api.addr_validate(&new_owner)?;
+    let existing_bond = must_get_mixnode_bond_by_owner(deps.storage, &owner)?;
+
+    ensure_bonded(&existing_bond)?;
+    ensure_transferable(&existing_bond)?;
+    if new_owner == owner {
+        return Err(MixnetContractError::OwnershipTransferToSelf);
+    }
+
+    let mix_id = existing_bond.mix_id;
+    // any previous proposal is going to get overwritten
+    storage::PENDING_OWNERSHIP_TRANSFERS.save(
+        deps.storage,
+        mix_id,
+        &PendingOwnershipTransfer {
+            mix_id,
+            current_owner: owner.clone(),
+            proposed_owner: new_owner.clone(),
+            proposed_at_height: env.block.height,
+        },
+    )?;
+
+    Ok(
+        Response::new().add_event(new_mixnode_ownership_transfer_proposal_event(
+            mix_id, &owner, &new_owner,
+        )),
+    )
+}
+
+pub(crate) fn try_cancel_mixnode_ownership_transfer(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+) -> Result<Response, MixnetContractError> {
+    let owner = info.sender;
+    let existing_bond = must_get_mixnode_bond_by_owner(deps.storage, &owner)?;
+    let mix_id = existing_bond.mix_id;
+
+    if !storage::PENDING_OWNERSHIP_TRANSFERS.has(deps.storage, mix_id) {
+        return Err(MixnetContractError::NoPendingOwnershipTransfer { mix_id });
+    }
+    storage::PENDING_OWNERSHIP_TRANSFERS.remove(deps.storage, mix_id);
+
+    Ok(
+        Response::new().add_event(new_mixnode_ownership_transfer_cancellation_event(
+            mix_id, &owner,
+        )),
+    )
+}
+
+pub(crate) fn try_accept_mixnode_ownership(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    mix_id: MixId,
+) -> Result<Response, MixnetContractError> {
+    let new_owner = info.sender;
+    let Some(transfer) = storage::PENDING_OWNERSHIP_TRANSFERS.may_load(deps.storage, mix_id)?
+    else {
+        return Err(MixnetContractError::NoPendingOwnershipTransfer { mix_id });
+    };
+
+    if transfer.proposed_owner != new_owner {
+        return Err(MixnetContractError::InvalidOwnershipTransferRecipient {
+            mix_id,
+            proposed_owner: transfer.proposed_owner,
+            sender: new_owner,
+        });
+    }
+
+    // the proposal should have been removed if the node unbonded, but let's be extra careful
+    let existing_bond = must_get_mixnode_bond_by_owner(deps.storage, &transfer.current_owner)?;
+    if existing_bond.mix_id != mix_id {
+        return Err(MixnetContractError::inconsistent_state(
+            "the proposed ownership transfer refers to a mixnode no longer owned by the proposer",
+        ));
+    }
+    ensure_bonded(&existing_bond)?;
+    ensure_transferable(&existing_bond)?;
+    ensure_no_existing_bond(&new_owner, deps.storage)?;
+
+    // moving the bond while the pledge is being changed would make the pending event
+    // refer to the wrong owner
+    let pending_changes = storage::PENDING_MIXNODE_CHANGES
+        .may_load(deps.storage, mix_id)?
+        .unwrap_or_default();
+    ensure_no_pending_pledge_changes(&pending_changes)?;
+
+    let mut updated_bond = existing_bond.clone();
+    updated_bond.owner = new_owner.clone();
+    storage::mixnode_bonds().replace(
+        deps.storage,
+        mix_id,
+        Some(&updated_bond),
+        Some(&existing_bond),
+    )?;
+    storage::PENDING_OWNERSHIP_TRANSFERS.remove(deps.storage, mix_id);
+
+    Ok(
+        Response::new().add_event(new_mixnode_ownership_transfer_event(
+            mix_id,
+            &transfer.current_owner,
+            &new_owner,
+        )),
+    )
+}
+
+pub(crate) fn try_rotate_mixnode_sphinx_key(
+    deps: DepsMut<'_>,
+    info: MessageInfo,
+    next_sphinx_key: SphinxKey,
+    activation_epoch: EpochId,
+) -> Result<Response, MixnetContractError> {
+    let owner = info.sender;
+    let existing_bond = must_get_mixnode_bond_by_owner(deps.storage, &owner)?;
+    ensure_bonded(&existing_bond)?;
+
+    // clients need at least a full epoch to learn about the new key before it becomes active
+    let current_epoch =
+        interval_storage::current_interval(deps.storage)?.current_epoch_absolute_id();
+    if activation_epoch <= current_epoch {
+        return Err(MixnetContractError::SphinxKeyRotationNotInFuture {
+            current_epoch,
+            activation_epoch,
+        });
+    }
+
+    // this also covers the current key of this very node
+    if storage::mixnode_bonds()
+        .idx
+        .sphinx_key
+        .item(deps.storage, next_sphinx_key.clone())?
+        .is_some()
+    {
+        return Err(MixnetContractError::SphinxKeyAlreadyInUse {
+            sphinx_key: next_sphinx_key,
+        });
+    }
+
+    let mix_id = existing_bond.mix_id;
+    // any previous announcement is going to get overwritten
+    if let Some(previous) = &existing_bond.mix_node.next_sphinx_key {
+        storage::PENDING_SPHINX_KEY_ROTATIONS
+            .remove(deps.storage, (previous.activation_epoch, mix_id));
+    }
+
+    let next = NextSphinxKey {
+        sphinx_key: next_sphinx_key,
+        activation_epoch,
+    };
+    storage::PENDING_SPHINX_KEY_ROTATIONS.save(
+        deps.storage,
+        (activation_epoch, mix_id),
+        &next.sphinx_key,
+    )?;
+
+    let mut updated_bond = existing_bond.clone();
+    updated_bond.mix_node.next_sphinx_key = Some(next.clone());
+    storage::mixnode_bonds().replace(
+        deps.storage,
+        mix_id,
+        Some(&updated_bond),
+        Some(&existing_bond),
+    )?;
+
+    Ok(
+        Response::new().add_event(new_mixnode_sphinx_key_rotation_announcement_event(
+            mix_id, &owner, &next,
+        )),
+    )
+}
+
+// nodes bonded with vesting tokens are tied to the vesting account of their owner
+fn ensure_transferable(bond: &MixNodeBond) -> Result<(), MixnetContractError> {
+    if bond.proxy.is_some() {
+        return Err(MixnetContractError::VestingMixnodeOwnershipTransfer {
+            mix_id: bond.mix_id,
+        });
+    }
+    Ok(())
+}
+
 pub(crate) fn try_update_mixnode_cost_params(
     deps: DepsMut<'_>,
     env: Env,
@@ -499,12 +721,12 @@ pub mod tests {
     use cosmwasm_std::testing::mock_info;
     use cosmwasm_std::{Order, StdResult, Uint128};
 
-    use mixnet_contract_common::mixnode::PendingMixNodeChanges;
+    use mixnet_contract_common::mixnode::{PendingMixNodeChanges, MAX_MONIKER_LENGTH};
     use mixnet_contract_common::{EpochState, EpochStatus, ExecuteMsg, LayerDistribution, Percent};
 
     use crate::contract::execute;
     use crate::mixnet_contract_settings::storage::minimum_mixnode_pledge;
-    use crate::mixnodes::helpers::get_mixnode_details_by_id;
+    use crate::mixnodes::helpers::{apply_due_sphinx_key_rotations, get_mixnode_details_by_id};
     use crate::support::tests::fixtures::{good_mixnode_pledge, TEST_COIN_DENOM};
     use crate::support::tests::test_helpers::TestSetup;
     use crate::support::tests::{fixtures, test_helpers};
@@ -603,6 +825,70 @@ pub mod tests {
         assert_eq!(expected, storage::LAYERS.load(test.deps().storage).unwrap())
     }
 
+    #[test]
+    fn mixnode_add_with_unsupported_version() {
+        let mut test = TestSetup::new();
+        let env = test.env();
+
+        let mut contract_state = mixnet_params_storage::CONTRACT_STATE
+            .load(test.deps().storage)
+            .unwrap();
+        contract_state.params.min_supported_version = Some("1.1.0".to_string());
+        mixnet_params_storage::CONTRACT_STATE
+            .save(test.deps_mut().storage, &contract_state)
+            .unwrap();
+
+        // the fixture node is running an older version
+        let sender = "alice";
+        let info = mock_info(sender, &good_mixnode_pledge());
+        let (mixnode, sig, _) = test.mixnode_with_signature(sender, None);
+        let cost_params = fixtures::mix_node_cost_params_fixture();
+
+        let res = try_add_mixnode(
+            test.deps_mut(),
+            env.clone(),
+            info.clone(),
+            mixnode.clone(),
+            cost_params.clone(),
+            sig,
+        );
+        assert_eq!(
+            res,
+            Err(MixnetContractError::UnsupportedMixnodeVersion {
+                version: mixnode.version,
+                min_supported_version: "1.1.0".to_string(),
+            })
+        );
+
+        // but nodes running the minimum version or newer are fine
+        let mut test = TestSetup::new();
+        let mut contract_state = mixnet_params_storage::CONTRACT_STATE
+            .load(test.deps().storage)
+            .unwrap();
+        contract_state.params.min_supported_version = Some("0.10.0".to_string());
+        mixnet_params_storage::CONTRACT_STATE
+            .save(test.deps_mut().storage, &contract_state)
+            .unwrap();
+
+        let (mixnode, sig, _) = test.mixnode_with_signature(sender, None);
+        let res = try_add_mixnode(test.deps_mut(), env, info, mixnode, cost_params, sig);
+        assert!(res.is_ok());
+
+        // and they can't downgrade afterwards
+        let update = MixNodeConfigUpdate {
+            host: "1.1.1.1:1234".to_string(),
+            mix_port: 1234,
+            verloc_port: 1235,
+            http_api_port: 1236,
+            version: "0.9.0".to_string(),
+        };
+        let res = try_update_mixnode_config(test.deps_mut(), mock_info(sender, &[]), update);
+        assert!(matches!(
+            res,
+            Err(MixnetContractError::UnsupportedMixnodeVersion { .. })
+        ));
+    }
+
     #[test]
     fn adding_mixnode_with_invalid_signatures() {
         let mut test = TestSetup::new();
@@ -943,6 +1229,290 @@ pub mod tests {
         assert_eq!(res, Err(MixnetContractError::MixnodeIsUnbonding { mix_id }))
     }
 
+    #[test]
+    fn updating_mixnode_description() {
+        let mut test = TestSetup::new();
+        let env = test.env();
+
+        let owner = "alice";
+        let info = mock_info(owner, &[]);
+        let description = MixNodeDescription {
+            moniker: "my-mixnode".to_string(),
+            website: "https://nymtech.net".to_string(),
+            security_contact: "security@nymtech.net".to_string(),
+            details: "just a mixnode".to_string(),
+        };
+
+        // try updating a non existing mixnode bond
+        let res =
+            try_update_mixnode_description(test.deps_mut(), info.clone(), description.clone());
+        assert_eq!(
+            res,
+            Err(MixnetContractError::NoAssociatedMixNodeBond {
+                owner: Addr::unchecked(owner)
+            })
+        );
+
+        let mix_id = test.add_dummy_mixnode(owner, None);
+
+        // overly long fields are rejected
+        let mut invalid = description.clone();
+        invalid.moniker = "a".repeat(MAX_MONIKER_LENGTH + 1);
+        let res = try_update_mixnode_description(test.deps_mut(), info.clone(), invalid);
+        assert_eq!(
+            res,
+            Err(MixnetContractError::DescriptionFieldTooLong {
+                field: "moniker".to_string(),
+                length: MAX_MONIKER_LENGTH + 1,
+                max_length: MAX_MONIKER_LENGTH,
+            })
+        );
+        assert!(storage::MIXNODE_DESCRIPTIONS
+            .may_load(test.deps().storage, mix_id)
+            .unwrap()
+            .is_none());
+
+        let res =
+            try_update_mixnode_description(test.deps_mut(), info.clone(), description.clone());
+        assert!(res.is_ok());
+        assert_eq!(
+            storage::MIXNODE_DESCRIPTIONS
+                .load(test.deps().storage, mix_id)
+                .unwrap(),
+            description
+        );
+
+        // and it can't be updated once the node is unbonding
+        try_remove_mixnode(test.deps_mut(), env, info.clone()).unwrap();
+        let res = try_update_mixnode_description(test.deps_mut(), info, description);
+        assert_eq!(res, Err(MixnetContractError::MixnodeIsUnbonding { mix_id }))
+    }
+
+    #[test]
+    fn transferring_mixnode_ownership() {
+        let mut test = TestSetup::new();
+        let env = test.env();
+
+        let owner = "alice";
+        let new_owner = "bob";
+        let mix_id = test.add_dummy_mixnode(owner, None);
+
+        // nothing to accept or cancel yet
+        let res = try_accept_mixnode_ownership(test.deps_mut(), mock_info(new_owner, &[]), mix_id);
+        assert_eq!(
+            res,
+            Err(MixnetContractError::NoPendingOwnershipTransfer { mix_id })
+        );
+        let res = try_cancel_mixnode_ownership_transfer(test.deps_mut(), mock_info(owner, &[]));
+        assert_eq!(
+            res,
+            Err(MixnetContractError::NoPendingOwnershipTransfer { mix_id })
+        );
+
+        let res = try_propose_mixnode_ownership_transfer(
+            test.deps_mut(),
+            env.clone(),
+            mock_info(owner, &[]),
+            owner.to_string(),
+        );
+        assert_eq!(res, Err(MixnetContractError::OwnershipTransferToSelf));
+
+        try_propose_mixnode_ownership_transfer(
+            test.deps_mut(),
+            env,
+            mock_info(owner, &[]),
+            new_owner.to_string(),
+        )
+        .unwrap();
+
+        // only the proposed owner can accept it
+        let res = try_accept_mixnode_ownership(test.deps_mut(), mock_info("mallory", &[]), mix_id);
+        assert_eq!(
+            res,
+            Err(MixnetContractError::InvalidOwnershipTransferRecipient {
+                mix_id,
+                proposed_owner: Addr::unchecked(new_owner),
+                sender: Addr::unchecked("mallory"),
+            })
+        );
+
+        try_accept_mixnode_ownership(test.deps_mut(), mock_info(new_owner, &[]), mix_id).unwrap();
+
+        let bond = must_get_mixnode_bond_by_owner(test.deps().storage, &Addr::unchecked(new_owner))
+            .unwrap();
+        assert_eq!(bond.mix_id, mix_id);
+        assert!(
+            must_get_mixnode_bond_by_owner(test.deps().storage, &Addr::unchecked(owner)).is_err()
+        );
+        assert!(!storage::PENDING_OWNERSHIP_TRANSFERS.has(test.deps().storage, mix_id));
+    }
+
+    #[test]
+    fn mixnode_ownership_cant_be_transferred_to_existing_operator() {
+        let mut test = TestSetup::new();
+        let env = test.env();
+
+        let owner = "alice";
+        let new_owner = "bob";
+        let mix_id = test.add_dummy_mixnode(owner, None);
+        test.add_dummy_mixnode(new_owner, None);
+
+        try_propose_mixnode_ownership_transfer(
+            test.deps_mut(),
+            env,
+            mock_info(owner, &[]),
+            new_owner.to_string(),
+        )
+        .unwrap();
+
+        let res = try_accept_mixnode_ownership(test.deps_mut(), mock_info(new_owner, &[]), mix_id);
+        assert_eq!(res, Err(MixnetContractError::AlreadyOwnsMixnode));
+    }
+
+    #[test]
+    fn rotating_mixnode_sphinx_key() {
+        let mut test = TestSetup::new();
+
+        let owner = "alice";
+        let info = mock_info(owner, &[]);
+        let mix_id = test.add_dummy_mixnode(owner, None);
+        let current_key = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id)
+            .unwrap()
+            .mix_node
+            .sphinx_key;
+        let current_epoch = interval_storage::current_interval(test.deps().storage)
+            .unwrap()
+            .current_epoch_absolute_id();
+
+        // the rotation can't be scheduled for the current epoch
+        let res = try_rotate_mixnode_sphinx_key(
+            test.deps_mut(),
+            info.clone(),
+            "new-sphinx-key".to_string(),
+            current_epoch,
+        );
+        assert_eq!(
+            res,
+            Err(MixnetContractError::SphinxKeyRotationNotInFuture {
+                current_epoch,
+                activation_epoch: current_epoch,
+            })
+        );
+
+        // nor can it reuse an existing key
+        let res = try_rotate_mixnode_sphinx_key(
+            test.deps_mut(),
+            info.clone(),
+            current_key.clone(),
+            current_epoch + 1,
+        );
+        assert_eq!(
+            res,
+            Err(MixnetContractError::SphinxKeyAlreadyInUse {
+                sphinx_key: current_key.clone()
+            })
+        );
+
+        try_rotate_mixnode_sphinx_key(
+            test.deps_mut(),
+            info.clone(),
+            "new-sphinx-key".to_string(),
+            current_epoch + 1,
+        )
+        .unwrap();
+
+        // announcing another key replaces the previous announcement
+        try_rotate_mixnode_sphinx_key(
+            test.deps_mut(),
+            info,
+            "newer-sphinx-key".to_string(),
+            current_epoch + 2,
+        )
+        .unwrap();
+        let expected = NextSphinxKey {
+            sphinx_key: "newer-sphinx-key".to_string(),
+            activation_epoch: current_epoch + 2,
+        };
+        let mix_node = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id)
+            .unwrap()
+            .mix_node;
+        assert_eq!(mix_node.next_sphinx_key, Some(expected.clone()));
+        assert!(!storage::PENDING_SPHINX_KEY_ROTATIONS
+            .has(test.deps().storage, (current_epoch + 1, mix_id)));
+        assert_eq!(
+            mix_node.sphinx_key_for_epoch(current_epoch + 1),
+            &current_key
+        );
+        assert_eq!(
+            mix_node.sphinx_key_for_epoch(current_epoch + 2),
+            &expected.sphinx_key
+        );
+
+        // nothing happens until the activation epoch
+        apply_due_sphinx_key_rotations(test.deps_mut().storage, current_epoch + 1).unwrap();
+        let mix_node = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id)
+            .unwrap()
+            .mix_node;
+        assert_eq!(mix_node.sphinx_key, current_key);
+        assert_eq!(mix_node.next_sphinx_key, Some(expected.clone()));
+
+        apply_due_sphinx_key_rotations(test.deps_mut().storage, current_epoch + 2).unwrap();
+        let mix_node = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id)
+            .unwrap()
+            .mix_node;
+        assert_eq!(mix_node.sphinx_key, expected.sphinx_key);
+        assert!(mix_node.next_sphinx_key.is_none());
+        assert!(!storage::PENDING_SPHINX_KEY_ROTATIONS
+            .has(test.deps().storage, (current_epoch + 2, mix_id)));
+    }
+
+    #[test]
+    fn conflicting_sphinx_key_rotations_are_discarded() {
+        let mut test = TestSetup::new();
+
+        let mix_id1 = test.add_dummy_mixnode("alice", None);
+        let mix_id2 = test.add_dummy_mixnode("bob", None);
+        let original_key2 = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id2)
+            .unwrap()
+            .mix_node
+            .sphinx_key;
+        let activation_epoch = interval_storage::current_interval(test.deps().storage)
+            .unwrap()
+            .current_epoch_absolute_id()
+            + 1;
+
+        // until the keys are actually rotated, nothing prevents two operators announcing the same one
+        for owner in ["alice", "bob"] {
+            try_rotate_mixnode_sphinx_key(
+                test.deps_mut(),
+                mock_info(owner, &[]),
+                "shared-sphinx-key".to_string(),
+                activation_epoch,
+            )
+            .unwrap();
+        }
+
+        apply_due_sphinx_key_rotations(test.deps_mut().storage, activation_epoch).unwrap();
+
+        let mix_node1 = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id1)
+            .unwrap()
+            .mix_node;
+        let mix_node2 = storage::mixnode_bonds()
+            .load(test.deps().storage, mix_id2)
+            .unwrap()
+            .mix_node;
+        assert_eq!(mix_node1.sphinx_key, "shared-sphinx-key");
+        assert_eq!(mix_node2.sphinx_key, original_key2);
+        assert!(mix_node1.next_sphinx_key.is_none());
+        assert!(mix_node2.next_sphinx_key.is_none());
+    }
+
     #[test]
     fn updating_mixnode_config_with_illegal_proxy() {
         let mut test = TestSetup::new();
@@ -1152,6 +1722,7 @@ pub mod tests {
                 .to_base58_string(),
             identity_key: keypair1.public_key().to_base58_string(),
             version: "v0.1.2.3".to_string(),
+            next_sphinx_key: None,
         };
 
         // change identity but reuse sphinx key
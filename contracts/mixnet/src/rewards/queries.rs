@@ -2,24 +2,73 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use super::storage;
+use crate::constants::{
+    REWARDING_SIMULATION_DEFAULT_RETRIEVAL_LIMIT, REWARDING_SIMULATION_MAX_RETRIEVAL_LIMIT,
+};
 use crate::delegations::storage as delegations_storage;
+use crate::gateways::storage as gateways_storage;
 use crate::interval::storage as interval_storage;
+use crate::mixnet_contract_settings::storage as mixnet_params_storage;
 use crate::mixnodes;
 use crate::mixnodes::storage as mixnodes_storage;
-use cosmwasm_std::{coin, Coin, Decimal, Deps, StdResult};
+use cosmwasm_std::{coin, Coin, Decimal, Deps, Order, StdResult};
+use cw_storage_plus::Bound;
+use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::helpers::into_base_decimal;
 use mixnet_contract_common::mixnode::MixNodeDetails;
 use mixnet_contract_common::reward_params::{NodeRewardParams, Performance, RewardingParams};
 use mixnet_contract_common::rewarding::helpers::truncate_reward;
 use mixnet_contract_common::rewarding::{
-    EstimatedCurrentEpochRewardResponse, PendingRewardResponse,
+    EpochRewardingSimulationParams, EstimatedCurrentEpochRewardResponse, PendingRewardResponse,
+    RewardEstimate, SimulatedEpochRewardingResponse, SimulatedNodeReward, StakeSummaryResponse,
 };
-use mixnet_contract_common::{Delegation, MixId};
+use mixnet_contract_common::{Delegation, MixId, RewardedSetNodeStatus};
 
 pub(crate) fn query_rewarding_params(deps: Deps<'_>) -> StdResult<RewardingParams> {
     storage::REWARDING_PARAMS.load(deps.storage)
 }
 
+pub(crate) fn query_stake_summary(deps: Deps<'_>) -> StdResult<StakeSummaryResponse> {
+    let denom = mixnet_params_storage::CONTRACT_STATE
+        .load(deps.storage)?
+        .rewarding_denom;
+    let rewarding_params = storage::REWARDING_PARAMS.load(deps.storage)?;
+
+    // note: this has to go through every single node in the network. it's fine for a query,
+    // but it should never be called from within a transaction
+    let mut total_operators = Decimal::zero();
+    let mut total_delegates = Decimal::zero();
+    for entry in storage::MIXNODE_REWARDING.range(deps.storage, None, None, Order::Ascending) {
+        let (_, mix_rewarding) = entry?;
+        total_operators += mix_rewarding.operator;
+        total_delegates += mix_rewarding.delegates;
+    }
+
+    let mut total_gateway_pledges = Decimal::zero();
+    for entry in gateways_storage::gateways().range(deps.storage, None, None, Order::Ascending) {
+        let (_, gateway_bond) = entry?;
+        total_gateway_pledges += into_base_decimal(gateway_bond.pledge_amount.amount)?;
+    }
+
+    let mut active_set_stake = Decimal::zero();
+    for entry in interval_storage::REWARDED_SET.range(deps.storage, None, None, Order::Ascending) {
+        let (mix_id, status) = entry?;
+        if status != RewardedSetNodeStatus::Active {
+            continue;
+        }
+        if let Some(mix_rewarding) = storage::MIXNODE_REWARDING.may_load(deps.storage, mix_id)? {
+            active_set_stake += mix_rewarding.node_bond();
+        }
+    }
+
+    Ok(StakeSummaryResponse {
+        total_bonded: truncate_reward(total_operators + total_gateway_pledges, &denom),
+        total_delegated: truncate_reward(total_delegates, &denom),
+        active_set_stake: truncate_reward(active_set_stake, &denom),
+        reward_pool: truncate_reward(rewarding_params.interval.reward_pool, denom),
+    })
+}
+
 fn pending_operator_reward(
     mix_details: Option<MixNodeDetails>,
 ) -> StdResult<PendingRewardResponse> {
@@ -229,12 +278,109 @@ pub(crate) fn query_estimated_current_epoch_delegator_reward(
     })
 }
 
+fn simulated_rewarding_params(
+    deps: Deps<'_>,
+    params: &EpochRewardingSimulationParams,
+    epochs_in_interval: u32,
+) -> Result<RewardingParams, MixnetContractError> {
+    let mut rewarding_params = storage::REWARDING_PARAMS.load(deps.storage)?;
+
+    // if the active set is shrinking, change it before applying other updates,
+    // so that the rewarded set could also be shrunk in the same simulation
+    let mut deferred_active_set_size = params.active_set_size;
+    if let Some(active_set_size) = params.active_set_size {
+        if active_set_size <= rewarding_params.active_set_size {
+            rewarding_params.try_change_active_set_size(active_set_size)?;
+            deferred_active_set_size = None;
+        }
+    }
+
+    if let Some(updates) = params.interval_updates {
+        if updates.contains_updates() {
+            rewarding_params.try_apply_updates(updates, epochs_in_interval)?;
+        }
+    }
+
+    if let Some(active_set_size) = deferred_active_set_size {
+        rewarding_params.try_change_active_set_size(active_set_size)?;
+    }
+
+    Ok(rewarding_params)
+}
+
+pub(crate) fn query_simulated_epoch_rewarding(
+    deps: Deps<'_>,
+    params: EpochRewardingSimulationParams,
+    start_after: Option<MixId>,
+    limit: Option<u32>,
+) -> Result<SimulatedEpochRewardingResponse, MixnetContractError> {
+    let limit = limit
+        .unwrap_or(REWARDING_SIMULATION_DEFAULT_RETRIEVAL_LIMIT)
+        .min(REWARDING_SIMULATION_MAX_RETRIEVAL_LIMIT) as usize;
+
+    let interval = interval_storage::current_interval(deps.storage)?;
+    let epochs_in_interval = interval.epochs_in_interval();
+    let rewarding_params = simulated_rewarding_params(deps, &params, epochs_in_interval)?;
+    let performance = params.performance.unwrap_or(Performance::hundred());
+
+    let start = start_after.map(Bound::exclusive);
+    let rewarded_set = interval_storage::REWARDED_SET
+        .range(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    let mut nodes = Vec::with_capacity(rewarded_set.len());
+    for (mix_id, status) in rewarded_set {
+        let mix_details = mixnodes::helpers::get_mixnode_details_by_id(deps.storage, mix_id)?;
+
+        // nodes that are unbonding (or have already unbonded) are not going to receive anything
+        let reward = match mix_details {
+            Some(details) if !details.is_unbonding() && !performance.is_zero() => {
+                let mix_rewarding = details.rewarding_details;
+                let node_reward_params = NodeRewardParams::new(performance, status.is_active());
+                let node_reward = mix_rewarding.node_reward(&rewarding_params, node_reward_params);
+                let reward_distribution = mix_rewarding.determine_reward_split(
+                    node_reward,
+                    performance,
+                    epochs_in_interval,
+                );
+
+                RewardEstimate {
+                    total_node_reward: node_reward,
+                    operator: reward_distribution.operator,
+                    delegates: reward_distribution.delegates,
+                    operating_cost: mix_rewarding
+                        .cost_params
+                        .epoch_operating_cost(epochs_in_interval)
+                        * performance,
+                }
+            }
+            _ => RewardEstimate::default(),
+        };
+
+        nodes.push(SimulatedNodeReward {
+            mix_id,
+            status,
+            reward,
+        })
+    }
+
+    let start_next_after = nodes.last().map(|node| node.mix_id);
+
+    Ok(SimulatedEpochRewardingResponse {
+        rewarding_params,
+        nodes,
+        start_next_after,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::support::tests::test_helpers;
     use crate::support::tests::test_helpers::TestSetup;
     use cosmwasm_std::Uint128;
+    use mixnet_contract_common::rewarding::helpers::truncate_reward_amount;
 
     #[test]
     fn querying_for_rewarding_params() {
@@ -245,6 +391,47 @@ mod tests {
         assert!(res.is_ok())
     }
 
+    #[test]
+    fn querying_for_stake_summary() {
+        let mut test = TestSetup::new();
+
+        let empty = query_stake_summary(test.deps()).unwrap();
+        assert!(empty.total_bonded.amount.is_zero());
+        assert!(empty.total_delegated.amount.is_zero());
+        assert!(empty.active_set_stake.amount.is_zero());
+        assert_eq!(
+            empty.reward_pool,
+            truncate_reward(
+                test.rewarding_params().interval.reward_pool,
+                empty.reward_pool.denom.clone()
+            )
+        );
+
+        let active = test.add_dummy_mixnode("mix-owner1", Some(Uint128::new(100_000_000)));
+        let standby = test.add_dummy_mixnode("mix-owner2", Some(Uint128::new(200_000_000)));
+        test.add_dummy_gateway("gateway-owner", Some(Uint128::new(50_000_000)));
+        test.add_immediate_delegation("delegator", 10_000_000u128, active);
+        test.add_immediate_delegation("delegator", 20_000_000u128, standby);
+        test.force_change_rewarded_set(vec![active, standby]);
+
+        let statuses = test.rewarded_set();
+        assert!(statuses.contains(&(active, RewardedSetNodeStatus::Active)));
+
+        let expected_active_stake = statuses
+            .iter()
+            .filter(|(_, status)| status.is_active())
+            .map(|(mix_id, _)| test.mix_rewarding(*mix_id).node_bond())
+            .fold(Decimal::zero(), |acc, stake| acc + stake);
+
+        let summary = query_stake_summary(test.deps()).unwrap();
+        assert_eq!(summary.total_bonded.amount, Uint128::new(350_000_000));
+        assert_eq!(summary.total_delegated.amount, Uint128::new(30_000_000));
+        assert_eq!(
+            summary.active_set_stake.amount,
+            truncate_reward_amount(expected_active_stake)
+        );
+    }
+
     #[cfg(test)]
     mod querying_for_pending_operator_reward {
         use super::*;
@@ -1168,4 +1355,122 @@ mod tests {
             assert_eq!(ress[2], expected3);
         }
     }
+
+    #[cfg(test)]
+    mod simulating_epoch_rewarding {
+        use super::*;
+        use mixnet_contract_common::reward_params::IntervalRewardingParamsUpdate;
+        use mixnet_contract_common::RewardedSetNodeStatus;
+
+        #[test]
+        fn without_changes_matches_actual_distribution() {
+            let mut test = TestSetup::new();
+            let initial_stake = Uint128::new(1_000_000_000_000);
+            let mix_id = test.add_dummy_mixnode("mix-owner", Some(initial_stake));
+
+            test.skip_to_next_epoch_end();
+            test.force_change_rewarded_set(vec![mix_id]);
+
+            let params = EpochRewardingSimulationParams {
+                performance: Some(test_helpers::performance(95.0)),
+                ..Default::default()
+            };
+            let res = query_simulated_epoch_rewarding(test.deps(), params, None, None).unwrap();
+            assert_eq!(res.rewarding_params, test.rewarding_params());
+            assert_eq!(res.nodes.len(), 1);
+            assert_eq!(res.nodes[0].mix_id, mix_id);
+            assert_eq!(res.nodes[0].status, RewardedSetNodeStatus::Active);
+
+            let dist = test.reward_with_distribution_with_state_bypass(
+                mix_id,
+                test_helpers::performance(95.0),
+            );
+            assert_eq!(res.nodes[0].reward.operator, dist.operator);
+            assert_eq!(res.nodes[0].reward.delegates, dist.delegates);
+        }
+
+        #[test]
+        fn applies_hypothetical_parameters() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.force_change_rewarded_set(vec![mix_id]);
+
+            let current = query_simulated_epoch_rewarding(
+                test.deps(),
+                EpochRewardingSimulationParams::default(),
+                None,
+                None,
+            )
+            .unwrap();
+
+            let current_params = test.rewarding_params();
+            let params = EpochRewardingSimulationParams {
+                interval_updates: Some(IntervalRewardingParamsUpdate {
+                    reward_pool: Some(
+                        current_params.interval.reward_pool * Decimal::from_ratio(2u32, 1u32),
+                    ),
+                    ..Default::default()
+                }),
+                active_set_size: Some(current_params.active_set_size / 2),
+                performance: None,
+            };
+            let simulated =
+                query_simulated_epoch_rewarding(test.deps(), params, None, None).unwrap();
+
+            assert_eq!(
+                simulated.rewarding_params.active_set_size,
+                current_params.active_set_size / 2
+            );
+            assert_eq!(
+                simulated.rewarding_params.interval.reward_pool,
+                current_params.interval.reward_pool * Decimal::from_ratio(2u32, 1u32)
+            );
+            assert!(
+                simulated.rewarding_params.interval.epoch_reward_budget
+                    > current_params.interval.epoch_reward_budget
+            );
+            assert!(
+                simulated.nodes[0].reward.total_node_reward
+                    > current.nodes[0].reward.total_node_reward
+            );
+
+            // and the actual state is not affected
+            assert_eq!(test.rewarding_params(), current_params);
+        }
+
+        #[test]
+        fn rejects_invalid_parameters() {
+            let test = TestSetup::new();
+            let params = EpochRewardingSimulationParams {
+                active_set_size: Some(0),
+                ..Default::default()
+            };
+            let res = query_simulated_epoch_rewarding(test.deps(), params, None, None);
+            assert_eq!(res, Err(MixnetContractError::ZeroActiveSet));
+
+            let params = EpochRewardingSimulationParams {
+                active_set_size: Some(test.rewarding_params().rewarded_set_size + 1),
+                ..Default::default()
+            };
+            let res = query_simulated_epoch_rewarding(test.deps(), params, None, None);
+            assert_eq!(res, Err(MixnetContractError::InvalidActiveSetSize));
+        }
+
+        #[test]
+        fn unbonding_nodes_get_nothing() {
+            let mut test = TestSetup::new();
+            let mix_id = test.add_dummy_mixnode("mix-owner", None);
+            test.force_change_rewarded_set(vec![mix_id]);
+            test.start_unbonding_mixnode(mix_id);
+
+            let res = query_simulated_epoch_rewarding(
+                test.deps(),
+                EpochRewardingSimulationParams::default(),
+                None,
+                None,
+            )
+            .unwrap();
+            assert_eq!(res.nodes[0].reward, RewardEstimate::default());
+        }
+    }
 }
@@ -2,14 +2,15 @@
 // SPDX-License-Identifier: Apache-2.0
 
 use crate::constants::{
-    MIXNODES_REWARDING_PK_NAMESPACE, PENDING_REWARD_POOL_KEY, REWARDING_PARAMS_KEY,
+    MIXNODES_PERFORMANCE_NAMESPACE, MIXNODES_REWARDING_PK_NAMESPACE, PENDING_REWARD_POOL_KEY,
+    REWARDING_PARAMS_KEY,
 };
 use crate::rewards::models::RewardPoolChange;
 use cosmwasm_std::{Decimal, StdResult, Storage};
 use cw_storage_plus::{Item, Map};
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::mixnode::MixNodeRewarding;
-use mixnet_contract_common::reward_params::RewardingParams;
+use mixnet_contract_common::reward_params::{Performance, RewardingParams};
 use mixnet_contract_common::MixId;
 
 // current parameters used for rewarding purposes
@@ -20,6 +21,9 @@ pub(crate) const PENDING_REWARD_POOL_CHANGE: Item<'_, RewardPoolChange> =
 pub const MIXNODE_REWARDING: Map<MixId, MixNodeRewarding> =
     Map::new(MIXNODES_REWARDING_PK_NAMESPACE);
 
+// the performance reported by the rewarding validator when the node was most recently rewarded
+pub const MIXNODE_PERFORMANCE: Map<MixId, Performance> = Map::new(MIXNODES_PERFORMANCE_NAMESPACE);
+
 pub fn reward_accounting(
     storage: &mut dyn Storage,
     amount: Decimal,
@@ -99,6 +99,9 @@ pub(crate) fn try_reward_mixnode(
             absolute_epoch_id,
         })?;
 
+    // keep track of the reported performance so that it could be queried alongside the node details
+    storage::MIXNODE_PERFORMANCE.save(deps.storage, mix_id, &node_performance)?;
+
     // no need to calculate anything as rewards are going to be 0 for everything
     // however, we still need to update last_rewarded_epoch field
     if node_performance.is_zero() {
@@ -8,6 +8,7 @@ use cosmwasm_std::{wasm_execute, Addr, BankMsg, Coin, CosmosMsg, MessageInfo, Re
 use mixnet_contract_common::error::MixnetContractError;
 use mixnet_contract_common::mixnode::PendingMixNodeChanges;
 use mixnet_contract_common::{EpochState, EpochStatus, IdentityKeyRef, MixId, MixNodeBond};
+use semver::Version;
 use vesting_contract_common::messages::ExecuteMsg as VestingContractExecuteMsg;
 
 // helper trait to attach `Msg` to a response if it's provided
@@ -415,6 +416,33 @@ pub(crate) fn ensure_no_existing_bond(
     Ok(())
 }
 
+pub(crate) fn parse_semver(value: &str) -> Result<Version, MixnetContractError> {
+    Version::parse(value).map_err(|error| MixnetContractError::SemVerFailure {
+        value: value.to_string(),
+        error_message: error.to_string(),
+    })
+}
+
+// check if the mixnode is not running a version older than the minimum supported one, if any is set
+pub(crate) fn ensure_supported_mixnode_version(
+    storage: &dyn Storage,
+    version: &str,
+) -> Result<(), MixnetContractError> {
+    let Some(min_supported_version) =
+        mixnet_params_storage::min_supported_mixnode_version(storage)?
+    else {
+        return Ok(());
+    };
+
+    if parse_semver(version)? < parse_semver(&min_supported_version)? {
+        return Err(MixnetContractError::UnsupportedMixnodeVersion {
+            version: version.to_string(),
+            min_supported_version,
+        });
+    }
+    Ok(())
+}
+
 pub(crate) fn decode_ed25519_identity_key(
     encoded: IdentityKeyRef,
 ) -> Result<[u8; 32], MixnetContractError> {
@@ -14,6 +14,7 @@ pub fn mix_node_fixture() -> MixNode {
         sphinx_key: "sphinx".to_string(),
         identity_key: "identity".to_string(),
         version: "0.10.0".to_string(),
+        next_sphinx_key: None,
     }
 }
 
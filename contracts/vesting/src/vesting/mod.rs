@@ -640,6 +640,7 @@ mod tests {
             sphinx_key: "sphinx".to_string(),
             identity_key: "identity".to_string(),
             version: "0.10.0".to_string(),
+            next_sphinx_key: None,
         };
 
         let cost_params = MixNodeCostParams {
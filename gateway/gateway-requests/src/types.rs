@@ -304,6 +304,14 @@ pub enum ServerResponse {
     Error {
         message: String,
     },
+    /// Sent by the gateway when the client attempted to use a protocol version
+    /// that is no longer accepted and it has to be upgraded before it can connect.
+    UpgradeRequired {
+        #[serde(default)]
+        client_protocol_version: Option<u8>,
+        minimum_protocol_version: u8,
+        message: String,
+    },
 }
 
 impl ServerResponse {
@@ -314,6 +322,7 @@ impl ServerResponse {
             ServerResponse::Bandwidth { .. } => "Bandwidth".to_string(),
             ServerResponse::Send { .. } => "Send".to_string(),
             ServerResponse::Error { .. } => "Error".to_string(),
+            ServerResponse::UpgradeRequired { .. } => "UpgradeRequired".to_string(),
         }
     }
     pub fn new_error<S: Into<String>>(msg: S) -> Self {
@@ -322,8 +331,23 @@ impl ServerResponse {
         }
     }
 
+    pub fn new_upgrade_required<S: Into<String>>(
+        client_protocol_version: Option<u8>,
+        minimum_protocol_version: u8,
+        msg: S,
+    ) -> Self {
+        ServerResponse::UpgradeRequired {
+            client_protocol_version,
+            minimum_protocol_version,
+            message: msg.into(),
+        }
+    }
+
     pub fn is_error(&self) -> bool {
-        matches!(self, ServerResponse::Error { .. })
+        matches!(
+            self,
+            ServerResponse::Error { .. } | ServerResponse::UpgradeRequired { .. }
+        )
     }
 
     pub fn implies_successful_authentication(&self) -> bool {
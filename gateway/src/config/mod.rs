@@ -489,6 +489,12 @@ pub struct Debug {
     // existing nodes whilst everyone else is upgrading and getting the code for handling the new field.
     // It shall be disabled in the subsequent releases.
     pub use_legacy_framed_packet_version: bool,
+
+    /// Specifies the minimum gateway protocol version the clients must be using in order to be allowed to connect.
+    /// Clients using an older version are rejected with an 'upgrade required' response.
+    /// If not set, all protocol versions understood by this gateway are accepted.
+    #[serde(default)]
+    pub minimum_client_protocol_version: Option<u8>,
 }
 
 impl Default for Debug {
@@ -505,6 +511,7 @@ impl Default for Debug {
             client_bandwidth_max_delta_flushing_amount:
                 DEFAULT_CLIENT_BANDWIDTH_MAX_DELTA_FLUSHING_AMOUNT,
             use_legacy_framed_packet_version: false,
+            minimum_client_protocol_version: None,
         }
     }
 }
@@ -0,0 +1,130 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use dashmap::DashMap;
+use nym_gateway_requests::INITIAL_PROTOCOL_VERSION;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub(crate) struct ProtocolVersionCounts {
+    /// Number of connections that were accepted whilst reporting this protocol version.
+    pub(crate) accepted: u64,
+
+    /// Number of connections that got rejected whilst reporting this protocol version,
+    /// for example due to it being below the configured minimum.
+    pub(crate) rejected: u64,
+}
+
+/// Keeps track of the protocol versions reported by clients connecting to this gateway,
+/// so that it would be possible to determine when legacy protocol paths could be safely retired.
+#[derive(Clone, Default)]
+pub(crate) struct ClientProtocolVersions {
+    // `None` corresponds to clients that have not reported any protocol version at all
+    inner: Arc<DashMap<Option<u8>, ProtocolVersionCounts>>,
+}
+
+impl ClientProtocolVersions {
+    pub(crate) fn new() -> Self {
+        ClientProtocolVersions::default()
+    }
+
+    pub(crate) fn record_accepted(&self, client_protocol: Option<u8>) {
+        self.inner.entry(client_protocol).or_default().accepted += 1;
+        inc_metric(client_protocol, "accepted");
+    }
+
+    pub(crate) fn record_rejected(&self, client_protocol: Option<u8>) {
+        self.inner.entry(client_protocol).or_default().rejected += 1;
+        inc_metric(client_protocol, "rejected");
+    }
+
+    /// Returns the number of accepted connections keyed by the human-readable protocol version.
+    pub(crate) fn accepted_by_version(&self) -> HashMap<String, u64> {
+        self.labelled(|counts| counts.accepted)
+    }
+
+    /// Returns the number of rejected connections keyed by the human-readable protocol version.
+    pub(crate) fn rejected_by_version(&self) -> HashMap<String, u64> {
+        self.labelled(|counts| counts.rejected)
+    }
+
+    fn labelled<F>(&self, f: F) -> HashMap<String, u64>
+    where
+        F: Fn(&ProtocolVersionCounts) -> u64,
+    {
+        self.inner
+            .iter()
+            .map(|entry| (version_label(*entry.key()), f(entry.value())))
+            .collect()
+    }
+}
+
+/// Checks whether the protocol version reported by the client is at least the configured minimum.
+/// Clients that haven't reported any version are treated as using the initial one.
+pub(crate) fn meets_minimum_protocol(client_protocol: Option<u8>, minimum: u8) -> bool {
+    client_protocol.unwrap_or(INITIAL_PROTOCOL_VERSION) >= minimum
+}
+
+// the counts are also exposed on the prometheus endpoint of the node,
+// e.g. as `nym_gateway_client_protocol_v2_accepted`
+fn inc_metric(client_protocol: Option<u8>, outcome: &str) {
+    nym_metrics::REGISTRY.inc(&format!(
+        "nym_gateway_client_protocol_{}_{outcome}",
+        version_label(client_protocol)
+    ));
+}
+
+fn version_label(client_protocol: Option<u8>) -> String {
+    match client_protocol {
+        Some(version) => format!("v{version}"),
+        None => "unknown".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_are_aggregated_per_version() {
+        let versions = ClientProtocolVersions::new();
+        versions.record_accepted(Some(2));
+        versions.record_accepted(Some(2));
+        versions.record_accepted(None);
+        versions.record_rejected(Some(1));
+
+        let accepted = versions.accepted_by_version();
+        assert_eq!(accepted.len(), 3);
+        assert_eq!(accepted["v2"], 2);
+        assert_eq!(accepted["unknown"], 1);
+        assert_eq!(accepted["v1"], 0);
+
+        let rejected = versions.rejected_by_version();
+        assert_eq!(rejected["v1"], 1);
+        assert_eq!(rejected["v2"], 0);
+        assert!(!rejected.contains_key("v3"));
+    }
+
+    #[test]
+    fn counts_are_exposed_as_metrics() {
+        let versions = ClientProtocolVersions::new();
+        versions.record_accepted(Some(200));
+        versions.record_rejected(Some(201));
+
+        let metrics = nym_metrics::REGISTRY.to_string();
+        assert!(metrics.contains("nym_gateway_client_protocol_v200_accepted 1"));
+        assert!(metrics.contains("nym_gateway_client_protocol_v201_rejected 1"));
+    }
+
+    #[test]
+    fn minimum_protocol_threshold() {
+        assert!(meets_minimum_protocol(Some(3), 2));
+        assert!(meets_minimum_protocol(Some(2), 2));
+        assert!(!meets_minimum_protocol(Some(1), 2));
+
+        // unversioned clients are considered to be using the initial version
+        assert!(meets_minimum_protocol(None, INITIAL_PROTOCOL_VERSION));
+        assert!(!meets_minimum_protocol(None, INITIAL_PROTOCOL_VERSION + 1));
+    }
+}
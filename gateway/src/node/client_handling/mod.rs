@@ -5,8 +5,10 @@ use crate::node::client_handling::bandwidth::Bandwidth;
 
 pub(crate) mod active_clients;
 mod bandwidth;
+pub(crate) mod client_versions;
 pub(crate) mod embedded_clients;
 pub(crate) mod websocket;
+pub(crate) mod wireguard_credentials;
 
 pub(crate) const FREE_TESTNET_BANDWIDTH_VALUE: Bandwidth =
     Bandwidth::new_unchecked(64 * 1024 * 1024 * 1024); // 64GB
@@ -1,6 +1,7 @@
 // Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::node::client_handling::client_versions::ClientProtocolVersions;
 use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
 use crate::node::client_handling::websocket::connection_handler::BandwidthFlushingBehaviourConfig;
 use nym_crypto::asymmetric::identity;
@@ -13,4 +14,6 @@ pub(crate) struct CommonHandlerState {
     pub(crate) local_identity: Arc<identity::KeyPair>,
     pub(crate) only_coconut_credentials: bool,
     pub(crate) bandwidth_cfg: BandwidthFlushingBehaviourConfig,
+    pub(crate) minimum_client_protocol_version: Option<u8>,
+    pub(crate) client_protocol_versions: ClientProtocolVersions,
 }
@@ -26,6 +26,7 @@ use thiserror::Error;
 use tokio::io::{AsyncRead, AsyncWrite};
 use tokio_tungstenite::tungstenite::{protocol::Message, Error as WsError};
 
+use crate::node::client_handling::client_versions::meets_minimum_protocol;
 use crate::node::client_handling::websocket::common_state::CommonHandlerState;
 use crate::node::client_handling::websocket::connection_handler::AvailableBandwidth;
 use crate::node::{
@@ -80,6 +81,9 @@ pub(crate) enum InitialAuthenticationError {
     #[error("Attempted to negotiate connection with client using incompatible protocol version. Ours is {current} and the client reports {client:?}")]
     IncompatibleProtocol { client: Option<u8>, current: u8 },
 
+    #[error("upgrade required: this gateway only accepts clients using protocol version {minimum} or newer, but the client reports {client:?}")]
+    OutdatedProtocol { client: Option<u8>, minimum: u8 },
+
     #[error("failed to send authentication error response: {source}")]
     ErrorResponseSendFailure {
         #[source]
@@ -117,7 +121,12 @@ pub(crate) enum InitialAuthenticationError {
 impl InitialAuthenticationError {
     /// Converts this Error into an appropriate websocket Message.
     fn to_error_message(&self) -> Message {
-        ServerResponse::new_error(self.to_string()).into()
+        match self {
+            InitialAuthenticationError::OutdatedProtocol { client, minimum } => {
+                ServerResponse::new_upgrade_required(*client, *minimum, self.to_string()).into()
+            }
+            _ => ServerResponse::new_error(self.to_string()).into(),
+        }
     }
 }
 
@@ -391,8 +400,35 @@ where
     fn negotiate_client_protocol(
         &self,
         client_protocol: Option<u8>,
+    ) -> Result<u8, InitialAuthenticationError> {
+        let negotiated = self.check_client_protocol(client_protocol);
+
+        // keep track of what versions our clients are using so we'd know when it's safe to retire old ones
+        let versions = &self.shared_state.client_protocol_versions;
+        match negotiated {
+            Ok(_) => versions.record_accepted(client_protocol),
+            Err(_) => versions.record_rejected(client_protocol),
+        }
+        negotiated
+    }
+
+    fn check_client_protocol(
+        &self,
+        client_protocol: Option<u8>,
     ) -> Result<u8, InitialAuthenticationError> {
         debug!("client protocol: {client_protocol:?}, ours: {CURRENT_PROTOCOL_VERSION}");
+
+        if let Some(minimum) = self.shared_state.minimum_client_protocol_version {
+            if !meets_minimum_protocol(client_protocol, minimum) {
+                let err = InitialAuthenticationError::OutdatedProtocol {
+                    client: client_protocol,
+                    minimum,
+                };
+                warn!("{err}");
+                return Err(err);
+            }
+        }
+
         let Some(client_protocol_version) = client_protocol else {
             warn!("the client we're connected to has not specified its protocol version. It's probably running version < 1.1.X, but that's still fine for now. It will become a hard error in 1.2.0");
             // note: in +1.2.0 we will have to return a hard error here
@@ -0,0 +1,133 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::node::client_handling::bandwidth::Bandwidth;
+use crate::node::client_handling::websocket::connection_handler::authenticated::RequestHandlingError;
+use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
+use crate::node::storage::Storage;
+use async_trait::async_trait;
+use log::*;
+use nym_credentials::coconut::bandwidth::{bandwidth_credential_params, CredentialType};
+use nym_credentials_interface::Base58;
+use nym_gateway_requests::models::CredentialSpendingRequest;
+use nym_sphinx::DestinationAddressBytes;
+use nym_wireguard_types::{BandwidthCredential, CredentialVerifier, PeerPublicKey};
+use std::sync::Arc;
+
+/// Verifies the bandwidth credentials presented by the wireguard clients
+/// the same way as the ones sent by the clients connected via the websocket,
+/// i.e. the credential is verified against the aggregated key of its epoch,
+/// the vouchers get redeemed and the serial number is marked as spent.
+///
+/// The credential is expected to be the serialised `CredentialSpendingRequest`.
+pub(crate) struct WireguardCredentialVerifier<St> {
+    coconut_verifier: Arc<CoconutVerifier>,
+    storage: St,
+}
+
+impl<St> WireguardCredentialVerifier<St>
+where
+    St: Storage,
+{
+    pub(crate) fn new(coconut_verifier: Arc<CoconutVerifier>, storage: St) -> Self {
+        WireguardCredentialVerifier {
+            coconut_verifier,
+            storage,
+        }
+    }
+
+    async fn spend_credential(
+        &self,
+        client: PeerPublicKey,
+        credential: &BandwidthCredential,
+    ) -> Result<(), RequestHandlingError> {
+        let credential = CredentialSpendingRequest::try_from_bytes(credential)?;
+
+        let serial_number = credential.data.blinded_serial_number();
+        trace!(
+            "processing wireguard credential {} of {client}",
+            serial_number.to_bs58()
+        );
+
+        if self.storage.contains_credential(&serial_number).await? {
+            trace!("the credential has already been spent before");
+            return Err(RequestHandlingError::BandwidthCredentialAlreadySpent);
+        }
+
+        if !credential.data.validate_type_attribute() {
+            trace!("mismatch in the type attribute");
+            return Err(RequestHandlingError::InvalidTypeAttribute);
+        }
+
+        let Some(bandwidth_attribute) = credential.data.get_bandwidth_attribute() else {
+            trace!("missing bandwidth attribute");
+            return Err(RequestHandlingError::MissingBandwidthAttribute);
+        };
+
+        // this will extract token amounts out of bandwidth vouchers and validate expiry of free passes
+        let (raw_bandwidth, freepass_expiration) =
+            Bandwidth::parse_raw_bandwidth(bandwidth_attribute, credential.data.typ)?;
+
+        // the wireguard traffic is not metered (yet), but make sure the credential carries a sane value
+        Bandwidth::new(raw_bandwidth)?;
+
+        let aggregated_verification_key = self
+            .coconut_verifier
+            .verification_key(credential.data.epoch_id)
+            .await?;
+
+        let params = bandwidth_credential_params();
+        if !credential.data.verify(params, &aggregated_verification_key) {
+            trace!("the credential did not verify correctly");
+            return Err(RequestHandlingError::InvalidBandwidthCredential(
+                String::from("local credential verification has failed"),
+            ));
+        }
+
+        if credential.data.typ == CredentialType::Voucher {
+            trace!("the credential is a bandwidth voucher. attempting to release the funds");
+            let api_clients = self
+                .coconut_verifier
+                .api_clients(credential.data.epoch_id)
+                .await?;
+
+            self.coconut_verifier
+                .release_bandwidth_voucher_funds(&api_clients, credential)
+                .await?;
+        }
+
+        // the unique constraint on the serial number makes sure that out of any parallel
+        // attempts at spending the same credential, only a single one is going to succeed
+        self.storage
+            .insert_spent_credential(
+                serial_number,
+                freepass_expiration.is_some(),
+                DestinationAddressBytes::from_bytes(client.inner().to_bytes()),
+            )
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<St> CredentialVerifier for WireguardCredentialVerifier<St>
+where
+    St: Storage,
+{
+    async fn verify_credential(
+        &self,
+        client: PeerPublicKey,
+        credential: &BandwidthCredential,
+    ) -> Result<(), nym_wireguard_types::Error> {
+        self.spend_credential(client, credential)
+            .await
+            .map_err(|err| {
+                debug!("rejected the bandwidth credential of wireguard client {client}: {err}");
+                nym_wireguard_types::Error::InvalidBandwidthCredential {
+                    client: client.to_string(),
+                    reason: err.to_string(),
+                }
+            })
+    }
+}
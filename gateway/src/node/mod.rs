@@ -10,9 +10,11 @@ use crate::helpers::{
 };
 use crate::http::HttpApiBuilder;
 use crate::node::client_handling::active_clients::ActiveClientsStore;
+use crate::node::client_handling::client_versions::ClientProtocolVersions;
 use crate::node::client_handling::embedded_clients::{LocalEmbeddedClientHandle, MessageRouter};
 use crate::node::client_handling::websocket;
 use crate::node::client_handling::websocket::connection_handler::coconut::CoconutVerifier;
+use crate::node::client_handling::wireguard_credentials::WireguardCredentialVerifier;
 use crate::node::helpers::{initialise_main_storage, load_network_requester_config};
 use crate::node::mixnet_handling::receiver::connection_handler::ConnectionHandler;
 use crate::node::statistics::collector::GatewayStatisticsCollector;
@@ -248,6 +250,7 @@ impl<St> Gateway<St> {
         &self,
         forwarding_channel: MixForwardingSender,
         active_clients_store: ActiveClientsStore,
+        client_protocol_versions: ClientProtocolVersions,
         shutdown: TaskClient,
         coconut_verifier: Arc<CoconutVerifier>,
     ) where
@@ -265,6 +268,8 @@ impl<St> Gateway<St> {
             local_identity: Arc::clone(&self.identity_keypair),
             only_coconut_credentials: self.config.gateway.only_coconut_credentials,
             bandwidth_cfg: (&self.config).into(),
+            minimum_client_protocol_version: self.config.debug.minimum_client_protocol_version,
+            client_protocol_versions,
         };
 
         websocket::Listener::new(listening_address, shared_state).start(
@@ -496,12 +501,26 @@ impl<St> Gateway<St> {
             }
         }
 
-        let coconut_verifier =
-            CoconutVerifier::new(nyxd_client, self.config.gateway.only_coconut_credentials).await?;
+        let coconut_verifier = Arc::new(
+            CoconutVerifier::new(nyxd_client, self.config.gateway.only_coconut_credentials).await?,
+        );
+
+        if let Some(wireguard_data) = self.wireguard_data.as_ref() {
+            if wireguard_data.config().require_bandwidth_credential {
+                let verifier = WireguardCredentialVerifier::new(
+                    Arc::clone(&coconut_verifier),
+                    self.storage.clone(),
+                );
+                if !wireguard_data.set_credential_verifier(Arc::new(verifier)) {
+                    warn!("the wireguard credential verifier has already been installed");
+                }
+            }
+        }
 
         let mix_forwarding_channel = self.start_packet_forwarder(shutdown.fork("PacketForwarder"));
 
         let active_clients_store = ActiveClientsStore::new();
+        let client_protocol_versions = ClientProtocolVersions::new();
         self.start_mix_socket_listener(
             mix_forwarding_channel.clone(),
             active_clients_store.clone(),
@@ -513,6 +532,7 @@ impl<St> Gateway<St> {
             let stats_collector = GatewayStatisticsCollector::new(
                 self.identity_keypair.public_key().to_base58_string(),
                 active_clients_store.clone(),
+                client_protocol_versions.clone(),
                 statistics_service_url,
             );
             let mut stats_sender = StatisticsSender::new(stats_collector);
@@ -524,8 +544,9 @@ impl<St> Gateway<St> {
         self.start_client_websocket_listener(
             mix_forwarding_channel.clone(),
             active_clients_store.clone(),
+            client_protocol_versions,
             shutdown.fork("websocket::Listener"),
-            Arc::new(coconut_verifier),
+            coconut_verifier,
         );
 
         let nr_request_filter = if self.config.network_requester.enabled {
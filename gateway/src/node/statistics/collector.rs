@@ -12,10 +12,12 @@ use nym_statistics_common::{
 };
 
 use crate::node::client_handling::active_clients::ActiveClientsStore;
+use crate::node::client_handling::client_versions::ClientProtocolVersions;
 
 pub(crate) struct GatewayStatisticsCollector {
     gateway_id: String,
     active_clients_store: ActiveClientsStore,
+    client_protocol_versions: ClientProtocolVersions,
     statistics_service_url: Url,
 }
 
@@ -23,11 +25,13 @@ impl GatewayStatisticsCollector {
     pub fn new(
         gateway_id: String,
         active_clients_store: ActiveClientsStore,
+        client_protocol_versions: ClientProtocolVersions,
         statistics_service_url: Url,
     ) -> Self {
         GatewayStatisticsCollector {
             gateway_id,
             active_clients_store,
+            client_protocol_versions,
             statistics_service_url,
         }
     }
@@ -41,10 +45,13 @@ impl StatisticsCollector for GatewayStatisticsCollector {
         timestamp: DateTime<Utc>,
     ) -> StatsMessage {
         let inbox_count = self.active_clients_store.size() as u32;
-        let stats_data = vec![StatsData::Gateway(StatsGatewayData::new(
-            self.gateway_id.clone(),
-            inbox_count,
-        ))];
+        let stats_data = vec![StatsData::Gateway(
+            StatsGatewayData::new(self.gateway_id.clone(), inbox_count)
+                .with_client_protocol_versions(
+                    self.client_protocol_versions.accepted_by_version(),
+                    self.client_protocol_versions.rejected_by_version(),
+                ),
+        )];
         StatsMessage {
             stats_data,
             interval_seconds: interval.as_secs() as u32,
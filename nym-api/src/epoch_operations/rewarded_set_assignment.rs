@@ -8,10 +8,13 @@ use cosmwasm_std::Decimal;
 use nym_mixnet_contract_common::families::FamilyHead;
 use nym_mixnet_contract_common::reward_params::Performance;
 use nym_mixnet_contract_common::{
-    EpochState, IdentityKey, Interval, Layer, LayerAssignment, MixId, MixNodeDetails,
+    ActiveSetProvenance, EpochState, IdentityKey, Interval, Layer, LayerAssignment, MixId,
+    MixNodeDetails,
 };
 use rand::prelude::SliceRandom;
 use rand::rngs::OsRng;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha20Rng;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone)]
@@ -107,8 +110,9 @@ impl RewardedSetUpdater {
         Ok(assignments)
     }
 
-    fn determine_rewarded_set(
+    fn determine_rewarded_set<R: Rng>(
         &self,
+        rng: &mut R,
         mixnodes: Vec<MixnodeWithStakeAndPerformance>,
         nodes_to_select: u32,
     ) -> Result<Vec<MixnodeWithStakeAndPerformance>, RewardingError> {
@@ -116,8 +120,6 @@ impl RewardedSetUpdater {
             return Ok(Vec::new());
         }
 
-        let mut rng = OsRng;
-
         // generate list of mixnodes and their relatively weight (by total stake)
         let choices = mixnodes
             .into_iter()
@@ -133,11 +135,33 @@ impl RewardedSetUpdater {
         // - all weights are zero - it's impossible in our case as the list of nodes is not empty and weight is proportional to stake. You must have non-zero stake in order to bond
         // - we have more than u32::MAX values (which is incredibly unrealistic to have 4B mixnodes bonded... literally every other person on the planet would need one)
         Ok(choices
-            .choose_multiple_weighted(&mut rng, nodes_to_select as usize, |item| item.1)?
+            .choose_multiple_weighted(rng, nodes_to_select as usize, |item| item.1)?
             .map(|(mix, _weight)| mix.clone())
             .collect())
     }
 
+    // the selection is seeded with the hash of the most recent block, so that anyone could reproduce it
+    // given the same inputs. the provenance of the seed is recorded in the contract alongside the active set
+    async fn selection_seed(
+        &self,
+    ) -> Result<Option<([u8; 32], ActiveSetProvenance)>, RewardingError> {
+        let block_height = self.nyxd_client.current_block_height().await?;
+        let Some(block_hash) = self
+            .nyxd_client
+            .get_block_hash(u32::try_from(block_height)?)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let provenance = ActiveSetProvenance {
+            block_height,
+            block_hash: block_hash.iter().map(|b| format!("{b:02x}")).collect(),
+            vrf_seed: None,
+        };
+        Ok(Some((block_hash, provenance)))
+    }
+
     async fn attach_performance(
         &self,
         interval: Interval,
@@ -202,8 +226,27 @@ impl RewardedSetUpdater {
 
         debug!("Rewarding paremeters: {:?}", rewarding_parameters);
 
-        let new_rewarded_set =
-            self.determine_rewarded_set(all_mixnodes, rewarding_parameters.rewarded_set_size)?;
+        let (new_rewarded_set, selection_provenance) = match self.selection_seed().await? {
+            Some((seed, provenance)) => {
+                debug!("Selecting the rewarded set using {provenance:?}");
+                let mut rng = ChaCha20Rng::from_seed(seed);
+                let set = self.determine_rewarded_set(
+                    &mut rng,
+                    all_mixnodes,
+                    rewarding_parameters.rewarded_set_size,
+                )?;
+                (set, Some(provenance))
+            }
+            None => {
+                warn!("the most recent block has no hash - the rewarded set selection is going to be unseeded");
+                let set = self.determine_rewarded_set(
+                    &mut OsRng,
+                    all_mixnodes,
+                    rewarding_parameters.rewarded_set_size,
+                )?;
+                (set, None)
+            }
+        };
 
         debug!("New rewarded set: {:?}", new_rewarded_set);
 
@@ -240,6 +283,7 @@ impl RewardedSetUpdater {
             .advance_current_epoch(
                 active_set_layer_assignments,
                 rewarding_parameters.active_set_size,
+                selection_provenance,
             )
             .await?;
 
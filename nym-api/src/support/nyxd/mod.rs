@@ -27,8 +27,8 @@ use nym_mixnet_contract_common::families::FamilyHead;
 use nym_mixnet_contract_common::mixnode::MixNodeDetails;
 use nym_mixnet_contract_common::reward_params::RewardingParams;
 use nym_mixnet_contract_common::{
-    CurrentIntervalResponse, EpochStatus, ExecuteMsg, GatewayBond, IdentityKey, LayerAssignment,
-    MixId, RewardedSetNodeStatus,
+    ActiveSetProvenance, CurrentIntervalResponse, EpochStatus, ExecuteMsg, GatewayBond,
+    IdentityKey, LayerAssignment, MixId, RewardedSetNodeStatus,
 };
 use nym_name_service_common::msg::QueryMsg as NameServiceQueryMsg;
 use nym_service_provider_directory_common::msg::QueryMsg as SpQueryMsg;
@@ -184,13 +184,18 @@ impl Client {
         Ok(time)
     }
 
+    pub(crate) async fn current_block_height(&self) -> Result<u64, NyxdError> {
+        let height = nyxd_query!(self, get_current_block_height().await?);
+
+        Ok(height.value())
+    }
+
     /// Obtains the hash of a block specified by the provided height.
     /// If the resulting digest is empty, a `None` is returned instead.
     ///
     /// # Arguments
     ///
     /// * `height`: height of the block for which we want to obtain the hash.
-    #[allow(dead_code)]
     pub(crate) async fn get_block_hash(
         &self,
         height: u32,
@@ -326,10 +331,17 @@ impl Client {
         &self,
         new_rewarded_set: Vec<LayerAssignment>,
         expected_active_set_size: u32,
+        selection_provenance: Option<ActiveSetProvenance>,
     ) -> Result<(), NyxdError> {
         nyxd_signing!(
             self,
-            advance_current_epoch(new_rewarded_set, expected_active_set_size, None).await?
+            advance_current_epoch_with_provenance(
+                new_rewarded_set,
+                expected_active_set_size,
+                selection_provenance,
+                None
+            )
+            .await?
         );
         Ok(())
     }
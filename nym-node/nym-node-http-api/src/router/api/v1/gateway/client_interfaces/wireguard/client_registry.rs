@@ -7,51 +7,399 @@ use crate::api::v1::gateway::client_interfaces::wireguard::{
 };
 use crate::api::{FormattedResponse, OutputParams};
 use crate::router::types::RequestError;
+use axum::extract::rejection::JsonRejection;
 use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
 use axum::Json;
 use nym_node_requests::api::v1::gateway::client_interfaces::wireguard::models::{
-    ClientMessage, ClientRegistrationResponse, GatewayClient, InitMessage, Nonce, PeerPublicKey,
+    ClientMessage, ClientRegistrationResponse, FinalMessage, GatewayClient, InitMessage,
+    KeyRotationMessage, PeerPublicKey, RenewRegistrationMessage,
+};
+use nym_wireguard_types::credential::{
+    ensure_registration_credential, verify_registration_credential,
+};
+use nym_wireguard_types::events::emit_peer_event;
+use nym_wireguard_types::registration::{
+    reserve_requested_ip, transfer_reservation, unix_timestamp, PendingRegistration,
+};
+use nym_wireguard_types::{
+    BandwidthCredential, Error as WireguardTypesError, MacAlgorithm, PeerEvent, PowChallenge,
 };
 use rand::{prelude::IteratorRandom, thread_rng};
+use std::net::IpAddr;
+use std::time::{Instant, SystemTime};
+
+/// Outcome of a successfully processed registration message,
+/// alongside the time at which the resulting registration expires, if it's time-bound.
+struct Processed {
+    status: StatusCode,
+    expires_at: Option<SystemTime>,
+}
+
+impl Processed {
+    fn ok(expires_at: Option<SystemTime>) -> Self {
+        Processed {
+            status: StatusCode::OK,
+            expires_at,
+        }
+    }
+
+    fn expiry_timestamp(&self) -> Option<u64> {
+        self.expires_at.map(unix_timestamp)
+    }
+}
 
 async fn process_final_message(
-    client: GatewayClient,
+    final_message: FinalMessage,
     state: &WireguardAppStateInner,
-) -> Result<StatusCode, RequestError> {
-    let preshared_nonce = {
-        if let Some(nonce) = state.registration_in_progress.get(&client.pub_key()) {
-            *nonce
+) -> Result<Processed, RequestError> {
+    let FinalMessage {
+        gateway_client: client,
+        pow_solution,
+    } = final_message;
+
+    let pending = {
+        if let Some(pending) = state.registration_in_progress.get(&client.pub_key()) {
+            pending.clone()
         } else {
-            return Err(RequestError::from_err(
-                WireguardError::RegistrationNotInProgress,
+            return Err(RequestError::from_registration_err(
+                WireguardTypesError::StaleNonce {
+                    client: client.pub_key().to_string(),
+                },
                 StatusCode::BAD_REQUEST,
             ));
         }
     };
 
-    if client
-        .verify(state.keypair.private_key(), preshared_nonce)
-        .is_ok()
+    // the ip has been set aside for this client at the initial message, so it can't claim any other
+    if client.private_ip != pending.private_ip {
+        return Err(RequestError::from_err(
+            WireguardError::PrivateIpMismatch,
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    // checking the solution is far cheaper than the mac verification, so do it first
+    if let Some(challenge) = pending.pow_challenge {
+        challenge
+            .verify(&client.pub_key(), pow_solution)
+            .map_err(|err| RequestError::from_err(err, StatusCode::FORBIDDEN))?;
+    }
+
+    // other registrations might have been completed in the meantime
+    if !state.client_registry.contains_key(&client.pub_key()) {
+        ensure_registry_capacity(state)?;
+    }
+
+    client
+        .verify(state.keypair.private_key(), pending.nonce)
+        .map_err(|err| RequestError::from_registration_err(err, StatusCode::BAD_REQUEST))?;
+
+    // verifying the credential spends it, so it's only done once nothing else can fail
+    verify_client_credential(client.pub_key(), pending.credential.as_ref(), state).await?;
+
+    state.registration_in_progress.remove(&client.pub_key());
+    let event = PeerEvent::PeerRegistered {
+        pub_key: client.pub_key(),
+        private_ip: client.private_ip,
+    };
+    let pub_key = client.pub_key();
+    let expires_at = state.config.borrow().registration_expiry(SystemTime::now());
+    state.client_registry.insert(pub_key, client);
+    // the entry has just been inserted, so this can only fail if it got concurrently removed
+    let _ = state.client_registry.set_expiry(pub_key, expires_at);
+    emit_peer_event(&state.peer_events, event);
+
+    Ok(Processed::ok(expires_at))
+}
+
+async fn process_rotate_key_message(
+    rotation: KeyRotationMessage,
+    state: &WireguardAppStateInner,
+) -> Result<Processed, RequestError> {
+    let Some(registered_ip) = state
+        .client_registry
+        .get(&rotation.old_pub_key)
+        .map(|client| client.private_ip)
+    else {
+        return Err(RequestError::from_err(
+            WireguardError::ClientNotRegistered,
+            StatusCode::NOT_FOUND,
+        ));
+    };
+
+    // don't let suspended peers escape by switching to a different key
+    ensure_not_suspended(&rotation.old_pub_key, state)?;
+
+    if registered_ip != rotation.private_ip {
+        return Err(RequestError::from_err(
+            WireguardError::PrivateIpMismatch,
+            StatusCode::BAD_REQUEST,
+        ));
+    }
+
+    let now = SystemTime::now();
+    rotation
+        .verify(state.keypair.private_key(), now)
+        .map_err(|err| RequestError::from_registration_err(err, StatusCode::BAD_REQUEST))?;
+    rotation
+        .consume_nonce(&state.seen_rotation_nonces, now)
+        .map_err(|err| RequestError::from_registration_err(err, StatusCode::CONFLICT))?;
+
+    // the rotated key is granted a tunnel just like a freshly registered one, so it has to be paid for as well
+    verify_client_credential(rotation.new_pub_key, rotation.credential.as_ref(), state).await?;
+
+    if state.client_registry.contains_key(&rotation.new_pub_key) {
+        return Err(RequestError::from_err(
+            WireguardError::PublicKeyAlreadyRegistered,
+            StatusCode::CONFLICT,
+        ));
+    }
+
+    // the classification labels and the expiry carry over to the new key
+    let metadata = state.client_registry.metadata(&rotation.old_pub_key);
+    let expires_at = state.client_registry.expires_at(&rotation.old_pub_key);
+
+    // only a single concurrent rotation of the same key can succeed in removing the old entry,
+    // so the private ip is never going to be assigned to two different keys
+    if state
+        .client_registry
+        .remove(&rotation.old_pub_key)
+        .is_none()
     {
-        state.registration_in_progress.remove(&client.pub_key());
-        state.client_registry.insert(client.pub_key(), client);
+        return Err(RequestError::from_err(
+            WireguardError::ClientNotRegistered,
+            StatusCode::NOT_FOUND,
+        ));
+    }
+    state
+        .client_registry
+        .insert(rotation.new_pub_key, rotation.rotated_client());
+    if let Some(metadata) = metadata.filter(|metadata| !metadata.is_empty()) {
+        // the entry has just been inserted, so this can only fail if it got concurrently removed
+        let _ = state
+            .client_registry
+            .set_metadata(rotation.new_pub_key, metadata);
+    }
+    let _ = state
+        .client_registry
+        .set_expiry(rotation.new_pub_key, expires_at);
+    transfer_reservation(
+        &state.ip_reservations,
+        rotation.old_pub_key,
+        rotation.new_pub_key,
+    );
+    emit_peer_event(
+        &state.peer_events,
+        PeerEvent::PeerKeyRotated {
+            old_pub_key: rotation.old_pub_key,
+            new_pub_key: rotation.new_pub_key,
+            private_ip: rotation.private_ip,
+        },
+    );
 
-        Ok(StatusCode::OK)
-    } else {
-        Err(RequestError::from_err(
-            WireguardError::MacVerificationFailure,
+    Ok(Processed::ok(expires_at))
+}
+
+async fn process_renew_message(
+    renewal: RenewRegistrationMessage,
+    state: &WireguardAppStateInner,
+) -> Result<Processed, RequestError> {
+    let now = SystemTime::now();
+
+    // peers that have already expired have to register again, even if they haven't been removed yet
+    let Some(registered_ip) = state
+        .client_registry
+        .get(&renewal.pub_key)
+        .filter(|_| !state.client_registry.is_expired(&renewal.pub_key, now))
+        .map(|client| client.private_ip)
+    else {
+        return Err(RequestError::from_err(
+            WireguardError::ClientNotRegistered,
+            StatusCode::NOT_FOUND,
+        ));
+    };
+
+    // suspended peers are not allowed to extend their registrations
+    ensure_not_suspended(&renewal.pub_key, state)?;
+
+    if registered_ip != renewal.private_ip {
+        return Err(RequestError::from_err(
+            WireguardError::PrivateIpMismatch,
             StatusCode::BAD_REQUEST,
-        ))
+        ));
+    }
+
+    renewal
+        .verify(state.keypair.private_key(), now)
+        .map_err(|err| RequestError::from_registration_err(err, StatusCode::BAD_REQUEST))?;
+
+    // extending the registration is no different from getting a new one
+    verify_client_credential(renewal.pub_key, renewal.credential.as_ref(), state).await?;
+
+    let expires_at = state.config.borrow().registration_expiry(now);
+    state
+        .client_registry
+        .set_expiry(renewal.pub_key, expires_at)
+        .map_err(|err| RequestError::from_err(err, StatusCode::NOT_FOUND))?;
+
+    Ok(Processed::ok(expires_at))
+}
+
+// fields of the client messages holding the public keys
+const PUBLIC_KEY_FIELDS: [&str; 3] = ["pub_key", "old_pub_key", "new_pub_key"];
+
+fn parse_client_message(payload: serde_json::Value) -> Result<ClientMessage, RequestError> {
+    serde_json::from_value(payload.clone()).map_err(|err| {
+        // report malformed keys with the error of the key parser itself,
+        // so that the clients could tell the bugs in their key encoding apart from other malformed messages
+        let err = PUBLIC_KEY_FIELDS
+            .iter()
+            .filter_map(|field| payload.get(field)?.as_str())
+            .find_map(|key| key.parse::<PeerPublicKey>().err())
+            .unwrap_or(WireguardTypesError::MalformedRegistrationMessage {
+                reason: err.to_string(),
+            });
+        RequestError::from_registration_err(err, StatusCode::UNPROCESSABLE_ENTITY)
+    })
+}
+
+fn ensure_not_suspended(
+    pub_key: &PeerPublicKey,
+    state: &WireguardAppStateInner,
+) -> Result<(), RequestError> {
+    if state.suspended_peers.contains(pub_key) {
+        return Err(RequestError::from_err(
+            WireguardTypesError::PeerSuspended {
+                client: pub_key.to_string(),
+            },
+            StatusCode::FORBIDDEN,
+        ));
+    }
+    Ok(())
+}
+
+fn ensure_registry_capacity(state: &WireguardAppStateInner) -> Result<(), RequestError> {
+    let registered_peers = state.client_registry.len();
+    state
+        .config
+        .borrow()
+        .ensure_registry_capacity(registered_peers)
+        .map_err(|err| match err {
+            WireguardTypesError::RegistryFull { retry_after } => {
+                RequestError::from_err(err, StatusCode::SERVICE_UNAVAILABLE)
+                    .with_retry_after(retry_after)
+            }
+            err => RequestError::from_err(err, StatusCode::INTERNAL_SERVER_ERROR),
+        })
+}
+
+async fn verify_client_credential(
+    client: PeerPublicKey,
+    credential: Option<&BandwidthCredential>,
+    state: &WireguardAppStateInner,
+) -> Result<(), RequestError> {
+    let required = state.config.borrow().require_bandwidth_credential;
+    verify_registration_credential(
+        state.credential_verifier.get(),
+        required,
+        client,
+        credential,
+    )
+    .await
+    .map_err(credential_error)
+}
+
+/// Reject the registration early if it's missing a credential the gateway requires.
+/// The credential itself is only verified, and spent, when the registration gets finalised.
+fn ensure_client_credential(
+    client: PeerPublicKey,
+    credential: Option<&BandwidthCredential>,
+    state: &WireguardAppStateInner,
+) -> Result<(), RequestError> {
+    let required = state.config.borrow().require_bandwidth_credential;
+    ensure_registration_credential(
+        state.credential_verifier.get(),
+        required,
+        client,
+        credential,
+    )
+    .map_err(credential_error)
+}
+
+fn credential_error(err: WireguardTypesError) -> RequestError {
+    match err {
+        WireguardTypesError::MissingBandwidthCredential { .. }
+        | WireguardTypesError::InvalidBandwidthCredential { .. } => {
+            RequestError::from_err(err, StatusCode::UNAUTHORIZED)
+        }
+        WireguardTypesError::CredentialVerifierUnavailable => {
+            RequestError::from_err(err, StatusCode::SERVICE_UNAVAILABLE)
+        }
+        err => RequestError::from_err(err, StatusCode::BAD_REQUEST),
     }
 }
 
-async fn process_init_message(init_message: InitMessage, state: &WireguardAppStateInner) -> Nonce {
-    let nonce: u64 = fastrand::u64(..);
+fn assign_private_ip(
+    init_message: &InitMessage,
+    state: &WireguardAppStateInner,
+) -> Result<IpAddr, RequestError> {
+    if let Some(requested_ip) = init_message.requested_ip {
+        return match reserve_requested_ip(
+            &state.free_private_network_ips,
+            &state.ip_reservations,
+            init_message.pub_key(),
+            requested_ip,
+        ) {
+            Ok(()) => Ok(requested_ip),
+            Err(err @ WireguardTypesError::RequestedIpUnavailable { .. }) => {
+                Err(RequestError::from_err(err, StatusCode::CONFLICT))
+            }
+            Err(err) => Err(RequestError::from_err(err, StatusCode::BAD_REQUEST)),
+        };
+    }
+
+    let mut private_ip_ref = state
+        .free_private_network_ips
+        .iter_mut()
+        .filter(|r| **r)
+        .choose(&mut thread_rng())
+        .ok_or(RequestError::new(
+            "No more space in the network",
+            StatusCode::SERVICE_UNAVAILABLE,
+        ))?;
+    // mark it as used, even though it's not final
+    *private_ip_ref = false;
+    Ok(*private_ip_ref.key())
+}
+
+/// Record the incoming registration request and determine the difficulty of the proof of work
+/// challenge the client has to solve, if the gateway is currently under load.
+fn registration_pow_difficulty(state: &WireguardAppStateInner) -> u8 {
+    let threshold = state.config.borrow().pow_rate_threshold;
+    state
+        .registration_difficulty
+        .record_request(Instant::now(), threshold)
+}
+
+async fn process_init_message(
+    init_message: InitMessage,
+    private_ip: IpAddr,
+    pow_difficulty: u8,
+    state: &WireguardAppStateInner,
+) -> PendingRegistration {
+    let pub_key = init_message.pub_key();
+    let pending = PendingRegistration {
+        nonce: fastrand::u64(..),
+        private_ip,
+        pow_challenge: (pow_difficulty > 0)
+            .then(|| PowChallenge::new(pow_difficulty, fastrand::u64(..))),
+        credential: init_message.credential,
+    };
     state
         .registration_in_progress
-        .insert(init_message.pub_key(), nonce);
-    nonce
+        .insert(pub_key, pending.clone());
+    pending
 }
 
 /// Perform wireguard client registration.
@@ -68,6 +416,12 @@ async fn process_init_message(init_message: InitMessage, state: &WireguardAppSta
     responses(
         (status = 501, body = ErrorResponse, description = "the endpoint hasn't been implemented yet"),
         (status = 400, body = ErrorResponse),
+        (status = 401, body = ErrorResponse, description = "the gateway requires a valid bandwidth credential to register, rotate the key or renew the registration"),
+        (status = 403, body = ErrorResponse, description = "the client has been suspended or it has not solved the proof of work challenge issued with the nonce"),
+        (status = 404, body = ErrorResponse, description = "the client rotating its key or renewing its registration is not registered, or its registration has already expired"),
+        (status = 409, body = ErrorResponse, description = "the requested private ip is already reserved by another client, or the nonce of the key rotation has already been used"),
+        (status = 422, body = ErrorResponse, description = "the registration message, or one of the public keys it contains, is malformed"),
+        (status = 503, body = ErrorResponse, description = "the gateway can't accept any more peers at the moment, retry after the duration specified by the 'Retry-After' header, or it can't verify the bandwidth credentials yet"),
         (status = 200, content(
             ("application/json" = ClientRegistrationResponse),
             ("application/yaml" = ClientRegistrationResponse)
@@ -78,7 +432,7 @@ async fn process_init_message(init_message: InitMessage, state: &WireguardAppSta
 pub(crate) async fn register_client(
     State(state): State<WireguardAppState>,
     Query(output): Query<OutputParams>,
-    Json(payload): Json<ClientMessage>,
+    payload: Result<Json<serde_json::Value>, JsonRejection>,
 ) -> Result<RegisterClientResponse, RequestError> {
     let output = output.output.unwrap_or_default();
 
@@ -86,41 +440,82 @@ pub(crate) async fn register_client(
         return Err(RequestError::new_status(StatusCode::NOT_IMPLEMENTED));
     };
 
+    let payload = match payload {
+        Ok(Json(payload)) => parse_client_message(payload)?,
+        Err(rejection) => {
+            return Err(RequestError::from_registration_err(
+                WireguardTypesError::MalformedRegistrationMessage {
+                    reason: rejection.body_text(),
+                },
+                rejection.status(),
+            ))
+        }
+    };
+
     match payload {
         ClientMessage::Initial(init) => {
+            init.ensure_supported_version()
+                .map_err(|err| RequestError::from_registration_err(err, StatusCode::BAD_REQUEST))?;
+            ensure_not_suspended(&init.pub_key(), state)?;
+            let pow_difficulty = registration_pow_difficulty(state);
             let remote_public = init.pub_key().inner();
-            let nonce = process_init_message(init, state).await;
-            let mut private_ip_ref = state
-                .free_private_network_ips
-                .iter_mut()
-                .filter(|r| **r)
-                .choose(&mut thread_rng())
-                .ok_or(RequestError::new(
-                    "No more space in the network",
-                    StatusCode::SERVICE_UNAVAILABLE,
-                ))?;
-            // mark it as used, even though it's not final
-            *private_ip_ref = false;
-            let gateway_data = GatewayClient::new(
+            let mac_algorithm = MacAlgorithm::negotiate(
+                state.config.borrow().registration_mac,
+                &init.supported_macs,
+            );
+            ensure_registry_capacity(state)?;
+            ensure_client_credential(init.pub_key(), init.credential.as_ref(), state)?;
+            let private_ip = assign_private_ip(&init, state)?;
+            let pending = process_init_message(init, private_ip, pow_difficulty, state).await;
+            let gateway_data = GatewayClient::new_with_mac_algorithm(
                 state.keypair.private_key(),
                 remote_public,
-                *private_ip_ref.key(),
-                nonce,
+                private_ip,
+                pending.nonce,
+                mac_algorithm,
             );
             let response = ClientRegistrationResponse::PendingRegistration {
-                nonce,
+                nonce: pending.nonce,
                 gateway_data,
                 wg_port: state.binding_port,
+                pow_challenge: pending.pow_challenge,
             };
             Ok(output.to_response(response))
         }
         ClientMessage::Final(finalize) => {
             let result = process_final_message(finalize, state).await?;
-            if result.is_success() {
-                let response = ClientRegistrationResponse::Registered { success: true };
+            if result.status.is_success() {
+                let response = ClientRegistrationResponse::Registered {
+                    success: true,
+                    expires_at: result.expiry_timestamp(),
+                };
+                Ok(output.to_response(response))
+            } else {
+                Err(RequestError::new_status(result.status))
+            }
+        }
+        ClientMessage::RotateKey(rotation) => {
+            let result = process_rotate_key_message(rotation, state).await?;
+            if result.status.is_success() {
+                let response = ClientRegistrationResponse::KeyRotated {
+                    success: true,
+                    expires_at: result.expiry_timestamp(),
+                };
+                Ok(output.to_response(response))
+            } else {
+                Err(RequestError::new_status(result.status))
+            }
+        }
+        ClientMessage::RenewRegistration(renewal) => {
+            let result = process_renew_message(renewal, state).await?;
+            if result.status.is_success() {
+                let response = ClientRegistrationResponse::Renewed {
+                    success: true,
+                    expires_at: result.expiry_timestamp(),
+                };
                 Ok(output.to_response(response))
             } else {
-                Err(RequestError::new_status(result))
+                Err(RequestError::new_status(result.status))
             }
         }
     }
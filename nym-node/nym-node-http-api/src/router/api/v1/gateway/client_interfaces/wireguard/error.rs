@@ -5,9 +5,12 @@ use thiserror::Error;
 
 #[derive(Debug, Error)]
 pub enum WireguardError {
-    #[error("the client is currently not in the process of being registered")]
-    RegistrationNotInProgress,
+    #[error("the client is not registered")]
+    ClientNotRegistered,
 
-    #[error("the client mac failed to get verified correctly")]
-    MacVerificationFailure,
+    #[error("the provided private ip does not match the one assigned to the client")]
+    PrivateIpMismatch,
+
+    #[error("the new public key is already registered")]
+    PublicKeyAlreadyRegistered,
 }
@@ -7,12 +7,16 @@ use crate::api::v1::gateway::client_interfaces::wireguard::client_registry::{
 use crate::error::NymNodeHttpError;
 use axum::routing::{get, post};
 use axum::Router;
-use ipnetwork::IpNetwork;
 use nym_crypto::asymmetric::x25519::KeyPair;
 use nym_node_requests::routes::api::v1::gateway::client_interfaces::wireguard;
-use nym_wireguard_types::registration::PrivateIPs;
-use nym_wireguard_types::registration::{GatewayClientRegistry, PendingRegistrations};
-use nym_wireguard_types::WireguardGatewayData;
+use nym_wireguard_types::registration::{
+    GatewayClientRegistry, IpReservations, PendingRegistrations, PrivateIPs, SeenRotationNonces,
+    SuspendedPeers,
+};
+use nym_wireguard_types::{
+    ConfigReceiver, PeerEventSender, RegistrationDifficulty, SharedCredentialVerifier,
+    WireguardGatewayData,
+};
 use std::sync::Arc;
 
 pub(crate) mod client_registry;
@@ -30,17 +34,21 @@ impl WireguardAppState {
         wireguard_gateway_data: &WireguardGatewayData,
         registration_in_progress: Arc<PendingRegistrations>,
         binding_port: u16,
-        private_ip_network: IpNetwork,
     ) -> Result<Self, NymNodeHttpError> {
         Ok(WireguardAppState {
             inner: Some(WireguardAppStateInner {
                 keypair: wireguard_gateway_data.keypair().clone(),
                 client_registry: wireguard_gateway_data.client_registry().clone(),
+                ip_reservations: wireguard_gateway_data.ip_reservations().clone(),
+                suspended_peers: wireguard_gateway_data.suspended_peers().clone(),
+                peer_events: wireguard_gateway_data.peer_event_sender().clone(),
+                credential_verifier: wireguard_gateway_data.credential_verifier().clone(),
                 registration_in_progress,
+                registration_difficulty: Default::default(),
+                seen_rotation_nonces: Default::default(),
+                config: wireguard_gateway_data.subscribe_config(),
                 binding_port,
-                free_private_network_ips: Arc::new(
-                    private_ip_network.iter().map(|ip| (ip, true)).collect(),
-                ),
+                free_private_network_ips: wireguard_gateway_data.free_private_ips().clone(),
             }),
         })
     }
@@ -83,7 +91,14 @@ macro_rules! get_state {
 pub(crate) struct WireguardAppStateInner {
     keypair: Arc<KeyPair>,
     client_registry: Arc<GatewayClientRegistry>,
+    ip_reservations: Arc<IpReservations>,
+    suspended_peers: Arc<SuspendedPeers>,
+    peer_events: PeerEventSender,
+    credential_verifier: SharedCredentialVerifier,
     registration_in_progress: Arc<PendingRegistrations>,
+    registration_difficulty: Arc<RegistrationDifficulty>,
+    seen_rotation_nonces: Arc<SeenRotationNonces>,
+    config: ConfigReceiver,
     binding_port: u16,
     free_private_network_ips: Arc<PrivateIPs>,
 }
@@ -106,19 +121,25 @@ mod test {
     use axum::body::Body;
     use axum::http::Request;
     use axum::http::StatusCode;
+    use axum::response::Response;
     use base64::{engine::general_purpose, Engine as _};
     use dashmap::DashMap;
     use hmac::Mac;
     use ipnetwork::IpNetwork;
     use nym_crypto::asymmetric::encryption;
     use nym_node_requests::api::v1::gateway::client_interfaces::wireguard::models::{
-        ClientMac, ClientMessage, ClientRegistrationResponse, GatewayClient, InitMessage,
-        PeerPublicKey,
+        ClientMac, ClientMessage, ClientRegistrationResponse, FinalMessage, GatewayClient,
+        InitMessage, PeerPublicKey,
     };
     use nym_node_requests::routes::api::v1::gateway::client_interfaces::wireguard;
-    use nym_wireguard_types::registration::HmacSha256;
+    use nym_wireguard_types::registration::{HmacSha256, PendingRegistrations};
+    use nym_wireguard_types::{
+        BandwidthCredential, Config, ConfigSender, CredentialVerifier, MacAlgorithm, PeerEvent,
+        RenewRegistrationMessage, WireguardGatewayData,
+    };
     use std::net::IpAddr;
     use std::str::FromStr;
+    use std::sync::atomic::{AtomicUsize, Ordering};
     use std::sync::Arc;
     use tower::Service;
     use tower::ServiceExt;
@@ -169,7 +190,8 @@ mod test {
         let client_dh = client_static_private.diffie_hellman(&gateway_static_public);
 
         let registration_in_progress = Arc::new(DashMap::new());
-        let client_registry = Arc::new(DashMap::new());
+        let client_registry = Arc::new(GatewayClientRegistry::default());
+        let (peer_events, mut peer_events_receiver) = tokio::sync::broadcast::channel(16);
         let free_private_network_ips = Arc::new(
             IpNetwork::from_str("10.1.0.0/24")
                 .unwrap()
@@ -177,13 +199,29 @@ mod test {
                 .map(|ip| (ip, true))
                 .collect(),
         );
-        let client_private_ip = IpAddr::from_str("10.1.0.42").unwrap();
-
         let state = WireguardAppState {
             inner: Some(WireguardAppStateInner {
                 client_registry: Arc::clone(&client_registry),
+                ip_reservations: Arc::new(DashMap::new()),
+                suspended_peers: Arc::new(Default::default()),
+                peer_events,
+                credential_verifier: Default::default(),
                 keypair: Arc::new(gateway_key_pair),
                 registration_in_progress: Arc::clone(&registration_in_progress),
+                registration_difficulty: Default::default(),
+                seen_rotation_nonces: Default::default(),
+                config: ConfigSender::new(Config {
+                    bind_address: "0.0.0.0:8080".parse().unwrap(),
+                    private_ip: "10.1.0.1".parse().unwrap(),
+                    announced_port: 8080,
+                    private_network_prefix: 24,
+                    registration_mac: Default::default(),
+                    max_registered_peers: None,
+                    pow_rate_threshold: None,
+                    registration_ttl: None,
+                    require_bandwidth_credential: false,
+                })
+                .subscribe(),
                 binding_port: 8080,
                 free_private_network_ips,
             }),
@@ -193,9 +231,8 @@ mod test {
         // call it like any tower service, no need to run an HTTP server.
         let mut app = routes(state);
 
-        let init_message = ClientMessage::Initial(InitMessage {
-            pub_key: PeerPublicKey::new(client_static_public),
-        });
+        let init_message =
+            ClientMessage::Initial(InitMessage::new(PeerPublicKey::new(client_static_public)));
 
         let init_request = Request::builder()
             .method("POST")
@@ -218,6 +255,7 @@ mod test {
             nonce,
             gateway_data,
             wg_port: 8080,
+            pow_challenge: None,
         } = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap())
             .unwrap()
         else {
@@ -226,6 +264,7 @@ mod test {
         assert!(gateway_data
             .verify(client_key_pair.private_key(), nonce)
             .is_ok());
+        let client_private_ip = gateway_data.private_ip;
 
         let mut mac = HmacSha256::new_from_slice(client_dh.as_bytes()).unwrap();
         mac.update(client_static_public.as_bytes());
@@ -233,11 +272,12 @@ mod test {
         mac.update(&nonce.to_le_bytes());
         let mac = mac.finalize().into_bytes();
 
-        let finalized_message = ClientMessage::Final(GatewayClient {
+        let finalized_message = ClientMessage::Final(FinalMessage::new(GatewayClient {
             pub_key: PeerPublicKey::new(client_static_public),
             private_ip: client_private_ip,
             mac: ClientMac::new(mac.as_slice().to_vec()),
-        });
+            mac_algorithm: Default::default(),
+        }));
 
         let final_request = Request::builder()
             .method("POST")
@@ -255,6 +295,13 @@ mod test {
 
         assert_eq!(response.status(), StatusCode::OK);
         assert!(!client_registry.is_empty());
+        assert_eq!(
+            peer_events_receiver.try_recv().unwrap(),
+            PeerEvent::PeerRegistered {
+                pub_key: PeerPublicKey::new(client_static_public),
+                private_ip: client_private_ip,
+            }
+        );
 
         let clients_request = Request::builder()
             .method("GET")
@@ -285,4 +332,252 @@ mod test {
             clients
         )
     }
+
+    #[derive(Default)]
+    struct AcceptNonEmpty {
+        spent: AtomicUsize,
+    }
+
+    #[async_trait::async_trait]
+    impl CredentialVerifier for AcceptNonEmpty {
+        async fn verify_credential(
+            &self,
+            client: PeerPublicKey,
+            credential: &BandwidthCredential,
+        ) -> Result<(), nym_wireguard_types::Error> {
+            if credential.is_empty() {
+                return Err(nym_wireguard_types::Error::InvalidBandwidthCredential {
+                    client: client.to_string(),
+                    reason: "empty credential".to_string(),
+                });
+            }
+            self.spent.fetch_add(1, Ordering::Relaxed);
+            Ok(())
+        }
+    }
+
+    fn credential_gated_data(
+        verifier: Option<Arc<dyn CredentialVerifier>>,
+    ) -> WireguardGatewayData {
+        let mut rng = rand::thread_rng();
+        let config = Config {
+            bind_address: "0.0.0.0:8080".parse().unwrap(),
+            private_ip: "10.1.0.1".parse().unwrap(),
+            announced_port: 8080,
+            private_network_prefix: 24,
+            registration_mac: Default::default(),
+            max_registered_peers: None,
+            pow_rate_threshold: None,
+            registration_ttl: None,
+            require_bandwidth_credential: true,
+        };
+        let gateway_data =
+            WireguardGatewayData::new(config, Arc::new(encryption::KeyPair::new(&mut rng)));
+        if let Some(verifier) = verifier {
+            gateway_data.set_credential_verifier(verifier);
+        }
+        gateway_data
+    }
+
+    fn app_state(
+        gateway_data: &WireguardGatewayData,
+    ) -> (WireguardAppState, Arc<PendingRegistrations>) {
+        let registration_in_progress = Arc::new(DashMap::new());
+        let state =
+            WireguardAppState::new(gateway_data, Arc::clone(&registration_in_progress), 8080)
+                .unwrap();
+        (state, registration_in_progress)
+    }
+
+    async fn post(state: WireguardAppState, message: ClientMessage) -> Response {
+        let mut app = routes(state);
+        let request = Request::builder()
+            .method("POST")
+            .uri(wireguard::CLIENT)
+            .header("Content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&message).unwrap()))
+            .unwrap();
+
+        ServiceExt::<Request<Body>>::ready(&mut app)
+            .await
+            .unwrap()
+            .call(request)
+            .await
+            .unwrap()
+    }
+
+    async fn send(state: WireguardAppState, message: ClientMessage) -> StatusCode {
+        post(state, message).await.status()
+    }
+
+    async fn initialise(state: WireguardAppState, message: InitMessage) -> (u64, GatewayClient) {
+        let response = post(state, ClientMessage::Initial(message)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let ClientRegistrationResponse::PendingRegistration {
+            nonce,
+            gateway_data,
+            ..
+        } = serde_json::from_slice(&to_bytes(response.into_body(), usize::MAX).await.unwrap())
+            .unwrap()
+        else {
+            panic!("invalid response")
+        };
+        (nonce, gateway_data)
+    }
+
+    fn final_message(
+        client_key_pair: &encryption::KeyPair,
+        gateway_data: &WireguardGatewayData,
+        nonce: u64,
+        assigned: &GatewayClient,
+        private_ip: IpAddr,
+    ) -> ClientMessage {
+        ClientMessage::Final(FinalMessage::new(GatewayClient::new_with_mac_algorithm(
+            client_key_pair.private_key(),
+            PublicKey::from(gateway_data.keypair().public_key().to_bytes()),
+            private_ip,
+            nonce,
+            assigned.mac_algorithm,
+        )))
+    }
+
+    fn client_key() -> PeerPublicKey {
+        let client_key_pair = encryption::KeyPair::new(&mut rand::thread_rng());
+        PeerPublicKey::new(PublicKey::from(client_key_pair.public_key().to_bytes()))
+    }
+
+    #[tokio::test]
+    async fn registration_without_credential_is_rejected() {
+        let gateway_data = credential_gated_data(Some(Arc::new(AcceptNonEmpty::default())));
+        let (state, registration_in_progress) = app_state(&gateway_data);
+
+        let missing = ClientMessage::Initial(InitMessage::new(client_key()));
+        assert_eq!(send(state.clone(), missing).await, StatusCode::UNAUTHORIZED);
+        assert!(registration_in_progress.is_empty());
+
+        // the credential is only verified once the registration is about to be finalised
+        let client_key_pair = encryption::KeyPair::new(&mut rand::thread_rng());
+        let pub_key = PeerPublicKey::new(PublicKey::from(client_key_pair.public_key().to_bytes()));
+        let (nonce, assigned) = initialise(
+            state.clone(),
+            InitMessage::new(pub_key).with_credential(BandwidthCredential::new(vec![])),
+        )
+        .await;
+        let rejected = final_message(
+            &client_key_pair,
+            &gateway_data,
+            nonce,
+            &assigned,
+            assigned.private_ip,
+        );
+        assert_eq!(send(state, rejected).await, StatusCode::UNAUTHORIZED);
+        assert!(gateway_data.client_registry().is_empty());
+    }
+
+    #[tokio::test]
+    async fn registration_cannot_be_finalised_with_a_different_ip() {
+        let verifier = Arc::new(AcceptNonEmpty::default());
+        let gateway_data = credential_gated_data(Some(verifier.clone()));
+        let (state, registration_in_progress) = app_state(&gateway_data);
+
+        let client_key_pair = encryption::KeyPair::new(&mut rand::thread_rng());
+        let pub_key = PeerPublicKey::new(PublicKey::from(client_key_pair.public_key().to_bytes()));
+
+        let (nonce, assigned) = initialise(
+            state.clone(),
+            InitMessage::new(pub_key).with_credential(BandwidthCredential::new(vec![1, 2, 3])),
+        )
+        .await;
+        assert_eq!(verifier.spent.load(Ordering::Relaxed), 0);
+
+        let finalise = |private_ip| {
+            final_message(
+                &client_key_pair,
+                &gateway_data,
+                nonce,
+                &assigned,
+                private_ip,
+            )
+        };
+
+        // the mac is valid, but the ip might have been set aside for somebody else
+        let other_ip = IpNetwork::from_str("10.1.0.0/24")
+            .unwrap()
+            .iter()
+            .find(|ip| *ip != assigned.private_ip)
+            .unwrap();
+        assert_eq!(
+            send(state.clone(), finalise(other_ip)).await,
+            StatusCode::BAD_REQUEST
+        );
+        assert!(gateway_data.client_registry().is_empty());
+        assert!(registration_in_progress.contains_key(&pub_key));
+        // the failed attempt didn't cost the client its credential
+        assert_eq!(verifier.spent.load(Ordering::Relaxed), 0);
+
+        assert_eq!(
+            send(state, finalise(assigned.private_ip)).await,
+            StatusCode::OK
+        );
+        assert_eq!(verifier.spent.load(Ordering::Relaxed), 1);
+        assert_eq!(
+            gateway_data
+                .client_registry()
+                .get(&pub_key)
+                .map(|client| client.private_ip),
+            Some(assigned.private_ip)
+        );
+    }
+
+    #[tokio::test]
+    async fn registration_is_rejected_until_the_verifier_is_installed() {
+        let gateway_data = credential_gated_data(None);
+        let (state, registration_in_progress) = app_state(&gateway_data);
+
+        let init_message = ClientMessage::Initial(
+            InitMessage::new(client_key()).with_credential(BandwidthCredential::new(vec![1, 2, 3])),
+        );
+        assert_eq!(
+            send(state, init_message).await,
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert!(registration_in_progress.is_empty());
+    }
+
+    #[tokio::test]
+    async fn renewal_without_credential_is_rejected() {
+        let gateway_data = credential_gated_data(Some(Arc::new(AcceptNonEmpty::default())));
+        let (state, _) = app_state(&gateway_data);
+
+        let client_key_pair = encryption::KeyPair::new(&mut rand::thread_rng());
+        let pub_key = PeerPublicKey::new(PublicKey::from(client_key_pair.public_key().to_bytes()));
+        let private_ip: IpAddr = "10.1.0.42".parse().unwrap();
+        gateway_data.client_registry().insert(
+            pub_key,
+            GatewayClient {
+                pub_key,
+                private_ip,
+                mac: ClientMac::new(vec![]),
+                mac_algorithm: Default::default(),
+            },
+        );
+
+        let gateway_public = PublicKey::from(gateway_data.keypair().public_key().to_bytes());
+        let renewal = || {
+            RenewRegistrationMessage::new(
+                client_key_pair.private_key(),
+                gateway_public,
+                private_ip,
+                MacAlgorithm::default(),
+            )
+        };
+
+        let missing = ClientMessage::RenewRegistration(renewal());
+        assert_eq!(send(state.clone(), missing).await, StatusCode::UNAUTHORIZED);
+
+        let accepted = ClientMessage::RenewRegistration(
+            renewal().with_credential(BandwidthCredential::new(vec![1, 2, 3])),
+        );
+        assert_eq!(send(state, accepted).await, StatusCode::OK);
+    }
 }
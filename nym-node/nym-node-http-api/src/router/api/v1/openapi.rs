@@ -67,6 +67,11 @@ use utoipa_swagger_ui::SwaggerUi;
             api_requests::v1::gateway::client_interfaces::wireguard::models::ClientMessage,
             api_requests::v1::gateway::client_interfaces::wireguard::models::InitMessage,
             api_requests::v1::gateway::client_interfaces::wireguard::models::GatewayClient,
+            api_requests::v1::gateway::client_interfaces::wireguard::models::FinalMessage,
+            api_requests::v1::gateway::client_interfaces::wireguard::models::PowChallenge,
+            api_requests::v1::gateway::client_interfaces::wireguard::models::RegistrationErrorKind,
+            api_requests::v1::gateway::client_interfaces::wireguard::models::KeyRotationMessage,
+            api_requests::v1::gateway::client_interfaces::wireguard::models::RenewRegistrationMessage,
             api_requests::v1::gateway::client_interfaces::wireguard::models::ClientRegistrationResponse,
             api_requests::v1::mixnode::models::Mixnode,
             api_requests::v1::network_requester::models::NetworkRequester,
@@ -1,10 +1,11 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use axum::http::StatusCode;
+use axum::http::{header, StatusCode};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 pub use nym_node_requests::api::ErrorResponse;
+use std::time::Duration;
 use utoipa::ToResponse;
 
 #[derive(Debug, Clone, ToResponse)]
@@ -13,6 +14,8 @@ pub(crate) struct RequestError {
     pub(crate) inner: ErrorResponse,
 
     pub(crate) status: StatusCode,
+
+    pub(crate) retry_after: Option<Duration>,
 }
 
 impl RequestError {
@@ -20,8 +23,10 @@ impl RequestError {
         RequestError {
             inner: ErrorResponse {
                 message: message.into(),
+                registration_error: None,
             },
             status,
+            retry_after: None,
         }
     }
 
@@ -29,18 +34,47 @@ impl RequestError {
         RequestError {
             inner: ErrorResponse {
                 message: String::new(),
+                registration_error: None,
             },
             status,
+            retry_after: None,
         }
     }
 
+    /// Attach the `Retry-After` header to the response.
+    #[must_use]
+    pub(crate) fn with_retry_after(mut self, retry_after: Duration) -> Self {
+        self.retry_after = Some(retry_after);
+        self
+    }
+
     pub(crate) fn from_err<E: std::error::Error>(err: E, status: StatusCode) -> Self {
         Self::new(err.to_string(), status)
     }
+
+    /// Create the error response for a failed wireguard registration,
+    /// alongside its machine-readable kind, if applicable, so that the client could act on it.
+    pub(crate) fn from_registration_err(
+        err: nym_wireguard_types::Error,
+        status: StatusCode,
+    ) -> Self {
+        let registration_error = err.registration_error_kind();
+        let mut request_error = Self::from_err(err, status);
+        request_error.inner.registration_error = registration_error;
+        request_error
+    }
 }
 
 impl IntoResponse for RequestError {
     fn into_response(self) -> Response {
-        (self.status, Json(self.inner)).into_response()
+        match self.retry_after {
+            Some(retry_after) => (
+                self.status,
+                [(header::RETRY_AFTER, retry_after.as_secs().to_string())],
+                Json(self.inner),
+            )
+                .into_response(),
+            None => (self.status, Json(self.inner)).into_response(),
+        }
     }
 }
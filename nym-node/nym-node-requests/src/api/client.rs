@@ -9,11 +9,14 @@ use async_trait::async_trait;
 use nym_bin_common::build_information::BinaryBuildInformationOwned;
 use nym_http_api_client::{ApiClient, HttpClientError};
 use nym_wireguard_types::{ClientMessage, ClientRegistrationResponse};
+#[cfg(not(target_arch = "wasm32"))]
+use nym_wireguard_types::{RegistrationTransport, TransportError};
 
 use crate::api::v1::health::models::NodeHealth;
 use crate::api::v1::ip_packet_router::models::IpPacketRouter;
 use crate::api::v1::network_requester::exit_policy::models::UsedExitPolicy;
 use crate::api::v1::network_requester::models::NetworkRequester;
+use crate::error::Error;
 pub use nym_http_api_client::Client;
 
 pub type NymNodeApiClientError = HttpClientError<ErrorResponse>;
@@ -80,3 +83,38 @@ pub trait NymNodeApiClientExt: ApiClient {
 #[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
 #[cfg_attr(not(target_arch = "wasm32"), async_trait)]
 impl NymNodeApiClientExt for Client {}
+
+/// Registration transport going through the wireguard endpoint of the gateway's http api.
+/// It's meant to be used as a fallback for clients that can't reach the gateway over the primary
+/// channel. The gateway is authenticated by its TLS certificate, so only https urls are accepted,
+/// whereas the client authenticates itself with the mac included in the registration messages.
+#[derive(Debug, Clone)]
+pub struct HttpsRegistrationTransport {
+    client: Client,
+}
+
+impl HttpsRegistrationTransport {
+    pub fn new(client: Client) -> Result<Self, Error> {
+        if client.current_url().scheme() != "https" {
+            return Err(Error::InsecureRegistrationEndpoint {
+                url: client.current_url().to_string(),
+            });
+        }
+        Ok(HttpsRegistrationTransport { client })
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+#[async_trait]
+impl RegistrationTransport for HttpsRegistrationTransport {
+    fn name(&self) -> &'static str {
+        "https"
+    }
+
+    async fn exchange(
+        &self,
+        message: &ClientMessage,
+    ) -> Result<ClientRegistrationResponse, TransportError> {
+        Ok(self.client.post_gateway_register_client(message).await?)
+    }
+}
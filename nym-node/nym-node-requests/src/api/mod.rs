@@ -4,6 +4,7 @@
 use crate::api::v1::node::models::{HostInformation, LegacyHostInformation};
 use crate::error::Error;
 use nym_crypto::asymmetric::identity;
+use nym_wireguard_types::RegistrationErrorKind;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use std::fmt::{Display, Formatter};
@@ -86,6 +87,11 @@ impl<T> Deref for SignedData<T> {
 #[cfg_attr(feature = "openapi", derive(utoipa::ToSchema))]
 pub struct ErrorResponse {
     pub message: String,
+
+    /// Machine-readable kind of the failure of the wireguard registration, if applicable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[schemars(skip)]
+    pub registration_error: Option<RegistrationErrorKind>,
 }
 
 impl Display for ErrorResponse {
@@ -2,6 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 
 pub use nym_wireguard_types::{
-    ClientMac, ClientMessage, ClientRegistrationResponse, GatewayClient, InitMessage, Nonce,
-    PeerPublicKey,
+    BandwidthCredential, ClientMac, ClientMessage, ClientRegistrationResponse, FinalMessage,
+    GatewayClient, InitMessage, KeyRotationMessage, Nonce, PeerPublicKey, PowChallenge,
+    PowSolution, RegistrationErrorKind, RenewRegistrationMessage,
 };
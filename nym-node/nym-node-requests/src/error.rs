@@ -11,6 +11,9 @@ pub enum Error {
         source: serde_json::Error,
     },
 
+    #[error("wireguard registration endpoint at '{url}' does not use https")]
+    InsecureRegistrationEndpoint { url: String },
+
     #[error(transparent)]
     WireguardError {
         #[from]
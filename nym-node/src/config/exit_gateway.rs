@@ -175,6 +175,7 @@ pub fn ephemeral_exit_gateway_config(
                 upstream_exit_policy_url: Some(
                     config.exit_gateway.upstream_exit_policy_url.clone(),
                 ),
+                ip_pool: Default::default(),
             },
             storage_paths: nym_network_requester::config::NetworkRequesterPaths {
                 common_paths: config
@@ -251,6 +252,13 @@ pub fn ephemeral_exit_gateway_config(
             private_ip: config.wireguard.private_ip,
             announced_port: config.wireguard.announced_port,
             private_network_prefix: config.wireguard.private_network_prefix,
+            registration_mac: config.wireguard.registration_mac,
+            max_registered_peers: config.wireguard.max_registered_peers,
+            pow_rate_threshold: config.wireguard.pow_rate_threshold,
+            registration_ttl: config.wireguard.registration_ttl,
+            allow_unauthenticated_registrations: config
+                .wireguard
+                .allow_unauthenticated_registrations,
             storage_paths: config.wireguard.storage_paths.clone(),
         },
         custom_mixnet_path: None,
@@ -16,6 +16,7 @@ use nym_config::{
     must_get_home, parse_urls, read_config_from_toml_file, save_formatted_config_to_file,
     NymConfigTemplate, DEFAULT_CONFIG_DIR, DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_wireguard_types::MacAlgorithm;
 use serde::{Deserialize, Serialize};
 use std::env;
 use std::fmt::{Display, Formatter};
@@ -512,6 +513,34 @@ pub struct Wireguard {
     /// The maximum value for IPv4 is 32 and for IPv6 is 128
     pub private_network_prefix: u8,
 
+    /// Mac algorithm preferred for authenticating client registrations, if supported by the registering client.
+    /// default: `hmac_sha256`
+    #[serde(default)]
+    pub registration_mac: MacAlgorithm,
+
+    /// Maximum number of peers that can be registered with the gateway at the same time.
+    /// Set to 0 to only limit it by the size of the private network.
+    /// default: `0`
+    #[serde(default)]
+    pub max_registered_peers: usize,
+
+    /// Number of registration requests per minute beyond which clients have to solve a proof of work challenge.
+    /// Set to 0 to never issue the challenges.
+    /// default: `0`
+    #[serde(default)]
+    pub pow_rate_threshold: u32,
+
+    /// Duration for which the client registrations remain valid unless renewed, after which the peers are removed.
+    /// Set to 0 for the registrations to never expire.
+    /// default: `0s`
+    #[serde(default, with = "humantime_serde")]
+    pub registration_ttl: Duration,
+
+    /// Allow clients to register without presenting a valid bandwidth credential.
+    /// default: `false`
+    #[serde(default)]
+    pub allow_unauthenticated_registrations: bool,
+
     /// Paths for wireguard keys, client registries, etc.
     pub storage_paths: persistence::WireguardPaths,
 }
@@ -527,6 +556,11 @@ impl Wireguard {
             private_ip: DEFAULT_WIREGUARD_IP,
             announced_port: DEFAULT_WIREGUARD_PORT,
             private_network_prefix: DEFAULT_WIREGUARD_PREFIX,
+            registration_mac: Default::default(),
+            max_registered_peers: 0,
+            pow_rate_threshold: 0,
+            registration_ttl: Duration::ZERO,
+            allow_unauthenticated_registrations: false,
             storage_paths: persistence::WireguardPaths::new(data_dir),
         }
     }
@@ -539,6 +573,12 @@ impl From<Wireguard> for nym_wireguard_types::Config {
             private_ip: value.private_ip,
             announced_port: value.announced_port,
             private_network_prefix: value.private_network_prefix,
+            registration_mac: value.registration_mac,
+            max_registered_peers: (value.max_registered_peers != 0)
+                .then_some(value.max_registered_peers),
+            pow_rate_threshold: (value.pow_rate_threshold != 0).then_some(value.pow_rate_threshold),
+            registration_ttl: (!value.registration_ttl.is_zero()).then_some(value.registration_ttl),
+            require_bandwidth_credential: !value.allow_unauthenticated_registrations,
         }
     }
 }
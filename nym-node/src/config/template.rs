@@ -127,6 +127,25 @@ announced_port = {{ wireguard.announced_port }}
 # The maximum value for IPv4 is 32 and for IPv6 is 128
 private_network_prefix = {{ wireguard.private_network_prefix }}
 
+# Mac algorithm preferred for authenticating client registrations, if supported by the registering client.
+# Possible values: 'hmac_sha256', 'hmac_sha512' or 'blake3_keyed'
+registration_mac = '{{ wireguard.registration_mac }}'
+
+# Maximum number of peers that can be registered with the gateway at the same time.
+# Set to 0 to only limit it by the size of the private network.
+max_registered_peers = {{ wireguard.max_registered_peers }}
+
+# Number of registration requests per minute beyond which clients have to solve a proof of work challenge.
+# Set to 0 to never issue the challenges.
+pow_rate_threshold = {{ wireguard.pow_rate_threshold }}
+
+# Duration for which the client registrations remain valid unless renewed, after which the peers are removed.
+# Set to '0s' for the registrations to never expire.
+registration_ttl = '{{ wireguard.registration_ttl }}'
+
+# Allow clients to register without presenting a valid bandwidth credential.
+allow_unauthenticated_registrations = {{ wireguard.allow_unauthenticated_registrations }}
+
 [wireguard.storage_paths]
 # Path to file containing wireguard x25519 diffie hellman private key.
 private_diffie_hellman_key_file = '{{ wireguard.storage_paths.private_diffie_hellman_key_file }}'
@@ -122,6 +122,11 @@ async fn try_upgrade_config<P: AsRef<Path>>(path: P) -> Result<(), NymNodeError>
         private_ip: old_cfg.wireguard.private_network_ip,
         announced_port: old_cfg.wireguard.announced_port,
         private_network_prefix: old_cfg.wireguard.private_network_prefix,
+        registration_mac: Default::default(),
+        max_registered_peers: 0,
+        pow_rate_threshold: 0,
+        registration_ttl: Default::default(),
+        allow_unauthenticated_registrations: false,
         storage_paths: WireguardPaths::new(Config::default_data_directory(path)?),
     };
     initialise(&wireguard).map_err(|err| KeyIOFailure::KeyPairStoreFailure {
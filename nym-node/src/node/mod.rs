@@ -358,6 +358,12 @@ impl NymNode {
 
     pub(crate) async fn new(config: Config) -> Result<Self, NymNodeError> {
         let wireguard_data = WireguardData::new(&config.wireguard)?;
+
+        // the private ips assigned to the peers are taken from that network
+        IpNetwork::new(
+            config.wireguard.private_ip,
+            config.wireguard.private_network_prefix,
+        )?;
         let wireguard_gateway_data = WireguardGatewayData::new(
             config.wireguard.clone().into(),
             wireguard_data.x25519_wireguard_keys.clone(),
@@ -460,10 +466,8 @@ impl NymNode {
 
         let config =
             ephemeral_entry_gateway_config(self.config.clone(), &self.entry_gateway.mnemonic)?;
-        let wireguard_data = Arc::new(WireguardGatewayData::new(
-            self.config.wireguard.clone().into(),
-            self.wireguard.x25519_wireguard_keys.clone(),
-        ));
+        // share the state with the http api, so that the registrations it handles reach the interface
+        let wireguard_data = Arc::new(self.entry_gateway.wireguard_data.clone());
         let mut entry_gateway = Gateway::new_loaded(
             config,
             None,
@@ -489,10 +493,8 @@ impl NymNode {
 
         let config =
             ephemeral_exit_gateway_config(self.config.clone(), &self.entry_gateway.mnemonic)?;
-        let wireguard_data = Arc::new(WireguardGatewayData::new(
-            self.config.wireguard.clone().into(),
-            self.wireguard.x25519_wireguard_keys.clone(),
-        ));
+        // share the state with the http api, so that the registrations it handles reach the interface
+        let wireguard_data = Arc::new(self.entry_gateway.wireguard_data.clone());
 
         let mut exit_gateway = Gateway::new_loaded(
             config.gateway,
@@ -579,16 +581,10 @@ impl NymNode {
                 policy: None,
             };
 
-        let wireguard_private_network = IpNetwork::new(
-            self.config.wireguard.private_ip,
-            self.config.wireguard.private_network_prefix,
-        )?;
-
         let wg_state = WireguardAppState::new(
             &self.entry_gateway.wireguard_data,
             Default::default(),
             self.config.wireguard.bind_address.port(),
-            wireguard_private_network,
         )?;
 
         let mut config = nym_node_http_api::Config::new(bin_info_owned!(), host_details)
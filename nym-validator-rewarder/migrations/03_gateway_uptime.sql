@@ -0,0 +1,24 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+CREATE TABLE epoch_gateway_uptime
+(
+    rewarding_epoch_id INTEGER NOT NULL PRIMARY KEY REFERENCES rewarding_epoch (id),
+    num_gateways       INTEGER NOT NULL,
+    total_uptime       TEXT    NOT NULL,
+    budget             TEXT    NOT NULL
+);
+
+CREATE TABLE gateway_uptime_reward
+(
+    rewarding_epoch_id INTEGER NOT NULL REFERENCES rewarding_epoch (id),
+    gateway_identity   TEXT    NOT NULL,
+    operator_account   TEXT    NOT NULL,
+    amount             TEXT    NOT NULL,
+    uptime             TEXT    NOT NULL,
+    uptime_share       TEXT    NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, gateway_identity)
+);
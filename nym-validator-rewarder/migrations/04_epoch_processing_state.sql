@@ -0,0 +1,27 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- write-ahead record of the rewarding progress, so that an epoch that got interrupted midway
+-- could be resumed without paying it twice or skipping it altogether
+CREATE TABLE epoch_processing_state
+(
+    rewarding_epoch_id INTEGER                     NOT NULL PRIMARY KEY,
+    start_time         TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    end_time           TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+
+    -- one of 'computed', 'broadcast' or 'confirmed'
+    phase              TEXT                        NOT NULL,
+
+    -- json-encoded rewarding amounts that are (or were) going to be sent out
+    rewarding_amounts  TEXT                        NOT NULL,
+    budget             TEXT                        NOT NULL,
+    total_spent        TEXT                        NOT NULL,
+
+    -- chain height just before the rewards got broadcast, used for looking up the transaction on resumption
+    starting_height    INTEGER                     NOT NULL,
+    rewarding_tx       TEXT,
+
+    last_updated       TIMESTAMP WITHOUT TIME ZONE NOT NULL
+);
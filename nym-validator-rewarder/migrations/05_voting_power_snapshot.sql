@@ -0,0 +1,21 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- voting power of every known validator as observed at the start of given rewarding epoch
+CREATE TABLE voting_power_snapshot
+(
+    rewarding_epoch_id          INTEGER NOT NULL,
+    validator_consensus_address TEXT    NOT NULL,
+    whitelisted                 BOOLEAN NOT NULL,
+
+    -- height of the block from which the voting power has been obtained
+    height                      INTEGER NOT NULL,
+    voting_power                BIGINT  NOT NULL,
+    voting_power_share          TEXT    NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, validator_consensus_address)
+);
+
+CREATE INDEX voting_power_snapshot_validator ON voting_power_snapshot (validator_consensus_address);
@@ -0,0 +1,20 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- every started instance of the rewarder alongside the information on how it got stopped
+CREATE TABLE rewarder_run
+(
+    id                      INTEGER                     NOT NULL PRIMARY KEY AUTOINCREMENT,
+    started_at              TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+    stopped_at              TIMESTAMP WITHOUT TIME ZONE,
+
+    -- set once the rewarder has finished processing any due epochs and shut down in an orderly fashion
+    clean_shutdown          BOOLEAN                     NOT NULL DEFAULT FALSE,
+
+    -- the epoch that was still in progress at the time of the shutdown
+    in_progress_epoch_id    INTEGER,
+    in_progress_epoch_start TIMESTAMP WITHOUT TIME ZONE,
+    in_progress_epoch_end   TIMESTAMP WITHOUT TIME ZONE
+);
@@ -0,0 +1,8 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- address that has actually received the block signing rewards, if different from the operator account
+ALTER TABLE block_signing_reward
+    ADD COLUMN payout_account TEXT;
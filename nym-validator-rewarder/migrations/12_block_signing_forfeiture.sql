@@ -0,0 +1,16 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- whether the validator has signed fewer than the minimum ratio of blocks and thus forfeited its reward
+ALTER TABLE block_signing_reward
+    ADD COLUMN forfeited BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- the reward the validator would have received had it not forfeited it
+ALTER TABLE block_signing_reward
+    ADD COLUMN forfeited_amount TEXT;
+
+-- part of the `amount` that got redistributed from the validators that forfeited their rewards
+ALTER TABLE block_signing_reward
+    ADD COLUMN redistributed_amount TEXT;
@@ -0,0 +1,28 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- results of periodically probing the api endpoints of the credential issuers
+CREATE TABLE issuer_api_probe
+(
+    id               INTEGER                     NOT NULL PRIMARY KEY AUTOINCREMENT,
+    operator_account TEXT                        NOT NULL,
+    api_endpoint     TEXT                        NOT NULL,
+    probed_at        TIMESTAMP WITHOUT TIME ZONE NOT NULL,
+
+    -- whether the api has responded in time and the issuer's key share is available
+    available        BOOLEAN                     NOT NULL,
+
+    -- time it took the api to respond, if it did
+    latency_ms       INTEGER,
+
+    -- whether the tls certificate of the endpoint is valid. NULL for plain http endpoints
+    tls_valid        BOOLEAN,
+
+    -- whether the verification key share of the issuer has been verified in the DKG contract
+    key_available    BOOLEAN                     NOT NULL,
+    error            TEXT
+);
+
+CREATE INDEX issuer_api_probe_probed_at ON issuer_api_probe (probed_at);
@@ -0,0 +1,171 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- equivalent of all the sqlite migrations, using postgres types
+
+CREATE TABLE rewarding_epoch
+(
+    id              BIGINT      NOT NULL PRIMARY KEY,
+    start_time      TIMESTAMPTZ NOT NULL,
+    end_time        TIMESTAMPTZ NOT NULL,
+    budget          TEXT        NOT NULL,
+    spent           TEXT        NOT NULL,
+    rewarding_tx    TEXT,
+    rewarding_error TEXT
+);
+
+CREATE TABLE epoch_block_signing
+(
+    rewarding_epoch_id                BIGINT NOT NULL PRIMARY KEY REFERENCES rewarding_epoch (id),
+    total_voting_power_at_epoch_start BIGINT NOT NULL,
+    num_blocks                        BIGINT NOT NULL,
+    budget                            TEXT   NOT NULL
+);
+
+CREATE TABLE block_signing_reward
+(
+    rewarding_epoch_id          BIGINT  NOT NULL REFERENCES rewarding_epoch (id),
+    validator_consensus_address TEXT    NOT NULL,
+    operator_account            TEXT    NOT NULL,
+    whitelisted                 BOOLEAN NOT NULL,
+    amount                      TEXT    NOT NULL,
+    voting_power                BIGINT  NOT NULL,
+    voting_power_share          TEXT    NOT NULL,
+    signed_blocks               INTEGER NOT NULL,
+    signed_blocks_percent       TEXT    NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, operator_account)
+);
+
+CREATE TABLE epoch_credential_issuance
+(
+    rewarding_epoch_id               BIGINT NOT NULL PRIMARY KEY REFERENCES rewarding_epoch (id),
+    starting_dkg_epoch               BIGINT NOT NULL,
+    ending_dkg_epoch                 BIGINT NOT NULL,
+    total_issued_partial_credentials BIGINT NOT NULL,
+    budget                           TEXT   NOT NULL
+);
+
+CREATE TABLE credential_issuance_reward
+(
+    rewarding_epoch_id           BIGINT  NOT NULL REFERENCES rewarding_epoch (id),
+    operator_account             TEXT    NOT NULL,
+    amount                       TEXT    NOT NULL,
+    whitelisted                  BOOLEAN NOT NULL,
+    api_endpoint                 TEXT    NOT NULL,
+    issued_partial_credentials   BIGINT  NOT NULL,
+    issued_credentials_share     TEXT    NOT NULL,
+    validated_issued_credentials BIGINT  NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, operator_account)
+);
+
+CREATE TABLE validated_deposit
+(
+    operator_identity_bs58 TEXT   NOT NULL,
+    credential_id          BIGINT NOT NULL,
+    deposit_tx             TEXT   NOT NULL,
+
+    signed_plaintext       BYTEA  NOT NULL,
+    signature_bs58         TEXT   NOT NULL
+);
+
+CREATE INDEX validated_deposit_lookup ON validated_deposit (operator_identity_bs58, deposit_tx);
+
+-- evidence of attempting to re-use the same deposit tx for multiple credentials
+CREATE TABLE double_signing_evidence
+(
+    operator_identity_bs58 TEXT   NOT NULL,
+    credential_id          BIGINT NOT NULL,
+    original_credential_id BIGINT NOT NULL,
+    deposit_tx             TEXT   NOT NULL,
+
+    signed_plaintext       BYTEA  NOT NULL,
+    signature_bs58         TEXT   NOT NULL
+);
+
+-- evidence of foul play
+CREATE TABLE issuance_evidence
+(
+    operator_account       TEXT   NOT NULL,
+    operator_identity_bs58 TEXT   NOT NULL,
+
+    credential_id          BIGINT NOT NULL,
+    signed_plaintext       BYTEA  NOT NULL,
+    signature_bs58         TEXT   NOT NULL,
+
+    failure_message        TEXT   NOT NULL
+);
+
+-- does not necessarily imply foul play, but something has gone wrong
+CREATE TABLE issuance_validation_failure
+(
+    id                     BIGSERIAL NOT NULL PRIMARY KEY,
+
+    operator_account       TEXT      NOT NULL,
+    operator_identity_bs58 TEXT      NOT NULL,
+
+    failure_message        TEXT      NOT NULL
+);
+
+CREATE TABLE epoch_gateway_uptime
+(
+    rewarding_epoch_id BIGINT NOT NULL PRIMARY KEY REFERENCES rewarding_epoch (id),
+    num_gateways       BIGINT NOT NULL,
+    total_uptime       TEXT   NOT NULL,
+    budget             TEXT   NOT NULL
+);
+
+CREATE TABLE gateway_uptime_reward
+(
+    rewarding_epoch_id BIGINT NOT NULL REFERENCES rewarding_epoch (id),
+    gateway_identity   TEXT   NOT NULL,
+    operator_account   TEXT   NOT NULL,
+    amount             TEXT   NOT NULL,
+    uptime             TEXT   NOT NULL,
+    uptime_share       TEXT   NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, gateway_identity)
+);
+
+-- write-ahead record of the rewarding progress, so that an epoch that got interrupted midway
+-- could be resumed without paying it twice or skipping it altogether
+CREATE TABLE epoch_processing_state
+(
+    rewarding_epoch_id BIGINT      NOT NULL PRIMARY KEY,
+    start_time         TIMESTAMPTZ NOT NULL,
+    end_time           TIMESTAMPTZ NOT NULL,
+
+    -- one of 'computed', 'broadcast' or 'confirmed'
+    phase              TEXT        NOT NULL,
+
+    -- json-encoded rewarding amounts that are (or were) going to be sent out
+    rewarding_amounts  TEXT        NOT NULL,
+    budget             TEXT        NOT NULL,
+    total_spent        TEXT        NOT NULL,
+
+    -- chain height just before the rewards got broadcast, used for looking up the transaction on resumption
+    starting_height    BIGINT      NOT NULL,
+    rewarding_tx       TEXT,
+
+    last_updated       TIMESTAMPTZ NOT NULL
+);
+
+-- voting power of every known validator as observed at the start of given rewarding epoch
+CREATE TABLE voting_power_snapshot
+(
+    rewarding_epoch_id          BIGINT  NOT NULL,
+    validator_consensus_address TEXT    NOT NULL,
+    whitelisted                 BOOLEAN NOT NULL,
+
+    -- height of the block from which the voting power has been obtained
+    height                      BIGINT  NOT NULL,
+    voting_power                BIGINT  NOT NULL,
+    voting_power_share          TEXT    NOT NULL,
+
+    UNIQUE (rewarding_epoch_id, validator_consensus_address)
+);
+
+CREATE INDEX voting_power_snapshot_validator ON voting_power_snapshot (validator_consensus_address);
@@ -0,0 +1,20 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- every started instance of the rewarder alongside the information on how it got stopped
+CREATE TABLE rewarder_run
+(
+    id                      BIGSERIAL   NOT NULL PRIMARY KEY,
+    started_at              TIMESTAMPTZ NOT NULL,
+    stopped_at              TIMESTAMPTZ,
+
+    -- set once the rewarder has finished processing any due epochs and shut down in an orderly fashion
+    clean_shutdown          BOOLEAN     NOT NULL DEFAULT FALSE,
+
+    -- the epoch that was still in progress at the time of the shutdown
+    in_progress_epoch_id    BIGINT,
+    in_progress_epoch_start TIMESTAMPTZ,
+    in_progress_epoch_end   TIMESTAMPTZ
+);
@@ -0,0 +1,20 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- aggregated rewards of every account whose per-epoch details have been pruned from the database
+CREATE TABLE pruned_reward_summary
+(
+    operator_account TEXT   NOT NULL,
+
+    -- one of 'block_signing', 'credential_issuance' or 'gateway_uptime'
+    module           TEXT   NOT NULL,
+
+    first_epoch      BIGINT NOT NULL,
+    last_epoch       BIGINT NOT NULL,
+    rewarded_epochs  BIGINT NOT NULL,
+    total_amount     TEXT   NOT NULL,
+
+    UNIQUE (operator_account, module)
+);
@@ -0,0 +1,28 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- issuers caught misbehaving by the credential issuance monitor.
+-- as long as any of their violations has not been cleared, they're not going to receive any issuance rewards
+CREATE TABLE credential_issuance_violation
+(
+    id                     BIGSERIAL   NOT NULL PRIMARY KEY,
+    operator_account       TEXT        NOT NULL,
+    operator_identity_bs58 TEXT        NOT NULL,
+    dkg_epoch              BIGINT      NOT NULL,
+
+    -- id of the offending credential, if the violation concerns a specific one
+    credential_id          BIGINT,
+
+    -- one of 'invalid_signature', 'deposit_reuse', 'non_deposit_transaction', 'inconsistent_deposit_value',
+    -- 'inconsistent_deposit_info', 'malformed_commitment', 'invalid_partial_credential' or 'incomplete_response'
+    reason                 TEXT        NOT NULL,
+    details                TEXT        NOT NULL,
+    detected_at            TIMESTAMPTZ NOT NULL,
+
+    cleared_at             TIMESTAMPTZ,
+    clearing_note          TEXT
+);
+
+CREATE INDEX credential_issuance_violation_operator ON credential_issuance_violation (operator_account);
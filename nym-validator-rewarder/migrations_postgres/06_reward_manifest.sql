@@ -0,0 +1,20 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- signed manifests describing all rewards computed in the given epoch
+CREATE TABLE reward_manifest
+(
+    epoch_id   BIGINT      NOT NULL PRIMARY KEY,
+
+    -- base58-encoded ed25519 identity key of the rewarder
+    signer     TEXT        NOT NULL,
+
+    -- base58-encoded signature on the manifest
+    signature  TEXT        NOT NULL,
+
+    -- the exact, canonical, JSON encoding of the manifest that got signed
+    manifest   TEXT        NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL
+);
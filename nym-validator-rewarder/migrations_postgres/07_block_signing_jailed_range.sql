@@ -0,0 +1,16 @@
+/*
+ * Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+ * SPDX-License-Identifier: GPL-3.0-only
+ */
+
+-- ranges of blocks, [start_height, end_height), during which the validator was jailed
+-- and which got excluded from its expected blocks
+CREATE TABLE block_signing_jailed_range
+(
+    rewarding_epoch_id          BIGINT NOT NULL REFERENCES rewarding_epoch (id),
+    validator_consensus_address TEXT   NOT NULL,
+    start_height                BIGINT NOT NULL,
+    end_height                  BIGINT NOT NULL
+);
+
+CREATE INDEX block_signing_jailed_range_epoch ON block_signing_jailed_range (rewarding_epoch_id);
@@ -0,0 +1,56 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::pruning::{prune_rewarding_history, PruningOutcome};
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Directory into which the archive with the pruned records is going to be written.
+    #[clap(long)]
+    output_directory: PathBuf,
+
+    /// Number of the most recent epochs whose details should be kept in the database.
+    /// If not specified, the value from the `[retention]` section of the config is used.
+    #[clap(long)]
+    keep_epochs: Option<u32>,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct ArchiveResult(Option<PruningOutcome>);
+
+impl Display for ArchiveResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.0 {
+            Some(outcome) => outcome.fmt(f),
+            None => write!(f, "there were no records old enough to get archived"),
+        }
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let keep_epochs = args.keep_epochs.unwrap_or(config.retention.detailed_epochs);
+    let outcome =
+        prune_rewarding_history(&storage, keep_epochs, Some(args.output_directory.as_path()))
+            .await?;
+
+    args.output.to_stdout(&ArchiveResult(outcome));
+    Ok(())
+}
@@ -0,0 +1,25 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use clap::Subcommand;
+
+pub mod show;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: ConfigCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ConfigCommands {
+    /// Show the configuration of the validator rewarder with all secrets redacted.
+    Show(show::Args),
+}
+
+pub(crate) fn execute(args: Args) -> Result<(), NymRewarderError> {
+    match args.command {
+        ConfigCommands::Show(args) => show::execute(args),
+    }
+}
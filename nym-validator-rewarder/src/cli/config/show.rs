@@ -0,0 +1,114 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::{try_load_config_file, try_load_resolved_config, ConfigOverridableArgs};
+use crate::config::Config;
+use crate::env::vars::NYM_REWARDER_CONFIG_PATH_ARG;
+use crate::error::NymRewarderError;
+use nym_bin_common::output_format::OutputFormat;
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Show the final configuration after applying the environment and command line overrides on top of the file.
+    /// The resolved configuration is also validated.
+    #[clap(long)]
+    resolved: bool,
+
+    #[command(flatten)]
+    config_override: ConfigOverridableArgs,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long, env = NYM_REWARDER_CONFIG_PATH_ARG)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct RedactedConfig(Value);
+
+impl RedactedConfig {
+    fn new(config: &Config) -> Result<Self, NymRewarderError> {
+        let mut value = serde_json::to_value(config)
+            .map_err(|source| NymRewarderError::ConfigSerializationFailure { source })?;
+
+        redact(value.get_mut("mnemonic"));
+        if let Some(storage) = value.get_mut("storage") {
+            if storage["postgres_url"]
+                .as_str()
+                .is_some_and(|url| !url.is_empty())
+            {
+                redact(storage.get_mut("postgres_url"))
+            }
+        }
+        if value["admin"]["token"]
+            .as_str()
+            .is_some_and(|token| !token.is_empty())
+        {
+            redact(value["admin"].get_mut("token"))
+        }
+        if let Some(webhooks) = value["notifications"]["webhooks"].as_array_mut() {
+            for webhook in webhooks {
+                if !webhook["secret"].is_null() {
+                    redact(webhook.get_mut("secret"))
+                }
+            }
+        }
+
+        Ok(RedactedConfig(value))
+    }
+}
+
+fn redact(value: Option<&mut Value>) {
+    if let Some(value) = value {
+        *value = Value::String(REDACTED.to_string())
+    }
+}
+
+fn write_flattened(f: &mut Formatter<'_>, prefix: &str, value: &Value) -> std::fmt::Result {
+    match value {
+        Value::Object(map) => {
+            for (key, inner) in map {
+                let key = if prefix.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{prefix}.{key}")
+                };
+                write_flattened(f, &key, inner)?;
+            }
+            Ok(())
+        }
+        Value::Array(values) if values.iter().any(|v| v.is_object()) => {
+            for (i, inner) in values.iter().enumerate() {
+                write_flattened(f, &format!("{prefix}[{i}]"), inner)?;
+            }
+            Ok(())
+        }
+        other => writeln!(f, "{prefix} = {other}"),
+    }
+}
+
+impl Display for RedactedConfig {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write_flattened(f, "", &self.0)
+    }
+}
+
+pub(crate) fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = if args.resolved {
+        try_load_resolved_config(&args.custom_config_path, args.config_override)?
+    } else {
+        try_load_config_file(&args.custom_config_path)?
+    };
+
+    args.output.to_stdout(&RedactedConfig::new(&config)?);
+    Ok(())
+}
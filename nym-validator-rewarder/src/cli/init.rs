@@ -17,10 +17,6 @@ pub struct Args {
     #[clap(long)]
     custom_config_path: Option<PathBuf>,
 
-    /// Mnemonic used for rewarding  operations
-    #[clap(long)]
-    mnemonic: bip39::Mnemonic,
-
     /// Overwrite existing configuration file.
     #[clap(long, short)]
     force: bool,
@@ -31,7 +27,7 @@ fn init_paths() -> io::Result<()> {
     fs::create_dir_all(default_config_directory())
 }
 
-pub(crate) fn execute(args: Args) -> Result<(), NymRewarderError> {
+pub(crate) fn execute(mut args: Args) -> Result<(), NymRewarderError> {
     let path = args
         .custom_config_path
         .clone()
@@ -49,12 +45,19 @@ pub(crate) fn execute(args: Args) -> Result<(), NymRewarderError> {
         return Err(NymRewarderError::UnavailableWebsocketUrl);
     };
 
-    let config = Config::new(args.mnemonic, websocket, nyxd).with_override(args.config_override);
+    let Some(mnemonic) = args.config_override.mnemonic.take() else {
+        return Err(NymRewarderError::MissingMnemonic);
+    };
+
+    let config = Config::new(mnemonic, websocket, nyxd).with_override(args.config_override);
     config.validate()?;
 
     config
         .save_to_path(&path)
         .map_err(|source| NymRewarderError::ConfigSaveFailure { path, source })?;
 
+    // make sure to not override the existing keys, as they're used for verifying previously published manifests
+    config.storage_paths.load_or_generate_identity()?;
+
     Ok(())
 }
@@ -0,0 +1,235 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::storage::models::{BlockSigningRewardRecord, RewardingEpochSummary};
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+use time::format_description::well_known::Rfc3339;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Id of the rewarding epoch to inspect.
+    epoch_id: i64,
+
+    /// Print the details as json. Shorthand for `--output json`.
+    #[clap(long)]
+    json: bool,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default(), conflicts_with = "json")]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct EpochInspection {
+    epoch_id: i64,
+    start_time: String,
+    end_time: String,
+    budget: String,
+    spent: String,
+    rewarding_tx: Option<String>,
+    rewarding_error: Option<String>,
+    num_blocks: Option<i64>,
+    total_voting_power: Option<i64>,
+    block_signing: Vec<BlockSigningRewardRecord>,
+}
+
+impl EpochInspection {
+    fn new(summary: RewardingEpochSummary, block_signing: Vec<BlockSigningRewardRecord>) -> Self {
+        // safety: unwrap here is fine as we're using a predefined formatter
+        #[allow(clippy::unwrap_used)]
+        let (start_time, end_time) = (
+            summary.start_time.format(&Rfc3339).unwrap(),
+            summary.end_time.format(&Rfc3339).unwrap(),
+        );
+
+        EpochInspection {
+            epoch_id: summary.id,
+            start_time,
+            end_time,
+            budget: summary.budget,
+            spent: summary.spent,
+            rewarding_tx: summary.rewarding_tx,
+            rewarding_error: summary.rewarding_error,
+            num_blocks: summary.num_blocks,
+            total_voting_power: summary.total_voting_power,
+            block_signing,
+        }
+    }
+}
+
+fn optional<T: Display>(value: &Option<T>) -> String {
+    value
+        .as_ref()
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+impl Display for EpochInspection {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "epoch {}: {} - {}",
+            self.epoch_id, self.start_time, self.end_time
+        )?;
+        writeln!(f, "budget: {}, spent: {}", self.budget, self.spent)?;
+        writeln!(f, "rewarding tx: {}", optional(&self.rewarding_tx))?;
+        if let Some(error) = &self.rewarding_error {
+            writeln!(f, "rewarding error: {error}")?;
+        }
+        writeln!(
+            f,
+            "blocks: {}, total voting power: {}",
+            optional(&self.num_blocks),
+            optional(&self.total_voting_power)
+        )?;
+
+        if self.block_signing.is_empty() {
+            return write!(f, "\nno block signing rewards found");
+        }
+
+        let header = [
+            "VALIDATOR",
+            "OPERATOR",
+            "WHITELISTED",
+            "SIGNED BLOCKS",
+            "VOTING POWER SHARE",
+            "PAID",
+        ];
+        let rows = self
+            .block_signing
+            .iter()
+            .map(|r| {
+                [
+                    r.validator_consensus_address.clone(),
+                    r.payout_account
+                        .clone()
+                        .unwrap_or_else(|| r.operator_account.clone()),
+                    r.whitelisted.to_string(),
+                    format!("{} ({})", r.signed_blocks, r.signed_blocks_percent),
+                    r.voting_power_share.clone(),
+                    r.amount.clone(),
+                ]
+            })
+            .collect::<Vec<_>>();
+
+        let mut widths = header.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len())
+            }
+        }
+
+        writeln!(f)?;
+        for (column, (name, width)) in header.iter().zip(widths).enumerate() {
+            let separator = if column + 1 == widths.len() {
+                "\n"
+            } else {
+                "  "
+            };
+            write!(f, "{name:<width$}{separator}")?;
+        }
+        for (i, row) in rows.iter().enumerate() {
+            for (column, (cell, width)) in row.iter().zip(widths).enumerate() {
+                if column + 1 == widths.len() {
+                    write!(f, "{cell}")?;
+                } else {
+                    write!(f, "{cell:<width$}  ")?;
+                }
+            }
+            if i + 1 != rows.len() {
+                writeln!(f)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let summary = storage
+        .get_rewarding_epoch_summary(args.epoch_id)
+        .await?
+        .ok_or(NymRewarderError::RewardingEpochNotFound {
+            epoch_id: args.epoch_id,
+        })?;
+    let block_signing = storage
+        .get_epoch_block_signing_rewards(args.epoch_id)
+        .await?;
+
+    let output = if args.json {
+        OutputFormat::Json
+    } else {
+        args.output
+    };
+    output.to_stdout(&EpochInspection::new(summary, block_signing));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use time::OffsetDateTime;
+
+    fn record(address: &str, amount: &str) -> BlockSigningRewardRecord {
+        BlockSigningRewardRecord {
+            rewarding_epoch_id: 42,
+            validator_consensus_address: address.to_string(),
+            operator_account: format!("n1{address}"),
+            payout_account: None,
+            whitelisted: true,
+            amount: amount.to_string(),
+            voting_power: 100,
+            voting_power_share: "0.5".to_string(),
+            signed_blocks: 700,
+            signed_blocks_percent: "0.97".to_string(),
+            forfeited: false,
+            forfeited_amount: None,
+            redistributed_amount: None,
+        }
+    }
+
+    #[test]
+    fn renders_aligned_table() {
+        let summary = RewardingEpochSummary {
+            id: 42,
+            start_time: OffsetDateTime::from_unix_timestamp(1704103200).unwrap(),
+            end_time: OffsetDateTime::from_unix_timestamp(1704106800).unwrap(),
+            budget: "1000unym".to_string(),
+            spent: "900unym".to_string(),
+            rewarding_tx: None,
+            rewarding_error: None,
+            num_blocks: Some(720),
+            total_voting_power: Some(200),
+        };
+        let inspection = EpochInspection::new(
+            summary,
+            vec![
+                record("short", "450unym"),
+                record("a-much-longer-address", "450unym"),
+            ],
+        );
+
+        let rendered = inspection.to_string();
+        assert!(rendered.starts_with("epoch 42: 2024-01-01T10:00:00Z - 2024-01-01T11:00:00Z"));
+
+        let table = rendered.lines().skip(5).collect::<Vec<_>>();
+        assert_eq!(table.len(), 3);
+        let operator_column = table[0].find("OPERATOR").unwrap();
+        assert_eq!(table[1].find("n1short"), Some(operator_column));
+        assert_eq!(
+            table[2].find("n1a-much-longer-address"),
+            Some(operator_column)
+        );
+    }
+}
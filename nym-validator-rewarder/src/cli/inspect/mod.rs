@@ -0,0 +1,30 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use clap::Subcommand;
+
+pub mod epoch;
+pub mod voting_power;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: InspectCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum InspectCommands {
+    /// Show the per-validator block signing statistics and rewards of the specified epoch.
+    Epoch(epoch::Args),
+
+    /// Show the voting power snapshots taken at the start of the rewarding epochs.
+    VotingPower(voting_power::Args),
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    match args.command {
+        InspectCommands::Epoch(args) => epoch::execute(args).await,
+        InspectCommands::VotingPower(args) => voting_power::execute(args).await,
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::storage::models::VotingPowerSnapshot;
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Id of the rewarding epoch for which the snapshot should be shown.
+    #[clap(long, required_unless_present = "validator")]
+    epoch: Option<i64>,
+
+    /// Consensus address of the validator whose voting power should be shown.
+    /// If no epoch is specified, its snapshots from all epochs are returned.
+    #[clap(long)]
+    validator: Option<String>,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct VotingPowerSnapshots(Vec<VotingPowerSnapshot>);
+
+impl Display for VotingPowerSnapshots {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "no voting power snapshots found");
+        }
+        for snapshot in &self.0 {
+            writeln!(f, "{snapshot}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let snapshots = match (args.epoch, &args.validator) {
+        (Some(epoch), validator) => storage
+            .get_epoch_voting_power_snapshot(epoch)
+            .await?
+            .into_iter()
+            .filter(|s| {
+                validator
+                    .as_ref()
+                    .map(|v| &s.validator_consensus_address == v)
+                    .unwrap_or(true)
+            })
+            .collect(),
+        (None, Some(validator)) => {
+            storage
+                .get_validator_voting_power_history(validator)
+                .await?
+        }
+        // clap ensures at least one of the arguments is present
+        (None, None) => Vec::new(),
+    };
+
+    args.output.to_stdout(&VotingPowerSnapshots(snapshots));
+    Ok(())
+}
@@ -0,0 +1,31 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use clap::Subcommand;
+
+pub mod show;
+pub mod verify;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: ManifestCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ManifestCommands {
+    /// Show the signed reward manifest of the specified epoch.
+    Show(show::Args),
+
+    /// Verify a published reward manifest against its signature and the on-chain payouts.
+    /// It does not require the rewarder to be initialised.
+    Verify(verify::Args),
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    match args.command {
+        ManifestCommands::Show(args) => show::execute(args).await,
+        ManifestCommands::Verify(args) => verify::execute(args).await,
+    }
+}
@@ -0,0 +1,36 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Id of the rewarding epoch whose manifest should be shown.
+    #[clap(long)]
+    epoch: i64,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let manifest = storage.get_reward_manifest(args.epoch).await?.ok_or(
+        NymRewarderError::ManifestNotFound {
+            epoch_id: args.epoch,
+        },
+    )?;
+
+    args.output.to_stdout(&manifest);
+    Ok(())
+}
@@ -0,0 +1,79 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use crate::rewarder::manifest::SignedRewardManifest;
+use nym_bin_common::output_format::OutputFormat;
+use nym_crypto::asymmetric::ed25519;
+use nym_network_defaults::NymNetworkDetails;
+use nym_validator_client::nyxd::{tx, CosmWasmClient, Hash, Msg, MsgSend};
+use nym_validator_client::{nyxd, QueryHttpRpcNyxdClient};
+use std::path::PathBuf;
+use url::Url;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Path to the published, signed, reward manifest.
+    #[clap(long)]
+    manifest: PathBuf,
+
+    /// Expected base58-encoded identity key of the rewarder that has signed the manifest.
+    #[clap(long)]
+    signer: Option<ed25519::PublicKey>,
+
+    /// Nyxd endpoint used for retrieving the rewarding transaction.
+    /// If not specified, the endpoint of the network set in the environment is used.
+    #[clap(long)]
+    nyxd_url: Option<Url>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+async fn rewarding_transfers(
+    nyxd_url: Option<Url>,
+    tx_hash: Hash,
+) -> Result<Vec<MsgSend>, NymRewarderError> {
+    let network = NymNetworkDetails::new_from_env();
+    let nyxd_url = nyxd_url.unwrap_or(network.endpoints[0].nyxd_url());
+    let client_config = nyxd::Config::try_from_nym_network_details(&network)?;
+    let client = QueryHttpRpcNyxdClient::connect(client_config, nyxd_url.as_str())?;
+
+    let res = client.get_tx(tx_hash).await?;
+    if res.tx_result.code.is_err() {
+        return Err(NymRewarderError::MalformedRewardingTransaction { tx_hash });
+    }
+    let decoded = tx::Tx::from_bytes(&res.tx)
+        .map_err(|_| NymRewarderError::MalformedRewardingTransaction { tx_hash })?;
+
+    Ok(decoded
+        .body
+        .messages
+        .iter()
+        .filter_map(|msg| MsgSend::from_any(msg).ok())
+        .collect())
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let manifest = SignedRewardManifest::load(&args.manifest)?;
+    manifest.verify_signature(args.signer.as_ref())?;
+
+    let epoch_id = manifest.manifest.epoch_id;
+    let Some(raw_tx_hash) = &manifest.manifest.rewarding_tx else {
+        return Err(NymRewarderError::ManifestWithoutRewardingTx { epoch_id });
+    };
+    let tx_hash = raw_tx_hash
+        .parse()
+        .map_err(|_| NymRewarderError::MalformedManifestTxHash {
+            tx_hash: raw_tx_hash.clone(),
+        })?;
+
+    let transfers = rewarding_transfers(args.nyxd_url, tx_hash).await?;
+    let verification = manifest.compare_payouts(&transfers)?;
+
+    args.output.to_stdout(&verification);
+    if !verification.is_valid() {
+        return Err(NymRewarderError::ManifestPayoutsMismatch { epoch_id });
+    }
+    Ok(())
+}
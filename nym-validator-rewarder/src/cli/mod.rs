@@ -1,7 +1,8 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::config::Config;
+use crate::config::{Config, StorageBackend};
+use crate::env::vars::*;
 use crate::error::NymRewarderError;
 use clap::{Parser, Subcommand};
 use nym_bin_common::bin_info;
@@ -11,10 +12,15 @@ use std::sync::OnceLock;
 use tracing::{debug, error};
 use url::Url;
 
+pub mod archive;
 pub mod build_info;
+pub mod config;
 pub mod init;
+pub mod inspect;
+pub mod manifest;
 pub mod run;
 pub mod upgrade_helpers;
+pub mod violations;
 
 // Helper for passing LONG_VERSION to clap
 fn pretty_build_info_static() -> &'static str {
@@ -42,51 +48,78 @@ impl Cli {
         match self.command {
             Commands::Init(args) => init::execute(args),
             Commands::Run(args) => run::execute(args).await,
+            Commands::Inspect(args) => inspect::execute(args).await,
+            Commands::Config(args) => config::execute(args),
+            Commands::Archive(args) => archive::execute(args).await,
+            Commands::Violations(args) => violations::execute(args).await,
+            Commands::Manifest(args) => manifest::execute(args).await,
             Commands::BuildInfo(args) => build_info::execute(args),
         }
     }
 }
 
+/// Values that take precedence over the ones defined in the configuration file.
+/// Each of them can be provided either as a command line argument or via its associated environment variable,
+/// with the command line argument taking precedence over the environment.
 #[derive(Debug, clap::Args)]
 pub struct ConfigOverridableArgs {
-    #[clap(long)]
+    /// Mnemonic of the nyx account distributing the rewards.
+    #[clap(long, env = NYM_REWARDER_MNEMONIC_ARG, hide_env_values = true)]
+    pub mnemonic: Option<bip39::Mnemonic>,
+
+    #[clap(long, env = NYM_REWARDER_DISABLE_BLOCK_SIGNING_REWARDING_ARG)]
     pub disable_block_signing_rewarding: bool,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_BLOCK_SIGNING_MONITORING_ONLY_ARG)]
     pub block_signing_monitoring_only: bool,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_DISABLE_CREDENTIAL_ISSUANCE_REWARDING_ARG)]
     pub disable_credential_issuance_rewarding: bool,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CREDENTIAL_MONITOR_RUN_INTERVAL_ARG)]
     pub credential_monitor_run_interval: Option<humantime::Duration>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CREDENTIAL_MONITOR_MIN_VALIDATION_ARG)]
     pub credential_monitor_min_validation: Option<usize>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CREDENTIAL_MONITOR_SAMPLING_RATE_ARG)]
     pub credential_monitor_sampling_rate: Option<f64>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_SCRAPER_ENDPOINT_ARG)]
     pub scraper_endpoint: Option<Url>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_NYXD_ENDPOINT_ARG)]
     pub nyxd_endpoint: Option<Url>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_EPOCH_BUDGET_ARG)]
     pub epoch_budget: Option<Coin>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_EPOCH_DURATION_ARG)]
     pub epoch_duration: Option<humantime::Duration>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_BLOCK_SIGNING_REWARD_RATIO_ARG)]
     pub block_signing_reward_ratio: Option<f64>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CREDENTIAL_ISSUANCE_REWARD_RATIO_ARG)]
     pub credential_issuance_reward_ratio: Option<f64>,
 
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CREDENTIAL_VERIFICATION_REWARD_RATIO_ARG)]
     pub credential_verification_reward_ratio: Option<f64>,
+
+    #[clap(long, env = NYM_REWARDER_GATEWAY_UPTIME_REWARD_RATIO_ARG)]
+    pub gateway_uptime_reward_ratio: Option<f64>,
+
+    /// Database backend used for storing the rewarding history.
+    #[clap(long, value_enum, env = NYM_REWARDER_STORAGE_BACKEND_ARG)]
+    pub storage_backend: Option<StorageBackend>,
+
+    /// Connection url of the PostgreSQL database used by the `postgres` storage backend.
+    #[clap(long, env = NYM_REWARDER_POSTGRES_URL_ARG, hide_env_values = true)]
+    pub postgres_url: Option<String>,
+
+    /// Token required for accessing the admin api.
+    #[clap(long, env = NYM_REWARDER_ADMIN_TOKEN_ARG, hide_env_values = true)]
+    pub admin_token: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -97,11 +130,28 @@ pub(crate) enum Commands {
     /// Run the validator rewarder with the preconfigured settings.
     Run(run::Args),
 
+    /// Inspect the historical data stored by the validator rewarder.
+    Inspect(inspect::Args),
+
+    /// Inspect the configuration of the validator rewarder.
+    Config(config::Args),
+
+    /// Export the per-account rewarding details of old epochs into a compressed archive
+    /// and remove them from the database.
+    Archive(archive::Args),
+
+    /// Review and clear the credential issuance violations of the credential issuers.
+    Violations(violations::Args),
+
+    /// Show and verify the signed manifests describing the rewards computed in every epoch.
+    Manifest(manifest::Args),
+
     /// Show build information of this binary
     BuildInfo(build_info::Args),
 }
 
-fn try_load_current_config(custom_path: &Option<PathBuf>) -> Result<Config, NymRewarderError> {
+/// Attempts to load the configuration file, without applying any overrides nor performing any validation.
+fn try_load_config_file(custom_path: &Option<PathBuf>) -> Result<Config, NymRewarderError> {
     let config_path = custom_path.clone().unwrap_or(Config::default_location());
 
     debug!(
@@ -110,7 +160,6 @@ fn try_load_current_config(custom_path: &Option<PathBuf>) -> Result<Config, NymR
     );
 
     if let Ok(cfg) = Config::read_from_toml_file(&config_path) {
-        cfg.validate()?;
         return Ok(cfg);
     }
 
@@ -122,6 +171,23 @@ fn try_load_current_config(custom_path: &Option<PathBuf>) -> Result<Config, NymR
         );
         err
     })?;
+    Ok(config)
+}
+
+fn try_load_current_config(custom_path: &Option<PathBuf>) -> Result<Config, NymRewarderError> {
+    let config = try_load_config_file(custom_path)?;
+    config.validate()?;
+    Ok(config)
+}
+
+/// Loads the configuration by layering, in order of increasing precedence, the values from the config file,
+/// the environment and the command line arguments. Validation is only performed on the final result,
+/// so that an invalid value in the file can be corrected by any of the later layers.
+fn try_load_resolved_config(
+    custom_path: &Option<PathBuf>,
+    overrides: ConfigOverridableArgs,
+) -> Result<Config, NymRewarderError> {
+    let config = try_load_config_file(custom_path)?.with_override(overrides);
     config.validate()?;
     Ok(config)
 }
@@ -1,7 +1,8 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::cli::{try_load_current_config, ConfigOverridableArgs};
+use crate::cli::{try_load_resolved_config, ConfigOverridableArgs};
+use crate::env::vars::NYM_REWARDER_CONFIG_PATH_ARG;
 use crate::error::NymRewarderError;
 use crate::rewarder::Rewarder;
 use std::path::PathBuf;
@@ -12,13 +13,12 @@ pub struct Args {
     config_override: ConfigOverridableArgs,
 
     /// Specifies custom location for the configuration file of nym validators rewarder.
-    #[clap(long)]
+    #[clap(long, env = NYM_REWARDER_CONFIG_PATH_ARG)]
     custom_config_path: Option<PathBuf>,
 }
 
 pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
-    let config =
-        try_load_current_config(&args.custom_config_path)?.with_override(args.config_override);
+    let config = try_load_resolved_config(&args.custom_config_path, args.config_override)?;
 
     Rewarder::new(config).await?.run().await
 }
@@ -0,0 +1,68 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use nym_validator_client::nyxd::AccountId;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+#[clap(group(clap::ArgGroup::new("target").required(true).args(&["id", "operator"])))]
+pub struct Args {
+    /// Id of the violation that should be cleared.
+    #[clap(long)]
+    id: Option<i64>,
+
+    /// Account of the credential issuer whose all active violations should be cleared.
+    #[clap(long)]
+    operator: Option<AccountId>,
+
+    /// Optional note explaining why the violation has been cleared.
+    #[clap(long)]
+    note: Option<String>,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+struct ClearedViolations {
+    cleared: u64,
+}
+
+impl Display for ClearedViolations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "cleared {} credential issuance violation(s)",
+            self.cleared
+        )
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let cleared = match (args.id, &args.operator) {
+        (Some(id), _) => storage.clear_issuance_violation(id, args.note).await?,
+        (None, Some(operator)) => {
+            storage
+                .clear_operator_issuance_violations(operator, args.note)
+                .await?
+        }
+        // clap ensures exactly one of the arguments is present
+        (None, None) => 0,
+    };
+
+    args.output.to_stdout(&ClearedViolations { cleared });
+    Ok(())
+}
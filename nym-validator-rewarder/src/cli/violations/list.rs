@@ -0,0 +1,58 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::cli::try_load_current_config;
+use crate::error::NymRewarderError;
+use crate::rewarder::credential_issuance::violation::IssuanceViolation;
+use crate::rewarder::storage::RewarderStorage;
+use nym_bin_common::output_format::OutputFormat;
+use nym_validator_client::nyxd::AccountId;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    /// Account of the credential issuer whose violations should be shown.
+    #[clap(long)]
+    operator: Option<AccountId>,
+
+    /// Also show the violations that have already been cleared.
+    #[clap(long)]
+    include_cleared: bool,
+
+    /// Specifies custom location for the configuration file of nym validators rewarder.
+    #[clap(long)]
+    custom_config_path: Option<PathBuf>,
+
+    #[clap(short, long, default_value_t = OutputFormat::default())]
+    output: OutputFormat,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct IssuanceViolations(Vec<IssuanceViolation>);
+
+impl Display for IssuanceViolations {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        if self.0.is_empty() {
+            return write!(f, "no credential issuance violations found");
+        }
+        for violation in &self.0 {
+            writeln!(f, "{violation}")?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    let config = try_load_current_config(&args.custom_config_path)?;
+    let storage = RewarderStorage::init(&config).await?;
+
+    let violations = storage
+        .get_issuance_violations(args.operator.as_ref(), args.include_cleared)
+        .await?;
+
+    args.output.to_stdout(&IssuanceViolations(violations));
+    Ok(())
+}
@@ -0,0 +1,30 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use clap::Subcommand;
+
+pub mod clear;
+pub mod list;
+
+#[derive(Debug, clap::Args)]
+pub struct Args {
+    #[clap(subcommand)]
+    command: ViolationsCommands,
+}
+
+#[derive(Subcommand, Debug)]
+pub(crate) enum ViolationsCommands {
+    /// Show the credential issuance violations detected by the issuance monitor.
+    List(list::Args),
+
+    /// Clear credential issuance violations so that the rewards of the affected issuers are no longer withheld.
+    Clear(clear::Args),
+}
+
+pub(crate) async fn execute(args: Args) -> Result<(), NymRewarderError> {
+    match args.command {
+        ViolationsCommands::List(args) => list::execute(args).await,
+        ViolationsCommands::Clear(args) => clear::execute(args).await,
+    }
+}
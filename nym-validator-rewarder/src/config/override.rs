@@ -10,6 +10,10 @@ pub trait ConfigOverride {
 
 impl ConfigOverride for ConfigOverridableArgs {
     fn override_config(self, config: &mut Config) {
+        if let Some(mnemonic) = self.mnemonic {
+            config.base.mnemonic = mnemonic
+        }
+
         if self.disable_block_signing_rewarding {
             config.block_signing.enabled = false
         }
@@ -63,5 +67,21 @@ impl ConfigOverride for ConfigOverridableArgs {
         {
             config.rewarding.ratios.credential_verification = credential_verification_reward_ratio;
         }
+
+        if let Some(gateway_uptime_reward_ratio) = self.gateway_uptime_reward_ratio {
+            config.rewarding.ratios.gateway_uptime = gateway_uptime_reward_ratio;
+        }
+
+        if let Some(storage_backend) = self.storage_backend {
+            config.storage.backend = storage_backend
+        }
+
+        if let Some(postgres_url) = self.postgres_url {
+            config.storage.postgres_url = postgres_url
+        }
+
+        if let Some(admin_token) = self.admin_token {
+            config.admin.token = admin_token
+        }
     }
 }
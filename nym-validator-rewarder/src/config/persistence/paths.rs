@@ -2,17 +2,71 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::config::default_data_directory;
+use crate::error::NymRewarderError;
+use nym_crypto::asymmetric::ed25519;
+use nym_pemstore::KeyPairPath;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 pub const DEFAULT_SCRAPER_DB_FILENAME: &str = "nyxd_blocks.sqlite";
 pub const DEFAULT_REWARD_HISTORY_DB_FILENAME: &str = "rewards.sqlite";
+pub const DEFAULT_PRIVATE_IDENTITY_KEY_FILENAME: &str = "private_identity.pem";
+pub const DEFAULT_PUBLIC_IDENTITY_KEY_FILENAME: &str = "public_identity.pem";
+
+fn default_private_identity_key_file() -> PathBuf {
+    default_data_directory().join(DEFAULT_PRIVATE_IDENTITY_KEY_FILENAME)
+}
+
+fn default_public_identity_key_file() -> PathBuf {
+    default_data_directory().join(DEFAULT_PUBLIC_IDENTITY_KEY_FILENAME)
+}
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ValidatorRewarderPaths {
     pub nyxd_scraper: PathBuf,
 
     pub reward_history: PathBuf,
+
+    /// Path to file containing private identity key used for signing the reward manifests.
+    #[serde(default = "default_private_identity_key_file")]
+    pub private_identity_key_file: PathBuf,
+
+    /// Path to file containing public identity key used for verifying the reward manifests.
+    #[serde(default = "default_public_identity_key_file")]
+    pub public_identity_key_file: PathBuf,
+}
+
+impl ValidatorRewarderPaths {
+    fn identity_key_paths(&self) -> KeyPairPath {
+        KeyPairPath::new(
+            &self.private_identity_key_file,
+            &self.public_identity_key_file,
+        )
+    }
+
+    pub fn load_identity(&self) -> Result<ed25519::KeyPair, NymRewarderError> {
+        nym_pemstore::load_keypair(&self.identity_key_paths())
+            .map_err(|source| NymRewarderError::IdentityKeyLoadFailure { source })
+    }
+
+    pub fn generate_identity(&self) -> Result<ed25519::KeyPair, NymRewarderError> {
+        let mut rng = rand::thread_rng();
+        let keypair = ed25519::KeyPair::new(&mut rng);
+
+        nym_pemstore::store_keypair(&keypair, &self.identity_key_paths())
+            .map_err(|source| NymRewarderError::IdentityKeyStoreFailure { source })?;
+        Ok(keypair)
+    }
+
+    /// Load the identity keys or generate fresh ones if they don't exist,
+    /// for example if the rewarder had been initialised before the keys were introduced.
+    pub fn load_or_generate_identity(&self) -> Result<ed25519::KeyPair, NymRewarderError> {
+        if self.private_identity_key_file.exists() || self.public_identity_key_file.exists() {
+            self.load_identity()
+        } else {
+            self.generate_identity()
+        }
+    }
 }
 
 impl Default for ValidatorRewarderPaths {
@@ -20,6 +74,8 @@ impl Default for ValidatorRewarderPaths {
         ValidatorRewarderPaths {
             nyxd_scraper: default_data_directory().join(DEFAULT_SCRAPER_DB_FILENAME),
             reward_history: default_data_directory().join(DEFAULT_REWARD_HISTORY_DB_FILENAME),
+            private_identity_key_file: default_private_identity_key_file(),
+            public_identity_key_file: default_public_identity_key_file(),
         }
     }
 }
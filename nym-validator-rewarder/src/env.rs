@@ -0,0 +1,42 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+pub mod vars {
+    pub const NYM_REWARDER_MNEMONIC_ARG: &str = "NYM_REWARDER_MNEMONIC";
+
+    pub const NYM_REWARDER_DISABLE_BLOCK_SIGNING_REWARDING_ARG: &str =
+        "NYM_REWARDER_DISABLE_BLOCK_SIGNING_REWARDING";
+    pub const NYM_REWARDER_BLOCK_SIGNING_MONITORING_ONLY_ARG: &str =
+        "NYM_REWARDER_BLOCK_SIGNING_MONITORING_ONLY";
+    pub const NYM_REWARDER_DISABLE_CREDENTIAL_ISSUANCE_REWARDING_ARG: &str =
+        "NYM_REWARDER_DISABLE_CREDENTIAL_ISSUANCE_REWARDING";
+
+    pub const NYM_REWARDER_CREDENTIAL_MONITOR_RUN_INTERVAL_ARG: &str =
+        "NYM_REWARDER_CREDENTIAL_MONITOR_RUN_INTERVAL";
+    pub const NYM_REWARDER_CREDENTIAL_MONITOR_MIN_VALIDATION_ARG: &str =
+        "NYM_REWARDER_CREDENTIAL_MONITOR_MIN_VALIDATION";
+    pub const NYM_REWARDER_CREDENTIAL_MONITOR_SAMPLING_RATE_ARG: &str =
+        "NYM_REWARDER_CREDENTIAL_MONITOR_SAMPLING_RATE";
+
+    pub const NYM_REWARDER_SCRAPER_ENDPOINT_ARG: &str = "NYM_REWARDER_SCRAPER_ENDPOINT";
+    pub const NYM_REWARDER_NYXD_ENDPOINT_ARG: &str = "NYM_REWARDER_NYXD_ENDPOINT";
+
+    pub const NYM_REWARDER_EPOCH_BUDGET_ARG: &str = "NYM_REWARDER_EPOCH_BUDGET";
+    pub const NYM_REWARDER_EPOCH_DURATION_ARG: &str = "NYM_REWARDER_EPOCH_DURATION";
+
+    pub const NYM_REWARDER_BLOCK_SIGNING_REWARD_RATIO_ARG: &str =
+        "NYM_REWARDER_BLOCK_SIGNING_REWARD_RATIO";
+    pub const NYM_REWARDER_CREDENTIAL_ISSUANCE_REWARD_RATIO_ARG: &str =
+        "NYM_REWARDER_CREDENTIAL_ISSUANCE_REWARD_RATIO";
+    pub const NYM_REWARDER_CREDENTIAL_VERIFICATION_REWARD_RATIO_ARG: &str =
+        "NYM_REWARDER_CREDENTIAL_VERIFICATION_REWARD_RATIO";
+    pub const NYM_REWARDER_GATEWAY_UPTIME_REWARD_RATIO_ARG: &str =
+        "NYM_REWARDER_GATEWAY_UPTIME_REWARD_RATIO";
+
+    pub const NYM_REWARDER_STORAGE_BACKEND_ARG: &str = "NYM_REWARDER_STORAGE_BACKEND";
+    pub const NYM_REWARDER_POSTGRES_URL_ARG: &str = "NYM_REWARDER_POSTGRES_URL";
+
+    pub const NYM_REWARDER_ADMIN_TOKEN_ARG: &str = "NYM_REWARDER_ADMIN_TOKEN";
+
+    pub const NYM_REWARDER_CONFIG_PATH_ARG: &str = "NYM_REWARDER_CONFIG";
+}
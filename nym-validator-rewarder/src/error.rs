@@ -11,6 +11,7 @@ use nym_validator_client::nyxd::{AccountId, Coin, Hash};
 use std::io;
 use std::path::PathBuf;
 use thiserror::Error;
+use url::Url;
 
 #[derive(Debug, Error)]
 pub enum NymRewarderError {
@@ -44,6 +45,15 @@ pub enum NymRewarderError {
         source: io::Error,
     },
 
+    #[error("no mnemonic has been provided. use either the --mnemonic flag or the NYM_REWARDER_MNEMONIC environment variable")]
+    MissingMnemonic,
+
+    #[error("failed to serialize the config: {source}")]
+    ConfigSerializationFailure {
+        #[source]
+        source: serde_json::Error,
+    },
+
     #[error("there already exists a config file at: {}. if you want to overwrite its content, use --force flag", path.display())]
     ExistingConfig { path: PathBuf },
 
@@ -54,6 +64,19 @@ pub enum NymRewarderError {
     #[error("the provided rewarding ratios don't add up to 1. ratios: {ratios:?}")]
     InvalidRewardingRatios { ratios: RewardingRatios },
 
+    #[error(
+        "the postgres storage backend has been selected, but no postgres_url has been provided"
+    )]
+    MissingPostgresUrl,
+
+    #[error("the minimum block signing uptime must be between 0 and 1. got: {value}")]
+    InvalidMinimumSigningUptime { value: f64 },
+
+    #[error(
+        "the minimum credential issuer api availability must be between 0 and 1. got: {value}"
+    )]
+    InvalidMinimumApiAvailability { value: f64 },
+
     #[error("chain scraping failure: {source}")]
     ScraperFailure {
         #[from]
@@ -73,6 +96,15 @@ pub enum NymRewarderError {
         source: ErrorReport,
     },
 
+    #[error(
+        "validator {operator_account} has specified an invalid payout address '{raw}': {reason}"
+    )]
+    InvalidPayoutAddress {
+        operator_account: String,
+        raw: String,
+        reason: String,
+    },
+
     #[error(
         "could not convert validator public key: {public_key} into a consensus address: {source}"
     )]
@@ -177,11 +209,150 @@ pub enum NymRewarderError {
     #[error("there were no validators to reward in this epoch")]
     NoValidatorsToReward,
 
+    #[error("'{phase}' is not a valid epoch processing phase")]
+    UnknownEpochProcessingPhase { phase: String },
+
+    #[error("the persisted rewarding amounts are malformed: {reason}")]
+    MalformedPersistedRewardingAmounts { reason: String },
+
     #[error("the current pruning strategy is set to 'everything' - we won't have any block data for rewarding")]
     EverythingPruningStrategy,
 
     #[error("pruning.keep_recent must not be smaller than {min_to_keep}. got: {keep_recent}")]
     TooSmallKeepRecent { min_to_keep: u32, keep_recent: u32 },
+
+    #[error(
+        "webhook {url} is using the telegram format, but no telegram_chat_id has been provided"
+    )]
+    MissingTelegramChatId { url: Url },
+
+    #[error("the admin api has been enabled, but no token has been provided")]
+    MissingAdminToken,
+
+    #[error("payouts have been paused via the admin api. the rewards are going to be sent once they're resumed")]
+    PayoutsPaused,
+
+    #[error("the admin token contains characters that can't be used in a request header")]
+    MalformedAdminToken,
+
+    #[error("failed to build the webhook http client: {source}")]
+    WebhookClientBuildFailure {
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("failed to serialize webhook notification: {source}")]
+    WebhookSerializationFailure {
+        #[from]
+        source: serde_json::Error,
+    },
+
+    #[error("failed to deliver webhook notification: {source}")]
+    WebhookDeliveryFailure {
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error("'{reason}' is not a valid credential issuance violation reason")]
+    UnknownIssuanceViolationReason { reason: String },
+
+    #[error("the retention policy must keep the details of at least a single epoch")]
+    ZeroRetainedEpochs,
+
+    #[error("the stored reward amount '{amount}' is malformed")]
+    MalformedStoredRewardAmount { amount: String },
+
+    #[error(
+        "the rewards of {account} have been paid in different denominations: {expected} and {got}"
+    )]
+    MismatchedRewardDenoms {
+        account: String,
+        expected: String,
+        got: String,
+    },
+
+    #[error(
+    "failed to write the rewarding archive to '{}'. detailed message: {source}", path.display()
+    )]
+    ArchiveWriteFailure {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to load the identity keys of the rewarder: {source}")]
+    IdentityKeyLoadFailure {
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to store the identity keys of the rewarder: {source}")]
+    IdentityKeyStoreFailure {
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("failed to serialize the reward manifest: {source}")]
+    ManifestSerializationFailure {
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error("the reward manifest is malformed: {source}")]
+    MalformedManifest {
+        #[source]
+        source: serde_json::Error,
+    },
+
+    #[error(
+    "failed to read the reward manifest from '{}'. detailed message: {source}", path.display()
+    )]
+    ManifestReadFailure {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error(
+    "failed to publish the reward manifest to '{}'. detailed message: {source}", path.display()
+    )]
+    ManifestWriteFailure {
+        path: PathBuf,
+        #[source]
+        source: io::Error,
+    },
+
+    #[error("the reward amount '{amount}' in the manifest is malformed")]
+    MalformedManifestAmount { amount: String },
+
+    #[error("no reward amount has been determined for {account} in the {module} module")]
+    MissingRewardAmount { module: String, account: String },
+
+    #[error("there's no reward manifest for epoch {epoch_id}")]
+    ManifestNotFound { epoch_id: i64 },
+
+    #[error("there's no record of the rewarding epoch {epoch_id}")]
+    RewardingEpochNotFound { epoch_id: i64 },
+
+    #[error("the signature on the reward manifest of epoch {epoch_id} is invalid")]
+    ManifestSignatureVerificationFailure { epoch_id: i64 },
+
+    #[error("the reward manifest has been signed by {got} while {expected} was expected")]
+    UnexpectedManifestSigner { expected: String, got: String },
+
+    #[error(
+        "no rewards have been paid out in epoch {epoch_id}, there's nothing to verify on chain"
+    )]
+    ManifestWithoutRewardingTx { epoch_id: i64 },
+
+    #[error("the rewarding transaction hash '{tx_hash}' in the manifest is malformed")]
+    MalformedManifestTxHash { tx_hash: String },
+
+    #[error("the rewarding transaction {tx_hash} has failed or could not be decoded")]
+    MalformedRewardingTransaction { tx_hash: Hash },
+
+    #[error("the on-chain payouts do not match the reward manifest of epoch {epoch_id}")]
+    ManifestPayoutsMismatch { epoch_id: i64 },
 }
 
 #[derive(Debug)]
@@ -13,6 +13,7 @@ use nym_network_defaults::setup_env;
 
 pub mod cli;
 pub mod config;
+mod env;
 pub mod error;
 mod rewarder;
 
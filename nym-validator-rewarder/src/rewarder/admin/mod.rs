@@ -0,0 +1,156 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config;
+use crate::error::NymRewarderError;
+use crate::rewarder::admin::proto::rewarder_admin_server::{RewarderAdmin, RewarderAdminServer};
+use crate::rewarder::admin::proto::{
+    EpochEvaluation, EvaluateEpochRequest, GetStateRequest, PayoutsStatus, RewarderState,
+    SetPayoutsPausedRequest,
+};
+use nym_task::TaskClient;
+use subtle::ConstantTimeEq;
+use tokio::sync::{mpsc, oneshot};
+use tonic::metadata::MetadataValue;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+use tracing::{error, info};
+
+// generated code
+#[allow(clippy::unwrap_used)]
+pub(crate) mod proto;
+
+/// Operations requested via the admin api that have to be executed by the main rewarder loop
+/// as they require access to its state.
+pub(crate) enum AdminCommand {
+    EvaluateEpoch {
+        respond_to: oneshot::Sender<EpochEvaluation>,
+    },
+    SetPayoutsPaused {
+        paused: bool,
+        respond_to: oneshot::Sender<PayoutsStatus>,
+    },
+    GetState {
+        respond_to: oneshot::Sender<RewarderState>,
+    },
+}
+
+pub(crate) type AdminCommandSender = mpsc::Sender<AdminCommand>;
+pub(crate) type AdminCommandReceiver = mpsc::Receiver<AdminCommand>;
+
+struct AdminService {
+    commands: AdminCommandSender,
+}
+
+impl AdminService {
+    async fn execute<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> AdminCommand,
+    ) -> Result<Response<T>, Status> {
+        let (tx, rx) = oneshot::channel();
+        self.commands
+            .send(command(tx))
+            .await
+            .map_err(|_| Status::unavailable("the rewarder is shutting down"))?;
+
+        rx.await
+            .map(Response::new)
+            .map_err(|_| Status::internal("the rewarder has failed to process the request"))
+    }
+}
+
+#[tonic::async_trait]
+impl RewarderAdmin for AdminService {
+    async fn evaluate_epoch(
+        &self,
+        _request: Request<EvaluateEpochRequest>,
+    ) -> Result<Response<EpochEvaluation>, Status> {
+        self.execute(|respond_to| AdminCommand::EvaluateEpoch { respond_to })
+            .await
+    }
+
+    async fn set_payouts_paused(
+        &self,
+        request: Request<SetPayoutsPausedRequest>,
+    ) -> Result<Response<PayoutsStatus>, Status> {
+        let paused = request.into_inner().paused;
+        self.execute(|respond_to| AdminCommand::SetPayoutsPaused { paused, respond_to })
+            .await
+    }
+
+    async fn get_state(
+        &self,
+        _request: Request<GetStateRequest>,
+    ) -> Result<Response<RewarderState>, Status> {
+        self.execute(|respond_to| AdminCommand::GetState { respond_to })
+            .await
+    }
+}
+
+fn token_interceptor(
+    token: &str,
+) -> Result<impl Fn(Request<()>) -> Result<Request<()>, Status> + Clone, NymRewarderError> {
+    let expected: MetadataValue<_> = format!("Bearer {token}")
+        .parse()
+        .map_err(|_| NymRewarderError::MalformedAdminToken)?;
+
+    Ok(
+        move |request: Request<()>| match request.metadata().get("authorization") {
+            // make sure not to leak how much of the token got guessed correctly
+            Some(provided) if bool::from(provided.as_bytes().ct_eq(expected.as_bytes())) => {
+                Ok(request)
+            }
+            _ => Err(Status::unauthenticated("invalid or missing admin token")),
+        },
+    )
+}
+
+/// Start the admin api in a background task. The returned receiver has to be polled by the main rewarder loop.
+pub(crate) fn start_admin_api(
+    config: &config::Admin,
+    mut task_client: TaskClient,
+) -> Result<AdminCommandReceiver, NymRewarderError> {
+    let (commands, receiver) = mpsc::channel(8);
+    let interceptor = token_interceptor(&config.token)?;
+    let service = RewarderAdminServer::with_interceptor(AdminService { commands }, interceptor);
+    let bind_address = config.bind_address;
+
+    info!("starting the admin api on {bind_address}");
+    tokio::spawn(async move {
+        if let Err(err) = Server::builder()
+            .add_service(service)
+            .serve_with_shutdown(bind_address, async move { task_client.recv().await })
+            .await
+        {
+            error!("the admin api has failed: {err}")
+        }
+    });
+
+    Ok(receiver)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_with_token(token: Option<&str>) -> Request<()> {
+        let mut request = Request::new(());
+        if let Some(token) = token {
+            request
+                .metadata_mut()
+                .insert("authorization", token.parse().unwrap());
+        }
+        request
+    }
+
+    #[test]
+    fn only_requests_with_the_configured_token_are_accepted() {
+        let interceptor = token_interceptor("secret-token").unwrap();
+
+        assert!(interceptor(request_with_token(Some("Bearer secret-token"))).is_ok());
+        assert!(interceptor(request_with_token(Some("Bearer secret-toke"))).is_err());
+        assert!(interceptor(request_with_token(Some("Bearer secret-tokem"))).is_err());
+        assert!(interceptor(request_with_token(Some("secret-token"))).is_err());
+        assert!(interceptor(request_with_token(None)).is_err());
+    }
+}
@@ -0,0 +1,338 @@
+// This file is @generated by prost-build and tonic-build 0.11 (server only) from `proto/admin.proto`.
+// It's checked in so that building the rewarder would not require `protoc`.
+// Make sure to regenerate it whenever the protobuf definitions change.
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct EvaluateEpochRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RewardAmount {
+    #[prost(string, tag = "1")]
+    pub account: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub amount: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct EpochEvaluation {
+    #[prost(int64, tag = "1")]
+    pub epoch_id: i64,
+    #[prost(string, tag = "2")]
+    pub epoch_start: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub epoch_end: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub total_budget: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub total_amount: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "6")]
+    pub rewards: ::prost::alloc::vec::Vec<RewardAmount>,
+    /// failures of the individual rewarding modules
+    #[prost(string, repeated, tag = "7")]
+    pub errors: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct SetPayoutsPausedRequest {
+    #[prost(bool, tag = "1")]
+    pub paused: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct PayoutsStatus {
+    #[prost(bool, tag = "1")]
+    pub paused: bool,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GetStateRequest {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RewarderState {
+    #[prost(int64, tag = "1")]
+    pub run_id: i64,
+    #[prost(int64, tag = "2")]
+    pub epoch_id: i64,
+    #[prost(string, tag = "3")]
+    pub epoch_start: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub epoch_end: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub secs_until_epoch_end: u64,
+    #[prost(bool, tag = "6")]
+    pub payouts_paused: bool,
+    #[prost(bool, tag = "7")]
+    pub monitor_only: bool,
+    #[prost(bool, tag = "8")]
+    pub block_signing_enabled: bool,
+    #[prost(bool, tag = "9")]
+    pub credential_issuance_enabled: bool,
+    #[prost(bool, tag = "10")]
+    pub gateway_uptime_enabled: bool,
+}
+/// Generated server implementations.
+pub mod rewarder_admin_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with RewarderAdminServer.
+    #[async_trait]
+    pub trait RewarderAdmin: Send + Sync + 'static {
+        /// Evaluate the rewards of the current epoch based on the data gathered so far.
+        /// This is a dry run: nothing is sent out nor persisted and the epoch is not advanced.
+        async fn evaluate_epoch(
+            &self,
+            request: tonic::Request<super::EvaluateEpochRequest>,
+        ) -> std::result::Result<tonic::Response<super::EpochEvaluation>, tonic::Status>;
+        /// Pause or resume sending out the rewards. While paused, epochs are still evaluated and recorded,
+        /// and their rewards are sent out once the payouts are resumed.
+        async fn set_payouts_paused(
+            &self,
+            request: tonic::Request<super::SetPayoutsPausedRequest>,
+        ) -> std::result::Result<tonic::Response<super::PayoutsStatus>, tonic::Status>;
+        /// Get the current in-memory state of the rewarder.
+        async fn get_state(
+            &self,
+            request: tonic::Request<super::GetStateRequest>,
+        ) -> std::result::Result<tonic::Response<super::RewarderState>, tonic::Status>;
+    }
+    /// Local administrative api of the validator rewarder.
+    /// Every request must include the configured token in the `authorization` metadata as `Bearer <token>`.
+    #[derive(Debug)]
+    pub struct RewarderAdminServer<T: RewarderAdmin> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: RewarderAdmin> RewarderAdminServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(inner: T, interceptor: F) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for RewarderAdminServer<T>
+    where
+        T: RewarderAdmin,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/nym.rewarder.admin.RewarderAdmin/EvaluateEpoch" => {
+                    #[allow(non_camel_case_types)]
+                    struct EvaluateEpochSvc<T: RewarderAdmin>(pub Arc<T>);
+                    impl<T: RewarderAdmin> tonic::server::UnaryService<super::EvaluateEpochRequest>
+                        for EvaluateEpochSvc<T>
+                    {
+                        type Response = super::EpochEvaluation;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::EvaluateEpochRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RewarderAdmin>::evaluate_epoch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = EvaluateEpochSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nym.rewarder.admin.RewarderAdmin/SetPayoutsPaused" => {
+                    #[allow(non_camel_case_types)]
+                    struct SetPayoutsPausedSvc<T: RewarderAdmin>(pub Arc<T>);
+                    impl<T: RewarderAdmin>
+                        tonic::server::UnaryService<super::SetPayoutsPausedRequest>
+                        for SetPayoutsPausedSvc<T>
+                    {
+                        type Response = super::PayoutsStatus;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SetPayoutsPausedRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RewarderAdmin>::set_payouts_paused(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SetPayoutsPausedSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/nym.rewarder.admin.RewarderAdmin/GetState" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetStateSvc<T: RewarderAdmin>(pub Arc<T>);
+                    impl<T: RewarderAdmin> tonic::server::UnaryService<super::GetStateRequest> for GetStateSvc<T> {
+                        type Response = super::RewarderState;
+                        type Future = BoxFuture<tonic::Response<Self::Response>, tonic::Status>;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetStateRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as RewarderAdmin>::get_state(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetStateSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => Box::pin(async move {
+                    Ok(http::Response::builder()
+                        .status(200)
+                        .header("grpc-status", "12")
+                        .header("content-type", "application/grpc")
+                        .body(empty_body())
+                        .unwrap())
+                }),
+            }
+        }
+    }
+    impl<T: RewarderAdmin> Clone for RewarderAdminServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: RewarderAdmin> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: RewarderAdmin> tonic::server::NamedService for RewarderAdminServer<T> {
+        const NAME: &'static str = "nym.rewarder.admin.RewarderAdmin";
+    }
+}
@@ -0,0 +1,187 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use std::collections::HashMap;
+
+/// Change of the jailing status of a validator, as observed on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JailingEvent {
+    /// The validator got jailed by the slashing module at the specified height.
+    Jailed {
+        height: i64,
+        consensus_address: String,
+    },
+
+    /// The validator has submitted an unjail transaction that got included at the specified height.
+    Unjailed {
+        height: i64,
+        operator_address: String,
+    },
+}
+
+impl JailingEvent {
+    pub fn height(&self) -> i64 {
+        match self {
+            JailingEvent::Jailed { height, .. } | JailingEvent::Unjailed { height, .. } => *height,
+        }
+    }
+}
+
+/// Range of blocks, `[start_height, end_height)`, during which the validator was jailed
+/// and thus could not have signed any of them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct JailedRange {
+    pub start_height: i64,
+    pub end_height: i64,
+}
+
+impl JailedRange {
+    pub fn blocks(&self) -> i64 {
+        self.end_height - self.start_height
+    }
+}
+
+/// Determine the ranges of blocks within `first_block..=last_block` each of the validators has been jailed for,
+/// keyed by their consensus addresses.
+///
+/// A validator is removed from the active set at the end of the block it got jailed in and rejoins it
+/// at the end of the block its unjail transaction got included in, so neither of those blocks is excluded.
+/// If the first observed event of a validator is unjailing, it must have been jailed since before the epoch started.
+/// Validators that have been jailed for the entire epoch do not have any events, but they also have no
+/// recorded voting power and are not considered for the rewards to begin with.
+pub fn jailed_ranges(
+    mut events: Vec<JailingEvent>,
+    operator_to_consensus: &HashMap<String, String>,
+    first_block: i64,
+    last_block: i64,
+) -> HashMap<String, Vec<JailedRange>> {
+    events.sort_by_key(JailingEvent::height);
+
+    let mut jailed_since: HashMap<String, Option<i64>> = HashMap::new();
+    let mut ranges: HashMap<String, Vec<JailedRange>> = HashMap::new();
+
+    let mut push_range = |consensus_address: String, start_height: i64, end_height: i64| {
+        let range = JailedRange {
+            start_height: start_height.max(first_block),
+            end_height: end_height.min(last_block + 1),
+        };
+        if range.blocks() > 0 {
+            ranges.entry(consensus_address).or_default().push(range)
+        }
+    };
+
+    for event in events {
+        match event {
+            JailingEvent::Jailed {
+                height,
+                consensus_address,
+            } => {
+                let since = jailed_since.entry(consensus_address).or_insert(None);
+                if since.is_none() {
+                    *since = Some(height + 1)
+                }
+            }
+            JailingEvent::Unjailed {
+                height,
+                operator_address,
+            } => {
+                let Some(consensus_address) = operator_to_consensus.get(&operator_address) else {
+                    continue;
+                };
+                let start = match jailed_since.insert(consensus_address.clone(), None) {
+                    Some(Some(since)) => since,
+                    // we haven't seen the jailing, so it must have happened before the epoch
+                    Some(None) | None => first_block,
+                };
+                push_range(consensus_address.clone(), start, height + 1)
+            }
+        }
+    }
+
+    // anyone who is still jailed remains so until the end of the epoch
+    for (consensus_address, since) in jailed_since {
+        if let Some(since) = since {
+            push_range(consensus_address, since, last_block + 1)
+        }
+    }
+
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jailed(height: i64, validator: &str) -> JailingEvent {
+        JailingEvent::Jailed {
+            height,
+            consensus_address: format!("valcons-{validator}"),
+        }
+    }
+
+    fn unjailed(height: i64, validator: &str) -> JailingEvent {
+        JailingEvent::Unjailed {
+            height,
+            operator_address: format!("valoper-{validator}"),
+        }
+    }
+
+    fn operators() -> HashMap<String, String> {
+        ["a", "b", "c"]
+            .into_iter()
+            .map(|v| (format!("valoper-{v}"), format!("valcons-{v}")))
+            .collect()
+    }
+
+    fn range(start_height: i64, end_height: i64) -> JailedRange {
+        JailedRange {
+            start_height,
+            end_height,
+        }
+    }
+
+    #[test]
+    fn jailed_periods_are_clamped_to_the_epoch() {
+        let events = vec![
+            // jailed and unjailed within the epoch
+            unjailed(150, "a"),
+            jailed(120, "a"),
+            // jailed before the epoch
+            unjailed(110, "b"),
+            // never unjailed
+            jailed(190, "c"),
+        ];
+
+        let ranges = jailed_ranges(events, &operators(), 100, 199);
+        assert_eq!(ranges["valcons-a"], vec![range(121, 151)]);
+        assert_eq!(ranges["valcons-b"], vec![range(100, 111)]);
+        assert_eq!(ranges["valcons-c"], vec![range(191, 200)]);
+        assert_eq!(
+            ranges
+                .values()
+                .flatten()
+                .map(JailedRange::blocks)
+                .sum::<i64>(),
+            30 + 11 + 9
+        );
+    }
+
+    #[test]
+    fn repeated_jailing_produces_multiple_ranges() {
+        let events = vec![
+            jailed(110, "a"),
+            unjailed(120, "a"),
+            jailed(130, "a"),
+            jailed(135, "a"),
+            unjailed(140, "a"),
+            // unknown validator
+            unjailed(140, "d"),
+            // jailed in the very last block
+            jailed(199, "b"),
+        ];
+
+        let ranges = jailed_ranges(events, &operators(), 100, 199);
+        assert_eq!(ranges.len(), 1);
+        assert_eq!(ranges["valcons-a"], vec![range(111, 121), range(131, 141)]);
+    }
+}
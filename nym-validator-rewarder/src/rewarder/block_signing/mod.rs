@@ -2,23 +2,46 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::error::NymRewarderError;
+use crate::rewarder::block_signing::jailing::{jailed_ranges, JailedRange};
+use crate::rewarder::block_signing::policy::SigningRewardPolicy;
 use crate::rewarder::block_signing::types::{EpochSigningResults, RawValidatorResult};
 use crate::rewarder::epoch::Epoch;
+use crate::rewarder::helpers::consensus_pubkey_to_address;
 use crate::rewarder::nyxd_client::NyxdClient;
+use futures::{stream, StreamExt, TryStreamExt};
 use nym_validator_client::nyxd::module_traits::staking;
 use nym_validator_client::nyxd::{AccountId, PageRequest};
-use nyxd_scraper::NyxdScraper;
+use nyxd_scraper::{models, NyxdScraper};
 use std::cmp::min;
 use std::collections::HashMap;
 use std::ops::Range;
 use tracing::{debug, error, info, trace, warn};
 
+pub(crate) mod jailing;
+pub(crate) mod policy;
 pub(crate) mod types;
 
+/// Blocks produced within a rewarding epoch.
+struct EpochBlocks {
+    first_block: i64,
+    last_block: i64,
+    total: i64,
+}
+
+struct ValidatorSigningData {
+    voting_power: i64,
+    voting_power_height: i64,
+    signed_blocks: i32,
+}
+
 pub struct EpochSigning {
     pub(crate) nyxd_client: NyxdClient,
     pub(crate) nyxd_scraper: NyxdScraper,
     pub(crate) whitelist: Vec<AccountId>,
+    pub(crate) policy: Box<dyn SigningRewardPolicy>,
+    pub(crate) minimum_uptime: f64,
+    pub(crate) allow_payout_overrides: bool,
+    pub(crate) max_concurrent_queries: usize,
 }
 
 impl EpochSigning {
@@ -26,7 +49,7 @@ impl EpochSigning {
         &self,
         address: &str,
         height_range: Range<i64>,
-    ) -> Result<Option<i64>, NymRewarderError> {
+    ) -> Result<Option<(i64, i64)>, NymRewarderError> {
         for height in height_range {
             trace!("attempting to get pre-commit for {address} at height {height}");
             if let Some(precommit) = self
@@ -35,7 +58,7 @@ impl EpochSigning {
                 .get_precommit(address, height)
                 .await?
             {
-                return Ok(Some(precommit.voting_power));
+                return Ok(Some((height, precommit.voting_power)));
             }
         }
 
@@ -76,52 +99,144 @@ impl EpochSigning {
         }
     }
 
-    pub(crate) async fn get_signed_blocks_results(
+    /// First stage of the evaluation: determine the range of blocks that belong to the epoch.
+    async fn fetch_epoch_blocks(&self, epoch: Epoch) -> Result<EpochBlocks, NymRewarderError> {
+        let storage = &self.nyxd_scraper.storage;
+        let (first_block, last_block, total) = tokio::try_join!(
+            storage.get_first_block_height_after(epoch.start_time),
+            storage.get_last_block_height_before(epoch.end_time),
+            storage.get_blocks_between(epoch.start_time, epoch.end_time),
+        )?;
+        let first_block = first_block.unwrap_or_default();
+        let last_block = last_block.unwrap_or_default();
+
+        let disagreements = storage
+            .get_rpc_endpoint_disagreements(first_block, last_block)
+            .await?;
+        if !disagreements.is_empty() {
+            warn!(
+                "there were {} rpc endpoint disagreements recorded for blocks in this epoch",
+                disagreements.len()
+            );
+            for disagreement in &disagreements {
+                debug!(
+                    "{} reported hash {} for block {} (expected {})",
+                    disagreement.endpoint,
+                    disagreement.received_hash,
+                    disagreement.height,
+                    disagreement.expected_hash
+                )
+            }
+        }
+
+        Ok(EpochBlocks {
+            first_block,
+            last_block,
+            total,
+        })
+    }
+
+    /// Determine the ranges of blocks each of the validators, keyed by their consensus addresses, has been jailed for.
+    async fn get_jailed_ranges(
         &self,
-        current_epoch: Epoch,
-    ) -> Result<EpochSigningResults, NymRewarderError> {
-        info!(
-            "looking up block signers for epoch {} ({} - {})",
-            current_epoch.id,
-            current_epoch.start_rfc3339(),
-            current_epoch.end_rfc3339()
-        );
+        blocks: &EpochBlocks,
+        details: &[staking::Validator],
+    ) -> Result<HashMap<String, Vec<JailedRange>>, NymRewarderError> {
+        // validators can't sign any blocks while they're jailed, so those are excluded from their expected blocks
+        let jailing_events = self
+            .nyxd_client
+            .jailing_events(blocks.first_block, blocks.last_block)
+            .await?;
+        if !jailing_events.is_empty() {
+            info!(
+                "there were {} jailing events in this epoch",
+                jailing_events.len()
+            );
+        }
+        let mut operator_to_consensus = HashMap::new();
+        for validator in details {
+            if let Some(pubkey) = validator.consensus_pubkey.clone() {
+                let consensus_address = consensus_pubkey_to_address(pubkey)?;
+                operator_to_consensus.insert(
+                    validator.operator_address.to_string(),
+                    consensus_address.to_string(),
+                );
+            }
+        }
+        Ok(jailed_ranges(
+            jailing_events,
+            &operator_to_consensus,
+            blocks.first_block,
+            blocks.last_block,
+        ))
+    }
 
-        let validators = self.nyxd_scraper.storage.get_all_known_validators().await?;
-        debug!("retrieved {} known validators", validators.len());
+    /// Get the voting power and the number of blocks signed by the validator in the epoch.
+    /// Returns `None` if the voting power of the validator could not be determined.
+    async fn get_validator_signing(
+        &self,
+        validator: &models::Validator,
+        vp_range: Range<i64>,
+        epoch: Epoch,
+    ) -> Result<Option<ValidatorSigningData>, NymRewarderError> {
+        let addr = &validator.consensus_address;
+        debug!("getting voting power and signed blocks of {addr}");
 
-        let epoch_start = current_epoch.start_time;
-        let epoch_end = current_epoch.end_time;
-        let first_block = self
-            .nyxd_scraper
-            .storage
-            .get_first_block_height_after(epoch_start)
-            .await?
-            .unwrap_or_default();
-        let last_block = self
+        let Some((voting_power_height, voting_power)) =
+            self.get_voting_power(addr, vp_range.clone()).await?
+        else {
+            error!("failed to obtain voting power for validator {addr} for any block between heights {vp_range:?} - there were no stored pre-commits for that validator.");
+            return Ok(None);
+        };
+
+        let signed_blocks = self
             .nyxd_scraper
             .storage
-            .get_last_block_height_before(epoch_end)
-            .await?
-            .unwrap_or_default();
+            .get_signed_between_times(addr, epoch.start_time, epoch.end_time)
+            .await?;
+
+        Ok(Some(ValidatorSigningData {
+            voting_power,
+            voting_power_height,
+            signed_blocks,
+        }))
+    }
+
+    /// Second stage of the evaluation: score all known validators based on the blocks they have signed.
+    /// The chain queries are performed concurrently, but never more than `max_concurrent_queries` at once.
+    async fn score_signers(
+        &self,
+        epoch: Epoch,
+        blocks: &EpochBlocks,
+        jailed: &mut HashMap<String, Vec<JailedRange>>,
+    ) -> Result<(i64, HashMap<models::Validator, RawValidatorResult>), NymRewarderError> {
+        let validators = self.nyxd_scraper.storage.get_all_known_validators().await?;
+        debug!("retrieved {} known validators", validators.len());
 
         // each validator MUST be online at some point during the first 20 blocks, otherwise they're not getting anything.
-        let vp_range_end = min(first_block + 20, last_block);
-        let vp_range = first_block..vp_range_end;
+        let vp_range_end = min(blocks.first_block + 20, blocks.last_block);
+        let vp_range = blocks.first_block..vp_range_end;
+
+        let results = stream::iter(validators)
+            .map(|validator| {
+                let vp_range = vp_range.clone();
+                async move {
+                    let signing = self
+                        .get_validator_signing(&validator, vp_range, epoch)
+                        .await?;
+                    Ok::<_, NymRewarderError>((validator, signing))
+                }
+            })
+            .buffer_unordered(self.max_concurrent_queries.max(1))
+            .try_collect::<Vec<_>>()
+            .await?;
 
         let mut total_vp = 0;
         let mut signed_in_epoch = HashMap::new();
 
         // for each validator, with a valid voting power, get number of signed blocks in the rewarding epoch
-        for validator in validators {
-            let addr = &validator.consensus_address;
-            debug!("getting voting power and signed blocks of {addr}");
-
-            let Some(vp) = self
-                .get_voting_power(&validator.consensus_address, vp_range.clone())
-                .await?
-            else {
-                error!("failed to obtain voting power for validator {addr} for any block between heights {vp_range:?} - there were no stored pre-commits for that validator.");
+        for (validator, signing) in results {
+            let Some(signing) = signing else {
                 continue;
             };
 
@@ -130,7 +245,7 @@ impl EpochSigning {
             let whitelisted = if let Ok(parsed) = cons_address.parse() {
                 if self.whitelist.contains(&parsed) {
                     debug!("{cons_address} is on the whitelist");
-                    total_vp += vp;
+                    total_vp += signing.voting_power;
                     true
                 } else {
                     warn!("{cons_address} is not a valid consensus address");
@@ -141,22 +256,50 @@ impl EpochSigning {
                 false
             };
 
-            let signed = self
-                .nyxd_scraper
-                .storage
-                .get_signed_between_times(&validator.consensus_address, epoch_start, epoch_end)
-                .await?;
-            signed_in_epoch.insert(validator, RawValidatorResult::new(signed, vp, whitelisted));
+            let jailed_ranges = jailed.remove(cons_address).unwrap_or_default();
+            signed_in_epoch.insert(
+                validator,
+                RawValidatorResult::new(
+                    signing.signed_blocks,
+                    signing.voting_power,
+                    signing.voting_power_height,
+                    whitelisted,
+                )
+                .with_jailed_ranges(jailed_ranges),
+            );
         }
 
-        let total = self
-            .nyxd_scraper
-            .storage
-            .get_blocks_between(epoch_start, epoch_end)
-            .await?;
+        Ok((total_vp, signed_in_epoch))
+    }
 
-        let details = self.get_validator_details(last_block).await?;
+    pub(crate) async fn get_signed_blocks_results(
+        &self,
+        current_epoch: Epoch,
+    ) -> Result<EpochSigningResults, NymRewarderError> {
+        info!(
+            "looking up block signers for epoch {} ({} - {})",
+            current_epoch.id,
+            current_epoch.start_rfc3339(),
+            current_epoch.end_rfc3339()
+        );
+
+        let blocks = self.fetch_epoch_blocks(current_epoch).await?;
+
+        let details = self.get_validator_details(blocks.last_block).await?;
+        let mut jailed = self.get_jailed_ranges(&blocks, &details).await?;
+
+        let (total_vp, signed_in_epoch) = self
+            .score_signers(current_epoch, &blocks, &mut jailed)
+            .await?;
 
-        EpochSigningResults::construct(total, total_vp, signed_in_epoch, details)
+        EpochSigningResults::construct(
+            blocks.total,
+            total_vp,
+            signed_in_epoch,
+            details,
+            self.policy.as_ref(),
+            self.minimum_uptime,
+            self.allow_payout_overrides,
+        )
     }
 }
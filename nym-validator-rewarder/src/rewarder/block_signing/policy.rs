@@ -0,0 +1,160 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::{BlockSigning, SigningRewardPolicyKind};
+use crate::rewarder::block_signing::types::ValidatorSigning;
+use cosmwasm_std::Decimal;
+
+/// Formula used for distributing the block signing budget between the validators.
+pub trait SigningRewardPolicy: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Determine the share of the block signing budget each of the validators should receive.
+    /// The returned values are in the same order as the provided validators and must not add up to more than 1.
+    fn reward_shares(&self, blocks: i64, validators: &[ValidatorSigning]) -> Vec<Decimal>;
+}
+
+pub(crate) fn signing_reward_policy(config: &BlockSigning) -> Box<dyn SigningRewardPolicy> {
+    match config.reward_policy {
+        SigningRewardPolicyKind::ProportionalToVotingPower => Box::new(ProportionalToVotingPower),
+        SigningRewardPolicyKind::EqualShareWithMinimumUptime => {
+            Box::new(EqualShareWithMinimumUptime)
+        }
+    }
+}
+
+/// Each whitelisted validator receives the share corresponding to its voting power
+/// scaled by the ratio of blocks it has signed in the epoch.
+pub struct ProportionalToVotingPower;
+
+impl SigningRewardPolicy for ProportionalToVotingPower {
+    fn name(&self) -> &'static str {
+        "proportional to voting power"
+    }
+
+    fn reward_shares(&self, _blocks: i64, validators: &[ValidatorSigning]) -> Vec<Decimal> {
+        validators
+            .iter()
+            .map(|v| {
+                if v.whitelisted {
+                    v.ratio_signed * v.voting_power_ratio
+                } else {
+                    Decimal::zero()
+                }
+            })
+            .collect()
+    }
+}
+
+/// The budget is split equally between all whitelisted validators that were expected to sign any blocks.
+/// The minimum uptime is not enforced here, but by the forfeiture applied on top of every policy:
+/// since the forfeited shares are redistributed proportionally, the qualifying validators still end up
+/// with equal shares of the entire budget.
+pub struct EqualShareWithMinimumUptime;
+
+impl EqualShareWithMinimumUptime {
+    fn is_eligible(validator: &ValidatorSigning) -> bool {
+        // the blocks produced while the validator was jailed do not count towards its uptime
+        validator.whitelisted && validator.expected_blocks > 0
+    }
+}
+
+impl SigningRewardPolicy for EqualShareWithMinimumUptime {
+    fn name(&self) -> &'static str {
+        "equal share with minimum uptime"
+    }
+
+    fn reward_shares(&self, _blocks: i64, validators: &[ValidatorSigning]) -> Vec<Decimal> {
+        let eligible = validators.iter().filter(|v| Self::is_eligible(v)).count();
+        if eligible == 0 {
+            return vec![Decimal::zero(); validators.len()];
+        }
+
+        let share = Decimal::from_ratio(1u64, eligible as u64);
+        validators
+            .iter()
+            .map(|v| {
+                if Self::is_eligible(v) {
+                    share
+                } else {
+                    Decimal::zero()
+                }
+            })
+            .collect()
+    }
+}
+
+/// Determine the additional share each of the qualifying validators should receive
+/// once the shares of all non-qualifying validators have been forfeited.
+/// The forfeited shares are redistributed proportionally to the shares of the qualifying validators.
+/// If nobody qualifies, nothing is redistributed and the forfeited budget remains unspent.
+pub(crate) fn redistribute_forfeited(shares: &[Decimal], qualifying: &[bool]) -> Vec<Decimal> {
+    let mut forfeited = Decimal::zero();
+    let mut retained = Decimal::zero();
+    for (share, qualifies) in shares.iter().zip(qualifying) {
+        if *qualifies {
+            retained += share
+        } else {
+            forfeited += share
+        }
+    }
+
+    if forfeited.is_zero() || retained.is_zero() {
+        return vec![Decimal::zero(); shares.len()];
+    }
+
+    shares
+        .iter()
+        .zip(qualifying)
+        .map(|(share, qualifies)| {
+            if *qualifies {
+                forfeited * (*share / retained)
+            } else {
+                Decimal::zero()
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn percent(value: u64) -> Decimal {
+        Decimal::percent(value)
+    }
+
+    #[test]
+    fn forfeited_shares_are_redistributed_proportionally() {
+        let shares = [percent(10), percent(20), percent(30), percent(40)];
+        let qualifying = [false, true, true, false];
+
+        let redistributed = redistribute_forfeited(&shares, &qualifying);
+        assert_eq!(
+            redistributed,
+            vec![percent(0), percent(20), percent(30), percent(0)]
+        );
+
+        let total: Decimal = shares
+            .iter()
+            .zip(&qualifying)
+            .zip(&redistributed)
+            .filter(|((_, qualifies), _)| **qualifies)
+            .map(|((share, _), extra)| *share + extra)
+            .sum();
+        assert_eq!(total, Decimal::one());
+    }
+
+    #[test]
+    fn nothing_is_redistributed_without_qualifying_validators() {
+        let shares = [percent(50), percent(50)];
+        assert_eq!(
+            redistribute_forfeited(&shares, &[false, false]),
+            vec![Decimal::zero(); 2]
+        );
+        assert_eq!(
+            redistribute_forfeited(&shares, &[true, true]),
+            vec![Decimal::zero(); 2]
+        );
+    }
+}
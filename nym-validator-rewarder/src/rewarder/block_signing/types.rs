@@ -2,26 +2,57 @@
 // SPDX-License-Identifier: GPL-3.0-only
 
 use crate::error::NymRewarderError;
-use crate::rewarder::helpers::{consensus_pubkey_to_address, operator_account_to_owner_account};
+use crate::rewarder::block_signing::jailing::JailedRange;
+use crate::rewarder::block_signing::policy::{redistribute_forfeited, SigningRewardPolicy};
+use crate::rewarder::helpers::{
+    consensus_pubkey_to_address, operator_account_to_owner_account, parse_payout_address,
+};
 use cosmwasm_std::{Decimal, Uint128};
 use nym_validator_client::nyxd::module_traits::staking;
 use nym_validator_client::nyxd::{AccountId, Coin};
 use nyxd_scraper::models;
 use std::collections::HashMap;
-use tracing::info;
+use tracing::{info, warn};
 
 #[derive(Debug)]
 pub struct ValidatorSigning {
     pub validator: models::Validator,
     pub staking_details: staking::Validator,
     pub operator_account: AccountId,
+
+    /// Address receiving the rewards. Unless overridden by the validator, it's the same as the `operator_account`.
+    pub payout_account: AccountId,
     pub whitelisted: bool,
 
     pub voting_power_at_epoch_start: i64,
+    pub voting_power_height: i64,
     pub voting_power_ratio: Decimal,
 
+    /// Ranges of blocks during which the validator has been jailed.
+    pub jailed_ranges: Vec<JailedRange>,
+
+    /// Number of blocks the validator was expected to sign, i.e. all blocks in the epoch
+    /// apart from the ones produced while it was jailed.
+    pub expected_blocks: i64,
+
     pub signed_blocks: i32,
+
+    /// Ratio of the signed blocks to the expected blocks.
     pub ratio_signed: Decimal,
+
+    /// Share of the block signing budget the validator is going to receive, i.e. the share determined
+    /// by the configured reward policy alongside any forfeited rewards redistributed to it.
+    pub reward_share: Decimal,
+
+    /// Indicates whether the validator has signed fewer than the minimum ratio of blocks
+    /// and thus forfeited its entire reward.
+    pub forfeited: bool,
+
+    /// Share of the block signing budget the validator has forfeited.
+    pub forfeited_share: Decimal,
+
+    /// Part of the `reward_share` that got redistributed from the validators that forfeited their rewards.
+    pub redistributed_share: Decimal,
 }
 
 impl ValidatorSigning {
@@ -33,16 +64,41 @@ impl ValidatorSigning {
             .unwrap_or("UNKNOWN MONIKER".to_string())
     }
 
-    pub fn reward_amount(&self, signing_budget: &Coin) -> Coin {
+    pub fn payout_override(&self) -> Option<&AccountId> {
+        (self.payout_account != self.operator_account).then_some(&self.payout_account)
+    }
+
+    fn budget_share(&self, signing_budget: &Coin, share: Decimal) -> Coin {
         if !self.whitelisted {
             return Coin::new(0, &signing_budget.denom);
         }
 
-        let amount =
-            Uint128::new(signing_budget.amount) * self.ratio_signed * self.voting_power_ratio;
+        let amount = Uint128::new(signing_budget.amount) * share;
 
         Coin::new(amount.u128(), &signing_budget.denom)
     }
+
+    pub fn reward_amount(&self, signing_budget: &Coin) -> Coin {
+        self.budget_share(signing_budget, self.reward_share)
+    }
+
+    pub fn forfeited_amount(&self, signing_budget: &Coin) -> Option<Coin> {
+        self.forfeited
+            .then(|| self.budget_share(signing_budget, self.forfeited_share))
+    }
+
+    pub fn redistributed_amount(&self, signing_budget: &Coin) -> Option<Coin> {
+        (!self.redistributed_share.is_zero())
+            .then(|| self.budget_share(signing_budget, self.redistributed_share))
+    }
+
+    /// Checks whether the validator has signed at least the minimum ratio of the blocks it was expected to sign.
+    /// A validator that has been jailed for the entire epoch had nothing to sign, and it doesn't qualify
+    /// for any rewards regardless of the threshold.
+    fn meets_minimum_uptime(&self, minimum_uptime: f64) -> bool {
+        self.expected_blocks > 0
+            && self.signed_blocks as f64 >= minimum_uptime * self.expected_blocks as f64
+    }
 }
 
 #[derive(Debug)]
@@ -57,17 +113,37 @@ pub struct EpochSigningResults {
 pub struct RawValidatorResult {
     pub signed_blocks: i32,
     pub voting_power: i64,
+    pub voting_power_height: i64,
     pub whitelisted: bool,
+    pub jailed_ranges: Vec<JailedRange>,
 }
 
 impl RawValidatorResult {
-    pub fn new(signed_blocks: i32, voting_power: i64, whitelisted: bool) -> Self {
+    pub fn new(
+        signed_blocks: i32,
+        voting_power: i64,
+        voting_power_height: i64,
+        whitelisted: bool,
+    ) -> Self {
         Self {
             signed_blocks,
             voting_power,
+            voting_power_height,
             whitelisted,
+            jailed_ranges: Vec::new(),
         }
     }
+
+    #[must_use]
+    pub fn with_jailed_ranges(mut self, jailed_ranges: Vec<JailedRange>) -> Self {
+        self.jailed_ranges = jailed_ranges;
+        self
+    }
+
+    fn expected_blocks(&self, blocks: i64) -> i64 {
+        let jailed: i64 = self.jailed_ranges.iter().map(JailedRange::blocks).sum();
+        (blocks - jailed).max(0)
+    }
 }
 
 impl EpochSigningResults {
@@ -76,13 +152,16 @@ impl EpochSigningResults {
         total_vp: i64,
         validator_results: HashMap<models::Validator, RawValidatorResult>,
         validator_details: Vec<staking::Validator>,
+        policy: &dyn SigningRewardPolicy,
+        minimum_uptime: f64,
+        allow_payout_overrides: bool,
     ) -> Result<Self, NymRewarderError> {
         let Ok(total_vp_u64): Result<u64, _> = total_vp.try_into() else {
             return Err(NymRewarderError::NegativeTotalVotingPower { val: total_vp });
         };
-        let Ok(blocks_u64): Result<u64, _> = blocks.try_into() else {
+        if blocks < 0 {
             return Err(NymRewarderError::NegativeSignedBlocks { val: blocks });
-        };
+        }
 
         let mut validator_details: HashMap<_, _> = validator_details
             .into_iter()
@@ -107,8 +186,15 @@ impl EpochSigningResults {
                 Decimal::zero()
             };
 
-            debug_assert!(signed <= blocks_u64);
-            let ratio_signed = Decimal::from_ratio(signed, blocks_u64);
+            // don't penalise the validators for the blocks they could not have signed
+            let expected_blocks = raw_results.expected_blocks(blocks);
+            let ratio_signed = if expected_blocks == 0 {
+                Decimal::zero()
+            } else {
+                // the boundaries of the jailed ranges are approximate,
+                // so the validator might appear to have signed slightly more than expected
+                Decimal::from_ratio(signed.min(expected_blocks as u64), expected_blocks as u64)
+            };
             let staking_details = validator_details
                 .remove(&validator.consensus_address)
                 .ok_or_else(|| NymRewarderError::MissingValidatorDetails {
@@ -118,18 +204,78 @@ impl EpochSigningResults {
             let operator_account =
                 operator_account_to_owner_account(&staking_details.operator_address)?;
 
+            let details = staking_details
+                .description
+                .as_ref()
+                .map(|d| d.details.as_str());
+            let payout_account = match details {
+                Some(details) if allow_payout_overrides => {
+                    // an invalid override shouldn't affect the entire epoch, so just fall back to the operator account
+                    match parse_payout_address(&operator_account, details) {
+                        Ok(payout) => payout.unwrap_or_else(|| operator_account.clone()),
+                        Err(err) => {
+                            warn!("{err}. the rewards are going to be sent to the operator account instead");
+                            operator_account.clone()
+                        }
+                    }
+                }
+                _ => operator_account.clone(),
+            };
+
             validators.push(ValidatorSigning {
                 validator,
                 staking_details,
                 operator_account,
+                payout_account,
                 whitelisted: raw_results.whitelisted,
                 voting_power_at_epoch_start: raw_results.voting_power,
+                voting_power_height: raw_results.voting_power_height,
                 voting_power_ratio,
+                jailed_ranges: raw_results.jailed_ranges,
+                expected_blocks,
                 signed_blocks: raw_results.signed_blocks,
                 ratio_signed,
+                reward_share: Decimal::zero(),
+                forfeited: false,
+                forfeited_share: Decimal::zero(),
+                redistributed_share: Decimal::zero(),
             })
         }
 
+        info!(
+            "determining block signing rewards using the '{}' policy",
+            policy.name()
+        );
+        let shares = policy.reward_shares(blocks, &validators);
+
+        // whoever hasn't signed enough blocks forfeits the reward in favour of everyone else
+        let qualifying = validators
+            .iter()
+            .map(|v| !v.whitelisted || v.meets_minimum_uptime(minimum_uptime))
+            .collect::<Vec<_>>();
+        let redistributed = redistribute_forfeited(&shares, &qualifying);
+
+        for (((validator, share), qualifies), extra) in validators
+            .iter_mut()
+            .zip(shares)
+            .zip(qualifying)
+            .zip(redistributed)
+        {
+            if qualifies {
+                validator.reward_share = share + extra;
+                validator.redistributed_share = extra;
+            } else {
+                info!(
+                    "validator {} has signed {}/{} blocks, which is below the required minimum. it forfeits its reward",
+                    validator.moniker(),
+                    validator.signed_blocks,
+                    validator.expected_blocks
+                );
+                validator.forfeited = true;
+                validator.forfeited_share = share;
+            }
+        }
+
         Ok(EpochSigningResults {
             blocks,
             total_voting_power_at_epoch_start: total_vp,
@@ -145,11 +291,11 @@ impl EpochSigningResults {
                     "validator {} will receive {} at address {} for block signing work (whitelisted: {})",
                     v.moniker(),
                     v.reward_amount(budget),
-                    v.operator_account,
+                    v.payout_account,
                     v.whitelisted
                 );
             })
-            .map(|v| (v.operator_account.clone(), vec![v.reward_amount(budget)]))
+            .map(|v| (v.payout_account.clone(), vec![v.reward_amount(budget)]))
             .collect()
     }
 }
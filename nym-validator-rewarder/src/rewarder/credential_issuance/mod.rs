@@ -4,6 +4,7 @@
 use crate::config;
 use crate::error::NymRewarderError;
 use crate::rewarder::credential_issuance::monitor::CredentialIssuanceMonitor;
+use crate::rewarder::credential_issuance::prober::IssuerApiProber;
 use crate::rewarder::credential_issuance::types::{CredentialIssuanceResults, MonitoringResults};
 use crate::rewarder::epoch::Epoch;
 use crate::rewarder::nyxd_client::NyxdClient;
@@ -13,11 +14,14 @@ use nym_validator_client::nyxd::AccountId;
 use tracing::info;
 
 mod monitor;
+pub(crate) mod prober;
 pub mod types;
+pub mod violation;
 
 pub struct CredentialIssuance {
     monitoring_results: MonitoringResults,
     storage: RewarderStorage,
+    minimum_api_availability: f64,
 }
 
 impl CredentialIssuance {
@@ -26,11 +30,13 @@ impl CredentialIssuance {
         storage: RewarderStorage,
         nyxd_client: &NyxdClient,
         whitelist: &[AccountId],
+        minimum_api_availability: f64,
     ) -> Result<Self, NymRewarderError> {
         Ok(CredentialIssuance {
             monitoring_results: MonitoringResults::new_initial(epoch, nyxd_client, whitelist)
                 .await?,
             storage,
+            minimum_api_availability,
         })
     }
 
@@ -51,6 +57,17 @@ impl CredentialIssuance {
         tokio::spawn(async move { monitor.run(task_client).await });
     }
 
+    pub(crate) fn start_api_prober(
+        &self,
+        monitor_config: config::IssuanceMonitor,
+        nyxd_client: NyxdClient,
+        task_client: TaskClient,
+    ) {
+        let prober = IssuerApiProber::new(monitor_config, nyxd_client, self.storage.clone());
+
+        tokio::spawn(async move { prober.run(task_client).await });
+    }
+
     pub(crate) async fn get_issued_credentials_results(
         &self,
         current_epoch: Epoch,
@@ -64,6 +81,30 @@ impl CredentialIssuance {
 
         let raw_results = self.monitoring_results.finish_epoch().await;
 
-        Ok(raw_results.into())
+        self.with_adjusted_rewards(current_epoch, raw_results.into())
+            .await
+    }
+
+    /// Get the credential issuance results gathered so far in the current epoch, without finishing it.
+    pub(crate) async fn peek_issued_credentials_results(
+        &self,
+    ) -> Result<CredentialIssuanceResults, NymRewarderError> {
+        let snapshot = self.monitoring_results.current_snapshot().await;
+        let epoch = snapshot.epoch;
+
+        self.with_adjusted_rewards(epoch, snapshot.into()).await
+    }
+
+    async fn with_adjusted_rewards(
+        &self,
+        epoch: Epoch,
+        mut results: CredentialIssuanceResults,
+    ) -> Result<CredentialIssuanceResults, NymRewarderError> {
+        let flagged = self.storage.get_flagged_issuers().await?;
+        results.withhold_rewards(&flagged);
+
+        let availability = self.storage.get_issuer_api_availability(epoch).await?;
+        results.apply_api_availability(&availability, self.minimum_api_availability);
+        Ok(results)
     }
 }
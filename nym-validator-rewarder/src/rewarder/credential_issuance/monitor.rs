@@ -6,6 +6,7 @@ use crate::error::NymRewarderError;
 use crate::rewarder::credential_issuance::types::{
     CredentialIssuer, MonitoringResults, RawOperatorResult,
 };
+use crate::rewarder::credential_issuance::violation::IssuanceViolationReason;
 use crate::rewarder::helpers::api_client;
 use crate::rewarder::nyxd_client::NyxdClient;
 use crate::rewarder::storage::RewarderStorage;
@@ -21,7 +22,7 @@ use nym_task::TaskClient;
 use nym_validator_client::nym_api::{IssuedCredential, IssuedCredentialBody, NymApiClientExt};
 use std::cmp::max;
 use tokio::time::interval;
-use tracing::{debug, error, info, instrument, trace};
+use tracing::{debug, error, info, instrument, trace, warn};
 
 pub struct CredentialIssuanceMonitor {
     nyxd_client: NyxdClient,
@@ -213,6 +214,28 @@ impl CredentialIssuanceMonitor {
         Ok(())
     }
 
+    /// Flag the issuer if the provided error is attributable to its misbehaviour.
+    async fn record_violation(
+        &self,
+        epoch_id: EpochId,
+        issuer: &CredentialIssuer,
+        credential_id: Option<i64>,
+        err: &NymRewarderError,
+    ) -> Result<(), NymRewarderError> {
+        let Some(reason) = IssuanceViolationReason::from_error(err) else {
+            return Ok(());
+        };
+
+        warn!(
+            "flagging {} for '{reason}' violation. its issuance rewards are going to be withheld until it's cleared",
+            issuer.operator_account
+        );
+        self.storage
+            .insert_issuance_violation(issuer, epoch_id, credential_id, reason, err.to_string())
+            .await?;
+        Ok(())
+    }
+
     fn sample_credential_ids(&self, first_id: i64, total_issued: i64) -> Vec<i64> {
         let credential_range: Vec<_> = (first_id..first_id + total_issued).collect();
         let issued = credential_range.len();
@@ -267,11 +290,13 @@ impl CredentialIssuanceMonitor {
         let credentials = api_client.issued_credentials(sampled.clone()).await?;
         if credentials.credentials.len() != request_size {
             error!("received an incomplete credential request! the issuer **MIGHT** be cheating!! but we're lacking sufficient signatures to be certain");
-            return Err(NymRewarderError::IncompleteRequest {
+            let err = NymRewarderError::IncompleteRequest {
                 runner_account: issuer.operator_account.clone(),
                 requested: request_size,
                 received: credentials.credentials.len(),
-            });
+            };
+            self.record_violation(epoch_id, issuer, None, &err).await?;
+            return Err(err);
         }
 
         for (id, credential) in credentials.credentials {
@@ -284,6 +309,8 @@ impl CredentialIssuanceMonitor {
                 self.storage
                     .insert_issuance_foul_play_evidence(issuer, &credential, err.to_string())
                     .await?;
+                self.record_violation(epoch_id, issuer, Some(credential.credential.id), &err)
+                    .await?;
                 return Err(err);
             }
         }
@@ -0,0 +1,163 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config;
+use crate::error::NymRewarderError;
+use crate::rewarder::credential_issuance::types::CredentialIssuer;
+use crate::rewarder::nyxd_client::NyxdClient;
+use crate::rewarder::storage::RewarderStorage;
+use nym_coconut_dkg_common::types::EpochId;
+use nym_task::TaskClient;
+use nym_validator_client::nym_api::{self, error::NymAPIError, NymApiClientExt};
+use std::error::Error;
+use std::time::Instant;
+use time::OffsetDateTime;
+use tokio::time::interval;
+use tracing::{debug, error, info, warn};
+
+/// Result of a single probe of the api endpoint of a credential issuer.
+#[derive(Debug, Clone)]
+pub(crate) struct ApiProbeResult {
+    pub(crate) probed_at: OffsetDateTime,
+
+    /// Indicates whether the api has responded in time and the issuer's key share is available,
+    /// i.e. whether the issuer could have issued any credentials.
+    pub(crate) available: bool,
+
+    /// Time it took the api to respond, if it did.
+    pub(crate) latency_ms: Option<i64>,
+
+    /// Indicates whether the tls certificate of the endpoint is valid. `None` for plain http endpoints.
+    pub(crate) tls_valid: Option<bool>,
+
+    /// Indicates whether the issuer's verification key share has been verified in the DKG contract.
+    pub(crate) key_available: bool,
+    pub(crate) error: Option<String>,
+}
+
+impl ApiProbeResult {
+    fn failed(issuer: &CredentialIssuer, tls_valid: Option<bool>, error: String) -> Self {
+        ApiProbeResult {
+            probed_at: OffsetDateTime::now_utc(),
+            available: false,
+            latency_ms: None,
+            tls_valid,
+            key_available: issuer.key_share_verified,
+            error: Some(error),
+        }
+    }
+}
+
+/// Attempt to determine whether the request has failed due to an invalid tls certificate of the remote.
+fn is_tls_failure(err: &NymAPIError) -> bool {
+    let mut source: Option<&(dyn Error + 'static)> = Some(err);
+    while let Some(err) = source {
+        let message = err.to_string().to_lowercase();
+        if message.contains("certificate") || message.contains("tls") {
+            return true;
+        }
+        source = err.source();
+    }
+    false
+}
+
+/// Periodically probes the api endpoints of all credential issuers and records their availability,
+/// which is later factored into their issuance rewards.
+pub struct IssuerApiProber {
+    nyxd_client: NyxdClient,
+    config: config::IssuanceMonitor,
+    storage: RewarderStorage,
+}
+
+impl IssuerApiProber {
+    pub fn new(
+        config: config::IssuanceMonitor,
+        nyxd_client: NyxdClient,
+        storage: RewarderStorage,
+    ) -> IssuerApiProber {
+        IssuerApiProber {
+            nyxd_client,
+            config,
+            storage,
+        }
+    }
+
+    async fn probe_issuer(&self, epoch_id: EpochId, issuer: &CredentialIssuer) -> ApiProbeResult {
+        let uses_tls = issuer.api_runner.starts_with("https://");
+
+        let url = match issuer.api_runner.parse() {
+            Ok(url) => url,
+            Err(err) => return ApiProbeResult::failed(issuer, None, err.to_string()),
+        };
+        let api_client = nym_api::Client::new(url, Some(self.config.api_probe_timeout));
+
+        let start = Instant::now();
+        match api_client.epoch_credentials(epoch_id).await {
+            Ok(_) => {
+                let latency = start.elapsed();
+                let error = (!issuer.key_share_verified)
+                    .then(|| "the verification key share has not been verified".to_string());
+                ApiProbeResult {
+                    probed_at: OffsetDateTime::now_utc(),
+                    available: issuer.key_share_verified,
+                    latency_ms: Some(latency.as_millis() as i64),
+                    tls_valid: uses_tls.then_some(true),
+                    key_available: issuer.key_share_verified,
+                    error,
+                }
+            }
+            Err(err) => {
+                let tls_valid = uses_tls.then(|| !is_tls_failure(&err));
+                ApiProbeResult::failed(issuer, tls_valid, err.to_string())
+            }
+        }
+    }
+
+    async fn probe_issuers(&self) -> Result<(), NymRewarderError> {
+        debug!("probing credential issuer apis");
+        let epoch = self.nyxd_client.dkg_epoch().await?;
+        let issuers = self
+            .nyxd_client
+            .get_credential_issuers(epoch.epoch_id)
+            .await?;
+
+        let probes = futures::future::join_all(
+            issuers
+                .iter()
+                .map(|issuer| self.probe_issuer(epoch.epoch_id, issuer)),
+        )
+        .await;
+
+        for (issuer, probe) in issuers.iter().zip(probes) {
+            if let Some(err) = &probe.error {
+                warn!(
+                    "the api of {} ({}) is unavailable: {err}",
+                    issuer.operator_account, issuer.api_runner
+                );
+            }
+            self.storage.insert_issuer_api_probe(issuer, probe).await?;
+        }
+
+        Ok(())
+    }
+
+    pub async fn run(&self, mut task_client: TaskClient) {
+        info!("starting");
+        let mut probe_interval = interval(self.config.api_probe_interval);
+
+        while !task_client.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = task_client.recv() => {
+                    info!("received shutdown");
+                    break
+                }
+                _ = probe_interval.tick() => {
+                    if let Err(err) = self.probe_issuers().await {
+                        error!("failed to probe credential issuer apis: {err}")
+                    }
+                }
+            }
+        }
+    }
+}
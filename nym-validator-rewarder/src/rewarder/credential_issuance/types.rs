@@ -5,6 +5,7 @@ use crate::error::NymRewarderError;
 use crate::rewarder::epoch::Epoch;
 use crate::rewarder::helpers::api_client;
 use crate::rewarder::nyxd_client::NyxdClient;
+use crate::rewarder::storage::models::IssuerApiAvailability;
 use cosmwasm_std::{Addr, Decimal, Uint128};
 use nym_coconut::VerificationKey;
 use nym_crypto::asymmetric::ed25519;
@@ -106,6 +107,11 @@ impl MonitoringResults {
         }
     }
 
+    /// Get the results gathered so far without resetting any of the counters.
+    pub(crate) async fn current_snapshot(&self) -> MonitoringResultsInner {
+        self.inner.lock().await.clone()
+    }
+
     pub(crate) async fn finish_epoch(&self) -> MonitoringResultsInner {
         let mut guard = self.inner.lock().await;
         let next_epoch = guard.epoch.next();
@@ -150,6 +156,7 @@ impl MonitoringResults {
     }
 }
 
+#[derive(Clone)]
 pub(crate) struct MonitoringResultsInner {
     pub(crate) epoch: Epoch,
     pub(crate) dkg_epochs: Vec<u32>,
@@ -193,6 +200,8 @@ impl From<MonitoringResultsInner> for CredentialIssuanceResults {
                         validated_credentials: runner.validated_credentials(),
                         api_runner: runner.api_runner,
                         whitelisted: runner.whitelisted,
+                        withheld: false,
+                        api_availability: Decimal::one(),
                         runner_account: runner.runner_account,
                     }
                 })
@@ -241,6 +250,7 @@ impl RawOperatorResult {
     }
 }
 
+#[derive(Clone)]
 pub struct RawOperatorIssuing {
     pub api_runner: String,
     pub runner_account: AccountId,
@@ -274,6 +284,7 @@ impl RawOperatorIssuing {
     }
 }
 
+#[derive(Clone)]
 pub struct IssuedEpochCredentials {
     pub issued_since_monitor_started: u32,
     pub validated_ids: HashSet<i64>,
@@ -293,8 +304,14 @@ impl IssuedEpochCredentials {
 pub struct OperatorIssuing {
     pub api_runner: String,
     pub whitelisted: bool,
+
+    /// Indicates whether the operator has unresolved issuance violations and thus its rewards are withheld.
+    pub withheld: bool,
     pub runner_account: AccountId,
 
+    /// Multiplier applied to the reward of the operator if its api has not been sufficiently available.
+    pub api_availability: Decimal,
+
     pub issued_ratio: Decimal,
     pub issued_credentials: u32,
     pub validated_credentials: u32,
@@ -302,11 +319,12 @@ pub struct OperatorIssuing {
 
 impl OperatorIssuing {
     pub fn reward_amount(&self, issuance_budget: &Coin) -> Coin {
-        if !self.whitelisted {
+        if !self.whitelisted || self.withheld {
             return Coin::new(0, &issuance_budget.denom);
         }
 
-        let amount = Uint128::new(issuance_budget.amount) * self.issued_ratio;
+        let amount =
+            Uint128::new(issuance_budget.amount) * (self.issued_ratio * self.api_availability);
 
         Coin::new(amount.u128(), &issuance_budget.denom)
     }
@@ -319,16 +337,57 @@ pub struct CredentialIssuanceResults {
 }
 
 impl CredentialIssuanceResults {
+    /// Withhold rewards of all operators with unresolved issuance violations.
+    pub fn withhold_rewards(&mut self, flagged: &HashSet<String>) {
+        for operator in &mut self.api_runners {
+            if flagged.contains(operator.runner_account.as_ref()) {
+                warn!(
+                    "operator {} ({}) has unresolved credential issuance violations. its rewards are going to be withheld",
+                    operator.api_runner, operator.runner_account
+                );
+                operator.withheld = true;
+            }
+        }
+    }
+
+    /// Scale down rewards of all operators whose apis have been available for less than the minimum ratio
+    /// of the probes. Operators without any recorded probes are not affected.
+    pub fn apply_api_availability(
+        &mut self,
+        availability: &HashMap<String, IssuerApiAvailability>,
+        minimum_availability: f64,
+    ) {
+        for operator in &mut self.api_runners {
+            let Some(probed) = availability.get(operator.runner_account.as_ref()) else {
+                continue;
+            };
+            if probed.probes <= 0 {
+                continue;
+            }
+
+            let successful = probed.successful.clamp(0, probed.probes);
+            if (successful as f64) < minimum_availability * probed.probes as f64 {
+                warn!(
+                    "the api of operator {} ({}) has only been available for {successful}/{} probes. its rewards are going to be scaled down",
+                    operator.api_runner, operator.runner_account, probed.probes
+                );
+                operator.api_availability =
+                    Decimal::from_ratio(successful as u64, probed.probes as u64);
+            }
+        }
+    }
+
     pub fn rewarding_amounts(&self, budget: &Coin) -> Vec<(AccountId, Vec<Coin>)> {
         self.api_runners
             .iter()
             .inspect(|a| {
                 info!(
-                    "operator {} will receive {} at address {} for credential issuance work (whitelisted: {})",
+                    "operator {} will receive {} at address {} for credential issuance work (whitelisted: {}, withheld: {})",
                     a.api_runner,
                     a.reward_amount(budget),
                     a.runner_account,
-                    a.whitelisted
+                    a.whitelisted,
+                    a.withheld
                 );
             })
             .map(|v| (v.runner_account.clone(), vec![v.reward_amount(budget)]))
@@ -336,12 +395,15 @@ impl CredentialIssuanceResults {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct CredentialIssuer {
     pub public_key: ed25519::PublicKey,
     pub operator_account: AccountId,
     pub api_runner: String,
     pub verification_key: VerificationKey,
+
+    /// Indicates whether the issuer's verification key share has been verified in the DKG contract.
+    pub key_share_verified: bool,
 }
 
 // safety: we're converting between different wrappers for bech32 addresses
@@ -350,3 +412,58 @@ pub(crate) fn addr_to_account_id(addr: Addr) -> AccountId {
     #[allow(clippy::unwrap_used)]
     addr.as_str().parse().unwrap()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rewarder::testing::test_account;
+
+    fn operator(seed: u8) -> OperatorIssuing {
+        OperatorIssuing {
+            api_runner: format!("https://issuer{seed}.example.com"),
+            whitelisted: true,
+            withheld: false,
+            runner_account: test_account(seed),
+            api_availability: Decimal::one(),
+            issued_ratio: Decimal::percent(50),
+            issued_credentials: 100,
+            validated_credentials: 10,
+        }
+    }
+
+    fn availability(seed: u8, probes: i64, successful: i64) -> (String, IssuerApiAvailability) {
+        let operator_account = test_account(seed).to_string();
+        (
+            operator_account.clone(),
+            IssuerApiAvailability {
+                operator_account,
+                probes,
+                successful,
+            },
+        )
+    }
+
+    #[test]
+    fn unavailable_apis_get_their_rewards_scaled_down() {
+        let mut results = CredentialIssuanceResults {
+            total_issued_partial_credentials: 300,
+            dkg_epochs: vec![1],
+            api_runners: vec![operator(1), operator(2), operator(3)],
+        };
+        let probed = [availability(1, 60, 57), availability(2, 60, 30)]
+            .into_iter()
+            .collect();
+
+        results.apply_api_availability(&probed, 0.9);
+
+        let budget = Coin::new(1000, "unym");
+        let rewards = results
+            .api_runners
+            .iter()
+            .map(|r| r.reward_amount(&budget).amount)
+            .collect::<Vec<_>>();
+
+        // above the threshold, below the threshold and never probed
+        assert_eq!(rewards, vec![500, 250, 500]);
+    }
+}
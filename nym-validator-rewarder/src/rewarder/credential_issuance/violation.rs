@@ -0,0 +1,245 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use serde::{Serialize, Serializer};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use time::format_description::well_known::Rfc3339;
+use time::OffsetDateTime;
+
+/// Kind of misbehaviour of a credential issuer detected by the issuance monitor.
+/// Unlike other validation failures, such as an unreachable api, those are attributable to the issuer
+/// and result in its issuance rewards being withheld until the violation is cleared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum IssuanceViolationReason {
+    /// The issued credential information has not been signed with the issuer's identity key.
+    InvalidSignature,
+
+    /// The same deposit has been used for issuing multiple credentials.
+    DepositReuse,
+
+    /// The referenced transaction does not contain a valid deposit.
+    NonDepositTransaction,
+
+    /// The value of the credential does not match the on-chain deposit.
+    InconsistentDepositValue,
+
+    /// The deposit information of the credential does not match the on-chain deposit.
+    InconsistentDepositInfo,
+
+    /// The private attribute commitments of the credential request are malformed.
+    MalformedCommitment,
+
+    /// The issued partial credential does not verify against the issuer's verification key.
+    InvalidPartialCredential,
+
+    /// The issuer has not returned all the requested credentials.
+    IncompleteResponse,
+}
+
+impl IssuanceViolationReason {
+    /// Attempt to classify the provided credential validation error as a violation.
+    pub fn from_error(err: &NymRewarderError) -> Option<Self> {
+        match err {
+            NymRewarderError::SignatureVerificationFailure { .. } => Some(Self::InvalidSignature),
+            NymRewarderError::DuplicateDepositHash { .. } => Some(Self::DepositReuse),
+            NymRewarderError::DepositValueNotFound { .. }
+            | NymRewarderError::DepositInfoNotFound { .. } => Some(Self::NonDepositTransaction),
+            NymRewarderError::InconsistentDepositValue { .. } => {
+                Some(Self::InconsistentDepositValue)
+            }
+            NymRewarderError::InconsistentDepositInfo { .. } => Some(Self::InconsistentDepositInfo),
+            NymRewarderError::MalformedCredentialCommitment { .. } => {
+                Some(Self::MalformedCommitment)
+            }
+            NymRewarderError::BlindVerificationFailure => Some(Self::InvalidPartialCredential),
+            NymRewarderError::IncompleteRequest { .. } => Some(Self::IncompleteResponse),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IssuanceViolationReason::InvalidSignature => "invalid_signature",
+            IssuanceViolationReason::DepositReuse => "deposit_reuse",
+            IssuanceViolationReason::NonDepositTransaction => "non_deposit_transaction",
+            IssuanceViolationReason::InconsistentDepositValue => "inconsistent_deposit_value",
+            IssuanceViolationReason::InconsistentDepositInfo => "inconsistent_deposit_info",
+            IssuanceViolationReason::MalformedCommitment => "malformed_commitment",
+            IssuanceViolationReason::InvalidPartialCredential => "invalid_partial_credential",
+            IssuanceViolationReason::IncompleteResponse => "incomplete_response",
+        }
+    }
+}
+
+impl Display for IssuanceViolationReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl FromStr for IssuanceViolationReason {
+    type Err = NymRewarderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "invalid_signature" => Ok(IssuanceViolationReason::InvalidSignature),
+            "deposit_reuse" => Ok(IssuanceViolationReason::DepositReuse),
+            "non_deposit_transaction" => Ok(IssuanceViolationReason::NonDepositTransaction),
+            "inconsistent_deposit_value" => Ok(IssuanceViolationReason::InconsistentDepositValue),
+            "inconsistent_deposit_info" => Ok(IssuanceViolationReason::InconsistentDepositInfo),
+            "malformed_commitment" => Ok(IssuanceViolationReason::MalformedCommitment),
+            "invalid_partial_credential" => Ok(IssuanceViolationReason::InvalidPartialCredential),
+            "incomplete_response" => Ok(IssuanceViolationReason::IncompleteResponse),
+            other => Err(NymRewarderError::UnknownIssuanceViolationReason {
+                reason: other.to_string(),
+            }),
+        }
+    }
+}
+
+fn serialize_rfc3339<S: Serializer>(
+    timestamp: &OffsetDateTime,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    let formatted = timestamp
+        .format(&Rfc3339)
+        .map_err(serde::ser::Error::custom)?;
+    serializer.serialize_str(&formatted)
+}
+
+fn serialize_optional_rfc3339<S: Serializer>(
+    timestamp: &Option<OffsetDateTime>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    match timestamp {
+        Some(timestamp) => serialize_rfc3339(timestamp, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuanceViolation {
+    pub id: i64,
+    pub operator_account: String,
+    pub operator_identity_bs58: String,
+    pub dkg_epoch: i64,
+    pub credential_id: Option<i64>,
+    pub reason: IssuanceViolationReason,
+    pub details: String,
+
+    #[serde(serialize_with = "serialize_rfc3339")]
+    pub detected_at: OffsetDateTime,
+
+    #[serde(serialize_with = "serialize_optional_rfc3339")]
+    pub cleared_at: Option<OffsetDateTime>,
+    pub clearing_note: Option<String>,
+}
+
+impl IssuanceViolation {
+    pub fn is_active(&self) -> bool {
+        self.cleared_at.is_none()
+    }
+}
+
+impl Display for IssuanceViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "[{}] {} ({}) in dkg epoch {}: {}",
+            self.id,
+            self.operator_account,
+            self.operator_identity_bs58,
+            self.dkg_epoch,
+            self.reason
+        )?;
+        if let Some(credential_id) = self.credential_id {
+            write!(f, " (credential {credential_id})")?;
+        }
+        write!(f, " - {}", self.details)?;
+        match (&self.cleared_at, &self.clearing_note) {
+            (Some(cleared_at), Some(note)) => write!(f, " [cleared at {cleared_at}: {note}]"),
+            (Some(cleared_at), None) => write!(f, " [cleared at {cleared_at}]"),
+            _ => write!(f, " [ACTIVE]"),
+        }
+    }
+}
+
+#[derive(sqlx::FromRow)]
+pub(crate) struct RawIssuanceViolation {
+    pub(crate) id: i64,
+    pub(crate) operator_account: String,
+    pub(crate) operator_identity_bs58: String,
+    pub(crate) dkg_epoch: i64,
+    pub(crate) credential_id: Option<i64>,
+    pub(crate) reason: String,
+    pub(crate) details: String,
+    pub(crate) detected_at: OffsetDateTime,
+    pub(crate) cleared_at: Option<OffsetDateTime>,
+    pub(crate) clearing_note: Option<String>,
+}
+
+impl TryFrom<RawIssuanceViolation> for IssuanceViolation {
+    type Error = NymRewarderError;
+
+    fn try_from(value: RawIssuanceViolation) -> Result<Self, Self::Error> {
+        Ok(IssuanceViolation {
+            id: value.id,
+            operator_account: value.operator_account,
+            operator_identity_bs58: value.operator_identity_bs58,
+            dkg_epoch: value.dkg_epoch,
+            credential_id: value.credential_id,
+            reason: value.reason.parse()?,
+            details: value.details,
+            detected_at: value.detected_at,
+            cleared_at: value.cleared_at,
+            clearing_note: value.clearing_note,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reason_string_roundtrip() {
+        for reason in [
+            IssuanceViolationReason::InvalidSignature,
+            IssuanceViolationReason::DepositReuse,
+            IssuanceViolationReason::NonDepositTransaction,
+            IssuanceViolationReason::InconsistentDepositValue,
+            IssuanceViolationReason::InconsistentDepositInfo,
+            IssuanceViolationReason::MalformedCommitment,
+            IssuanceViolationReason::InvalidPartialCredential,
+            IssuanceViolationReason::IncompleteResponse,
+        ] {
+            assert_eq!(
+                reason.as_str().parse::<IssuanceViolationReason>().unwrap(),
+                reason
+            );
+            assert_eq!(
+                serde_json::to_string(&reason).unwrap(),
+                format!("\"{reason}\"")
+            );
+        }
+        assert!("foomp".parse::<IssuanceViolationReason>().is_err());
+    }
+
+    #[test]
+    fn only_attributable_errors_are_violations() {
+        assert_eq!(
+            IssuanceViolationReason::from_error(&NymRewarderError::BlindVerificationFailure),
+            Some(IssuanceViolationReason::InvalidPartialCredential)
+        );
+        assert_eq!(
+            IssuanceViolationReason::from_error(&NymRewarderError::SignatureVerificationFailure {
+                credential_id: 42
+            }),
+            Some(IssuanceViolationReason::InvalidSignature)
+        );
+        assert!(IssuanceViolationReason::from_error(&NymRewarderError::PayoutsPaused).is_none());
+    }
+}
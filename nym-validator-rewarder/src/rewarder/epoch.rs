@@ -10,6 +10,39 @@ use time::OffsetDateTime;
 
 const HOUR: Duration = Duration::from_secs(60 * 60);
 
+/// Boundaries of the current epoch of the mixnet contract.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChainEpoch {
+    pub start_time: OffsetDateTime,
+    pub length: Duration,
+}
+
+impl ChainEpoch {
+    pub fn end_time(&self) -> OffsetDateTime {
+        self.start_time + self.length
+    }
+
+    /// Returns the first epoch boundary of the chain that is strictly after the provided time.
+    /// Note that the chain epochs are advanced by a transaction, so the current one might be overdue,
+    /// in which case the subsequent boundaries are extrapolated using the current epoch length.
+    pub fn next_boundary_after(&self, time: OffsetDateTime) -> OffsetDateTime {
+        let end = self.end_time();
+        if end > time || self.length.is_zero() {
+            return end;
+        }
+
+        let overdue = (time - end).unsigned_abs();
+        let elapsed_epochs = (overdue.as_secs_f64() / self.length.as_secs_f64()).floor() as u32 + 1;
+        let mut boundary = end + self.length * elapsed_epochs;
+
+        // guard against any floating point shenanigans
+        while boundary <= time {
+            boundary += self.length
+        }
+        boundary
+    }
+}
+
 #[derive(Debug, Clone, Copy, FromRow)]
 pub struct Epoch {
     pub id: i64,
@@ -34,6 +67,22 @@ impl Epoch {
         })
     }
 
+    /// Create the first epoch that is aligned with the epochs of the mixnet contract.
+    /// Similarly to [`Epoch::first`], it's not going to start until the current chain epoch is over.
+    pub fn first_aligned(chain_epoch: &ChainEpoch) -> Self {
+        let start = chain_epoch.next_boundary_after(OffsetDateTime::now_utc());
+
+        Epoch {
+            id: 0,
+            start_time: start,
+            end_time: start + chain_epoch.length,
+        }
+    }
+
+    pub fn duration(&self) -> Duration {
+        (self.end_time - self.start_time).unsigned_abs()
+    }
+
     pub fn until_end(&self) -> Duration {
         let now = OffsetDateTime::now_utc();
         (self.end_time - now).try_into().unwrap_or_default()
@@ -48,6 +97,17 @@ impl Epoch {
         }
     }
 
+    /// Returns the epoch following this one that ends on the next epoch boundary of the chain.
+    /// If the chain's epoch length has changed, the resultant epoch is going to be either shortened
+    /// or extended so that the subsequent epochs would be realigned with the chain.
+    pub fn next_aligned(&self, chain_epoch: &ChainEpoch) -> Self {
+        Epoch {
+            id: self.id + 1,
+            start_time: self.end_time,
+            end_time: chain_epoch.next_boundary_after(self.end_time),
+        }
+    }
+
     pub fn start_rfc3339(&self) -> String {
         // safety: unwrap here is fine as we're using a predefined formatter
         #[allow(clippy::unwrap_used)]
@@ -60,3 +120,80 @@ impl Epoch {
         self.end_time.format(&Rfc3339).unwrap()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MINUTE: Duration = Duration::from_secs(60);
+
+    // 2024-01-01 10:00 UTC
+    fn ten_am() -> OffsetDateTime {
+        OffsetDateTime::from_unix_timestamp(1704103200).unwrap()
+    }
+
+    fn chain_epoch(start_time: OffsetDateTime, length: Duration) -> ChainEpoch {
+        ChainEpoch { start_time, length }
+    }
+
+    #[test]
+    fn next_boundary_of_running_chain_epoch() {
+        let chain = chain_epoch(ten_am(), HOUR);
+
+        assert_eq!(
+            chain.next_boundary_after(ten_am() + 30 * MINUTE),
+            ten_am() + 60 * MINUTE
+        );
+    }
+
+    #[test]
+    fn next_boundary_of_overdue_chain_epoch() {
+        let chain = chain_epoch(ten_am(), HOUR);
+
+        assert_eq!(
+            chain.next_boundary_after(ten_am() + 60 * MINUTE),
+            ten_am() + 120 * MINUTE
+        );
+        assert_eq!(
+            chain.next_boundary_after(ten_am() + 200 * MINUTE),
+            ten_am() + 240 * MINUTE
+        );
+    }
+
+    #[test]
+    fn epochs_follow_the_chain() {
+        let chain = chain_epoch(ten_am(), HOUR);
+        let epoch = Epoch {
+            id: 5,
+            start_time: ten_am(),
+            end_time: ten_am() + 60 * MINUTE,
+        };
+
+        let next = epoch.next_aligned(&chain);
+        assert_eq!(next.id, 6);
+        assert_eq!(next.start_time, ten_am() + 60 * MINUTE);
+        assert_eq!(next.end_time, ten_am() + 120 * MINUTE);
+    }
+
+    #[test]
+    fn epochs_get_realigned_after_chain_epoch_length_change() {
+        let epoch = Epoch {
+            id: 5,
+            start_time: ten_am(),
+            end_time: ten_am() + 60 * MINUTE,
+        };
+
+        // the chain epoch got shortened mid-run and is now offset from ours
+        let chain = chain_epoch(ten_am() + 45 * MINUTE, HOUR / 2);
+        let next = epoch.next_aligned(&chain);
+        assert_eq!(next.start_time, epoch.end_time);
+        assert_eq!(next.end_time, ten_am() + 75 * MINUTE);
+
+        // and from then on, we're in sync again
+        let chain = chain_epoch(ten_am() + 75 * MINUTE, HOUR / 2);
+        let after = next.next_aligned(&chain);
+        assert_eq!(after.start_time, ten_am() + 75 * MINUTE);
+        assert_eq!(after.end_time, ten_am() + 105 * MINUTE);
+        assert_eq!(after.duration(), HOUR / 2);
+    }
+}
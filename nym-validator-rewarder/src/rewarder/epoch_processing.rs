@@ -0,0 +1,206 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use crate::rewarder::epoch::Epoch;
+use crate::rewarder::nyxd_client::NyxdClient;
+use crate::rewarder::storage::RewarderStorage;
+use nym_validator_client::nyxd::{AccountId, Coin};
+use serde::{Deserialize, Serialize};
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+use time::OffsetDateTime;
+use tracing::{info, warn};
+
+/// Progress of processing particular rewarding epoch.
+/// It's persisted before every irreversible step so that the rewarder could resume
+/// from the right place after a crash.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EpochProcessingPhase {
+    /// The rewarding amounts have been determined while the payouts were paused.
+    /// They're going to be sent out once the payouts get resumed.
+    Pending,
+
+    /// The rewarding amounts have been determined, but the transaction might not have been broadcast yet.
+    Computed,
+
+    /// The rewarding transaction has been broadcast and got included in a block.
+    Broadcast,
+
+    /// The outcome of the epoch rewarding has been fully persisted.
+    Confirmed,
+}
+
+impl EpochProcessingPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EpochProcessingPhase::Pending => "pending",
+            EpochProcessingPhase::Computed => "computed",
+            EpochProcessingPhase::Broadcast => "broadcast",
+            EpochProcessingPhase::Confirmed => "confirmed",
+        }
+    }
+}
+
+impl Display for EpochProcessingPhase {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        self.as_str().fmt(f)
+    }
+}
+
+impl FromStr for EpochProcessingPhase {
+    type Err = NymRewarderError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(EpochProcessingPhase::Pending),
+            "computed" => Ok(EpochProcessingPhase::Computed),
+            "broadcast" => Ok(EpochProcessingPhase::Broadcast),
+            "confirmed" => Ok(EpochProcessingPhase::Confirmed),
+            other => Err(NymRewarderError::UnknownEpochProcessingPhase {
+                phase: other.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedRewardingAmount {
+    account: String,
+    amount: Vec<Coin>,
+}
+
+pub(crate) fn encode_rewarding_amounts(
+    amounts: &[(AccountId, Vec<Coin>)],
+) -> Result<String, NymRewarderError> {
+    let persisted = amounts
+        .iter()
+        .map(|(account, amount)| PersistedRewardingAmount {
+            account: account.to_string(),
+            amount: amount.clone(),
+        })
+        .collect::<Vec<_>>();
+
+    serde_json::to_string(&persisted).map_err(|source| {
+        NymRewarderError::MalformedPersistedRewardingAmounts {
+            reason: source.to_string(),
+        }
+    })
+}
+
+pub(crate) fn decode_rewarding_amounts(
+    raw: &str,
+) -> Result<Vec<(AccountId, Vec<Coin>)>, NymRewarderError> {
+    let persisted: Vec<PersistedRewardingAmount> = serde_json::from_str(raw).map_err(|source| {
+        NymRewarderError::MalformedPersistedRewardingAmounts {
+            reason: source.to_string(),
+        }
+    })?;
+
+    persisted
+        .into_iter()
+        .map(|p| {
+            let account = p.account.parse().map_err(|_| {
+                NymRewarderError::MalformedPersistedRewardingAmounts {
+                    reason: format!("{} is not a valid account address", p.account),
+                }
+            })?;
+            Ok((account, p.amount))
+        })
+        .collect()
+}
+
+pub(crate) struct EpochProcessingState {
+    pub(crate) epoch: Epoch,
+    pub(crate) phase: EpochProcessingPhase,
+    pub(crate) rewarding_amounts: String,
+    pub(crate) budget: String,
+    pub(crate) total_spent: String,
+    pub(crate) starting_height: i64,
+    pub(crate) rewarding_tx: Option<String>,
+}
+
+#[derive(sqlx::FromRow)]
+pub(crate) struct RawEpochProcessingState {
+    pub(crate) rewarding_epoch_id: i64,
+    pub(crate) start_time: OffsetDateTime,
+    pub(crate) end_time: OffsetDateTime,
+    pub(crate) phase: String,
+    pub(crate) rewarding_amounts: String,
+    pub(crate) budget: String,
+    pub(crate) total_spent: String,
+    pub(crate) starting_height: i64,
+    pub(crate) rewarding_tx: Option<String>,
+}
+
+impl TryFrom<RawEpochProcessingState> for EpochProcessingState {
+    type Error = NymRewarderError;
+
+    fn try_from(value: RawEpochProcessingState) -> Result<Self, Self::Error> {
+        Ok(EpochProcessingState {
+            epoch: Epoch {
+                id: value.rewarding_epoch_id,
+                start_time: value.start_time,
+                end_time: value.end_time,
+            },
+            phase: value.phase.parse()?,
+            rewarding_amounts: value.rewarding_amounts,
+            budget: value.budget,
+            total_spent: value.total_spent,
+            starting_height: value.starting_height,
+            rewarding_tx: value.rewarding_tx,
+        })
+    }
+}
+
+/// Finish processing of an epoch that got interrupted, for example due to a crash,
+/// before its outcome got fully persisted.
+pub(crate) async fn resume_unfinished_epoch(
+    storage: &RewarderStorage,
+    nyxd_client: &NyxdClient,
+) -> Result<(), NymRewarderError> {
+    let Some(mut state) = storage.load_unfinished_epoch_processing_state().await? else {
+        return Ok(());
+    };
+
+    warn!(
+        "the processing of epoch {} has been interrupted in the '{}' phase. attempting to resume it",
+        state.epoch.id, state.phase
+    );
+
+    if state.phase == EpochProcessingPhase::Computed {
+        // we don't know whether the transaction has made it to the chain, so look it up before
+        // attempting to send it again. note that if the original transaction is still sitting
+        // in the mempool, both of them are going to use the same sequence number,
+        // so at most one of them could ever get executed
+        let rewarding_tx = match nyxd_client
+            .find_rewarding_tx(state.epoch, state.starting_height)
+            .await?
+        {
+            Some(rewarding_tx) => {
+                info!(
+                    "the rewards for epoch {} have already been sent in {rewarding_tx}",
+                    state.epoch.id
+                );
+                rewarding_tx
+            }
+            None => {
+                info!(
+                    "the rewards for epoch {} have not been sent. sending them now",
+                    state.epoch.id
+                );
+                let amounts = decode_rewarding_amounts(&state.rewarding_amounts)?;
+                nyxd_client.send_rewards(state.epoch, amounts).await?
+            }
+        };
+
+        storage
+            .record_broadcast_epoch_rewards(state.epoch.id, rewarding_tx)
+            .await?;
+        state.phase = EpochProcessingPhase::Broadcast;
+        state.rewarding_tx = Some(rewarding_tx.to_string());
+    }
+
+    storage.save_resumed_rewarding_information(&state).await?;
+    storage.record_confirmed_epoch_rewards(state.epoch.id).await
+}
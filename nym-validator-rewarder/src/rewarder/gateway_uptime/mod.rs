@@ -0,0 +1,58 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config;
+use crate::error::NymRewarderError;
+use crate::rewarder::credential_issuance::types::addr_to_account_id;
+use crate::rewarder::epoch::Epoch;
+use crate::rewarder::gateway_uptime::types::{GatewayUptimeResults, RawGatewayUptime};
+use nym_validator_client::nym_api;
+use nym_validator_client::nym_api::NymApiClientExt;
+use tracing::{debug, info};
+
+pub(crate) mod types;
+
+pub struct EpochGatewayUptime {
+    pub(crate) api_client: nym_api::Client,
+}
+
+impl EpochGatewayUptime {
+    pub(crate) fn new(config: &config::GatewayUptime) -> Self {
+        EpochGatewayUptime {
+            api_client: nym_api::Client::new(config.nym_api_url.clone(), None),
+        }
+    }
+
+    pub(crate) async fn get_gateway_uptime_results(
+        &self,
+        current_epoch: Epoch,
+    ) -> Result<GatewayUptimeResults, NymRewarderError> {
+        info!(
+            "looking up gateway uptime for epoch {} ({} - {})",
+            current_epoch.id,
+            current_epoch.start_rfc3339(),
+            current_epoch.end_rfc3339()
+        );
+
+        let gateways = self.api_client.get_gateways_detailed().await?;
+        debug!("retrieved {} gateways", gateways.len());
+
+        let mut raw_results = Vec::with_capacity(gateways.len());
+        for gateway in gateways {
+            let identity = gateway.identity().clone();
+            if gateway.blacklisted {
+                debug!("gateway {identity} is blacklisted - it's not going to receive any rewards");
+                continue;
+            }
+
+            // by default the rewarding epochs are an hour long, so use the matching performance window
+            raw_results.push(RawGatewayUptime {
+                owner: addr_to_account_id(gateway.gateway_bond.owner().clone()),
+                uptime: gateway.node_performance.last_hour.value(),
+                identity,
+            })
+        }
+
+        Ok(GatewayUptimeResults::construct(raw_results))
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use cosmwasm_std::{Decimal, Uint128};
+use nym_validator_client::nyxd::{AccountId, Coin};
+use tracing::info;
+
+pub(crate) struct RawGatewayUptime {
+    pub(crate) identity: String,
+    pub(crate) owner: AccountId,
+    pub(crate) uptime: Decimal,
+}
+
+pub struct GatewayUptime {
+    pub identity: String,
+    pub owner: AccountId,
+
+    pub uptime: Decimal,
+    pub uptime_ratio: Decimal,
+}
+
+impl GatewayUptime {
+    pub fn reward_amount(&self, uptime_budget: &Coin) -> Coin {
+        let amount = Uint128::new(uptime_budget.amount) * self.uptime_ratio;
+
+        Coin::new(amount.u128(), &uptime_budget.denom)
+    }
+}
+
+pub struct GatewayUptimeResults {
+    pub total_uptime: Decimal,
+    pub gateways: Vec<GatewayUptime>,
+}
+
+impl GatewayUptimeResults {
+    pub(crate) fn construct(raw_results: Vec<RawGatewayUptime>) -> Self {
+        let total_uptime: Decimal = raw_results.iter().map(|g| g.uptime).sum();
+
+        GatewayUptimeResults {
+            total_uptime,
+            gateways: raw_results
+                .into_iter()
+                .map(|raw| {
+                    let uptime_ratio = if total_uptime.is_zero() {
+                        Decimal::zero()
+                    } else {
+                        raw.uptime / total_uptime
+                    };
+                    GatewayUptime {
+                        identity: raw.identity,
+                        owner: raw.owner,
+                        uptime: raw.uptime,
+                        uptime_ratio,
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    pub fn rewarding_amounts(&self, budget: &Coin) -> Vec<(AccountId, Vec<Coin>)> {
+        self.gateways
+            .iter()
+            .inspect(|g| {
+                info!(
+                    "gateway {} will receive {} at address {} for its uptime of {}",
+                    g.identity,
+                    g.reward_amount(budget),
+                    g.owner,
+                    g.uptime,
+                );
+            })
+            .map(|g| (g.owner.clone(), vec![g.reward_amount(budget)]))
+            .collect()
+    }
+}
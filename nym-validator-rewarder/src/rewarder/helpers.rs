@@ -34,6 +34,42 @@ pub(crate) fn operator_account_to_owner_account(
     })
 }
 
+/// Tag that validators can include in the `details` of their on-chain description
+/// in order to receive the block signing rewards at a different address, for example `nym-payout:n1...`
+pub(crate) const PAYOUT_ADDRESS_TAG: &str = "nym-payout:";
+
+pub(crate) fn parse_payout_address(
+    operator_account: &AccountId,
+    details: &str,
+) -> Result<Option<AccountId>, NymRewarderError> {
+    let Some(raw) = details
+        .split_whitespace()
+        .find_map(|token| token.strip_prefix(PAYOUT_ADDRESS_TAG))
+    else {
+        return Ok(None);
+    };
+
+    let invalid = |reason: String| NymRewarderError::InvalidPayoutAddress {
+        operator_account: operator_account.to_string(),
+        raw: raw.to_string(),
+        reason,
+    };
+
+    let address: AccountId = raw
+        .parse()
+        .map_err(|_| invalid("it's not a valid bech32 address".to_string()))?;
+    if address.prefix() != BECH32_PREFIX {
+        return Err(invalid(format!(
+            "expected an address with the '{BECH32_PREFIX}' prefix"
+        )));
+    }
+
+    if &address == operator_account {
+        return Ok(None);
+    }
+    Ok(Some(address))
+}
+
 pub(crate) fn api_client(issuer: &CredentialIssuer) -> Result<nym_api::Client, NymRewarderError> {
     let url = match issuer.api_runner.parse() {
         Ok(url) => url,
@@ -48,3 +84,78 @@ pub(crate) fn api_client(issuer: &CredentialIssuer) -> Result<nym_api::Client, N
 
     Ok(nym_api::Client::new(url, None))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn account(prefix: &str, seed: u8) -> AccountId {
+        AccountId::new(prefix, &[seed; 20]).unwrap()
+    }
+
+    #[test]
+    fn valid_payout_address_is_parsed() {
+        let operator = account(BECH32_PREFIX, 1);
+        let payout = account(BECH32_PREFIX, 2);
+
+        let details = format!("community validator {PAYOUT_ADDRESS_TAG}{payout} since 2021");
+        assert_eq!(
+            parse_payout_address(&operator, &details).unwrap(),
+            Some(payout)
+        );
+    }
+
+    #[test]
+    fn addresses_without_the_tag_are_ignored() {
+        let operator = account(BECH32_PREFIX, 1);
+        let payout = account(BECH32_PREFIX, 2);
+
+        assert_eq!(parse_payout_address(&operator, "").unwrap(), None);
+        assert_eq!(
+            parse_payout_address(&operator, &format!("payout:{payout}")).unwrap(),
+            None
+        );
+        assert_eq!(
+            parse_payout_address(&operator, &format!("nym-payout {payout}")).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn malformed_bech32_address_is_rejected() {
+        let operator = account(BECH32_PREFIX, 1);
+
+        // corrupt the checksum of an otherwise valid address
+        let mut corrupted = account(BECH32_PREFIX, 2).to_string();
+        let last = corrupted.pop().unwrap();
+        corrupted.push(if last == 'q' { 'p' } else { 'q' });
+
+        for raw in [corrupted.as_str(), "n1notanaddress", "garbage"] {
+            let details = format!("{PAYOUT_ADDRESS_TAG}{raw}");
+            assert!(matches!(
+                parse_payout_address(&operator, &details),
+                Err(NymRewarderError::InvalidPayoutAddress { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn address_of_a_different_chain_is_rejected() {
+        let operator = account(BECH32_PREFIX, 1);
+        let payout = account("cosmos", 2);
+
+        let details = format!("{PAYOUT_ADDRESS_TAG}{payout}");
+        assert!(matches!(
+            parse_payout_address(&operator, &details),
+            Err(NymRewarderError::InvalidPayoutAddress { .. })
+        ));
+    }
+
+    #[test]
+    fn operator_own_address_is_not_a_distinct_payout_address() {
+        let operator = account(BECH32_PREFIX, 1);
+
+        let details = format!("{PAYOUT_ADDRESS_TAG}{operator}");
+        assert_eq!(parse_payout_address(&operator, &details).unwrap(), None);
+    }
+}
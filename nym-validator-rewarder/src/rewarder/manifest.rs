@@ -0,0 +1,540 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Signed, canonical descriptions of the rewards computed in every epoch.
+//!
+//! The manifests are meant to be published so that third parties could check the payouts
+//! made by the rewarder without having to trust its database. The signature is made over the compact
+//! JSON encoding of [RewardManifest], whose fields are emitted in declaration order and whose rewards
+//! are sorted, so that re-encoding a parsed manifest always reproduces the signed bytes.
+
+use crate::error::NymRewarderError;
+use crate::rewarder::pruning::{
+    BLOCK_SIGNING_MODULE, CREDENTIAL_ISSUANCE_MODULE, GATEWAY_UPTIME_MODULE,
+};
+use crate::rewarder::storage::models::RawRewardManifest;
+use crate::rewarder::{EpochRewards, RewardingResult};
+use nym_crypto::asymmetric::ed25519;
+use nym_validator_client::nyxd::{AccountId, Coin, Hash, MsgSend};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::{Display, Formatter};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MANIFEST_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ManifestReward {
+    pub module: String,
+    pub account: String,
+    pub amount: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RewardManifest {
+    pub version: u32,
+    pub epoch_id: i64,
+    pub epoch_start: String,
+    pub epoch_end: String,
+
+    /// Account sending out the rewards.
+    pub distributor: String,
+    pub budget: String,
+    pub total_spent: String,
+
+    /// Hash of the transaction paying out the rewards, if they have been sent.
+    pub rewarding_tx: Option<String>,
+    pub rewards: Vec<ManifestReward>,
+}
+
+impl RewardManifest {
+    pub(crate) fn new(
+        rewards: &EpochRewards,
+        rewarding_result: &Result<RewardingResult, NymRewarderError>,
+        distributor: &AccountId,
+    ) -> Result<Self, NymRewarderError> {
+        let mut entries = Vec::new();
+        if let Ok(Some(signing)) = &rewards.signing {
+            entries.extend(manifest_entries(
+                BLOCK_SIGNING_MODULE,
+                signing.rewarding_amounts(&rewards.signing_budget),
+            )?);
+        }
+        if let Ok(Some(credentials)) = &rewards.credentials {
+            entries.extend(manifest_entries(
+                CREDENTIAL_ISSUANCE_MODULE,
+                credentials.rewarding_amounts(&rewards.credentials_budget),
+            )?);
+        }
+        if let Ok(Some(gateway_uptime)) = &rewards.gateway_uptime {
+            entries.extend(manifest_entries(
+                GATEWAY_UPTIME_MODULE,
+                gateway_uptime.rewarding_amounts(&rewards.gateway_uptime_budget),
+            )?);
+        }
+        entries.sort_by(|a, b| (&a.module, &a.account).cmp(&(&b.module, &b.account)));
+
+        // in monitor only mode no transaction is sent and a placeholder hash is returned instead
+        let placeholder = Hash::Sha256([0u8; 32]);
+        let (total_spent, rewarding_tx) = match rewarding_result {
+            Ok(result) if result.rewarding_tx != placeholder => (
+                result.total_spent.clone(),
+                Some(result.rewarding_tx.to_string()),
+            ),
+            _ => (Coin::new(0, &rewards.total_budget.denom), None),
+        };
+
+        Ok(RewardManifest {
+            version: MANIFEST_VERSION,
+            epoch_id: rewards.epoch.id,
+            epoch_start: rewards.epoch.start_rfc3339(),
+            epoch_end: rewards.epoch.end_rfc3339(),
+            distributor: distributor.to_string(),
+            budget: rewards.total_budget.to_string(),
+            total_spent: total_spent.to_string(),
+            rewarding_tx,
+            rewards: entries,
+        })
+    }
+
+    pub fn canonical_json(&self) -> Result<String, NymRewarderError> {
+        serde_json::to_string(self)
+            .map_err(|source| NymRewarderError::ManifestSerializationFailure { source })
+    }
+
+    /// The exact bytes covered by the manifest signature.
+    pub fn canonical_bytes(&self) -> Result<Vec<u8>, NymRewarderError> {
+        self.canonical_json().map(String::into_bytes)
+    }
+
+    pub fn sign(
+        self,
+        identity: &ed25519::KeyPair,
+    ) -> Result<SignedRewardManifest, NymRewarderError> {
+        let signature = identity.private_key().sign(self.canonical_bytes()?);
+
+        Ok(SignedRewardManifest {
+            manifest: self,
+            signer: identity.public_key().to_base58_string(),
+            signature: signature.to_base58_string(),
+        })
+    }
+
+    /// Total amounts each account is expected to receive, keyed by the account and the denomination.
+    fn expected_payouts(&self) -> Result<BTreeMap<(String, String), u128>, NymRewarderError> {
+        let mut payouts = BTreeMap::new();
+        for reward in &self.rewards {
+            let amount: Coin =
+                reward
+                    .amount
+                    .parse()
+                    .map_err(|_| NymRewarderError::MalformedManifestAmount {
+                        amount: reward.amount.clone(),
+                    })?;
+            *payouts
+                .entry((reward.account.clone(), amount.denom))
+                .or_default() += amount.amount;
+        }
+        Ok(payouts)
+    }
+}
+
+// Each account is expected to be rewarded with a single coin by every module.
+// The accounts that are not getting anything are left out of the manifest.
+fn manifest_entries(
+    module: &str,
+    amounts: Vec<(AccountId, Vec<Coin>)>,
+) -> Result<Vec<ManifestReward>, NymRewarderError> {
+    let mut entries = Vec::new();
+    for (account, amount) in amounts {
+        let Some(amount) = amount.first() else {
+            return Err(NymRewarderError::MissingRewardAmount {
+                module: module.to_string(),
+                account: account.to_string(),
+            });
+        };
+        if amount.amount != 0 {
+            entries.push(ManifestReward {
+                module: module.to_string(),
+                account: account.to_string(),
+                amount: amount.to_string(),
+            })
+        }
+    }
+    Ok(entries)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignedRewardManifest {
+    pub manifest: RewardManifest,
+
+    /// Base58-encoded ed25519 identity key of the rewarder.
+    pub signer: String,
+
+    /// Base58-encoded signature on the canonical encoding of the manifest.
+    pub signature: String,
+}
+
+impl SignedRewardManifest {
+    pub fn from_json(raw: &str) -> Result<Self, NymRewarderError> {
+        serde_json::from_str(raw).map_err(|source| NymRewarderError::MalformedManifest { source })
+    }
+
+    pub fn to_json_pretty(&self) -> Result<String, NymRewarderError> {
+        serde_json::to_string_pretty(self)
+            .map_err(|source| NymRewarderError::ManifestSerializationFailure { source })
+    }
+
+    pub fn load(path: &Path) -> Result<Self, NymRewarderError> {
+        let raw =
+            fs::read_to_string(path).map_err(|source| NymRewarderError::ManifestReadFailure {
+                path: path.to_path_buf(),
+                source,
+            })?;
+        Self::from_json(&raw)
+    }
+
+    /// Write the manifest as `epoch_<id>.json` within the provided directory.
+    pub fn publish(&self, directory: &Path) -> Result<PathBuf, NymRewarderError> {
+        let path = directory.join(format!("epoch_{}.json", self.manifest.epoch_id));
+        let write_err = |source| NymRewarderError::ManifestWriteFailure {
+            path: path.clone(),
+            source,
+        };
+
+        fs::create_dir_all(directory).map_err(write_err)?;
+        fs::write(&path, self.to_json_pretty()?).map_err(write_err)?;
+        Ok(path)
+    }
+
+    /// Verify the manifest signature, optionally making sure it has been produced by the expected signer.
+    pub fn verify_signature(
+        &self,
+        expected_signer: Option<&ed25519::PublicKey>,
+    ) -> Result<(), NymRewarderError> {
+        let signer = ed25519::PublicKey::from_base58_string(&self.signer)?;
+        if let Some(expected) = expected_signer {
+            if expected != &signer {
+                return Err(NymRewarderError::UnexpectedManifestSigner {
+                    expected: expected.to_base58_string(),
+                    got: self.signer.clone(),
+                });
+            }
+        }
+
+        let epoch_id = self.manifest.epoch_id;
+        let signature = ed25519::Signature::from_base58_string(&self.signature)
+            .map_err(|_| NymRewarderError::ManifestSignatureVerificationFailure { epoch_id })?;
+
+        signer
+            .verify(self.manifest.canonical_bytes()?, &signature)
+            .map_err(|_| NymRewarderError::ManifestSignatureVerificationFailure { epoch_id })
+    }
+
+    /// Compare the rewards described by the manifest against the transfers included in its rewarding transaction.
+    pub fn compare_payouts(
+        &self,
+        transfers: &[MsgSend],
+    ) -> Result<ManifestVerification, NymRewarderError> {
+        let Some(rewarding_tx) = self.manifest.rewarding_tx.clone() else {
+            return Err(NymRewarderError::ManifestWithoutRewardingTx {
+                epoch_id: self.manifest.epoch_id,
+            });
+        };
+
+        let expected = self.manifest.expected_payouts()?;
+
+        let mut on_chain: BTreeMap<(String, String), u128> = BTreeMap::new();
+        for transfer in transfers {
+            if transfer.from_address.as_ref() != self.manifest.distributor {
+                continue;
+            }
+            for coin in &transfer.amount {
+                *on_chain
+                    .entry((transfer.to_address.to_string(), coin.denom.to_string()))
+                    .or_default() += coin.amount;
+            }
+        }
+
+        let all_recipients: BTreeSet<_> = expected.keys().chain(on_chain.keys()).collect();
+        let mut mismatches = Vec::new();
+        for key in all_recipients {
+            let expected_amount = expected.get(key).copied().unwrap_or_default();
+            let on_chain_amount = on_chain.get(key).copied().unwrap_or_default();
+            if expected_amount != on_chain_amount {
+                mismatches.push(PayoutMismatch {
+                    account: key.0.clone(),
+                    denom: key.1.clone(),
+                    expected: expected_amount,
+                    on_chain: on_chain_amount,
+                })
+            }
+        }
+
+        Ok(ManifestVerification {
+            epoch_id: self.manifest.epoch_id,
+            rewarding_tx,
+            signer: self.signer.clone(),
+            verified_payouts: expected.len(),
+            mismatches,
+        })
+    }
+}
+
+impl Display for SignedRewardManifest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let manifest = &self.manifest;
+        writeln!(
+            f,
+            "epoch {} ({} - {}) signed by {}",
+            manifest.epoch_id, manifest.epoch_start, manifest.epoch_end, self.signer
+        )?;
+        writeln!(
+            f,
+            "distributor: {}, budget: {}, total spent: {}, rewarding tx: {}",
+            manifest.distributor,
+            manifest.budget,
+            manifest.total_spent,
+            manifest.rewarding_tx.as_deref().unwrap_or("none")
+        )?;
+        for reward in &manifest.rewards {
+            writeln!(
+                f,
+                "{}: {} - {}",
+                reward.module, reward.account, reward.amount
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl TryFrom<RawRewardManifest> for SignedRewardManifest {
+    type Error = NymRewarderError;
+
+    fn try_from(value: RawRewardManifest) -> Result<Self, Self::Error> {
+        Ok(SignedRewardManifest {
+            manifest: serde_json::from_str(&value.manifest)
+                .map_err(|source| NymRewarderError::MalformedManifest { source })?,
+            signer: value.signer,
+            signature: value.signature,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PayoutMismatch {
+    pub account: String,
+    pub denom: String,
+    pub expected: u128,
+    pub on_chain: u128,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ManifestVerification {
+    pub epoch_id: i64,
+    pub rewarding_tx: String,
+    pub signer: String,
+    pub verified_payouts: usize,
+    pub mismatches: Vec<PayoutMismatch>,
+}
+
+impl ManifestVerification {
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+impl Display for ManifestVerification {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "manifest of epoch {} signed by {} (rewarding tx: {})",
+            self.epoch_id, self.signer, self.rewarding_tx
+        )?;
+        if self.is_valid() {
+            return write!(
+                f,
+                "all {} payouts match the on-chain transfers",
+                self.verified_payouts
+            );
+        }
+        for mismatch in &self.mismatches {
+            writeln!(
+                f,
+                "MISMATCH: {} was expected to receive {}{} but got {}{}",
+                mismatch.account,
+                mismatch.expected,
+                mismatch.denom,
+                mismatch.on_chain,
+                mismatch.denom
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_validator_client::nyxd::CosmosCoin;
+
+    fn test_identity() -> ed25519::KeyPair {
+        let mut rng = rand::thread_rng();
+        ed25519::KeyPair::new(&mut rng)
+    }
+
+    fn account(seed: u8) -> AccountId {
+        AccountId::new("n", &[seed; 20]).unwrap()
+    }
+
+    fn manifest() -> RewardManifest {
+        RewardManifest {
+            version: MANIFEST_VERSION,
+            epoch_id: 42,
+            epoch_start: "2024-01-01T10:00:00Z".to_string(),
+            epoch_end: "2024-01-01T11:00:00Z".to_string(),
+            distributor: account(0).to_string(),
+            budget: "1000unym".to_string(),
+            total_spent: "600unym".to_string(),
+            rewarding_tx: Some(Hash::Sha256([1u8; 32]).to_string()),
+            rewards: vec![
+                ManifestReward {
+                    module: BLOCK_SIGNING_MODULE.to_string(),
+                    account: account(1).to_string(),
+                    amount: "300unym".to_string(),
+                },
+                ManifestReward {
+                    module: CREDENTIAL_ISSUANCE_MODULE.to_string(),
+                    account: account(1).to_string(),
+                    amount: "100unym".to_string(),
+                },
+                ManifestReward {
+                    module: CREDENTIAL_ISSUANCE_MODULE.to_string(),
+                    account: account(2).to_string(),
+                    amount: "200unym".to_string(),
+                },
+            ],
+        }
+    }
+
+    fn transfer(to: u8, amount: u128) -> MsgSend {
+        MsgSend {
+            from_address: account(0),
+            to_address: account(to),
+            amount: vec![CosmosCoin {
+                denom: "unym".parse().unwrap(),
+                amount,
+            }],
+        }
+    }
+
+    #[test]
+    fn signature_survives_json_roundtrip() {
+        let identity = test_identity();
+        let signed = manifest().sign(&identity).unwrap();
+
+        let recovered = SignedRewardManifest::from_json(&signed.to_json_pretty().unwrap()).unwrap();
+        assert_eq!(recovered, signed);
+        assert!(recovered
+            .verify_signature(Some(identity.public_key()))
+            .is_ok());
+    }
+
+    #[test]
+    fn tampered_manifest_fails_verification() {
+        let identity = test_identity();
+        let mut signed = manifest().sign(&identity).unwrap();
+        signed.manifest.rewards[0].amount = "3000unym".to_string();
+
+        assert!(matches!(
+            signed.verify_signature(None),
+            Err(NymRewarderError::ManifestSignatureVerificationFailure { epoch_id: 42 })
+        ));
+    }
+
+    #[test]
+    fn unexpected_signer_is_rejected() {
+        let signed = manifest().sign(&test_identity()).unwrap();
+        let other = test_identity();
+
+        assert!(matches!(
+            signed.verify_signature(Some(other.public_key())),
+            Err(NymRewarderError::UnexpectedManifestSigner { .. })
+        ));
+    }
+
+    #[test]
+    fn payouts_are_compared_per_account() {
+        let signed = manifest().sign(&test_identity()).unwrap();
+
+        // rewards for multiple modules might be sent in separate transfers
+        let matching = signed
+            .compare_payouts(&[transfer(1, 300), transfer(2, 200), transfer(1, 100)])
+            .unwrap();
+        assert!(matching.is_valid());
+        assert_eq!(matching.verified_payouts, 2);
+
+        let mismatched = signed
+            .compare_payouts(&[transfer(1, 400), transfer(2, 150), transfer(3, 50)])
+            .unwrap();
+        assert_eq!(
+            mismatched.mismatches,
+            vec![
+                PayoutMismatch {
+                    account: account(2).to_string(),
+                    denom: "unym".to_string(),
+                    expected: 200,
+                    on_chain: 150,
+                },
+                PayoutMismatch {
+                    account: account(3).to_string(),
+                    denom: "unym".to_string(),
+                    expected: 0,
+                    on_chain: 50,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn manifest_without_payouts_cannot_be_checked_on_chain() {
+        let mut manifest = manifest();
+        manifest.rewarding_tx = None;
+        let signed = manifest.sign(&test_identity()).unwrap();
+
+        assert!(matches!(
+            signed.compare_payouts(&[]),
+            Err(NymRewarderError::ManifestWithoutRewardingTx { epoch_id: 42 })
+        ));
+    }
+
+    #[test]
+    fn zero_rewards_are_left_out_of_the_manifest() {
+        let entries = manifest_entries(
+            BLOCK_SIGNING_MODULE,
+            vec![
+                (account(1), vec![Coin::new(300, "unym")]),
+                (account(2), vec![Coin::new(0, "unym")]),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            entries,
+            vec![ManifestReward {
+                module: BLOCK_SIGNING_MODULE.to_string(),
+                account: account(1).to_string(),
+                amount: "300unym".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_reward_amount_is_rejected() {
+        assert!(matches!(
+            manifest_entries(BLOCK_SIGNING_MODULE, vec![(account(1), vec![])]),
+            Err(NymRewarderError::MissingRewardAmount { .. })
+        ));
+    }
+}
@@ -1,32 +1,54 @@
 // Copyright 2023-2024 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
-use crate::config::Config;
+use crate::config::{Config, EpochAlignment};
 use crate::error::{InsufficientBalance, NymRewarderError};
+use crate::rewarder::admin::proto::{EpochEvaluation, PayoutsStatus, RewardAmount, RewarderState};
+use crate::rewarder::admin::{start_admin_api, AdminCommand};
+use crate::rewarder::block_signing::policy::signing_reward_policy;
 use crate::rewarder::block_signing::types::EpochSigningResults;
 use crate::rewarder::block_signing::EpochSigning;
 use crate::rewarder::credential_issuance::types::CredentialIssuanceResults;
 use crate::rewarder::credential_issuance::CredentialIssuance;
 use crate::rewarder::epoch::Epoch;
+use crate::rewarder::epoch_processing::{
+    decode_rewarding_amounts, resume_unfinished_epoch, EpochProcessingState,
+};
+use crate::rewarder::gateway_uptime::types::GatewayUptimeResults;
+use crate::rewarder::gateway_uptime::EpochGatewayUptime;
+use crate::rewarder::manifest::RewardManifest;
+use crate::rewarder::notifier::{Notifier, RewardingNotification};
 use crate::rewarder::nyxd_client::NyxdClient;
+use crate::rewarder::pruning::HistoryPruner;
 use crate::rewarder::storage::RewarderStorage;
 use futures::future::{FusedFuture, OptionFuture};
 use futures::FutureExt;
+use nym_crypto::asymmetric::ed25519;
 use nym_task::TaskManager;
 use nym_validator_client::nyxd::{AccountId, Coin, Hash};
 use nyxd_scraper::NyxdScraper;
 use std::ops::Add;
 use tokio::pin;
-use tokio::time::{interval_at, Instant};
+use tokio::time::{sleep, Instant};
 use tracing::{error, info, instrument, warn};
 
+mod admin;
 mod block_signing;
-mod credential_issuance;
+pub(crate) mod credential_issuance;
 mod epoch;
+mod epoch_processing;
+mod gateway_uptime;
 mod helpers;
+pub(crate) mod manifest;
+mod notifier;
 mod nyxd_client;
-mod storage;
+pub(crate) mod pruning;
+pub(crate) mod storage;
 mod tasks;
+// not every helper is used by the crate's own tests
+#[cfg(any(test, feature = "test-utils"))]
+#[allow(dead_code)]
+pub(crate) mod testing;
 
 pub struct RewardingResult {
     pub total_spent: Coin,
@@ -37,10 +59,12 @@ pub struct EpochRewards {
     pub epoch: Epoch,
     pub signing: Result<Option<EpochSigningResults>, NymRewarderError>,
     pub credentials: Result<Option<CredentialIssuanceResults>, NymRewarderError>,
+    pub gateway_uptime: Result<Option<GatewayUptimeResults>, NymRewarderError>,
 
     pub total_budget: Coin,
     pub signing_budget: Coin,
     pub credentials_budget: Coin,
+    pub gateway_uptime_budget: Coin,
 }
 
 impl EpochRewards {
@@ -65,6 +89,16 @@ impl EpochRewards {
             }
         }
 
+        if let Ok(Some(gateway_uptime)) = &self.gateway_uptime {
+            for (account, uptime_amount) in
+                gateway_uptime.rewarding_amounts(&self.gateway_uptime_budget)
+            {
+                if uptime_amount[0].amount != 0 {
+                    amounts.push((account, uptime_amount))
+                }
+            }
+        }
+
         Ok(amounts)
     }
 }
@@ -74,24 +108,99 @@ pub fn total_spent(amounts: &[(AccountId, Vec<Coin>)], denom: &str) -> Coin {
     Coin::new(amount, denom)
 }
 
+/// Determine the epoch following the provided one, according to the configured epoch alignment.
+async fn next_epoch(config: &Config, nyxd_client: &NyxdClient, previous: &Epoch) -> Epoch {
+    match config.rewarding.epoch_alignment {
+        EpochAlignment::Fixed => previous.next(),
+        EpochAlignment::Chain => match nyxd_client.mixnet_epoch().await {
+            Ok(chain_epoch) => {
+                let next = previous.next_aligned(&chain_epoch);
+                if next.duration() != chain_epoch.length {
+                    info!(
+                        "epoch {} is going to last for {}s in order to realign with the mixnet contract epochs (of {}s)",
+                        next.id,
+                        next.duration().as_secs(),
+                        chain_epoch.length.as_secs()
+                    );
+                }
+                next
+            }
+            Err(err) => {
+                warn!("failed to retrieve the current mixnet epoch: {err}. the next epoch is going to have the same length as the previous one");
+                previous.next()
+            }
+        },
+    }
+}
+
+/// Create the very first rewarding epoch, according to the configured epoch alignment.
+async fn first_epoch(config: &Config, nyxd_client: &NyxdClient) -> Result<Epoch, NymRewarderError> {
+    match config.rewarding.epoch_alignment {
+        EpochAlignment::Fixed => Epoch::first(config.rewarding.epoch_duration),
+        EpochAlignment::Chain => {
+            let chain_epoch = nyxd_client.mixnet_epoch().await?;
+            Ok(Epoch::first_aligned(&chain_epoch))
+        }
+    }
+}
+
 pub struct Rewarder {
     config: Config,
     current_epoch: Epoch,
+    run_id: i64,
 
     storage: RewarderStorage,
     nyxd_client: NyxdClient,
     epoch_signing: Option<EpochSigning>,
     credential_issuance: Option<CredentialIssuance>,
+    gateway_uptime: Option<EpochGatewayUptime>,
+    notifier: Option<Notifier>,
+
+    // used for signing the reward manifests, if enabled
+    manifest_signer: Option<ed25519::KeyPair>,
+
+    // set via the admin api
+    payouts_paused: bool,
 }
 
 impl Rewarder {
     pub async fn new(config: Config) -> Result<Self, NymRewarderError> {
         let nyxd_client = NyxdClient::new(&config)?;
-        let storage = RewarderStorage::init(&config.storage_paths.reward_history).await?;
+        Self::new_with_nyxd_client(config, nyxd_client).await
+    }
+
+    pub(crate) async fn new_with_nyxd_client(
+        config: Config,
+        nyxd_client: NyxdClient,
+    ) -> Result<Self, NymRewarderError> {
+        let storage = RewarderStorage::init(&config).await?;
+
+        let last_run = storage.load_last_rewarder_run().await?;
+        if let Some(last_run) = &last_run {
+            if !last_run.clean_shutdown {
+                warn!(
+                    "the previous rewarder run (started at {}) has not been shut down cleanly",
+                    last_run.started_at
+                );
+            }
+        }
+        resume_unfinished_epoch(&storage, &nyxd_client).await?;
+
+        let checkpoint = last_run.as_ref().and_then(|run| run.in_progress_epoch());
         let current_epoch = if let Some(last_epoch) = storage.load_last_rewarding_epoch().await? {
-            last_epoch.next()
+            next_epoch(&config, &nyxd_client, &last_epoch).await
+        } else if let Some(checkpoint) = checkpoint {
+            // we haven't finished a single epoch yet, so make sure to carry on with the one we've started
+            // rather than creating a brand new one and skipping all the blocks observed so far
+            info!(
+                "resuming the checkpointed epoch {} ({} - {})",
+                checkpoint.id,
+                checkpoint.start_rfc3339(),
+                checkpoint.end_rfc3339()
+            );
+            checkpoint
         } else {
-            Epoch::first(config.rewarding.epoch_duration)?
+            first_epoch(&config, &nyxd_client).await?
         };
 
         let epoch_signing = if config.block_signing.enabled {
@@ -110,6 +219,10 @@ impl Rewarder {
                 nyxd_scraper,
                 nyxd_client: nyxd_client.clone(),
                 whitelist,
+                policy: signing_reward_policy(&config.block_signing),
+                minimum_uptime: config.block_signing.minimum_uptime,
+                allow_payout_overrides: config.block_signing.allow_payout_overrides,
+                max_concurrent_queries: config.rewarding.max_concurrent_queries,
             })
         } else {
             None
@@ -122,14 +235,27 @@ impl Rewarder {
             }
 
             Some(
-                CredentialIssuance::new(current_epoch, storage.clone(), &nyxd_client, whitelist)
-                    .await?,
+                CredentialIssuance::new(
+                    current_epoch,
+                    storage.clone(),
+                    &nyxd_client,
+                    whitelist,
+                    config.issuance_monitor.minimum_api_availability,
+                )
+                .await?,
             )
         } else {
             None
         };
 
+        let gateway_uptime = if config.gateway_uptime.enabled {
+            Some(EpochGatewayUptime::new(&config.gateway_uptime))
+        } else {
+            None
+        };
+
         if config.issuance_monitor.enabled
+            || config.gateway_uptime.enabled
             || (config.block_signing.enabled && !config.block_signing.monitor_only)
         {
             let balance = nyxd_client
@@ -151,22 +277,35 @@ impl Rewarder {
             }
         }
 
+        let notifier = Notifier::new(&config.notifications)?;
+        let manifest_signer = if config.manifests.enabled {
+            Some(config.storage_paths.load_or_generate_identity()?)
+        } else {
+            None
+        };
+        let run_id = storage.register_rewarder_run().await?;
+
         Ok(Rewarder {
             current_epoch,
+            run_id,
             credential_issuance,
             epoch_signing,
+            gateway_uptime,
+            notifier,
+            manifest_signer,
             nyxd_client,
             storage,
             config,
+            payouts_paused: false,
         })
     }
 
     #[instrument(skip(self))]
     async fn calculate_block_signing_rewards(
-        &mut self,
+        &self,
     ) -> Result<Option<EpochSigningResults>, NymRewarderError> {
         info!("calculating reward shares");
-        if let Some(epoch_signing) = &mut self.epoch_signing {
+        if let Some(epoch_signing) = &self.epoch_signing {
             Some(
                 epoch_signing
                     .get_signed_blocks_results(self.current_epoch)
@@ -180,13 +319,35 @@ impl Rewarder {
 
     #[instrument(skip(self))]
     async fn calculate_credential_rewards(
-        &mut self,
+        &self,
+        finalise: bool,
     ) -> Result<Option<CredentialIssuanceResults>, NymRewarderError> {
         info!("calculating reward shares");
-        if let Some(credential_issuance) = &mut self.credential_issuance {
+        if let Some(credential_issuance) = &self.credential_issuance {
+            if finalise {
+                Some(
+                    credential_issuance
+                        .get_issued_credentials_results(self.current_epoch)
+                        .await,
+                )
+            } else {
+                Some(credential_issuance.peek_issued_credentials_results().await)
+            }
+        } else {
+            None
+        }
+        .transpose()
+    }
+
+    #[instrument(skip(self))]
+    async fn calculate_gateway_uptime_rewards(
+        &self,
+    ) -> Result<Option<GatewayUptimeResults>, NymRewarderError> {
+        info!("calculating reward shares");
+        if let Some(gateway_uptime) = &self.gateway_uptime {
             Some(
-                credential_issuance
-                    .get_issued_credentials_results(self.current_epoch)
+                gateway_uptime
+                    .get_gateway_uptime_results(self.current_epoch)
                     .await,
             )
         } else {
@@ -195,7 +356,9 @@ impl Rewarder {
         .transpose()
     }
 
-    async fn determine_epoch_rewards(&mut self) -> EpochRewards {
+    /// Determine the rewards of the current epoch. If `finalise` is not set,
+    /// the data gathered so far is not going to be reset, so that the epoch could be evaluated again.
+    async fn determine_epoch_rewards(&mut self, finalise: bool) -> EpochRewards {
         let epoch_budget = self.config.rewarding.epoch_budget.clone();
         let denom = &epoch_budget.denom;
         let signing_budget = Coin::new(
@@ -206,17 +369,28 @@ impl Rewarder {
             (self.config.rewarding.ratios.credential_issuance * epoch_budget.amount as f64) as u128,
             denom,
         );
+        let gateway_uptime_budget = Coin::new(
+            (self.config.rewarding.ratios.gateway_uptime * epoch_budget.amount as f64) as u128,
+            denom,
+        );
 
-        let signing_rewards = self.calculate_block_signing_rewards().await;
-        let credential_rewards = self.calculate_credential_rewards().await;
+        // the modules are independent of each other, so they can be scored concurrently
+        // before the payouts are determined
+        let (signing_rewards, credential_rewards, gateway_uptime_rewards) = tokio::join!(
+            self.calculate_block_signing_rewards(),
+            self.calculate_credential_rewards(finalise),
+            self.calculate_gateway_uptime_rewards(),
+        );
 
         EpochRewards {
             epoch: self.current_epoch,
             signing: signing_rewards,
             credentials: credential_rewards,
+            gateway_uptime: gateway_uptime_rewards,
             total_budget: epoch_budget.clone(),
             signing_budget,
             credentials_budget,
+            gateway_uptime_budget,
         }
     }
 
@@ -224,6 +398,7 @@ impl Rewarder {
     async fn send_rewards(
         &self,
         amounts: Vec<(AccountId, Vec<Coin>)>,
+        total_spent: &Coin,
     ) -> Result<Hash, NymRewarderError> {
         if self.config.block_signing.monitor_only {
             info!("skipping sending rewards, monitoring mode only");
@@ -235,9 +410,96 @@ impl Rewarder {
             return Err(NymRewarderError::NoValidatorsToReward);
         }
 
+        if self.payouts_paused {
+            warn!("deferring sending rewards until the payouts are resumed");
+            self.storage
+                .record_pending_epoch_rewards(
+                    self.current_epoch,
+                    &amounts,
+                    &self.config.rewarding.epoch_budget,
+                    total_spent,
+                )
+                .await?;
+            return Err(NymRewarderError::PayoutsPaused);
+        }
+
+        // make sure we know about the rewards before they're sent out in case we crash midway
+        let starting_height = self.nyxd_client.current_block_height().await?;
+        self.storage
+            .record_computed_epoch_rewards(
+                self.current_epoch,
+                &amounts,
+                &self.config.rewarding.epoch_budget,
+                total_spent,
+                starting_height,
+            )
+            .await?;
+
         info!("sending rewards");
-        self.nyxd_client
+        let rewarding_tx = self
+            .nyxd_client
             .send_rewards(self.current_epoch, amounts)
+            .await?;
+
+        // the rewards have already been sent, so don't report it as a rewarding failure.
+        // if the rewarder crashes before the epoch is confirmed, the transaction is going to be looked up on chain
+        if let Err(err) = self
+            .storage
+            .record_broadcast_epoch_rewards(self.current_epoch.id, rewarding_tx)
+            .await
+        {
+            error!("failed to record the broadcast of the rewarding transaction: {err}")
+        }
+
+        Ok(rewarding_tx)
+    }
+
+    /// Send out the rewards of all the epochs that have ended while the payouts were paused.
+    async fn send_pending_rewards(&self) {
+        let pending = match self.storage.load_pending_epoch_rewards().await {
+            Ok(pending) => pending,
+            Err(err) => {
+                error!("failed to load the pending rewards: {err}");
+                return;
+            }
+        };
+
+        for state in pending {
+            if let Err(err) = self.send_pending_epoch_rewards(state).await {
+                // they're going to be retried once the payouts are resumed again, or on the next startup
+                error!("failed to send the pending rewards: {err}");
+                return;
+            }
+        }
+    }
+
+    async fn send_pending_epoch_rewards(
+        &self,
+        mut state: EpochProcessingState,
+    ) -> Result<(), NymRewarderError> {
+        info!(
+            "sending the rewards of epoch {} deferred while the payouts were paused",
+            state.epoch.id
+        );
+        let amounts = decode_rewarding_amounts(&state.rewarding_amounts)?;
+
+        // make sure we know about the rewards before they're sent out in case we crash midway
+        let starting_height = self.nyxd_client.current_block_height().await?;
+        self.storage
+            .record_resumed_pending_epoch_rewards(state.epoch.id, starting_height)
+            .await?;
+
+        let rewarding_tx = self.nyxd_client.send_rewards(state.epoch, amounts).await?;
+        self.storage
+            .record_broadcast_epoch_rewards(state.epoch.id, rewarding_tx)
+            .await?;
+        state.rewarding_tx = Some(rewarding_tx.to_string());
+
+        self.storage
+            .save_resumed_rewarding_information(&state)
+            .await?;
+        self.storage
+            .record_confirmed_epoch_rewards(state.epoch.id)
             .await
     }
 
@@ -251,7 +513,7 @@ impl Rewarder {
             &self.config.rewarding.epoch_budget.denom,
         );
 
-        let rewarding_tx = self.send_rewards(rewarding_amounts).await?;
+        let rewarding_tx = self.send_rewards(rewarding_amounts, &total_spent).await?;
 
         Ok(RewardingResult {
             total_spent,
@@ -259,24 +521,154 @@ impl Rewarder {
         })
     }
 
+    async fn produce_reward_manifest(
+        &self,
+        rewards: &EpochRewards,
+        rewarding_result: &Result<RewardingResult, NymRewarderError>,
+    ) -> Result<(), NymRewarderError> {
+        let Some(identity) = &self.manifest_signer else {
+            return Ok(());
+        };
+
+        let distributor = self.nyxd_client.address().await;
+        let signed =
+            RewardManifest::new(rewards, rewarding_result, &distributor)?.sign(identity)?;
+        self.storage.save_reward_manifest(&signed).await?;
+
+        if let Some(directory) = &self.config.manifests.publish_directory {
+            let path = signed.publish(directory)?;
+            info!(
+                "published the reward manifest of epoch {} to {}",
+                rewards.epoch.id,
+                path.display()
+            );
+        }
+        Ok(())
+    }
+
     async fn handle_epoch_end(&mut self) {
         info!("handling the epoch end");
-        let base_rewards = self.determine_epoch_rewards().await;
+        let base_rewards = self.determine_epoch_rewards(true).await;
+        if let Ok(Some(signing)) = &base_rewards.signing {
+            if let Err(err) = self
+                .storage
+                .save_voting_power_snapshot(self.current_epoch.id, signing)
+                .await
+            {
+                error!("failed to persist the voting power snapshot: {err}")
+            }
+        }
 
         let rewarding_result = self
             .calculate_and_send_epoch_rewards(&base_rewards)
             .await
             .inspect_err(|err| error!("failed to determine and send epoch_rewards: {err}"));
 
+        if let Some(notifier) = &self.notifier {
+            notifier
+                .notify(&RewardingNotification::new(
+                    &base_rewards,
+                    &rewarding_result,
+                ))
+                .await
+        }
+
+        if let Err(err) = self
+            .produce_reward_manifest(&base_rewards, &rewarding_result)
+            .await
+        {
+            error!("failed to produce the reward manifest: {err}")
+        }
+
+        let epoch_id = base_rewards.epoch.id;
         if let Err(err) = self
             .storage
             .save_rewarding_information(base_rewards, rewarding_result)
             .await
         {
             error!("failed to persist rewarding information: {err}")
+        } else if let Err(err) = self.storage.record_confirmed_epoch_rewards(epoch_id).await {
+            error!("failed to mark epoch {epoch_id} as confirmed: {err}")
+        }
+
+        self.current_epoch = next_epoch(&self.config, &self.nyxd_client, &self.current_epoch).await;
+    }
+
+    async fn evaluate_current_epoch(&mut self) -> EpochEvaluation {
+        info!(
+            "evaluating the rewards of the current epoch {}",
+            self.current_epoch.id
+        );
+        let rewards = self.determine_epoch_rewards(false).await;
+        let denom = &rewards.total_budget.denom;
+
+        let errors = [
+            rewards.signing.as_ref().err(),
+            rewards.credentials.as_ref().err(),
+            rewards.gateway_uptime.as_ref().err(),
+        ]
+        .into_iter()
+        .flatten()
+        .map(ToString::to_string);
+
+        let (amounts, errors) = match rewards.amounts() {
+            Ok(amounts) => (amounts, errors.collect()),
+            Err(err) => (Vec::new(), errors.chain([err.to_string()]).collect()),
+        };
+
+        EpochEvaluation {
+            epoch_id: rewards.epoch.id,
+            epoch_start: rewards.epoch.start_rfc3339(),
+            epoch_end: rewards.epoch.end_rfc3339(),
+            total_budget: rewards.total_budget.to_string(),
+            total_amount: total_spent(&amounts, denom).to_string(),
+            rewards: amounts
+                .into_iter()
+                .map(|(account, amount)| RewardAmount {
+                    account: account.to_string(),
+                    amount: amount[0].to_string(),
+                })
+                .collect(),
+            errors,
         }
+    }
 
-        self.current_epoch = self.current_epoch.next();
+    fn current_state(&self) -> RewarderState {
+        RewarderState {
+            run_id: self.run_id,
+            epoch_id: self.current_epoch.id,
+            epoch_start: self.current_epoch.start_rfc3339(),
+            epoch_end: self.current_epoch.end_rfc3339(),
+            secs_until_epoch_end: self.current_epoch.until_end().as_secs(),
+            payouts_paused: self.payouts_paused,
+            monitor_only: self.config.block_signing.monitor_only,
+            block_signing_enabled: self.epoch_signing.is_some(),
+            credential_issuance_enabled: self.credential_issuance.is_some(),
+            gateway_uptime_enabled: self.gateway_uptime.is_some(),
+        }
+    }
+
+    async fn handle_admin_command(&mut self, command: AdminCommand) {
+        // if the requester has gone away, there's nothing we can do about it
+        match command {
+            AdminCommand::EvaluateEpoch { respond_to } => {
+                let _ = respond_to.send(self.evaluate_current_epoch().await);
+            }
+            AdminCommand::SetPayoutsPaused { paused, respond_to } => {
+                let resumed = self.payouts_paused && !paused;
+                if paused != self.payouts_paused {
+                    warn!("setting payouts paused to {paused} via the admin api");
+                }
+                self.payouts_paused = paused;
+                let _ = respond_to.send(PayoutsStatus { paused });
+                if resumed {
+                    self.send_pending_rewards().await;
+                }
+            }
+            AdminCommand::GetState { respond_to } => {
+                let _ = respond_to.send(self.current_state());
+            }
+        }
     }
 
     pub async fn run(mut self) -> Result<(), NymRewarderError> {
@@ -291,6 +683,17 @@ impl Rewarder {
                 self.nyxd_client.clone(),
                 task_manager.subscribe(),
             );
+            credential_issuance.start_api_prober(
+                self.config.issuance_monitor.clone(),
+                self.nyxd_client.clone(),
+                task_manager.subscribe(),
+            );
+        }
+
+        if self.config.retention.enabled {
+            let pruner = HistoryPruner::new(self.config.retention.clone(), self.storage.clone());
+            let task_client = task_manager.subscribe();
+            tokio::spawn(async move { pruner.run(task_client).await });
         }
 
         let mut scraper_cancellation: OptionFuture<_> =
@@ -304,6 +707,19 @@ impl Rewarder {
             }
             .into();
 
+        let mut admin_commands = if self.config.admin.enabled {
+            Some(start_admin_api(
+                &self.config.admin,
+                task_manager.subscribe(),
+            )?)
+        } else {
+            None
+        };
+
+        // the payouts are never paused after a restart,
+        // so send out whatever got deferred by the previous run
+        self.send_pending_rewards().await;
+
         let until_end = self.current_epoch.until_end();
 
         info!(
@@ -311,14 +727,17 @@ impl Rewarder {
             self.current_epoch.id,
             until_end.as_secs()
         );
-        let mut epoch_ticker = interval_at(
-            Instant::now().add(until_end),
-            self.config.rewarding.epoch_duration,
-        );
+        // note: the epoch end is re-armed after every epoch, rather than relying on a fixed interval,
+        // as with the chain alignment, the epochs do not necessarily have the same length
+        let epoch_end = sleep(until_end);
+        pin!(epoch_end);
 
         let shutdown_future = task_manager.catch_interrupt();
         pin!(shutdown_future);
 
+        // note: if the signal arrives while an epoch is being handled,
+        // it's only going to be acted upon once the processing is finished
+        let mut interrupted = false;
         loop {
             tokio::select! {
                 biased;
@@ -327,20 +746,51 @@ impl Rewarder {
                     if let Err(err) = interrupt_res {
                         error!("runtime interrupt failure: {err}")
                     }
+                    interrupted = true;
                     break;
                 }
                 _ = &mut scraper_cancellation, if !scraper_cancellation.is_terminated() => {
                     warn!("the nyxd scraper has been cancelled");
                     break
                 }
-                _ = epoch_ticker.tick() => self.handle_epoch_end().await
+                _ = &mut epoch_end => {
+                    self.handle_epoch_end().await;
+                    epoch_end.as_mut().reset(Instant::now().add(self.current_epoch.until_end()));
+                }
+                Some(command) = async { admin_commands.as_mut()?.recv().await }, if admin_commands.is_some() => {
+                    self.handle_admin_command(command).await
+                }
             }
         }
 
-        if let Some(epoch_signing) = self.epoch_signing {
+        if interrupted && self.current_epoch.until_end().is_zero() {
+            // the epoch has already ended, but its processing got preempted by the shutdown signal
+            info!(
+                "finishing the processing of epoch {} before shutting down",
+                self.current_epoch.id
+            );
+            self.handle_epoch_end().await;
+        }
+
+        if let Some(epoch_signing) = self.epoch_signing.take() {
             epoch_signing.nyxd_scraper.stop().await;
         }
 
+        if interrupted {
+            if let Err(err) = self
+                .storage
+                .record_clean_shutdown(self.run_id, self.current_epoch)
+                .await
+            {
+                error!("failed to record the clean shutdown: {err}")
+            } else {
+                info!(
+                    "checkpointed the in-progress epoch {}",
+                    self.current_epoch.id
+                );
+            }
+        }
+
         Ok(())
     }
 }
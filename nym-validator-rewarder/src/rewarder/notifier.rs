@@ -0,0 +1,173 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::{Notifications, Webhook, WebhookFormat};
+use crate::error::NymRewarderError;
+use crate::rewarder::{EpochRewards, RewardingResult};
+use hmac::{Hmac, Mac};
+use reqwest::header::CONTENT_TYPE;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::{debug, warn};
+
+pub(crate) const SIGNATURE_HEADER: &str = "X-Rewarder-Signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RewardingNotification {
+    pub epoch_id: i64,
+    pub epoch_start: String,
+    pub epoch_end: String,
+
+    pub success: bool,
+    pub total_budget: String,
+    pub total_spent: Option<String>,
+    pub rewarding_tx: Option<String>,
+    pub rewarding_error: Option<String>,
+}
+
+impl RewardingNotification {
+    pub(crate) fn new(
+        rewards: &EpochRewards,
+        rewarding_result: &Result<RewardingResult, NymRewarderError>,
+    ) -> Self {
+        let (total_spent, rewarding_tx, rewarding_error) = match rewarding_result {
+            Ok(res) => (
+                Some(res.total_spent.to_string()),
+                Some(res.rewarding_tx.to_string()),
+                None,
+            ),
+            Err(err) => (None, None, Some(err.to_string())),
+        };
+
+        RewardingNotification {
+            epoch_id: rewards.epoch.id,
+            epoch_start: rewards.epoch.start_rfc3339(),
+            epoch_end: rewards.epoch.end_rfc3339(),
+            success: rewarding_result.is_ok(),
+            total_budget: rewards.total_budget.to_string(),
+            total_spent,
+            rewarding_tx,
+            rewarding_error,
+        }
+    }
+
+    fn summary(&self) -> String {
+        if self.success {
+            format!(
+                "rewarding for epoch {} ({} - {}) has succeeded. spent {} out of {}. tx: {}",
+                self.epoch_id,
+                self.epoch_start,
+                self.epoch_end,
+                self.total_spent.as_deref().unwrap_or_default(),
+                self.total_budget,
+                self.rewarding_tx.as_deref().unwrap_or_default(),
+            )
+        } else {
+            format!(
+                "rewarding for epoch {} ({} - {}) has FAILED: {}",
+                self.epoch_id,
+                self.epoch_start,
+                self.epoch_end,
+                self.rewarding_error.as_deref().unwrap_or_default(),
+            )
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SlackMessage {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct TelegramMessage<'a> {
+    chat_id: &'a str,
+    text: String,
+}
+
+fn sign_payload(secret: &str, payload: &[u8]) -> String {
+    // safety: HMAC can take a key of any size
+    #[allow(clippy::unwrap_used)]
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).unwrap();
+    mac.update(payload);
+    hex::encode(mac.finalize().into_bytes())
+}
+
+pub(crate) struct Notifier {
+    client: reqwest::Client,
+    webhooks: Vec<Webhook>,
+}
+
+impl Notifier {
+    pub(crate) fn new(config: &Notifications) -> Result<Option<Self>, NymRewarderError> {
+        if config.webhooks.is_empty() {
+            return Ok(None);
+        }
+
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .build()
+            .map_err(|source| NymRewarderError::WebhookClientBuildFailure { source })?;
+
+        Ok(Some(Notifier {
+            client,
+            webhooks: config.webhooks.clone(),
+        }))
+    }
+
+    fn payload(
+        webhook: &Webhook,
+        notification: &RewardingNotification,
+    ) -> Result<Vec<u8>, NymRewarderError> {
+        let payload = match webhook.format {
+            WebhookFormat::Json => serde_json::to_vec(notification)?,
+            WebhookFormat::Slack => serde_json::to_vec(&SlackMessage {
+                text: notification.summary(),
+            })?,
+            WebhookFormat::Telegram => serde_json::to_vec(&TelegramMessage {
+                chat_id: webhook.telegram_chat_id.as_deref().unwrap_or_default(),
+                text: notification.summary(),
+            })?,
+        };
+        Ok(payload)
+    }
+
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        notification: &RewardingNotification,
+    ) -> Result<(), NymRewarderError> {
+        let payload = Self::payload(webhook, notification)?;
+
+        let mut request = self
+            .client
+            .post(webhook.url.clone())
+            .header(CONTENT_TYPE, "application/json");
+        if let Some(secret) = &webhook.secret {
+            request = request.header(SIGNATURE_HEADER, sign_payload(secret, &payload))
+        }
+
+        request
+            .body(payload)
+            .send()
+            .await
+            .and_then(|res| res.error_for_status())
+            .map_err(|source| NymRewarderError::WebhookDeliveryFailure { source })?;
+        Ok(())
+    }
+
+    pub(crate) async fn notify(&self, notification: &RewardingNotification) {
+        for webhook in &self.webhooks {
+            if webhook.only_failures && notification.success {
+                continue;
+            }
+
+            debug!("notifying {} about epoch rewarding outcome", webhook.url);
+            if let Err(err) = self.send(webhook, notification).await {
+                warn!("failed to notify {}: {err}", webhook.url)
+            }
+        }
+    }
+}
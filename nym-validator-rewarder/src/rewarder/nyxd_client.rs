@@ -3,7 +3,11 @@
 
 use crate::config::Config;
 use crate::error::NymRewarderError;
+use crate::rewarder::block_signing::jailing::JailingEvent;
 use crate::rewarder::credential_issuance::types::{addr_to_account_id, CredentialIssuer};
+use crate::rewarder::epoch::ChainEpoch;
+use async_trait::async_trait;
+use futures::{stream, StreamExt, TryStreamExt};
 use nym_coconut::{Base58, VerificationKey};
 use nym_coconut_bandwidth_contract_common::events::{
     COSMWASM_DEPOSITED_FUNDS_EVENT_TYPE, DEPOSIT_INFO, DEPOSIT_VALUE,
@@ -11,13 +15,16 @@ use nym_coconut_bandwidth_contract_common::events::{
 use nym_coconut_dkg_common::types::Epoch;
 use nym_crypto::asymmetric::ed25519;
 use nym_network_defaults::NymNetworkDetails;
-use nym_validator_client::nyxd::contract_traits::{DkgQueryClient, PagedDkgQueryClient};
+use nym_validator_client::nyxd::contract_traits::{
+    DkgQueryClient, MixnetQueryClient, PagedDkgQueryClient,
+};
 use nym_validator_client::nyxd::helpers::find_tx_attribute;
 use nym_validator_client::nyxd::module_traits::staking::{
     QueryHistoricalInfoResponse, QueryValidatorsResponse,
 };
 use nym_validator_client::nyxd::{
-    AccountId, Coin, CosmWasmClient, Hash, PageRequest, StakingQueryClient,
+    tx, AccountId, Coin, CosmWasmClient, Hash, Order, PageRequest, Query, StakingQueryClient,
+    TendermintRpcClient,
 };
 use nym_validator_client::{nyxd, DirectSigningHttpRpcNyxdClient};
 use std::collections::HashMap;
@@ -25,13 +32,171 @@ use std::ops::Deref;
 use std::sync::Arc;
 use tokio::sync::RwLock;
 
+const SLASH_EVENT_TYPE: &str = "slash";
+const JAILED_ATTRIBUTE: &str = "jailed";
+const UNJAIL_MESSAGE_ACTION: &str = "/cosmos.slashing.v1beta1.MsgUnjail";
+const BLOCK_SEARCH_PAGE_SIZE: u8 = 100;
+const MAX_CONCURRENT_BLOCK_RESULTS_QUERIES: usize = 16;
+
+fn rewarding_memo(epoch: crate::rewarder::Epoch) -> String {
+    format!("sending rewards for {epoch:?}")
+}
+
+/// Chain operations the rewarder relies on. It's implemented by the signing nyxd client
+/// used in production and can be swapped out for a mock so that the whole epoch processing
+/// could be exercised without a live chain.
+#[async_trait]
+pub(crate) trait ChainClient: Send + Sync {
+    /// Address of the account sending out the rewards.
+    async fn address(&self) -> AccountId;
+
+    async fn balance(&self, denom: &str) -> Result<Coin, NymRewarderError>;
+
+    async fn send_rewards(
+        &self,
+        epoch: crate::rewarder::Epoch,
+        amounts: Vec<(AccountId, Vec<Coin>)>,
+    ) -> Result<Hash, NymRewarderError>;
+
+    async fn current_block_height(&self) -> Result<i64, NymRewarderError>;
+
+    /// Attempt to find a successful rewarding transaction for the provided epoch
+    /// that got included in a block at or after the specified height.
+    async fn find_rewarding_tx(
+        &self,
+        epoch: crate::rewarder::Epoch,
+        from_height: i64,
+    ) -> Result<Option<Hash>, NymRewarderError>;
+
+    async fn historical_info(
+        &self,
+        height: i64,
+    ) -> Result<QueryHistoricalInfoResponse, NymRewarderError>;
+
+    async fn validators(
+        &self,
+        pagination: Option<PageRequest>,
+    ) -> Result<QueryValidatorsResponse, NymRewarderError>;
+
+    /// Retrieve all jailing and unjailing events that happened between the provided heights (inclusive).
+    async fn jailing_events(
+        &self,
+        from_height: i64,
+        to_height: i64,
+    ) -> Result<Vec<JailingEvent>, NymRewarderError>;
+
+    async fn dkg_epoch(&self) -> Result<Epoch, NymRewarderError>;
+
+    /// Retrieve the boundaries of the current epoch of the mixnet contract.
+    async fn mixnet_epoch(&self) -> Result<ChainEpoch, NymRewarderError>;
+
+    async fn get_credential_issuers(
+        &self,
+        dkg_epoch: u64,
+    ) -> Result<Vec<CredentialIssuer>, NymRewarderError>;
+
+    async fn get_deposit_transaction_attributes(
+        &self,
+        tx_hash: Hash,
+    ) -> Result<(String, String), NymRewarderError>;
+}
+
 #[derive(Clone)]
 pub struct NyxdClient {
-    inner: Arc<RwLock<DirectSigningHttpRpcNyxdClient>>,
+    inner: Arc<dyn ChainClient>,
 }
 
 impl NyxdClient {
     pub(crate) fn new(config: &Config) -> Result<Self, NymRewarderError> {
+        Ok(NyxdClient {
+            inner: Arc::new(SigningChainClient::new(config)?),
+        })
+    }
+
+    #[cfg(any(test, feature = "test-utils"))]
+    pub(crate) fn new_with_chain_client(inner: Arc<dyn ChainClient>) -> Self {
+        NyxdClient { inner }
+    }
+
+    pub(crate) async fn address(&self) -> AccountId {
+        self.inner.address().await
+    }
+
+    pub(crate) async fn balance(&self, denom: &str) -> Result<Coin, NymRewarderError> {
+        self.inner.balance(denom).await
+    }
+
+    pub(crate) async fn send_rewards(
+        &self,
+        epoch: crate::rewarder::Epoch,
+        amounts: Vec<(AccountId, Vec<Coin>)>,
+    ) -> Result<Hash, NymRewarderError> {
+        self.inner.send_rewards(epoch, amounts).await
+    }
+
+    pub(crate) async fn current_block_height(&self) -> Result<i64, NymRewarderError> {
+        self.inner.current_block_height().await
+    }
+
+    pub(crate) async fn find_rewarding_tx(
+        &self,
+        epoch: crate::rewarder::Epoch,
+        from_height: i64,
+    ) -> Result<Option<Hash>, NymRewarderError> {
+        self.inner.find_rewarding_tx(epoch, from_height).await
+    }
+
+    pub(crate) async fn historical_info(
+        &self,
+        height: i64,
+    ) -> Result<QueryHistoricalInfoResponse, NymRewarderError> {
+        self.inner.historical_info(height).await
+    }
+
+    pub(crate) async fn validators(
+        &self,
+        pagination: Option<PageRequest>,
+    ) -> Result<QueryValidatorsResponse, NymRewarderError> {
+        self.inner.validators(pagination).await
+    }
+
+    pub(crate) async fn jailing_events(
+        &self,
+        from_height: i64,
+        to_height: i64,
+    ) -> Result<Vec<JailingEvent>, NymRewarderError> {
+        self.inner.jailing_events(from_height, to_height).await
+    }
+
+    pub(crate) async fn dkg_epoch(&self) -> Result<Epoch, NymRewarderError> {
+        self.inner.dkg_epoch().await
+    }
+
+    pub(crate) async fn mixnet_epoch(&self) -> Result<ChainEpoch, NymRewarderError> {
+        self.inner.mixnet_epoch().await
+    }
+
+    pub(crate) async fn get_credential_issuers(
+        &self,
+        dkg_epoch: u64,
+    ) -> Result<Vec<CredentialIssuer>, NymRewarderError> {
+        self.inner.get_credential_issuers(dkg_epoch).await
+    }
+
+    pub(crate) async fn get_deposit_transaction_attributes(
+        &self,
+        tx_hash: Hash,
+    ) -> Result<(String, String), NymRewarderError> {
+        self.inner.get_deposit_transaction_attributes(tx_hash).await
+    }
+}
+
+struct SigningChainClient {
+    inner: RwLock<DirectSigningHttpRpcNyxdClient>,
+}
+
+impl SigningChainClient {
+    fn new(config: &Config) -> Result<Self, NymRewarderError> {
         let client_config =
             nyxd::Config::try_from_nym_network_details(&NymNetworkDetails::new_from_env())?;
         let nyxd_url = config.base.upstream_nyxd.as_str();
@@ -44,12 +209,19 @@ impl NyxdClient {
             mnemonic,
         )?;
 
-        Ok(NyxdClient {
-            inner: Arc::new(RwLock::new(inner)),
+        Ok(SigningChainClient {
+            inner: RwLock::new(inner),
         })
     }
+}
 
-    pub(crate) async fn balance(&self, denom: &str) -> Result<Coin, NymRewarderError> {
+#[async_trait]
+impl ChainClient for SigningChainClient {
+    async fn address(&self) -> AccountId {
+        self.inner.read().await.address()
+    }
+
+    async fn balance(&self, denom: &str) -> Result<Coin, NymRewarderError> {
         let guard = self.inner.read().await;
         let address = guard.address();
         Ok(guard
@@ -58,7 +230,7 @@ impl NyxdClient {
             .unwrap_or(Coin::new(0, denom)))
     }
 
-    pub(crate) async fn send_rewards(
+    async fn send_rewards(
         &self,
         epoch: crate::rewarder::Epoch,
         amounts: Vec<(AccountId, Vec<Coin>)>,
@@ -66,20 +238,55 @@ impl NyxdClient {
         self.inner
             .write()
             .await
-            .send_multiple(amounts, format!("sending rewards for {epoch:?}"), None)
+            .send_multiple(amounts, rewarding_memo(epoch), None)
             .await
             .map(|res| res.hash)
             .map_err(Into::into)
     }
 
-    pub(crate) async fn historical_info(
+    async fn current_block_height(&self) -> Result<i64, NymRewarderError> {
+        Ok(self
+            .inner
+            .read()
+            .await
+            .get_current_block_height()
+            .await?
+            .value() as i64)
+    }
+
+    async fn find_rewarding_tx(
+        &self,
+        epoch: crate::rewarder::Epoch,
+        from_height: i64,
+    ) -> Result<Option<Hash>, NymRewarderError> {
+        let guard = self.inner.read().await;
+        let query = Query::eq("message.sender", guard.address().to_string())
+            .and_gte("tx.height", from_height.max(0) as u64);
+        let memo = rewarding_memo(epoch);
+
+        for res in guard.search_tx(query).await? {
+            if res.tx_result.code.is_err() {
+                continue;
+            }
+            let Ok(decoded) = tx::Tx::from_bytes(&res.tx) else {
+                continue;
+            };
+            if decoded.body.memo == memo {
+                return Ok(Some(res.hash));
+            }
+        }
+
+        Ok(None)
+    }
+
+    async fn historical_info(
         &self,
         height: i64,
     ) -> Result<QueryHistoricalInfoResponse, NymRewarderError> {
         Ok(self.inner.read().await.historical_info(height).await?)
     }
 
-    pub(crate) async fn validators(
+    async fn validators(
         &self,
         pagination: Option<PageRequest>,
     ) -> Result<QueryValidatorsResponse, NymRewarderError> {
@@ -87,11 +294,108 @@ impl NyxdClient {
         Ok(StakingQueryClient::validators(guard.deref(), "".to_string(), pagination).await?)
     }
 
-    pub(crate) async fn dkg_epoch(&self) -> Result<Epoch, NymRewarderError> {
+    async fn jailing_events(
+        &self,
+        from_height: i64,
+        to_height: i64,
+    ) -> Result<Vec<JailingEvent>, NymRewarderError> {
+        let guard = self.inner.read().await;
+        let from_height = from_height.max(0) as u64;
+        let to_height = to_height.max(0) as u64;
+        let mut events = Vec::new();
+
+        // validators are jailed by the slashing module in the begin blocker
+        let query = Query::exists(format!("{SLASH_EVENT_TYPE}.{JAILED_ATTRIBUTE}"))
+            .and_gte("block.height", from_height)
+            .and_lte("block.height", to_height);
+        let mut page = 1;
+        loop {
+            let res = guard
+                .block_search(
+                    query.clone(),
+                    page,
+                    BLOCK_SEARCH_PAGE_SIZE,
+                    Order::Ascending,
+                )
+                .await?;
+            let block_results = stream::iter(&res.blocks)
+                .map(|block| guard.block_results(block.block.header.height))
+                .buffered(MAX_CONCURRENT_BLOCK_RESULTS_QUERIES)
+                .try_collect::<Vec<_>>()
+                .await?;
+            for results in block_results {
+                let height = results.height;
+                for event in results.begin_block_events.iter().flatten() {
+                    if event.kind != SLASH_EVENT_TYPE {
+                        continue;
+                    }
+                    for attribute in &event.attributes {
+                        if attribute.key == JAILED_ATTRIBUTE {
+                            events.push(JailingEvent::Jailed {
+                                height: height.value() as i64,
+                                consensus_address: attribute.value.clone(),
+                            })
+                        }
+                    }
+                }
+            }
+
+            if page * BLOCK_SEARCH_PAGE_SIZE as u32 >= res.total_count {
+                break;
+            }
+            page += 1;
+        }
+
+        // while they have to unjail themselves with a transaction
+        let query = Query::eq("message.action", UNJAIL_MESSAGE_ACTION)
+            .and_gte("tx.height", from_height)
+            .and_lte("tx.height", to_height);
+        for res in guard.search_tx(query).await? {
+            if res.tx_result.code.is_err() {
+                continue;
+            }
+            // the slashing module emits the operator address of the validator as the sender of the message
+            for event in &res.tx_result.events {
+                if event.kind != "message"
+                    || !event
+                        .attributes
+                        .iter()
+                        .any(|attr| attr.key == "module" && attr.value == "slashing")
+                {
+                    continue;
+                }
+                if let Some(sender) = event.attributes.iter().find(|attr| attr.key == "sender") {
+                    events.push(JailingEvent::Unjailed {
+                        height: res.height.value() as i64,
+                        operator_address: sender.value.clone(),
+                    })
+                }
+            }
+        }
+
+        Ok(events)
+    }
+
+    async fn dkg_epoch(&self) -> Result<Epoch, NymRewarderError> {
         Ok(self.inner.read().await.get_current_epoch().await?)
     }
 
-    pub(crate) async fn get_credential_issuers(
+    async fn mixnet_epoch(&self) -> Result<ChainEpoch, NymRewarderError> {
+        let interval = self
+            .inner
+            .read()
+            .await
+            .get_current_interval_details()
+            .await?
+            .interval;
+
+        Ok(ChainEpoch {
+            start_time: interval.current_epoch_start(),
+            length: interval.epoch_length(),
+        })
+    }
+
+    async fn get_credential_issuers(
         &self,
         dkg_epoch: u64,
     ) -> Result<Vec<CredentialIssuer>, NymRewarderError> {
@@ -116,6 +420,7 @@ impl NyxdClient {
                             source,
                         },
                     )?,
+                    key_share_verified: share.verified,
                 })
             }
         }
@@ -123,7 +428,7 @@ impl NyxdClient {
         Ok(issuers)
     }
 
-    pub(crate) async fn get_deposit_transaction_attributes(
+    async fn get_deposit_transaction_attributes(
         &self,
         tx_hash: Hash,
     ) -> Result<(String, String), NymRewarderError> {
@@ -0,0 +1,452 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::config::Retention;
+use crate::error::NymRewarderError;
+use crate::rewarder::storage::models::{
+    BlockSigningRewardRecord, CredentialIssuanceRewardRecord, GatewayUptimeRewardRecord,
+    PrunedRewardSummary, VotingPowerSnapshot,
+};
+use crate::rewarder::storage::RewarderStorage;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use nym_task::TaskClient;
+use nym_validator_client::nyxd::Coin;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tokio::time::interval;
+use tracing::{error, info};
+
+pub(crate) const BLOCK_SIGNING_MODULE: &str = "block_signing";
+pub(crate) const CREDENTIAL_ISSUANCE_MODULE: &str = "credential_issuance";
+pub(crate) const GATEWAY_UPTIME_MODULE: &str = "gateway_uptime";
+
+/// Determine the first epoch whose per-account details should be retained,
+/// if any epoch is old enough to get pruned.
+pub(crate) fn pruning_cutoff(last_epoch: i64, detailed_epochs: u32) -> Option<i64> {
+    let cutoff = last_epoch - detailed_epochs as i64 + 1;
+    (cutoff > 0).then_some(cutoff)
+}
+
+/// The per-account rewarding details of all epochs preceding `before_epoch`,
+/// as exported before getting removed from the database.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct RewardsArchive {
+    /// Unix timestamp of when the archive got created.
+    pub created_at: i64,
+    pub before_epoch: i64,
+    pub block_signing: Vec<BlockSigningRewardRecord>,
+    pub credential_issuance: Vec<CredentialIssuanceRewardRecord>,
+    pub gateway_uptime: Vec<GatewayUptimeRewardRecord>,
+    pub voting_power: Vec<VotingPowerSnapshot>,
+}
+
+impl RewardsArchive {
+    pub fn records(&self) -> usize {
+        self.block_signing.len()
+            + self.credential_issuance.len()
+            + self.gateway_uptime.len()
+            + self.voting_power.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.records() == 0
+    }
+
+    pub fn file_name(&self) -> String {
+        format!(
+            "rewarder-archive-before-epoch-{}-{}.json.gz",
+            self.before_epoch, self.created_at
+        )
+    }
+
+    pub fn write_compressed<P: AsRef<Path>>(&self, path: P) -> Result<(), NymRewarderError> {
+        let path = path.as_ref();
+        let write = || -> io::Result<()> {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            let file = BufWriter::new(File::create(path)?);
+            let mut encoder = GzEncoder::new(file, Compression::default());
+            serde_json::to_writer(&mut encoder, self)?;
+            encoder.finish()?.flush()
+        };
+
+        write().map_err(|source| NymRewarderError::ArchiveWriteFailure {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    /// Fold the archived rewards into the existing per-account summaries,
+    /// returning the summaries that got changed.
+    pub(crate) fn updated_summaries(
+        &self,
+        existing: Vec<PrunedRewardSummary>,
+    ) -> Result<Vec<PrunedRewardSummary>, NymRewarderError> {
+        let mut summaries: HashMap<_, _> = existing
+            .into_iter()
+            .map(|s| ((s.operator_account.clone(), s.module.clone()), (s, false)))
+            .collect();
+
+        let rewards = self
+            .block_signing
+            .iter()
+            .map(|r| {
+                (
+                    BLOCK_SIGNING_MODULE,
+                    r.rewarding_epoch_id,
+                    &r.operator_account,
+                    &r.amount,
+                )
+            })
+            .chain(self.credential_issuance.iter().map(|r| {
+                (
+                    CREDENTIAL_ISSUANCE_MODULE,
+                    r.rewarding_epoch_id,
+                    &r.operator_account,
+                    &r.amount,
+                )
+            }))
+            .chain(self.gateway_uptime.iter().map(|r| {
+                (
+                    GATEWAY_UPTIME_MODULE,
+                    r.rewarding_epoch_id,
+                    &r.operator_account,
+                    &r.amount,
+                )
+            }));
+
+        for (module, epoch, account, raw_amount) in rewards {
+            let amount: Coin =
+                raw_amount
+                    .parse()
+                    .map_err(|_| NymRewarderError::MalformedStoredRewardAmount {
+                        amount: raw_amount.clone(),
+                    })?;
+
+            let (summary, changed) = summaries
+                .entry((account.clone(), module.to_string()))
+                .or_insert_with(|| {
+                    (
+                        PrunedRewardSummary {
+                            operator_account: account.clone(),
+                            module: module.to_string(),
+                            first_epoch: epoch,
+                            last_epoch: epoch,
+                            rewarded_epochs: 0,
+                            total_amount: Coin::new(0, &amount.denom).to_string(),
+                        },
+                        true,
+                    )
+                });
+
+            let mut total: Coin = summary.total_amount.parse().map_err(|_| {
+                NymRewarderError::MalformedStoredRewardAmount {
+                    amount: summary.total_amount.clone(),
+                }
+            })?;
+            if total.denom != amount.denom {
+                return Err(NymRewarderError::MismatchedRewardDenoms {
+                    account: account.clone(),
+                    expected: total.denom,
+                    got: amount.denom,
+                });
+            }
+            total.amount += amount.amount;
+
+            summary.total_amount = total.to_string();
+            summary.first_epoch = summary.first_epoch.min(epoch);
+            summary.last_epoch = summary.last_epoch.max(epoch);
+            summary.rewarded_epochs += 1;
+            *changed = true;
+        }
+
+        let mut updated = summaries
+            .into_values()
+            .filter(|(_, changed)| *changed)
+            .map(|(summary, _)| summary)
+            .collect::<Vec<_>>();
+        updated.sort_by(|a, b| {
+            (&a.operator_account, &a.module).cmp(&(&b.operator_account, &b.module))
+        });
+        Ok(updated)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PruningOutcome {
+    pub before_epoch: i64,
+    pub pruned_records: u64,
+    pub archive: Option<PathBuf>,
+}
+
+impl Display for PruningOutcome {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "pruned {} records of epochs preceding epoch {}",
+            self.pruned_records, self.before_epoch
+        )?;
+        if let Some(archive) = &self.archive {
+            write!(f, " (archived to {})", archive.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// Remove the per-account rewarding details of all but the `detailed_epochs` most recent epochs.
+/// If the archive directory is specified, the removed records are exported into it beforehand.
+pub(crate) async fn prune_rewarding_history(
+    storage: &RewarderStorage,
+    detailed_epochs: u32,
+    archive_directory: Option<&Path>,
+) -> Result<Option<PruningOutcome>, NymRewarderError> {
+    if detailed_epochs == 0 {
+        return Err(NymRewarderError::ZeroRetainedEpochs);
+    }
+
+    let Some(last_epoch) = storage.load_last_rewarding_epoch().await? else {
+        return Ok(None);
+    };
+    let Some(before_epoch) = pruning_cutoff(last_epoch.id, detailed_epochs) else {
+        return Ok(None);
+    };
+
+    let archive = storage.load_epoch_details_before(before_epoch).await?;
+    if archive.is_empty() {
+        return Ok(None);
+    }
+
+    let archive_path = match archive_directory {
+        Some(directory) => {
+            let path = directory.join(archive.file_name());
+            archive.write_compressed(&path)?;
+            Some(path)
+        }
+        None => None,
+    };
+
+    let pruned_records = storage.prune_epoch_details(&archive).await?;
+
+    Ok(Some(PruningOutcome {
+        before_epoch,
+        pruned_records,
+        archive: archive_path,
+    }))
+}
+
+/// Background task periodically enforcing the configured retention policy.
+pub(crate) struct HistoryPruner {
+    retention: Retention,
+    storage: RewarderStorage,
+}
+
+impl HistoryPruner {
+    pub(crate) fn new(retention: Retention, storage: RewarderStorage) -> Self {
+        HistoryPruner { retention, storage }
+    }
+
+    async fn prune(&self) {
+        match prune_rewarding_history(
+            &self.storage,
+            self.retention.detailed_epochs,
+            self.retention.archive_directory.as_deref(),
+        )
+        .await
+        {
+            Ok(Some(outcome)) => info!("{outcome}"),
+            Ok(None) => info!("there was nothing to prune"),
+            Err(err) => error!("failed to prune the rewarding history: {err}"),
+        }
+    }
+
+    pub(crate) async fn run(&self, mut task_client: TaskClient) {
+        info!("starting");
+        let mut run_interval = interval(self.retention.pruning_interval);
+
+        while !task_client.is_shutdown() {
+            tokio::select! {
+                biased;
+                _ = task_client.recv() => {
+                    info!("received shutdown");
+                    break
+                }
+                _ = run_interval.tick() => self.prune().await,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rewarder::testing::test_config;
+    use flate2::read::GzDecoder;
+
+    fn block_signing_reward(epoch: i64, account: &str, amount: &str) -> BlockSigningRewardRecord {
+        BlockSigningRewardRecord {
+            rewarding_epoch_id: epoch,
+            validator_consensus_address: format!("{account}-cons"),
+            operator_account: account.to_string(),
+            payout_account: None,
+            whitelisted: true,
+            amount: amount.to_string(),
+            voting_power: 100,
+            voting_power_share: "0.5".to_string(),
+            signed_blocks: 10,
+            signed_blocks_percent: "1".to_string(),
+            forfeited: false,
+            forfeited_amount: None,
+            redistributed_amount: None,
+        }
+    }
+
+    fn archive(before_epoch: i64, block_signing: Vec<BlockSigningRewardRecord>) -> RewardsArchive {
+        RewardsArchive {
+            created_at: 0,
+            before_epoch,
+            block_signing,
+            credential_issuance: vec![],
+            gateway_uptime: vec![],
+            voting_power: vec![],
+        }
+    }
+
+    #[test]
+    fn pruning_cutoff_keeps_requested_epochs() {
+        assert_eq!(pruning_cutoff(10, 5), Some(6));
+        assert_eq!(pruning_cutoff(10, 10), Some(1));
+        assert_eq!(pruning_cutoff(10, 11), None);
+        assert_eq!(pruning_cutoff(0, 1), None);
+    }
+
+    #[test]
+    fn summaries_are_aggregated_per_account() {
+        let existing = vec![PrunedRewardSummary {
+            operator_account: "alice".to_string(),
+            module: BLOCK_SIGNING_MODULE.to_string(),
+            first_epoch: 0,
+            last_epoch: 1,
+            rewarded_epochs: 2,
+            total_amount: "100unym".to_string(),
+        }];
+
+        let archive = archive(
+            4,
+            vec![
+                block_signing_reward(2, "alice", "10unym"),
+                block_signing_reward(3, "alice", "20unym"),
+                block_signing_reward(3, "bob", "5unym"),
+            ],
+        );
+
+        let updated = archive.updated_summaries(existing).unwrap();
+        assert_eq!(
+            updated,
+            vec![
+                PrunedRewardSummary {
+                    operator_account: "alice".to_string(),
+                    module: BLOCK_SIGNING_MODULE.to_string(),
+                    first_epoch: 0,
+                    last_epoch: 3,
+                    rewarded_epochs: 4,
+                    total_amount: "130unym".to_string(),
+                },
+                PrunedRewardSummary {
+                    operator_account: "bob".to_string(),
+                    module: BLOCK_SIGNING_MODULE.to_string(),
+                    first_epoch: 3,
+                    last_epoch: 3,
+                    rewarded_epochs: 1,
+                    total_amount: "5unym".to_string(),
+                },
+            ]
+        );
+
+        let mismatched = archive(4, vec![block_signing_reward(2, "bob", "5unyx")]);
+        assert!(matches!(
+            mismatched.updated_summaries(updated),
+            Err(NymRewarderError::MismatchedRewardDenoms { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn old_epoch_details_are_archived_and_pruned() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = test_config(data_dir.path());
+        let storage = RewarderStorage::init(&config).await.unwrap();
+
+        for epoch in 0..5 {
+            storage
+                .manager
+                .insert_voting_power_snapshot(
+                    epoch,
+                    "nvalcons1".to_string(),
+                    true,
+                    epoch * 100,
+                    1000,
+                    "1".to_string(),
+                )
+                .await
+                .unwrap();
+            storage
+                .manager
+                .insert_rewarding_epoch(
+                    crate::rewarder::epoch::Epoch {
+                        id: epoch,
+                        start_time: time::OffsetDateTime::UNIX_EPOCH,
+                        end_time: time::OffsetDateTime::UNIX_EPOCH,
+                    },
+                    "1000unym".to_string(),
+                    "1000unym".to_string(),
+                    None,
+                    None,
+                )
+                .await
+                .unwrap();
+        }
+
+        let archive_dir = data_dir.path().join("archive");
+        let outcome = prune_rewarding_history(&storage, 2, Some(&archive_dir))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(outcome.before_epoch, 3);
+        assert_eq!(outcome.pruned_records, 3);
+
+        let archived: RewardsArchive = serde_json::from_reader(GzDecoder::new(
+            File::open(outcome.archive.unwrap()).unwrap(),
+        ))
+        .unwrap();
+        assert_eq!(archived.voting_power.len(), 3);
+        assert!(archived
+            .voting_power
+            .iter()
+            .all(|snapshot| snapshot.rewarding_epoch_id < 3));
+
+        assert!(storage
+            .get_epoch_voting_power_snapshot(2)
+            .await
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            storage
+                .get_epoch_voting_power_snapshot(3)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        // nothing else is old enough to get pruned
+        assert!(prune_rewarding_history(&storage, 2, None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,326 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::rewarder::credential_issuance::violation::RawIssuanceViolation;
+use crate::rewarder::epoch::Epoch;
+use crate::rewarder::epoch_processing::RawEpochProcessingState;
+use crate::rewarder::storage::models::{
+    BlockSigningRewardRecord, CredentialIssuanceRewardRecord, GatewayUptimeRewardRecord,
+    IssuerApiAvailability, PrunedRewardSummary, RawRewardManifest, RewarderRun,
+    RewardingEpochSummary, VotingPowerSnapshot,
+};
+use async_trait::async_trait;
+use time::OffsetDateTime;
+
+pub(crate) mod postgres;
+pub(crate) mod sqlite;
+
+/// Database backend used for persisting the rewarding history.
+#[async_trait]
+pub(crate) trait StorageManager: Send + Sync {
+    async fn load_last_rewarding_epoch(&self) -> Result<Option<Epoch>, sqlx::Error>;
+
+    async fn rewarding_epoch_exists(&self, epoch: i64) -> Result<bool, sqlx::Error>;
+
+    async fn get_rewarding_epoch_summary(
+        &self,
+        epoch: i64,
+    ) -> Result<Option<RewardingEpochSummary>, sqlx::Error>;
+
+    async fn get_epoch_block_signing_rewards(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<BlockSigningRewardRecord>, sqlx::Error>;
+
+    async fn load_unfinished_epoch_processing_state(
+        &self,
+    ) -> Result<Option<RawEpochProcessingState>, sqlx::Error>;
+
+    async fn insert_epoch_processing_state(
+        &self,
+        epoch: Epoch,
+        phase: &str,
+        rewarding_amounts: String,
+        budget: String,
+        total_spent: String,
+        starting_height: i64,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn update_epoch_processing_phase(
+        &self,
+        epoch: i64,
+        phase: &str,
+        rewarding_tx: Option<String>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn load_pending_epoch_processing_states(
+        &self,
+    ) -> Result<Vec<RawEpochProcessingState>, sqlx::Error>;
+
+    async fn update_epoch_processing_start(
+        &self,
+        epoch: i64,
+        phase: &str,
+        starting_height: i64,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn load_last_rewarder_run(&self) -> Result<Option<RewarderRun>, sqlx::Error>;
+
+    async fn insert_rewarder_run(&self, started_at: OffsetDateTime) -> Result<i64, sqlx::Error>;
+
+    async fn record_clean_shutdown(
+        &self,
+        run_id: i64,
+        in_progress_epoch: Epoch,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_rewarding_epoch(
+        &self,
+        epoch: Epoch,
+        rewarding_budget: String,
+        total_spent: String,
+        rewarding_tx: Option<String>,
+        rewarding_error: Option<String>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn update_rewarding_epoch_payout(
+        &self,
+        epoch: i64,
+        total_spent: String,
+        rewarding_tx: Option<String>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_rewarding_epoch_block_signing(
+        &self,
+        epoch: i64,
+        total_voting_power_at_epoch_start: i64,
+        num_blocks: i64,
+        budget: String,
+    ) -> Result<(), sqlx::Error>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_rewarding_epoch_block_signing_reward(
+        &self,
+        epoch: i64,
+        consensus_address: String,
+        operator_account: String,
+        payout_account: Option<String>,
+        whitelisted: bool,
+        amount: String,
+        voting_power: i64,
+        voting_power_share: String,
+        signed_blocks: i32,
+        signed_blocks_percent: String,
+        forfeited: bool,
+        forfeited_amount: Option<String>,
+        redistributed_amount: Option<String>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_block_signing_jailed_range(
+        &self,
+        epoch: i64,
+        consensus_address: String,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_rewarding_epoch_credential_issuance(
+        &self,
+        epoch: i64,
+        starting_dkg_epoch: i64,
+        ending_dkg_epoch: i64,
+        total_issued_partial_credentials: i64,
+        budget: String,
+    ) -> Result<(), sqlx::Error>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_rewarding_epoch_credential_issuance_reward(
+        &self,
+        epoch: i64,
+        operator_account: String,
+        whitelisted: bool,
+        amount: String,
+        api_endpoint: String,
+        issued_partial_credentials: u32,
+        issued_credentials_share: String,
+        validated_issued_credentials: u32,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_rewarding_epoch_gateway_uptime(
+        &self,
+        epoch: i64,
+        num_gateways: i64,
+        total_uptime: String,
+        budget: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_rewarding_epoch_gateway_uptime_reward(
+        &self,
+        epoch: i64,
+        gateway_identity: String,
+        operator_account: String,
+        amount: String,
+        uptime: String,
+        uptime_share: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_voting_power_snapshot(
+        &self,
+        epoch: i64,
+        validator_consensus_address: String,
+        whitelisted: bool,
+        height: i64,
+        voting_power: i64,
+        voting_power_share: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_epoch_voting_power_snapshot(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error>;
+
+    async fn get_validator_voting_power_history(
+        &self,
+        validator_consensus_address: &str,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error>;
+
+    async fn insert_validated_deposit(
+        &self,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        deposit_tx: String,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_deposit_credential_id(
+        &self,
+        operator_identity_bs58: String,
+        deposit_tx: String,
+    ) -> Result<Option<i64>, sqlx::Error>;
+
+    async fn insert_double_signing_evidence(
+        &self,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        original_credential_id: i64,
+        deposit_tx: String,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_foul_play_evidence(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+        failure_message: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn insert_validation_failure_info(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        failure_message: String,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_block_signing_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<BlockSigningRewardRecord>, sqlx::Error>;
+
+    async fn get_credential_issuance_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<CredentialIssuanceRewardRecord>, sqlx::Error>;
+
+    async fn get_gateway_uptime_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<GatewayUptimeRewardRecord>, sqlx::Error>;
+
+    async fn get_voting_power_snapshots_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error>;
+
+    async fn get_pruned_reward_summaries(&self) -> Result<Vec<PrunedRewardSummary>, sqlx::Error>;
+
+    /// Within a single transaction, save the updated reward summaries and remove
+    /// the per-account details of all epochs preceding the provided one.
+    /// Returns the number of removed rows.
+    async fn prune_epoch_details_before(
+        &self,
+        epoch: i64,
+        summaries: Vec<PrunedRewardSummary>,
+    ) -> Result<u64, sqlx::Error>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_issuance_violation(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        dkg_epoch: i64,
+        credential_id: Option<i64>,
+        reason: &str,
+        details: String,
+        detected_at: OffsetDateTime,
+    ) -> Result<i64, sqlx::Error>;
+
+    async fn get_issuance_violations(
+        &self,
+        operator_account: Option<String>,
+        include_cleared: bool,
+    ) -> Result<Vec<RawIssuanceViolation>, sqlx::Error>;
+
+    async fn get_flagged_issuers(&self) -> Result<Vec<String>, sqlx::Error>;
+
+    async fn clear_issuance_violation(
+        &self,
+        id: i64,
+        clearing_note: Option<String>,
+        cleared_at: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn clear_operator_issuance_violations(
+        &self,
+        operator_account: String,
+        clearing_note: Option<String>,
+        cleared_at: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error>;
+
+    async fn insert_reward_manifest(
+        &self,
+        epoch_id: i64,
+        signer: String,
+        signature: String,
+        manifest: String,
+        created_at: OffsetDateTime,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_reward_manifest(
+        &self,
+        epoch_id: i64,
+    ) -> Result<Option<RawRewardManifest>, sqlx::Error>;
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_issuer_api_probe(
+        &self,
+        operator_account: String,
+        api_endpoint: String,
+        probed_at: OffsetDateTime,
+        available: bool,
+        latency_ms: Option<i64>,
+        tls_valid: Option<bool>,
+        key_available: bool,
+        error: Option<String>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_issuer_api_availability(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<Vec<IssuerApiAvailability>, sqlx::Error>;
+}
@@ -0,0 +1,1175 @@
+// Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::error::NymRewarderError;
+use crate::rewarder::credential_issuance::violation::RawIssuanceViolation;
+use crate::rewarder::epoch::Epoch;
+use crate::rewarder::epoch_processing::RawEpochProcessingState;
+use crate::rewarder::storage::manager::StorageManager;
+use crate::rewarder::storage::models::{
+    BlockSigningRewardRecord, CredentialIssuanceRewardRecord, GatewayUptimeRewardRecord,
+    IssuerApiAvailability, PrunedRewardSummary, RawRewardManifest, RewarderRun,
+    RewardingEpochSummary, VotingPowerSnapshot,
+};
+use async_trait::async_trait;
+use sqlx::ConnectOptions;
+use std::fmt::Debug;
+use std::path::Path;
+use time::OffsetDateTime;
+use tracing::{error, info, instrument};
+
+#[derive(Clone)]
+pub(crate) struct SqliteStorageManager {
+    pub(crate) connection_pool: sqlx::SqlitePool,
+}
+
+impl SqliteStorageManager {
+    #[instrument]
+    pub(crate) async fn init<P: AsRef<Path> + Debug>(
+        database_path: P,
+    ) -> Result<Self, NymRewarderError> {
+        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
+            .filename(database_path)
+            .create_if_missing(true);
+
+        // TODO: do we want auto_vacuum ?
+
+        opts.disable_statement_logging();
+
+        let connection_pool = match sqlx::SqlitePool::connect_with(opts).await {
+            Ok(db) => db,
+            Err(err) => {
+                error!("Failed to connect to SQLx database: {err}");
+                return Err(err.into());
+            }
+        };
+
+        if let Err(err) = sqlx::migrate!("./migrations").run(&connection_pool).await {
+            error!("Failed to initialize SQLx database: {err}");
+            return Err(err.into());
+        }
+
+        info!("Database migration finished!");
+
+        Ok(SqliteStorageManager { connection_pool })
+    }
+}
+
+#[async_trait]
+impl StorageManager for SqliteStorageManager {
+    async fn load_last_rewarding_epoch(&self) -> Result<Option<Epoch>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                    SELECT id, start_time, end_time
+                    FROM rewarding_epoch
+                    ORDER BY id DESC
+                    LIMIT 1
+                "#,
+        )
+        .fetch_optional(&self.connection_pool)
+        .await
+    }
+
+    async fn rewarding_epoch_exists(&self, epoch: i64) -> Result<bool, sqlx::Error> {
+        let exists = sqlx::query!("SELECT id FROM rewarding_epoch WHERE id = ?", epoch)
+            .fetch_optional(&self.connection_pool)
+            .await?
+            .is_some();
+        Ok(exists)
+    }
+
+    async fn get_rewarding_epoch_summary(
+        &self,
+        epoch: i64,
+    ) -> Result<Option<RewardingEpochSummary>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    e.id,
+                    e.start_time,
+                    e.end_time,
+                    e.budget,
+                    e.spent,
+                    e.rewarding_tx,
+                    e.rewarding_error,
+                    b.num_blocks,
+                    b.total_voting_power_at_epoch_start AS total_voting_power
+                FROM rewarding_epoch e
+                LEFT JOIN epoch_block_signing b ON b.rewarding_epoch_id = e.id
+                WHERE e.id = ?
+            "#,
+        )
+        .bind(epoch)
+        .fetch_optional(&self.connection_pool)
+        .await
+    }
+
+    async fn get_epoch_block_signing_rewards(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<BlockSigningRewardRecord>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    rewarding_epoch_id,
+                    validator_consensus_address,
+                    operator_account,
+                    payout_account,
+                    whitelisted,
+                    amount,
+                    voting_power,
+                    voting_power_share,
+                    signed_blocks,
+                    signed_blocks_percent,
+                forfeited,
+                forfeited_amount,
+                redistributed_amount
+                FROM block_signing_reward
+                WHERE rewarding_epoch_id = ?
+                ORDER BY voting_power DESC
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn load_unfinished_epoch_processing_state(
+        &self,
+    ) -> Result<Option<RawEpochProcessingState>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                    SELECT rewarding_epoch_id, start_time, end_time, phase, rewarding_amounts, budget, total_spent, starting_height, rewarding_tx
+                    FROM epoch_processing_state
+                    WHERE phase IN ('computed', 'broadcast')
+                    ORDER BY rewarding_epoch_id DESC
+                    LIMIT 1
+                "#,
+        )
+        .fetch_optional(&self.connection_pool)
+        .await
+    }
+
+    async fn insert_epoch_processing_state(
+        &self,
+        epoch: Epoch,
+        phase: &str,
+        rewarding_amounts: String,
+        budget: String,
+        total_spent: String,
+        starting_height: i64,
+    ) -> Result<(), sqlx::Error> {
+        let now = OffsetDateTime::now_utc();
+        sqlx::query!(
+            r#"
+                INSERT INTO epoch_processing_state (
+                    rewarding_epoch_id,
+                    start_time,
+                    end_time,
+                    phase,
+                    rewarding_amounts,
+                    budget,
+                    total_spent,
+                    starting_height,
+                    last_updated
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            epoch.id,
+            epoch.start_time,
+            epoch.end_time,
+            phase,
+            rewarding_amounts,
+            budget,
+            total_spent,
+            starting_height,
+            now,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn update_epoch_processing_phase(
+        &self,
+        epoch: i64,
+        phase: &str,
+        rewarding_tx: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        let now = OffsetDateTime::now_utc();
+        sqlx::query!(
+            r#"
+                UPDATE epoch_processing_state
+                SET phase = ?, rewarding_tx = COALESCE(?, rewarding_tx), last_updated = ?
+                WHERE rewarding_epoch_id = ?
+            "#,
+            phase,
+            rewarding_tx,
+            now,
+            epoch,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_pending_epoch_processing_states(
+        &self,
+    ) -> Result<Vec<RawEpochProcessingState>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                    SELECT rewarding_epoch_id, start_time, end_time, phase, rewarding_amounts, budget, total_spent, starting_height, rewarding_tx
+                    FROM epoch_processing_state
+                    WHERE phase = 'pending'
+                    ORDER BY rewarding_epoch_id
+                "#,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn update_epoch_processing_start(
+        &self,
+        epoch: i64,
+        phase: &str,
+        starting_height: i64,
+    ) -> Result<(), sqlx::Error> {
+        let now = OffsetDateTime::now_utc();
+        sqlx::query!(
+            r#"
+                UPDATE epoch_processing_state
+                SET phase = ?, starting_height = ?, last_updated = ?
+                WHERE rewarding_epoch_id = ?
+            "#,
+            phase,
+            starting_height,
+            now,
+            epoch,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn load_last_rewarder_run(&self) -> Result<Option<RewarderRun>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                    SELECT id, started_at, stopped_at, clean_shutdown, in_progress_epoch_id, in_progress_epoch_start, in_progress_epoch_end
+                    FROM rewarder_run
+                    ORDER BY id DESC
+                    LIMIT 1
+                "#,
+        )
+        .fetch_optional(&self.connection_pool)
+        .await
+    }
+
+    async fn insert_rewarder_run(&self, started_at: OffsetDateTime) -> Result<i64, sqlx::Error> {
+        let id = sqlx::query!(
+            "INSERT INTO rewarder_run (started_at) VALUES (?)",
+            started_at
+        )
+        .execute(&self.connection_pool)
+        .await?
+        .last_insert_rowid();
+
+        Ok(id)
+    }
+
+    async fn record_clean_shutdown(
+        &self,
+        run_id: i64,
+        in_progress_epoch: Epoch,
+    ) -> Result<(), sqlx::Error> {
+        let now = OffsetDateTime::now_utc();
+        sqlx::query!(
+            r#"
+                UPDATE rewarder_run
+                SET stopped_at = ?,
+                    clean_shutdown = TRUE,
+                    in_progress_epoch_id = ?,
+                    in_progress_epoch_start = ?,
+                    in_progress_epoch_end = ?
+                WHERE id = ?
+            "#,
+            now,
+            in_progress_epoch.id,
+            in_progress_epoch.start_time,
+            in_progress_epoch.end_time,
+            run_id,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_rewarding_epoch(
+        &self,
+        epoch: Epoch,
+        rewarding_budget: String,
+        total_spent: String,
+        rewarding_tx: Option<String>,
+        rewarding_error: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO rewarding_epoch (id, start_time, end_time, budget, spent, rewarding_tx, rewarding_error)
+                VALUES (?, ?, ? ,?, ?, ?, ?)
+            "#,
+            epoch.id,
+            epoch.start_time,
+            epoch.end_time,
+            rewarding_budget,
+            total_spent: String,
+            rewarding_tx,
+            rewarding_error
+        ).execute(&self.connection_pool).await?;
+
+        Ok(())
+    }
+
+    async fn update_rewarding_epoch_payout(
+        &self,
+        epoch: i64,
+        total_spent: String,
+        rewarding_tx: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                UPDATE rewarding_epoch
+                SET spent = ?, rewarding_tx = COALESCE(?, rewarding_tx), rewarding_error = NULL
+                WHERE id = ?
+            "#,
+            total_spent,
+            rewarding_tx,
+            epoch,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_rewarding_epoch_block_signing(
+        &self,
+        epoch: i64,
+        total_voting_power_at_epoch_start: i64,
+        num_blocks: i64,
+        budget: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO epoch_block_signing (rewarding_epoch_id, total_voting_power_at_epoch_start, num_blocks, budget)
+                VALUES (?, ?, ?, ?)
+            "#,
+            epoch,
+            total_voting_power_at_epoch_start,
+            num_blocks,
+            budget,
+        ).execute(&self.connection_pool).await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_rewarding_epoch_block_signing_reward(
+        &self,
+        epoch: i64,
+        consensus_address: String,
+        operator_account: String,
+        payout_account: Option<String>,
+        whitelisted: bool,
+        amount: String,
+        voting_power: i64,
+        voting_power_share: String,
+        signed_blocks: i32,
+        signed_blocks_percent: String,
+        forfeited: bool,
+        forfeited_amount: Option<String>,
+        redistributed_amount: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO block_signing_reward (
+                    rewarding_epoch_id,
+                    validator_consensus_address,
+                    operator_account,
+                    payout_account,
+                    whitelisted,
+                    amount,
+                    voting_power,
+                    voting_power_share,
+                    signed_blocks,
+                    signed_blocks_percent,
+                    forfeited,
+                    forfeited_amount,
+                    redistributed_amount
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            epoch,
+            consensus_address,
+            operator_account,
+            payout_account,
+            whitelisted,
+            amount,
+            voting_power,
+            voting_power_share,
+            signed_blocks,
+            signed_blocks_percent,
+            forfeited,
+            forfeited_amount,
+            redistributed_amount,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_block_signing_jailed_range(
+        &self,
+        epoch: i64,
+        consensus_address: String,
+        start_height: i64,
+        end_height: i64,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+                INSERT INTO block_signing_jailed_range (
+                    rewarding_epoch_id,
+                    validator_consensus_address,
+                    start_height,
+                    end_height
+                ) VALUES (?, ?, ?, ?)
+            "#,
+        )
+        .bind(epoch)
+        .bind(consensus_address)
+        .bind(start_height)
+        .bind(end_height)
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_rewarding_epoch_credential_issuance(
+        &self,
+        epoch: i64,
+        starting_dkg_epoch: i64,
+        ending_dkg_epoch: i64,
+        total_issued_partial_credentials: i64,
+        budget: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO epoch_credential_issuance (
+                    rewarding_epoch_id,
+                    starting_dkg_epoch, 
+                    ending_dkg_epoch, 
+                    total_issued_partial_credentials,
+                    budget
+                )
+                VALUES (?, ?, ?, ?, ?)
+            "#,
+            epoch,
+            starting_dkg_epoch,
+            ending_dkg_epoch,
+            total_issued_partial_credentials,
+            budget,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn insert_rewarding_epoch_credential_issuance_reward(
+        &self,
+        epoch: i64,
+        operator_account: String,
+        whitelisted: bool,
+        amount: String,
+        api_endpoint: String,
+        issued_partial_credentials: u32,
+        issued_credentials_share: String,
+        validated_issued_credentials: u32,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO credential_issuance_reward (
+                    rewarding_epoch_id,
+                    operator_account,
+                    whitelisted,
+                    amount,
+                    api_endpoint,
+                    issued_partial_credentials,
+                    issued_credentials_share,
+                    validated_issued_credentials
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+            epoch,
+            operator_account,
+            whitelisted,
+            amount,
+            api_endpoint,
+            issued_partial_credentials,
+            issued_credentials_share,
+            validated_issued_credentials,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_rewarding_epoch_gateway_uptime(
+        &self,
+        epoch: i64,
+        num_gateways: i64,
+        total_uptime: String,
+        budget: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO epoch_gateway_uptime (
+                    rewarding_epoch_id,
+                    num_gateways,
+                    total_uptime,
+                    budget
+                )
+                VALUES (?, ?, ?, ?)
+            "#,
+            epoch,
+            num_gateways,
+            total_uptime,
+            budget,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_rewarding_epoch_gateway_uptime_reward(
+        &self,
+        epoch: i64,
+        gateway_identity: String,
+        operator_account: String,
+        amount: String,
+        uptime: String,
+        uptime_share: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO gateway_uptime_reward (
+                    rewarding_epoch_id,
+                    gateway_identity,
+                    operator_account,
+                    amount,
+                    uptime,
+                    uptime_share
+                ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            epoch,
+            gateway_identity,
+            operator_account,
+            amount,
+            uptime,
+            uptime_share,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn insert_voting_power_snapshot(
+        &self,
+        epoch: i64,
+        validator_consensus_address: String,
+        whitelisted: bool,
+        height: i64,
+        voting_power: i64,
+        voting_power_share: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO voting_power_snapshot (
+                    rewarding_epoch_id,
+                    validator_consensus_address,
+                    whitelisted,
+                    height,
+                    voting_power,
+                    voting_power_share
+                ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            epoch,
+            validator_consensus_address,
+            whitelisted,
+            height,
+            voting_power,
+            voting_power_share,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn get_epoch_voting_power_snapshot(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT rewarding_epoch_id, validator_consensus_address, whitelisted, height, voting_power, voting_power_share
+                FROM voting_power_snapshot
+                WHERE rewarding_epoch_id = ?
+                ORDER BY voting_power DESC
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_validator_voting_power_history(
+        &self,
+        validator_consensus_address: &str,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT rewarding_epoch_id, validator_consensus_address, whitelisted, height, voting_power, voting_power_share
+                FROM voting_power_snapshot
+                WHERE validator_consensus_address = ?
+                ORDER BY rewarding_epoch_id
+            "#,
+        )
+        .bind(validator_consensus_address)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn insert_validated_deposit(
+        &self,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        deposit_tx: String,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO validated_deposit (
+                    operator_identity_bs58,
+                    credential_id,
+                    deposit_tx,
+                    signed_plaintext,
+                    signature_bs58
+                ) VALUES (?, ?, ?, ?, ?)
+            "#,
+            operator_identity_bs58,
+            credential_id,
+            deposit_tx,
+            signed_plaintext,
+            signature_bs58
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_deposit_credential_id(
+        &self,
+        operator_identity_bs58: String,
+        deposit_tx: String,
+    ) -> Result<Option<i64>, sqlx::Error> {
+        Ok(sqlx::query!(
+            r#"
+                SELECT credential_id
+                FROM validated_deposit
+                WHERE operator_identity_bs58 = ? AND deposit_tx = ?
+            "#,
+            operator_identity_bs58,
+            deposit_tx
+        )
+        .fetch_optional(&self.connection_pool)
+        .await?
+        .map(|record| record.credential_id))
+    }
+
+    async fn insert_double_signing_evidence(
+        &self,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        original_credential_id: i64,
+        deposit_tx: String,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO double_signing_evidence (
+                    operator_identity_bs58,
+                    credential_id,
+                    original_credential_id,
+                    deposit_tx,
+                    signed_plaintext,
+                    signature_bs58
+                ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            operator_identity_bs58,
+            credential_id,
+            original_credential_id,
+            deposit_tx,
+            signed_plaintext,
+            signature_bs58
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_foul_play_evidence(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        credential_id: i64,
+        signed_plaintext: Vec<u8>,
+        signature_bs58: String,
+        failure_message: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO issuance_evidence (
+                    operator_account,
+                    operator_identity_bs58,
+                    credential_id,
+                    signed_plaintext,
+                    signature_bs58,
+                    failure_message
+                ) VALUES (?, ?, ?, ?, ?, ?)
+            "#,
+            operator_account,
+            operator_identity_bs58,
+            credential_id,
+            signed_plaintext,
+            signature_bs58,
+            failure_message,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn insert_validation_failure_info(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        failure_message: String,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query!(
+            r#"
+                INSERT INTO issuance_validation_failure (
+                    operator_account,
+                    operator_identity_bs58,
+                    failure_message
+                ) VALUES (?, ?, ?)
+            "#,
+            operator_account,
+            operator_identity_bs58,
+            failure_message,
+        )
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_block_signing_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<BlockSigningRewardRecord>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    rewarding_epoch_id,
+                    validator_consensus_address,
+                    operator_account,
+                    payout_account,
+                    whitelisted,
+                    amount,
+                    voting_power,
+                    voting_power_share,
+                    signed_blocks,
+                    signed_blocks_percent,
+                forfeited,
+                forfeited_amount,
+                redistributed_amount
+                FROM block_signing_reward
+                WHERE rewarding_epoch_id < ?
+                ORDER BY rewarding_epoch_id
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_credential_issuance_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<CredentialIssuanceRewardRecord>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    rewarding_epoch_id,
+                    operator_account,
+                    amount,
+                    whitelisted,
+                    api_endpoint,
+                    issued_partial_credentials,
+                    issued_credentials_share,
+                    validated_issued_credentials
+                FROM credential_issuance_reward
+                WHERE rewarding_epoch_id < ?
+                ORDER BY rewarding_epoch_id
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_gateway_uptime_rewards_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<GatewayUptimeRewardRecord>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT rewarding_epoch_id, gateway_identity, operator_account, amount, uptime, uptime_share
+                FROM gateway_uptime_reward
+                WHERE rewarding_epoch_id < ?
+                ORDER BY rewarding_epoch_id
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_voting_power_snapshots_before(
+        &self,
+        epoch: i64,
+    ) -> Result<Vec<VotingPowerSnapshot>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT rewarding_epoch_id, validator_consensus_address, whitelisted, height, voting_power, voting_power_share
+                FROM voting_power_snapshot
+                WHERE rewarding_epoch_id < ?
+                ORDER BY rewarding_epoch_id
+            "#,
+        )
+        .bind(epoch)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_pruned_reward_summaries(&self) -> Result<Vec<PrunedRewardSummary>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT operator_account, module, first_epoch, last_epoch, rewarded_epochs, total_amount
+                FROM pruned_reward_summary
+                ORDER BY operator_account, module
+            "#,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn prune_epoch_details_before(
+        &self,
+        epoch: i64,
+        summaries: Vec<PrunedRewardSummary>,
+    ) -> Result<u64, sqlx::Error> {
+        let mut tx = self.connection_pool.begin().await?;
+
+        for summary in summaries {
+            sqlx::query(
+                r#"
+                    INSERT INTO pruned_reward_summary (
+                        operator_account,
+                        module,
+                        first_epoch,
+                        last_epoch,
+                        rewarded_epochs,
+                        total_amount
+                    ) VALUES (?, ?, ?, ?, ?, ?)
+                    ON CONFLICT (operator_account, module) DO UPDATE SET
+                        first_epoch = excluded.first_epoch,
+                        last_epoch = excluded.last_epoch,
+                        rewarded_epochs = excluded.rewarded_epochs,
+                        total_amount = excluded.total_amount
+                "#,
+            )
+            .bind(summary.operator_account)
+            .bind(summary.module)
+            .bind(summary.first_epoch)
+            .bind(summary.last_epoch)
+            .bind(summary.rewarded_epochs)
+            .bind(summary.total_amount)
+            .execute(&mut *tx)
+            .await?;
+        }
+
+        let mut removed = 0;
+        for table in [
+            "block_signing_reward",
+            "block_signing_jailed_range",
+            "credential_issuance_reward",
+            "gateway_uptime_reward",
+            "voting_power_snapshot",
+        ] {
+            removed += sqlx::query(&format!("DELETE FROM {table} WHERE rewarding_epoch_id < ?"))
+                .bind(epoch)
+                .execute(&mut *tx)
+                .await?
+                .rows_affected();
+        }
+
+        // api probes aren't tied to any epoch, so remove everything from before the retained epochs started
+        removed += sqlx::query(
+            "DELETE FROM issuer_api_probe WHERE probed_at < (SELECT start_time FROM rewarding_epoch WHERE id = ?)",
+        )
+        .bind(epoch)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        tx.commit().await?;
+        Ok(removed)
+    }
+
+    async fn insert_issuance_violation(
+        &self,
+        operator_account: String,
+        operator_identity_bs58: String,
+        dkg_epoch: i64,
+        credential_id: Option<i64>,
+        reason: &str,
+        details: String,
+        detected_at: OffsetDateTime,
+    ) -> Result<i64, sqlx::Error> {
+        let id = sqlx::query(
+            r#"
+                INSERT INTO credential_issuance_violation (
+                    operator_account,
+                    operator_identity_bs58,
+                    dkg_epoch,
+                    credential_id,
+                    reason,
+                    details,
+                    detected_at
+                ) VALUES (?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(operator_account)
+        .bind(operator_identity_bs58)
+        .bind(dkg_epoch)
+        .bind(credential_id)
+        .bind(reason)
+        .bind(details)
+        .bind(detected_at)
+        .execute(&self.connection_pool)
+        .await?
+        .last_insert_rowid();
+        Ok(id)
+    }
+
+    async fn get_issuance_violations(
+        &self,
+        operator_account: Option<String>,
+        include_cleared: bool,
+    ) -> Result<Vec<RawIssuanceViolation>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    id,
+                    operator_account,
+                    operator_identity_bs58,
+                    dkg_epoch,
+                    credential_id,
+                    reason,
+                    details,
+                    detected_at,
+                    cleared_at,
+                    clearing_note
+                FROM credential_issuance_violation
+                WHERE (?1 IS NULL OR operator_account = ?1) AND (?2 OR cleared_at IS NULL)
+                ORDER BY id
+            "#,
+        )
+        .bind(operator_account)
+        .bind(include_cleared)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn get_flagged_issuers(&self) -> Result<Vec<String>, sqlx::Error> {
+        sqlx::query_scalar(
+            r#"
+                SELECT DISTINCT operator_account
+                FROM credential_issuance_violation
+                WHERE cleared_at IS NULL
+            "#,
+        )
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+
+    async fn clear_issuance_violation(
+        &self,
+        id: i64,
+        clearing_note: Option<String>,
+        cleared_at: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+                UPDATE credential_issuance_violation
+                SET cleared_at = ?, clearing_note = ?
+                WHERE id = ? AND cleared_at IS NULL
+            "#,
+        )
+        .bind(cleared_at)
+        .bind(clearing_note)
+        .bind(id)
+        .execute(&self.connection_pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn clear_operator_issuance_violations(
+        &self,
+        operator_account: String,
+        clearing_note: Option<String>,
+        cleared_at: OffsetDateTime,
+    ) -> Result<u64, sqlx::Error> {
+        Ok(sqlx::query(
+            r#"
+                UPDATE credential_issuance_violation
+                SET cleared_at = ?, clearing_note = ?
+                WHERE operator_account = ? AND cleared_at IS NULL
+            "#,
+        )
+        .bind(cleared_at)
+        .bind(clearing_note)
+        .bind(operator_account)
+        .execute(&self.connection_pool)
+        .await?
+        .rows_affected())
+    }
+
+    async fn insert_reward_manifest(
+        &self,
+        epoch_id: i64,
+        signer: String,
+        signature: String,
+        manifest: String,
+        created_at: OffsetDateTime,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+                INSERT INTO reward_manifest (epoch_id, signer, signature, manifest, created_at)
+                VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(epoch_id)
+        .bind(signer)
+        .bind(signature)
+        .bind(manifest)
+        .bind(created_at)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_reward_manifest(
+        &self,
+        epoch_id: i64,
+    ) -> Result<Option<RawRewardManifest>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT epoch_id, signer, signature, manifest
+                FROM reward_manifest
+                WHERE epoch_id = ?
+            "#,
+        )
+        .bind(epoch_id)
+        .fetch_optional(&self.connection_pool)
+        .await
+    }
+
+    async fn insert_issuer_api_probe(
+        &self,
+        operator_account: String,
+        api_endpoint: String,
+        probed_at: OffsetDateTime,
+        available: bool,
+        latency_ms: Option<i64>,
+        tls_valid: Option<bool>,
+        key_available: bool,
+        error: Option<String>,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+                INSERT INTO issuer_api_probe (
+                    operator_account,
+                    api_endpoint,
+                    probed_at,
+                    available,
+                    latency_ms,
+                    tls_valid,
+                    key_available,
+                    error
+                ) VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(operator_account)
+        .bind(api_endpoint)
+        .bind(probed_at)
+        .bind(available)
+        .bind(latency_ms)
+        .bind(tls_valid)
+        .bind(key_available)
+        .bind(error)
+        .execute(&self.connection_pool)
+        .await?;
+        Ok(())
+    }
+
+    async fn get_issuer_api_availability(
+        &self,
+        start: OffsetDateTime,
+        end: OffsetDateTime,
+    ) -> Result<Vec<IssuerApiAvailability>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+                SELECT
+                    operator_account,
+                    COUNT(*) AS probes,
+                    SUM(CASE WHEN available THEN 1 ELSE 0 END) AS successful
+                FROM issuer_api_probe
+                WHERE probed_at >= ? AND probed_at < ?
+                GROUP BY operator_account
+            "#,
+        )
+        .bind(start)
+        .bind(end)
+        .fetch_all(&self.connection_pool)
+        .await
+    }
+}
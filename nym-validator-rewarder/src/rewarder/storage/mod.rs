@@ -1,61 +1,280 @@
 // Copyright 2023 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: GPL-3.0-only
 
+use crate::config::{Config, StorageBackend};
 use crate::error::NymRewarderError;
+use crate::rewarder::block_signing::types::EpochSigningResults;
+use crate::rewarder::credential_issuance::prober::ApiProbeResult;
 use crate::rewarder::credential_issuance::types::CredentialIssuer;
+use crate::rewarder::credential_issuance::violation::{IssuanceViolation, IssuanceViolationReason};
 use crate::rewarder::epoch::Epoch;
+use crate::rewarder::epoch_processing::{
+    encode_rewarding_amounts, EpochProcessingPhase, EpochProcessingState,
+};
+use crate::rewarder::manifest::SignedRewardManifest;
+use crate::rewarder::pruning::RewardsArchive;
+use crate::rewarder::storage::manager::postgres::PostgresStorageManager;
+use crate::rewarder::storage::manager::sqlite::SqliteStorageManager;
 use crate::rewarder::storage::manager::StorageManager;
+use crate::rewarder::storage::models::{
+    BlockSigningRewardRecord, IssuerApiAvailability, RewarderRun, RewardingEpochSummary,
+    VotingPowerSnapshot,
+};
 use crate::rewarder::{EpochRewards, RewardingResult};
 use nym_validator_client::nym_api::IssuedCredentialBody;
-use nym_validator_client::nyxd::Coin;
-use sqlx::ConnectOptions;
-use std::fmt::Debug;
-use std::path::Path;
-use tracing::{error, info, instrument};
+use nym_validator_client::nyxd::{AccountId, Coin, Hash};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use time::OffsetDateTime;
+use tracing::info;
 
 mod manager;
+pub(crate) mod models;
 
 #[derive(Clone)]
 pub struct RewarderStorage {
-    pub(crate) manager: StorageManager,
+    pub(crate) manager: Arc<dyn StorageManager>,
 }
 
 impl RewarderStorage {
-    #[instrument]
-    pub async fn init<P: AsRef<Path> + Debug>(database_path: P) -> Result<Self, NymRewarderError> {
-        let mut opts = sqlx::sqlite::SqliteConnectOptions::new()
-            .filename(database_path)
-            .create_if_missing(true);
+    pub async fn init(config: &Config) -> Result<Self, NymRewarderError> {
+        let manager: Arc<dyn StorageManager> = match config.storage.backend {
+            StorageBackend::Sqlite => {
+                Arc::new(SqliteStorageManager::init(&config.storage_paths.reward_history).await?)
+            }
+            StorageBackend::Postgres => {
+                Arc::new(PostgresStorageManager::init(&config.storage.postgres_url).await?)
+            }
+        };
 
-        // TODO: do we want auto_vacuum ?
+        Ok(RewarderStorage { manager })
+    }
+
+    pub(crate) async fn load_last_rewarding_epoch(
+        &self,
+    ) -> Result<Option<Epoch>, NymRewarderError> {
+        Ok(self.manager.load_last_rewarding_epoch().await?)
+    }
 
-        opts.disable_statement_logging();
+    pub(crate) async fn load_last_rewarder_run(
+        &self,
+    ) -> Result<Option<RewarderRun>, NymRewarderError> {
+        Ok(self.manager.load_last_rewarder_run().await?)
+    }
 
-        let connection_pool = match sqlx::SqlitePool::connect_with(opts).await {
-            Ok(db) => db,
-            Err(err) => {
-                error!("Failed to connect to SQLx database: {err}");
-                return Err(err.into());
-            }
-        };
+    /// Register a new instance of the rewarder. Until it's explicitly marked as cleanly shut down,
+    /// the run is treated as if it had crashed.
+    pub(crate) async fn register_rewarder_run(&self) -> Result<i64, NymRewarderError> {
+        Ok(self
+            .manager
+            .insert_rewarder_run(OffsetDateTime::now_utc())
+            .await?)
+    }
+
+    /// Record the `clean_shutdown` marker alongside the checkpoint of the epoch that was still in progress,
+    /// so that the next run could carry on with it.
+    pub(crate) async fn record_clean_shutdown(
+        &self,
+        run_id: i64,
+        in_progress_epoch: Epoch,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .record_clean_shutdown(run_id, in_progress_epoch)
+            .await?)
+    }
+
+    pub(crate) async fn load_unfinished_epoch_processing_state(
+        &self,
+    ) -> Result<Option<EpochProcessingState>, NymRewarderError> {
+        self.manager
+            .load_unfinished_epoch_processing_state()
+            .await?
+            .map(TryInto::try_into)
+            .transpose()
+    }
+
+    pub(crate) async fn record_computed_epoch_rewards(
+        &self,
+        epoch: Epoch,
+        amounts: &[(AccountId, Vec<Coin>)],
+        budget: &Coin,
+        total_spent: &Coin,
+        starting_height: i64,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .insert_epoch_processing_state(
+                epoch,
+                EpochProcessingPhase::Computed.as_str(),
+                encode_rewarding_amounts(amounts)?,
+                budget.to_string(),
+                total_spent.to_string(),
+                starting_height,
+            )
+            .await?)
+    }
+
+    /// Defer the payout of the determined rewards until the payouts get resumed.
+    pub(crate) async fn record_pending_epoch_rewards(
+        &self,
+        epoch: Epoch,
+        amounts: &[(AccountId, Vec<Coin>)],
+        budget: &Coin,
+        total_spent: &Coin,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .insert_epoch_processing_state(
+                epoch,
+                EpochProcessingPhase::Pending.as_str(),
+                encode_rewarding_amounts(amounts)?,
+                budget.to_string(),
+                total_spent.to_string(),
+                0,
+            )
+            .await?)
+    }
+
+    pub(crate) async fn load_pending_epoch_rewards(
+        &self,
+    ) -> Result<Vec<EpochProcessingState>, NymRewarderError> {
+        self.manager
+            .load_pending_epoch_processing_states()
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    /// Move the deferred rewards back into the regular processing, right before they're sent out,
+    /// so that they could be resumed like any other epoch if the rewarder crashes midway.
+    pub(crate) async fn record_resumed_pending_epoch_rewards(
+        &self,
+        epoch_id: i64,
+        starting_height: i64,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .update_epoch_processing_start(
+                epoch_id,
+                EpochProcessingPhase::Computed.as_str(),
+                starting_height,
+            )
+            .await?)
+    }
 
-        if let Err(err) = sqlx::migrate!("./migrations").run(&connection_pool).await {
-            error!("Failed to initialize SQLx database: {err}");
-            return Err(err.into());
+    pub(crate) async fn record_broadcast_epoch_rewards(
+        &self,
+        epoch_id: i64,
+        rewarding_tx: Hash,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .update_epoch_processing_phase(
+                epoch_id,
+                EpochProcessingPhase::Broadcast.as_str(),
+                Some(rewarding_tx.to_string()),
+            )
+            .await?)
+    }
+
+    pub(crate) async fn record_confirmed_epoch_rewards(
+        &self,
+        epoch_id: i64,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .update_epoch_processing_phase(epoch_id, EpochProcessingPhase::Confirmed.as_str(), None)
+            .await?)
+    }
+
+    /// Persist the outcome of an epoch whose processing has been resumed after a restart,
+    /// or whose payout has been deferred while the payouts were paused.
+    /// The per-module details got lost alongside the process, so only the general epoch information is saved.
+    pub(crate) async fn save_resumed_rewarding_information(
+        &self,
+        state: &EpochProcessingState,
+    ) -> Result<(), NymRewarderError> {
+        if self.manager.rewarding_epoch_exists(state.epoch.id).await? {
+            // the epoch has been recorded without the payout if it failed, or if it got deferred
+            return Ok(self
+                .manager
+                .update_rewarding_epoch_payout(
+                    state.epoch.id,
+                    state.total_spent.clone(),
+                    state.rewarding_tx.clone(),
+                )
+                .await?);
         }
 
-        info!("Database migration finished!");
+        Ok(self
+            .manager
+            .insert_rewarding_epoch(
+                state.epoch,
+                state.budget.clone(),
+                state.total_spent.clone(),
+                state.rewarding_tx.clone(),
+                None,
+            )
+            .await?)
+    }
 
-        let manager = StorageManager { connection_pool };
-        let storage = RewarderStorage { manager };
+    pub(crate) async fn save_voting_power_snapshot(
+        &self,
+        epoch_id: i64,
+        signing: &EpochSigningResults,
+    ) -> Result<(), NymRewarderError> {
+        for validator in &signing.validators {
+            self.manager
+                .insert_voting_power_snapshot(
+                    epoch_id,
+                    validator.validator.consensus_address.clone(),
+                    validator.whitelisted,
+                    validator.voting_power_height,
+                    validator.voting_power_at_epoch_start,
+                    validator.voting_power_ratio.to_string(),
+                )
+                .await?;
+        }
+        Ok(())
+    }
 
-        Ok(storage)
+    pub(crate) async fn get_epoch_voting_power_snapshot(
+        &self,
+        epoch_id: i64,
+    ) -> Result<Vec<VotingPowerSnapshot>, NymRewarderError> {
+        Ok(self
+            .manager
+            .get_epoch_voting_power_snapshot(epoch_id)
+            .await?)
     }
 
-    pub(crate) async fn load_last_rewarding_epoch(
+    pub(crate) async fn get_rewarding_epoch_summary(
         &self,
-    ) -> Result<Option<Epoch>, NymRewarderError> {
-        Ok(self.manager.load_last_rewarding_epoch().await?)
+        epoch_id: i64,
+    ) -> Result<Option<RewardingEpochSummary>, NymRewarderError> {
+        Ok(self.manager.get_rewarding_epoch_summary(epoch_id).await?)
+    }
+
+    pub(crate) async fn get_epoch_block_signing_rewards(
+        &self,
+        epoch_id: i64,
+    ) -> Result<Vec<BlockSigningRewardRecord>, NymRewarderError> {
+        Ok(self
+            .manager
+            .get_epoch_block_signing_rewards(epoch_id)
+            .await?)
+    }
+
+    pub(crate) async fn get_validator_voting_power_history(
+        &self,
+        validator_consensus_address: &str,
+    ) -> Result<Vec<VotingPowerSnapshot>, NymRewarderError> {
+        Ok(self
+            .manager
+            .get_validator_voting_power_history(validator_consensus_address)
+            .await?)
     }
 
     async fn insert_failed_rewarding_epoch_block_signing(
@@ -80,6 +299,55 @@ impl RewarderStorage {
             .await?)
     }
 
+    async fn insert_failed_rewarding_epoch_gateway_uptime(
+        &self,
+        epoch: i64,
+        budget: &Coin,
+    ) -> Result<(), NymRewarderError> {
+        Ok(self
+            .manager
+            .insert_rewarding_epoch_gateway_uptime(epoch, -1, "-1".to_string(), budget.to_string())
+            .await?)
+    }
+
+    /// Load all the per-account rewarding details of epochs preceding the provided one.
+    pub(crate) async fn load_epoch_details_before(
+        &self,
+        epoch: i64,
+    ) -> Result<RewardsArchive, NymRewarderError> {
+        Ok(RewardsArchive {
+            created_at: OffsetDateTime::now_utc().unix_timestamp(),
+            before_epoch: epoch,
+            block_signing: self.manager.get_block_signing_rewards_before(epoch).await?,
+            credential_issuance: self
+                .manager
+                .get_credential_issuance_rewards_before(epoch)
+                .await?,
+            gateway_uptime: self
+                .manager
+                .get_gateway_uptime_rewards_before(epoch)
+                .await?,
+            voting_power: self
+                .manager
+                .get_voting_power_snapshots_before(epoch)
+                .await?,
+        })
+    }
+
+    /// Remove the archived per-account details from the database,
+    /// folding the pruned rewards into the per-account summaries.
+    pub(crate) async fn prune_epoch_details(
+        &self,
+        archive: &RewardsArchive,
+    ) -> Result<u64, NymRewarderError> {
+        let existing = self.manager.get_pruned_reward_summaries().await?;
+        let summaries = archive.updated_summaries(existing)?;
+        Ok(self
+            .manager
+            .prune_epoch_details_before(archive.before_epoch, summaries)
+            .await?)
+    }
+
     pub(crate) async fn get_deposit_credential_id(
         &self,
         operator_identity_bs58: String,
@@ -161,6 +429,140 @@ impl RewarderStorage {
         Ok(())
     }
 
+    pub(crate) async fn insert_issuance_violation(
+        &self,
+        issuer: &CredentialIssuer,
+        dkg_epoch: u64,
+        credential_id: Option<i64>,
+        reason: IssuanceViolationReason,
+        details: String,
+    ) -> Result<i64, NymRewarderError> {
+        Ok(self
+            .manager
+            .insert_issuance_violation(
+                issuer.operator_account.to_string(),
+                issuer.public_key.to_base58_string(),
+                dkg_epoch as i64,
+                credential_id,
+                reason.as_str(),
+                details,
+                OffsetDateTime::now_utc(),
+            )
+            .await?)
+    }
+
+    pub(crate) async fn get_issuance_violations(
+        &self,
+        operator_account: Option<&AccountId>,
+        include_cleared: bool,
+    ) -> Result<Vec<IssuanceViolation>, NymRewarderError> {
+        self.manager
+            .get_issuance_violations(operator_account.map(|a| a.to_string()), include_cleared)
+            .await?
+            .into_iter()
+            .map(TryInto::try_into)
+            .collect()
+    }
+
+    /// Get accounts of all credential issuers with at least a single uncleared violation.
+    pub(crate) async fn insert_issuer_api_probe(
+        &self,
+        issuer: &CredentialIssuer,
+        probe: ApiProbeResult,
+    ) -> Result<(), NymRewarderError> {
+        self.manager
+            .insert_issuer_api_probe(
+                issuer.operator_account.to_string(),
+                issuer.api_runner.clone(),
+                probe.probed_at,
+                probe.available,
+                probe.latency_ms,
+                probe.tls_valid,
+                probe.key_available,
+                probe.error,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Get the aggregated api probe results of all credential issuers within the provided epoch,
+    /// keyed by their operator accounts.
+    pub(crate) async fn get_issuer_api_availability(
+        &self,
+        epoch: Epoch,
+    ) -> Result<HashMap<String, IssuerApiAvailability>, NymRewarderError> {
+        Ok(self
+            .manager
+            .get_issuer_api_availability(epoch.start_time, epoch.end_time)
+            .await?
+            .into_iter()
+            .map(|availability| (availability.operator_account.clone(), availability))
+            .collect())
+    }
+
+    pub(crate) async fn get_flagged_issuers(&self) -> Result<HashSet<String>, NymRewarderError> {
+        Ok(self
+            .manager
+            .get_flagged_issuers()
+            .await?
+            .into_iter()
+            .collect())
+    }
+
+    pub(crate) async fn clear_issuance_violation(
+        &self,
+        id: i64,
+        clearing_note: Option<String>,
+    ) -> Result<u64, NymRewarderError> {
+        Ok(self
+            .manager
+            .clear_issuance_violation(id, clearing_note, OffsetDateTime::now_utc())
+            .await?)
+    }
+
+    pub(crate) async fn clear_operator_issuance_violations(
+        &self,
+        operator_account: &AccountId,
+        clearing_note: Option<String>,
+    ) -> Result<u64, NymRewarderError> {
+        Ok(self
+            .manager
+            .clear_operator_issuance_violations(
+                operator_account.to_string(),
+                clearing_note,
+                OffsetDateTime::now_utc(),
+            )
+            .await?)
+    }
+
+    pub(crate) async fn save_reward_manifest(
+        &self,
+        signed: &SignedRewardManifest,
+    ) -> Result<(), NymRewarderError> {
+        let canonical = signed.manifest.canonical_json()?;
+        self.manager
+            .insert_reward_manifest(
+                signed.manifest.epoch_id,
+                signed.signer.clone(),
+                signed.signature.clone(),
+                canonical,
+                OffsetDateTime::now_utc(),
+            )
+            .await?;
+        Ok(())
+    }
+
+    pub(crate) async fn get_reward_manifest(
+        &self,
+        epoch_id: i64,
+    ) -> Result<Option<SignedRewardManifest>, NymRewarderError> {
+        self.manager
+            .get_reward_manifest(epoch_id)
+            .await?
+            .map(TryInto::try_into)
+            .transpose()
+    }
+
     pub(crate) async fn save_rewarding_information(
         &self,
         reward: EpochRewards,
@@ -205,17 +607,36 @@ impl RewarderStorage {
             if let Some(signing) = block_signing {
                 for validator in signing.validators {
                     let reward_amount = validator.reward_amount(&reward.signing_budget).to_string();
+                    let consensus_address = validator.validator.consensus_address.clone();
+                    for jailed in &validator.jailed_ranges {
+                        self.manager
+                            .insert_block_signing_jailed_range(
+                                epoch_id,
+                                consensus_address.clone(),
+                                jailed.start_height,
+                                jailed.end_height,
+                            )
+                            .await?;
+                    }
                     self.manager
                         .insert_rewarding_epoch_block_signing_reward(
                             epoch_id,
-                            validator.validator.consensus_address,
+                            consensus_address,
                             validator.operator_account.to_string(),
+                            validator.payout_override().map(ToString::to_string),
                             validator.whitelisted,
                             reward_amount,
                             validator.voting_power_at_epoch_start,
                             validator.voting_power_ratio.to_string(),
                             validator.signed_blocks,
                             validator.ratio_signed.to_string(),
+                            validator.forfeited,
+                            validator
+                                .forfeited_amount(&reward.signing_budget)
+                                .map(|amount| amount.to_string()),
+                            validator
+                                .redistributed_amount(&reward.signing_budget)
+                                .map(|amount| amount.to_string()),
                         )
                         .await?;
                 }
@@ -280,6 +701,50 @@ impl RewarderStorage {
             .await?;
         }
 
+        // gateway uptime info
+        if let Ok(gateway_uptime) = reward.gateway_uptime {
+            self.manager
+                .insert_rewarding_epoch_gateway_uptime(
+                    epoch_id,
+                    gateway_uptime
+                        .as_ref()
+                        .map(|g| g.gateways.len())
+                        .unwrap_or_default() as i64,
+                    gateway_uptime
+                        .as_ref()
+                        .map(|g| g.total_uptime)
+                        .unwrap_or_default()
+                        .to_string(),
+                    reward.gateway_uptime_budget.to_string(),
+                )
+                .await?;
+
+            if let Some(uptime) = gateway_uptime {
+                for gateway in uptime.gateways {
+                    let reward_amount = gateway
+                        .reward_amount(&reward.gateway_uptime_budget)
+                        .to_string();
+
+                    self.manager
+                        .insert_rewarding_epoch_gateway_uptime_reward(
+                            epoch_id,
+                            gateway.identity,
+                            gateway.owner.to_string(),
+                            reward_amount,
+                            gateway.uptime.to_string(),
+                            gateway.uptime_ratio.to_string(),
+                        )
+                        .await?;
+                }
+            }
+        } else {
+            self.insert_failed_rewarding_epoch_gateway_uptime(
+                epoch_id,
+                &reward.gateway_uptime_budget,
+            )
+            .await?;
+        }
+
         Ok(())
     }
 }
@@ -0,0 +1,140 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+use crate::rewarder::epoch::Epoch;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt::{Display, Formatter};
+use time::OffsetDateTime;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct VotingPowerSnapshot {
+    pub rewarding_epoch_id: i64,
+    pub validator_consensus_address: String,
+    pub whitelisted: bool,
+    pub height: i64,
+    pub voting_power: i64,
+    pub voting_power_share: String,
+}
+
+impl Display for VotingPowerSnapshot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "epoch {}: {} had voting power of {} at height {} (share: {}, whitelisted: {})",
+            self.rewarding_epoch_id,
+            self.validator_consensus_address,
+            self.voting_power,
+            self.height,
+            self.voting_power_share,
+            self.whitelisted
+        )
+    }
+}
+
+/// Overview of a single rewarding epoch alongside its block signing statistics, if it has been rewarded.
+#[derive(Debug, Clone, FromRow)]
+pub struct RewardingEpochSummary {
+    pub id: i64,
+    pub start_time: OffsetDateTime,
+    pub end_time: OffsetDateTime,
+    pub budget: String,
+    pub spent: String,
+    pub rewarding_tx: Option<String>,
+    pub rewarding_error: Option<String>,
+    pub num_blocks: Option<i64>,
+    pub total_voting_power: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockSigningRewardRecord {
+    pub rewarding_epoch_id: i64,
+    pub validator_consensus_address: String,
+    pub operator_account: String,
+    pub payout_account: Option<String>,
+    pub whitelisted: bool,
+    pub amount: String,
+    pub voting_power: i64,
+    pub voting_power_share: String,
+    pub signed_blocks: i64,
+    pub signed_blocks_percent: String,
+
+    #[serde(default)]
+    pub forfeited: bool,
+    #[serde(default)]
+    pub forfeited_amount: Option<String>,
+    #[serde(default)]
+    pub redistributed_amount: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CredentialIssuanceRewardRecord {
+    pub rewarding_epoch_id: i64,
+    pub operator_account: String,
+    pub amount: String,
+    pub whitelisted: bool,
+    pub api_endpoint: String,
+    pub issued_partial_credentials: i64,
+    pub issued_credentials_share: String,
+    pub validated_issued_credentials: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct GatewayUptimeRewardRecord {
+    pub rewarding_epoch_id: i64,
+    pub gateway_identity: String,
+    pub operator_account: String,
+    pub amount: String,
+    pub uptime: String,
+    pub uptime_share: String,
+}
+
+/// Rewards received by given account for a particular module over all the epochs
+/// whose per-epoch details have already been pruned.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, FromRow)]
+pub struct PrunedRewardSummary {
+    pub operator_account: String,
+    pub module: String,
+    pub first_epoch: i64,
+    pub last_epoch: i64,
+    pub rewarded_epochs: i64,
+    pub total_amount: String,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RewarderRun {
+    pub id: i64,
+    pub started_at: OffsetDateTime,
+    pub stopped_at: Option<OffsetDateTime>,
+    pub clean_shutdown: bool,
+    pub in_progress_epoch_id: Option<i64>,
+    pub in_progress_epoch_start: Option<OffsetDateTime>,
+    pub in_progress_epoch_end: Option<OffsetDateTime>,
+}
+
+impl RewarderRun {
+    /// The epoch that was checkpointed as being in progress when this run has been shut down.
+    pub fn in_progress_epoch(&self) -> Option<Epoch> {
+        Some(Epoch {
+            id: self.in_progress_epoch_id?,
+            start_time: self.in_progress_epoch_start?,
+            end_time: self.in_progress_epoch_end?,
+        })
+    }
+}
+
+/// Aggregated results of probing the api endpoint of a credential issuer.
+#[derive(Debug, Clone, FromRow)]
+pub struct IssuerApiAvailability {
+    pub operator_account: String,
+    pub probes: i64,
+    pub successful: i64,
+}
+
+#[derive(Debug, Clone, FromRow)]
+pub struct RawRewardManifest {
+    pub epoch_id: i64,
+    pub signer: String,
+    pub signature: String,
+    pub manifest: String,
+}
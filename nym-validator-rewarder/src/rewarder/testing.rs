@@ -0,0 +1,692 @@
+// Copyright 2024 - Nym Technologies SA <contact@nymtech.net>
+// SPDX-License-Identifier: GPL-3.0-only
+
+//! Test support for running the rewarder against a deterministic, in-memory chain.
+//!
+//! [MockChainClient] stands in for the signing nyxd client, while [MockTendermint] seeds the scraper
+//! database with blocks and pre-commits, as if they had been received from a tendermint RPC.
+//! Together they allow driving full epoch evaluation, including the reward calculation and every
+//! `StorageManager` write, without a live chain. Note that the credential issuance and gateway uptime
+//! modules still talk to the nym-apis directly, so they're disabled in the default test config.
+
+use crate::config::Config;
+use crate::error::NymRewarderError;
+use crate::rewarder::block_signing::jailing::JailingEvent;
+use crate::rewarder::credential_issuance::types::CredentialIssuer;
+use crate::rewarder::epoch::{ChainEpoch, Epoch};
+use crate::rewarder::nyxd_client::{ChainClient, NyxdClient};
+use crate::rewarder::Rewarder;
+use async_trait::async_trait;
+use nym_coconut_dkg_common::types::Epoch as DkgEpoch;
+use nym_validator_client::nyxd::module_traits::staking::{
+    self, QueryHistoricalInfoResponse, QueryValidatorsResponse,
+};
+use nym_validator_client::nyxd::{AccountId, Coin, Hash, PageRequest};
+use nyxd_scraper::NyxdScraper;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use time::OffsetDateTime;
+
+pub(crate) const TEST_DENOM: &str = "unym";
+
+/// Create a config suitable for running the rewarder against the mock chain,
+/// with all of its databases placed within the provided directory.
+/// Only the block signing module is enabled.
+pub(crate) fn test_config(data_dir: &Path) -> Config {
+    // safety: 32 bytes of entropy always produce a valid mnemonic
+    #[allow(clippy::unwrap_used)]
+    let mnemonic = bip39::Mnemonic::from_entropy(&[42u8; 32]).unwrap();
+
+    // safety: those are hardcoded, valid urls
+    #[allow(clippy::unwrap_used)]
+    let mut config = Config::new(
+        mnemonic,
+        "ws://localhost:26657/websocket".parse().unwrap(),
+        "http://localhost:26657".parse().unwrap(),
+    );
+
+    config.storage_paths.nyxd_scraper = data_dir.join("nyxd_scraper.sqlite");
+    config.storage_paths.reward_history = data_dir.join("rewards.sqlite");
+    config.storage_paths.private_identity_key_file = data_dir.join("private_identity.pem");
+    config.storage_paths.public_identity_key_file = data_dir.join("public_identity.pem");
+    config.rewarding.epoch_budget = Coin::new(1_000_000, TEST_DENOM);
+    config.block_signing.enabled = true;
+    config.block_signing.monitor_only = false;
+    config.block_signing.whitelist = vec![test_consensus_address(0)];
+    config.issuance_monitor.enabled = false;
+    config.gateway_uptime.enabled = false;
+    config
+}
+
+/// Deterministic account address derived from the provided seed.
+pub(crate) fn test_account(seed: u8) -> AccountId {
+    // safety: the prefix is valid and the key hash has the expected length
+    #[allow(clippy::unwrap_used)]
+    AccountId::new("n", &[seed; 20]).unwrap()
+}
+
+/// Deterministic validator consensus address derived from the provided seed.
+pub(crate) fn test_consensus_address(seed: u8) -> AccountId {
+    // safety: the prefix is valid and the key hash has the expected length
+    #[allow(clippy::unwrap_used)]
+    AccountId::new("nvalcons", &[seed; 20]).unwrap()
+}
+
+/// Construct the rewarder that's going to use the provided mock chain rather than a real nyxd instance.
+pub(crate) async fn test_rewarder(
+    config: Config,
+    chain: &MockChainClient,
+) -> Result<Rewarder, NymRewarderError> {
+    Rewarder::new_with_nyxd_client(config, chain.nyxd_client()).await
+}
+
+#[derive(Debug, Clone)]
+pub(crate) struct SentRewards {
+    pub(crate) epoch_id: i64,
+    pub(crate) height: i64,
+    pub(crate) rewarding_tx: Hash,
+    pub(crate) amounts: Vec<(AccountId, Vec<Coin>)>,
+}
+
+struct MockChainState {
+    balance: Coin,
+    block_height: i64,
+    validators: Vec<staking::Validator>,
+    jailing_events: Vec<JailingEvent>,
+    dkg_epoch: DkgEpoch,
+    mixnet_epoch: ChainEpoch,
+    credential_issuers: Vec<CredentialIssuer>,
+    deposits: HashMap<Hash, (String, String)>,
+    sent_rewards: Vec<SentRewards>,
+}
+
+/// In-memory replacement of the signing nyxd client.
+/// Every rewarding transaction is "included" in a new block and recorded so that it could be inspected
+/// and later found by [ChainClient::find_rewarding_tx].
+#[derive(Clone)]
+pub(crate) struct MockChainClient {
+    state: Arc<Mutex<MockChainState>>,
+}
+
+impl Default for MockChainClient {
+    fn default() -> Self {
+        MockChainClient {
+            state: Arc::new(Mutex::new(MockChainState {
+                balance: Coin::new(u64::MAX as u128, TEST_DENOM),
+                block_height: 1,
+                validators: Vec::new(),
+                jailing_events: Vec::new(),
+                dkg_epoch: DkgEpoch::default(),
+                mixnet_epoch: ChainEpoch {
+                    start_time: OffsetDateTime::now_utc(),
+                    length: Duration::from_secs(60 * 60),
+                },
+                credential_issuers: Vec::new(),
+                deposits: HashMap::new(),
+                sent_rewards: Vec::new(),
+            })),
+        }
+    }
+}
+
+impl MockChainClient {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockChainState> {
+        // the state is never left in an inconsistent state, so it's fine to recover from poisoning
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    pub(crate) fn nyxd_client(&self) -> NyxdClient {
+        NyxdClient::new_with_chain_client(Arc::new(self.clone()))
+    }
+
+    pub(crate) fn set_balance(&self, balance: Coin) {
+        self.state().balance = balance
+    }
+
+    pub(crate) fn set_block_height(&self, height: i64) {
+        self.state().block_height = height
+    }
+
+    /// Set the staking details of the validators, as returned by the staking module.
+    pub(crate) fn set_validators(&self, validators: Vec<staking::Validator>) {
+        self.state().validators = validators
+    }
+
+    /// Set the jail and unjail events that happened on the chain.
+    pub(crate) fn set_jailing_events(&self, events: Vec<JailingEvent>) {
+        self.state().jailing_events = events
+    }
+
+    pub(crate) fn set_dkg_epoch(&self, epoch: DkgEpoch) {
+        self.state().dkg_epoch = epoch
+    }
+
+    pub(crate) fn set_mixnet_epoch(&self, epoch: ChainEpoch) {
+        self.state().mixnet_epoch = epoch
+    }
+
+    pub(crate) fn set_credential_issuers(&self, issuers: Vec<CredentialIssuer>) {
+        self.state().credential_issuers = issuers
+    }
+
+    pub(crate) fn add_deposit(&self, tx_hash: Hash, deposit_value: String, deposit_info: String) {
+        self.state()
+            .deposits
+            .insert(tx_hash, (deposit_value, deposit_info));
+    }
+
+    pub(crate) fn sent_rewards(&self) -> Vec<SentRewards> {
+        self.state().sent_rewards.clone()
+    }
+}
+
+#[async_trait]
+impl ChainClient for MockChainClient {
+    async fn address(&self) -> AccountId {
+        test_account(u8::MAX)
+    }
+
+    async fn balance(&self, denom: &str) -> Result<Coin, NymRewarderError> {
+        let state = self.state();
+        if state.balance.denom == denom {
+            Ok(state.balance.clone())
+        } else {
+            Ok(Coin::new(0, denom))
+        }
+    }
+
+    async fn send_rewards(
+        &self,
+        epoch: Epoch,
+        amounts: Vec<(AccountId, Vec<Coin>)>,
+    ) -> Result<Hash, NymRewarderError> {
+        let mut state = self.state();
+        state.block_height += 1;
+
+        let digest = Sha256::digest(format!(
+            "{}/{}/{}",
+            epoch.id,
+            state.block_height,
+            state.sent_rewards.len()
+        ));
+        let rewarding_tx = Hash::Sha256(digest.into());
+
+        for (_, amount) in &amounts {
+            for coin in amount {
+                if coin.denom == state.balance.denom {
+                    state.balance.amount = state.balance.amount.saturating_sub(coin.amount);
+                }
+            }
+        }
+
+        let height = state.block_height;
+        state.sent_rewards.push(SentRewards {
+            epoch_id: epoch.id,
+            height,
+            rewarding_tx,
+            amounts,
+        });
+        Ok(rewarding_tx)
+    }
+
+    async fn current_block_height(&self) -> Result<i64, NymRewarderError> {
+        Ok(self.state().block_height)
+    }
+
+    async fn find_rewarding_tx(
+        &self,
+        epoch: Epoch,
+        from_height: i64,
+    ) -> Result<Option<Hash>, NymRewarderError> {
+        Ok(self
+            .state()
+            .sent_rewards
+            .iter()
+            .find(|sent| sent.epoch_id == epoch.id && sent.height >= from_height)
+            .map(|sent| sent.rewarding_tx))
+    }
+
+    async fn historical_info(
+        &self,
+        _height: i64,
+    ) -> Result<QueryHistoricalInfoResponse, NymRewarderError> {
+        // make the rewarder fallback to the current validator set
+        Ok(QueryHistoricalInfoResponse { hist: None })
+    }
+
+    async fn validators(
+        &self,
+        _pagination: Option<PageRequest>,
+    ) -> Result<QueryValidatorsResponse, NymRewarderError> {
+        Ok(QueryValidatorsResponse {
+            validators: self.state().validators.clone(),
+            pagination: None,
+        })
+    }
+
+    async fn jailing_events(
+        &self,
+        from_height: i64,
+        to_height: i64,
+    ) -> Result<Vec<JailingEvent>, NymRewarderError> {
+        Ok(self
+            .state()
+            .jailing_events
+            .iter()
+            .filter(|event| (from_height..=to_height).contains(&event.height()))
+            .cloned()
+            .collect())
+    }
+
+    async fn dkg_epoch(&self) -> Result<DkgEpoch, NymRewarderError> {
+        Ok(self.state().dkg_epoch)
+    }
+
+    async fn mixnet_epoch(&self) -> Result<ChainEpoch, NymRewarderError> {
+        Ok(self.state().mixnet_epoch)
+    }
+
+    async fn get_credential_issuers(
+        &self,
+        _dkg_epoch: u64,
+    ) -> Result<Vec<CredentialIssuer>, NymRewarderError> {
+        Ok(self.state().credential_issuers.clone())
+    }
+
+    async fn get_deposit_transaction_attributes(
+        &self,
+        tx_hash: Hash,
+    ) -> Result<(String, String), NymRewarderError> {
+        self.state()
+            .deposits
+            .get(&tx_hash)
+            .cloned()
+            .ok_or(NymRewarderError::DepositValueNotFound { tx_hash })
+    }
+}
+
+/// Seeds the scraper database with the chain data that would have otherwise been received
+/// from the tendermint RPC.
+pub(crate) struct MockTendermint {
+    scraper: NyxdScraper,
+    next_height: i64,
+}
+
+impl MockTendermint {
+    pub(crate) async fn new(config: &Config) -> Result<Self, NymRewarderError> {
+        Ok(MockTendermint {
+            scraper: NyxdScraper::new(config.scraper_config()).await?,
+            next_height: 1,
+        })
+    }
+
+    pub(crate) async fn add_validator(
+        &self,
+        consensus_address: &AccountId,
+    ) -> Result<(), NymRewarderError> {
+        let consensus_pubkey = hex::encode(consensus_address.to_bytes());
+
+        let mut tx = self.scraper.storage.begin_processing_tx().await?;
+        sqlx::query("INSERT INTO validator (consensus_address, consensus_pubkey) VALUES (?, ?)")
+            .bind(consensus_address.to_string())
+            .bind(consensus_pubkey)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Produce a new block at the provided time alongside pre-commits of all of its signers.
+    /// The first signer is treated as the block proposer.
+    pub(crate) async fn add_block(
+        &mut self,
+        timestamp: OffsetDateTime,
+        signers: &[(AccountId, i64)],
+    ) -> Result<i64, NymRewarderError> {
+        let height = self.next_height;
+        self.next_height += 1;
+
+        let hash = hex::encode_upper(Sha256::digest(height.to_be_bytes()));
+        let proposer = signers.first().map(|(address, _)| address.to_string());
+
+        let mut tx = self.scraper.storage.begin_processing_tx().await?;
+        sqlx::query(
+            "INSERT INTO block (height, hash, num_txs, total_gas, proposer_address, timestamp) VALUES (?, ?, 0, 0, ?, ?)",
+        )
+        .bind(height)
+        .bind(hash)
+        .bind(proposer)
+        .bind(timestamp)
+        .execute(&mut *tx)
+        .await?;
+
+        for (signer, voting_power) in signers {
+            sqlx::query(
+                "INSERT INTO pre_commit (validator_address, height, timestamp, voting_power, proposer_priority) VALUES (?, ?, ?, ?, 0)",
+            )
+            .bind(signer.to_string())
+            .bind(height)
+            .bind(timestamp)
+            .bind(voting_power)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        Ok(height)
+    }
+
+    /// Produce `blocks` blocks evenly spread throughout the provided epoch.
+    pub(crate) async fn fill_epoch(
+        &mut self,
+        epoch: Epoch,
+        blocks: u32,
+        signers: &[(AccountId, i64)],
+    ) -> Result<(), NymRewarderError> {
+        let interval = (epoch.end_time - epoch.start_time) / (blocks + 1);
+        for i in 1..=blocks {
+            self.add_block(epoch.start_time + interval * i, signers)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::EpochAlignment;
+    use crate::rewarder::admin::AdminCommand;
+    use crate::rewarder::epoch_processing::EpochProcessingPhase;
+    use crate::rewarder::storage::RewarderStorage;
+    use crate::rewarder::EpochRewards;
+
+    fn rewarding_amounts() -> Vec<(AccountId, Vec<Coin>)> {
+        vec![
+            (test_account(1), vec![Coin::new(1000, TEST_DENOM)]),
+            (test_account(2), vec![Coin::new(2000, TEST_DENOM)]),
+        ]
+    }
+
+    #[tokio::test]
+    async fn epoch_without_rewardable_validators_is_persisted_without_payouts() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = test_config(data_dir.path());
+        let chain = MockChainClient::new();
+
+        let mut rewarder = test_rewarder(config.clone(), &chain).await.unwrap();
+        let epoch = rewarder.current_epoch;
+
+        let mut tendermint = MockTendermint::new(&config).await.unwrap();
+        tendermint.fill_epoch(epoch, 10, &[]).await.unwrap();
+
+        rewarder.handle_epoch_end().await;
+
+        assert!(chain.sent_rewards().is_empty());
+        assert_eq!(rewarder.current_epoch.id, epoch.id + 1);
+
+        let last = rewarder
+            .storage
+            .load_last_rewarding_epoch()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(last.id, epoch.id);
+        assert!(rewarder
+            .storage
+            .load_unfinished_epoch_processing_state()
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn interrupted_epoch_is_not_paid_twice() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = test_config(data_dir.path());
+        let chain = MockChainClient::new();
+        chain.set_block_height(100);
+
+        // simulate a crash right after the rewarding transaction got broadcast
+        let storage = RewarderStorage::init(&config).await.unwrap();
+        let epoch = Epoch::first(config.rewarding.epoch_duration).unwrap();
+        let budget = config.rewarding.epoch_budget.clone();
+        let total_spent = Coin::new(3000, TEST_DENOM);
+        storage
+            .record_computed_epoch_rewards(epoch, &rewarding_amounts(), &budget, &total_spent, 100)
+            .await
+            .unwrap();
+        let rewarding_tx = chain
+            .nyxd_client()
+            .send_rewards(epoch, rewarding_amounts())
+            .await
+            .unwrap();
+
+        let rewarder = test_rewarder(config, &chain).await.unwrap();
+
+        let sent = chain.sent_rewards();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].rewarding_tx, rewarding_tx);
+        assert_eq!(rewarder.current_epoch.id, epoch.id + 1);
+        assert!(storage
+            .load_unfinished_epoch_processing_state()
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn interrupted_epoch_is_paid_if_transaction_is_missing() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = test_config(data_dir.path());
+        let chain = MockChainClient::new();
+        chain.set_block_height(100);
+
+        // simulate a crash before the rewarding transaction got broadcast
+        let storage = RewarderStorage::init(&config).await.unwrap();
+        let epoch = Epoch::first(config.rewarding.epoch_duration).unwrap();
+        let budget = config.rewarding.epoch_budget.clone();
+        let total_spent = Coin::new(3000, TEST_DENOM);
+        storage
+            .record_computed_epoch_rewards(epoch, &rewarding_amounts(), &budget, &total_spent, 100)
+            .await
+            .unwrap();
+        let unfinished = storage
+            .load_unfinished_epoch_processing_state()
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unfinished.phase, EpochProcessingPhase::Computed);
+
+        let rewarder = test_rewarder(config, &chain).await.unwrap();
+
+        let sent = chain.sent_rewards();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].epoch_id, epoch.id);
+        assert_eq!(sent[0].amounts, rewarding_amounts());
+        assert_eq!(rewarder.current_epoch.id, epoch.id + 1);
+        assert_eq!(
+            rewarder
+                .storage
+                .load_last_rewarding_epoch()
+                .await
+                .unwrap()
+                .unwrap()
+                .id,
+            epoch.id
+        );
+    }
+
+    async fn set_payouts_paused(rewarder: &mut Rewarder, paused: bool) {
+        let (respond_to, _) = tokio::sync::oneshot::channel();
+        rewarder
+            .handle_admin_command(AdminCommand::SetPayoutsPaused { paused, respond_to })
+            .await
+    }
+
+    #[tokio::test]
+    async fn rewards_deferred_while_paused_are_sent_once_resumed() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let config = test_config(data_dir.path());
+        let chain = MockChainClient::new();
+        chain.set_block_height(100);
+
+        let mut rewarder = test_rewarder(config.clone(), &chain).await.unwrap();
+        let epoch = rewarder.current_epoch;
+        let budget = config.rewarding.epoch_budget.clone();
+        let total_spent = Coin::new(3000, TEST_DENOM);
+
+        set_payouts_paused(&mut rewarder, true).await;
+        let rewarding_result = rewarder
+            .send_rewards(rewarding_amounts(), &total_spent)
+            .await;
+        assert!(matches!(
+            rewarding_result,
+            Err(NymRewarderError::PayoutsPaused)
+        ));
+        assert!(chain.sent_rewards().is_empty());
+
+        // the epoch gets recorded without the payout, as it would have been at its end
+        rewarder
+            .storage
+            .save_rewarding_information(
+                EpochRewards {
+                    epoch,
+                    signing: Ok(None),
+                    credentials: Ok(None),
+                    gateway_uptime: Ok(None),
+                    total_budget: budget.clone(),
+                    signing_budget: budget.clone(),
+                    credentials_budget: Coin::new(0, TEST_DENOM),
+                    gateway_uptime_budget: Coin::new(0, TEST_DENOM),
+                },
+                Err(NymRewarderError::PayoutsPaused),
+            )
+            .await
+            .unwrap();
+
+        // the deferred rewards are not an interrupted epoch that should be resumed on startup
+        assert!(rewarder
+            .storage
+            .load_unfinished_epoch_processing_state()
+            .await
+            .unwrap()
+            .is_none());
+        assert_eq!(
+            rewarder
+                .storage
+                .load_pending_epoch_rewards()
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        set_payouts_paused(&mut rewarder, false).await;
+
+        let sent = chain.sent_rewards();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].epoch_id, epoch.id);
+        assert_eq!(sent[0].amounts, rewarding_amounts());
+        assert!(rewarder
+            .storage
+            .load_pending_epoch_rewards()
+            .await
+            .unwrap()
+            .is_empty());
+
+        let summary = rewarder
+            .storage
+            .get_rewarding_epoch_summary(epoch.id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(summary.rewarding_tx, Some(sent[0].rewarding_tx.to_string()));
+        assert_eq!(summary.spent, total_spent.to_string());
+        assert!(summary.rewarding_error.is_none());
+
+        // nothing is sent twice
+        set_payouts_paused(&mut rewarder, true).await;
+        set_payouts_paused(&mut rewarder, false).await;
+        assert_eq!(chain.sent_rewards().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn chain_aligned_epochs_follow_mixnet_epoch_length_changes() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(data_dir.path());
+        config.rewarding.epoch_alignment = EpochAlignment::Chain;
+
+        let half_hour = Duration::from_secs(30 * 60);
+        let chain_start = OffsetDateTime::now_utc() - Duration::from_secs(10 * 60);
+        let chain = MockChainClient::new();
+        chain.set_mixnet_epoch(ChainEpoch {
+            start_time: chain_start,
+            length: half_hour,
+        });
+
+        // the first epoch starts once the current chain epoch is over
+        let mut rewarder = test_rewarder(config.clone(), &chain).await.unwrap();
+        let epoch = rewarder.current_epoch;
+        assert_eq!(epoch.start_time, chain_start + half_hour);
+        assert_eq!(epoch.end_time, chain_start + 2 * half_hour);
+
+        let mut tendermint = MockTendermint::new(&config).await.unwrap();
+        tendermint.fill_epoch(epoch, 10, &[]).await.unwrap();
+
+        // the chain epoch got extended in the meantime
+        let hour = 2 * half_hour;
+        chain.set_mixnet_epoch(ChainEpoch {
+            start_time: epoch.start_time,
+            length: hour,
+        });
+        rewarder.handle_epoch_end().await;
+
+        let next = rewarder.current_epoch;
+        assert_eq!(next.id, epoch.id + 1);
+        assert_eq!(next.start_time, epoch.end_time);
+        assert_eq!(next.end_time, epoch.start_time + hour);
+    }
+
+    #[tokio::test]
+    async fn epoch_end_produces_signed_manifest() {
+        let data_dir = tempfile::tempdir().unwrap();
+        let mut config = test_config(data_dir.path());
+        config.manifests.enabled = true;
+        let chain = MockChainClient::new();
+
+        let mut rewarder = test_rewarder(config.clone(), &chain).await.unwrap();
+        let epoch = rewarder.current_epoch;
+
+        let mut tendermint = MockTendermint::new(&config).await.unwrap();
+        tendermint.fill_epoch(epoch, 10, &[]).await.unwrap();
+
+        rewarder.handle_epoch_end().await;
+
+        let identity = config.storage_paths.load_identity().unwrap();
+        let manifest = rewarder
+            .storage
+            .get_reward_manifest(epoch.id)
+            .await
+            .unwrap()
+            .unwrap();
+        manifest
+            .verify_signature(Some(identity.public_key()))
+            .unwrap();
+
+        assert_eq!(manifest.manifest.epoch_id, epoch.id);
+        assert_eq!(
+            manifest.manifest.distributor,
+            test_account(u8::MAX).to_string()
+        );
+        assert!(manifest.manifest.rewarding_tx.is_none());
+        assert!(manifest.manifest.rewards.is_empty());
+    }
+}
@@ -1,6 +1,7 @@
 // Copyright 2022 - Nym Technologies SA <contact@nymtech.net>
 // SPDX-License-Identifier: Apache-2.0
 
+use cosmwasm_std::Decimal;
 use nym_mixnet_contract_common::ContractStateParams;
 use nym_types::currency::{DecCoin, RegisteredCoins};
 use nym_types::error::TypesError;
@@ -16,6 +17,11 @@ pub struct TauriContractStateParams {
     minimum_mixnode_pledge: DecCoin,
     minimum_gateway_pledge: DecCoin,
     minimum_mixnode_delegation: Option<DecCoin>,
+    #[cfg_attr(feature = "generate-ts", ts(type = "string | null"))]
+    #[serde(default)]
+    max_delegation_to_pledge_ratio: Option<Decimal>,
+    #[serde(default)]
+    min_supported_version: Option<String>,
 }
 
 impl TauriContractStateParams {
@@ -32,6 +38,8 @@ impl TauriContractStateParams {
                 .minimum_mixnode_delegation
                 .map(|min_del| reg.attempt_convert_to_display_dec_coin(min_del.into()))
                 .transpose()?,
+            max_delegation_to_pledge_ratio: state_params.max_delegation_to_pledge_ratio,
+            min_supported_version: state_params.min_supported_version,
         })
     }
 
@@ -51,6 +59,8 @@ impl TauriContractStateParams {
             minimum_gateway_pledge: reg
                 .attempt_convert_to_base_coin(self.minimum_gateway_pledge)?
                 .into(),
+            max_delegation_to_pledge_ratio: self.max_delegation_to_pledge_ratio,
+            min_supported_version: self.min_supported_version,
         })
     }
 }
@@ -201,6 +201,7 @@ mod tests {
             sphinx_key: "totally-legit-sphinx-key".to_string(),
             identity_key: identity_keypair.public_key().to_base58_string(),
             version: "v1.2.3".to_string(),
+            next_sphinx_key: None,
         };
         let dummy_cost_params = MixNodeCostParams {
             profit_margin_percent: Percent::from_percentage_value(42).unwrap(),
@@ -5,4 +5,6 @@ export interface TauriContractStateParams {
   minimum_mixnode_pledge: DecCoin;
   minimum_gateway_pledge: DecCoin;
   minimum_mixnode_delegation: DecCoin | null;
+  max_delegation_to_pledge_ratio: string | null;
+  min_supported_version: string | null;
 }
@@ -1,5 +1,10 @@
 pub use nym_client_core::config::Config as BaseClientConfig;
 
+use crate::constants::{
+    DEFAULT_IPV4_NETWORK_ADDRESS, DEFAULT_IPV4_NETWORK_PREFIX, DEFAULT_IPV6_NETWORK_ADDRESS,
+    DEFAULT_IPV6_NETWORK_PREFIX, DEFAULT_IP_LEASE_DURATION,
+};
+use ip_network::{Ipv4Network, Ipv6Network};
 use nym_bin_common::logging::LoggingSettings;
 use nym_client_core::{cli_helpers::CliClientConfig, config::disk_persistence::CommonClientPaths};
 use nym_config::{
@@ -7,12 +12,15 @@ use nym_config::{
     serde_helpers::de_maybe_stringified, NymConfigTemplate, OptionalSet, DEFAULT_CONFIG_DIR,
     DEFAULT_CONFIG_FILENAME, DEFAULT_DATA_DIR, NYM_DIR,
 };
+use nym_ip_packet_requests::nat64::{Nat64Prefix, WELL_KNOWN_NAT64_PREFIX};
 use nym_service_providers_common::DEFAULT_SERVICE_PROVIDERS_DIR;
 use serde::{Deserialize, Serialize};
 use std::{
     io,
+    net::{IpAddr, Ipv6Addr},
     path::{Path, PathBuf},
     str::FromStr,
+    time::Duration,
 };
 use url::Url;
 
@@ -193,6 +201,12 @@ pub struct IpPacketRouter {
     /// Specifies the url for an upstream source of the exit policy used by this node.
     #[serde(deserialize_with = "de_maybe_stringified")]
     pub upstream_exit_policy_url: Option<Url>,
+
+    /// Specifies the pool of addresses that get assigned to the connected clients.
+    pub ip_pool: IpPoolConfig,
+
+    /// Specifies the translation of IPv6 packets to IPv4 destinations for IPv6-only clients.
+    pub nat64: Nat64Config,
 }
 
 impl Default for IpPacketRouter {
@@ -204,6 +218,73 @@ impl Default for IpPacketRouter {
                     .parse()
                     .expect("invalid default exit policy URL"),
             ),
+            ip_pool: Default::default(),
+            nat64: Default::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IpPoolConfig {
+    /// The IPv4 network that client addresses are allocated from.
+    /// The first address within the network is assigned to the tun device.
+    pub ipv4_network: Ipv4Network,
+
+    /// The IPv6 network that client addresses are allocated from.
+    /// The first address within the network is assigned to the tun device.
+    pub ipv6_network: Ipv6Network,
+
+    /// Addresses within the pool that should never be assigned to any client.
+    pub reserved_addresses: Vec<IpAddr>,
+
+    /// Specifies for how long the addresses are held for a client after it disconnects,
+    /// so that it could reclaim them upon reconnecting.
+    #[serde(with = "humantime_serde")]
+    pub lease_duration: Duration,
+}
+
+impl Default for IpPoolConfig {
+    fn default() -> Self {
+        IpPoolConfig {
+            ipv4_network: Ipv4Network::new(
+                DEFAULT_IPV4_NETWORK_ADDRESS,
+                DEFAULT_IPV4_NETWORK_PREFIX,
+            )
+            .expect("invalid default ipv4 network"),
+            ipv6_network: Ipv6Network::new(
+                DEFAULT_IPV6_NETWORK_ADDRESS,
+                DEFAULT_IPV6_NETWORK_PREFIX,
+            )
+            .expect("invalid default ipv6 network"),
+            reserved_addresses: Vec::new(),
+            lease_duration: DEFAULT_IP_LEASE_DURATION,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize, PartialEq, Serialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Nat64Config {
+    /// Specifies whether IPv6 packets sent to addresses within the NAT64 prefix are translated
+    /// and routed to the IPv4 destinations embedded in them.
+    pub enabled: bool,
+
+    /// The /96 prefix the IPv4 destinations are embedded in. Its last 32 bits are ignored.
+    pub prefix: Ipv6Addr,
+}
+
+impl Nat64Config {
+    pub fn prefix(&self) -> Option<Nat64Prefix> {
+        self.enabled.then(|| Nat64Prefix::new(self.prefix))
+    }
+}
+
+impl Default for Nat64Config {
+    fn default() -> Self {
+        Nat64Config {
+            enabled: false,
+            prefix: WELL_KNOWN_NAT64_PREFIX,
         }
     }
 }
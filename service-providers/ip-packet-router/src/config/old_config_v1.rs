@@ -90,6 +90,8 @@ impl From<IpPacketRouterV1> for IpPacketRouter {
         IpPacketRouter {
             disable_poisson_rate: value.disable_poisson_rate,
             upstream_exit_policy_url: value.upstream_exit_policy_url,
+            ip_pool: Default::default(),
+            nat64: Default::default(),
         }
     }
 }
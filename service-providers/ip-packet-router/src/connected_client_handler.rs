@@ -15,6 +15,16 @@ use crate::{
 // Out: mixnet_listener -> decode -> handle_packet -> write_to_tun
 // In: tun_listener -> [connected_client_handler -> encode] -> mixnet_sender
 
+// Sent to the handler to make it stop. When the client disconnected on its own accord, the packets
+// still queued for it are flushed first and their number is reported back on the included channel.
+pub(crate) type CloseSignal = Option<tokio::sync::oneshot::Sender<FlushOutcome>>;
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum FlushOutcome {
+    Flushed { queued: u32 },
+    Failed { queued: u32 },
+}
+
 // This handler is spawned as a task, and it listens to IP packets passed from the tun_listener,
 // encodes it, and then sends to mixnet.
 pub(crate) struct ConnectedClientHandler {
@@ -22,7 +32,7 @@ pub(crate) struct ConnectedClientHandler {
     mix_hops: Option<u8>,
     forward_from_tun_rx: tokio::sync::mpsc::UnboundedReceiver<Vec<u8>>,
     mixnet_client_sender: nym_sdk::mixnet::MixnetClientSender,
-    close_rx: tokio::sync::oneshot::Receiver<()>,
+    close_rx: tokio::sync::oneshot::Receiver<CloseSignal>,
     activity_timeout: tokio::time::Interval,
     encoder: MultiIpPacketCodec,
 }
@@ -35,7 +45,7 @@ impl ConnectedClientHandler {
         mixnet_client_sender: nym_sdk::mixnet::MixnetClientSender,
     ) -> (
         tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
-        tokio::sync::oneshot::Sender<()>,
+        tokio::sync::oneshot::Sender<CloseSignal>,
         tokio::task::JoinHandle<()>,
     ) {
         let (close_tx, close_rx) = tokio::sync::oneshot::channel();
@@ -96,11 +106,43 @@ impl ConnectedClientHandler {
         }
     }
 
+    // Send out everything that is still waiting to be forwarded to the client
+    async fn flush_queued_packets(&mut self) -> FlushOutcome {
+        let mut queued = self.encoder.buffered_packets() as u32;
+        let mut bundles = Vec::new();
+        while let Ok(packet) = self.forward_from_tun_rx.try_recv() {
+            queued += 1;
+            bundles.extend(self.encoder.append_packet(packet.into()));
+        }
+        let remaining = self.encoder.flush_current_buffer();
+        if !remaining.is_empty() {
+            bundles.push(remaining);
+        }
+
+        for bundled_packets in bundles {
+            if let Err(err) = self.send_packets_to_mixnet(bundled_packets).await {
+                log::error!("client handler: failed to flush queued packets: {err}");
+                return FlushOutcome::Failed { queued };
+            }
+        }
+        FlushOutcome::Flushed { queued }
+    }
+
+    async fn handle_close(&mut self, signal: CloseSignal) {
+        if let Some(ack) = signal {
+            let outcome = self.flush_queued_packets().await;
+            ack.send(outcome).ok();
+        }
+    }
+
     async fn run(mut self) -> Result<()> {
         loop {
             tokio::select! {
-                _ = &mut self.close_rx => {
+                signal = &mut self.close_rx => {
                     log::info!("client handler stopping: received close: {}", self.nym_address);
+                    if let Ok(signal) = signal {
+                        self.handle_close(signal).await;
+                    }
                     break;
                 },
                 _ = self.activity_timeout.tick() => {
@@ -3,11 +3,17 @@ use std::time::Duration;
 
 // The interface used to route traffic
 pub const TUN_BASE_NAME: &str = "nymtun";
-pub const TUN_DEVICE_ADDRESS_V4: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 1);
-pub const TUN_DEVICE_NETMASK_V4: Ipv4Addr = Ipv4Addr::new(255, 255, 0, 0);
-pub const TUN_DEVICE_ADDRESS_V6: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0xa160, 0, 0, 0, 0, 0x1); // 2001:db8:a160::1
 
-pub const TUN_DEVICE_NETMASK_V6: &str = "112";
+// The default networks that client addresses are allocated from. The first address of each
+// network is assigned to the tun device.
+pub const DEFAULT_IPV4_NETWORK_ADDRESS: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 0);
+pub const DEFAULT_IPV4_NETWORK_PREFIX: u8 = 16;
+pub const DEFAULT_IPV6_NETWORK_ADDRESS: Ipv6Addr =
+    Ipv6Addr::new(0x2001, 0xdb8, 0xa160, 0, 0, 0, 0, 0); // 2001:db8:a160::
+pub const DEFAULT_IPV6_NETWORK_PREFIX: u8 = 112;
+
+// For how long the addresses are held for a client after it disconnects
+pub const DEFAULT_IP_LEASE_DURATION: Duration = Duration::from_secs(10 * 60);
 
 // We routinely check if any clients needs to be disconnected at this interval
 pub(crate) const DISCONNECT_TIMER_INTERVAL: Duration = Duration::from_secs(10);
@@ -15,6 +21,14 @@ pub(crate) const DISCONNECT_TIMER_INTERVAL: Duration = Duration::from_secs(10);
 // We consider a client inactive if it hasn't sent any mixnet packets in this duration
 pub(crate) const CLIENT_MIXNET_INACTIVITY_TIMEOUT: Duration = Duration::from_secs(5 * 60);
 
+// For how long we wait for the client handler to flush the packets queued for a client that
+// requested to disconnect, before acknowledging the disconnect anyway
+pub(crate) const CLIENT_DISCONNECT_FLUSH_TIMEOUT: Duration = Duration::from_secs(2);
+
+// The maximum number of distinct IPv4 destinations a single client can reach via NAT64 at the
+// same time, so that the translation state couldn't grow without bounds
+pub(crate) const MAX_NAT64_REMOTES_PER_CLIENT: usize = 4096;
+
 // We consider a client handler inactive if it hasn't received any packets from the tun device in
 // this duration
 pub(crate) const CLIENT_HANDLER_ACTIVITY_TIMEOUT: Duration = Duration::from_secs(10 * 60);
@@ -53,6 +53,9 @@ pub enum IpPacketRouterError {
     #[error("parsed packet is missing transport header")]
     PacketMissingTransportHeader,
 
+    #[error("failed to translate the packet between ipv6 and ipv4: {reason}")]
+    Nat64TranslationFailed { reason: &'static str },
+
     #[error("failed to write packet to tun")]
     FailedToWritePacketToTun,
 
@@ -83,6 +86,12 @@ pub enum IpPacketRouterError {
     #[error("failed to update client activity")]
     FailedToUpdateClientActivity,
 
+    #[error("the tun listener is no longer accepting connected clients")]
+    TunListenerUnavailable,
+
+    #[error("the configured ip pool is invalid: {reason}")]
+    InvalidIpPool { reason: String },
+
     #[error(transparent)]
     ConfigUpgradeFailure(#[from] nym_client_core::config::ConfigUpgradeFailure),
 
@@ -14,6 +14,7 @@ use nym_task::{TaskClient, TaskHandle};
 use crate::{
     config::Config,
     error::IpPacketRouterError,
+    ip_pool::IpPool,
     request_filter::{self, RequestFilter},
 };
 
@@ -136,31 +137,39 @@ impl IpPacketRouter {
 
         let self_address = *mixnet_client.nym_address();
 
+        // The pool of addresses that we hand out to the connected clients. The tun device is
+        // assigned the first address of the pool.
+        let ip_pool = IpPool::new(&self.config.ip_packet_router.ip_pool)?;
+        let tun_ips = ip_pool.tun_ips();
+
         // Create the TUN device that we interact with the rest of the world with
         let config = nym_tun::tun_device::TunDeviceConfig {
             base_name: crate::constants::TUN_BASE_NAME.to_string(),
-            ipv4: crate::constants::TUN_DEVICE_ADDRESS_V4,
-            netmaskv4: crate::constants::TUN_DEVICE_NETMASK_V4,
-            ipv6: crate::constants::TUN_DEVICE_ADDRESS_V6,
-            netmaskv6: crate::constants::TUN_DEVICE_NETMASK_V6.to_string(),
+            ipv4: tun_ips.ipv4,
+            netmaskv4: ip_pool.ipv4_network().full_netmask(),
+            ipv6: tun_ips.ipv6,
+            netmaskv6: ip_pool.ipv6_network().netmask().to_string(),
         };
         let (tun_reader, tun_writer) =
             tokio::io::split(nym_tun::tun_device::TunDevice::new_device_only(config)?);
 
         // Channel used by the IpPacketRouter to signal connected and disconnected clients to the
         // TunListener
-        let (connected_clients, connected_clients_rx) = mixnet_listener::ConnectedClients::new();
+        let (connected_clients, connected_clients_rx) =
+            mixnet_listener::ConnectedClients::new(ip_pool);
 
         let tun_listener = tun_listener::TunListener {
             tun_reader,
             task_client: task_handle.get_handle(),
             connected_clients: connected_clients_rx,
+            nat64_prefix: self.config.ip_packet_router.nat64.prefix(),
         };
         tun_listener.start();
 
         let request_filter = request_filter::RequestFilter::new(&self.config).await?;
         request_filter.start_update_tasks().await;
 
+        let nat64_prefix = self.config.ip_packet_router.nat64.prefix();
         let mixnet_listener = mixnet_listener::MixnetListener {
             _config: self.config,
             request_filter: request_filter.clone(),
@@ -168,6 +177,7 @@ impl IpPacketRouter {
             mixnet_client,
             task_handle,
             connected_clients,
+            nat64_prefix,
         };
 
         log::info!("The address of this client is: {self_address}");
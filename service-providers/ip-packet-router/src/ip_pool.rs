@@ -0,0 +1,391 @@
+use std::collections::{HashMap, HashSet};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+use std::time::{Duration, Instant};
+
+use ip_network::{Ipv4Network, Ipv6Network};
+use nym_ip_packet_requests::IpPair;
+use nym_sdk::mixnet::Recipient;
+
+use crate::config::IpPoolConfig;
+use crate::error::{IpPacketRouterError, Result};
+
+// The first address after the network address is always assigned to the tun device
+const TUN_DEVICE_OFFSET: u32 = 1;
+
+struct Lease {
+    holder: Recipient,
+
+    // The lease is kept indefinitely while the client remains connected. Once it disconnects,
+    // the addresses are held for the lease duration so that the client could reclaim them if it
+    // reconnects.
+    expires_at: Option<Instant>,
+}
+
+// Reasons for not being able to lease the specific addresses requested by a client
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum LeaseError {
+    // The addresses are not within the pool, or they're not placed at the same position
+    OutsidePool,
+
+    // The addresses are reserved or leased to somebody else
+    Unavailable,
+}
+
+impl Lease {
+    fn is_expired(&self, now: Instant) -> bool {
+        matches!(self.expires_at, Some(expires_at) if expires_at <= now)
+    }
+}
+
+// Allocates pairs of IPv4 and IPv6 addresses to connected clients. Both addresses of a pair are
+// always at the same offset within their respective networks.
+pub(crate) struct IpPool {
+    ipv4_network: Ipv4Network,
+    ipv6_network: Ipv6Network,
+
+    // Number of address pairs within the pool, including the reserved ones
+    size: u32,
+
+    // Offsets of addresses that must never be assigned to any client
+    reserved: HashSet<u32>,
+
+    lease_duration: Duration,
+
+    // Current leases, keyed by the offset of the addresses within the networks
+    leases: HashMap<u32, Lease>,
+
+    // Offset from which we start searching for free addresses, so that the recently released
+    // addresses are not immediately reused
+    next_offset: u32,
+}
+
+impl IpPool {
+    pub(crate) fn new(config: &IpPoolConfig) -> Result<Self> {
+        let ipv4_size = 1u64 << (32 - config.ipv4_network.netmask());
+        let ipv6_size = 1u128
+            .checked_shl(128 - config.ipv6_network.netmask() as u32)
+            .unwrap_or(u128::MAX);
+        let size = ipv4_size.min(ipv6_size.min(u32::MAX as u128) as u64) as u32;
+
+        let mut reserved = HashSet::from([0, TUN_DEVICE_OFFSET]);
+        if ipv4_size == size as u64 {
+            // don't hand out the IPv4 broadcast address
+            reserved.insert(size - 1);
+        }
+
+        let mut pool = IpPool {
+            ipv4_network: config.ipv4_network,
+            ipv6_network: config.ipv6_network,
+            size,
+            reserved,
+            lease_duration: config.lease_duration,
+            leases: HashMap::new(),
+            next_offset: TUN_DEVICE_OFFSET + 1,
+        };
+
+        for address in &config.reserved_addresses {
+            let offset = match address {
+                IpAddr::V4(ipv4) => pool.ipv4_offset(ipv4),
+                IpAddr::V6(ipv6) => pool.ipv6_offset(ipv6),
+            };
+            match offset {
+                Some(offset) => {
+                    pool.reserved.insert(offset);
+                }
+                None => log::warn!("reserved address {address} is outside of the ip pool"),
+            }
+        }
+
+        if pool.capacity() == 0 {
+            return Err(IpPacketRouterError::InvalidIpPool {
+                reason: format!(
+                    "there are no assignable addresses within {} and {}",
+                    config.ipv4_network, config.ipv6_network
+                ),
+            });
+        }
+
+        Ok(pool)
+    }
+
+    // The addresses used by the tun device
+    pub(crate) fn tun_ips(&self) -> IpPair {
+        self.ips_at(TUN_DEVICE_OFFSET)
+    }
+
+    pub(crate) fn ipv4_network(&self) -> Ipv4Network {
+        self.ipv4_network
+    }
+
+    pub(crate) fn ipv6_network(&self) -> Ipv6Network {
+        self.ipv6_network
+    }
+
+    // The total number of addresses that could be assigned to clients
+    pub(crate) fn capacity(&self) -> u32 {
+        self.size.saturating_sub(self.reserved.len() as u32)
+    }
+
+    fn ips_at(&self, offset: u32) -> IpPair {
+        let ipv4 = Ipv4Addr::from(u32::from(self.ipv4_network.network_address()) + offset);
+        let ipv6 = Ipv6Addr::from(u128::from(self.ipv6_network.network_address()) + offset as u128);
+        IpPair::new(ipv4, ipv6)
+    }
+
+    fn ipv4_offset(&self, ip: &Ipv4Addr) -> Option<u32> {
+        if !self.ipv4_network.contains(*ip) {
+            return None;
+        }
+        let offset = u32::from(*ip) - u32::from(self.ipv4_network.network_address());
+        (offset < self.size).then_some(offset)
+    }
+
+    fn ipv6_offset(&self, ip: &Ipv6Addr) -> Option<u32> {
+        if !self.ipv6_network.contains(*ip) {
+            return None;
+        }
+        let offset = u128::from(*ip) - u128::from(self.ipv6_network.network_address());
+        (offset < self.size as u128).then_some(offset as u32)
+    }
+
+    // Get the offset of the pair, as long as both addresses are within the pool and they are
+    // placed at the same position
+    fn offset_of(&self, ips: &IpPair) -> Option<u32> {
+        let offset = self.ipv4_offset(&ips.ipv4)?;
+        (self.ipv6_offset(&ips.ipv6)? == offset).then_some(offset)
+    }
+
+    fn is_available_for(&self, offset: u32, holder: &Recipient, now: Instant) -> bool {
+        if self.reserved.contains(&offset) {
+            return false;
+        }
+        match self.leases.get(&offset) {
+            None => true,
+            Some(lease) => lease.is_expired(now) || lease.holder == *holder,
+        }
+    }
+
+    // Allocate addresses for the provided client. If the client still holds a lease from a
+    // previous connection, the same addresses are returned.
+    pub(crate) fn allocate(&mut self, holder: Recipient, now: Instant) -> Option<IpPair> {
+        if let Some((offset, lease)) = self
+            .leases
+            .iter_mut()
+            .find(|(_, lease)| lease.holder == holder && !lease.is_expired(now))
+        {
+            lease.expires_at = None;
+            let offset = *offset;
+            return Some(self.ips_at(offset));
+        }
+
+        for i in 0..self.size {
+            let offset = ((self.next_offset as u64 + i as u64) % self.size as u64) as u32;
+            if self.is_available_for(offset, &holder, now) {
+                self.leases.insert(
+                    offset,
+                    Lease {
+                        holder,
+                        expires_at: None,
+                    },
+                );
+                self.next_offset = ((offset as u64 + 1) % self.size as u64) as u32;
+                return Some(self.ips_at(offset));
+            }
+        }
+        None
+    }
+
+    // Attempt to lease the specific addresses requested by the client. This fails if they are
+    // outside the pool, reserved or leased to somebody else. Any other addresses the client might
+    // still hold from a previous connection are given up.
+    pub(crate) fn try_lease(
+        &mut self,
+        ips: IpPair,
+        holder: Recipient,
+        now: Instant,
+    ) -> std::result::Result<(), LeaseError> {
+        let offset = self.offset_of(&ips).ok_or(LeaseError::OutsidePool)?;
+        if !self.is_available_for(offset, &holder, now) {
+            return Err(LeaseError::Unavailable);
+        }
+        self.leases
+            .retain(|leased, lease| *leased == offset || lease.holder != holder);
+        self.leases.insert(
+            offset,
+            Lease {
+                holder,
+                expires_at: None,
+            },
+        );
+        Ok(())
+    }
+
+    // Mark the addresses as no longer being actively used. They will become available to other
+    // clients once the lease expires.
+    pub(crate) fn release(&mut self, ips: &IpPair, now: Instant) {
+        let Some(offset) = self.offset_of(ips) else {
+            return;
+        };
+        if let Some(lease) = self.leases.get_mut(&offset) {
+            lease.expires_at = Some(now + self.lease_duration);
+        }
+    }
+
+    // Immediately give up the lease, e.g. because the client never got to use the addresses
+    pub(crate) fn revoke(&mut self, ips: &IpPair) {
+        if let Some(offset) = self.offset_of(ips) {
+            self.leases.remove(&offset);
+        }
+    }
+
+    // Remove all leases that have expired
+    pub(crate) fn prune_expired(&mut self, now: Instant) {
+        self.leases.retain(|_, lease| !lease.is_expired(now));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nym_crypto::asymmetric::{encryption, identity};
+    use std::str::FromStr;
+
+    fn config(ipv4: &str, ipv6: &str) -> IpPoolConfig {
+        IpPoolConfig {
+            ipv4_network: Ipv4Network::from_str(ipv4).unwrap(),
+            ipv6_network: Ipv6Network::from_str(ipv6).unwrap(),
+            reserved_addresses: vec![],
+            lease_duration: Duration::from_secs(60),
+        }
+    }
+
+    fn recipient(seed: u8) -> Recipient {
+        let identity = identity::PrivateKey::from_bytes(&[seed; 32])
+            .unwrap()
+            .public_key();
+        let encryption =
+            encryption::PublicKey::from(&encryption::PrivateKey::from_bytes(&[seed; 32]).unwrap());
+        Recipient::new(identity, encryption, identity)
+    }
+
+    #[test]
+    fn tun_device_uses_first_address() {
+        let pool = IpPool::new(&IpPoolConfig::default()).unwrap();
+        assert_eq!(
+            pool.tun_ips(),
+            IpPair::new(
+                Ipv4Addr::new(10, 0, 0, 1),
+                Ipv6Addr::new(0x2001, 0xdb8, 0xa160, 0, 0, 0, 0, 0x1)
+            )
+        );
+        // network address, tun device and the broadcast address
+        assert_eq!(pool.capacity(), 65536 - 3);
+    }
+
+    #[test]
+    fn pool_gets_exhausted() {
+        let mut pool = IpPool::new(&config("10.0.0.0/29", "fd00::/125")).unwrap();
+        let now = Instant::now();
+        assert_eq!(pool.capacity(), 5);
+
+        let mut allocated = HashSet::new();
+        for seed in 0..5 {
+            let ips = pool.allocate(recipient(seed), now).unwrap();
+            assert_ne!(ips, pool.tun_ips());
+            assert_ne!(ips.ipv4, Ipv4Addr::new(10, 0, 0, 7));
+            assert!(allocated.insert(ips));
+        }
+        assert!(pool.allocate(recipient(5), now).is_none());
+    }
+
+    #[test]
+    fn released_addresses_are_reused_after_lease_expires() {
+        let mut pool = IpPool::new(&config("10.0.0.0/30", "fd00::/126")).unwrap();
+        let now = Instant::now();
+        assert_eq!(pool.capacity(), 1);
+
+        let first = recipient(1);
+        let ips = pool.allocate(first, now).unwrap();
+        pool.release(&ips, now);
+
+        // the client reclaims its previous addresses while the lease is still valid
+        assert_eq!(pool.allocate(first, now), Some(ips));
+        pool.release(&ips, now);
+
+        // but nobody else can get them until it expires
+        let second = recipient(2);
+        assert!(pool.allocate(second, now).is_none());
+        assert_eq!(
+            pool.try_lease(ips, second, now),
+            Err(LeaseError::Unavailable)
+        );
+
+        let later = now + Duration::from_secs(61);
+        assert_eq!(pool.allocate(second, later), Some(ips));
+    }
+
+    #[test]
+    fn static_leases_must_be_within_the_pool() {
+        let mut cfg = config("10.0.0.0/24", "fd00::/120");
+        cfg.reserved_addresses = vec!["10.0.0.42".parse().unwrap()];
+        let mut pool = IpPool::new(&cfg).unwrap();
+        let now = Instant::now();
+
+        let valid = IpPair::new("10.0.0.5".parse().unwrap(), "fd00::5".parse().unwrap());
+        let mismatched = IpPair::new("10.0.0.5".parse().unwrap(), "fd00::6".parse().unwrap());
+        let outside = IpPair::new("10.0.1.5".parse().unwrap(), "fd00::105".parse().unwrap());
+        let reserved = IpPair::new("10.0.0.42".parse().unwrap(), "fd00::2a".parse().unwrap());
+
+        assert_eq!(
+            pool.try_lease(mismatched, recipient(1), now),
+            Err(LeaseError::OutsidePool)
+        );
+        assert_eq!(
+            pool.try_lease(outside, recipient(1), now),
+            Err(LeaseError::OutsidePool)
+        );
+        assert_eq!(
+            pool.try_lease(reserved, recipient(1), now),
+            Err(LeaseError::Unavailable)
+        );
+        assert_eq!(
+            pool.try_lease(pool.tun_ips(), recipient(1), now),
+            Err(LeaseError::Unavailable)
+        );
+        assert_eq!(pool.try_lease(valid, recipient(1), now), Ok(()));
+        // the same client can lease it again, but nobody else can
+        assert_eq!(pool.try_lease(valid, recipient(1), now), Ok(()));
+        assert_eq!(
+            pool.try_lease(valid, recipient(2), now),
+            Err(LeaseError::Unavailable)
+        );
+    }
+
+    #[test]
+    fn static_lease_replaces_previous_lease_of_the_client() {
+        let mut pool = IpPool::new(&config("10.0.0.0/29", "fd00::/125")).unwrap();
+        let now = Instant::now();
+
+        let previous = pool.allocate(recipient(1), now).unwrap();
+        pool.release(&previous, now);
+
+        let requested = IpPair::new("10.0.0.5".parse().unwrap(), "fd00::5".parse().unwrap());
+        assert_ne!(previous, requested);
+        assert_eq!(pool.try_lease(requested, recipient(1), now), Ok(()));
+
+        // the previous addresses are immediately available to other clients
+        assert_eq!(pool.try_lease(previous, recipient(2), now), Ok(()));
+    }
+
+    #[test]
+    fn revoked_leases_are_immediately_available() {
+        let mut pool = IpPool::new(&config("10.0.0.0/30", "fd00::/126")).unwrap();
+        let now = Instant::now();
+
+        let ips = pool.allocate(recipient(1), now).unwrap();
+        assert!(pool.allocate(recipient(2), now).is_none());
+
+        pool.revoke(&ips);
+        assert_eq!(pool.allocate(recipient(2), now), Some(ips));
+    }
+}
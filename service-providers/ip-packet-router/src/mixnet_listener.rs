@@ -1,18 +1,23 @@
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 use std::sync::Arc;
-use std::{collections::HashMap, net::SocketAddr};
+use std::time::Instant;
+use std::{
+    collections::{HashMap, HashSet},
+    net::SocketAddr,
+};
 
 use bytes::{Bytes, BytesMut};
 use futures::StreamExt;
 use nym_ip_packet_requests::response::InfoLevel;
 use nym_ip_packet_requests::{
     codec::MultiIpPacketCodec,
+    nat64::Nat64Prefix,
     request::{IpPacketRequest, IpPacketRequestData},
     response::{
-        DynamicConnectFailureReason, InfoResponseReply, IpPacketResponse,
+        DisconnectFailureReason, DynamicConnectFailureReason, InfoResponseReply, IpPacketResponse,
         StaticConnectFailureReason,
     },
-    IpPair,
+    v7, IpPair,
 };
 use nym_sdk::mixnet::{MixnetMessageSender, Recipient};
 use nym_sphinx::receiver::ReconstructedMessage;
@@ -25,14 +30,18 @@ use tokio_util::codec::Decoder;
 
 use crate::{
     config::Config,
-    connected_client_handler,
-    constants::{CLIENT_MIXNET_INACTIVITY_TIMEOUT, DISCONNECT_TIMER_INTERVAL},
+    connected_client_handler::{self, CloseSignal, FlushOutcome},
+    constants::{
+        CLIENT_DISCONNECT_FLUSH_TIMEOUT, CLIENT_MIXNET_INACTIVITY_TIMEOUT,
+        DISCONNECT_TIMER_INTERVAL, MAX_NAT64_REMOTES_PER_CLIENT,
+    },
     error::{IpPacketRouterError, Result},
+    ip_pool::{IpPool, LeaseError},
     request_filter::{self},
     tun_listener,
-    util::generate_new_ip,
     util::{
         create_message::create_input_message,
+        nat64::translate_to_ipv4,
         parse_ip::{parse_packet, ParsedPacket},
     },
 };
@@ -42,17 +51,21 @@ pub(crate) struct ConnectedClients {
     clients_ipv4_mapping: HashMap<Ipv4Addr, ConnectedClient>,
     clients_ipv6_mapping: HashMap<Ipv6Addr, ConnectedClient>,
 
+    // The pool of addresses that we lease to the connected clients
+    ip_pool: IpPool,
+
     // Notify the tun listener when a new client connects or disconnects
     tun_listener_connected_client_tx: tokio::sync::mpsc::UnboundedSender<ConnectedClientEvent>,
 }
 
 impl ConnectedClients {
-    pub(crate) fn new() -> (Self, tun_listener::ConnectedClientsListener) {
+    pub(crate) fn new(ip_pool: IpPool) -> (Self, tun_listener::ConnectedClientsListener) {
         let (connected_client_tx, connected_client_rx) = tokio::sync::mpsc::unbounded_channel();
         (
             Self {
                 clients_ipv4_mapping: Default::default(),
                 clients_ipv6_mapping: Default::default(),
+                ip_pool,
                 tun_listener_connected_client_tx: connected_client_tx,
             },
             tun_listener::ConnectedClientsListener::new(connected_client_rx),
@@ -101,40 +114,64 @@ impl ConnectedClients {
             })
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn connect(
         &mut self,
         ips: IpPair,
         nym_address: Recipient,
+        connect_request_id: u64,
         mix_hops: Option<u8>,
         forward_from_tun_tx: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
-        close_tx: tokio::sync::oneshot::Sender<()>,
+        close_tx: tokio::sync::oneshot::Sender<CloseSignal>,
         handle: tokio::task::JoinHandle<()>,
-    ) {
+    ) -> Result<()> {
         // The map of connected clients that the mixnet listener keeps track of. It monitors
         // activity and disconnects clients that have been inactive for too long.
         let client = ConnectedClient {
             nym_address,
+            connect_request_id,
+            ipv4: ips.ipv4,
             ipv6: ips.ipv6,
             mix_hops,
             last_activity: Arc::new(RwLock::new(std::time::Instant::now())),
-            _close_tx: Arc::new(CloseTx {
+            close_tx: Arc::new(CloseTx {
                 nym_address,
-                inner: Some(close_tx),
+                inner: std::sync::Mutex::new(Some(close_tx)),
             }),
             handle: Arc::new(handle),
+            nat64_remotes: HashSet::new(),
         };
-        log::info!("Inserting {} and {}", ips.ipv4, ips.ipv6);
-        self.clients_ipv4_mapping.insert(ips.ipv4, client.clone());
-        self.clients_ipv6_mapping.insert(ips.ipv6, client);
         // Send the connected client info to the tun listener, which will use it to forward packets
         // to the connected client handler.
-        self.tun_listener_connected_client_tx
+        if let Err(err) = self
+            .tun_listener_connected_client_tx
             .send(ConnectedClientEvent::Connect(Box::new(ConnectEvent {
                 ips,
                 forward_from_tun_tx,
             })))
+        {
+            log::error!("Failed to send connected client event: {err}");
+            // The client never gets to use the addresses, so don't hold them for it. Dropping the
+            // client closes its handler.
+            self.ip_pool.revoke(&ips);
+            return Err(IpPacketRouterError::TunListenerUnavailable);
+        }
+        log::info!("Inserting {} and {}", ips.ipv4, ips.ipv6);
+        self.clients_ipv4_mapping.insert(ips.ipv4, client.clone());
+        self.clients_ipv6_mapping.insert(ips.ipv6, client);
+        Ok(())
+    }
+
+    // Let the tun listener know it should translate the packets coming from the remote back to
+    // the IPv6 address of the client
+    fn register_nat64_remote(&self, ips: IpPair, remote: Ipv4Addr) {
+        self.tun_listener_connected_client_tx
+            .send(ConnectedClientEvent::Nat64Remote(Nat64RemoteEvent {
+                ips,
+                remote,
+            }))
             .tap_err(|err| {
-                log::error!("Failed to send connected client event: {err}");
+                log::error!("Failed to send nat64 remote event: {err}");
             })
             .ok();
     }
@@ -186,6 +223,7 @@ impl ConnectedClients {
             log::info!("Disconnect stopped client: {ips}");
             self.clients_ipv4_mapping.remove(&ips.ipv4);
             self.clients_ipv6_mapping.remove(&ips.ipv6);
+            self.ip_pool.release(ips, Instant::now());
             self.tun_listener_connected_client_tx
                 .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
                 .tap_err(|err| {
@@ -200,6 +238,7 @@ impl ConnectedClients {
             log::info!("Disconnect inactive client: {ips}");
             self.clients_ipv4_mapping.remove(&ips.ipv4);
             self.clients_ipv6_mapping.remove(&ips.ipv6);
+            self.ip_pool.release(ips, Instant::now());
             self.tun_listener_connected_client_tx
                 .send(ConnectedClientEvent::Disconnect(DisconnectEvent(*ips)))
                 .tap_err(|err| {
@@ -209,16 +248,61 @@ impl ConnectedClients {
         }
     }
 
-    fn find_new_ip(&self) -> Option<IpPair> {
-        generate_new_ip::find_new_ips(&self.clients_ipv4_mapping, &self.clients_ipv6_mapping)
+    // Disconnect the client on its own request, returning it so that its handler could be closed
+    fn disconnect_client(&mut self, nym_address: &Recipient) -> Option<ConnectedClient> {
+        let ips = self.lookup_ip_from_nym_address(nym_address)?;
+        log::info!("Disconnect client on request: {ips}");
+        self.clients_ipv6_mapping.remove(&ips.ipv6);
+        let client = self.clients_ipv4_mapping.remove(&ips.ipv4);
+        self.ip_pool.release(&ips, Instant::now());
+        self.tun_listener_connected_client_tx
+            .send(ConnectedClientEvent::Disconnect(DisconnectEvent(ips)))
+            .tap_err(|err| {
+                log::error!("Failed to send disconnect event: {err}");
+            })
+            .ok();
+        client
+    }
+
+    // Lease new addresses for the client. If it recently disconnected and its lease hasn't
+    // expired yet, it gets the same addresses back.
+    fn allocate_ips(&mut self, nym_address: Recipient) -> Option<IpPair> {
+        self.ip_pool.allocate(nym_address, Instant::now())
+    }
+
+    // Lease the specific addresses requested by the client
+    fn try_lease_ips(
+        &mut self,
+        ips: IpPair,
+        nym_address: Recipient,
+    ) -> std::result::Result<(), LeaseError> {
+        self.ip_pool.try_lease(ips, nym_address, Instant::now())
+    }
+
+    fn prune_expired_leases(&mut self) {
+        self.ip_pool.prune_expired(Instant::now());
+    }
+
+    fn ip_pool_capacity(&self) -> u32 {
+        self.ip_pool.capacity()
     }
 }
 
 pub(crate) struct CloseTx {
     pub(crate) nym_address: Recipient,
     // Send to connected clients listener to stop. This is option only because we need to take
-    // ownership of it when the client is dropped.
-    pub(crate) inner: Option<tokio::sync::oneshot::Sender<()>>,
+    // ownership of it when the client is disconnected or dropped.
+    pub(crate) inner: std::sync::Mutex<Option<tokio::sync::oneshot::Sender<CloseSignal>>>,
+}
+
+impl CloseTx {
+    // Tell the client handler to flush the packets still queued for the client before stopping
+    fn close_with_flush(&self) -> Option<tokio::sync::oneshot::Receiver<FlushOutcome>> {
+        let close_tx = self.inner.lock().ok()?.take()?;
+        let (ack_tx, ack_rx) = tokio::sync::oneshot::channel();
+        close_tx.send(Some(ack_tx)).ok()?;
+        Some(ack_rx)
+    }
 }
 
 #[derive(Clone)]
@@ -227,6 +311,13 @@ pub(crate) struct ConnectedClient {
     // the mixnet
     pub(crate) nym_address: Recipient,
 
+    // The id of the connect request that established the session. The client has to present it
+    // when disconnecting, as the nym address alone can be claimed by anyone.
+    pub(crate) connect_request_id: u64,
+
+    // The assigned IPv4 address of this client
+    pub(crate) ipv4: Ipv4Addr,
+
     // The assigned IPv6 address of this client
     pub(crate) ipv6: Ipv6Addr,
 
@@ -236,10 +327,14 @@ pub(crate) struct ConnectedClient {
     // Keep track of last activity so we can disconnect inactive clients
     pub(crate) last_activity: Arc<RwLock<std::time::Instant>>,
 
-    pub(crate) _close_tx: Arc<CloseTx>,
+    pub(crate) close_tx: Arc<CloseTx>,
 
     // Handle for the connected client handler
     pub(crate) handle: Arc<tokio::task::JoinHandle<()>>,
+
+    // The IPv4 destinations the client has reached via NAT64. Translated packets always originate
+    // from the IPv6 address of the client, so it's only ever updated in the IPv6 mapping.
+    pub(crate) nat64_remotes: HashSet<Ipv4Addr>,
 }
 
 impl ConnectedClient {
@@ -251,8 +346,8 @@ impl ConnectedClient {
 impl Drop for CloseTx {
     fn drop(&mut self) {
         log::debug!("signal to close client: {}", self.nym_address);
-        if let Some(close_tx) = self.inner.take() {
-            close_tx.send(()).ok();
+        if let Some(close_tx) = self.inner.get_mut().ok().and_then(Option::take) {
+            close_tx.send(None).ok();
         }
     }
 }
@@ -279,6 +374,11 @@ pub(crate) struct MixnetListener {
     // The map of connected clients that the mixnet listener keeps track of. It monitors
     // activity and disconnects clients that have been inactive for too long.
     pub(crate) connected_clients: ConnectedClients,
+
+    // The prefix IPv4 destinations are embedded in by IPv6-only clients, if NAT64 is enabled.
+    // It's only announced in the v7 connect responses, as the v6 ones have no room for it, so the
+    // v6 clients have to be configured with it.
+    pub(crate) nat64_prefix: Option<Nat64Prefix>,
 }
 
 #[cfg(target_os = "linux")]
@@ -324,6 +424,29 @@ impl MixnetListener {
                 )))
             }
             (false, false) => {
+                if let Err(err) = self
+                    .connected_clients
+                    .try_lease_ips(requested_ips, reply_to)
+                {
+                    let reason = match err {
+                        LeaseError::OutsidePool => {
+                            log::info!("Requested IP is outside the pool");
+                            // v6 has no dedicated variant, so the v7 one is sent as its message
+                            StaticConnectFailureReason::Other(
+                                v7::response::StaticConnectFailureReason::RequestedIpOutsidePool
+                                    .to_string(),
+                            )
+                        }
+                        LeaseError::Unavailable => {
+                            log::info!("Requested IP is reserved or leased to another client");
+                            StaticConnectFailureReason::RequestedIpAlreadyInUse
+                        }
+                    };
+                    return Ok(Some(IpPacketResponse::new_static_connect_failure(
+                        request_id, reply_to, reason,
+                    )));
+                }
+
                 log::info!("Connecting a new client");
 
                 // Spawn the ConnectedClientHandler for the new client
@@ -336,14 +459,21 @@ impl MixnetListener {
                     );
 
                 // Register the new client in the set of connected clients
-                self.connected_clients.connect(
+                if let Err(err) = self.connected_clients.connect(
                     requested_ips,
                     reply_to,
+                    request_id,
                     reply_to_hops,
                     forward_from_tun_tx,
                     close_tx,
                     handle,
-                );
+                ) {
+                    return Ok(Some(IpPacketResponse::new_static_connect_failure(
+                        request_id,
+                        reply_to,
+                        StaticConnectFailureReason::Other(err.to_string()),
+                    )));
+                }
                 Ok(Some(IpPacketResponse::new_static_connect_success(
                     request_id, reply_to,
                 )))
@@ -404,12 +534,17 @@ impl MixnetListener {
             )));
         }
 
-        let Some(new_ips) = self.connected_clients.find_new_ip() else {
-            log::info!("No available IP address");
+        let Some(new_ips) = self.connected_clients.allocate_ips(reply_to) else {
+            let capacity = self.connected_clients.ip_pool_capacity();
+            log::info!("No available IP address: all {capacity} addresses in the pool are leased");
+            // v6 has no dedicated variant, so the v7 one is sent as its message
             return Ok(Some(IpPacketResponse::new_dynamic_connect_failure(
                 request_id,
                 reply_to,
-                DynamicConnectFailureReason::NoAvailableIp,
+                DynamicConnectFailureReason::Other(
+                    v7::response::DynamicConnectFailureReason::IpPoolExhausted { capacity }
+                        .to_string(),
+                ),
             )));
         };
 
@@ -423,24 +558,88 @@ impl MixnetListener {
             );
 
         // Register the new client in the set of connected clients
-        self.connected_clients.connect(
+        if let Err(err) = self.connected_clients.connect(
             new_ips,
             reply_to,
+            request_id,
             reply_to_hops,
             forward_from_tun_tx,
             close_tx,
             handle,
-        );
+        ) {
+            return Ok(Some(IpPacketResponse::new_dynamic_connect_failure(
+                request_id,
+                reply_to,
+                DynamicConnectFailureReason::Other(err.to_string()),
+            )));
+        }
         Ok(Some(IpPacketResponse::new_dynamic_connect_success(
             request_id, reply_to, new_ips,
         )))
     }
 
     fn on_disconnect_request(
-        &self,
-        _disconnect_request: nym_ip_packet_requests::request::DisconnectRequest,
+        &mut self,
+        disconnect_request: nym_ip_packet_requests::request::DisconnectRequest,
     ) -> PacketHandleResult {
-        log::info!("Received disconnect request: not implemented, dropping");
+        log::info!(
+            "Received disconnect request from {sender_address}",
+            sender_address = disconnect_request.reply_to
+        );
+
+        let request_id = disconnect_request.request_id;
+        let reply_to = disconnect_request.reply_to;
+
+        let Some(connected) = self
+            .connected_clients
+            .lookup_client_from_nym_address(&reply_to)
+        else {
+            log::info!("Nym address is not connected");
+            return Ok(Some(IpPacketResponse::new_disconnect_failure(
+                request_id,
+                reply_to,
+                DisconnectFailureReason::RequestedNymAddressNotConnected,
+            )));
+        };
+
+        if connected.connect_request_id != request_id {
+            log::info!("Disconnect request doesn't match the session of the connected client");
+            return Ok(Some(IpPacketResponse::new_disconnect_failure(
+                request_id,
+                reply_to,
+                DisconnectFailureReason::Other(
+                    "the request doesn't match the connected session".to_string(),
+                ),
+            )));
+        }
+
+        let Some(client) = self.connected_clients.disconnect_client(&reply_to) else {
+            return Ok(Some(IpPacketResponse::new_disconnect_failure(
+                request_id,
+                reply_to,
+                DisconnectFailureReason::RequestedNymAddressNotConnected,
+            )));
+        };
+
+        // Make sure everything queued for the client has been sent out before we acknowledge
+        // the disconnect, so that the client can release its state as soon as it gets the response.
+        // The flush is awaited on its own task so that it doesn't hold up the other clients.
+        let ack = client.close_tx.close_with_flush();
+        let mixnet_sender = self.mixnet_client.split_sender();
+        let mix_hops = client.mix_hops;
+        tokio::spawn(async move {
+            let outcome = match ack {
+                Some(ack) => tokio::time::timeout(CLIENT_DISCONNECT_FLUSH_TIMEOUT, ack)
+                    .await
+                    .ok()
+                    .and_then(|outcome| outcome.ok()),
+                None => None,
+            };
+            let response = disconnect_response(request_id, reply_to, outcome);
+            if let Err(err) = send_response(&mixnet_sender, response, mix_hops).await {
+                log::error!("Failed to send disconnect response: {err}");
+            }
+        });
         Ok(None)
     }
 
@@ -470,19 +669,55 @@ impl MixnetListener {
 
             // For packets without a port, use 0.
             let dst = dst.unwrap_or_else(|| SocketAddr::new(dst_addr, 0));
+            let nym_address = connected_client.nym_address;
+            let ips = IpPair::new(connected_client.ipv4, connected_client.ipv6);
+
+            // IPv6 packets sent to the NAT64 prefix get translated and routed out over IPv4
+            let nat64 = match (self.nat64_prefix, dst) {
+                (Some(prefix), SocketAddr::V6(dst_v6)) if prefix.contains(dst_v6.ip()) => {
+                    let (packet, remote) = translate_to_ipv4(ip_packet, &prefix, ips.ipv4)?;
+                    let remotes = &mut connected_client.nat64_remotes;
+                    let is_new_remote = !remotes.contains(&remote);
+                    if is_new_remote && remotes.len() >= MAX_NAT64_REMOTES_PER_CLIENT {
+                        log::warn!(
+                            "dropping nat64 packet: client {ips} has reached too many remotes"
+                        );
+                        return Ok(None);
+                    }
+                    remotes.insert(remote);
+                    Some((packet, remote, is_new_remote))
+                }
+                _ => None,
+            };
+
+            // Apply the filter to the actual destination of the translated packets
+            let dst = match &nat64 {
+                Some((_, remote, _)) => SocketAddr::new((*remote).into(), dst.port()),
+                None => dst,
+            };
 
             // Filter check
             if self.request_filter.check_address(&dst).await {
+                let ip_packet = match nat64 {
+                    Some((packet, remote, is_new_remote)) => {
+                        if is_new_remote {
+                            self.connected_clients.register_nat64_remote(ips, remote);
+                        }
+                        Bytes::from(packet)
+                    }
+                    None => ip_packet.clone(),
+                };
+
                 // Forward the packet to the TUN device where it will be routed out to the internet
                 self.tun_writer
-                    .write_all(ip_packet)
+                    .write_all(&ip_packet)
                     .await
                     .map_err(|_| IpPacketRouterError::FailedToWritePacketToTun)?;
                 Ok(None)
             } else {
                 log::info!("Denied filter check: {dst}");
                 Ok(Some(IpPacketResponse::new_data_info_response(
-                    connected_client.nym_address,
+                    nym_address,
                     InfoResponseReply::ExitPolicyFilterCheckFailed {
                         dst: dst.to_string(),
                     },
@@ -594,36 +829,22 @@ impl MixnetListener {
             .disconnect_stopped_client_handlers(stopped_clients);
         self.connected_clients
             .disconnect_inactive_clients(inactive_clients);
+        self.connected_clients.prune_expired_leases();
     }
 
     // When an incoming mixnet message triggers a response that we send back, such as during
     // connect handshake.
     async fn handle_response(&self, response: IpPacketResponse) -> Result<()> {
-        let Some(recipient) = response.recipient() else {
-            log::error!("No recipient in response packet, this should NOT happen!");
-            return Err(IpPacketRouterError::NoRecipientInResponse);
-        };
-
-        let response_packet = response.to_bytes().map_err(|err| {
-            log::error!("Failed to serialize response packet");
-            IpPacketRouterError::FailedToSerializeResponsePacket { source: err }
-        })?;
-
         // We could avoid this lookup if we check this when we create the response.
-        let mix_hops = if let Some(c) = self
-            .connected_clients
-            .lookup_client_from_nym_address(recipient)
-        {
-            c.mix_hops
-        } else {
-            None
-        };
+        let mix_hops = response
+            .recipient()
+            .and_then(|recipient| {
+                self.connected_clients
+                    .lookup_client_from_nym_address(recipient)
+            })
+            .and_then(|c| c.mix_hops);
 
-        let input_message = create_input_message(*recipient, response_packet, mix_hops);
-        self.mixnet_client
-            .send(input_message)
-            .await
-            .map_err(|err| IpPacketRouterError::FailedToSendPacketToMixnet { source: err })
+        send_response(&self.mixnet_client, response, mix_hops).await
     }
 
     // A single incoming request can trigger multiple responses, such as when data requests contain
@@ -680,9 +901,61 @@ impl MixnetListener {
     }
 }
 
+// The v6 acknowledgement has no room for the number of flushed packets, and adding it would break
+// the existing clients decoding the response, so it's only logged here. The v7 `DisconnectSuccess`
+// carries it.
+#[cfg(target_os = "linux")]
+fn disconnect_response(
+    request_id: u64,
+    reply_to: Recipient,
+    outcome: Option<FlushOutcome>,
+) -> IpPacketResponse {
+    match outcome {
+        Some(FlushOutcome::Flushed { queued }) => {
+            log::info!("Flushed {queued} queued packets to the disconnected client");
+            IpPacketResponse::new_disconnect_success(request_id, reply_to)
+        }
+        Some(FlushOutcome::Failed { queued }) => IpPacketResponse::new_disconnect_failure(
+            request_id,
+            reply_to,
+            DisconnectFailureReason::Other(format!(
+                "failed to flush the {queued} packets queued for the client"
+            )),
+        ),
+        None => {
+            log::warn!("Client handler did not report flushing its queued packets in time");
+            IpPacketResponse::new_disconnect_success(request_id, reply_to)
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+async fn send_response<S: MixnetMessageSender>(
+    mixnet_sender: &S,
+    response: IpPacketResponse,
+    mix_hops: Option<u8>,
+) -> Result<()> {
+    let Some(recipient) = response.recipient() else {
+        log::error!("No recipient in response packet, this should NOT happen!");
+        return Err(IpPacketRouterError::NoRecipientInResponse);
+    };
+
+    let response_packet = response.to_bytes().map_err(|err| {
+        log::error!("Failed to serialize response packet");
+        IpPacketRouterError::FailedToSerializeResponsePacket { source: err }
+    })?;
+
+    let input_message = create_input_message(*recipient, response_packet, mix_hops);
+    mixnet_sender
+        .send(input_message)
+        .await
+        .map_err(|err| IpPacketRouterError::FailedToSendPacketToMixnet { source: err })
+}
+
 pub(crate) enum ConnectedClientEvent {
     Disconnect(DisconnectEvent),
     Connect(Box<ConnectEvent>),
+    Nat64Remote(Nat64RemoteEvent),
 }
 
 pub(crate) struct DisconnectEvent(pub(crate) IpPair);
@@ -691,3 +964,9 @@ pub(crate) struct ConnectEvent {
     pub(crate) ips: IpPair,
     pub(crate) forward_from_tun_tx: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
 }
+
+// The client has started reaching the remote IPv4 destination via NAT64
+pub(crate) struct Nat64RemoteEvent {
+    pub(crate) ips: IpPair,
+    pub(crate) remote: Ipv4Addr,
+}
@@ -1,6 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
 
+use nym_ip_packet_requests::nat64::Nat64Prefix;
 use nym_ip_packet_requests::IpPair;
 use nym_task::TaskClient;
 #[cfg(target_os = "linux")]
@@ -9,7 +10,10 @@ use tokio::io::AsyncReadExt;
 use crate::{
     error::Result,
     mixnet_listener::{self},
-    util::parse_ip::parse_dst_addr,
+    util::{
+        nat64::translate_to_ipv6,
+        parse_ip::{parse_dst_addr, parse_ipv4_src_addr},
+    },
 };
 
 // The TUN listener keeps a local map of the connected clients that has its state updated by the
@@ -18,6 +22,10 @@ use crate::{
 pub(crate) struct ConnectedClientMirror {
     pub(crate) forward_from_tun_tx: tokio::sync::mpsc::UnboundedSender<Vec<u8>>,
     pub(crate) ips: IpPair,
+
+    // The IPv4 destinations the client has reached via NAT64, whose packets have to be
+    // translated back. Only tracked in the IPv4 mapping, as that's where the replies arrive.
+    pub(crate) nat64_remotes: HashSet<Ipv4Addr>,
 }
 
 pub(crate) struct ConnectedClientsListener {
@@ -60,6 +68,7 @@ impl ConnectedClientsListener {
                     ConnectedClientMirror {
                         forward_from_tun_tx: forward_from_tun_tx.clone(),
                         ips,
+                        nat64_remotes: HashSet::new(),
                     },
                 );
                 self.clients_ipv6.insert(
@@ -67,6 +76,7 @@ impl ConnectedClientsListener {
                     ConnectedClientMirror {
                         forward_from_tun_tx,
                         ips,
+                        nat64_remotes: HashSet::new(),
                     },
                 );
             }
@@ -77,6 +87,14 @@ impl ConnectedClientsListener {
                 self.clients_ipv4.remove(&ips.ipv4);
                 self.clients_ipv6.remove(&ips.ipv6);
             }
+            mixnet_listener::ConnectedClientEvent::Nat64Remote(
+                mixnet_listener::Nat64RemoteEvent { ips, remote },
+            ) => {
+                log::trace!("Nat64 remote for client {ips}: {remote}");
+                if let Some(client) = self.clients_ipv4.get_mut(&ips.ipv4) {
+                    client.nat64_remotes.insert(remote);
+                }
+            }
         }
     }
 }
@@ -87,6 +105,7 @@ pub(crate) struct TunListener {
     pub(crate) tun_reader: tokio::io::ReadHalf<tokio_tun::Tun>,
     pub(crate) task_client: TaskClient,
     pub(crate) connected_clients: ConnectedClientsListener,
+    pub(crate) nat64_prefix: Option<Nat64Prefix>,
 }
 
 #[cfg(target_os = "linux")]
@@ -100,9 +119,23 @@ impl TunListener {
         if let Some(ConnectedClientMirror {
             forward_from_tun_tx,
             ips,
+            nat64_remotes,
         }) = self.connected_clients.get(&dst_addr)
         {
-            let packet = buf[..len].to_vec();
+            let packet = &buf[..len];
+            // Replies from the destinations the client reached via NAT64 are translated back
+            let packet = match (self.nat64_prefix, parse_ipv4_src_addr(packet)) {
+                (Some(prefix), Some(src)) if nat64_remotes.contains(&src) => {
+                    match translate_to_ipv6(packet, &prefix, ips.ipv6) {
+                        Ok(translated) => translated,
+                        Err(err) => {
+                            log::debug!("dropping packet from {src} to {dst_addr}: {err}");
+                            return Ok(());
+                        }
+                    }
+                }
+                _ => packet.to_vec(),
+            };
             if forward_from_tun_tx.send(packet).is_err() {
                 log::warn!("Failed to forward packet to connected client {dst_addr}: disconnecting it from tun listener");
                 self.connected_clients
@@ -1,3 +1,3 @@
 pub(crate) mod create_message;
-pub(crate) mod generate_new_ip;
+pub(crate) mod nat64;
 pub(crate) mod parse_ip;
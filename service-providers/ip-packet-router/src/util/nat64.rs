@@ -0,0 +1,396 @@
+// Stateless translation (RFC 7915) between the IPv6 packets sent by the clients to destinations
+// embedded in the NAT64 prefix and the IPv4 packets actually routed out to these destinations.
+// Only TCP, UDP and ICMP echo messages are supported, which covers the vast majority of the
+// traffic of the clients. Anything else, including fragmented IPv4 packets, is dropped.
+
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use nym_ip_packet_requests::nat64::Nat64Prefix;
+
+use crate::error::IpPacketRouterError;
+
+const IPV4_MIN_HEADER_LEN: usize = 20;
+const IPV6_HEADER_LEN: usize = 40;
+
+const PROTOCOL_ICMPV4: u8 = 1;
+const PROTOCOL_TCP: u8 = 6;
+const PROTOCOL_UDP: u8 = 17;
+const PROTOCOL_ICMPV6: u8 = 58;
+
+const ICMPV4_ECHO_REPLY: u8 = 0;
+const ICMPV4_ECHO_REQUEST: u8 = 8;
+const ICMPV6_ECHO_REQUEST: u8 = 128;
+const ICMPV6_ECHO_REPLY: u8 = 129;
+
+// Minimum lengths of the transport headers, alongside the offsets of their checksums
+const TCP_MIN_HEADER_LEN: usize = 20;
+const TCP_CHECKSUM_OFFSET: usize = 16;
+const UDP_HEADER_LEN: usize = 8;
+const UDP_CHECKSUM_OFFSET: usize = 6;
+const ICMP_HEADER_LEN: usize = 8;
+const ICMP_CHECKSUM_OFFSET: usize = 2;
+
+// The don't fragment flag, set on all the translated IPv4 packets as IPv6 doesn't allow
+// fragmentation by the routers either
+const IPV4_DONT_FRAGMENT: u16 = 0x4000;
+const IPV4_MORE_FRAGMENTS: u16 = 0x2000;
+const IPV4_FRAGMENT_OFFSET_MASK: u16 = 0x1fff;
+
+fn translation_failure(reason: &'static str) -> IpPacketRouterError {
+    IpPacketRouterError::Nat64TranslationFailed { reason }
+}
+
+// Translate the IPv6 packet sent by the client to a destination embedded in the NAT64 prefix
+// into an IPv4 packet sent from the IPv4 address assigned to the client.
+// Returns the translated packet alongside the IPv4 destination.
+pub(crate) fn translate_to_ipv4(
+    packet: &[u8],
+    prefix: &Nat64Prefix,
+    client_ipv4: Ipv4Addr,
+) -> Result<(Vec<u8>, Ipv4Addr), IpPacketRouterError> {
+    if packet.len() < IPV6_HEADER_LEN || packet[0] >> 4 != 6 {
+        return Err(translation_failure("not an ipv6 packet"));
+    }
+
+    let payload_len = u16::from_be_bytes([packet[4], packet[5]]) as usize;
+    let payload = packet
+        .get(IPV6_HEADER_LEN..IPV6_HEADER_LEN + payload_len)
+        .ok_or(translation_failure("truncated ipv6 payload"))?;
+    let total_len = u16::try_from(IPV4_MIN_HEADER_LEN + payload_len)
+        .map_err(|_| translation_failure("payload too large for an ipv4 packet"))?;
+
+    let traffic_class = (packet[0] << 4) | (packet[1] >> 4);
+    let next_header = packet[6];
+    let hop_limit = packet[7];
+
+    let mut destination = [0u8; 16];
+    destination.copy_from_slice(&packet[24..40]);
+    let destination = prefix
+        .extract(&Ipv6Addr::from(destination))
+        .ok_or(translation_failure("destination outside the nat64 prefix"))?;
+
+    let protocol = match next_header {
+        PROTOCOL_TCP | PROTOCOL_UDP => next_header,
+        PROTOCOL_ICMPV6 => PROTOCOL_ICMPV4,
+        _ => return Err(translation_failure("unsupported ipv6 next header")),
+    };
+
+    let mut translated = Vec::with_capacity(total_len as usize);
+    translated.extend_from_slice(&[0x45, traffic_class]);
+    translated.extend_from_slice(&total_len.to_be_bytes());
+    // identification
+    translated.extend_from_slice(&[0, 0]);
+    translated.extend_from_slice(&IPV4_DONT_FRAGMENT.to_be_bytes());
+    translated.extend_from_slice(&[hop_limit, protocol]);
+    // header checksum, filled in below
+    translated.extend_from_slice(&[0, 0]);
+    translated.extend_from_slice(&client_ipv4.octets());
+    translated.extend_from_slice(&destination.octets());
+
+    let header_checksum = finalize_checksum(ones_complement_sum(0, &translated));
+    translated[10..12].copy_from_slice(&header_checksum.to_be_bytes());
+
+    translated.extend_from_slice(payload);
+    let transport = &mut translated[IPV4_MIN_HEADER_LEN..];
+    match protocol {
+        PROTOCOL_ICMPV4 => {
+            translate_icmp_type(
+                transport,
+                [
+                    (ICMPV6_ECHO_REQUEST, ICMPV4_ECHO_REQUEST),
+                    (ICMPV6_ECHO_REPLY, ICMPV4_ECHO_REPLY),
+                ],
+            )?;
+            // unlike ICMPv6, the ICMPv4 checksum doesn't cover the pseudo-header
+            update_checksum(transport, ICMP_HEADER_LEN, ICMP_CHECKSUM_OFFSET, 0, false)?;
+        }
+        _ => {
+            let pseudo_header =
+                ipv4_pseudo_header_sum(client_ipv4, destination, protocol, payload_len as u16);
+            update_transport_checksum(transport, protocol, pseudo_header)?;
+        }
+    }
+
+    Ok((translated, destination))
+}
+
+// Translate the IPv4 packet received from a destination the client has reached via NAT64 into
+// an IPv6 packet sent from the destination embedded in the NAT64 prefix to the client.
+pub(crate) fn translate_to_ipv6(
+    packet: &[u8],
+    prefix: &Nat64Prefix,
+    client_ipv6: Ipv6Addr,
+) -> Result<Vec<u8>, IpPacketRouterError> {
+    if packet.len() < IPV4_MIN_HEADER_LEN || packet[0] >> 4 != 4 {
+        return Err(translation_failure("not an ipv4 packet"));
+    }
+
+    let header_len = (packet[0] & 0x0f) as usize * 4;
+    let total_len = u16::from_be_bytes([packet[2], packet[3]]) as usize;
+    if header_len < IPV4_MIN_HEADER_LEN || total_len < header_len {
+        return Err(translation_failure("malformed ipv4 header"));
+    }
+    let payload = packet
+        .get(header_len..total_len)
+        .ok_or(translation_failure("truncated ipv4 payload"))?;
+
+    let fragmentation = u16::from_be_bytes([packet[6], packet[7]]);
+    if fragmentation & (IPV4_MORE_FRAGMENTS | IPV4_FRAGMENT_OFFSET_MASK) != 0 {
+        return Err(translation_failure("fragmented ipv4 packet"));
+    }
+
+    let tos = packet[1];
+    let ttl = packet[8];
+    let protocol = packet[9];
+    let source = prefix.embed(Ipv4Addr::new(
+        packet[12], packet[13], packet[14], packet[15],
+    ));
+
+    let next_header = match protocol {
+        PROTOCOL_TCP | PROTOCOL_UDP => protocol,
+        PROTOCOL_ICMPV4 => PROTOCOL_ICMPV6,
+        _ => return Err(translation_failure("unsupported ipv4 protocol")),
+    };
+    let payload_len = payload.len() as u16;
+
+    let mut translated = Vec::with_capacity(IPV6_HEADER_LEN + payload.len());
+    // version, traffic class and (empty) flow label
+    translated.extend_from_slice(&[0x60 | (tos >> 4), tos << 4, 0, 0]);
+    translated.extend_from_slice(&payload_len.to_be_bytes());
+    translated.extend_from_slice(&[next_header, ttl]);
+    translated.extend_from_slice(&source.octets());
+    translated.extend_from_slice(&client_ipv6.octets());
+    translated.extend_from_slice(payload);
+
+    let pseudo_header = ipv6_pseudo_header_sum(source, client_ipv6, next_header, payload_len);
+    let transport = &mut translated[IPV6_HEADER_LEN..];
+    match next_header {
+        PROTOCOL_ICMPV6 => {
+            translate_icmp_type(
+                transport,
+                [
+                    (ICMPV4_ECHO_REQUEST, ICMPV6_ECHO_REQUEST),
+                    (ICMPV4_ECHO_REPLY, ICMPV6_ECHO_REPLY),
+                ],
+            )?;
+            update_checksum(
+                transport,
+                ICMP_HEADER_LEN,
+                ICMP_CHECKSUM_OFFSET,
+                pseudo_header,
+                false,
+            )?;
+        }
+        _ => update_transport_checksum(transport, next_header, pseudo_header)?,
+    }
+
+    Ok(translated)
+}
+
+// Map the echo request and reply types between ICMPv4 and ICMPv6, given as (from, to) pairs
+fn translate_icmp_type(icmp: &mut [u8], mapping: [(u8, u8); 2]) -> Result<(), IpPacketRouterError> {
+    let icmp_type = icmp
+        .first_mut()
+        .ok_or(translation_failure("truncated icmp header"))?;
+    *icmp_type = mapping
+        .iter()
+        .find(|(from, _)| *from == *icmp_type)
+        .map(|(_, to)| *to)
+        .ok_or(translation_failure("unsupported icmp message type"))?;
+    Ok(())
+}
+
+fn update_transport_checksum(
+    transport: &mut [u8],
+    protocol: u8,
+    pseudo_header: u32,
+) -> Result<(), IpPacketRouterError> {
+    match protocol {
+        PROTOCOL_TCP => update_checksum(
+            transport,
+            TCP_MIN_HEADER_LEN,
+            TCP_CHECKSUM_OFFSET,
+            pseudo_header,
+            false,
+        ),
+        _ => update_checksum(
+            transport,
+            UDP_HEADER_LEN,
+            UDP_CHECKSUM_OFFSET,
+            pseudo_header,
+            true,
+        ),
+    }
+}
+
+// Recompute the checksum of the transport segment, covering the provided pseudo-header sum
+fn update_checksum(
+    transport: &mut [u8],
+    min_header_len: usize,
+    checksum_offset: usize,
+    pseudo_header: u32,
+    is_udp: bool,
+) -> Result<(), IpPacketRouterError> {
+    if transport.len() < min_header_len {
+        return Err(translation_failure("truncated transport header"));
+    }
+
+    transport[checksum_offset..checksum_offset + 2].copy_from_slice(&[0, 0]);
+    let mut checksum = finalize_checksum(ones_complement_sum(pseudo_header, transport));
+    // zero denotes a missing checksum in UDP, so it's transmitted as all ones instead
+    if is_udp && checksum == 0 {
+        checksum = 0xffff;
+    }
+    transport[checksum_offset..checksum_offset + 2].copy_from_slice(&checksum.to_be_bytes());
+    Ok(())
+}
+
+fn ipv4_pseudo_header_sum(source: Ipv4Addr, destination: Ipv4Addr, protocol: u8, len: u16) -> u32 {
+    let sum = ones_complement_sum(0, &source.octets());
+    let sum = ones_complement_sum(sum, &destination.octets());
+    sum + protocol as u32 + len as u32
+}
+
+fn ipv6_pseudo_header_sum(
+    source: Ipv6Addr,
+    destination: Ipv6Addr,
+    next_header: u8,
+    len: u16,
+) -> u32 {
+    let sum = ones_complement_sum(0, &source.octets());
+    let sum = ones_complement_sum(sum, &destination.octets());
+    sum + next_header as u32 + len as u32
+}
+
+fn ones_complement_sum(initial: u32, data: &[u8]) -> u32 {
+    data.chunks(2).fold(initial, |sum, chunk| {
+        let word = match chunk {
+            [high, low] => u16::from_be_bytes([*high, *low]),
+            [high] => u16::from_be_bytes([*high, 0]),
+            _ => 0,
+        };
+        sum + word as u32
+    })
+}
+
+fn finalize_checksum(mut sum: u32) -> u16 {
+    while sum >> 16 != 0 {
+        sum = (sum & 0xffff) + (sum >> 16);
+    }
+    !(sum as u16)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CLIENT_IPV4: Ipv4Addr = Ipv4Addr::new(10, 0, 0, 2);
+    const CLIENT_IPV6: Ipv6Addr = Ipv6Addr::new(0x2001, 0xdb8, 0xa160, 0, 0, 0, 0, 2);
+    const REMOTE: Ipv4Addr = Ipv4Addr::new(192, 0, 2, 33);
+
+    fn build(
+        builder: etherparse::PacketBuilderStep<etherparse::UdpHeader>,
+        payload: &[u8],
+    ) -> Vec<u8> {
+        let mut packet = Vec::with_capacity(builder.size(payload.len()));
+        builder.write(&mut packet, payload).unwrap();
+        packet
+    }
+
+    #[test]
+    fn udp_packets_are_translated_both_ways() {
+        let prefix = Nat64Prefix::default();
+        let payload = [1, 2, 3, 4, 5, 6, 7];
+
+        let outbound = build(
+            etherparse::PacketBuilder::ipv6(
+                CLIENT_IPV6.octets(),
+                prefix.embed(REMOTE).octets(),
+                64,
+            )
+            .udp(4321, 53),
+            &payload,
+        );
+        let expected_outbound = build(
+            etherparse::PacketBuilder::ipv4(CLIENT_IPV4.octets(), REMOTE.octets(), 64)
+                .udp(4321, 53),
+            &payload,
+        );
+
+        let (translated, destination) = translate_to_ipv4(&outbound, &prefix, CLIENT_IPV4).unwrap();
+        assert_eq!(destination, REMOTE);
+        assert_eq!(translated.len(), expected_outbound.len());
+        // the ipv4 header checksums to zero once it includes its own checksum
+        assert_eq!(
+            finalize_checksum(ones_complement_sum(0, &translated[..IPV4_MIN_HEADER_LEN])),
+            0
+        );
+        assert_eq!(
+            translated[IPV4_MIN_HEADER_LEN..],
+            expected_outbound[IPV4_MIN_HEADER_LEN..]
+        );
+
+        let inbound = build(
+            etherparse::PacketBuilder::ipv4(REMOTE.octets(), CLIENT_IPV4.octets(), 50)
+                .udp(53, 4321),
+            &payload,
+        );
+        let expected_inbound = build(
+            etherparse::PacketBuilder::ipv6(
+                prefix.embed(REMOTE).octets(),
+                CLIENT_IPV6.octets(),
+                50,
+            )
+            .udp(53, 4321),
+            &payload,
+        );
+        assert_eq!(
+            translate_to_ipv6(&inbound, &prefix, CLIENT_IPV6).unwrap(),
+            expected_inbound
+        );
+    }
+
+    #[test]
+    fn destinations_outside_the_prefix_are_rejected() {
+        let outbound = build(
+            etherparse::PacketBuilder::ipv6(
+                CLIENT_IPV6.octets(),
+                Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1).octets(),
+                64,
+            )
+            .udp(4321, 53),
+            &[1, 2, 3],
+        );
+        assert!(translate_to_ipv4(&outbound, &Nat64Prefix::default(), CLIENT_IPV4).is_err());
+    }
+
+    #[test]
+    fn echo_requests_are_translated() {
+        let prefix = Nat64Prefix::default();
+        let mut echo = vec![ICMPV6_ECHO_REQUEST, 0, 0, 0, 0, 1, 0, 1, 0xaa, 0xbb];
+        let checksum = finalize_checksum(ones_complement_sum(
+            ipv6_pseudo_header_sum(
+                CLIENT_IPV6,
+                prefix.embed(REMOTE),
+                PROTOCOL_ICMPV6,
+                echo.len() as u16,
+            ),
+            &echo,
+        ));
+        echo[2..4].copy_from_slice(&checksum.to_be_bytes());
+
+        let mut outbound = vec![0x60, 0, 0, 0];
+        outbound.extend_from_slice(&(echo.len() as u16).to_be_bytes());
+        outbound.extend_from_slice(&[PROTOCOL_ICMPV6, 64]);
+        outbound.extend_from_slice(&CLIENT_IPV6.octets());
+        outbound.extend_from_slice(&prefix.embed(REMOTE).octets());
+        outbound.extend_from_slice(&echo);
+
+        let (translated, _) = translate_to_ipv4(&outbound, &prefix, CLIENT_IPV4).unwrap();
+        let icmp = &translated[IPV4_MIN_HEADER_LEN..];
+        assert_eq!(translated[9], PROTOCOL_ICMPV4);
+        assert_eq!(icmp[0], ICMPV4_ECHO_REQUEST);
+        assert_eq!(finalize_checksum(ones_complement_sum(0, icmp)), 0);
+        assert_eq!(icmp[4..], echo[4..]);
+    }
+}
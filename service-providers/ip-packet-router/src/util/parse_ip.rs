@@ -58,6 +58,7 @@ const IPV4_DEST_ADDR_START: usize = 16;
 const IPV4_DEST_ADDR_LEN: usize = 4;
 const IPV6_DEST_ADDR_START: usize = 24;
 const IPV6_DEST_ADDR_LEN: usize = 16;
+const IPV4_SRC_ADDR_START: usize = 12;
 
 // Only parse the destination address, for when we don't need the other stuff
 pub(crate) fn parse_dst_addr(packet: &[u8]) -> Option<IpAddr> {
@@ -85,6 +86,17 @@ pub(crate) fn parse_dst_addr(packet: &[u8]) -> Option<IpAddr> {
     }
 }
 
+// Only parse the source address of an IPv4 packet
+pub(crate) fn parse_ipv4_src_addr(packet: &[u8]) -> Option<Ipv4Addr> {
+    if packet.first().map(|v| v >> 4)? != 4 {
+        return None;
+    }
+    let addr_end = IPV4_SRC_ADDR_START + IPV4_DEST_ADDR_LEN;
+    let addr_array: [u8; IPV4_DEST_ADDR_LEN] =
+        packet.get(IPV4_SRC_ADDR_START..addr_end)?.try_into().ok()?;
+    Some(Ipv4Addr::from(addr_array))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
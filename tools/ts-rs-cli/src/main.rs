@@ -6,8 +6,8 @@ use nym_api_requests::models::{
 use nym_mixnet_contract_common::rewarding::RewardEstimate;
 use nym_mixnet_contract_common::{
     GatewayConfigUpdate, Interval as ContractInterval, IntervalRewardParams,
-    IntervalRewardingParamsUpdate, MixNode, MixNodeConfigUpdate, RewardedSetNodeStatus,
-    RewardingParams, UnbondedMixnode,
+    IntervalRewardingParamsUpdate, MixNode, MixNodeConfigUpdate, MixNodeDescription, MixNodeStatus,
+    NextSphinxKey, RewardedSetNodeStatus, RewardingParams, UnbondedMixnode,
 };
 use nym_types::account::{Account, AccountEntry, AccountWithMnemonic, Balance};
 use nym_types::currency::{CurrencyDenom, DecCoin};
@@ -66,6 +66,9 @@ fn main() {
     do_export!(IntervalRewardingParamsUpdate);
     do_export!(MixNode);
     do_export!(MixNodeConfigUpdate);
+    do_export!(MixNodeDescription);
+    do_export!(MixNodeStatus);
+    do_export!(NextSphinxKey);
     do_export!(RewardingParams);
     do_export!(RewardedSetNodeStatus);
     do_export!(UnbondedMixnode);
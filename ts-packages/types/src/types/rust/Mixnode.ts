@@ -1,4 +1,5 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { NextSphinxKey } from './NextSphinxKey';
 
 export interface MixNode {
   host: string;
@@ -8,4 +9,5 @@ export interface MixNode {
   sphinx_key: string;
   identity_key: string;
   version: string;
+  next_sphinx_key?: NextSphinxKey;
 }
@@ -27,8 +27,11 @@ export * from './IntervalRewardParams';
 export * from './Mixnode';
 export * from './MixNodeBond';
 export * from './MixNodeConfigUpdate';
+export * from './MixNodeContractStatus';
 export * from './MixnodeCoreStatusResponse';
 export * from './MixNodeCostParams';
+export * from './MixNodeDescription';
+export * from './NextSphinxKey';
 export * from './MixNodeDetails';
 export * from './MixNodeRewarding';
 export * from './MixnodeStatus';